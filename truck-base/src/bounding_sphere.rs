@@ -0,0 +1,267 @@
+use crate::{cgmath64::*, eigen::symmetric_eigen, tolerance::*};
+use serde::*;
+
+/// the minimal sphere enclosing points
+/// # Examples
+/// ```
+/// use truck_base::{assert_near, bounding_sphere::BoundingSphere, cgmath64::*, tolerance::*};
+/// let sphere: BoundingSphere = vec![
+///     Point3::new(-1.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 0.5, 0.0),
+///     Point3::new(0.0, 0.0, -0.5),
+/// ]
+/// .into_iter()
+/// .collect();
+/// assert_near!(sphere.center(), Point3::origin());
+/// assert_near!(sphere.radius(), 1.0);
+/// assert!(sphere.contains(Point3::new(0.0, 0.9, 0.0)));
+/// assert!(!sphere.contains(Point3::new(0.0, 1.1, 0.0)));
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct BoundingSphere {
+    center: Point3,
+    radius: f64,
+}
+
+/// the centroid and the principal axes of the covariance of points
+/// # Examples
+/// ```
+/// use truck_base::{assert_near, bounding_sphere::PrincipalAxes, cgmath64::*, tolerance::*};
+/// // points spread along the diagonal of the xy-plane
+/// let axes: PrincipalAxes = (0..=10)
+///     .flat_map(|i| {
+///         let t = i as f64;
+///         [Point3::new(t, t + 1.0, 2.0), Point3::new(t + 1.0, t, 2.0)]
+///     })
+///     .collect();
+/// assert_near!(axes.centroid(), Point3::new(5.5, 5.5, 2.0));
+/// assert_near!(axes.axes().x, Vector3::new(1.0, 1.0, 0.0).normalize());
+/// assert_near!(axes.axes().z.cross(Vector3::unit_z()), Vector3::zero());
+/// assert!(axes.variances().x > axes.variances().y);
+/// assert_near!(axes.variances().z, 0.0);
+///
+/// // the local frame maps the origin to the centroid
+/// let matrix = axes.to_matrix();
+/// assert_near!(matrix.transform_point(Point3::origin()), axes.centroid());
+/// assert_near!(matrix.determinant(), 1.0);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PrincipalAxes {
+    centroid: Point3,
+    axes: Matrix3,
+    variances: Vector3,
+}
+
+impl BoundingSphere {
+    /// Creates the sphere with `center` and `radius`.
+    #[inline(always)]
+    pub const fn new(center: Point3, radius: f64) -> Self { Self { center, radius } }
+    /// Returns the center.
+    #[inline(always)]
+    pub const fn center(&self) -> Point3 { self.center }
+    /// Returns the radius. The radius of the empty sphere is negative infinity.
+    #[inline(always)]
+    pub const fn radius(&self) -> f64 { self.radius }
+    /// Returns `true` if the sphere encloses no point.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.radius < 0.0 }
+    /// Returns `true` if `point` is in the sphere up to `TOLERANCE`.
+    #[inline(always)]
+    pub fn contains(&self, point: Point3) -> bool {
+        !self.is_empty() && self.center.distance(point) <= self.radius + TOLERANCE
+    }
+    /// Creates the minimal sphere enclosing `points` by Welzl's algorithm.
+    ///
+    /// The points are shuffled by a deterministic permutation, so the result does not depend on
+    /// any random state.
+    pub fn from_points(points: &[Point3]) -> Self {
+        let mut points = points.to_vec();
+        shuffle(&mut points);
+        let mut sphere = match points.first() {
+            Some(first) => Self::new(*first, 0.0),
+            None => return Self::default(),
+        };
+        (1..points.len()).for_each(|i| {
+            if sphere.encloses(points[i]) {
+                return;
+            }
+            sphere = Self::new(points[i], 0.0);
+            (0..i).for_each(|j| {
+                if sphere.encloses(points[j]) {
+                    return;
+                }
+                sphere = diametral_sphere(points[i], points[j]);
+                (0..j).for_each(|k| {
+                    if sphere.encloses(points[k]) {
+                        return;
+                    }
+                    sphere = circumcircle(points[i], points[j], points[k]);
+                    (0..k).for_each(|l| {
+                        if !sphere.encloses(points[l]) {
+                            sphere = circumsphere(points[i], points[j], points[k], points[l]);
+                        }
+                    })
+                })
+            })
+        });
+        sphere
+    }
+    /// `contains` with the tolerance relative to the radius, for the construction.
+    #[inline(always)]
+    fn encloses(&self, point: Point3) -> bool {
+        let eps = 1.0e-10 * (1.0 + self.radius + self.center.to_vec().magnitude());
+        self.center.distance(point) <= self.radius + eps
+    }
+}
+
+impl Default for BoundingSphere {
+    #[inline(always)]
+    fn default() -> Self { Self::new(Point3::origin(), f64::NEG_INFINITY) }
+}
+
+impl FromIterator<Point3> for BoundingSphere {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = Point3>>(iter: I) -> Self {
+        Self::from_points(&iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<'a> FromIterator<&'a Point3> for BoundingSphere {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = &'a Point3>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// a deterministic Fisher-Yates shuffle by xorshift
+fn shuffle(points: &mut [Point3]) {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (1..points.len()).rev().for_each(|i| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        points.swap(i, (state % (i as u64 + 1)) as usize);
+    })
+}
+
+fn diametral_sphere(p: Point3, q: Point3) -> BoundingSphere {
+    BoundingSphere::new(p.midpoint(q), p.distance(q) / 2.0)
+}
+
+/// the smallest sphere whose boundary passes through the three points
+fn circumcircle(p: Point3, q: Point3, r: Point3) -> BoundingSphere {
+    let (a, b) = (q - p, r - p);
+    let normal = a.cross(b);
+    let den = 2.0 * normal.magnitude2();
+    if den < 1.0e-24 * a.magnitude2() * b.magnitude2() || den == 0.0 {
+        // collinear: the farthest pair is the diameter
+        let pairs = [(p, q), (q, r), (r, p)];
+        let (p, q) = pairs
+            .into_iter()
+            .max_by(|(p0, q0), (p1, q1)| p0.distance2(*q0).total_cmp(&p1.distance2(*q1)))
+            .unwrap();
+        return diametral_sphere(p, q);
+    }
+    let center = p + (b * a.magnitude2() - a * b.magnitude2()).cross(normal) / den;
+    BoundingSphere::new(center, center.distance(p))
+}
+
+/// the sphere whose boundary passes through the four points
+fn circumsphere(p: Point3, q: Point3, r: Point3, s: Point3) -> BoundingSphere {
+    let (a, b, c) = (q - p, r - p, s - p);
+    let rows = Matrix3::from_cols(a, b, c).transpose();
+    let rhs = Vector3::new(a.magnitude2(), b.magnitude2(), c.magnitude2()) / 2.0;
+    let scale = a.magnitude() * b.magnitude() * c.magnitude();
+    if rows.determinant().abs() > 1.0e-12 * scale {
+        if let Some(inv) = rows.invert() {
+            let center = p + inv * rhs;
+            return BoundingSphere::new(center, center.distance(p));
+        }
+    }
+    // coplanar: the smallest circle through three of the points enclosing the other
+    let candidates = [
+        circumcircle(p, q, r),
+        circumcircle(p, q, s),
+        circumcircle(p, r, s),
+        circumcircle(q, r, s),
+    ];
+    let enclosing = candidates
+        .iter()
+        .filter(|sphere| [p, q, r, s].into_iter().all(|x| sphere.encloses(x)))
+        .min_by(|x, y| x.radius.total_cmp(&y.radius));
+    let largest = || {
+        candidates
+            .iter()
+            .max_by(|x, y| x.radius.total_cmp(&y.radius))
+    };
+    *enclosing.or_else(largest).unwrap()
+}
+
+impl PrincipalAxes {
+    /// Returns the centroid.
+    #[inline(always)]
+    pub const fn centroid(&self) -> Point3 { self.centroid }
+    /// Returns the principal axes, the columns of the matrix.
+    ///
+    /// The axes are normalized and sorted in descending order of the variances, and form a
+    /// right-handed frame. The direction of each of the first two axes is taken so that the third
+    /// moment of the points along it is non-negative, which makes the frame canonical.
+    #[inline(always)]
+    pub const fn axes(&self) -> Matrix3 { self.axes }
+    /// Returns the variances of the points along the principal axes, in descending order.
+    #[inline(always)]
+    pub const fn variances(&self) -> Vector3 { self.variances }
+    /// Returns the matrix from the local frame to the world, which maps the unit vectors to the
+    /// principal axes and the origin to the centroid.
+    #[inline(always)]
+    pub fn to_matrix(&self) -> Matrix4 {
+        let translation = Matrix4::from_translation(self.centroid.to_vec());
+        translation * Matrix4::from(self.axes)
+    }
+}
+
+impl FromIterator<Point3> for PrincipalAxes {
+    fn from_iter<I: IntoIterator<Item = Point3>>(iter: I) -> Self {
+        let points: Vec<Point3> = iter.into_iter().collect();
+        if points.is_empty() {
+            return Self {
+                centroid: Point3::origin(),
+                axes: Matrix3::identity(),
+                variances: Vector3::zero(),
+            };
+        }
+        let n = points.len() as f64;
+        let centroid = Point3::from_vec(points.iter().map(|p| p.to_vec()).sum::<Vector3>() / n);
+        let covariance = points.iter().fold(Matrix3::zero(), |sum, p| {
+            let v = p - centroid;
+            sum + Matrix3::from_cols(v * v.x, v * v.y, v * v.z)
+        }) / n;
+        // in descending order of the variances
+        let (values, vectors) = symmetric_eigen(covariance.into());
+        let variances = Vector3::new(values[2], values[1], values[0]);
+        let orient = |axis: Vector3| {
+            let moment: f64 = points
+                .iter()
+                .map(|p| (p - centroid).dot(axis).powi(3))
+                .sum();
+            match moment < 0.0 {
+                true => -axis,
+                false => axis,
+            }
+        };
+        let (x, y) = (orient(vectors[2].into()), orient(vectors[1].into()));
+        Self {
+            centroid,
+            axes: Matrix3::from_cols(x, y, x.cross(y)),
+            variances,
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a Point3> for PrincipalAxes {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = &'a Point3>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
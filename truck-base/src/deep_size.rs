@@ -0,0 +1,101 @@
+use crate::cgmath64::*;
+use std::collections::VecDeque;
+use std::mem::size_of;
+
+/// Reports the memory usage including the heap allocations owned by the value.
+///
+/// The shared allocations, e.g. the geometries of topological elements shared by `Arc`,
+/// are counted only once in one value.
+/// # Examples
+/// ```
+/// use truck_base::deep_size::DeepSize;
+/// let vec: Vec<f64> = Vec::with_capacity(10);
+/// assert_eq!(vec.heap_size(), 80);
+/// assert_eq!(vec.deep_size(), std::mem::size_of::<Vec<f64>>() + 80);
+///
+/// let nested = vec![vec![0_u8; 3], vec![0_u8; 5]];
+/// assert_eq!(nested.heap_size(), 2 * std::mem::size_of::<Vec<u8>>() + 8);
+/// ```
+pub trait DeepSize {
+    /// Returns the bytes of the heap allocations owned by `self`.
+    fn heap_size(&self) -> usize;
+    /// Returns the bytes of `self` itself and its heap allocations.
+    #[inline(always)]
+    fn deep_size(&self) -> usize { size_of_val(self) + self.heap_size() }
+}
+
+/// Implements [`DeepSize`] for the types without heap allocations.
+#[macro_export]
+macro_rules! impl_deep_size_without_heap {
+    ($($type: ty),* $(,)?) => {
+        $(impl $crate::deep_size::DeepSize for $type {
+            #[inline(always)]
+            fn heap_size(&self) -> usize { 0 }
+        })*
+    };
+}
+
+impl_deep_size_without_heap!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    isize,
+    f32,
+    f64,
+    Vector1,
+    Vector2,
+    Vector3,
+    Vector4,
+    Matrix2,
+    Matrix3,
+    Matrix4,
+    Point1,
+    Point2,
+    Point3,
+);
+
+impl<T: DeepSize> DeepSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: DeepSize> DeepSize for VecDeque<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: DeepSize> DeepSize for Box<T> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { T::deep_size(self) }
+}
+
+impl<T: DeepSize> DeepSize for Option<T> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.as_ref().map_or(0, T::heap_size) }
+}
+
+impl<T: DeepSize, const N: usize> DeepSize for [T; N] {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.iter().map(T::heap_size).sum() }
+}
+
+impl<A: DeepSize, B: DeepSize> DeepSize for (A, B) {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.0.heap_size() + self.1.heap_size() }
+}
+
+impl DeepSize for String {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.capacity() }
+}
@@ -0,0 +1,43 @@
+/// Disjoint sets of the indices `0..len`, merged by union-find with path halving.
+/// # Examples
+/// ```
+/// use truck_base::disjoint_set::DisjointSet;
+/// let mut sets = DisjointSet::new(4);
+/// assert!(sets.union(0, 1));
+/// assert!(sets.union(2, 1));
+/// assert!(!sets.union(0, 2));
+/// assert_eq!(sets.root(0), sets.root(2));
+/// assert_ne!(sets.root(0), sets.root(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+}
+
+impl DisjointSet {
+    /// Creates `len` singletons.
+    #[inline(always)]
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    /// Returns the representative of the set including `i`.
+    pub fn root(&mut self, mut i: usize) -> usize {
+        let parents = &mut self.parents;
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+        }
+        i
+    }
+
+    /// Merges the sets including `i` and `j`, with the representative of the set of `j`.
+    /// Returns `false` if they are already in the same set.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (i, j) = (self.root(i), self.root(j));
+        self.parents[i] = j;
+        i != j
+    }
+}
@@ -0,0 +1,65 @@
+/// Returns the eigenvalues and the eigenvectors of the symmetric matrix by the Jacobi eigenvalue
+/// algorithm, in ascending order of the eigenvalues.
+///
+/// The `i`-th eigenvector is the `i`-th element of the returned array, and the eigenvectors are
+/// orthonormal. Since cgmath matrices are column-major, the columns of the matrix made from the
+/// returned array are the eigenvectors.
+/// # Examples
+/// ```
+/// use truck_base::{assert_near, cgmath64::*, eigen, tolerance::*};
+/// let matrix = Matrix3::new(
+///     2.0, 1.0, 0.0,
+///     1.0, 2.0, 0.0,
+///     0.0, 0.0, 5.0,
+/// );
+/// let (values, vectors) = eigen::symmetric_eigen(matrix.into());
+/// assert_near!(Vector3::from(values), Vector3::new(1.0, 3.0, 5.0));
+/// let vectors = Matrix3::from(vectors);
+/// assert_near!(matrix * vectors, vectors * Matrix3::from_diagonal(Vector3::from(values)));
+/// assert_near!(vectors.transpose() * vectors, Matrix3::identity());
+/// ```
+pub fn symmetric_eigen<const N: usize>(mut matrix: [[f64; N]; N]) -> ([f64; N], [[f64; N]; N]) {
+    let mut vectors = [[0.0; N]; N];
+    (0..N).for_each(|i| vectors[i][i] = 1.0);
+    for _ in 0..100 * N {
+        let pivot = (0..N)
+            .flat_map(|p| (p + 1..N).map(move |q| (p, q)))
+            .max_by(|(p0, q0), (p1, q1)| matrix[*p0][*q0].abs().total_cmp(&matrix[*p1][*q1].abs()));
+        let (p, q) = match pivot {
+            Some(pivot) => pivot,
+            None => break,
+        };
+        if matrix[p][q].abs() <= 1.0e-15 * (matrix[p][p].abs() + matrix[q][q].abs()) {
+            break;
+        }
+        let theta = (matrix[q][q] - matrix[p][p]) / (2.0 * matrix[p][q]);
+        let t = f64::signum(theta) / (theta.abs() + f64::sqrt(theta * theta + 1.0));
+        let c = 1.0 / f64::sqrt(t * t + 1.0);
+        let s = t * c;
+        (0..N).for_each(|k| {
+            let (kp, kq) = (matrix[k][p], matrix[k][q]);
+            matrix[k][p] = c * kp - s * kq;
+            matrix[k][q] = s * kp + c * kq;
+        });
+        (0..N).for_each(|k| {
+            let (pk, qk) = (matrix[p][k], matrix[q][k]);
+            matrix[p][k] = c * pk - s * qk;
+            matrix[q][k] = s * pk + c * qk;
+        });
+        let (vp, vq) = (vectors[p], vectors[q]);
+        (0..N).for_each(|k| {
+            vectors[p][k] = c * vp[k] - s * vq[k];
+            vectors[q][k] = s * vp[k] + c * vq[k];
+        });
+    }
+    let mut idcs = [0; N];
+    (0..N).for_each(|i| idcs[i] = i);
+    idcs.sort_by(|i, j| matrix[*i][*i].total_cmp(&matrix[*j][*j]));
+    let mut values = [0.0; N];
+    let mut sorted = [[0.0; N]; N];
+    idcs.iter().enumerate().for_each(|(i, j)| {
+        values[i] = matrix[*j][*j];
+        sorted[i] = vectors[*j];
+    });
+    (values, sorted)
+}
@@ -15,13 +15,23 @@
 
 /// Defines bounding box
 pub mod bounding_box;
+/// Defines bounding sphere and principal axes
+pub mod bounding_sphere;
 /// Redefines vectors, matrices or points with scalar = f64.
 pub mod cgmath64;
 /// Additional traits for cgmath
 pub mod cgmath_extend_traits;
+/// Memory usage accounting
+pub mod deep_size;
+/// Union-find of the indices
+pub mod disjoint_set;
+/// Eigenvalue decomposition of symmetric matrices
+pub mod eigen;
 /// Deterministic hash functions
 pub mod hash;
 /// ID structure with `Copy`, `Hash` and `Eq` using raw pointers
 pub mod id;
+/// 2D predicates of polygons: signed area, orientation, point-in-polygon and convexity
+pub mod polygon;
 /// Setting Tolerance
 pub mod tolerance;
@@ -0,0 +1,192 @@
+use crate::{cgmath64::*, tolerance::*};
+use std::f64::consts::PI;
+
+/// Returns twice the signed area of the triangle `abc`, positive if `abc` is counterclockwise.
+/// # Examples
+/// ```
+/// use truck_base::{cgmath64::*, polygon};
+/// let (a, b) = (Point2::new(0.0, 0.0), Point2::new(1.0, 0.0));
+/// assert_eq!(polygon::orient(a, b, Point2::new(0.0, 1.0)), 1.0);
+/// assert_eq!(polygon::orient(a, b, Point2::new(0.0, -1.0)), -1.0);
+/// assert_eq!(polygon::orient(a, b, Point2::new(2.0, 0.0)), 0.0);
+/// ```
+#[inline(always)]
+pub fn orient(a: Point2, b: Point2, c: Point2) -> f64 { (b - a).perp_dot(c - a) }
+
+fn segments(polygon: &[Point2]) -> impl Iterator<Item = (Point2, Point2)> + '_ {
+    let len = polygon.len();
+    (0..len).map(move |i| (polygon[i], polygon[(i + 1) % len]))
+}
+
+/// Returns the signed area of the polygon, positive if the polygon is counterclockwise.
+///
+/// The polygon is closed implicitly, so the last point may be the same as the first one or not.
+/// # Examples
+/// ```
+/// use truck_base::{cgmath64::*, polygon};
+/// let mut square = vec![
+///     Point2::new(0.0, 0.0),
+///     Point2::new(2.0, 0.0),
+///     Point2::new(2.0, 2.0),
+///     Point2::new(0.0, 2.0),
+/// ];
+/// assert_eq!(polygon::signed_area(&square), 4.0);
+/// square.reverse();
+/// assert_eq!(polygon::signed_area(&square), -4.0);
+/// ```
+pub fn signed_area(polygon: &[Point2]) -> f64 {
+    segments(polygon)
+        .map(|(p, q)| p.x * q.y - p.y * q.x)
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Returns `true` if the signed area of the polygon is positive.
+#[inline(always)]
+pub fn is_ccw(polygon: &[Point2]) -> bool { signed_area(polygon) > 0.0 }
+
+/// Reverses the polygon if it is clockwise, and returns `true` if reversed.
+/// # Examples
+/// ```
+/// use truck_base::{cgmath64::*, polygon};
+/// let mut triangle = vec![
+///     Point2::new(0.0, 0.0),
+///     Point2::new(0.0, 1.0),
+///     Point2::new(1.0, 0.0),
+/// ];
+/// assert!(polygon::make_ccw(&mut triangle));
+/// assert!(polygon::is_ccw(&triangle));
+/// assert!(!polygon::make_ccw(&mut triangle));
+/// ```
+#[inline(always)]
+pub fn make_ccw(polygon: &mut [Point2]) -> bool {
+    let reversed = signed_area(polygon) < 0.0;
+    if reversed {
+        polygon.reverse();
+    }
+    reversed
+}
+
+/// Returns the winding number of the polygon around `point`.
+///
+/// The result is positive for the counterclockwise polygons. The result for the point on the
+/// boundary is undefined, so check [`on_boundary`] if needed.
+/// # Examples
+/// ```
+/// use truck_base::{cgmath64::*, polygon};
+/// // the square wound twice
+/// let square = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(1.0, 0.0),
+///     Point2::new(1.0, 1.0),
+///     Point2::new(0.0, 1.0),
+/// ];
+/// let twice = [square, square].concat();
+/// assert_eq!(polygon::winding_number(&twice, Point2::new(0.5, 0.5)), 2);
+/// assert_eq!(polygon::winding_number(&twice, Point2::new(1.5, 0.5)), 0);
+/// ```
+pub fn winding_number(polygon: &[Point2], point: Point2) -> i32 {
+    segments(polygon).fold(0, |number, (p, q)| match (p.y <= point.y, q.y <= point.y) {
+        (true, false) if orient(p, q, point) > 0.0 => number + 1,
+        (false, true) if orient(p, q, point) < 0.0 => number - 1,
+        _ => number,
+    })
+}
+
+/// Returns `true` if the distance between `point` and the boundary of the polygon is within
+/// `TOLERANCE`.
+pub fn on_boundary(polygon: &[Point2], point: Point2) -> bool {
+    segments(polygon).any(|(p, q)| {
+        let dir = q - p;
+        let t = match dir.magnitude2().so_small2() {
+            true => 0.0,
+            false => f64::clamp((point - p).dot(dir) / dir.magnitude2(), 0.0, 1.0),
+        };
+        point.near(&(p + dir * t))
+    })
+}
+
+/// Returns `true` if `point` is on the boundary or its winding number is not zero.
+/// # Examples
+/// ```
+/// use truck_base::{cgmath64::*, polygon};
+/// // the L-shaped hexagon
+/// let polygon = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(2.0, 0.0),
+///     Point2::new(2.0, 1.0),
+///     Point2::new(1.0, 1.0),
+///     Point2::new(1.0, 2.0),
+///     Point2::new(0.0, 2.0),
+/// ];
+/// assert!(polygon::contains(&polygon, Point2::new(0.5, 1.5)));
+/// assert!(polygon::contains(&polygon, Point2::new(1.5, 1.0)));
+/// assert!(!polygon::contains(&polygon, Point2::new(1.5, 1.5)));
+/// ```
+#[inline(always)]
+pub fn contains(polygon: &[Point2], point: Point2) -> bool {
+    on_boundary(polygon, point) || winding_number(polygon, point) != 0
+}
+
+/// Returns `true` if the polygon is convex.
+///
+/// The consecutive coincident points and the straight angles are ignored. The polygons
+/// turning more than once, like pentagrams, and the degenerate polygons whose areas are zero
+/// are not convex. Both orientations are allowed.
+/// # Examples
+/// ```
+/// use truck_base::{cgmath64::*, polygon};
+/// let square = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(1.0, 0.0),
+///     Point2::new(2.0, 0.0),
+///     Point2::new(2.0, 2.0),
+///     Point2::new(0.0, 2.0),
+/// ];
+/// assert!(polygon::is_convex(&square));
+/// let arrow = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(2.0, 1.0),
+///     Point2::new(0.0, 2.0),
+///     Point2::new(1.0, 1.0),
+/// ];
+/// assert!(!polygon::is_convex(&arrow));
+/// let pentagram: Vec<Point2> = (0..5)
+///     .map(|i| {
+///         let t = (i * 2) as f64 * std::f64::consts::PI * 2.0 / 5.0;
+///         Point2::new(t.cos(), t.sin())
+///     })
+///     .collect();
+/// assert!(!polygon::is_convex(&pentagram));
+/// ```
+pub fn is_convex(polygon: &[Point2]) -> bool {
+    let mut points: Vec<Point2> = Vec::with_capacity(polygon.len());
+    polygon.iter().for_each(|p| match points.last() {
+        Some(q) if p.near(q) => {}
+        _ => points.push(*p),
+    });
+    while points.len() > 1 && points[0].near(&points[points.len() - 1]) {
+        points.pop();
+    }
+    if points.len() < 3 || signed_area(&points).so_small() {
+        return false;
+    }
+    let len = points.len();
+    let (mut sign, mut turning) = (0.0, 0.0);
+    for i in 0..len {
+        let (d0, d1) = (
+            points[i] - points[(i + len - 1) % len],
+            points[(i + 1) % len] - points[i],
+        );
+        let sine = d0.perp_dot(d1) / (d0.magnitude() * d1.magnitude());
+        if sine.so_small() && d0.dot(d1) > 0.0 {
+            continue;
+        }
+        if sine * sign < 0.0 || sine.so_small() {
+            return false;
+        }
+        sign = sine;
+        turning += f64::atan2(d0.perp_dot(d1), d0.dot(d1));
+    }
+    turning.abs().near(&(2.0 * PI))
+}
@@ -0,0 +1,121 @@
+use truck_base::{assert_near, bounding_sphere::*, cgmath64::*, hash::HashGen, tolerance::*};
+
+fn random_points(seed: f64, len: usize) -> Vec<Point3> {
+    (0..len)
+        .map(|i| {
+            let [x, y, z] = HashGen::hash3(seed + i as f64);
+            Point3::new(x * 4.0 - 2.0, y * 2.0 - 1.0, z - 0.5)
+        })
+        .collect()
+}
+
+fn sphere_through(support: &[Point3]) -> Option<(Point3, f64)> {
+    let p = support[0];
+    match support.len() {
+        1 => Some((p, 0.0)),
+        2 => Some((p.midpoint(support[1]), p.distance(support[1]) / 2.0)),
+        3 => {
+            let (a, b) = (support[1] - p, support[2] - p);
+            let normal = a.cross(b);
+            if normal.magnitude2().so_small2() {
+                return None;
+            }
+            let center = p
+                + (b * a.magnitude2() - a * b.magnitude2()).cross(normal)
+                    / (2.0 * normal.magnitude2());
+            Some((center, center.distance(p)))
+        }
+        _ => {
+            let (a, b, c) = (support[1] - p, support[2] - p, support[3] - p);
+            let rows = Matrix3::from_cols(a, b, c).transpose();
+            let rhs = Vector3::new(a.magnitude2(), b.magnitude2(), c.magnitude2()) / 2.0;
+            let center = p + rows.invert()? * rhs;
+            Some((center, center.distance(p)))
+        }
+    }
+}
+
+fn subsets(len: usize, size: usize) -> Vec<Vec<usize>> {
+    match size {
+        0 => vec![Vec::new()],
+        _ => (size - 1..len)
+            .flat_map(|last| {
+                subsets(last, size - 1).into_iter().map(move |mut subset| {
+                    subset.push(last);
+                    subset
+                })
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn minimal_bounding_sphere() {
+    (0..10).for_each(|n| {
+        let points = random_points(n as f64 * 100.0, 12);
+        let sphere: BoundingSphere = points.iter().collect();
+        assert!(points.iter().all(|p| sphere.contains(*p)));
+        let brute_force = (1..=4)
+            .flat_map(|size| subsets(points.len(), size))
+            .filter_map(|subset| {
+                let support: Vec<Point3> = subset.iter().map(|i| points[*i]).collect();
+                sphere_through(&support)
+            })
+            .filter(|(center, radius)| {
+                points
+                    .iter()
+                    .all(|p| center.distance(*p) <= radius + 1.0e-9)
+            })
+            .map(|(_, radius)| radius)
+            .fold(f64::INFINITY, f64::min);
+        assert_near!(sphere.radius(), brute_force);
+    })
+}
+
+#[test]
+fn degenerate_bounding_sphere() {
+    let empty: BoundingSphere = Vec::<Point3>::new().into_iter().collect();
+    assert!(empty.is_empty());
+    assert!(!empty.contains(Point3::origin()));
+
+    let points = [Point3::new(1.0, 2.0, 3.0); 5];
+    let sphere: BoundingSphere = points.iter().collect();
+    assert_near!(sphere.center(), points[0]);
+    assert_near!(sphere.radius(), 0.0);
+
+    // collinear and coplanar points
+    let points: Vec<Point3> = (0..=10).map(|i| Point3::new(i as f64, 0.0, 0.0)).collect();
+    let sphere: BoundingSphere = points.iter().collect();
+    assert_near!(sphere.center(), Point3::new(5.0, 0.0, 0.0));
+    assert_near!(sphere.radius(), 5.0);
+    let points: Vec<Point3> = (0..16)
+        .map(|i| {
+            let t = i as f64 * std::f64::consts::PI / 8.0;
+            Point3::new(t.cos(), t.sin(), 0.0)
+        })
+        .collect();
+    let sphere: BoundingSphere = points.iter().collect();
+    assert_near!(sphere.center(), Point3::origin());
+    assert_near!(sphere.radius(), 1.0);
+}
+
+#[test]
+fn canonical_principal_axes() {
+    let points = random_points(0.0, 1000);
+    let axes: PrincipalAxes = points.iter().collect();
+    let frame = axes.axes();
+    assert_near!(frame.determinant(), 1.0);
+    assert_near!(frame.transpose() * frame, Matrix3::identity());
+    // the random points are spread mostly along the x-axis, next along the y-axis
+    assert!(frame.x.x.abs() > 0.99 && frame.y.y.abs() > 0.99);
+    let variances = axes.variances();
+    assert!(variances.x > variances.y && variances.y > variances.z);
+
+    // the axes are invariant under rigid motions of the points
+    let motion = Matrix4::from_translation(Vector3::new(3.0, -1.0, 2.0))
+        * Matrix4::from_axis_angle(Vector3::new(1.0, 2.0, 3.0).normalize(), Rad(1.0));
+    let moved: PrincipalAxes = points.iter().map(|p| motion.transform_point(*p)).collect();
+    assert_near!(moved.centroid(), motion.transform_point(axes.centroid()));
+    assert_near!(moved.to_matrix(), motion * axes.to_matrix());
+    assert_near!(moved.variances(), variances);
+}
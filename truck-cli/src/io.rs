@@ -0,0 +1,85 @@
+use super::Result;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use truck_meshalgo::prelude::*;
+use truck_stepio::r#in::Table;
+
+/// the file formats distinguished by the extensions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Step,
+    Obj,
+    Stl,
+    Ply,
+    Gltf,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Format> {
+        let ext = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match ext.as_deref() {
+            Some("step" | "stp") => Ok(Format::Step),
+            Some("obj") => Ok(Format::Obj),
+            Some("stl") => Ok(Format::Stl),
+            Some("ply") => Ok(Format::Ply),
+            Some("gltf") => Ok(Format::Gltf),
+            _ => Err(format!("unsupported file format: {}", path.display()).into()),
+        }
+    }
+}
+
+/// Reads the mesh. The STEP file is tessellated with `tol`.
+pub fn read_mesh(path: &Path, tol: f64) -> Result<PolygonMesh> {
+    let format = Format::from_path(path)?;
+    if format == Format::Step {
+        return read_step(path, tol);
+    }
+    let reader = BufReader::new(File::open(path)?);
+    let mesh = match format {
+        Format::Obj => obj::read(reader)?,
+        Format::Stl => stl::read(reader, stl::STLType::Automatic)?,
+        Format::Ply => ply::read(reader)?,
+        Format::Gltf => return Err("reading glTF files is not supported.".into()),
+        Format::Step => unreachable!(),
+    };
+    Ok(mesh)
+}
+
+/// Reads all shells in the STEP file and tessellates them with `tol`.
+pub fn read_step(path: &Path, tol: f64) -> Result<PolygonMesh> {
+    if Format::from_path(path)? != Format::Step {
+        return Err(format!("not a STEP file: {}", path.display()).into());
+    }
+    let step = std::fs::read_to_string(path)?;
+    let table = Table::from_step(&step).map_err(|e| e.to_string())?;
+    let mut shells: Vec<_> = table.to_compressed_shells().into_iter().collect();
+    // the order of the shells in the file
+    shells.sort_by_key(|(id, _)| *id);
+    let mut mesh = PolygonMesh::default();
+    for (id, shell) in shells {
+        let shell = shell.map_err(|e| format!("shell #{id}: {e}"))?;
+        mesh.merge(shell.triangulation(tol).to_polygon());
+    }
+    Ok(mesh)
+}
+
+/// Writes the mesh in the format of the extension.
+pub fn write_mesh(path: &Path, mesh: &PolygonMesh) -> Result<()> {
+    let format = Format::from_path(path)?;
+    if format == Format::Step {
+        return Err("writing meshes to STEP files is not supported.".into());
+    }
+    let mut writer = BufWriter::new(File::create(path)?);
+    match format {
+        Format::Obj => obj::write(mesh, writer)?,
+        Format::Stl => stl::write(mesh, &mut writer, stl::STLType::Binary)?,
+        Format::Ply => ply::write(mesh, writer)?,
+        Format::Gltf => gltf::write(mesh, writer)?,
+        Format::Step => unreachable!(),
+    }
+    Ok(())
+}
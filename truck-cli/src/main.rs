@@ -0,0 +1,214 @@
+//! Command-line tool for batch conversion and healing of meshes and STEP files.
+//!
+//! ### usage
+//!
+//! ```bash
+//! truck-cli convert <input> <output> [--tol <tolerance>]
+//! truck-cli heal <input> <output> [--tol <tolerance>] [--report <json file>]
+//! truck-cli tessellate --tol <tolerance> <input step file> <output>
+//! truck-cli info <input> [--tol <tolerance>]
+//! ```
+//!
+//! The formats are determined by the extensions: STEP (`.step`, `.stp`, input only),
+//! wavefront obj (`.obj`), STL (`.stl`), PLY (`.ply`) and glTF (`.gltf`, output only).
+//! STEP files are tessellated with the tolerance, whose default is `0.01`.
+//! `heal` writes the JSON report of the defects, the fixes, and the residual defects to the file
+//! given by `--report`.
+
+#![cfg_attr(not(debug_assertions), deny(warnings))]
+#![deny(clippy::all, rust_2018_idioms)]
+#![warn(
+    missing_debug_implementations,
+    trivial_casts,
+    trivial_numeric_casts,
+    unsafe_code,
+    unstable_features,
+    unused_import_braces,
+    unused_qualifications
+)]
+
+mod io;
+
+use std::path::Path;
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const USAGE: &str = "usage:
+    truck-cli convert <input> <output> [--tol <tolerance>]
+    truck-cli heal <input> <output> [--tol <tolerance>] [--report <json file>]
+    truck-cli tessellate --tol <tolerance> <input step file> <output>
+    truck-cli info <input> [--tol <tolerance>]
+
+formats: step/stp (input only), obj, stl, ply, gltf (output only)";
+
+const DEFAULT_TOLERANCE: f64 = 0.01;
+
+/// parsed command-line arguments
+#[derive(Clone, Debug)]
+struct Args {
+    command: String,
+    paths: Vec<String>,
+    tol: Option<f64>,
+    report: Option<String>,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Args> {
+        let mut args = args.into_iter();
+        let command = args.next().ok_or("no command is given.")?;
+        let mut paths = Vec::new();
+        let mut tol = None;
+        let mut report = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--tol" => {
+                    let value = args.next().ok_or("--tol requires a value.")?;
+                    let value = value
+                        .parse::<f64>()
+                        .map_err(|_| format!("invalid tolerance: {value}"))?;
+                    if value <= 0.0 {
+                        return Err(format!("the tolerance must be positive: {value}").into());
+                    }
+                    tol = Some(value);
+                }
+                "--report" => {
+                    let path = args.next().ok_or("--report requires a file.")?;
+                    report = Some(path);
+                }
+                _ if arg.starts_with("--") => return Err(format!("unknown option: {arg}").into()),
+                _ => paths.push(arg),
+            }
+        }
+        Ok(Args {
+            command,
+            paths,
+            tol,
+            report,
+        })
+    }
+
+    fn paths<const N: usize>(&self) -> Result<[&Path; N]> {
+        match self.paths.len() == N {
+            true => Ok(std::array::from_fn(|i| Path::new(&self.paths[i]))),
+            false => Err(format!(
+                "{} requires {N} file(s), but {} were given.",
+                self.command,
+                self.paths.len()
+            )
+            .into()),
+        }
+    }
+
+    fn tol(&self) -> f64 { self.tol.unwrap_or(DEFAULT_TOLERANCE) }
+}
+
+fn convert(args: &Args) -> Result<()> {
+    let [input, output] = args.paths()?;
+    let mesh = io::read_mesh(input, args.tol())?;
+    io::write_mesh(output, &mesh)
+}
+
+fn heal(args: &Args) -> Result<()> {
+    let [input, output] = args.paths()?;
+    let mut mesh = io::read_mesh(input, args.tol())?;
+    mesh.put_together_same_attrs();
+    let report = mesh.repair();
+    mesh.remove_unused_attrs();
+    let count = |defect| report.count(defect);
+    println!(
+        "removed zero area faces: {}",
+        count(DefectKind::ZeroAreaFace)
+    );
+    println!(
+        "removed duplicate faces: {}",
+        count(DefectKind::DuplicateFace)
+    );
+    println!(
+        "removed collapsed faces: {}",
+        count(DefectKind::CollapsedFace)
+    );
+    println!("removed unused positions: {}", report.removed_positions);
+    println!(
+        "split non-manifold edges: {}",
+        count(DefectKind::NonManifoldEdge)
+    );
+    println!(
+        "duplicated vertices: {}",
+        count(DefectKind::NonManifoldVertex)
+    );
+    println!(
+        "flipped faces: {}",
+        count(DefectKind::InconsistentOrientation)
+    );
+    if let Some(path) = &args.report {
+        std::fs::write(path, report.to_json())?;
+    }
+    io::write_mesh(output, &mesh)
+}
+
+fn tessellate(args: &Args) -> Result<()> {
+    let tol = args.tol.ok_or("tessellate requires --tol.")?;
+    let [input, output] = args.paths()?;
+    let mesh = io::read_step(input, tol)?;
+    io::write_mesh(output, &mesh)
+}
+
+fn info(args: &Args) -> Result<()> {
+    let [input] = args.paths()?;
+    let mesh = io::read_mesh(input, args.tol())?;
+    let faces = mesh.faces();
+    println!("positions: {}", mesh.positions().len());
+    println!("uv coords: {}", mesh.uv_coords().len());
+    println!("normals: {}", mesh.normals().len());
+    println!(
+        "faces: {} (triangles: {}, quadrangles: {}, others: {})",
+        faces.len(),
+        faces.tri_faces().len(),
+        faces.quad_faces().len(),
+        faces.other_faces().len(),
+    );
+    let bdd_box: BoundingBox<Point3> = mesh.positions().iter().collect();
+    if !bdd_box.is_empty() {
+        let (min, max) = (bdd_box.min(), bdd_box.max());
+        println!(
+            "bounding box: [{}, {}, {}] - [{}, {}, {}]",
+            min.x, min.y, min.z, max.x, max.y, max.z
+        );
+    }
+    let mut welded = mesh.clone();
+    welded.put_together_same_attrs();
+    let condition = welded.shell_condition();
+    println!("shell condition: {condition:?}");
+    println!("boundaries: {}", welded.extract_boundaries().len());
+    println!("surface area: {}", welded.surface_area());
+    if condition == ShellCondition::Closed {
+        if let Ok(props) = welded.mass_properties() {
+            println!("volume: {}", props.volume);
+        }
+    }
+    Ok(())
+}
+
+fn run(args: impl IntoIterator<Item = String>) -> Result<()> {
+    let args = Args::parse(args)?;
+    match args.command.as_str() {
+        "convert" => convert(&args),
+        "heal" => heal(&args),
+        "tessellate" => tessellate(&args),
+        "info" => info(&args),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        command => Err(format!("unknown command: {command}").into()),
+    }
+}
+
+fn main() {
+    if let Err(error) = run(std::env::args().skip(1)) {
+        eprintln!("error: {error}\n\n{USAGE}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,120 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use truck_modeling::*;
+use truck_stepio::out;
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("truck-cli-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+fn truck_cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_truck-cli"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn success(args: &[&str]) -> String {
+    let output = truck_cli(args);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{args:?}: {stderr}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn unit_cube_step() -> PathBuf {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    let step =
+        out::CompleteStepDisplay::new(out::StepModel::new(&cube.compress()), Default::default())
+            .to_string();
+    let path = temp_path("cube.step");
+    std::fs::write(&path, step).unwrap();
+    path
+}
+
+#[test]
+fn interchange_round_trip() {
+    let step = unit_cube_step();
+    let step = step.to_str().unwrap();
+    let obj = temp_path("cube.obj");
+    let stl = temp_path("cube.stl");
+    let ply = temp_path("cube.ply");
+    let obj2 = temp_path("cube2.obj");
+    let gltf = temp_path("cube.gltf");
+    let (obj, stl, ply, obj2, gltf) = (
+        obj.to_str().unwrap(),
+        stl.to_str().unwrap(),
+        ply.to_str().unwrap(),
+        obj2.to_str().unwrap(),
+        gltf.to_str().unwrap(),
+    );
+
+    success(&["tessellate", "--tol", "0.01", step, obj]);
+    success(&["convert", obj, stl]);
+    success(&["convert", stl, ply]);
+    success(&["convert", ply, obj2]);
+    success(&["convert", obj2, gltf]);
+
+    let info = success(&["info", obj2]);
+    assert!(info.contains("faces: 12 (triangles: 12"), "{info}");
+    assert!(info.contains("shell condition: Closed"), "{info}");
+    let volume = info
+        .lines()
+        .find_map(|line| line.strip_prefix("volume: "))
+        .unwrap();
+    assert!(
+        (volume.parse::<f64>().unwrap() - 1.0).abs() < 1.0e-6,
+        "{info}"
+    );
+
+    let info_step = success(&["info", step]);
+    assert!(info_step.contains("shell condition: Closed"), "{info_step}");
+
+    let gltf = std::fs::read_to_string(gltf).unwrap();
+    assert!(gltf.starts_with("{\"asset\":{\"version\":\"2.0\""));
+}
+
+#[test]
+fn heal_mesh() {
+    let input = temp_path("broken.obj");
+    let output = temp_path("healed.obj");
+    // a tetrahedron with a flipped face and a duplicate face
+    let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 0 0 1\n\
+f 1 3 2\nf 1 2 4\nf 1 4 3\nf 3 2 4\nf 1 2 4\n";
+    std::fs::write(&input, obj).unwrap();
+    let (input, output) = (input.to_str().unwrap(), output.to_str().unwrap());
+
+    let report = temp_path("report.json");
+    let report = report.to_str().unwrap();
+    let log = success(&["heal", input, output, "--report", report]);
+    assert!(log.contains("removed duplicate faces: 1"), "{log}");
+    assert!(log.contains("flipped faces: 1"), "{log}");
+    let json = std::fs::read_to_string(report).unwrap();
+    assert!(
+        json.contains("\"defect\": \"inconsistent_orientation\""),
+        "{json}"
+    );
+    assert!(json.contains("\"shell_condition\": \"Closed\""), "{json}");
+    let info = success(&["info", output]);
+    assert!(info.contains("faces: 4"), "{info}");
+    assert!(info.contains("shell condition: Closed"), "{info}");
+}
+
+#[test]
+fn errors() {
+    let output = truck_cli(&["extrude"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown command"));
+
+    let step = unit_cube_step();
+    let output = truck_cli(&["convert", step.to_str().unwrap(), "cube.step"]);
+    assert!(!output.status.success());
+
+    let output = truck_cli(&["tessellate", step.to_str().unwrap(), "cube.obj"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--tol"));
+}
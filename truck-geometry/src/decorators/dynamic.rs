@@ -0,0 +1,444 @@
+use super::*;
+use std::any::Any;
+
+/// The object-safe layer of the geometric traits of 3D curves.
+///
+/// This trait is implemented for all curves satisfying the bounds, and one does not have to
+/// implement it by hand. The curves are wrapped by [`DynCurve`] to be used as the generic curves.
+pub trait CurveObject: Debug + Send + Sync + 'static {
+    /// Substitutes the parameter `t`.
+    fn dyn_subs(&self, t: f64) -> Point3;
+    /// Returns the derivation.
+    fn dyn_der(&self, t: f64) -> Vector3;
+    /// Returns the 2nd-order derivation.
+    fn dyn_der2(&self, t: f64) -> Vector3;
+    /// Returns the parameter range.
+    fn dyn_parameter_range(&self) -> (f64, f64);
+    /// Inverts the curve.
+    fn dyn_invert(&mut self);
+    /// Transforms the curve by `trans`.
+    fn dyn_transform_by(&mut self, trans: Matrix4);
+    /// Searches the parameter `t` such that `self.subs(t)` is near `point`.
+    fn dyn_search_parameter(&self, point: Point3, hint: SPHint1D, trials: usize) -> Option<f64>;
+    /// Searches the parameter `t` such that `self.subs(t)` is the nearest to `point`.
+    fn dyn_search_nearest_parameter(
+        &self,
+        point: Point3,
+        hint: SPHint1D,
+        trials: usize,
+    ) -> Option<f64>;
+    /// Creates the curve division.
+    fn dyn_parameter_division(&self, range: (f64, f64), tol: f64) -> (Vec<f64>, Vec<Point3>);
+    /// Cuts the curve at `t`, and returns the latter curve.
+    fn dyn_cut(&mut self, t: f64) -> Box<dyn CurveObject>;
+    /// Returns whether `other` is the curve of the same type and equal to `self`.
+    fn dyn_eq(&self, other: &dyn Any) -> bool;
+    /// Returns the boxed clone.
+    fn clone_box(&self) -> Box<dyn CurveObject>;
+    /// Returns `self` as `Any` for downcasting.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<C> CurveObject for C
+where C: ParametricCurve3D
+        + BoundedCurve
+        + Invertible
+        + Transformed<Matrix4>
+        + SearchParameter<D1, Point = Point3>
+        + SearchNearestParameter<D1, Point = Point3>
+        + ParameterDivision1D<Point = Point3>
+        + Cut
+        + PartialEq
+        + Debug
+        + Send
+        + Sync
+        + 'static
+{
+    #[inline(always)]
+    fn dyn_subs(&self, t: f64) -> Point3 { ParametricCurve::subs(self, t) }
+    #[inline(always)]
+    fn dyn_der(&self, t: f64) -> Vector3 { ParametricCurve::der(self, t) }
+    #[inline(always)]
+    fn dyn_der2(&self, t: f64) -> Vector3 { ParametricCurve::der2(self, t) }
+    #[inline(always)]
+    fn dyn_parameter_range(&self) -> (f64, f64) { BoundedCurve::parameter_range(self) }
+    #[inline(always)]
+    fn dyn_invert(&mut self) { Invertible::invert(self) }
+    #[inline(always)]
+    fn dyn_transform_by(&mut self, trans: Matrix4) { Transformed::transform_by(self, trans) }
+    #[inline(always)]
+    fn dyn_search_parameter(&self, point: Point3, hint: SPHint1D, trials: usize) -> Option<f64> {
+        SearchParameter::<D1>::search_parameter(self, point, hint, trials)
+    }
+    #[inline(always)]
+    fn dyn_search_nearest_parameter(
+        &self,
+        point: Point3,
+        hint: SPHint1D,
+        trials: usize,
+    ) -> Option<f64> {
+        SearchNearestParameter::<D1>::search_nearest_parameter(self, point, hint, trials)
+    }
+    #[inline(always)]
+    fn dyn_parameter_division(&self, range: (f64, f64), tol: f64) -> (Vec<f64>, Vec<Point3>) {
+        ParameterDivision1D::parameter_division(self, range, tol)
+    }
+    #[inline(always)]
+    fn dyn_cut(&mut self, t: f64) -> Box<dyn CurveObject> { Box::new(Cut::cut(self, t)) }
+    #[inline(always)]
+    fn dyn_eq(&self, other: &dyn Any) -> bool { other.downcast_ref::<C>() == Some(self) }
+    #[inline(always)]
+    fn clone_box(&self) -> Box<dyn CurveObject> { Box::new(self.clone()) }
+    #[inline(always)]
+    fn as_any(&self) -> &dyn Any { self }
+}
+
+/// The object-safe layer of the geometric traits of 3D surfaces.
+///
+/// This trait is implemented for all surfaces satisfying the bounds, and one does not have to
+/// implement it by hand. The surfaces are wrapped by [`DynSurface`] to be used as the generic
+/// surfaces.
+pub trait SurfaceObject: Debug + Send + Sync + 'static {
+    /// Substitutes the parameter `(u, v)`.
+    fn dyn_subs(&self, u: f64, v: f64) -> Point3;
+    /// Returns the derivation by `u`.
+    fn dyn_uder(&self, u: f64, v: f64) -> Vector3;
+    /// Returns the derivation by `v`.
+    fn dyn_vder(&self, u: f64, v: f64) -> Vector3;
+    /// Returns the 2nd-order derivation by `u`.
+    fn dyn_uuder(&self, u: f64, v: f64) -> Vector3;
+    /// Returns the 2nd-order derivation by both `u` and `v`.
+    fn dyn_uvder(&self, u: f64, v: f64) -> Vector3;
+    /// Returns the 2nd-order derivation by `v`.
+    fn dyn_vvder(&self, u: f64, v: f64) -> Vector3;
+    /// Returns the normal vector at `(u, v)`.
+    fn dyn_normal(&self, u: f64, v: f64) -> Vector3;
+    /// Returns the parameter range.
+    fn dyn_parameter_range(&self) -> ((f64, f64), (f64, f64));
+    /// Inverts the surface.
+    fn dyn_invert(&mut self);
+    /// Transforms the surface by `trans`.
+    fn dyn_transform_by(&mut self, trans: Matrix4);
+    /// Searches the parameter `(u, v)` such that `self.subs(u, v)` is near `point`.
+    fn dyn_search_parameter(
+        &self,
+        point: Point3,
+        hint: SPHint2D,
+        trials: usize,
+    ) -> Option<(f64, f64)>;
+    /// Searches the parameter `(u, v)` such that `self.subs(u, v)` is the nearest to `point`.
+    fn dyn_search_nearest_parameter(
+        &self,
+        point: Point3,
+        hint: SPHint2D,
+        trials: usize,
+    ) -> Option<(f64, f64)>;
+    /// Creates the surface division.
+    fn dyn_parameter_division(
+        &self,
+        range: ((f64, f64), (f64, f64)),
+        tol: f64,
+    ) -> (Vec<f64>, Vec<f64>);
+    /// Returns whether `other` is the surface of the same type and equal to `self`.
+    fn dyn_eq(&self, other: &dyn Any) -> bool;
+    /// Returns the boxed clone.
+    fn clone_box(&self) -> Box<dyn SurfaceObject>;
+    /// Returns `self` as `Any` for downcasting.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<S> SurfaceObject for S
+where S: ParametricSurface3D
+        + BoundedSurface
+        + Invertible
+        + Transformed<Matrix4>
+        + SearchParameter<D2, Point = Point3>
+        + SearchNearestParameter<D2, Point = Point3>
+        + ParameterDivision2D
+        + PartialEq
+        + Debug
+        + Send
+        + Sync
+        + 'static
+{
+    #[inline(always)]
+    fn dyn_subs(&self, u: f64, v: f64) -> Point3 { ParametricSurface::subs(self, u, v) }
+    #[inline(always)]
+    fn dyn_uder(&self, u: f64, v: f64) -> Vector3 { ParametricSurface::uder(self, u, v) }
+    #[inline(always)]
+    fn dyn_vder(&self, u: f64, v: f64) -> Vector3 { ParametricSurface::vder(self, u, v) }
+    #[inline(always)]
+    fn dyn_uuder(&self, u: f64, v: f64) -> Vector3 { ParametricSurface::uuder(self, u, v) }
+    #[inline(always)]
+    fn dyn_uvder(&self, u: f64, v: f64) -> Vector3 { ParametricSurface::uvder(self, u, v) }
+    #[inline(always)]
+    fn dyn_vvder(&self, u: f64, v: f64) -> Vector3 { ParametricSurface::vvder(self, u, v) }
+    #[inline(always)]
+    fn dyn_normal(&self, u: f64, v: f64) -> Vector3 { ParametricSurface3D::normal(self, u, v) }
+    #[inline(always)]
+    fn dyn_parameter_range(&self) -> ((f64, f64), (f64, f64)) {
+        BoundedSurface::parameter_range(self)
+    }
+    #[inline(always)]
+    fn dyn_invert(&mut self) { Invertible::invert(self) }
+    #[inline(always)]
+    fn dyn_transform_by(&mut self, trans: Matrix4) { Transformed::transform_by(self, trans) }
+    #[inline(always)]
+    fn dyn_search_parameter(
+        &self,
+        point: Point3,
+        hint: SPHint2D,
+        trials: usize,
+    ) -> Option<(f64, f64)> {
+        SearchParameter::<D2>::search_parameter(self, point, hint, trials)
+    }
+    #[inline(always)]
+    fn dyn_search_nearest_parameter(
+        &self,
+        point: Point3,
+        hint: SPHint2D,
+        trials: usize,
+    ) -> Option<(f64, f64)> {
+        SearchNearestParameter::<D2>::search_nearest_parameter(self, point, hint, trials)
+    }
+    #[inline(always)]
+    fn dyn_parameter_division(
+        &self,
+        range: ((f64, f64), (f64, f64)),
+        tol: f64,
+    ) -> (Vec<f64>, Vec<f64>) {
+        ParameterDivision2D::parameter_division(self, range, tol)
+    }
+    #[inline(always)]
+    fn dyn_eq(&self, other: &dyn Any) -> bool { other.downcast_ref::<S>() == Some(self) }
+    #[inline(always)]
+    fn clone_box(&self) -> Box<dyn SurfaceObject> { Box::new(self.clone()) }
+    #[inline(always)]
+    fn as_any(&self) -> &dyn Any { self }
+}
+
+impl DynCurve {
+    /// constructor
+    #[inline(always)]
+    pub fn new<C: CurveObject>(curve: C) -> Self { Self(Box::new(curve)) }
+    /// Returns the reference of the wrapped curve.
+    #[inline(always)]
+    pub fn get_ref(&self) -> &dyn CurveObject { &*self.0 }
+    /// Returns the reference of the wrapped curve if its type is `C`.
+    #[inline(always)]
+    pub fn downcast_ref<C: CurveObject>(&self) -> Option<&C> { self.0.as_any().downcast_ref() }
+}
+
+impl Clone for DynCurve {
+    #[inline(always)]
+    fn clone(&self) -> Self { Self(self.0.clone_box()) }
+}
+
+impl PartialEq for DynCurve {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool { self.0.dyn_eq(other.0.as_any()) }
+}
+
+/// Counts only the wrapped curve itself, since the heap of the curve is unknown.
+impl DeepSize for DynCurve {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { size_of_val(&*self.0) }
+}
+
+/// Quantizes the sampled points, since the control data of the curve is unknown.
+impl Quantize for DynCurve {
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        const N: usize = 8;
+        let (t0, t1) = self.parameter_range();
+        (t0, t1).quantize(tol, key);
+        (0..=N).for_each(|i| {
+            let t = t0 + (t1 - t0) * i as f64 / N as f64;
+            self.subs(t).quantize(tol, key);
+        });
+    }
+}
+
+impl ParametricCurve for DynCurve {
+    type Point = Point3;
+    type Vector = Vector3;
+    #[inline(always)]
+    fn subs(&self, t: f64) -> Point3 { self.0.dyn_subs(t) }
+    #[inline(always)]
+    fn der(&self, t: f64) -> Vector3 { self.0.dyn_der(t) }
+    #[inline(always)]
+    fn der2(&self, t: f64) -> Vector3 { self.0.dyn_der2(t) }
+}
+
+impl BoundedCurve for DynCurve {
+    #[inline(always)]
+    fn parameter_range(&self) -> (f64, f64) { self.0.dyn_parameter_range() }
+}
+
+impl Invertible for DynCurve {
+    #[inline(always)]
+    fn invert(&mut self) { self.0.dyn_invert() }
+}
+
+impl Transformed<Matrix4> for DynCurve {
+    #[inline(always)]
+    fn transform_by(&mut self, trans: Matrix4) { self.0.dyn_transform_by(trans) }
+}
+
+impl SearchParameter<D1> for DynCurve {
+    type Point = Point3;
+    #[inline(always)]
+    fn search_parameter<H: Into<SPHint1D>>(
+        &self,
+        point: Point3,
+        hint: H,
+        trials: usize,
+    ) -> Option<f64> {
+        self.0.dyn_search_parameter(point, hint.into(), trials)
+    }
+}
+
+impl SearchNearestParameter<D1> for DynCurve {
+    type Point = Point3;
+    #[inline(always)]
+    fn search_nearest_parameter<H: Into<SPHint1D>>(
+        &self,
+        point: Point3,
+        hint: H,
+        trials: usize,
+    ) -> Option<f64> {
+        self.0
+            .dyn_search_nearest_parameter(point, hint.into(), trials)
+    }
+}
+
+impl Cut for DynCurve {
+    #[inline(always)]
+    fn cut(&mut self, t: f64) -> Self { Self(self.0.dyn_cut(t)) }
+}
+
+impl ParameterDivision1D for DynCurve {
+    type Point = Point3;
+    #[inline(always)]
+    fn parameter_division(&self, range: (f64, f64), tol: f64) -> (Vec<f64>, Vec<Point3>) {
+        self.0.dyn_parameter_division(range, tol)
+    }
+}
+
+impl DynSurface {
+    /// constructor
+    #[inline(always)]
+    pub fn new<S: SurfaceObject>(surface: S) -> Self { Self(Box::new(surface)) }
+    /// Returns the reference of the wrapped surface.
+    #[inline(always)]
+    pub fn get_ref(&self) -> &dyn SurfaceObject { &*self.0 }
+    /// Returns the reference of the wrapped surface if its type is `S`.
+    #[inline(always)]
+    pub fn downcast_ref<S: SurfaceObject>(&self) -> Option<&S> { self.0.as_any().downcast_ref() }
+}
+
+impl Clone for DynSurface {
+    #[inline(always)]
+    fn clone(&self) -> Self { Self(self.0.clone_box()) }
+}
+
+impl PartialEq for DynSurface {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool { self.0.dyn_eq(other.0.as_any()) }
+}
+
+/// Counts only the wrapped surface itself, since the heap of the surface is unknown.
+impl DeepSize for DynSurface {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { size_of_val(&*self.0) }
+}
+
+/// Quantizes the sampled points, since the control data of the surface is unknown.
+impl Quantize for DynSurface {
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        const N: usize = 4;
+        let ((u0, u1), (v0, v1)) = self.parameter_range();
+        ((u0, u1), (v0, v1)).quantize(tol, key);
+        (0..=N).for_each(|i| {
+            let u = u0 + (u1 - u0) * i as f64 / N as f64;
+            (0..=N).for_each(|j| {
+                let v = v0 + (v1 - v0) * j as f64 / N as f64;
+                self.subs(u, v).quantize(tol, key);
+            })
+        });
+    }
+}
+
+impl ParametricSurface for DynSurface {
+    type Point = Point3;
+    type Vector = Vector3;
+    #[inline(always)]
+    fn subs(&self, u: f64, v: f64) -> Point3 { self.0.dyn_subs(u, v) }
+    #[inline(always)]
+    fn uder(&self, u: f64, v: f64) -> Vector3 { self.0.dyn_uder(u, v) }
+    #[inline(always)]
+    fn vder(&self, u: f64, v: f64) -> Vector3 { self.0.dyn_vder(u, v) }
+    #[inline(always)]
+    fn uuder(&self, u: f64, v: f64) -> Vector3 { self.0.dyn_uuder(u, v) }
+    #[inline(always)]
+    fn uvder(&self, u: f64, v: f64) -> Vector3 { self.0.dyn_uvder(u, v) }
+    #[inline(always)]
+    fn vvder(&self, u: f64, v: f64) -> Vector3 { self.0.dyn_vvder(u, v) }
+}
+
+impl ParametricSurface3D for DynSurface {
+    #[inline(always)]
+    fn normal(&self, u: f64, v: f64) -> Vector3 { self.0.dyn_normal(u, v) }
+}
+
+impl BoundedSurface for DynSurface {
+    #[inline(always)]
+    fn parameter_range(&self) -> ((f64, f64), (f64, f64)) { self.0.dyn_parameter_range() }
+}
+
+impl Invertible for DynSurface {
+    #[inline(always)]
+    fn invert(&mut self) { self.0.dyn_invert() }
+}
+
+impl Transformed<Matrix4> for DynSurface {
+    #[inline(always)]
+    fn transform_by(&mut self, trans: Matrix4) { self.0.dyn_transform_by(trans) }
+}
+
+impl SearchParameter<D2> for DynSurface {
+    type Point = Point3;
+    #[inline(always)]
+    fn search_parameter<H: Into<SPHint2D>>(
+        &self,
+        point: Point3,
+        hint: H,
+        trials: usize,
+    ) -> Option<(f64, f64)> {
+        self.0.dyn_search_parameter(point, hint.into(), trials)
+    }
+}
+
+impl SearchNearestParameter<D2> for DynSurface {
+    type Point = Point3;
+    #[inline(always)]
+    fn search_nearest_parameter<H: Into<SPHint2D>>(
+        &self,
+        point: Point3,
+        hint: H,
+        trials: usize,
+    ) -> Option<(f64, f64)> {
+        self.0
+            .dyn_search_nearest_parameter(point, hint.into(), trials)
+    }
+}
+
+impl ParameterDivision2D for DynSurface {
+    #[inline(always)]
+    fn parameter_division(
+        &self,
+        range: ((f64, f64), (f64, f64)),
+        tol: f64,
+    ) -> (Vec<f64>, Vec<f64>) {
+        self.0.dyn_parameter_division(range, tol)
+    }
+}
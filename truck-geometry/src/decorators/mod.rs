@@ -30,7 +30,7 @@ use std::ops::{Deref, DerefMut, Mul};
 ///     }
 /// }
 /// ```
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RevolutedCurve<C> {
     curve: C,
     origin: Point3,
@@ -38,7 +38,7 @@ pub struct RevolutedCurve<C> {
 }
 
 /// Linearly extruded curve
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ExtrudedCurve<C, V> {
     curve: C,
     vector: V,
@@ -102,7 +102,7 @@ pub struct ExtrudedCurve<C, V> {
 ///     }
 /// }
 /// ```
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Processor<E, T> {
     entity: E,
     transform: T,
@@ -110,14 +110,14 @@ pub struct Processor<E, T> {
 }
 
 /// The composited maps
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PCurve<C, S> {
     curve: C,
     surface: S,
 }
 
 /// Intersection curve between two surfaces.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IntersectionCurve<C, S> {
     // Considering rotational surfaces, we can consider the case
     // where the class `S` holds the curve `C` as a variable.
@@ -128,16 +128,160 @@ pub struct IntersectionCurve<C, S> {
 }
 
 /// trimmed curve for parametric curve
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct TrimmedCurve<C> {
     curve: C,
     range: (f64, f64),
 }
 
+/// 3D curve whose type is determined at runtime, e.g. the curve supplied by a plugin
+///
+/// Any curve implementing the bounds of [`CurveObject`] can be wrapped, and the wrapper
+/// implements the geometric traits required by the modeling and the tessellation.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+/// let line = Line(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+/// let bspcurve = BSplineCurve::new(
+///     KnotVec::bezier_knot(2),
+///     vec![
+///         Point3::new(1.0, 0.0, 0.0),
+///         Point3::new(1.0, 1.0, 0.0),
+///         Point3::new(0.0, 1.0, 0.0),
+///     ],
+/// );
+/// // the curves of different types in one vector
+/// let mut curves = vec![DynCurve::new(line), DynCurve::new(bspcurve.clone())];
+/// assert_near!(curves[0].subs(0.5), Point3::new(0.5, 0.0, 0.0));
+/// assert_near!(curves[1].back(), Point3::new(0.0, 1.0, 0.0));
+///
+/// // the generic operations are dispatched to the wrapped curves.
+/// curves[1].invert();
+/// assert_near!(curves[1].front(), Point3::new(0.0, 1.0, 0.0));
+/// let t = curves[1].search_parameter(Point3::new(1.0, 0.0, 0.0), None, 10).unwrap();
+/// assert_near!(t, 1.0);
+/// let moved = curves[0].transformed(Matrix4::from_translation(Vector3::unit_z()));
+/// assert_near!(moved.front(), Point3::new(0.0, 0.0, 1.0));
+///
+/// // the original curves can be recovered.
+/// assert!(curves[0].downcast_ref::<Line<Point3>>().is_some());
+/// assert_eq!(curves[1].downcast_ref(), Some(&bspcurve.inverse()));
+/// ```
+#[derive(Debug)]
+pub struct DynCurve(Box<dyn CurveObject>);
+
+/// 3D surface whose type is determined at runtime, e.g. the surface supplied by a plugin
+///
+/// Any surface implementing the bounds of [`SurfaceObject`] can be wrapped, and the wrapper
+/// implements the geometric traits required by the modeling and the tessellation.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+/// let plane = Plane::new(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// );
+/// let mut surface = DynSurface::new(plane);
+/// assert_near!(surface.normal(0.3, 0.4), Vector3::unit_z());
+/// surface.invert();
+/// assert_near!(surface.normal(0.3, 0.4), -Vector3::unit_z());
+/// let (u, v) = surface.search_parameter(Point3::new(0.3, 0.4, 0.0), None, 10).unwrap();
+/// assert_near!(surface.subs(u, v), Point3::new(0.3, 0.4, 0.0));
+/// assert!(surface.downcast_ref::<Plane>().is_some());
+/// assert!(surface.downcast_ref::<BSplineSurface<Point3>>().is_none());
+/// ```
+#[derive(Debug)]
+pub struct DynSurface(Box<dyn SurfaceObject>);
+
+impl<C: DeepSize> DeepSize for RevolutedCurve<C> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.curve.heap_size() }
+}
+
+impl<C: DeepSize, V: DeepSize> DeepSize for ExtrudedCurve<C, V> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.curve.heap_size() + self.vector.heap_size() }
+}
+
+impl<E: DeepSize, T: DeepSize> DeepSize for Processor<E, T> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.entity.heap_size() + self.transform.heap_size() }
+}
+
+impl<C: DeepSize, S: DeepSize> DeepSize for PCurve<C, S> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.curve.heap_size() + self.surface.heap_size() }
+}
+
+impl<C: DeepSize, S: DeepSize> DeepSize for IntersectionCurve<C, S> {
+    fn heap_size(&self) -> usize {
+        self.surface0.heap_size() + self.surface1.heap_size() + self.leader.heap_size()
+    }
+}
+
+impl<C: DeepSize> DeepSize for TrimmedCurve<C> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.curve.heap_size() }
+}
+
+impl<C: Quantize> Quantize for RevolutedCurve<C> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.curve.quantize(tol, key);
+        self.origin.quantize(tol, key);
+        self.axis.quantize(tol, key);
+    }
+}
+
+impl<C: Quantize, V: Quantize> Quantize for ExtrudedCurve<C, V> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.curve.quantize(tol, key);
+        self.vector.quantize(tol, key);
+    }
+}
+
+impl<E: Quantize, T: Quantize> Quantize for Processor<E, T> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.entity.quantize(tol, key);
+        self.transform.quantize(tol, key);
+        self.orientation.quantize(tol, key);
+    }
+}
+
+impl<C: Quantize, S: Quantize> Quantize for PCurve<C, S> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.curve.quantize(tol, key);
+        self.surface.quantize(tol, key);
+    }
+}
+
+impl<C: Quantize, S: Quantize> Quantize for IntersectionCurve<C, S> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.surface0.quantize(tol, key);
+        self.surface1.quantize(tol, key);
+        self.leader.quantize(tol, key);
+    }
+}
+
+impl<C: Quantize> Quantize for TrimmedCurve<C> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.curve.quantize(tol, key);
+        self.range.quantize(tol, key);
+    }
+}
+
 mod curve_on_surface;
+mod dynamic;
 mod extruded_curve;
 mod intersection_curve;
 mod processor;
 mod revolved_curve;
 mod trimmied_curve;
+pub use dynamic::{CurveObject, SurfaceObject};
 pub use intersection_curve::double_projection;
@@ -0,0 +1,630 @@
+use crate::*;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Closed interval of `f64` with outward rounding.
+///
+/// The results of the arithmetic operations include all results of the operations of the
+/// elements, even if the floating point errors are taken into account.
+/// # Examples
+/// ```
+/// use truck_geometry::interval::Interval;
+///
+/// let x = Interval::new(-1.0, 2.0);
+/// let y = x * x - x;
+/// // the exact range is [-0.25, 2.0], but the interval arithmetic
+/// // does not know that both `x` are the same value.
+/// assert!(y.contains(-4.0) && y.contains(5.0));
+/// assert!(!y.contains(5.1));
+/// assert!(x.square().contains(0.0) && !x.square().contains(-0.1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Interval {
+    lo: f64,
+    hi: f64,
+}
+
+impl Interval {
+    /// Creates the interval `[lo, hi]`.
+    /// # Panics
+    /// Panic occurs if `lo > hi`.
+    #[inline(always)]
+    pub fn new(lo: f64, hi: f64) -> Self {
+        assert!(
+            lo <= hi,
+            "the lower bound {lo} is greater than the upper bound {hi}"
+        );
+        Self { lo, hi }
+    }
+    /// Creates the interval including only `x`.
+    #[inline(always)]
+    pub const fn point(x: f64) -> Self { Self { lo: x, hi: x } }
+    /// Returns the lower bound.
+    #[inline(always)]
+    pub const fn lo(self) -> f64 { self.lo }
+    /// Returns the upper bound.
+    #[inline(always)]
+    pub const fn hi(self) -> f64 { self.hi }
+    /// Returns the midpoint.
+    #[inline(always)]
+    pub fn mid(self) -> f64 { (self.lo + self.hi) / 2.0 }
+    /// Returns the width.
+    #[inline(always)]
+    pub fn width(self) -> f64 { self.hi - self.lo }
+    /// Returns whether `x` is included in `self`.
+    #[inline(always)]
+    pub fn contains(self, x: f64) -> bool { self.lo <= x && x <= self.hi }
+    /// Returns whether all elements are positive.
+    #[inline(always)]
+    pub fn is_positive(self) -> bool { self.lo > 0.0 }
+    /// Returns whether all elements are negative.
+    #[inline(always)]
+    pub fn is_negative(self) -> bool { self.hi < 0.0 }
+    /// Returns the smallest interval including `self` and `other`.
+    #[inline(always)]
+    pub fn hull(self, other: Self) -> Self {
+        Self {
+            lo: f64::min(self.lo, other.lo),
+            hi: f64::max(self.hi, other.hi),
+        }
+    }
+    /// Returns the common part of `self` and `other`, or `None` if they are disjoint.
+    #[inline(always)]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        let (lo, hi) = (f64::max(self.lo, other.lo), f64::min(self.hi, other.hi));
+        (lo <= hi).then_some(Self { lo, hi })
+    }
+    /// Divides `self` at the midpoint.
+    #[inline(always)]
+    pub fn bisect(self) -> (Self, Self) {
+        let mid = self.mid();
+        (Self::new(self.lo, mid), Self::new(mid, self.hi))
+    }
+    /// Returns the interval including the squares of all elements.
+    pub fn square(self) -> Self {
+        if self.lo >= 0.0 {
+            Self::rounded(self.lo * self.lo, self.hi * self.hi)
+        } else if self.hi <= 0.0 {
+            Self::rounded(self.hi * self.hi, self.lo * self.lo)
+        } else {
+            Self {
+                lo: 0.0,
+                hi: f64::max(self.lo * self.lo, self.hi * self.hi).next_up(),
+            }
+        }
+    }
+    /// Returns the interval including the square roots of all non-negative elements,
+    /// or `None` if all elements are negative.
+    pub fn sqrt(self) -> Option<Self> {
+        if self.hi < 0.0 {
+            return None;
+        }
+        Some(Self {
+            lo: f64::max(f64::sqrt(f64::max(self.lo, 0.0)).next_down(), 0.0),
+            hi: f64::sqrt(self.hi).next_up(),
+        })
+    }
+    #[inline(always)]
+    fn rounded(lo: f64, hi: f64) -> Self {
+        Self {
+            lo: lo.next_down(),
+            hi: hi.next_up(),
+        }
+    }
+}
+
+impl From<f64> for Interval {
+    #[inline(always)]
+    fn from(x: f64) -> Self { Self::point(x) }
+}
+
+impl Neg for Interval {
+    type Output = Self;
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+impl Add for Interval {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, other: Self) -> Self { Self::rounded(self.lo + other.lo, self.hi + other.hi) }
+}
+
+impl Sub for Interval {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, other: Self) -> Self { Self::rounded(self.lo - other.hi, self.hi - other.lo) }
+}
+
+impl Mul for Interval {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let products = [
+            self.lo * other.lo,
+            self.lo * other.hi,
+            self.hi * other.lo,
+            self.hi * other.hi,
+        ];
+        let lo = products.iter().copied().fold(f64::INFINITY, f64::min);
+        let hi = products.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Self::rounded(lo, hi)
+    }
+}
+
+macro_rules! impl_scalar_ops {
+    ($trait: ident, $method: ident) => {
+        impl $trait<f64> for Interval {
+            type Output = Self;
+            #[inline(always)]
+            fn $method(self, other: f64) -> Self { self.$method(Self::point(other)) }
+        }
+        impl $trait<Interval> for f64 {
+            type Output = Interval;
+            #[inline(always)]
+            fn $method(self, other: Interval) -> Interval { Interval::point(self).$method(other) }
+        }
+    };
+}
+
+impl_scalar_ops!(Add, add);
+impl_scalar_ops!(Sub, sub);
+impl_scalar_ops!(Mul, mul);
+
+/// Enlarges `bdd` by the rounding errors of the operations creating it.
+fn inflate(bdd: BoundingBox<Point3>) -> BoundingBox<Point3> {
+    if bdd.is_empty() {
+        return bdd;
+    }
+    let (min, max) = (*bdd.min(), *bdd.max());
+    let scale = (0..3).fold(1.0, |s, i| {
+        f64::max(s, f64::max(min[i].abs(), max[i].abs()))
+    });
+    let margin = Vector3::new(1.0, 1.0, 1.0) * (scale * 1.0e-12);
+    [min - margin, max + margin].iter().collect()
+}
+
+fn to_box(coords: [Interval; 3]) -> BoundingBox<Point3> {
+    let [x, y, z] = coords;
+    [
+        Point3::new(x.lo(), y.lo(), z.lo()),
+        Point3::new(x.hi(), y.hi(), z.hi()),
+    ]
+    .iter()
+    .collect()
+}
+
+fn to_intervals(bdd: &BoundingBox<Point3>) -> [Interval; 3] {
+    let (min, max) = (bdd.min(), bdd.max());
+    [0, 1, 2].map(|i| Interval::new(min[i], max[i]))
+}
+
+/// Curves whose points on parameter intervals are enclosed by boxes.
+pub trait CurveEnclosure {
+    /// Returns a box including all points on the curve with the parameters in `t`,
+    /// or `None` if the curve cannot be enclosed.
+    fn enclosure(&self, t: Interval) -> Option<BoundingBox<Point3>>;
+}
+
+/// Surfaces whose points on parameter rectangles are enclosed by boxes.
+pub trait SurfaceEnclosure {
+    /// Returns a box including all points on the surface with the parameters in `u` and `v`,
+    /// or `None` if the surface cannot be enclosed.
+    fn enclosure(&self, u: Interval, v: Interval) -> Option<BoundingBox<Point3>>;
+}
+
+/// Restricts `t` to the open parameter range and returns the cut points.
+fn cut_range(t: Interval, (t0, t1): (f64, f64)) -> Option<(Option<f64>, Option<f64>)> {
+    let t = t.intersection(Interval::new(t0, t1))?;
+    // too short intervals are enlarged toward the back.
+    let front = (t.lo() > t0 + TOLERANCE && t.lo() < t.hi() - TOLERANCE).then_some(t.lo());
+    let back = (t.hi() < t1 - TOLERANCE).then_some(t.hi());
+    Some((front, back))
+}
+
+impl CurveEnclosure for BSplineCurve<Point3> {
+    /// The box of the control points of the cut curve, by the convex hull property.
+    fn enclosure(&self, t: Interval) -> Option<BoundingBox<Point3>> {
+        let (front, back) = match cut_range(t, self.parameter_range()) {
+            Some(cut) => cut,
+            None => return Some(BoundingBox::new()),
+        };
+        let mut curve = self.clone();
+        if let Some(t1) = back {
+            curve.cut(t1);
+        }
+        if let Some(t0) = front {
+            curve = curve.cut(t0);
+        }
+        Some(inflate(curve.roughly_bounding_box()))
+    }
+}
+
+impl CurveEnclosure for NURBSCurve<Vector4> {
+    /// The box of the control points of the cut curve, by the convex hull property.
+    /// Returns `None` if some weights are not positive.
+    fn enclosure(&self, t: Interval) -> Option<BoundingBox<Point3>> {
+        if self.control_points().iter().any(|p| p.w <= 0.0) {
+            return None;
+        }
+        let (front, back) = match cut_range(t, self.parameter_range()) {
+            Some(cut) => cut,
+            None => return Some(BoundingBox::new()),
+        };
+        let mut curve = self.clone();
+        if let Some(t1) = back {
+            curve.cut(t1);
+        }
+        if let Some(t0) = front {
+            curve = curve.cut(t0);
+        }
+        Some(inflate(curve.roughly_bounding_box()))
+    }
+}
+
+impl CurveEnclosure for Line<Point3> {
+    fn enclosure(&self, t: Interval) -> Option<BoundingBox<Point3>> {
+        let Line(p, q) = *self;
+        let coords = [0, 1, 2].map(|i| p[i] + t * (q[i] - p[i]));
+        Some(to_box(coords))
+    }
+}
+
+impl SurfaceEnclosure for BSplineSurface<Point3> {
+    /// The box of the control points of the cut surface, by the convex hull property.
+    fn enclosure(&self, u: Interval, v: Interval) -> Option<BoundingBox<Point3>> {
+        let (urange, vrange) = self.parameter_range();
+        let (ucut, vcut) = match cut_range(u, urange).zip(cut_range(v, vrange)) {
+            Some(cut) => cut,
+            None => return Some(BoundingBox::new()),
+        };
+        let mut surface = self.clone();
+        if let Some(u1) = ucut.1 {
+            surface.ucut(u1);
+        }
+        if let Some(u0) = ucut.0 {
+            surface = surface.ucut(u0);
+        }
+        if let Some(v1) = vcut.1 {
+            surface.vcut(v1);
+        }
+        if let Some(v0) = vcut.0 {
+            surface = surface.vcut(v0);
+        }
+        Some(inflate(surface.roughly_bounding_box()))
+    }
+}
+
+impl SurfaceEnclosure for NURBSSurface<Vector4> {
+    /// The box of the control points of the cut surface, by the convex hull property.
+    /// Returns `None` if some weights are not positive.
+    fn enclosure(&self, u: Interval, v: Interval) -> Option<BoundingBox<Point3>> {
+        if self.control_points().iter().flatten().any(|p| p.w <= 0.0) {
+            return None;
+        }
+        let (urange, vrange) = self.parameter_range();
+        let (ucut, vcut) = match cut_range(u, urange).zip(cut_range(v, vrange)) {
+            Some(cut) => cut,
+            None => return Some(BoundingBox::new()),
+        };
+        let mut surface = self.clone();
+        if let Some(u1) = ucut.1 {
+            surface.ucut(u1);
+        }
+        if let Some(u0) = ucut.0 {
+            surface = surface.ucut(u0);
+        }
+        if let Some(v1) = vcut.1 {
+            surface.vcut(v1);
+        }
+        if let Some(v0) = vcut.0 {
+            surface = surface.vcut(v0);
+        }
+        Some(inflate(surface.roughly_bounding_box()))
+    }
+}
+
+impl SurfaceEnclosure for Plane {
+    fn enclosure(&self, u: Interval, v: Interval) -> Option<BoundingBox<Point3>> {
+        let (o, a, b) = (self.origin(), self.u_axis(), self.v_axis());
+        let coords = [0, 1, 2].map(|i| o[i] + u * a[i] + v * b[i]);
+        Some(to_box(coords))
+    }
+}
+
+impl<C> SurfaceEnclosure for RevolutedCurve<C>
+where C: CurveEnclosure
+{
+    /// The box of the cylinder including the revolved box of the entity curve.
+    /// The parameter `v` of the angle is not taken into account.
+    fn enclosure(&self, u: Interval, _: Interval) -> Option<BoundingBox<Point3>> {
+        let bdd = self.entity_curve().enclosure(u)?;
+        if bdd.is_empty() {
+            return Some(bdd);
+        }
+        let (origin, axis) = (self.origin(), self.axis());
+        let (min, max) = (*bdd.min(), *bdd.max());
+        let (heights, radius) = (0..8).fold((None::<Interval>, 0.0_f64), |(heights, radius), i| {
+            let corner = Point3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            );
+            let vec = corner - origin;
+            let h = Interval::point(vec.dot(axis));
+            let heights = Some(heights.map_or(h, |heights| heights.hull(h)));
+            // the distance from the axis is convex, hence maximized at a corner.
+            (
+                heights,
+                f64::max(radius, (vec - vec.dot(axis) * axis).magnitude()),
+            )
+        });
+        let heights = heights?;
+        let coords = [0, 1, 2].map(|i| {
+            let spread = radius * f64::sqrt(f64::max(1.0 - axis[i] * axis[i], 0.0));
+            origin[i] + heights * axis[i] + Interval::new(-spread, spread)
+        });
+        Some(inflate(to_box(coords)))
+    }
+}
+
+impl<E> SurfaceEnclosure for Processor<E, Matrix4>
+where E: SurfaceEnclosure + BoundedSurface
+{
+    /// The transformed box of the whole entity surface.
+    /// The parameters `u` and `v` are not taken into account.
+    fn enclosure(&self, _: Interval, _: Interval) -> Option<BoundingBox<Point3>> {
+        let ((u0, u1), (v0, v1)) = self.entity().parameter_range();
+        let bdd = self
+            .entity()
+            .enclosure(Interval::new(u0, u1), Interval::new(v0, v1))?;
+        if bdd.is_empty() {
+            return Some(bdd);
+        }
+        let mat = *self.transform();
+        if mat[0][3] != 0.0 || mat[1][3] != 0.0 || mat[2][3] != 0.0 || mat[3][3] != 1.0 {
+            // projective transformations are not treated.
+            return None;
+        }
+        let coords = to_intervals(&bdd);
+        let transformed = [0, 1, 2].map(|i| {
+            (0..3).fold(Interval::point(mat[3][i]), |sum, j| {
+                sum + coords[j] * mat[j][i]
+            })
+        });
+        Some(inflate(to_box(transformed)))
+    }
+}
+
+fn disjoint(bdd0: &BoundingBox<Point3>, bdd1: &BoundingBox<Point3>) -> bool {
+    bdd0.is_empty()
+        || bdd1.is_empty()
+        || (0..3).any(|i| bdd0.max()[i] < bdd1.min()[i] || bdd1.max()[i] < bdd0.min()[i])
+}
+
+fn box_distance(bdd: &BoundingBox<Point3>, point: Point3) -> f64 {
+    let (min, max) = (bdd.min(), bdd.max());
+    let vec = Vector3::new(
+        f64::max(f64::max(min.x - point.x, point.x - max.x), 0.0),
+        f64::max(f64::max(min.y - point.y, point.y - max.y), 0.0),
+        f64::max(f64::max(min.z - point.z, point.z - max.z), 0.0),
+    );
+    vec.magnitude()
+}
+
+/// Certifies that `surface0` on the parameter rectangle `range0` and `surface1` on `range1`
+/// have no common points, by bisecting the rectangles at most `depth` times.
+///
+/// Returns `false` if the surfaces may intersect, e.g. near the tangencies where the
+/// tolerance checks are not reliable.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+/// use truck_geometry::interval::*;
+///
+/// let plane0 = Plane::new(Point3::origin(), Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0));
+/// let plane1 = Plane::new(
+///     Point3::new(0.0, 0.0, 1.0e-8),
+///     Point3::new(1.0, 0.0, 1.0e-8),
+///     Point3::new(0.0, 1.0, 1.0e-8),
+/// );
+/// let range = (Interval::new(0.0, 1.0), Interval::new(0.0, 1.0));
+/// // separated by the distance less than `TOLERANCE`, but certified disjoint.
+/// assert!(surfaces_disjoint(&plane0, range, &plane1, range, 0));
+/// assert!(!surfaces_disjoint(&plane0, range, &plane0, range, 4));
+/// ```
+pub fn surfaces_disjoint<S0, S1>(
+    surface0: &S0,
+    range0: (Interval, Interval),
+    surface1: &S1,
+    range1: (Interval, Interval),
+    depth: usize,
+) -> bool
+where
+    S0: SurfaceEnclosure,
+    S1: SurfaceEnclosure,
+{
+    let (bdd0, bdd1) = match surface0
+        .enclosure(range0.0, range0.1)
+        .zip(surface1.enclosure(range1.0, range1.1))
+    {
+        Some(bdds) => bdds,
+        None => return false,
+    };
+    if disjoint(&bdd0, &bdd1) {
+        return true;
+    }
+    if depth == 0 {
+        return false;
+    }
+    let split = |(u, v): (Interval, Interval)| {
+        let ((u0, u1), (v0, v1)) = (u.bisect(), v.bisect());
+        [(u0, v0), (u0, v1), (u1, v0), (u1, v1)]
+    };
+    match bdd0.diameter() > bdd1.diameter() {
+        true => split(range0)
+            .into_iter()
+            .all(|range0| surfaces_disjoint(surface0, range0, surface1, range1, depth - 1)),
+        false => split(range1)
+            .into_iter()
+            .all(|range1| surfaces_disjoint(surface0, range0, surface1, range1, depth - 1)),
+    }
+}
+
+/// Certifies that all points of `surface` on the parameter rectangle `range` are strictly on
+/// one side of the infinite plane `plane`, by bisecting the rectangle at most `depth` times.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+/// use truck_geometry::interval::*;
+///
+/// // the paraboloid touching the plane z = 0 at the origin
+/// let surface = BSplineSurface::new(
+///     (KnotVec::bezier_knot(2), KnotVec::bezier_knot(2)),
+///     vec![
+///         vec![Point3::new(-1.0, -1.0, 2.0), Point3::new(-1.0, 0.0, 0.0), Point3::new(-1.0, 1.0, 2.0)],
+///         vec![Point3::new(0.0, -1.0, 0.0), Point3::new(0.0, 0.0, -2.0), Point3::new(0.0, 1.0, 0.0)],
+///         vec![Point3::new(1.0, -1.0, 2.0), Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 2.0)],
+///     ],
+/// );
+/// let range = (Interval::new(0.0, 1.0), Interval::new(0.0, 1.0));
+/// let plane = |z: f64| {
+///     Plane::new(Point3::new(0.0, 0.0, z), Point3::new(1.0, 0.0, z), Point3::new(0.0, 1.0, z))
+/// };
+/// assert!(separated_from_plane(&surface, range, &plane(-1.0e-4), 12));
+/// // the tangency can not be certified.
+/// assert!(!separated_from_plane(&surface, range, &plane(0.0), 12));
+/// ```
+pub fn separated_from_plane<S: SurfaceEnclosure>(
+    surface: &S,
+    range: (Interval, Interval),
+    plane: &Plane,
+    depth: usize,
+) -> bool {
+    let (origin, normal) = (plane.origin(), plane.normal());
+    let side = |range: (Interval, Interval)| {
+        let bdd = surface.enclosure(range.0, range.1)?;
+        if bdd.is_empty() {
+            return Some(0.0);
+        }
+        let coords = to_intervals(&bdd);
+        let distance = (0..3).fold(Interval::point(0.0), |sum, i| {
+            sum + (coords[i] - origin[i]) * normal[i]
+        });
+        match (distance.is_positive(), distance.is_negative()) {
+            (true, _) => Some(1.0),
+            (_, true) => Some(-1.0),
+            _ => None,
+        }
+    };
+    fn sub<F: Fn((Interval, Interval)) -> Option<f64>>(
+        side: &F,
+        range: (Interval, Interval),
+        depth: usize,
+    ) -> Option<f64> {
+        if let Some(sign) = side(range) {
+            return Some(sign);
+        }
+        if depth == 0 {
+            return None;
+        }
+        let ((u0, u1), (v0, v1)) = (range.0.bisect(), range.1.bisect());
+        [(u0, v0), (u0, v1), (u1, v0), (u1, v1)]
+            .into_iter()
+            .try_fold(0.0, |sign, range| {
+                let s = sub(side, range, depth - 1)?;
+                match sign * s < 0.0 {
+                    true => None,
+                    false => Some(sign + s),
+                }
+            })
+    }
+    sub(&side, range, depth).is_some()
+}
+
+/// Certifies that all points of `surface` on the parameter rectangle `range` are farther than
+/// `distance` from `point`, by bisecting the rectangle at most `depth` times.
+///
+/// The classifications of points by rays or winding numbers are reliable only for the points
+/// certified to be off the boundary.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+/// use truck_geometry::interval::*;
+///
+/// let plane = Plane::new(Point3::origin(), Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0));
+/// let range = (Interval::new(0.0, 1.0), Interval::new(0.0, 1.0));
+/// assert!(point_separated(&plane, range, Point3::new(0.5, 0.5, 0.1), 0.05, 0));
+/// assert!(!point_separated(&plane, range, Point3::new(0.5, 0.5, 0.01), 0.05, 8));
+/// ```
+pub fn point_separated<S: SurfaceEnclosure>(
+    surface: &S,
+    range: (Interval, Interval),
+    point: Point3,
+    distance: f64,
+    depth: usize,
+) -> bool {
+    let bdd = match surface.enclosure(range.0, range.1) {
+        Some(bdd) => bdd,
+        None => return false,
+    };
+    if bdd.is_empty() || box_distance(&bdd, point) > distance {
+        return true;
+    }
+    if depth == 0 {
+        return false;
+    }
+    let ((u0, u1), (v0, v1)) = (range.0.bisect(), range.1.bisect());
+    [(u0, v0), (u0, v1), (u1, v0), (u1, v1)]
+        .into_iter()
+        .all(|range| point_separated(surface, range, point, distance, depth - 1))
+}
+
+#[test]
+fn enclosure_random_test() {
+    use rand::random;
+    let interval = |range: (f64, f64)| {
+        let (s, t) = (random::<f64>(), random::<f64>());
+        let (s, t) = (f64::min(s, t), f64::max(s, t));
+        let lerp = |x: f64| range.0 + (range.1 - range.0) * x;
+        Interval::new(lerp(s), lerp(t))
+    };
+    let sample = |t: Interval| t.lo() + t.width() * random::<f64>();
+    let ctrl_pts = (0..4)
+        .map(|i| {
+            (0..3)
+                .map(|j| {
+                    let w = 0.5 + random::<f64>();
+                    Vector4::new(i as f64, j as f64, random::<f64>(), 1.0) * w
+                })
+                .collect()
+        })
+        .collect();
+    let surface = NURBSSurface::new(BSplineSurface::new(
+        (KnotVec::uniform_knot(2, 2), KnotVec::bezier_knot(2)),
+        ctrl_pts,
+    ));
+    let curve = NURBSCurve::new(BSplineCurve::new(
+        KnotVec::uniform_knot(2, 3),
+        (0..5)
+            .map(|i| Vector4::new(1.0 + random::<f64>(), i as f64, random::<f64>(), 1.0))
+            .collect(),
+    ));
+    let revolution = RevolutedCurve::by_revolution(curve, Point3::origin(), Vector3::unit_y());
+    for _ in 0..100 {
+        let (urange, vrange) = surface.parameter_range();
+        let (u, v) = (interval(urange), interval(vrange));
+        let bdd = surface.enclosure(u, v).unwrap();
+        let p = surface.subs(sample(u), sample(v));
+        assert!(box_distance(&bdd, p) == 0.0, "{bdd:?} {p:?}");
+
+        let (urange, vrange) = revolution.parameter_range();
+        let (u, v) = (interval(urange), interval(vrange));
+        let bdd = revolution.enclosure(u, v).unwrap();
+        let p = revolution.subs(sample(u), sample(v));
+        assert!(box_distance(&bdd, p) == 0.0, "{bdd:?} {p:?}");
+    }
+}
@@ -25,6 +25,7 @@ const PRESEARCH_DIVISION: usize = 50;
 pub mod base {
     pub use truck_base::bounding_box::*;
     pub use truck_base::cgmath64::*;
+    pub use truck_base::deep_size::DeepSize;
     pub use truck_base::tolerance::*;
     pub use truck_base::{assert_near, assert_near2};
     pub use truck_base::{hash, hash::HashGen};
@@ -46,3 +47,7 @@ pub use specifieds::*;
 /// Declares some decorators
 pub mod decorators;
 pub use decorators::*;
+
+/// Interval arithmetic and certified enclosures of curves and surfaces.
+#[cfg(feature = "interval")]
+pub mod interval;
@@ -133,3 +133,59 @@ pub fn inv_or_zero(delta: f64) -> f64 {
         1.0 / delta
     }
 }
+
+impl DeepSize for KnotVec {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.0.heap_size() }
+}
+
+impl<P: DeepSize> DeepSize for BSplineCurve<P> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.knot_vec.heap_size() + self.control_points.heap_size() }
+}
+
+impl<V: DeepSize> DeepSize for BSplineSurface<V> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.knot_vecs.heap_size() + self.control_points.heap_size() }
+}
+
+impl<V: DeepSize> DeepSize for NURBSCurve<V> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.0.heap_size() }
+}
+
+impl<V: DeepSize> DeepSize for NURBSSurface<V> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.0.heap_size() }
+}
+
+impl Quantize for KnotVec {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) { self.0.quantize(tol, key) }
+}
+
+impl<P: Quantize> Quantize for BSplineCurve<P> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.knot_vec.quantize(tol, key);
+        self.control_points.quantize(tol, key);
+    }
+}
+
+impl<V: Quantize> Quantize for BSplineSurface<V> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.knot_vecs.quantize(tol, key);
+        self.control_points.quantize(tol, key);
+    }
+}
+
+impl<V: Quantize> Quantize for NURBSCurve<V> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) { self.0.quantize(tol, key) }
+}
+
+impl<V: Quantize> Quantize for NURBSSurface<V> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) { self.0.quantize(tol, key) }
+}
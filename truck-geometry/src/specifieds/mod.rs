@@ -104,3 +104,57 @@ macro_rules! always_true {
 always_true!(UnitCircle);
 always_true!(UnitParabola);
 always_true!(UnitHyperbola);
+
+truck_base::impl_deep_size_without_heap!(Plane, Sphere);
+
+impl<P: DeepSize> DeepSize for Line<P> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.0.heap_size() + self.1.heap_size() }
+}
+
+macro_rules! unit_deep_size {
+    ($($ty: tt),*) => {
+        $(impl<P> DeepSize for $ty<P> {
+            #[inline(always)]
+            fn heap_size(&self) -> usize { 0 }
+        })*
+    };
+}
+
+unit_deep_size!(UnitCircle, UnitParabola, UnitHyperbola);
+
+impl<P: Quantize> Quantize for Line<P> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.0.quantize(tol, key);
+        self.1.quantize(tol, key);
+    }
+}
+
+macro_rules! unit_quantize {
+    ($($ty: tt),*) => {
+        $(impl<P> Quantize for $ty<P> {
+            #[inline(always)]
+            fn quantize(&self, _: f64, _: &mut QuantizedKey) {}
+        })*
+    };
+}
+
+unit_quantize!(UnitCircle, UnitParabola, UnitHyperbola);
+
+impl Quantize for Plane {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.o.quantize(tol, key);
+        self.p.quantize(tol, key);
+        self.q.quantize(tol, key);
+    }
+}
+
+impl Quantize for Sphere {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.center.quantize(tol, key);
+        self.radius.quantize(tol, key);
+    }
+}
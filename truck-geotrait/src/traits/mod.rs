@@ -6,6 +6,8 @@ mod surface;
 pub use surface::*;
 mod search_parameter;
 pub use search_parameter::*;
+mod quantize;
+pub use quantize::*;
 
 /// Oriented and reversible
 pub trait Invertible: Clone {
@@ -0,0 +1,114 @@
+use super::*;
+
+/// The data of a geometry quantized by a tolerance, the key of the deduplication.
+///
+/// The keys can be compared and hashed exactly, and so can be the keys of the hash maps.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QuantizedKey(Vec<i64>);
+
+impl QuantizedKey {
+    /// Pushes the value rounded to the multiple of `tol`.
+    #[inline(always)]
+    pub fn push(&mut self, value: f64, tol: f64) { self.0.push(f64::round(value / tol) as i64) }
+    /// Pushes the tag identifying the type, the variant, or the size of the following data.
+    #[inline(always)]
+    pub fn push_tag(&mut self, tag: usize) { self.0.push(tag as i64) }
+}
+
+/// Tolerance-aware identification of geometries.
+///
+/// The geometries with the same [`QuantizedKey`]s are regarded as identical. The key consists of
+/// the coordinates of the control data rounded to the multiples of the tolerance, and so the
+/// geometries nearly coincident within the tolerance have the same keys except in the cases
+/// their coordinates are near the halfway of the multiples.
+/// # Examples
+/// ```
+/// use truck_geotrait::*;
+/// use truck_base::cgmath64::*;
+///
+/// let p = Point3::new(0.0, 1.0, 2.0);
+/// let q = Point3::new(1.0e-8, 1.0, 2.0);
+/// let r = Point3::new(1.0e-3, 1.0, 2.0);
+/// assert_eq!(p.quantized_key(1.0e-6), q.quantized_key(1.0e-6));
+/// assert_ne!(p.quantized_key(1.0e-6), r.quantized_key(1.0e-6));
+/// ```
+pub trait Quantize {
+    /// Pushes the quantized data of `self` into `key`.
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey);
+    /// Returns the quantized key of `self`.
+    #[inline(always)]
+    fn quantized_key(&self, tol: f64) -> QuantizedKey {
+        let mut key = QuantizedKey::default();
+        self.quantize(tol, &mut key);
+        key
+    }
+}
+
+impl Quantize for f64 {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) { key.push(*self, tol) }
+}
+
+impl Quantize for bool {
+    #[inline(always)]
+    fn quantize(&self, _: f64, key: &mut QuantizedKey) { key.push_tag(*self as usize) }
+}
+
+macro_rules! impl_quantize_vector {
+    ($($type: ident, $dim: expr),*) => {
+        $(impl Quantize for $type {
+            #[inline(always)]
+            fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+                (0..$dim).for_each(|i| key.push(self[i], tol))
+            }
+        })*
+    };
+}
+
+impl_quantize_vector!(
+    Vector1, 1, Vector2, 2, Vector3, 3, Vector4, 4, Point1, 1, Point2, 2, Point3, 3
+);
+
+macro_rules! impl_quantize_matrix {
+    ($($type: ident, $dim: expr),*) => {
+        $(impl Quantize for $type {
+            #[inline(always)]
+            fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+                (0..$dim).for_each(|i| self[i].quantize(tol, key))
+            }
+        })*
+    };
+}
+
+impl_quantize_matrix!(Matrix2, 2, Matrix3, 3, Matrix4, 4);
+
+impl<T: Quantize> Quantize for [T] {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        key.push_tag(self.len());
+        self.iter().for_each(|x| x.quantize(tol, key))
+    }
+}
+
+impl<T: Quantize> Quantize for Vec<T> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) { self.as_slice().quantize(tol, key) }
+}
+
+impl<T: Quantize + ?Sized> Quantize for Box<T> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) { (**self).quantize(tol, key) }
+}
+
+impl<T: Quantize + ?Sized> Quantize for &T {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) { (**self).quantize(tol, key) }
+}
+
+impl<S: Quantize, T: Quantize> Quantize for (S, T) {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        self.0.quantize(tol, key);
+        self.1.quantize(tol, key);
+    }
+}
@@ -0,0 +1,267 @@
+use super::distance::TriangleTree;
+use super::*;
+use std::f64::consts::PI;
+
+/// The options of baking the ambient occlusion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AmbientOcclusionOptions {
+    /// the number of the rays cast from each sample point. Default is `64`.
+    pub samples: usize,
+    /// the rays hitting the mesh farther than this distance are not occluded.
+    /// Default is infinity.
+    pub max_distance: f64,
+    /// the distance by which the origins of the rays are moved along the normals
+    /// to avoid the self-intersection. Default is `TOLERANCE`.
+    pub bias: f64,
+}
+
+impl Default for AmbientOcclusionOptions {
+    fn default() -> Self {
+        Self {
+            samples: 64,
+            max_distance: f64::INFINITY,
+            bias: TOLERANCE,
+        }
+    }
+}
+
+/// Baking the ambient occlusion on the CPU.
+///
+/// # Details
+/// The occlusion at a point is the cosine-weighted ratio of the rays, cast in the hemisphere
+/// around the normal, which hit the mesh within [`AmbientOcclusionOptions::max_distance`].
+/// The occlusion is `0.0` at the open points and `1.0` at the fully covered points,
+/// so `1.0 - occlusion` can be multiplied to the colors as the contact shadows.
+/// The directions of the rays are the same at all points, and the results are deterministic.
+/// The polygons are divided into triangles by [`Faces::triangle_iter`], and the triangles are
+/// stored in a bounding volume hierarchy.
+pub trait AmbientOcclusion {
+    /// Returns the occlusions at the positions, i.e. `result[i]` is the occlusion at
+    /// `self.positions()[i]`.
+    ///
+    /// The normals are the area-weighted averages of the normals of the adjacent triangles.
+    /// The occlusions of the positions not used by any faces are `0.0`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the floor and the wall
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 1.0),
+    ///             Point3::new(1.0, 1.0, 1.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[[0, 1, 2, 3], [3, 2, 5, 4]]),
+    /// );
+    /// let occlusion = mesh.vertex_occlusion(&Default::default());
+    /// // the front of the floor is partially covered by the wall.
+    /// assert!(0.0 < occlusion[0] && occlusion[0] < 0.5);
+    /// // the corner between the floor and the wall is darker.
+    /// assert!(occlusion[3] > occlusion[0]);
+    /// assert!(occlusion[3] > occlusion[4]);
+    /// ```
+    fn vertex_occlusion(&self, options: &AmbientOcclusionOptions) -> Vec<f64>;
+    /// Returns the occlusion texture of `width` x `height` texels in the row-major order.
+    ///
+    /// The texel `(i, j)` is at `result[j * width + i]` and has the center
+    /// `(u, v) = ((i + 0.5) / width, (j + 0.5) / height)` in the texture coordinates,
+    /// so the first row is at `v = 0` as in the textures of `truck-rendimpl`.
+    /// The occlusions are sampled at the points of the triangles including the centers
+    /// in the uv-space, with the normals of the triangles.
+    /// The occlusions of the texels not covered by any triangles are `0.0`,
+    /// and the texture is all zero if the mesh has no texture coordinates.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the floor and the wall, mapped to the lower and the upper halves of the texture
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 1.0),
+    ///             Point3::new(1.0, 1.0, 1.0),
+    ///         ],
+    ///         uv_coords: vec![
+    ///             Vector2::new(0.0, 0.0),
+    ///             Vector2::new(1.0, 0.0),
+    ///             Vector2::new(1.0, 0.5),
+    ///             Vector2::new(0.0, 0.5),
+    ///             Vector2::new(0.0, 1.0),
+    ///             Vector2::new(1.0, 1.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[
+    ///         [(0, Some(0), None), (1, Some(1), None), (2, Some(2), None), (3, Some(3), None)],
+    ///         [(3, Some(3), None), (2, Some(2), None), (5, Some(5), None), (4, Some(4), None)],
+    ///     ]),
+    /// );
+    /// let texture = mesh.occlusion_texture(4, 8, &Default::default());
+    /// assert_eq!(texture.len(), 32);
+    /// // the texels near the corner are darker.
+    /// assert!(texture[3 * 4] > texture[0]);
+    /// assert!(texture[4 * 4] > texture[7 * 4]);
+    /// ```
+    fn occlusion_texture(
+        &self,
+        width: usize,
+        height: usize,
+        options: &AmbientOcclusionOptions,
+    ) -> Vec<f64>;
+}
+
+impl AmbientOcclusion for PolygonMesh {
+    fn vertex_occlusion(&self, options: &AmbientOcclusionOptions) -> Vec<f64> {
+        let positions = self.positions();
+        let mut normals = vec![Vector3::zero(); positions.len()];
+        self.faces().triangle_iter().for_each(|tri| {
+            let [p0, p1, p2] = tri.map(|v| positions[v.pos]);
+            // the length is twice the area.
+            let normal = (p1 - p0).cross(p2 - p0);
+            tri.iter().for_each(|v| normals[v.pos] += normal);
+        });
+        let sampler = OcclusionSampler::new(self, options);
+        positions
+            .iter()
+            .zip(normals)
+            .map(|(p, n)| match n.so_small() {
+                true => 0.0,
+                false => sampler.occlusion(*p, n.normalize()),
+            })
+            .collect()
+    }
+
+    fn occlusion_texture(
+        &self,
+        width: usize,
+        height: usize,
+        options: &AmbientOcclusionOptions,
+    ) -> Vec<f64> {
+        let mut texture = vec![0.0; width * height];
+        let (positions, uv_coords) = (self.positions(), self.uv_coords());
+        let sampler = OcclusionSampler::new(self, options);
+        self.faces().triangle_iter().for_each(|tri| {
+            let uv = match (tri[0].uv, tri[1].uv, tri[2].uv) {
+                (Some(a), Some(b), Some(c)) => [uv_coords[a], uv_coords[b], uv_coords[c]],
+                _ => return,
+            };
+            let [p0, p1, p2] = tri.map(|v| positions[v.pos]);
+            let normal = (p1 - p0).cross(p2 - p0);
+            if normal.so_small() {
+                return;
+            }
+            let normal = normal.normalize();
+            rasterize(uv, width, height, |i, j, [w0, w1, w2]| {
+                let point =
+                    Point3::from_vec(p0.to_vec() * w0 + p1.to_vec() * w1 + p2.to_vec() * w2);
+                texture[j * width + i] = sampler.occlusion(point, normal);
+            });
+        });
+        texture
+    }
+}
+
+/// the bounding volume hierarchy and the cosine-weighted directions in the local frame
+struct OcclusionSampler {
+    tree: TriangleTree,
+    directions: Vec<Vector3>,
+    options: AmbientOcclusionOptions,
+}
+
+impl OcclusionSampler {
+    fn new(mesh: &PolygonMesh, options: &AmbientOcclusionOptions) -> Self {
+        // Hammersley points mapped to the cosine-weighted hemisphere
+        let n = options.samples;
+        let directions = (0..n)
+            .map(|i| {
+                let (s, t) = ((i as f64 + 0.5) / n as f64, radical_inverse(i as u32));
+                let (r, phi) = (f64::sqrt(s), 2.0 * PI * t);
+                Vector3::new(r * f64::cos(phi), r * f64::sin(phi), f64::sqrt(1.0 - s))
+            })
+            .collect();
+        Self {
+            tree: TriangleTree::new(mesh),
+            directions,
+            options: *options,
+        }
+    }
+
+    fn occlusion(&self, point: Point3, normal: Vector3) -> f64 {
+        if self.directions.is_empty() {
+            return 0.0;
+        }
+        let tangent = match normal.x.abs() < 0.5 {
+            true => Vector3::unit_x().cross(normal).normalize(),
+            false => Vector3::unit_y().cross(normal).normalize(),
+        };
+        let bitangent = normal.cross(tangent);
+        let origin = point + normal * self.options.bias;
+        let hits = self
+            .directions
+            .iter()
+            .filter(|d| {
+                let dir = tangent * d.x + bitangent * d.y + normal * d.z;
+                self.tree.ray_hit(origin, dir, self.options.max_distance)
+            })
+            .count();
+        hits as f64 / self.directions.len() as f64
+    }
+}
+
+/// the van der Corput sequence in base 2
+fn radical_inverse(i: u32) -> f64 { i.reverse_bits() as f64 / (u32::MAX as f64 + 1.0) }
+
+/// Calls `f(i, j, barycentric)` for the texels whose centers are in the triangle `uv`.
+fn rasterize(
+    uv: [Vector2; 3],
+    width: usize,
+    height: usize,
+    mut f: impl FnMut(usize, usize, [f64; 3]),
+) {
+    let [a, b, c] = uv;
+    let det = (b - a).perp_dot(c - a);
+    if det.so_small2() {
+        return;
+    }
+    let (min, max) = uv.iter().fold(
+        (
+            Vector2::new(f64::INFINITY, f64::INFINITY),
+            Vector2::new(f64::NEG_INFINITY, f64::NEG_INFINITY),
+        ),
+        |(min, max), p| {
+            (
+                Vector2::new(f64::min(min.x, p.x), f64::min(min.y, p.y)),
+                Vector2::new(f64::max(max.x, p.x), f64::max(max.y, p.y)),
+            )
+        },
+    );
+    let range = |min: f64, max: f64, len: usize| {
+        let start = f64::max(f64::floor(min * len as f64 - 0.5), 0.0) as usize;
+        let end = f64::min(f64::ceil(max * len as f64 - 0.5) + 1.0, len as f64);
+        start..f64::max(end, 0.0) as usize
+    };
+    range(min.y, max.y, height).for_each(|j| {
+        range(min.x, max.x, width).for_each(|i| {
+            let p = Vector2::new(
+                (i as f64 + 0.5) / width as f64,
+                (j as f64 + 0.5) / height as f64,
+            );
+            let w1 = (p - a).perp_dot(c - a) / det;
+            let w2 = (b - a).perp_dot(p - a) / det;
+            let w0 = 1.0 - w1 - w2;
+            let eps = -1.0e-9;
+            if w0 >= eps && w1 >= eps && w2 >= eps {
+                f(i, j, [w0, w1, w2]);
+            }
+        })
+    });
+}
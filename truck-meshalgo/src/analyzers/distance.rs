@@ -0,0 +1,290 @@
+use super::point_cloud::distance2_point_triangle;
+use super::*;
+
+/// Distances between two polygon meshes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshDistance {
+    /// the maximum distance from the samples of `self` to the other mesh
+    pub forward: f64,
+    /// the maximum distance from the samples of the other mesh to `self`
+    pub backward: f64,
+    /// the symmetric Hausdorff distance, i.e. the maximum of `forward` and `backward`
+    pub hausdorff: f64,
+    /// the root mean square of the distances of the samples of both meshes
+    pub rms: f64,
+}
+
+/// Measures distances from points and polygon meshes.
+///
+/// # Details
+/// The distances are measured from the sample points, that is, the vertices and the
+/// centroids of the triangles, to the triangles of the other mesh. The polygons are divided
+/// into triangles by [`Faces::triangle_iter`], and the triangles are stored in a bounding
+/// volume hierarchy. The distance to the mesh without faces is infinity.
+pub trait MeshDistanceAnalysis {
+    /// Returns the distance from `point` to the mesh.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2, 3]]),
+    /// );
+    /// assert!(mesh.distance_to_point(Point3::new(0.5, 0.5, 2.0)).near(&2.0));
+    /// assert!(mesh.distance_to_point(Point3::new(4.0, 0.5, 4.0)).near(&5.0));
+    /// ```
+    fn distance_to_point(&self, point: Point3) -> f64;
+    /// Returns the one-sided and symmetric Hausdorff distances and the RMS distance
+    /// between `self` and `other`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let square = |size: f64, z: f64| {
+    ///     PolygonMesh::new(
+    ///         StandardAttributes {
+    ///             positions: vec![
+    ///                 Point3::new(0.0, 0.0, z),
+    ///                 Point3::new(size, 0.0, z),
+    ///                 Point3::new(size, size, z),
+    ///                 Point3::new(0.0, size, z),
+    ///             ],
+    ///             ..Default::default()
+    ///         },
+    ///         Faces::from_iter(&[&[0, 1, 2, 3]]),
+    ///     )
+    /// };
+    /// let dist = square(1.0, 0.0).distance_to(&square(1.0, 0.1));
+    /// assert!(dist.hausdorff.near(&0.1));
+    /// assert!(dist.rms.near(&0.1));
+    ///
+    /// // the distances from the larger mesh is longer.
+    /// let dist = square(1.0, 0.0).distance_to(&square(2.0, 0.0));
+    /// assert!(dist.forward.so_small());
+    /// assert!(dist.backward.near(&f64::sqrt(2.0)));
+    /// assert_eq!(dist.hausdorff, dist.backward);
+    /// ```
+    fn distance_to(&self, other: &PolygonMesh) -> MeshDistance;
+}
+
+impl MeshDistanceAnalysis for PolygonMesh {
+    fn distance_to_point(&self, point: Point3) -> f64 {
+        TriangleTree::new(self).distance2(point).sqrt()
+    }
+
+    fn distance_to(&self, other: &PolygonMesh) -> MeshDistance {
+        let (tree0, tree1) = (TriangleTree::new(self), TriangleTree::new(other));
+        let (forward, sum0, len0) = one_sided_distance(&tree0, &tree1);
+        let (backward, sum1, len1) = one_sided_distance(&tree1, &tree0);
+        let rms = match len0 + len1 {
+            0 => 0.0,
+            len => f64::sqrt((sum0 + sum1) / len as f64),
+        };
+        MeshDistance {
+            forward,
+            backward,
+            hausdorff: f64::max(forward, backward),
+            rms,
+        }
+    }
+}
+
+/// the maximum distance, the sum of the squared distances, and the number of samples
+fn one_sided_distance(from: &TriangleTree, to: &TriangleTree) -> (f64, f64, usize) {
+    let samples = from.triangles.iter().flat_map(|tri| {
+        let centroid = Point3::centroid(tri);
+        tri.iter().copied().chain(std::iter::once(centroid))
+    });
+    let (max, sum, len) = samples.fold((0.0_f64, 0.0, 0), |(max, sum, len), pt| {
+        let dist2 = to.distance2(pt);
+        (max.max(dist2), sum + dist2, len + 1)
+    });
+    (max.sqrt(), sum, len)
+}
+
+/// bounding volume hierarchy of triangles
+#[derive(Clone, Debug)]
+pub(super) struct TriangleTree {
+    pub(super) triangles: Vec<[Point3; 3]>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    bdd_box: BoundingBox<Point3>,
+    /// the children or the range of the triangles
+    content: NodeContent,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NodeContent {
+    Inner(usize, usize),
+    Leaf(usize, usize),
+}
+
+const LEAF_SIZE: usize = 4;
+
+impl TriangleTree {
+    pub(super) fn new(mesh: &PolygonMesh) -> Self {
+        let positions = mesh.positions();
+        let triangles = mesh
+            .faces()
+            .triangle_iter()
+            .map(|tri| tri.map(|v| positions[v.pos]))
+            .collect::<Vec<_>>();
+        let mut tree = TriangleTree {
+            triangles,
+            nodes: Vec::new(),
+        };
+        if !tree.triangles.is_empty() {
+            tree.build_node(0, tree.triangles.len());
+        }
+        tree
+    }
+
+    /// Builds the node of the triangles in `start..end` and returns its index.
+    fn build_node(&mut self, start: usize, end: usize) -> usize {
+        let bdd_box: BoundingBox<Point3> = self.triangles[start..end].iter().flatten().collect();
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            bdd_box: bdd_box.clone(),
+            content: NodeContent::Leaf(start, end),
+        });
+        if end - start > LEAF_SIZE {
+            let diagonal = bdd_box.diagonal();
+            let axis = (0..3)
+                .max_by(|i, j| diagonal[*i].partial_cmp(&diagonal[*j]).unwrap())
+                .unwrap();
+            let mid = (start + end) / 2;
+            self.triangles[start..end].select_nth_unstable_by(mid - start, |a, b| {
+                let (a, b) = (
+                    a[0][axis] + a[1][axis] + a[2][axis],
+                    b[0][axis] + b[1][axis] + b[2][axis],
+                );
+                a.partial_cmp(&b).unwrap()
+            });
+            let left = self.build_node(start, mid);
+            let right = self.build_node(mid, end);
+            self.nodes[idx].content = NodeContent::Inner(left, right);
+        }
+        idx
+    }
+
+    /// the squared distance from `point` to the nearest triangle
+    pub(super) fn distance2(&self, point: Point3) -> f64 {
+        let mut res = f64::INFINITY;
+        if self.nodes.is_empty() {
+            return res;
+        }
+        let mut stack = vec![(0, box_distance2(&self.nodes[0].bdd_box, point))];
+        while let Some((idx, dist2)) = stack.pop() {
+            if dist2 >= res {
+                continue;
+            }
+            match self.nodes[idx].content {
+                NodeContent::Inner(left, right) => {
+                    let left = (left, box_distance2(&self.nodes[left].bdd_box, point));
+                    let right = (right, box_distance2(&self.nodes[right].bdd_box, point));
+                    // the nearer child is popped first.
+                    match left.1 < right.1 {
+                        true => stack.extend([right, left]),
+                        false => stack.extend([left, right]),
+                    }
+                }
+                NodeContent::Leaf(start, end) => {
+                    self.triangles[start..end].iter().for_each(|tri| {
+                        res = f64::min(res, distance2_point_triangle(point, *tri));
+                    });
+                }
+            }
+        }
+        res
+    }
+
+    /// Returns whether the ray `origin + t * dir` hits a triangle with `t` in `(0, max_t)`.
+    pub(super) fn ray_hit(&self, origin: Point3, dir: Vector3, max_t: f64) -> bool {
+        if self.nodes.is_empty() {
+            return false;
+        }
+        let mut stack = vec![0];
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            if !ray_hits_box(&node.bdd_box, origin, dir, max_t) {
+                continue;
+            }
+            match node.content {
+                NodeContent::Inner(left, right) => stack.extend([left, right]),
+                NodeContent::Leaf(start, end) => {
+                    let hit = self.triangles[start..end]
+                        .iter()
+                        .any(|tri| ray_triangle(origin, dir, *tri).is_some_and(|t| t < max_t));
+                    if hit {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+/// slab test of the ray `origin + t * dir` with `t` in `[0, max_t]`
+fn ray_hits_box(bdd_box: &BoundingBox<Point3>, origin: Point3, dir: Vector3, max_t: f64) -> bool {
+    let (min, max) = (bdd_box.min(), bdd_box.max());
+    let (mut t0, mut t1) = (0.0_f64, max_t);
+    for i in 0..3 {
+        let inv = 1.0 / dir[i];
+        let (a, b) = ((min[i] - origin[i]) * inv, (max[i] - origin[i]) * inv);
+        let (a, b) = if a < b { (a, b) } else { (b, a) };
+        // NaN occurs only if the ray is on the plane of a face of the box.
+        t0 = if a > t0 { a } else { t0 };
+        t1 = if b < t1 { b } else { t1 };
+        if t0 > t1 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Möller-Trumbore intersection: returns the positive parameter of the hit point.
+fn ray_triangle(origin: Point3, dir: Vector3, [p0, p1, p2]: [Point3; 3]) -> Option<f64> {
+    let (e1, e2) = (p1 - p0, p2 - p0);
+    let p = dir.cross(e2);
+    let det = e1.dot(p);
+    if det.abs() < f64::EPSILON {
+        return None;
+    }
+    let s = origin - p0;
+    let u = s.dot(p) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(e1);
+    let v = dir.dot(q) / det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = e2.dot(q) / det;
+    match t > 0.0 {
+        true => Some(t),
+        false => None,
+    }
+}
+
+/// the squared distance from `point` to the box
+fn box_distance2(bdd_box: &BoundingBox<Point3>, point: Point3) -> f64 {
+    let (min, max) = (bdd_box.min(), bdd_box.max());
+    (0..3)
+        .map(|i| {
+            let d = f64::max(f64::max(min[i] - point[i], point[i] - max[i]), 0.0);
+            d * d
+        })
+        .sum()
+}
@@ -0,0 +1,340 @@
+use super::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Geodesic distances on polygon meshes.
+///
+/// # Details
+///
+/// The distances are computed by the fast marching method on the triangulation of the mesh.
+/// Each vertex is updated from the triangles whose other vertices are already fixed, by the
+/// virtual source unfolded on the plane of the triangle. If the straight line from the virtual
+/// source does not pass through the triangle, the distance along the edges is adopted.
+/// The paths are traced backward from the target by the steepest descent of the distance field.
+pub trait Geodesic {
+    /// Returns the geodesic distances from the position `source` to all positions.
+    ///
+    /// The distances of the positions unreachable from `source` are `f64::INFINITY`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    ///
+    /// // the 3 x 3 planar grid on [0, 3]^2
+    /// let positions = (0..16)
+    ///     .map(|i| Point3::new((i % 4) as f64, (i / 4) as f64, 0.0))
+    ///     .collect();
+    /// let faces = (0..9)
+    ///     .map(|i| i + i / 3)
+    ///     .map(|i| [i, i + 1, i + 5, i + 4])
+    ///     .collect();
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions,
+    ///         ..Default::default()
+    ///     },
+    ///     faces,
+    /// );
+    ///
+    /// let distances = mesh.geodesic_distances(0);
+    /// assert!(distances[3].near(&3.0));
+    /// assert!(distances[15].near(&(3.0 * f64::sqrt(2.0))));
+    /// ```
+    fn geodesic_distances(&self, source: usize) -> Vec<f64>;
+    /// Traces the geodesic path from the source of `distances` to the position `target`,
+    /// where `distances` is the output of [`Geodesic::geodesic_distances`].
+    ///
+    /// Returns `None` if `target` is unreachable from the source.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    ///
+    /// let positions = (0..16)
+    ///     .map(|i| Point3::new((i % 4) as f64, (i / 4) as f64, 0.0))
+    ///     .collect();
+    /// let faces = (0..9)
+    ///     .map(|i| i + i / 3)
+    ///     .map(|i| [i, i + 1, i + 5, i + 4])
+    ///     .collect();
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions,
+    ///         ..Default::default()
+    ///     },
+    ///     faces,
+    /// );
+    ///
+    /// let distances = mesh.geodesic_distances(1);
+    /// let path = mesh.geodesic_path(&distances, 14).unwrap();
+    /// assert!(path[0].near(&Point3::new(1.0, 0.0, 0.0)));
+    /// assert!(path[path.len() - 1].near(&Point3::new(2.0, 3.0, 0.0)));
+    /// // the path is nearly the straight line segment.
+    /// let length: f64 = path.windows(2).map(|p| p[0].distance(p[1])).sum();
+    /// assert!(f64::abs(length - f64::sqrt(10.0)) < 0.05);
+    /// ```
+    fn geodesic_path(&self, distances: &[f64], target: usize) -> Option<Vec<Point3>>;
+}
+
+impl Geodesic for PolygonMesh {
+    fn geodesic_distances(&self, source: usize) -> Vec<f64> {
+        let domain = Domain::new(self);
+        domain.fast_marching(source)
+    }
+    fn geodesic_path(&self, distances: &[f64], target: usize) -> Option<Vec<Point3>> {
+        let domain = Domain::new(self);
+        domain.trace(distances, target)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Trial {
+    distance: f64,
+    index: usize,
+}
+
+impl Eq for Trial {}
+
+impl Ord for Trial {
+    // reversed for the min-heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Trial {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+/// a point on the mesh
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Location {
+    Vertex(usize),
+    /// the point `(1 - t) * p[a] + t * p[b]`
+    Edge(usize, usize, f64),
+}
+
+#[derive(Clone, Debug)]
+struct Domain<'a> {
+    positions: &'a [Point3],
+    triangles: Vec<[usize; 3]>,
+    vertex_triangles: Vec<Vec<usize>>,
+}
+
+impl<'a> Domain<'a> {
+    fn new(mesh: &'a PolygonMesh) -> Self {
+        let positions = mesh.positions().as_slice();
+        let triangles: Vec<[usize; 3]> = mesh
+            .faces()
+            .triangle_iter()
+            .map(|tri| [tri[0].pos, tri[1].pos, tri[2].pos])
+            .collect();
+        let mut vertex_triangles = vec![Vec::new(); positions.len()];
+        triangles.iter().enumerate().for_each(|(i, tri)| {
+            tri.iter().for_each(|v| vertex_triangles[*v].push(i));
+        });
+        Self {
+            positions,
+            triangles,
+            vertex_triangles,
+        }
+    }
+
+    fn fast_marching(&self, source: usize) -> Vec<f64> {
+        let len = self.positions.len();
+        let mut distances = vec![f64::INFINITY; len];
+        let mut fixed = vec![false; len];
+        if source >= len {
+            return distances;
+        }
+        distances[source] = 0.0;
+        let mut heap = BinaryHeap::new();
+        heap.push(Trial {
+            distance: 0.0,
+            index: source,
+        });
+        while let Some(Trial { index, .. }) = heap.pop() {
+            if fixed[index] {
+                continue;
+            }
+            fixed[index] = true;
+            self.vertex_triangles[index].iter().for_each(|i| {
+                let tri = self.triangles[*i];
+                let k = tri.iter().position(|v| *v == index).unwrap();
+                let (v0, v1) = (tri[(k + 1) % 3], tri[(k + 2) % 3]);
+                [(v0, v1), (v1, v0)].into_iter().for_each(|(w, other)| {
+                    if fixed[w] {
+                        return;
+                    }
+                    let mut distance =
+                        distances[index] + self.positions[index].distance(self.positions[w]);
+                    if fixed[other] {
+                        if let Some(d) = self.unfolded_distance(
+                            w,
+                            (index, distances[index]),
+                            (other, distances[other]),
+                        ) {
+                            distance = f64::min(distance, d);
+                        }
+                    }
+                    if distance < distances[w] {
+                        distances[w] = distance;
+                        heap.push(Trial { distance, index: w });
+                    }
+                });
+            });
+        }
+        distances
+    }
+
+    /// the distance from the virtual source whose distances from `a` and `b` are given.
+    fn unfolded_distance(
+        &self,
+        w: usize,
+        (a, ta): (usize, f64),
+        (b, tb): (usize, f64),
+    ) -> Option<f64> {
+        let (pa, pb, pw) = (self.positions[a], self.positions[b], self.positions[w]);
+        let c = pa.distance(pb);
+        if c.so_small() {
+            return None;
+        }
+        let e = (pb - pa) / c;
+        let (wx, wy) = (
+            (pw - pa).dot(e),
+            ((pw - pa) - (pw - pa).dot(e) * e).magnitude(),
+        );
+        if wy.so_small() {
+            return None;
+        }
+        let sx = (ta * ta - tb * tb + c * c) / (2.0 * c);
+        let sy2 = ta * ta - sx * sx;
+        if sy2 < 0.0 {
+            return None;
+        }
+        let sy = -f64::sqrt(sy2);
+        // the straight line from the source must cross the edge ab.
+        let cross = sx + (wx - sx) * (-sy / (wy - sy));
+        match (0.0..=c).contains(&cross) {
+            true => Some(f64::sqrt((wx - sx) * (wx - sx) + (wy - sy) * (wy - sy))),
+            false => None,
+        }
+    }
+
+    fn point(&self, location: Location) -> Point3 {
+        match location {
+            Location::Vertex(v) => self.positions[v],
+            Location::Edge(a, b, t) => {
+                self.positions[a] + (self.positions[b] - self.positions[a]) * t
+            }
+        }
+    }
+
+    fn value(distances: &[f64], location: Location) -> f64 {
+        match location {
+            Location::Vertex(v) => distances[v],
+            Location::Edge(a, b, t) => distances[a] * (1.0 - t) + distances[b] * t,
+        }
+    }
+
+    /// Returns the next location by the steepest descent in the triangle.
+    fn descend_in_triangle(
+        &self,
+        distances: &[f64],
+        tri: [usize; 3],
+        location: Location,
+    ) -> Option<Location> {
+        let p = tri.map(|v| self.positions[v]);
+        let (e1, e2) = (p[1] - p[0], p[2] - p[0]);
+        let gram = Matrix2::new(e1.dot(e1), e1.dot(e2), e1.dot(e2), e2.dot(e2));
+        let inv = gram.invert()?;
+        let coord = |v: Vector3| inv * Vector2::new(v.dot(e1), v.dot(e2));
+        // the coordinates of the gradient w.r.t. (e1, e2) are `inv * (dT1, dT2)`.
+        let dt = Vector2::new(
+            distances[tri[1]] - distances[tri[0]],
+            distances[tri[2]] - distances[tri[0]],
+        );
+        let mu = -(inv * dt);
+        if !mu.x.is_finite() || !mu.y.is_finite() || mu.so_small() {
+            return None;
+        }
+        let lambda = coord(self.point(location) - p[0]);
+        // exit parameters of the ray `lambda + s * mu` from the triangle
+        let candidates = [
+            (mu.x < 0.0).then(|| -lambda.x / mu.x),
+            (mu.y < 0.0).then(|| -lambda.y / mu.y),
+            (mu.x + mu.y > 0.0).then(|| (1.0 - lambda.x - lambda.y) / (mu.x + mu.y)),
+        ];
+        let s = candidates
+            .into_iter()
+            .flatten()
+            .fold(f64::INFINITY, f64::min);
+        if !s.is_finite() || s < 1.0e-12 {
+            return None;
+        }
+        let q = lambda + mu * s;
+        let bary = [1.0 - q.x - q.y, q.x, q.y];
+        const EPS: f64 = 1.0e-9;
+        if bary.iter().any(|x| *x < -EPS) {
+            return None;
+        }
+        if let Some(k) = (0..3).find(|k| bary[*k] > 1.0 - EPS) {
+            return Some(Location::Vertex(tri[k]));
+        }
+        let k = (0..3).find(|k| bary[*k] < EPS)?;
+        let (a, b) = (tri[(k + 1) % 3], tri[(k + 2) % 3]);
+        let t = bary[(k + 2) % 3] / (bary[(k + 1) % 3] + bary[(k + 2) % 3]);
+        Some(Location::Edge(a, b, t))
+    }
+
+    fn trace(&self, distances: &[f64], target: usize) -> Option<Vec<Point3>> {
+        if !distances.get(target)?.is_finite() {
+            return None;
+        }
+        let mut location = Location::Vertex(target);
+        let mut path = vec![self.positions[target]];
+        for _ in 0..4 * self.positions.len() + 4 {
+            let current = self.point(location);
+            let value = Self::value(distances, location);
+            if let Location::Vertex(v) = location {
+                if distances[v] == 0.0 {
+                    path.reverse();
+                    return Some(path);
+                }
+            }
+            let (triangles, vertices): (Vec<usize>, Vec<usize>) = match location {
+                Location::Vertex(v) => (self.vertex_triangles[v].clone(), Vec::new()),
+                Location::Edge(a, b, _) => {
+                    let triangles = self.vertex_triangles[a]
+                        .iter()
+                        .filter(|i| self.triangles[**i].contains(&b))
+                        .copied()
+                        .collect();
+                    (triangles, vec![a, b])
+                }
+            };
+            let edge_neighbors = triangles
+                .iter()
+                .flat_map(|i| self.triangles[*i])
+                .chain(vertices)
+                .map(Location::Vertex);
+            let next = triangles
+                .iter()
+                .filter_map(|i| self.descend_in_triangle(distances, self.triangles[*i], location))
+                .chain(edge_neighbors)
+                .filter_map(|next| {
+                    let length = self.point(next).distance(current);
+                    let slope = (value - Self::value(distances, next)) / length;
+                    match length > 1.0e-12 && slope > 0.0 {
+                        true => Some((next, slope)),
+                        false => None,
+                    }
+                })
+                .max_by(|(_, s0), (_, s1)| s0.partial_cmp(s1).unwrap_or(Ordering::Equal));
+            location = next?.0;
+            path.push(self.point(location));
+        }
+        None
+    }
+}
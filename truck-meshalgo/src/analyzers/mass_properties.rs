@@ -0,0 +1,155 @@
+use super::*;
+use thiserror::Error;
+use truck_topology::shell::ShellCondition;
+
+/// Mass properties of the solid bounded by a closed polygon mesh with the unit density.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+    /// the signed volume, which is negative if the faces are inward
+    pub volume: f64,
+    /// the area of the surface
+    pub area: f64,
+    /// the center of mass
+    pub centroid: Point3,
+    /// the inertia tensor around the centroid
+    pub inertia: Matrix3,
+}
+
+/// Errors occurred by computing mass properties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum MassPropertiesError {
+    /// The mesh does not bound any solid.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_topology::shell::ShellCondition;
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2]]),
+    /// );
+    /// assert_eq!(
+    ///     mesh.mass_properties(),
+    ///     Err(MassPropertiesError::NotClosed(ShellCondition::Oriented)),
+    /// );
+    /// ```
+    #[error(
+        "The mesh is not closed but {0:?}. Mass properties are defined only for closed meshes."
+    )]
+    NotClosed(ShellCondition),
+}
+
+/// Computes volumes, areas, centroids, and inertia tensors.
+///
+/// # Details
+/// The integrals over the solid are reduced to the ones over the surface by the divergence
+/// theorem, that is, summed over the tetrahedra spanned by the triangles and a fixed point.
+/// The polygons are divided into triangles by [`Faces::triangle_iter`].
+pub trait MassAnalysis {
+    /// Returns the area of the surface. The mesh need not be closed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(2.0, 0.0, 0.0),
+    ///             Point3::new(2.0, 3.0, 0.0),
+    ///             Point3::new(0.0, 3.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2, 3]]),
+    /// );
+    /// assert!(mesh.surface_area().near(&6.0));
+    /// ```
+    fn surface_area(&self) -> f64;
+    /// Returns the mass properties of the solid bounded by the mesh.
+    /// # Errors
+    /// Returns [`MassPropertiesError::NotClosed`] if the shell condition of the mesh
+    /// is not [`ShellCondition::Closed`]. Apply [`OptimizingFilter::put_together_same_attrs`]
+    /// to separated meshes beforehand.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the cube [1, 2] x [1, 2] x [1, 2]
+    /// let positions = (0..8)
+    ///     .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+    ///     .map(|p| p + Vector3::new(1.0, 1.0, 1.0))
+    ///     .collect();
+    /// let faces = Faces::from_iter(&[
+    ///     &[0, 2, 3, 1],
+    ///     &[0, 1, 5, 4],
+    ///     &[1, 3, 7, 5],
+    ///     &[3, 2, 6, 7],
+    ///     &[2, 0, 4, 6],
+    ///     &[4, 5, 7, 6],
+    /// ]);
+    /// let cube = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions,
+    ///         ..Default::default()
+    ///     },
+    ///     faces,
+    /// );
+    ///
+    /// let props = cube.mass_properties().unwrap();
+    /// assert!(props.volume.near(&1.0));
+    /// assert!(props.area.near(&6.0));
+    /// assert!(props.centroid.near(&Point3::new(1.5, 1.5, 1.5)));
+    /// assert!(props.inertia.near(&(Matrix3::identity() / 6.0)));
+    /// ```
+    fn mass_properties(&self) -> Result<MassProperties, MassPropertiesError>;
+}
+
+impl MassAnalysis for PolygonMesh {
+    fn surface_area(&self) -> f64 {
+        self.faces()
+            .triangle_iter()
+            .map(|tri| {
+                let [p, q, r] = tri.map(|v| self.positions()[v.pos]);
+                (q - p).cross(r - p).magnitude() / 2.0
+            })
+            .sum()
+    }
+
+    fn mass_properties(&self) -> Result<MassProperties, MassPropertiesError> {
+        let condition = self.shell_condition();
+        if condition != ShellCondition::Closed {
+            return Err(MassPropertiesError::NotClosed(condition));
+        }
+        // The integrals are taken around the center of the bounding box for the precision.
+        let origin = self.bounding_box().center();
+        let (mut volume, mut moment, mut second) = (0.0, Vector3::zero(), Matrix3::zero());
+        self.faces().triangle_iter().for_each(|tri| {
+            let [p, q, r] = tri.map(|v| self.positions()[v.pos] - origin);
+            // the signed volume of the tetrahedron spanned by the triangle and the origin
+            let v = p.dot(q.cross(r)) / 6.0;
+            let sum = p + q + r;
+            volume += v;
+            moment += sum * (v / 4.0);
+            let outer = |a: Vector3, b: Vector3| Matrix3::from_cols(a * b.x, a * b.y, a * b.z);
+            second += (outer(p, p) + outer(q, q) + outer(r, r) + outer(sum, sum)) * (v / 20.0);
+        });
+        let center = match volume.so_small() {
+            true => Vector3::zero(),
+            false => moment / volume,
+        };
+        let covariance = second
+            - Matrix3::from_cols(center * center.x, center * center.y, center * center.z) * volume;
+        let trace = covariance.x.x + covariance.y.y + covariance.z.z;
+        Ok(MassProperties {
+            volume,
+            area: self.surface_area(),
+            centroid: origin + center,
+            inertia: Matrix3::identity() * trace - covariance,
+        })
+    }
+}
@@ -1,14 +1,38 @@
 use crate::*;
 
+mod ambient_occlusion;
 mod collision;
+mod distance;
+mod geodesic;
 mod in_out_judge;
+mod mass_properties;
+mod morphing;
 mod point_cloud;
+mod primitives;
+mod quality;
+mod sampling;
+mod selection;
 mod splitting;
+mod symmetry;
 mod topology;
 
+pub use ambient_occlusion::{AmbientOcclusion, AmbientOcclusionOptions};
 pub use collision::Collision;
+pub use distance::{MeshDistance, MeshDistanceAnalysis};
+pub use geodesic::Geodesic;
 pub use in_out_judge::IncludingPointInDomain;
+pub use mass_properties::{MassAnalysis, MassProperties, MassPropertiesError};
+pub use morphing::{MorphInterpolation, Morphing, MorphingError};
 pub use point_cloud::WithPointCloud;
+pub use primitives::{DetectedPrimitive, FittedPrimitive, PrimitiveDetection};
+pub use quality::{
+    FaceQuality, Histogram, QualityAnalysis, QualityDistributions, QualityReport, QualityThresholds,
+};
+pub use sampling::InteriorSampling;
+pub use selection::Selection;
+#[cfg(feature = "brep")]
+pub(crate) use splitting::connect_edges;
 pub use splitting::ExperimentalSplitters;
 pub use splitting::Splitting;
+pub use symmetry::{DetectedSymmetry, MirrorPlane, Symmetry};
 pub use topology::Topology;
@@ -0,0 +1,194 @@
+use super::*;
+use thiserror::Error;
+
+/// The interpolation of the morphing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MorphInterpolation {
+    /// The vertices move at constant speeds. The parameters out of `[0, 1]` extrapolate the morphing.
+    #[default]
+    Linear,
+    /// The vertices start and stop smoothly, by the smoothstep `3t^2 - 2t^3`.
+    /// The parameters are clamped to `[0, 1]`.
+    Smooth,
+}
+
+impl MorphInterpolation {
+    /// Returns the ratio of the target in the interpolation at the parameter `t`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// assert_eq!(MorphInterpolation::Linear.weight(0.25), 0.25);
+    /// assert_eq!(MorphInterpolation::Smooth.weight(0.5), 0.5);
+    /// assert!(MorphInterpolation::Smooth.weight(0.25) < 0.25);
+    /// assert_eq!(MorphInterpolation::Smooth.weight(2.0), 1.0);
+    /// ```
+    #[inline(always)]
+    pub fn weight(self, t: f64) -> f64 {
+        match self {
+            MorphInterpolation::Linear => t,
+            MorphInterpolation::Smooth => {
+                let t = t.clamp(0.0, 1.0);
+                t * t * (3.0 - 2.0 * t)
+            }
+        }
+    }
+}
+
+/// Errors occurred by morphing meshes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum MorphingError {
+    /// The faces of the meshes are different.
+    #[error("The faces of the meshes are different. Only the meshes with the same connectivity can be morphed.")]
+    DifferentFaces,
+    /// The numbers of the attributes of the meshes are different.
+    #[error("The numbers of the positions, the uv coordinates, or the normals of the meshes are different.")]
+    DifferentAttributes,
+}
+
+/// Morphing between two meshes with the same connectivity.
+///
+/// # Details
+/// The meshes must have the same faces and the same numbers of positions, uv coordinates
+/// and normals, e.g. the meshes of the same shape tessellated at two values of a parameter
+/// with the same divisions. The positions and the uv coordinates are interpolated linearly
+/// with the weight by [`MorphInterpolation::weight`], and the normals are interpolated in the
+/// same way and normalized.
+pub trait Morphing: Sized {
+    /// Returns the intermediate mesh at the parameter `t`:
+    /// `self` at `t = 0` and `target` at `t = 1`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let triangle = |x: f64| {
+    ///     PolygonMesh::new(
+    ///         StandardAttributes {
+    ///             positions: vec![
+    ///                 Point3::new(x, 0.0, 0.0),
+    ///                 Point3::new(1.0, 0.0, 0.0),
+    ///                 Point3::new(0.0, 1.0, 0.0),
+    ///             ],
+    ///             ..Default::default()
+    ///         },
+    ///         Faces::from_iter(&[[0, 1, 2]]),
+    ///     )
+    /// };
+    /// let (mesh0, mesh1) = (triangle(0.0), triangle(-1.0));
+    /// let mesh = mesh0.morph(&mesh1, 0.25, MorphInterpolation::Linear).unwrap();
+    /// assert!(mesh.positions()[0].near(&Point3::new(-0.25, 0.0, 0.0)));
+    /// assert_eq!(mesh.faces(), mesh0.faces());
+    ///
+    /// // The meshes with different faces cannot be morphed.
+    /// let mut mesh2 = triangle(-1.0);
+    /// mesh2.invert();
+    /// assert_eq!(
+    ///     mesh0.morph(&mesh2, 0.5, MorphInterpolation::Linear),
+    ///     Err(MorphingError::DifferentFaces),
+    /// );
+    /// ```
+    fn morph(
+        &self,
+        target: &Self,
+        t: f64,
+        interpolation: MorphInterpolation,
+    ) -> Result<Self, MorphingError>;
+    /// Returns `frames` meshes at the parameters divided `[0, 1]` equally,
+    /// including `self` and `target` at the ends.
+    ///
+    /// Returns `[self]` if `frames == 1` and the empty vector if `frames == 0`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let segment = |y: f64| {
+    ///     PolygonMesh::new(
+    ///         StandardAttributes {
+    ///             positions: vec![
+    ///                 Point3::new(0.0, 0.0, 0.0),
+    ///                 Point3::new(1.0, 0.0, 0.0),
+    ///                 Point3::new(0.0, y, 0.0),
+    ///             ],
+    ///             ..Default::default()
+    ///         },
+    ///         Faces::from_iter(&[[0, 1, 2]]),
+    ///     )
+    /// };
+    /// let frames = segment(1.0)
+    ///     .morph_frames(&segment(2.0), 5, MorphInterpolation::Smooth)
+    ///     .unwrap();
+    /// let heights: Vec<f64> = frames.iter().map(|mesh| mesh.positions()[2].y).collect();
+    /// assert_eq!(heights[0], 1.0);
+    /// assert_eq!(heights[2], 1.5);
+    /// assert_eq!(heights[4], 2.0);
+    /// // slow at the start
+    /// assert!(heights[1] - heights[0] < 0.25);
+    /// ```
+    fn morph_frames(
+        &self,
+        target: &Self,
+        frames: usize,
+        interpolation: MorphInterpolation,
+    ) -> Result<Vec<Self>, MorphingError> {
+        (0..frames)
+            .map(|i| {
+                let t = match frames {
+                    1 => 0.0,
+                    _ => i as f64 / (frames - 1) as f64,
+                };
+                self.morph(target, t, interpolation)
+            })
+            .collect()
+    }
+}
+
+impl Morphing for PolygonMesh {
+    fn morph(
+        &self,
+        target: &Self,
+        t: f64,
+        interpolation: MorphInterpolation,
+    ) -> Result<Self, MorphingError> {
+        if self.faces() != target.faces() {
+            return Err(MorphingError::DifferentFaces);
+        }
+        if self.positions().len() != target.positions().len()
+            || self.uv_coords().len() != target.uv_coords().len()
+            || self.normals().len() != target.normals().len()
+        {
+            return Err(MorphingError::DifferentAttributes);
+        }
+        let w = interpolation.weight(t);
+        let positions = self
+            .positions()
+            .iter()
+            .zip(target.positions())
+            .map(|(p, q)| Point3::from_vec(p.to_vec() * (1.0 - w) + q.to_vec() * w))
+            .collect();
+        let uv_coords = self
+            .uv_coords()
+            .iter()
+            .zip(target.uv_coords())
+            .map(|(p, q)| p * (1.0 - w) + q * w)
+            .collect();
+        let normals = self
+            .normals()
+            .iter()
+            .zip(target.normals())
+            .map(|(n, m)| {
+                let normal = n * (1.0 - w) + m * w;
+                // the opposite normals are switched at the middle.
+                match normal.so_small() {
+                    true if w < 0.5 => *n,
+                    true => *m,
+                    false => normal.normalize(),
+                }
+            })
+            .collect();
+        Ok(PolygonMesh::new(
+            StandardAttributes {
+                positions,
+                uv_coords,
+                normals,
+            },
+            self.faces().clone(),
+        ))
+    }
+}
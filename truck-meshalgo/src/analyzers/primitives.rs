@@ -0,0 +1,354 @@
+use super::*;
+
+/// Analytic surfaces fitted to the faces of polygon meshes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FittedPrimitive {
+    /// plane through `origin` with the unit `normal`
+    Plane {
+        /// a point on the plane, the centroid of the samples
+        origin: Point3,
+        /// the unit normal
+        normal: Vector3,
+    },
+    /// sphere
+    Sphere {
+        /// the center
+        center: Point3,
+        /// the radius
+        radius: f64,
+    },
+    /// infinite cylinder
+    Cylinder {
+        /// a point on the axis, the nearest one to the centroid of the samples
+        origin: Point3,
+        /// the unit direction of the axis
+        axis: Vector3,
+        /// the radius
+        radius: f64,
+    },
+    /// infinite cone, one nappe
+    Cone {
+        /// the apex
+        apex: Point3,
+        /// the unit direction of the axis from the apex to the samples
+        axis: Vector3,
+        /// the angle between the axis and the generating lines, in radians
+        half_angle: f64,
+    },
+}
+
+impl FittedPrimitive {
+    /// Returns the distance from `point` to the surface.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let cylinder = FittedPrimitive::Cylinder {
+    ///     origin: Point3::origin(),
+    ///     axis: Vector3::unit_z(),
+    ///     radius: 1.0,
+    /// };
+    /// assert!(cylinder.distance(Point3::new(0.0, 3.0, 5.0)).near(&2.0));
+    /// ```
+    pub fn distance(&self, point: Point3) -> f64 {
+        match *self {
+            Self::Plane { origin, normal } => (point - origin).dot(normal).abs(),
+            Self::Sphere { center, radius } => (point.distance(center) - radius).abs(),
+            Self::Cylinder {
+                origin,
+                axis,
+                radius,
+            } => {
+                let vec = point - origin;
+                ((vec - axis * vec.dot(axis)).magnitude() - radius).abs()
+            }
+            Self::Cone {
+                apex,
+                axis,
+                half_angle,
+            } => {
+                let vec = point - apex;
+                let height = vec.dot(axis);
+                let rho = (vec - axis * height).magnitude();
+                let (sin, cos) = half_angle.sin_cos();
+                // the distance to the generating line in the half plane including `point`
+                match rho * sin + height * cos < 0.0 {
+                    true => vec.magnitude(),
+                    false => (rho * cos - height * sin).abs(),
+                }
+            }
+        }
+    }
+}
+
+/// Primitive detected by [`PrimitiveDetection::detect_primitives`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetectedPrimitive {
+    /// the fitted surface
+    pub primitive: FittedPrimitive,
+    /// the indices of the faces in the order of [`Faces::face_iter`]
+    pub faces: Vec<usize>,
+    /// the root mean square of the distances from the samples to the surface
+    pub rms: f64,
+}
+
+/// Detects planes, spheres, cylinders and cones in polygon meshes.
+pub trait PrimitiveDetection {
+    /// Detects the analytic surfaces in the mesh.
+    /// # Details
+    /// The faces are grown into the regions separated by the sharp edges, in the same way as
+    /// [`Splitting::feature_segmentation`] with `feature_angle`. Then, the plane, sphere, cylinder,
+    /// and cone are fitted to the vertices and the face normals of each region by least squares,
+    /// in this order, and the first one whose distances from the vertices and the centroids of
+    /// the faces are within `tol` is adopted. The regions which no primitive fits are not returned.
+    ///
+    /// The faces are connected by the position indices. Apply
+    /// [`OptimizingFilter::put_together_same_attrs`] to separated meshes beforehand.
+    /// # Panics
+    /// `tol` must be more than `TOLERANCE`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // cube
+    /// let positions = (0..8)
+    ///     .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+    ///     .collect();
+    /// let faces = Faces::from_iter(&[
+    ///     &[0, 2, 3, 1],
+    ///     &[0, 1, 5, 4],
+    ///     &[1, 3, 7, 5],
+    ///     &[3, 2, 6, 7],
+    ///     &[2, 0, 4, 6],
+    ///     &[4, 5, 7, 6],
+    /// ]);
+    /// let cube = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions,
+    ///         ..Default::default()
+    ///     },
+    ///     faces,
+    /// );
+    ///
+    /// let primitives = cube.detect_primitives(1.0e-3, std::f64::consts::PI / 6.0);
+    /// assert_eq!(primitives.len(), 6);
+    /// match primitives[5].primitive {
+    ///     FittedPrimitive::Plane { origin, normal } => {
+    ///         assert!(origin.near(&Point3::new(0.5, 0.5, 1.0)));
+    ///         assert!(normal.cross(Vector3::unit_z()).so_small());
+    ///     }
+    ///     _ => panic!("the face must be planar."),
+    /// }
+    /// ```
+    fn detect_primitives(&self, tol: f64, feature_angle: f64) -> Vec<DetectedPrimitive>;
+}
+
+impl PrimitiveDetection for PolygonMesh {
+    fn detect_primitives(&self, tol: f64, feature_angle: f64) -> Vec<DetectedPrimitive> {
+        nonpositive_tolerance!(tol);
+        let (labels, _) = self.feature_segmentation(feature_angle);
+        let mut regions = Vec::<Vec<usize>>::new();
+        labels.into_iter().enumerate().for_each(|(i, label)| {
+            if regions.len() <= label {
+                regions.resize(label + 1, Vec::new());
+            }
+            regions[label].push(i);
+        });
+        regions
+            .into_iter()
+            .filter_map(|faces| {
+                let samples = Samples::new(self, &faces);
+                let (primitive, rms) = samples.fit(tol)?;
+                Some(DetectedPrimitive {
+                    primitive,
+                    faces,
+                    rms,
+                })
+            })
+            .collect()
+    }
+}
+
+/// the points and the normals of a region
+#[derive(Clone, Debug)]
+struct Samples {
+    /// the positions of the vertices and the centroids of the faces
+    points: Vec<Point3>,
+    /// the unit normals of the faces
+    normals: Vec<Vector3>,
+    /// the centroids of the faces, corresponding to `normals`
+    centers: Vec<Point3>,
+}
+
+impl Samples {
+    fn new(mesh: &PolygonMesh, faces: &[usize]) -> Self {
+        let positions = mesh.positions();
+        let mut used = vec![false; positions.len()];
+        let mut points = Vec::new();
+        let (mut normals, mut centers) = (Vec::new(), Vec::new());
+        faces.iter().for_each(|i| {
+            let face = &mesh.faces()[*i];
+            face.iter().for_each(|v| {
+                if !used[v.pos] {
+                    used[v.pos] = true;
+                    points.push(positions[v.pos]);
+                }
+            });
+            let normal = FaceNormal::new(positions, face, *i).normal;
+            if normal.magnitude2().near(&1.0) {
+                let center = face
+                    .iter()
+                    .fold(Point3::origin(), |sum, v| sum + positions[v.pos].to_vec())
+                    / face.len() as f64;
+                normals.push(normal);
+                centers.push(center);
+            }
+        });
+        points.extend(&centers);
+        Samples {
+            points,
+            normals,
+            centers,
+        }
+    }
+
+    /// the first primitive fitted within `tol` and the RMS of the distances
+    fn fit(&self, tol: f64) -> Option<(FittedPrimitive, f64)> {
+        if self.normals.is_empty() {
+            return None;
+        }
+        let candidates: [fn(&Self) -> Option<FittedPrimitive>; 4] = [
+            Self::fit_plane,
+            Self::fit_sphere,
+            Self::fit_cylinder,
+            Self::fit_cone,
+        ];
+        candidates.into_iter().find_map(|fit| {
+            let primitive = fit(self)?;
+            let (max, sum) = self.points.iter().fold((0.0_f64, 0.0), |(max, sum), p| {
+                let dist = primitive.distance(*p);
+                (max.max(dist), sum + dist * dist)
+            });
+            match max <= tol {
+                true => Some((primitive, f64::sqrt(sum / self.points.len() as f64))),
+                false => None,
+            }
+        })
+    }
+
+    fn centroid(&self) -> Point3 { Point3::centroid(&self.points) }
+
+    fn fit_plane(&self) -> Option<FittedPrimitive> {
+        let origin = self.centroid();
+        let covariance = self.points.iter().fold(Matrix3::zero(), |sum, p| {
+            let vec = p - origin;
+            sum + outer(vec, vec)
+        });
+        let mut normal = smallest_eigenvector(covariance);
+        // oriented along the face normals
+        if self.normals.iter().sum::<Vector3>().dot(normal) < 0.0 {
+            normal = -normal;
+        }
+        Some(FittedPrimitive::Plane { origin, normal })
+    }
+
+    /// algebraic fitting: |p|^2 = 2 c.p + (r^2 - |c|^2)
+    fn fit_sphere(&self) -> Option<FittedPrimitive> {
+        // The coordinates are relative to the centroid for the precision.
+        let origin = self.centroid();
+        let (mat, vec) =
+            self.points
+                .iter()
+                .fold((Matrix4::zero(), Vector4::zero()), |(mat, vec), p| {
+                    let p = p - origin;
+                    let row = Vector4::new(2.0 * p.x, 2.0 * p.y, 2.0 * p.z, 1.0);
+                    let outer =
+                        Matrix4::from_cols(row * row.x, row * row.y, row * row.z, row * row.w);
+                    (mat + outer, vec + row * p.magnitude2())
+                });
+        let sol = mat.invert()? * vec;
+        let center = Vector3::new(sol.x, sol.y, sol.z);
+        let radius2 = sol.w + center.magnitude2();
+        match radius2 > 0.0 {
+            true => Some(FittedPrimitive::Sphere {
+                center: origin + center,
+                radius: radius2.sqrt(),
+            }),
+            false => None,
+        }
+    }
+
+    /// The axis is perpendicular to all normals, and the points are fitted to the circle
+    /// in the plane perpendicular to the axis.
+    fn fit_cylinder(&self) -> Option<FittedPrimitive> {
+        let normal_matrix = self
+            .normals
+            .iter()
+            .fold(Matrix3::zero(), |sum, n| sum + outer(*n, *n));
+        let axis = smallest_eigenvector(normal_matrix);
+        let origin = self.centroid();
+        let u = match axis.x.abs() < 0.9 {
+            true => Vector3::unit_x().cross(axis).normalize(),
+            false => Vector3::unit_y().cross(axis).normalize(),
+        };
+        let v = axis.cross(u);
+        // |q|^2 = 2 c.q + (r^2 - |c|^2) in the plane
+        let (mat, vec) =
+            self.points
+                .iter()
+                .fold((Matrix3::zero(), Vector3::zero()), |(mat, vec), p| {
+                    let (x, y) = ((p - origin).dot(u), (p - origin).dot(v));
+                    let row = Vector3::new(2.0 * x, 2.0 * y, 1.0);
+                    (mat + outer(row, row), vec + row * (x * x + y * y))
+                });
+        let sol = mat.invert()? * vec;
+        let radius2 = sol.z + sol.x * sol.x + sol.y * sol.y;
+        match radius2 > 0.0 {
+            true => Some(FittedPrimitive::Cylinder {
+                origin: origin + u * sol.x + v * sol.y,
+                axis,
+                radius: radius2.sqrt(),
+            }),
+            false => None,
+        }
+    }
+
+    /// The normals have the constant angle with the axis, and the tangent planes
+    /// pass through the apex.
+    fn fit_cone(&self) -> Option<FittedPrimitive> {
+        let len = self.normals.len() as f64;
+        let mean = self.normals.iter().sum::<Vector3>() / len;
+        let covariance = self.normals.iter().fold(Matrix3::zero(), |sum, n| {
+            let vec = n - mean;
+            sum + outer(vec, vec)
+        });
+        let mut axis = smallest_eigenvector(covariance);
+        let (mat, vec) = self
+            .normals
+            .iter()
+            .zip(&self.centers)
+            .fold((Matrix3::zero(), Vector3::zero()), |(mat, vec), (n, c)| {
+                (mat + outer(*n, *n), vec + n * n.dot(c.to_vec()))
+            });
+        let apex = Point3::from_vec(mat.invert()? * vec);
+        let centroid = self.centroid();
+        if (centroid - apex).dot(axis) < 0.0 {
+            axis = -axis;
+        }
+        let angles: Vec<f64> = self
+            .points
+            .iter()
+            .filter(|p| !p.distance2(apex).so_small())
+            .map(|p| (p - apex).angle(axis).0)
+            .collect();
+        let half_angle = angles.iter().sum::<f64>() / angles.len() as f64;
+        match 0.0 < half_angle && half_angle < std::f64::consts::PI / 2.0 {
+            true => Some(FittedPrimitive::Cone {
+                apex,
+                axis,
+                half_angle,
+            }),
+            false => None,
+        }
+    }
+}
+
+fn outer(a: Vector3, b: Vector3) -> Matrix3 { Matrix3::from_cols(a * b.x, a * b.y, a * b.z) }
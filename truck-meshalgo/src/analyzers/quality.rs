@@ -0,0 +1,451 @@
+use super::*;
+use rustc_hash::FxHashSet as HashSet;
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+
+/// The quality metrics of a polygon.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FaceQuality {
+    /// the number of the vertices
+    pub num_vertices: usize,
+    /// the minimum angle between the adjacent edges, in radians
+    pub min_angle: f64,
+    /// the maximum angle between the adjacent edges, in radians
+    pub max_angle: f64,
+    /// the ratio which is one for the regular polygons and increases as the polygon
+    /// is distorted; see [`QualityAnalysis::quality_report`] for the definition
+    pub aspect_ratio: f64,
+    /// the equiangular skewness, zero for the equiangular polygons and one for the degenerate ones
+    pub skewness: f64,
+    /// the length of the shortest edge
+    pub min_edge_length: f64,
+    /// the length of the longest edge
+    pub max_edge_length: f64,
+    /// the non-planarity, zero for the planar polygons; see
+    /// [`QualityAnalysis::quality_report`] for the definition
+    pub warp: f64,
+}
+
+/// The thresholds of the quality metrics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QualityThresholds {
+    /// the lower bound of the angles, in radians
+    pub min_angle: f64,
+    /// the upper bound of the angles, in radians
+    pub max_angle: f64,
+    /// the upper bound of the aspect ratios
+    pub max_aspect_ratio: f64,
+    /// the upper bound of the skewnesses
+    pub max_skewness: f64,
+    /// the upper bound of the warps
+    pub max_warp: f64,
+}
+
+impl Default for QualityThresholds {
+    /// No thresholds: all faces satisfy them.
+    fn default() -> Self {
+        Self {
+            min_angle: 0.0,
+            max_angle: PI,
+            max_aspect_ratio: f64::INFINITY,
+            max_skewness: 1.0,
+            max_warp: f64::INFINITY,
+        }
+    }
+}
+
+impl FaceQuality {
+    /// Returns whether the face satisfies all `thresholds` or not.
+    #[inline(always)]
+    pub fn satisfies(&self, thresholds: &QualityThresholds) -> bool {
+        self.min_angle >= thresholds.min_angle
+            && self.max_angle <= thresholds.max_angle
+            && self.aspect_ratio <= thresholds.max_aspect_ratio
+            && self.skewness <= thresholds.max_skewness
+            && self.warp <= thresholds.max_warp
+    }
+}
+
+/// The histogram of values with bins of the same widths.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    /// the minimum and the maximum of the values
+    pub range: (f64, f64),
+    /// the number of the values in each bin
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Creates the histogram of the finite values in `values` with `bins` bins.
+    ///
+    /// The range of the histogram is the one of the values.
+    /// If there is no finite value, the range is `(0.0, 0.0)` and all counts are zero.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::analyzers::Histogram;
+    /// let histogram = Histogram::new([0.0, 0.1, 0.2, 0.5, 1.0, f64::INFINITY], 4);
+    /// assert_eq!(histogram.range, (0.0, 1.0));
+    /// assert_eq!(histogram.counts, vec![3, 0, 1, 1]);
+    /// assert_eq!(histogram.bin_range(1), (0.25, 0.5));
+    /// ```
+    pub fn new(values: impl IntoIterator<Item = f64>, bins: usize) -> Self {
+        let values: Vec<f64> = values.into_iter().filter(|x| x.is_finite()).collect();
+        let mut counts = vec![0; bins];
+        if values.is_empty() {
+            return Self {
+                range: (0.0, 0.0),
+                counts,
+            };
+        }
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let width = (max - min) / bins as f64;
+        values.into_iter().for_each(|x| {
+            let i = match width > 0.0 {
+                true => ((x - min) / width) as usize,
+                false => 0,
+            };
+            if let Some(count) = counts.get_mut(usize::min(i, bins.saturating_sub(1))) {
+                *count += 1;
+            }
+        });
+        Self {
+            range: (min, max),
+            counts,
+        }
+    }
+    /// Returns the range of the `i`th bin.
+    #[inline(always)]
+    pub fn bin_range(&self, i: usize) -> (f64, f64) {
+        let width = (self.range.1 - self.range.0) / self.counts.len() as f64;
+        (
+            self.range.0 + width * i as f64,
+            self.range.0 + width * (i + 1) as f64,
+        )
+    }
+}
+
+impl Display for Histogram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let max_count = self.counts.iter().copied().max().unwrap_or(0);
+        (0..self.counts.len()).try_for_each(|i| {
+            let (start, end) = self.bin_range(i);
+            let bar = match max_count {
+                0 => 0,
+                _ => (self.counts[i] * 40).div_ceil(max_count),
+            };
+            writeln!(
+                f,
+                "[{start:>12.6}, {end:>12.6}) {:>8} {}",
+                self.counts[i],
+                "#".repeat(bar)
+            )
+        })
+    }
+}
+
+/// The quality report of a polygon mesh.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QualityReport {
+    /// the metrics of the faces, in the order of [`Faces::face_iter`]
+    pub faces: Vec<FaceQuality>,
+    /// the histogram of the lengths of the edges, each edge counted once
+    pub edge_lengths: Histogram,
+}
+
+impl QualityReport {
+    /// the number of the bins of the histograms in the report
+    pub const HISTOGRAM_BINS: usize = 10;
+    /// Returns the minimum angle of all faces.
+    #[inline(always)]
+    pub fn min_angle(&self) -> f64 { self.faces.iter().map(|q| q.min_angle).fold(PI, f64::min) }
+    /// Returns the maximum angle of all faces.
+    #[inline(always)]
+    pub fn max_angle(&self) -> f64 { self.faces.iter().map(|q| q.max_angle).fold(0.0, f64::max) }
+    /// Returns the maximum aspect ratio of all faces.
+    #[inline(always)]
+    pub fn max_aspect_ratio(&self) -> f64 {
+        self.faces
+            .iter()
+            .map(|q| q.aspect_ratio)
+            .fold(1.0, f64::max)
+    }
+    /// Returns the maximum skewness of all faces.
+    #[inline(always)]
+    pub fn max_skewness(&self) -> f64 { self.faces.iter().map(|q| q.skewness).fold(0.0, f64::max) }
+    /// Returns the histogram of a metric of the faces, e.g. `report.histogram(|q| q.skewness, 20)`.
+    #[inline(always)]
+    pub fn histogram(&self, metric: impl Fn(&FaceQuality) -> f64, bins: usize) -> Histogram {
+        Histogram::new(self.faces.iter().map(metric), bins)
+    }
+    /// Returns the indices of the faces which do not satisfy `thresholds`.
+    #[inline(always)]
+    pub fn violations(&self, thresholds: &QualityThresholds) -> Vec<usize> {
+        (0..self.faces.len())
+            .filter(|i| !self.faces[*i].satisfies(thresholds))
+            .collect()
+    }
+}
+
+/// The distributions of the quality metrics of a polygon mesh.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QualityDistributions {
+    /// the aspect ratios of the non-degenerate triangles
+    pub aspect_ratios: Histogram,
+    /// the minimum angles of the faces, in radians
+    pub min_angles: Histogram,
+    /// the maximum angles of the faces, in radians
+    pub max_angles: Histogram,
+    /// the lengths of the edges, each edge counted once
+    pub edge_lengths: Histogram,
+    /// the warps of the quadrangles
+    pub quad_warps: Histogram,
+    /// the number of the degenerate faces, whose aspect ratios are infinity
+    pub degenerate_faces: usize,
+}
+
+impl QualityReport {
+    /// Returns the distributions of the metrics with [`QualityReport::HISTOGRAM_BINS`] bins.
+    pub fn distributions(&self) -> QualityDistributions {
+        let bins = Self::HISTOGRAM_BINS;
+        let triangles = self.faces.iter().filter(|q| q.num_vertices == 3);
+        let quads = self.faces.iter().filter(|q| q.num_vertices == 4);
+        QualityDistributions {
+            aspect_ratios: Histogram::new(triangles.map(|q| q.aspect_ratio), bins),
+            min_angles: self.histogram(|q| q.min_angle, bins),
+            max_angles: self.histogram(|q| q.max_angle, bins),
+            edge_lengths: self.edge_lengths.clone(),
+            quad_warps: Histogram::new(quads.map(|q| q.warp), bins),
+            degenerate_faces: self
+                .faces
+                .iter()
+                .filter(|q| q.aspect_ratio == f64::INFINITY)
+                .count(),
+        }
+    }
+}
+
+impl Display for QualityDistributions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "degenerate faces: {}", self.degenerate_faces)?;
+        writeln!(f, "triangle aspect ratios:")?;
+        write!(f, "{}", self.aspect_ratios)?;
+        writeln!(f, "minimum angles (rad):")?;
+        write!(f, "{}", self.min_angles)?;
+        writeln!(f, "maximum angles (rad):")?;
+        write!(f, "{}", self.max_angles)?;
+        writeln!(f, "edge lengths:")?;
+        write!(f, "{}", self.edge_lengths)?;
+        writeln!(f, "quadrangle warps:")?;
+        write!(f, "{}", self.quad_warps)
+    }
+}
+
+impl Display for QualityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "faces: {}", self.faces.len())?;
+        writeln!(
+            f,
+            "angles: [{:.3}, {:.3}] deg",
+            self.min_angle().to_degrees(),
+            self.max_angle().to_degrees()
+        )?;
+        writeln!(f, "max aspect ratio: {:.3}", self.max_aspect_ratio())?;
+        writeln!(f, "max skewness: {:.3}", self.max_skewness())?;
+        writeln!(f, "edge lengths:")?;
+        write!(f, "{}", self.edge_lengths)
+    }
+}
+
+/// Analyzes the quality of the faces, for example, for the finite element analysis.
+pub trait QualityAnalysis {
+    /// Computes the quality metrics of each face and the histogram of the edge lengths.
+    ///
+    /// # Details
+    /// - The angles are the ones between the adjacent edges at the vertices of the polygons.
+    /// - The aspect ratio of a triangle is `l * p / (4 sqrt(3) A)`, where `l` is the length of
+    ///   the longest edge, `p` is the perimeter and `A` is the area.
+    ///   The aspect ratio of a polygon with more vertices is the ratio of the longest edge
+    ///   to the shortest one. Both are one for the regular polygons.
+    /// - The equiangular skewness is `max((t_max - t_e) / (PI - t_e), (t_e - t_min) / t_e)`,
+    ///   where `t_e = (n - 2) PI / n` is the angle of the regular `n`-gon.
+    /// - The faces with the edges of length zero or the triangles of area zero have the
+    ///   minimum angle zero, the maximum angle `PI`, the infinite aspect ratio and the skewness one.
+    /// - The warp of a polygon with four or more vertices is the maximum distance from the
+    ///   vertices to the plane through the centroid perpendicular to the Newell normal,
+    ///   divided by the length of the longest edge. The warp of a triangle is zero,
+    ///   and the one of a polygon whose Newell normal vanishes is infinity.
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use truck_meshalgo::prelude::*;
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(0.5, 2.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[[0, 1, 2, 3].as_ref(), &[3, 2, 4]]),
+    /// );
+    /// let report = mesh.quality_report();
+    /// // the faces are in the order of `face_iter`: the triangles come first.
+    /// // the square is perfect.
+    /// let square = report.faces[1];
+    /// assert!(square.aspect_ratio.near(&1.0));
+    /// assert!(square.skewness.so_small());
+    /// assert!(square.min_angle.near(&(PI / 2.0)));
+    /// // the triangle is isosceles with the apex angle 2 atan(1/2).
+    /// let triangle = report.faces[0];
+    /// assert!(triangle.min_angle.near(&(2.0 * f64::atan(0.5))));
+    /// assert!(triangle.skewness > 0.0);
+    ///
+    /// // the FEA mesh requires the angles not less than 30 degrees.
+    /// let thresholds = QualityThresholds {
+    ///     min_angle: PI / 6.0,
+    ///     ..Default::default()
+    /// };
+    /// assert!(report.violations(&thresholds).is_empty());
+    ///
+    /// // 6 edges in total
+    /// assert_eq!(report.edge_lengths.counts.iter().sum::<usize>(), 6);
+    /// ```
+    fn quality_report(&self) -> QualityReport;
+    /// Returns the distributions of the aspect ratios of triangles, the minimum and maximum
+    /// angles, the edge lengths and the warps of quadrangles.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.2),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(0.5, 2.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[[0, 1, 2, 3].as_ref(), &[3, 2, 4]]),
+    /// );
+    /// let quality = mesh.quality();
+    /// assert_eq!(quality.aspect_ratios.counts.iter().sum::<usize>(), 1);
+    /// assert_eq!(quality.quad_warps.counts.iter().sum::<usize>(), 1);
+    /// // the quadrangle is warped.
+    /// assert!(quality.quad_warps.range.0 > 0.01);
+    /// assert_eq!(quality.degenerate_faces, 0);
+    ///
+    /// // reject the warped quadrangles
+    /// let thresholds = QualityThresholds {
+    ///     max_warp: 0.01,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(mesh.quality_report().violations(&thresholds), vec![1]);
+    /// ```
+    #[inline(always)]
+    fn quality(&self) -> QualityDistributions { self.quality_report().distributions() }
+}
+
+impl QualityAnalysis for PolygonMesh {
+    fn quality_report(&self) -> QualityReport {
+        let positions = self.positions();
+        let faces = self
+            .face_iter()
+            .map(|face| {
+                let points: Vec<Point3> = face.iter().map(|v| positions[v.pos]).collect();
+                face_quality(&points)
+            })
+            .collect();
+        let mut edges = HashSet::<(usize, usize)>::default();
+        self.face_iter().for_each(|face| {
+            (0..face.len()).for_each(|i| {
+                let (a, b) = (face[i].pos, face[(i + 1) % face.len()].pos);
+                edges.insert((usize::min(a, b), usize::max(a, b)));
+            })
+        });
+        let lengths = edges
+            .into_iter()
+            .map(|(a, b)| positions[a].distance(positions[b]));
+        QualityReport {
+            faces,
+            edge_lengths: Histogram::new(lengths, QualityReport::HISTOGRAM_BINS),
+        }
+    }
+}
+
+fn face_quality(points: &[Point3]) -> FaceQuality {
+    let n = points.len();
+    let edges: Vec<Vector3> = (0..n).map(|i| points[(i + 1) % n] - points[i]).collect();
+    let lengths: Vec<f64> = edges.iter().map(|e| e.magnitude()).collect();
+    let min_edge_length = lengths.iter().copied().fold(f64::INFINITY, f64::min);
+    let max_edge_length = lengths.iter().copied().fold(0.0, f64::max);
+    let warp = warp(points, max_edge_length);
+    let degenerate = FaceQuality {
+        num_vertices: n,
+        min_angle: 0.0,
+        max_angle: PI,
+        aspect_ratio: f64::INFINITY,
+        skewness: 1.0,
+        min_edge_length,
+        max_edge_length,
+        warp,
+    };
+    if n < 3 || min_edge_length.so_small() {
+        return degenerate;
+    }
+    let angles = (0..n).map(|i| (-edges[(i + n - 1) % n]).angle(edges[i]).0);
+    let (min_angle, max_angle) = angles.fold((PI, 0.0), |(min, max), angle| {
+        (f64::min(min, angle), f64::max(max, angle))
+    });
+    let aspect_ratio = match n {
+        3 => {
+            let area = edges[0].cross(edges[1]).magnitude() / 2.0;
+            if area.so_small2() {
+                return degenerate;
+            }
+            let perimeter = lengths.iter().sum::<f64>();
+            max_edge_length * perimeter / (4.0 * f64::sqrt(3.0) * area)
+        }
+        _ => max_edge_length / min_edge_length,
+    };
+    let equiangle = (n - 2) as f64 * PI / n as f64;
+    let skewness = f64::max(
+        (max_angle - equiangle) / (PI - equiangle),
+        (equiangle - min_angle) / equiangle,
+    );
+    FaceQuality {
+        num_vertices: n,
+        min_angle,
+        max_angle,
+        aspect_ratio,
+        skewness: f64::max(skewness, 0.0),
+        min_edge_length,
+        max_edge_length,
+        warp,
+    }
+}
+
+fn warp(points: &[Point3], max_edge_length: f64) -> f64 {
+    if points.len() < 4 {
+        return 0.0;
+    }
+    let center = Point3::centroid(points);
+    let normal = (0..points.len()).fold(Vector3::zero(), |sum, i| {
+        let (p, q) = (points[i], points[(i + 1) % points.len()]);
+        sum + (p - center).cross(q - center)
+    });
+    if normal.so_small2() || max_edge_length.so_small() {
+        return f64::INFINITY;
+    }
+    let normal = normal.normalize();
+    let distance = points
+        .iter()
+        .map(|p| f64::abs((p - center).dot(normal)))
+        .fold(0.0, f64::max);
+    distance / max_edge_length
+}
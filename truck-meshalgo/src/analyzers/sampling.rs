@@ -0,0 +1,149 @@
+use super::distance::TriangleTree;
+use super::*;
+use crate::filters::OptimizingFilter;
+use crate::tessellation::{MeshableShape, MeshableSurface, MeshedShape, PolylineableCurve};
+use truck_topology::Solid;
+
+/// the number of the trials per sample before giving up
+const MAX_TRIALS: usize = 1000;
+
+/// the pseudo random numbers in `[0, 1)` by SplitMix64
+#[derive(Clone, Copy, Debug)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
+/// Uniform random sampling of the points inside solids.
+///
+/// # Details
+/// The points are sampled uniformly in the bounding box, and rejected if they are outside the
+/// domain by [`IncludingPointInDomain::inside`] or closer to the boundary than the margin.
+/// The points are generated by a deterministic pseudo random generator from the seed, so the
+/// same seed gives the same points.
+pub trait InteriorSampling {
+    /// Returns `n` points distributed uniformly and strictly inside the domain, generated from
+    /// `seed`.
+    ///
+    /// Fewer points are returned if the domain is too thin compared to its bounding box, e.g.
+    /// if the mesh is not closed.
+    fn sample_interior_with_seed(&self, n: usize, seed: u64) -> Vec<Point3>;
+    /// Returns `n` points distributed uniformly and strictly inside the domain, generated from
+    /// the seed `0`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    ///
+    /// // the cylinder with radius 1 and height 2
+    /// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    /// let disk = builder::try_attach_plane(&[circle]).unwrap();
+    /// let cylinder = builder::tsweep(&disk, Vector3::new(0.0, 0.0, 2.0));
+    ///
+    /// let points = cylinder.sample_interior(1000);
+    /// assert_eq!(points.len(), 1000);
+    /// assert!(points.iter().all(|p| {
+    ///     p.x * p.x + p.y * p.y < 1.0 && 0.0 < p.z && p.z < 2.0
+    /// }));
+    /// // the points in the lower half
+    /// let lower = points.iter().filter(|p| p.z < 1.0).count();
+    /// assert!(400 < lower && lower < 600);
+    /// ```
+    #[inline(always)]
+    fn sample_interior(&self, n: usize) -> Vec<Point3> { self.sample_interior_with_seed(n, 0) }
+}
+
+/// Samples the points inside the closed `mesh` whose distances to the mesh are more than `margin`.
+fn sample_mesh(mesh: &PolygonMesh, n: usize, seed: u64, margin: f64) -> Vec<Point3> {
+    let bdd_box = mesh.bounding_box();
+    if bdd_box.is_empty() {
+        return Vec::new();
+    }
+    let (min, diagonal) = (*bdd_box.min(), bdd_box.diagonal());
+    let tree = TriangleTree::new(mesh);
+    let mut random = SplitMix64(seed);
+    let mut points = Vec::with_capacity(n);
+    let mut trials = 0;
+    while points.len() < n && trials < n.saturating_mul(MAX_TRIALS) {
+        trials += 1;
+        let point = min
+            + Vector3::new(
+                diagonal.x * random.next(),
+                diagonal.y * random.next(),
+                diagonal.z * random.next(),
+            );
+        if tree.distance2(point) > margin * margin && mesh.inside(point) {
+            points.push(point);
+        }
+    }
+    points
+}
+
+impl InteriorSampling for PolygonMesh {
+    /// The margin is `TOLERANCE`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the regular octahedron
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(-1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, -1.0, 0.0),
+    ///             Point3::new(0.0, 0.0, 1.0),
+    ///             Point3::new(0.0, 0.0, -1.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[
+    ///         [0, 1, 4], [1, 2, 4], [2, 3, 4], [3, 0, 4],
+    ///         [1, 0, 5], [2, 1, 5], [3, 2, 5], [0, 3, 5],
+    ///     ]),
+    /// );
+    /// let points = mesh.sample_interior_with_seed(100, 42);
+    /// assert_eq!(points.len(), 100);
+    /// assert!(points.iter().all(|p| p.x.abs() + p.y.abs() + p.z.abs() < 1.0));
+    /// // the same seed gives the same points.
+    /// assert_eq!(mesh.sample_interior_with_seed(100, 42), points);
+    /// assert_ne!(mesh.sample_interior_with_seed(100, 43), points);
+    /// ```
+    fn sample_interior_with_seed(&self, n: usize, seed: u64) -> Vec<Point3> {
+        sample_mesh(self, n, seed, TOLERANCE)
+    }
+}
+
+/// The solid is tessellated by the tolerance `0.001` times the diameter of the bounding box of
+/// the edges, and the margin is the tolerance of the tessellation.
+impl<C: PolylineableCurve, S: MeshableSurface> InteriorSampling for Solid<Point3, C, S> {
+    fn sample_interior_with_seed(&self, n: usize, seed: u64) -> Vec<Point3> {
+        const DIVISION: usize = 8;
+        let bdd_box: BoundingBox<Point3> = self
+            .edge_iter()
+            .flat_map(|edge| {
+                let curve = edge.get_curve();
+                let (t0, t1) = curve.parameter_range();
+                (0..=DIVISION)
+                    .map(|i| curve.subs(t0 + (t1 - t0) * i as f64 / DIVISION as f64))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        if bdd_box.is_empty() {
+            return Vec::new();
+        }
+        let tol = f64::max(bdd_box.diameter() * 1.0e-3, 2.0 * TOLERANCE);
+        let mut mesh = self.triangulation(tol).to_polygon();
+        mesh.put_together_same_attrs();
+        sample_mesh(&mesh, n, seed, tol)
+    }
+}
@@ -0,0 +1,125 @@
+use super::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::collections::VecDeque;
+
+/// Region growing selections of faces, e.g. for the selection tools of mesh editors.
+///
+/// # Details
+/// The faces are identified by the order of [`Faces::face_iter`], and two faces are adjacent
+/// if they share an edge of positions, i.e. the uv coordinates and the normals are ignored.
+/// The selections are the sorted vectors of the indices of faces, and they are empty if the
+/// seed face does not exist or does not satisfy the condition.
+pub trait Selection {
+    /// Returns the faces reachable from `seed` through the adjacent faces satisfying `predicate`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the strip of four squares
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: (0..5)
+    ///             .flat_map(|i| [Point3::new(i as f64, 0.0, 0.0), Point3::new(i as f64, 1.0, 0.0)])
+    ///             .collect(),
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[[0, 2, 3, 1], [2, 4, 5, 3], [4, 6, 7, 5], [6, 8, 9, 7]]),
+    /// );
+    /// // the faces except the third one
+    /// let selection = mesh.select_connected(0, |i| i != 2);
+    /// assert_eq!(selection, vec![0, 1]);
+    /// assert_eq!(mesh.select_connected(3, |i| i != 2), vec![3]);
+    /// assert!(mesh.select_connected(2, |i| i != 2).is_empty());
+    /// ```
+    fn select_connected<F: FnMut(usize) -> bool>(&self, seed: usize, predicate: F) -> Vec<usize>;
+    /// Returns the faces reachable from `seed` through the adjacent faces whose normals
+    /// make the angles not more than `max_angle` with each other, i.e. the "tangent faces".
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use truck_meshalgo::prelude::*;
+    /// // the floor and the slope
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(2.0, 0.0, 0.5),
+    ///             Point3::new(2.0, 1.0, 0.5),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[[0, 1, 2, 3], [1, 4, 5, 2]]),
+    /// );
+    /// assert_eq!(mesh.select_smooth(0, PI / 6.0), vec![0, 1]);
+    /// assert_eq!(mesh.select_smooth(0, PI / 8.0), vec![0]);
+    /// ```
+    fn select_smooth(&self, seed: usize, max_angle: f64) -> Vec<usize>;
+}
+
+impl Selection for PolygonMesh {
+    fn select_connected<F: FnMut(usize) -> bool>(
+        &self,
+        seed: usize,
+        mut predicate: F,
+    ) -> Vec<usize> {
+        grow_region(self, seed, |_, j| predicate(j))
+    }
+
+    fn select_smooth(&self, seed: usize, max_angle: f64) -> Vec<usize> {
+        let positions = self.positions();
+        let normals: Vec<Vector3> = self
+            .face_iter()
+            .enumerate()
+            .map(|(i, face)| FaceNormal::new(positions, face, i).normal)
+            .collect();
+        // the degenerate faces, whose normals are NaN, are not separated.
+        grow_region(self, seed, |i, j| {
+            let angle = normals[i].angle(normals[j]).0;
+            angle <= max_angle || angle.is_nan()
+        })
+    }
+}
+
+/// Returns the faces reachable from `seed` via the edges `(i, j)` with `condition(i, j)`,
+/// where the seed also has to satisfy `condition(seed, seed)`.
+fn grow_region(
+    mesh: &PolygonMesh,
+    seed: usize,
+    mut condition: impl FnMut(usize, usize) -> bool,
+) -> Vec<usize> {
+    let len = mesh.faces().len();
+    if seed >= len || !condition(seed, seed) {
+        return Vec::new();
+    }
+    let mut edge_faces = HashMap::<[usize; 2], Vec<usize>>::default();
+    mesh.face_iter().enumerate().for_each(|(i, face)| {
+        face.iter()
+            .zip(face.iter().cycle().skip(1))
+            .filter(|(v0, v1)| v0.pos != v1.pos)
+            .for_each(|(v0, v1)| {
+                let edge = [usize::min(v0.pos, v1.pos), usize::max(v0.pos, v1.pos)];
+                edge_faces.entry(edge).or_default().push(i);
+            })
+    });
+    let mut adjacency = vec![Vec::<usize>::new(); len];
+    edge_faces.values().for_each(|faces| {
+        faces.iter().for_each(|i| {
+            let others = faces.iter().filter(|j| *j != i);
+            adjacency[*i].extend(others);
+        })
+    });
+    let mut selected = vec![false; len];
+    selected[seed] = true;
+    let mut queue = VecDeque::from([seed]);
+    while let Some(i) = queue.pop_front() {
+        adjacency[i].iter().for_each(|&j| {
+            if !selected[j] && condition(i, j) {
+                selected[j] = true;
+                queue.push_back(j);
+            }
+        });
+    }
+    (0..len).filter(|i| selected[*i]).collect()
+}
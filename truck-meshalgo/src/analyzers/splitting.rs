@@ -1,4 +1,6 @@
 use super::*;
+use crate::filters::OptimizingFilter;
+use rustc_hash::FxHashMap as HashMap;
 use std::f64::consts::PI;
 
 /// Splitting the faces into several clusters.
@@ -128,6 +130,105 @@ pub trait Splitting {
     /// assert_eq!(components.len(), 1);
     /// ```
     fn components(&self, use_normal: bool) -> Vec<Vec<usize>>;
+    /// Returns the labels of the components of the faces, the indices of the components
+    /// returned by [`Splitting::components`].
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use truck_meshalgo::analyzers::*;
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(5.0, 0.0, 0.0),
+    ///             Point3::new(6.0, 0.0, 0.0),
+    ///             Point3::new(5.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2], &[4, 5, 6], &[2, 1, 3]]),
+    /// );
+    /// assert_eq!(mesh.component_labels(false), vec![0, 1, 0]);
+    /// ```
+    fn component_labels(&self, use_normal: bool) -> Vec<usize>;
+    /// Splits into the separate meshes of the components.
+    /// The unused attributes are removed from each mesh.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use truck_meshalgo::analyzers::*;
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(5.0, 0.0, 0.0),
+    ///             Point3::new(6.0, 0.0, 0.0),
+    ///             Point3::new(5.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2], &[4, 5, 6], &[2, 1, 3]]),
+    /// );
+    /// let meshes = mesh.component_meshes(false);
+    /// assert_eq!(meshes.len(), 2);
+    /// assert_eq!(meshes[0].faces().len(), 2);
+    /// assert_eq!(meshes[0].positions().len(), 4);
+    /// assert_eq!(meshes[1].faces().len(), 1);
+    /// assert_eq!(meshes[1].positions().len(), 3);
+    /// ```
+    fn component_meshes(&self, use_normal: bool) -> Vec<PolygonMesh>;
+    /// Segments the faces into clusters separated by the sharp edges.
+    /// # Details
+    /// Two polygons sharing an edge are considered to be in the same cluster if the angle
+    /// between their face normals is not more than `feature_angle`. The polygons are
+    /// identified by the order of [`Faces::face_iter`].
+    /// # Returns
+    /// - The first vector is the labels of the clusters of the faces.
+    /// The labels are sequential numbers from zero.
+    /// - The second vector is the polylines of the edges separating the different clusters.
+    /// Each polyline consists of the indices of positions, and the closed ones end with
+    /// the same index as the first one.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use truck_meshalgo::analyzers::*;
+    ///
+    /// // cube
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 0.0, 1.0),
+    ///             Point3::new(1.0, 0.0, 1.0),
+    ///             Point3::new(1.0, 1.0, 1.0),
+    ///             Point3::new(0.0, 1.0, 1.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[
+    ///         &[3, 2, 0], &[1, 0, 2], &[0, 1, 4], &[5, 4, 1],
+    ///         &[1, 2, 5], &[6, 5, 2], &[2, 3, 6], &[7, 6, 3],
+    ///         &[3, 0, 7], &[4, 7, 0], &[4, 5, 7], &[6, 7, 5],
+    ///     ]),
+    /// );
+    ///
+    /// let (labels, polylines) = mesh.feature_segmentation(std::f64::consts::PI / 4.0);
+    /// // two triangles for each square
+    /// assert_eq!(labels, vec![0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5]);
+    /// // the polylines are split at the corners of the cube.
+    /// assert_eq!(polylines.len(), 12);
+    /// assert!(polylines.iter().all(|polyline| polyline.len() == 2));
+    /// ```
+    fn feature_segmentation(&self, feature_angle: f64) -> (Vec<usize>, Vec<Vec<usize>>);
 }
 
 impl Splitting for PolygonMesh {
@@ -157,6 +258,69 @@ impl Splitting for PolygonMesh {
         let face_adjacency = self.faces().face_adjacency(use_normal);
         get_components(&face_adjacency)
     }
+
+    fn component_labels(&self, use_normal: bool) -> Vec<usize> {
+        let mut labels = vec![0; self.faces().len()];
+        self.components(use_normal)
+            .into_iter()
+            .enumerate()
+            .for_each(|(label, component)| component.into_iter().for_each(|i| labels[i] = label));
+        labels
+    }
+
+    fn component_meshes(&self, use_normal: bool) -> Vec<PolygonMesh> {
+        self.components(use_normal)
+            .into_iter()
+            .map(|component| {
+                let mut mesh = self.create_mesh_by_face_indices(&component);
+                mesh.remove_unused_attrs();
+                mesh
+            })
+            .collect()
+    }
+
+    fn feature_segmentation(&self, feature_angle: f64) -> (Vec<usize>, Vec<Vec<usize>>) {
+        let positions = self.positions();
+        let normals: Vec<Vector3> = self
+            .face_iter()
+            .enumerate()
+            .map(|(i, face)| FaceNormal::new(positions, face, i).normal)
+            .collect();
+        let mut edge_faces = HashMap::<[usize; 2], Vec<usize>>::default();
+        self.face_iter().enumerate().for_each(|(i, face)| {
+            face.iter()
+                .zip(face.iter().cycle().skip(1))
+                .filter(|(v0, v1)| v0.pos != v1.pos)
+                .for_each(|(v0, v1)| {
+                    let edge = [usize::min(v0.pos, v1.pos), usize::max(v0.pos, v1.pos)];
+                    edge_faces.entry(edge).or_default().push(i);
+                })
+        });
+        let mut adjacency = vec![Vec::new(); normals.len()];
+        edge_faces.values().for_each(|faces| {
+            faces.iter().enumerate().for_each(|(k, i)| {
+                faces[k + 1..].iter().for_each(|j| {
+                    // degenerate faces, whose normals are NaN, are not separated.
+                    if !(normals[*i].angle(normals[*j]).0 > feature_angle) {
+                        adjacency[*i].push(*j);
+                        adjacency[*j].push(*i);
+                    }
+                })
+            })
+        });
+        let mut labels = vec![0; normals.len()];
+        get_components(&adjacency)
+            .into_iter()
+            .enumerate()
+            .for_each(|(label, component)| component.into_iter().for_each(|i| labels[i] = label));
+        let mut feature_edges: Vec<[usize; 2]> = edge_faces
+            .into_iter()
+            .filter(|(_, faces)| faces.iter().any(|i| labels[*i] != labels[faces[0]]))
+            .map(|(edge, _)| edge)
+            .collect();
+        feature_edges.sort();
+        (labels, connect_edges(positions.len(), &feature_edges))
+    }
 }
 
 #[doc(hidden)]
@@ -269,6 +433,51 @@ fn get_components(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
     }
 }
 
+/// connects the edges into the polylines separated at the branch points.
+pub(crate) fn connect_edges(num_of_vertices: usize, edges: &[[usize; 2]]) -> Vec<Vec<usize>> {
+    let mut vertex_edges = vec![Vec::new(); num_of_vertices];
+    edges.iter().enumerate().for_each(|(i, edge)| {
+        vertex_edges[edge[0]].push(i);
+        vertex_edges[edge[1]].push(i);
+    });
+    let mut unchecked = vec![true; edges.len()];
+    let mut polylines = Vec::new();
+    let mut trace = |start: usize, first_edge: usize, unchecked: &mut [bool]| {
+        let mut polyline = vec![start];
+        let (mut vertex, mut edge) = (start, first_edge);
+        loop {
+            unchecked[edge] = false;
+            vertex = edges[edge][0] + edges[edge][1] - vertex;
+            polyline.push(vertex);
+            if vertex_edges[vertex].len() != 2 {
+                break;
+            }
+            match vertex_edges[vertex].iter().find(|i| unchecked[**i]) {
+                Some(next) => edge = *next,
+                None => break,
+            }
+        }
+        polylines.push(polyline);
+    };
+    // open polylines from the end points and the branch points
+    (0..num_of_vertices)
+        .filter(|v| !vertex_edges[*v].is_empty() && vertex_edges[*v].len() != 2)
+        .for_each(|v| {
+            vertex_edges[v].iter().for_each(|i| {
+                if unchecked[*i] {
+                    trace(v, *i, &mut unchecked);
+                }
+            })
+        });
+    // closed polylines
+    (0..edges.len()).for_each(|i| {
+        if unchecked[i] {
+            trace(edges[i][0], i, &mut unchecked);
+        }
+    });
+    polylines
+}
+
 fn is_in_the_plane(positions: &[Point3], normals: &[Vector3], face: &[Vertex], tol2: f64) -> bool {
     let n = FaceNormal::new(positions, face, 0).normal;
     for v in face {
@@ -0,0 +1,204 @@
+use super::*;
+use truck_base::eigen::symmetric_eigen;
+
+/// The plane of a reflection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MirrorPlane {
+    /// a point on the plane
+    pub origin: Point3,
+    /// the unit normal
+    pub normal: Vector3,
+}
+
+impl MirrorPlane {
+    /// Creates the plane through `origin` perpendicular to `normal`. `normal` is normalized.
+    #[inline(always)]
+    pub fn new(origin: Point3, normal: Vector3) -> Self {
+        Self {
+            origin,
+            normal: normal.normalize(),
+        }
+    }
+    /// Returns the mirror image of `point`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let plane = MirrorPlane::new(Point3::new(1.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0));
+    /// let point = plane.reflect_point(Point3::new(3.0, 1.0, 2.0));
+    /// assert!(point.near(&Point3::new(-1.0, 1.0, 2.0)));
+    /// ```
+    #[inline(always)]
+    pub fn reflect_point(&self, point: Point3) -> Point3 {
+        point - 2.0 * (point - self.origin).dot(self.normal) * self.normal
+    }
+    /// Returns the mirror image of `vector`.
+    #[inline(always)]
+    pub fn reflect_vector(&self, vector: Vector3) -> Vector3 {
+        vector - 2.0 * vector.dot(self.normal) * self.normal
+    }
+}
+
+/// A plane of the approximate reflective symmetry detected by [`Symmetry::detect_symmetry`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DetectedSymmetry {
+    /// the plane of the symmetry
+    pub plane: MirrorPlane,
+    /// the distances between the mesh and its mirror image
+    pub distance: MeshDistance,
+}
+
+/// Mirroring and detecting the reflective symmetry of meshes.
+pub trait Symmetry {
+    /// Returns the mirror image of the mesh.
+    ///
+    /// The normals are reflected and the orders of the vertices of faces are reversed,
+    /// so that the faces and the normals of the image are oriented outward if the original is.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(2.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///         ],
+    ///         normals: vec![Vector3::unit_z()],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[(0, None, Some(0)), (1, None, Some(0)), (2, None, Some(0))]]),
+    /// );
+    /// let plane = MirrorPlane::new(Point3::origin(), Vector3::unit_x());
+    /// let mirror = mesh.mirror(&plane);
+    /// assert!(mirror.positions()[1].near(&Point3::new(-2.0, 0.0, 0.0)));
+    /// assert!(mirror.normals()[0].near(&Vector3::unit_z()));
+    ///
+    /// // The orientation of the face is kept.
+    /// let face = &mirror.faces()[0];
+    /// let p = face.iter().map(|v| mirror.positions()[v.pos]).collect::<Vec<_>>();
+    /// assert!((p[1] - p[0]).cross(p[2] - p[0]).z > 0.0);
+    /// ```
+    fn mirror(&self, plane: &MirrorPlane) -> Self;
+    /// Returns the distances between the mesh and its mirror image by `plane`.
+    fn symmetry_distance(&self, plane: &MirrorPlane) -> MeshDistance;
+    /// Detects the planes of the approximate reflective symmetry.
+    ///
+    /// Returns the planes whose symmetric Hausdorff distances between the mesh and its mirror
+    /// images are not more than `tol`, in ascending order of the RMS distances.
+    /// # Details
+    /// Any plane of the symmetry passes through the area centroid of the surface and is
+    /// perpendicular to an eigenvector of the covariance matrix of the surface.
+    /// So, the candidates are the planes through the centroid perpendicular to the principal
+    /// axes, and to the coordinate axes for the case where the eigenvalues are degenerate,
+    /// e.g. for the cubes and the cylinders.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the rectangle [0, 2] x [0, 1]
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(2.0, 0.0, 0.0),
+    ///             Point3::new(2.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2, 3]]),
+    /// );
+    /// // symmetric in x, y and z directions
+    /// let planes = mesh.detect_symmetry(1.0e-6);
+    /// assert_eq!(planes.len(), 3);
+    /// planes.iter().for_each(|sym| assert!(sym.plane.origin.near(&Point3::new(1.0, 0.5, 0.0))));
+    ///
+    /// // If one corner is moved, the mesh is symmetric only in z direction.
+    /// mesh.positions_mut()[2] = Point3::new(2.0, 1.1, 0.0);
+    /// let planes = mesh.detect_symmetry(1.0e-2);
+    /// assert_eq!(planes.len(), 1);
+    /// assert!(planes[0].plane.normal.cross(Vector3::unit_z()).so_small());
+    /// ```
+    fn detect_symmetry(&self, tol: f64) -> Vec<DetectedSymmetry>;
+}
+
+impl Symmetry for PolygonMesh {
+    fn mirror(&self, plane: &MirrorPlane) -> Self {
+        let mut mesh = self.clone();
+        let mut editor = mesh.debug_editor();
+        let PolygonMeshEditor {
+            attributes: StandardAttributes {
+                positions, normals, ..
+            },
+            faces,
+            ..
+        } = &mut editor;
+        positions
+            .iter_mut()
+            .for_each(|p| *p = plane.reflect_point(*p));
+        normals
+            .iter_mut()
+            .for_each(|n| *n = plane.reflect_vector(*n));
+        faces.face_iter_mut().for_each(|face| face.reverse());
+        drop(editor);
+        mesh
+    }
+    fn symmetry_distance(&self, plane: &MirrorPlane) -> MeshDistance {
+        self.distance_to(&self.mirror(plane))
+    }
+    fn detect_symmetry(&self, tol: f64) -> Vec<DetectedSymmetry> {
+        let (centroid, covariance) = match surface_moments(self) {
+            Some(moments) => moments,
+            None => return Vec::new(),
+        };
+        let axes = symmetric_eigen(covariance.into()).1;
+        let mut normals = Vec::<Vector3>::new();
+        axes.into_iter()
+            .map(Vector3::from)
+            .chain([Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()])
+            .for_each(|normal| {
+                let parallel = |n: &Vector3| n.cross(normal).so_small();
+                if !normals.iter().any(parallel) {
+                    normals.push(normal);
+                }
+            });
+        let mut planes: Vec<DetectedSymmetry> = normals
+            .into_iter()
+            .filter_map(|normal| {
+                let plane = MirrorPlane::new(centroid, normal);
+                let distance = self.symmetry_distance(&plane);
+                match distance.hausdorff <= tol {
+                    true => Some(DetectedSymmetry { plane, distance }),
+                    false => None,
+                }
+            })
+            .collect();
+        planes.sort_by(|a, b| a.distance.rms.total_cmp(&b.distance.rms));
+        planes
+    }
+}
+
+/// Returns the area centroid and the area-weighted covariance matrix of the surface.
+fn surface_moments(mesh: &PolygonMesh) -> Option<(Point3, Matrix3)> {
+    let triangles: Vec<[Vector3; 3]> = mesh
+        .faces()
+        .triangle_iter()
+        .map(|tri| tri.map(|v| mesh.positions()[v.pos].to_vec()))
+        .collect();
+    let area = |[a, b, c]: &[Vector3; 3]| (b - a).cross(c - a).magnitude() / 2.0;
+    let total_area = triangles.iter().map(area).sum::<f64>();
+    if total_area.so_small() {
+        return None;
+    }
+    let centroid = triangles.iter().fold(Vector3::zero(), |sum, tri| {
+        sum + (tri[0] + tri[1] + tri[2]) * area(tri) / 3.0
+    }) / total_area;
+    let outer = |a: Vector3, b: Vector3| Matrix3::from_cols(a * b.x, a * b.y, a * b.z);
+    // the exact second moment of the triangle: A/12 (sum v v^T + (sum v)(sum v)^T)
+    let covariance = triangles.iter().fold(Matrix3::zero(), |sum, tri| {
+        let [a, b, c] = tri.map(|v| v - centroid);
+        let s = a + b + c;
+        let moment = outer(a, a) + outer(b, b) + outer(c, c) + outer(s, s);
+        sum + moment * (area(tri) / 12.0)
+    });
+    Some((Point3::from_vec(centroid), covariance))
+}
@@ -0,0 +1,721 @@
+use crate::analyzers::{FittedPrimitive, PrimitiveDetection};
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::collections::hash_map::Entry;
+use std::f64::consts::PI;
+use thiserror::Error;
+use truck_modeling::{
+    builder, BSplineCurve, Curve, Edge, Face, IntersectionCurve, KnotVec, Line, Plane, Processor,
+    RevolutedCurve, Shell, Surface, Wire,
+};
+
+/// The options of [`polygon_mesh_to_brep`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BRepConversionOptions {
+    /// the tolerance of fitting the surfaces to the regions. Default is `0.01`.
+    pub tolerance: f64,
+    /// the regions are separated by the edges where the angles between the normals of the faces
+    /// are more than this angle, in radians. Default is `PI / 6.0`.
+    pub feature_angle: f64,
+}
+
+impl Default for BRepConversionOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.01,
+            feature_angle: PI / 6.0,
+        }
+    }
+}
+
+/// Errors occurred by [`polygon_mesh_to_brep`].
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum BRepConversionError {
+    /// The boundary of a region is not closed, i.e. the mesh is not oriented.
+    #[error("The boundary of the region is not closed. The mesh must be oriented.")]
+    NotOriented,
+    /// The point on the boundaries cannot be projected onto all the adjacent surfaces.
+    #[error(
+        "The position {0} cannot be projected onto all the adjacent surfaces within the tolerance."
+    )]
+    NotOnSurfaces(usize),
+    /// wrapper of topological error
+    #[error(transparent)]
+    FromTopology(#[from] truck_topology::errors::Error),
+}
+
+/// Converts the polygon mesh into the B-rep shell, i.e. the inverse of the tessellation.
+///
+/// # Details
+/// The mesh is segmented and the primitives are fitted to the regions by
+/// [`PrimitiveDetection::detect_primitives`] with the tolerance and the feature angle of
+/// `options`. The faces of the returned shell are
+/// - the planes, the spheres, the cylinders and the cones fitted to the regions, whose
+///   surfaces are [`Plane`]s or [`RevolutedCurve`]s, and
+/// - the planar triangles of the regions to which no primitive is fitted.
+///
+/// The spheres, the cylinders and the cones wrapping around their axes are divided into the
+/// sectors, and the seams of the surfaces are put out of the faces.
+///
+/// The boundaries of the regions are split at the corners, where the adjacent regions change,
+/// and the corners are projected onto all the adjacent surfaces. The edges between two
+/// different surfaces are the [`IntersectionCurve`]s led by the polylines of the mesh,
+/// except the lines between two planes. The edges on the open boundaries or between the sectors
+/// of one surface are the sections of the surface by the planes through the ends of the edges,
+/// or the polylines on the planes.
+///
+/// The orientations of the faces are the ones of the mesh, and the shell is closed
+/// if the mesh is closed. The faces are connected by the position indices, so apply
+/// [`OptimizingFilter::put_together_same_attrs`] and
+/// [`OptimizingFilter::remove_degenerate_faces`] to the separated meshes beforehand.
+/// # Panics
+/// Panics if `options.tolerance` is not more than `TOLERANCE`.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+///
+/// // cube
+/// let positions = (0..8)
+///     .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+///     .collect();
+/// let faces = Faces::from_iter(&[
+///     &[0, 2, 3, 1],
+///     &[0, 1, 5, 4],
+///     &[1, 3, 7, 5],
+///     &[3, 2, 6, 7],
+///     &[2, 0, 4, 6],
+///     &[4, 5, 7, 6],
+/// ]);
+/// let mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions,
+///         ..Default::default()
+///     },
+///     faces,
+/// );
+///
+/// let shell = polygon_mesh_to_brep(&mesh, &Default::default()).unwrap();
+/// assert_eq!(shell.len(), 6);
+/// assert_eq!(shell.edge_iter().count(), 24);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// assert!(shell.face_iter().all(|face| matches!(face.get_surface(), Surface::Plane(_))));
+///
+/// // the solid is the unit cube.
+/// let solid = Solid::new(vec![shell]);
+/// let (mesh, _) = solid.triangulation(0.01).to_watertight_polygon();
+/// assert!(mesh.mass_properties().unwrap().volume.near(&1.0));
+/// ```
+pub fn polygon_mesh_to_brep(
+    mesh: &PolygonMesh,
+    options: &BRepConversionOptions,
+) -> Result<Shell, BRepConversionError> {
+    let tol = options.tolerance;
+    nonpositive_tolerance!(tol);
+    let positions = mesh.positions();
+
+    // the polygons by the position indices, and the regions attached to the primitives
+    let primitives = mesh.detect_primitives(tol, options.feature_angle);
+    let mut polygons = Vec::<Vec<usize>>::new();
+    let mut regions = Vec::<Region>::new();
+    let mut fitted = vec![false; mesh.faces().len()];
+    primitives
+        .iter()
+        .enumerate()
+        .for_each(|(source, detected)| {
+            let faces: Vec<usize> = detected
+                .faces
+                .iter()
+                .map(|i| {
+                    fitted[*i] = true;
+                    polygons.push(mesh.faces()[*i].iter().map(|v| v.pos).collect());
+                    polygons.len() - 1
+                })
+                .collect();
+            let primitive = detected.primitive;
+            let labels = sector_labels(positions, &polygons, &faces, primitive);
+            regions.extend(
+                components(&polygons, &faces, &labels)
+                    .into_iter()
+                    .map(|faces| Region {
+                        source,
+                        primitive,
+                        faces,
+                    }),
+            );
+        });
+    let mut source = primitives.len();
+    mesh.face_iter()
+        .zip(fitted)
+        .filter(|(_, fitted)| !fitted)
+        .for_each(|(face, _)| {
+            (1..face.len() - 1).for_each(|i| {
+                let triangle = [face[0].pos, face[i].pos, face[i + 1].pos];
+                let [p0, p1, p2] = triangle.map(|i| positions[i]);
+                let normal = (p1 - p0).cross(p2 - p0);
+                if normal.so_small() {
+                    return;
+                }
+                polygons.push(triangle.to_vec());
+                regions.push(Region {
+                    source,
+                    primitive: FittedPrimitive::Plane {
+                        origin: p0,
+                        normal: normal.normalize(),
+                    },
+                    faces: vec![polygons.len() - 1],
+                });
+                source += 1;
+            })
+        });
+
+    let mut halfedge_region = HashMap::<[usize; 2], usize>::default();
+    regions.iter().enumerate().for_each(|(i, region)| {
+        region.faces.iter().for_each(|f| {
+            let polygon = &polygons[*f];
+            let len = polygon.len();
+            (0..len).for_each(|j| {
+                halfedge_region.insert([polygon[j], polygon[(j + 1) % len]], i);
+            })
+        })
+    });
+    let neighbor = |a: usize, b: usize| halfedge_region.get(&[b, a]).copied();
+    let loops = regions
+        .iter()
+        .enumerate()
+        .map(|(i, region)| boundary_loops(&polygons, &region.faces, i, &halfedge_region))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(BRepConversionError::NotOriented)?;
+
+    // the corners, where the adjacent regions change
+    let mut corners = vec![false; positions.len()];
+    loops.iter().flatten().for_each(|boundary| {
+        let len = boundary.len();
+        (0..len).for_each(|i| {
+            let prev = neighbor(boundary[(i + len - 1) % len], boundary[i]);
+            if prev != neighbor(boundary[i], boundary[(i + 1) % len]) {
+                corners[boundary[i]] = true;
+            }
+        })
+    });
+    // The loops with less than two corners are split at the vertices determined by the indices,
+    // which are the same on the both sides.
+    loops.iter().flatten().for_each(|boundary| {
+        if boundary.iter().filter(|v| corners[**v]).count() < 2 {
+            let len = boundary.len();
+            let min = (0..len).min_by_key(|i| boundary[*i]).unwrap();
+            let (next, prev) = (boundary[(min + 1) % len], boundary[(min + len - 1) % len]);
+            let opposite = match next < prev {
+                true => (min + len / 2) % len,
+                false => (min + len - len / 2) % len,
+            };
+            corners[boundary[min]] = true;
+            corners[boundary[opposite]] = true;
+        }
+    });
+
+    let mut vertex_sources = vec![Vec::<usize>::new(); positions.len()];
+    regions.iter().enumerate().for_each(|(i, region)| {
+        region
+            .faces
+            .iter()
+            .flat_map(|f| &polygons[*f])
+            .for_each(|v| {
+                let sources = &mut vertex_sources[*v];
+                if !sources.iter().any(|j| regions[*j].source == region.source) {
+                    sources.push(i);
+                }
+            })
+    });
+    let surfaces: Vec<Surface> = regions
+        .iter()
+        .map(|region| region.surface(positions, &polygons, tol))
+        .collect();
+
+    let mut vertices = HashMap::<usize, truck_modeling::Vertex>::default();
+    let mut edges = HashMap::<[usize; 2], Edge>::default();
+    let mut faces = Vec::<Face>::new();
+    for (i, region) in regions.iter().enumerate() {
+        let mut wires = Vec::<Wire>::new();
+        for boundary in &loops[i] {
+            let len = boundary.len();
+            let starts: Vec<usize> = (0..len).filter(|j| corners[boundary[*j]]).collect();
+            let mut wire = Wire::new();
+            for (k, start) in starts.iter().enumerate() {
+                let end = match starts.get(k + 1) {
+                    Some(end) => end,
+                    None => starts[0] + len,
+                };
+                let chain: Vec<usize> = (*start..=end).map(|j| boundary[j % len]).collect();
+                let (v0, v1) = (chain[0], chain[chain.len() - 1]);
+                if let Some(edge) = edges.get(&[v1, chain[chain.len() - 2]]) {
+                    wire.push_back(edge.inverse());
+                    continue;
+                }
+                for v in [v0, v1] {
+                    if let Entry::Vacant(entry) = vertices.entry(v) {
+                        let primitives: Vec<FittedPrimitive> = vertex_sources[v]
+                            .iter()
+                            .map(|j| regions[*j].primitive)
+                            .collect();
+                        let point = project(&primitives, positions[v], tol)
+                            .ok_or(BRepConversionError::NotOnSurfaces(v))?;
+                        entry.insert(builder::vertex(point));
+                    }
+                }
+                let other =
+                    neighbor(chain[0], chain[1]).filter(|j| regions[*j].source != region.source);
+                let (primitives, surfaces) = match (other, region.primitive) {
+                    (Some(j), _) => {
+                        let surfaces = match (&surfaces[i], &surfaces[j]) {
+                            (Surface::Plane(_), Surface::Plane(_)) => None,
+                            (surface0, surface1) => Some((surface0.clone(), surface1.clone())),
+                        };
+                        (vec![region.primitive, regions[j].primitive], surfaces)
+                    }
+                    (None, FittedPrimitive::Plane { .. }) => (vec![region.primitive], None),
+                    // the section by the plane through the ends, transversal to the surface
+                    (None, primitive) => {
+                        let (p0, p1) = (vertices[&v0].get_point(), vertices[&v1].get_point());
+                        let middle = positions[chain[chain.len() / 2]];
+                        let normal = (p1 - p0).cross(nearest_point(&primitive, middle).1);
+                        match normal.so_small() {
+                            true => (vec![primitive], None),
+                            false => {
+                                let normal = normal.normalize();
+                                let (u, v) = frame(normal);
+                                let plane = Plane::new(p0, p0 + u, p0 + v);
+                                let section = FittedPrimitive::Plane { origin: p0, normal };
+                                let surfaces = (surfaces[i].clone(), Surface::Plane(plane));
+                                (vec![primitive, section], Some(surfaces))
+                            }
+                        }
+                    }
+                };
+                let curve = chain_curve(&chain, &vertices, positions, &primitives, surfaces, tol)?;
+                let edge = Edge::try_new(&vertices[&v0], &vertices[&v1], curve)?;
+                edges.insert([v0, chain[1]], edge.clone());
+                wire.push_back(edge);
+            }
+            wires.push(wire);
+        }
+        let face = match region.is_reversed(&surfaces[i], positions, &polygons) {
+            false => Face::try_new(wires, surfaces[i].clone())?,
+            true => {
+                let wires = wires.iter().map(Wire::inverse).collect();
+                Face::try_new(wires, surfaces[i].clone())?.inverse()
+            }
+        };
+        faces.push(face);
+    }
+    Ok(faces.into())
+}
+
+/// the faces attached to one surface
+#[derive(Clone, Debug)]
+struct Region {
+    /// the index of the primitive, shared by the sectors of the surface
+    source: usize,
+    primitive: FittedPrimitive,
+    /// the indices of the polygons
+    faces: Vec<usize>,
+}
+
+impl Region {
+    fn vertices<'a>(&self, positions: &'a [Point3], polygons: &[Vec<usize>]) -> Vec<&'a Point3> {
+        let mut indices: Vec<usize> = self
+            .faces
+            .iter()
+            .flat_map(|f| polygons[*f].iter().copied())
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|i| &positions[i]).collect()
+    }
+
+    /// The seams of the surfaces of revolution are put at the widest gaps of the vertices.
+    fn surface(&self, positions: &[Point3], polygons: &[Vec<usize>], tol: f64) -> Surface {
+        let vertices = self.vertices(positions, polygons);
+        let revolution = |curve: Curve, origin: Point3, axis: Vector3| {
+            Surface::RevolutedCurve(Processor::new(RevolutedCurve::by_revolution(
+                curve, origin, axis,
+            )))
+        };
+        // the range of the generating line, with the margin
+        let range = |heights: Vec<f64>| {
+            let min = heights.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = heights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let margin = (max - min) * 0.1 + tol;
+            (min - margin, max + margin)
+        };
+        match self.primitive {
+            FittedPrimitive::Plane { origin, normal } => {
+                let (u, v) = frame(normal);
+                Surface::Plane(Plane::new(origin, origin + u, origin + v))
+            }
+            FittedPrimitive::Sphere { center, radius } => {
+                let mean = vertices.iter().map(|p| *p - center).sum::<Vector3>();
+                let seam = match mean.so_small() {
+                    true => Vector3::unit_x(),
+                    false => -mean.normalize(),
+                };
+                let axis = frame(seam).0;
+                let north = builder::vertex(center + axis * radius);
+                let south = builder::vertex(center - axis * radius);
+                let arc = builder::circle_arc(&north, &south, center + seam * radius);
+                revolution(arc.get_curve(), center, axis)
+            }
+            FittedPrimitive::Cylinder {
+                origin,
+                axis,
+                radius,
+            } => {
+                let seam = seam_direction(&vertices, origin, axis);
+                let (h0, h1) = range(vertices.iter().map(|p| (*p - origin).dot(axis)).collect());
+                let base = origin + seam * radius;
+                let line = Line(base + axis * h0, base + axis * h1);
+                revolution(Curve::Line(line), origin, axis)
+            }
+            FittedPrimitive::Cone {
+                apex,
+                axis,
+                half_angle,
+            } => {
+                let seam = seam_direction(&vertices, apex, axis);
+                let (sin, cos) = half_angle.sin_cos();
+                let generator = axis * cos + seam * sin;
+                let (t0, t1) = range(
+                    vertices
+                        .iter()
+                        .map(|p| (*p - apex).dot(generator))
+                        .collect(),
+                );
+                let line = Line(apex + generator * f64::max(t0, 0.0), apex + generator * t1);
+                revolution(Curve::Line(line), apex, axis)
+            }
+        }
+    }
+
+    /// Whether the normal of `surface` is opposite to the ones of the polygons.
+    fn is_reversed(
+        &self,
+        surface: &Surface,
+        positions: &[Point3],
+        polygons: &[Vec<usize>],
+    ) -> bool {
+        let (center, normal) = self
+            .faces
+            .iter()
+            .map(|f| {
+                let points: Vec<Point3> = polygons[*f].iter().map(|v| positions[*v]).collect();
+                let normal = (0..points.len()).fold(Vector3::zero(), |sum, i| {
+                    let (p, q) = (points[i], points[(i + 1) % points.len()]);
+                    sum + p.to_vec().cross(q.to_vec())
+                });
+                (Point3::centroid(&points), normal)
+            })
+            .max_by(|(_, n0), (_, n1)| n0.magnitude2().total_cmp(&n1.magnitude2()))
+            .unwrap();
+        match surface.search_nearest_parameter(center, None, 100) {
+            Some((u, v)) => surface.normal(u, v).dot(normal) < 0.0,
+            None => false,
+        }
+    }
+}
+
+/// the unit vectors `u` and `v` such that `u.cross(v) == axis`
+fn frame(axis: Vector3) -> (Vector3, Vector3) {
+    let u = match axis.x.abs() < 0.9 {
+        true => Vector3::unit_x().cross(axis).normalize(),
+        false => Vector3::unit_y().cross(axis).normalize(),
+    };
+    (u, axis.cross(u))
+}
+
+/// the angle around the axis in `[0, 2PI)`
+fn angle_around(point: Point3, origin: Point3, axis: Vector3) -> f64 {
+    let (u, v) = frame(axis);
+    let vec = point - origin;
+    f64::atan2(vec.dot(v), vec.dot(u)).rem_euclid(2.0 * PI)
+}
+
+/// the center and the width of the widest gap between the angles
+fn widest_gap(mut angles: Vec<f64>) -> (f64, f64) {
+    angles.sort_by(f64::total_cmp);
+    let (first, last) = (angles[0], angles[angles.len() - 1]);
+    let init = (
+        last + (first + 2.0 * PI - last) / 2.0,
+        first + 2.0 * PI - last,
+    );
+    angles
+        .windows(2)
+        .fold(init, |(center, width), a| match a[1] - a[0] > width {
+            true => ((a[0] + a[1]) / 2.0, a[1] - a[0]),
+            false => (center, width),
+        })
+}
+
+/// the unit direction perpendicular to the axis at the center of the widest gap
+fn seam_direction(vertices: &[&Point3], origin: Point3, axis: Vector3) -> Vector3 {
+    let angles = vertices
+        .iter()
+        .map(|p| angle_around(**p, origin, axis))
+        .collect();
+    let (center, _) = widest_gap(angles);
+    let (u, v) = frame(axis);
+    u * center.cos() + v * center.sin()
+}
+
+/// Labels the faces by the sectors if the region wraps around the axis or the center.
+fn sector_labels(
+    positions: &[Point3],
+    polygons: &[Vec<usize>],
+    faces: &[usize],
+    primitive: FittedPrimitive,
+) -> Vec<usize> {
+    let centroid = |f: &usize| {
+        let points: Vec<Point3> = polygons[*f].iter().map(|v| positions[*v]).collect();
+        Point3::centroid(&points)
+    };
+    let vertices = faces
+        .iter()
+        .flat_map(|f| polygons[*f].iter().map(|v| positions[*v]));
+    match primitive {
+        FittedPrimitive::Plane { .. } => vec![0; faces.len()],
+        FittedPrimitive::Sphere { center, .. } => {
+            let directions: Vec<Vector3> = vertices
+                .map(|p| p - center)
+                .filter(|vec| !vec.so_small())
+                .map(|vec| vec.normalize())
+                .collect();
+            let mean = directions.iter().sum::<Vector3>();
+            let wrapping = mean.so_small() || {
+                let mean = mean.normalize();
+                directions.iter().any(|dir| dir.dot(mean) < 0.5)
+            };
+            match wrapping {
+                // the nearest ones of the six directions of the axes
+                true => faces
+                    .iter()
+                    .map(|f| {
+                        let vec = centroid(f) - center;
+                        let i = (0..3).max_by(|i, j| vec[*i].abs().total_cmp(&vec[*j].abs()));
+                        let i = i.unwrap();
+                        2 * i + (vec[i] < 0.0) as usize
+                    })
+                    .collect(),
+                false => vec![0; faces.len()],
+            }
+        }
+        FittedPrimitive::Cylinder { origin, axis, .. }
+        | FittedPrimitive::Cone {
+            apex: origin, axis, ..
+        } => {
+            let angles: Vec<f64> = vertices.map(|p| angle_around(p, origin, axis)).collect();
+            match widest_gap(angles).1 < PI / 2.0 {
+                // three sectors
+                true => faces
+                    .iter()
+                    .map(|f| {
+                        let angle = angle_around(centroid(f), origin, axis);
+                        usize::min((angle * 1.5 / PI) as usize, 2)
+                    })
+                    .collect(),
+                false => vec![0; faces.len()],
+            }
+        }
+    }
+}
+
+/// the connected components of the faces with the same labels
+fn components(polygons: &[Vec<usize>], faces: &[usize], labels: &[usize]) -> Vec<Vec<usize>> {
+    let mut edge_faces = HashMap::<[usize; 2], Vec<usize>>::default();
+    faces.iter().enumerate().for_each(|(k, f)| {
+        let polygon = &polygons[*f];
+        let len = polygon.len();
+        (0..len).for_each(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % len]);
+            edge_faces.entry([a.min(b), a.max(b)]).or_default().push(k);
+        })
+    });
+    let mut visited = vec![false; faces.len()];
+    let mut result = Vec::new();
+    (0..faces.len()).for_each(|start| {
+        if visited[start] {
+            return;
+        }
+        visited[start] = true;
+        let (mut stack, mut component) = (vec![start], Vec::new());
+        while let Some(k) = stack.pop() {
+            component.push(faces[k]);
+            let polygon = &polygons[faces[k]];
+            let len = polygon.len();
+            (0..len).for_each(|i| {
+                let (a, b) = (polygon[i], polygon[(i + 1) % len]);
+                edge_faces[&[a.min(b), a.max(b)]].iter().for_each(|l| {
+                    if !visited[*l] && labels[*l] == labels[k] {
+                        visited[*l] = true;
+                        stack.push(*l);
+                    }
+                })
+            })
+        }
+        component.sort_unstable();
+        result.push(component);
+    });
+    result
+}
+
+/// The loops of the boundary of the region, whose directions are the ones of the polygons.
+/// Returns `None` if the loops are not closed.
+fn boundary_loops(
+    polygons: &[Vec<usize>],
+    faces: &[usize],
+    region: usize,
+    halfedge_region: &HashMap<[usize; 2], usize>,
+) -> Option<Vec<Vec<usize>>> {
+    let mut halfedges = Vec::<[usize; 2]>::new();
+    let mut outgoing = HashMap::<usize, Vec<usize>>::default();
+    faces.iter().for_each(|f| {
+        let polygon = &polygons[*f];
+        let len = polygon.len();
+        (0..len).for_each(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % len]);
+            if halfedge_region.get(&[b, a]) != Some(&region) {
+                outgoing.entry(a).or_default().push(halfedges.len());
+                halfedges.push([a, b]);
+            }
+        })
+    });
+    let mut used = vec![false; halfedges.len()];
+    let mut loops = Vec::new();
+    for start in 0..halfedges.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let [first, mut current] = halfedges[start];
+        let mut boundary = vec![first];
+        while current != first {
+            boundary.push(current);
+            let next = outgoing.get(&current)?.iter().find(|i| !used[**i])?;
+            used[*next] = true;
+            current = halfedges[*next][1];
+        }
+        loops.push(boundary);
+    }
+    Some(loops)
+}
+
+/// the nearest point on the primitive and the unit normal there
+fn nearest_point(primitive: &FittedPrimitive, point: Point3) -> (Point3, Vector3) {
+    // the unit vector perpendicular to the axis toward the point
+    let radial = |origin: Point3, axis: Vector3| {
+        let vec = point - origin;
+        let vec = vec - axis * vec.dot(axis);
+        match vec.so_small() {
+            true => frame(axis).0,
+            false => vec.normalize(),
+        }
+    };
+    match *primitive {
+        FittedPrimitive::Plane { origin, normal } => {
+            (point - normal * (point - origin).dot(normal), normal)
+        }
+        FittedPrimitive::Sphere { center, radius } => {
+            let dir = match (point - center).so_small() {
+                true => Vector3::unit_x(),
+                false => (point - center).normalize(),
+            };
+            (center + dir * radius, dir)
+        }
+        FittedPrimitive::Cylinder {
+            origin,
+            axis,
+            radius,
+        } => {
+            let dir = radial(origin, axis);
+            let height = (point - origin).dot(axis);
+            (origin + axis * height + dir * radius, dir)
+        }
+        FittedPrimitive::Cone {
+            apex,
+            axis,
+            half_angle,
+        } => {
+            let dir = radial(apex, axis);
+            let (sin, cos) = half_angle.sin_cos();
+            let generator = axis * cos + dir * sin;
+            let t = f64::max((point - apex).dot(generator), 0.0);
+            (apex + generator * t, dir * cos - axis * sin)
+        }
+    }
+}
+
+/// Projects the point onto the intersection of the primitives by the Gauss-Newton method.
+/// Returns `None` if the point does not converge within `tol` from all the primitives.
+fn project(primitives: &[FittedPrimitive], point: Point3, tol: f64) -> Option<Point3> {
+    let mut point = point;
+    for _ in 0..100 {
+        let (matrix, vector, max) = primitives.iter().fold(
+            (Matrix3::zero(), Vector3::zero(), 0.0_f64),
+            |(matrix, vector, max), primitive| {
+                let (foot, normal) = nearest_point(primitive, point);
+                let dist = (foot - point).dot(normal);
+                let outer =
+                    Matrix3::from_cols(normal * normal.x, normal * normal.y, normal * normal.z);
+                (matrix + outer, vector + normal * dist, max.max(dist.abs()))
+            },
+        );
+        if max < TOLERANCE * 1.0e-3 {
+            return Some(point);
+        }
+        // The small regularization fixes the directions along the intersection.
+        let matrix = matrix + Matrix3::identity() * TOLERANCE;
+        point += matrix.invert()? * vector;
+    }
+    primitives
+        .iter()
+        .all(|primitive| primitive.distance(point) < tol)
+        .then_some(point)
+}
+
+/// The curve of the edge along the chain of the positions, from the first to the last.
+///
+/// If `surfaces` is given, the curve is the intersection curve of them. Otherwise, the curve is
+/// the line or the polyline whose points are projected onto `primitives`.
+fn chain_curve(
+    chain: &[usize],
+    vertices: &HashMap<usize, truck_modeling::Vertex>,
+    positions: &[Point3],
+    primitives: &[FittedPrimitive],
+    surfaces: Option<(Surface, Surface)>,
+    tol: f64,
+) -> Result<Curve, BRepConversionError> {
+    let len = chain.len();
+    let (p0, p1) = (
+        vertices[&chain[0]].get_point(),
+        vertices[&chain[len - 1]].get_point(),
+    );
+    // The edges between two planes are lines.
+    if surfaces.is_none() && (len == 2 || primitives.len() == 2) {
+        return Ok(Curve::Line(Line(p0, p1)));
+    }
+    let mut points = vec![p0];
+    for v in &chain[1..len - 1] {
+        let point = project(primitives, positions[*v], tol)
+            .ok_or(BRepConversionError::NotOnSurfaces(*v))?;
+        points.push(point);
+    }
+    points.push(p1);
+    Ok(match surfaces {
+        Some((surface0, surface1)) => IntersectionCurve::new_unchecked(
+            Box::new(surface0),
+            Box::new(surface1),
+            PolylineCurve(points),
+            tol,
+        )
+        .into(),
+        None => Curve::BSplineCurve(BSplineCurve::new(KnotVec::uniform_knot(1, len - 1), points)),
+    })
+}
@@ -0,0 +1,8 @@
+use super::*;
+use truck_base::eigen::symmetric_eigen;
+
+/// the normalized eigenvector of the smallest eigenvalue of the symmetric matrix
+#[inline(always)]
+pub fn smallest_eigenvector(matrix: Matrix3) -> Vector3 {
+    symmetric_eigen(matrix.into()).1[0].into()
+}
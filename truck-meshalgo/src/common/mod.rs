@@ -1,6 +1,8 @@
 use crate::*;
 
 mod adjacency;
+mod eigen;
 mod face_normal;
 pub(super) use adjacency::Adjacency;
+pub(super) use eigen::smallest_eigenvector;
 pub(super) use face_normal::FaceNormal;
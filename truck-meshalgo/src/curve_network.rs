@@ -0,0 +1,294 @@
+use crate::analyzers::connect_edges;
+use crate::common::FaceNormal;
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::f64::consts::PI;
+use truck_modeling::{BSplineCurve, Curve, Edge, KnotVec, Line, Vertex, Wire};
+
+/// The options of [`feature_curve_network`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CurveNetworkOptions {
+    /// the tolerance of fitting the curves to the chains of the feature edges. Default is `0.01`.
+    pub tolerance: f64,
+    /// the edges of the mesh are the feature edges if the angles between the normals of the
+    /// adjacent faces are more than this angle, in radians. The chains are also split at the
+    /// points where they turn more than this angle. Default is `PI / 6.0`.
+    pub feature_angle: f64,
+}
+
+impl Default for CurveNetworkOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.01,
+            feature_angle: PI / 6.0,
+        }
+    }
+}
+
+/// Extracts the sharp edges of the mesh, and fits the curves to their chains.
+///
+/// # Details
+/// The feature edges are the edges of the mesh whose dihedral angles are sharper than
+/// `options.feature_angle`, the boundary edges and the non-manifold edges. They are connected
+/// into the chains separated at the branch points, and each chain is returned as a wire.
+///
+/// The chains are split at the corners where they turn sharply, and the pieces between the
+/// corners are the edges of the wires, whose curves are
+/// - the [`Line`]s if the pieces are straight within `options.tolerance`,
+/// - the cubic [`BSplineCurve`]s fitted by the least squares otherwise, which pass the ends of
+///   the pieces and all the points of the pieces within the tolerance.
+///
+/// The wires share the vertices at the branch points, so the network of the curves is connected
+/// as the feature edges of the mesh are. The closed chains without corners are split into two
+/// edges.
+///
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+///
+/// // the cube
+/// let mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions: vec![
+///             Point3::new(0.0, 0.0, 0.0),
+///             Point3::new(1.0, 0.0, 0.0),
+///             Point3::new(1.0, 1.0, 0.0),
+///             Point3::new(0.0, 1.0, 0.0),
+///             Point3::new(0.0, 0.0, 1.0),
+///             Point3::new(1.0, 0.0, 1.0),
+///             Point3::new(1.0, 1.0, 1.0),
+///             Point3::new(0.0, 1.0, 1.0),
+///         ],
+///         ..Default::default()
+///     },
+///     Faces::from_iter(&[
+///         &[3, 2, 0], &[1, 0, 2], &[0, 1, 4], &[5, 4, 1],
+///         &[1, 2, 5], &[6, 5, 2], &[2, 3, 6], &[7, 6, 3],
+///         &[3, 0, 7], &[4, 7, 0], &[4, 5, 7], &[6, 7, 5],
+///     ]),
+/// );
+///
+/// // the twelve edges of the cube, and the diagonals of the squares are not extracted.
+/// let wires = feature_curve_network(&mesh, &Default::default());
+/// assert_eq!(wires.len(), 12);
+/// assert!(wires.iter().all(|wire| wire.len() == 1));
+/// let vertices: std::collections::HashSet<_> = wires
+///     .iter()
+///     .flat_map(|wire| wire.vertex_iter())
+///     .map(|v| v.id())
+///     .collect();
+/// assert_eq!(vertices.len(), 8);
+/// ```
+pub fn feature_curve_network(mesh: &PolygonMesh, options: &CurveNetworkOptions) -> Vec<Wire> {
+    let positions = mesh.positions();
+    let normals: Vec<Vector3> = mesh
+        .face_iter()
+        .enumerate()
+        .map(|(i, face)| FaceNormal::new(positions, face, i).normal)
+        .collect();
+    let mut edge_faces = HashMap::<[usize; 2], Vec<usize>>::default();
+    mesh.face_iter().enumerate().for_each(|(i, face)| {
+        face.iter()
+            .zip(face.iter().cycle().skip(1))
+            .filter(|(v0, v1)| v0.pos != v1.pos)
+            .for_each(|(v0, v1)| {
+                let edge = [usize::min(v0.pos, v1.pos), usize::max(v0.pos, v1.pos)];
+                edge_faces.entry(edge).or_default().push(i);
+            })
+    });
+    let mut feature_edges: Vec<[usize; 2]> = edge_faces
+        .into_iter()
+        .filter(|(_, faces)| match faces.as_slice() {
+            [i, j] => normals[*i].angle(normals[*j]).0 > options.feature_angle,
+            _ => true,
+        })
+        .map(|(edge, _)| edge)
+        .collect();
+    feature_edges.sort();
+    let mut valences = vec![0; positions.len()];
+    feature_edges
+        .iter()
+        .flatten()
+        .for_each(|i| valences[*i] += 1);
+
+    let mut vertices = HashMap::<usize, Vertex>::default();
+    let mut vertex = |i: usize| {
+        vertices
+            .entry(i)
+            .or_insert_with(|| Vertex::new(positions[i]))
+            .clone()
+    };
+    connect_edges(positions.len(), &feature_edges)
+        .into_iter()
+        .map(|chain| {
+            split_at_corners(positions, &valences, chain, options.feature_angle)
+                .into_iter()
+                .map(|piece| {
+                    let points: Vec<Point3> = piece.iter().map(|i| positions[*i]).collect();
+                    let (v0, v1) = (vertex(piece[0]), vertex(piece[piece.len() - 1]));
+                    Edge::new(&v0, &v1, fit_curve(&points, options.tolerance))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Splits the chain at the corners. The closed chain starts from a corner if exists, or is split
+/// into two halves. The branch points, whose valences are not two, are also the corners.
+fn split_at_corners(
+    positions: &[Point3],
+    valences: &[usize],
+    mut chain: Vec<usize>,
+    angle: f64,
+) -> Vec<Vec<usize>> {
+    let closed = chain.len() > 2 && chain[0] == chain[chain.len() - 1];
+    if closed {
+        chain.pop();
+    }
+    let len = chain.len();
+    let is_corner = |k: usize| {
+        let prev = positions[chain[(k + len - 1) % len]];
+        let (current, next) = (positions[chain[k]], positions[chain[(k + 1) % len]]);
+        valences[chain[k]] != 2 || (current - prev).angle(next - current).0 > angle
+    };
+    let mut corners: Vec<usize> = match closed {
+        true => (0..len).filter(|k| is_corner(*k)).collect(),
+        false => (1..len - 1).filter(|k| is_corner(*k)).collect(),
+    };
+    if closed {
+        let start = corners.first().copied().unwrap_or(0);
+        chain.rotate_left(start);
+        corners.iter_mut().for_each(|k| *k -= start);
+        if corners.len() < 2 {
+            corners = vec![0, len / 2];
+        }
+        chain.push(chain[0]);
+        corners.remove(0);
+    }
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    corners
+        .into_iter()
+        .chain([chain.len() - 1])
+        .for_each(|end| {
+            pieces.push(chain[start..=end].to_vec());
+            start = end;
+        });
+    pieces
+}
+
+/// Fits the curve to the points, which passes the ends and all the points within `tol`.
+fn fit_curve(points: &[Point3], tol: f64) -> Curve {
+    let (front, back) = (points[0], points[points.len() - 1]);
+    let dir = back - front;
+    let straight = points.iter().all(|pt| {
+        let t = f64::clamp((pt - front).dot(dir) / dir.magnitude2(), 0.0, 1.0);
+        (front + dir * t).distance(*pt) < tol
+    });
+    if straight {
+        return Curve::Line(Line(front, back));
+    }
+    // the chord length parameters
+    let mut params = vec![0.0];
+    points.windows(2).for_each(|pts| {
+        let length = params[params.len() - 1] + pts[0].distance(pts[1]);
+        params.push(length);
+    });
+    let total = params[params.len() - 1];
+    params.iter_mut().for_each(|t| *t /= total);
+
+    let degree = usize::min(3, points.len() - 1);
+    (degree + 1..points.len())
+        .find_map(|len| {
+            let curve = least_squares(points, &params, degree, len)?;
+            let fitted = points
+                .iter()
+                .zip(&params)
+                .all(|(pt, t)| curve.subs(*t).distance(*pt) < tol);
+            match fitted {
+                true => Some(curve),
+                false => None,
+            }
+        })
+        .map(Curve::BSplineCurve)
+        .unwrap_or_else(|| {
+            // the polyline through all the points
+            let mut knots = vec![0.0];
+            knots.extend(&params);
+            knots.push(1.0);
+            Curve::BSplineCurve(BSplineCurve::new(KnotVec::from(knots), points.to_vec()))
+        })
+}
+
+/// The B-spline curve with `len` control points fitted to the points by the least squares,
+/// whose ends are the ends of the points.
+fn least_squares(
+    points: &[Point3],
+    params: &[f64],
+    degree: usize,
+    len: usize,
+) -> Option<BSplineCurve<Point3>> {
+    // the knots such that every span contains the parameters, by Piegl and Tiller
+    let num = points.len();
+    let d = num as f64 / (len - degree) as f64;
+    let mut knots = vec![0.0; degree + 1];
+    (1..len - degree).for_each(|j| {
+        let x = j as f64 * d;
+        let (i, alpha) = (x.floor() as usize, x.fract());
+        knots.push((1.0 - alpha) * params[i - 1] + alpha * params[i]);
+    });
+    knots.resize(len + degree + 1, 1.0);
+    let knot_vec = KnotVec::from(knots);
+
+    let (front, back) = (points[0], points[num - 1]);
+    let dim = len - 2;
+    let mut matrix = vec![vec![0.0; dim]; dim];
+    let mut vector = vec![Vector3::zero(); dim];
+    points[1..num - 1]
+        .iter()
+        .zip(&params[1..num - 1])
+        .for_each(|(pt, t)| {
+            let basis = knot_vec.bspline_basis_functions(degree, *t);
+            let residual = pt.to_vec() - front.to_vec() * basis[0] - back.to_vec() * basis[len - 1];
+            (0..dim).for_each(|i| {
+                (0..dim).for_each(|j| matrix[i][j] += basis[i + 1] * basis[j + 1]);
+                vector[i] += residual * basis[i + 1];
+            });
+        });
+    let mut control_points = vec![front];
+    control_points.extend(
+        solve_linear(matrix, vector)?
+            .into_iter()
+            .map(Point3::from_vec),
+    );
+    control_points.push(back);
+    Some(BSplineCurve::new(knot_vec, control_points))
+}
+
+/// Solves `matrix * x = vector` by the Gaussian elimination with the partial pivoting.
+fn solve_linear(mut matrix: Vec<Vec<f64>>, mut vector: Vec<Vector3>) -> Option<Vec<Vector3>> {
+    let n = vector.len();
+    for i in 0..n {
+        let pivot = (i..n).max_by(|a, b| matrix[*a][i].abs().total_cmp(&matrix[*b][i].abs()))?;
+        if matrix[pivot][i].abs() < f64::EPSILON {
+            return None;
+        }
+        matrix.swap(i, pivot);
+        vector.swap(i, pivot);
+        for j in i + 1..n {
+            let ratio = matrix[j][i] / matrix[i][i];
+            let (upper, lower) = matrix.split_at_mut(j);
+            lower[0][i..]
+                .iter_mut()
+                .zip(&upper[i][i..])
+                .for_each(|(x, y)| *x -= ratio * y);
+            let v = vector[i];
+            vector[j] -= v * ratio;
+        }
+    }
+    for i in (0..n).rev() {
+        let sum: Vector3 = (i + 1..n).map(|k| vector[k] * matrix[i][k]).sum();
+        vector[i] = (vector[i] - sum) / matrix[i][i];
+    }
+    Some(vector)
+}
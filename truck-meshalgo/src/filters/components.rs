@@ -0,0 +1,94 @@
+use super::*;
+use crate::analyzers::Splitting;
+
+/// Filters for removing the connected components.
+///
+/// The components are the ones by [`Splitting::components`] without normals, i.e. the
+/// polygons are connected if they share an edge by the position indices. Apply
+/// [`OptimizingFilter::put_together_same_attrs`] to separated meshes beforehand, and
+/// [`OptimizingFilter::remove_unused_attrs`] afterward if the attributes are to be compacted.
+pub trait ComponentFilters {
+    /// Removes all components except the one with the most faces.
+    /// If there are several largest components, the first one is kept.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(5.0, 0.0, 0.0),
+    ///             Point3::new(6.0, 0.0, 0.0),
+    ///             Point3::new(5.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[4, 5, 6], &[0, 1, 2], &[2, 1, 3]]),
+    /// );
+    /// mesh.keep_largest_component();
+    /// assert_eq!(mesh.faces().len(), 2);
+    /// assert_eq!(mesh.faces()[0], Faces::from_iter(&[&[0, 1, 2]])[0]);
+    /// ```
+    fn keep_largest_component(&mut self) -> &mut Self;
+    /// Removes the components with less than `min_faces` faces.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(5.0, 0.0, 0.0),
+    ///             Point3::new(6.0, 0.0, 0.0),
+    ///             Point3::new(5.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[4, 5, 6], &[0, 1, 2], &[2, 1, 3]]),
+    /// );
+    /// mesh.remove_small_components(2);
+    /// assert_eq!(mesh.faces().len(), 2);
+    /// mesh.remove_small_components(3);
+    /// assert_eq!(mesh.faces().len(), 0);
+    /// ```
+    fn remove_small_components(&mut self, min_faces: usize) -> &mut Self;
+}
+
+impl ComponentFilters for PolygonMesh {
+    fn keep_largest_component(&mut self) -> &mut Self {
+        let components = self.components(false);
+        // `max_by_key` returns the last one of the maxima.
+        let largest = components
+            .into_iter()
+            .rev()
+            .max_by_key(|component| component.len());
+        if let Some(largest) = largest {
+            retain_faces(self, largest);
+        }
+        self
+    }
+
+    fn remove_small_components(&mut self, min_faces: usize) -> &mut Self {
+        let indices: Vec<usize> = self
+            .components(false)
+            .into_iter()
+            .filter(|component| component.len() >= min_faces)
+            .flatten()
+            .collect();
+        retain_faces(self, indices);
+        self
+    }
+}
+
+/// Retains the faces of `indices`, keeping the original order.
+fn retain_faces(mesh: &mut PolygonMesh, mut indices: Vec<usize>) {
+    indices.sort_unstable();
+    let faces: Faces = indices.iter().map(|i| &mesh.faces()[*i]).collect();
+    *mesh.debug_editor().faces = faces;
+}
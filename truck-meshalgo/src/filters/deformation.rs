@@ -0,0 +1,500 @@
+use super::*;
+use cgmath::Quaternion;
+use rustc_hash::FxHashMap as HashMap;
+
+/// Interactive as-rigid-as-possible (ARAP) deformation of polygon meshes.
+///
+/// Pin the handle vertices by [`ArapDeformer::pin`], move them by pinning again, and call
+/// [`ArapDeformer::solve`]. The other vertices are moved so that the neighborhood of each
+/// vertex is transformed as rigidly as possible. The Laplacian weights are computed once by
+/// [`ArapDeformer::new`], and each solve is warm-started from the last one, so that the
+/// deformation can be previewed while dragging handles.
+///
+/// # Details
+/// The energy `Σ_i Σ_j w_ij |(p'_i - p'_j) - R_i (p_i - p_j)|^2` is minimized by alternating
+/// the local step, fitting the rotations `R_i`, and the global step, solving the Laplacian
+/// system by the conjugate gradient method. The weights `w_ij` are the cotangent weights,
+/// clamped to be positive.
+///
+/// # Remarks
+/// - The faces are connected by the position indices. Apply
+/// [`OptimizingFilter::put_together_same_attrs`] to separated meshes beforehand.
+/// - The connected components without handles are only rotated and translated in place.
+/// - The normals are not updated by [`ArapDeformer::apply`].
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+///
+/// // a strip along the x-axis
+/// let positions = (0..22)
+///     .map(|i| Point3::new((i / 2) as f64, (i % 2) as f64, 0.0))
+///     .collect();
+/// let faces = (0..10)
+///     .map(|i| [2 * i, 2 * i + 2, 2 * i + 3, 2 * i + 1])
+///     .collect();
+/// let mut mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions,
+///         ..Default::default()
+///     },
+///     faces,
+/// );
+///
+/// let mut deformer = ArapDeformer::new(&mesh);
+/// // fix the left end
+/// deformer.pin(0, Point3::new(0.0, 0.0, 0.0));
+/// deformer.pin(1, Point3::new(0.0, 1.0, 0.0));
+/// // lift the right end
+/// deformer.pin(20, Point3::new(10.0, 0.0, 2.0));
+/// deformer.pin(21, Point3::new(10.0, 1.0, 2.0));
+/// deformer.solve(10).apply(&mut mesh);
+///
+/// assert_eq!(mesh.positions()[21], Point3::new(10.0, 1.0, 2.0));
+/// // the middle is lifted smoothly
+/// let z = mesh.positions()[10].z;
+/// assert!(0.5 < z && z < 1.5, "{z}");
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArapDeformer {
+    rest: Vec<Point3>,
+    current: Vec<Point3>,
+    neighbors: Vec<Vec<(usize, f64)>>,
+    handles: Vec<Option<Point3>>,
+    rotations: Vec<Quaternion<f64>>,
+}
+
+impl ArapDeformer {
+    /// Creates the deformer of `mesh` without handles.
+    pub fn new(mesh: &PolygonMesh) -> Self {
+        let rest = mesh.positions().clone();
+        let len = rest.len();
+        let mut weights = HashMap::<(usize, usize), f64>::default();
+        mesh.faces().triangle_iter().for_each(|tri| {
+            let idx = [tri[0].pos, tri[1].pos, tri[2].pos];
+            (0..3).for_each(|k| {
+                let (a, b, c) = (idx[k], idx[(k + 1) % 3], idx[(k + 2) % 3]);
+                let (u, v) = (rest[b] - rest[a], rest[c] - rest[a]);
+                let cross = u.cross(v).magnitude();
+                let cot = match cross > TOLERANCE2 {
+                    true => u.dot(v) / cross,
+                    false => 0.0,
+                };
+                let key = if b < c { (b, c) } else { (c, b) };
+                *weights.entry(key).or_insert(0.0) += 0.5 * cot;
+            });
+        });
+        let mut neighbors = vec![Vec::new(); len];
+        weights.into_iter().for_each(|((i, j), w)| {
+            let w = f64::max(w, MIN_WEIGHT);
+            neighbors[i].push((j, w));
+            neighbors[j].push((i, w));
+        });
+        ArapDeformer {
+            current: rest.clone(),
+            rest,
+            neighbors,
+            handles: vec![None; len],
+            rotations: vec![Quaternion::new(1.0, 0.0, 0.0, 0.0); len],
+        }
+    }
+
+    /// Pins the vertex of the position index `index` at `position`. If the vertex has already
+    /// been pinned, the handle is moved.
+    /// # Panics
+    /// Panics if `index` is out of range.
+    #[inline(always)]
+    pub fn pin(&mut self, index: usize, position: Point3) -> &mut Self {
+        self.handles[index] = Some(position);
+        self.current[index] = position;
+        self
+    }
+
+    /// Releases the vertex of the position index `index`.
+    /// # Panics
+    /// Panics if `index` is out of range.
+    #[inline(always)]
+    pub fn unpin(&mut self, index: usize) -> &mut Self {
+        self.handles[index] = None;
+        self
+    }
+
+    /// Returns the iterator of the pinned position indices and their positions.
+    #[inline(always)]
+    pub fn handles(&self) -> impl Iterator<Item = (usize, Point3)> + '_ {
+        self.handles
+            .iter()
+            .enumerate()
+            .filter_map(|(i, handle)| handle.map(|p| (i, p)))
+    }
+
+    /// Returns the current deformed positions.
+    #[inline(always)]
+    pub fn positions(&self) -> &[Point3] { &self.current }
+
+    /// Discards the deformation and the handles.
+    pub fn reset(&mut self) -> &mut Self {
+        self.current = self.rest.clone();
+        self.handles.iter_mut().for_each(|handle| *handle = None);
+        self.rotations
+            .iter_mut()
+            .for_each(|q| *q = Quaternion::new(1.0, 0.0, 0.0, 0.0));
+        self
+    }
+
+    /// Runs the global step and `iterations` pairs of the local and global steps.
+    pub fn solve(&mut self, iterations: usize) -> &mut Self {
+        if self.handles.iter().all(Option::is_none) {
+            return self;
+        }
+        // The first global step with the last rotations is the initial guess, which is
+        // the Laplacian editing at the first solve.
+        self.solve_positions();
+        for _ in 0..iterations {
+            self.fit_rotations();
+            self.solve_positions();
+        }
+        self
+    }
+
+    /// Replaces the positions of `mesh` by the current deformed positions.
+    /// # Panics
+    /// Panics if the number of the positions of `mesh` is not the one of the mesh
+    /// given to [`ArapDeformer::new`].
+    pub fn apply(&self, mesh: &mut PolygonMesh) {
+        assert_eq!(
+            mesh.positions().len(),
+            self.current.len(),
+            "the mesh is not the one of the deformer."
+        );
+        mesh.debug_editor()
+            .attributes
+            .positions
+            .copy_from_slice(&self.current);
+    }
+
+    /// local step: the rotations maximizing `Σ_j w_ij (R_i e_ij)・e'_ij`, by the method of
+    /// Müller et al., "A Robust Method to Extract the Rotational Part of Deformations".
+    fn fit_rotations(&mut self) {
+        let (rest, current) = (&self.rest, &self.current);
+        self.rotations
+            .iter_mut()
+            .zip(&self.neighbors)
+            .enumerate()
+            .for_each(|(i, (q, neighbors))| {
+                let cols = neighbors.iter().fold([Vector3::zero(); 3], |cols, (j, w)| {
+                    let (e, e1) = (rest[i] - rest[*j], current[i] - current[*j]);
+                    [
+                        cols[0] + e1 * (w * e.x),
+                        cols[1] + e1 * (w * e.y),
+                        cols[2] + e1 * (w * e.z),
+                    ]
+                });
+                for _ in 0..ROTATION_ITERATIONS {
+                    let r = Matrix3::from(*q);
+                    let omega = (0..3).fold(Vector3::zero(), |sum, k| sum + r[k].cross(cols[k]));
+                    let denom = (0..3).fold(0.0, |sum, k| sum + r[k].dot(cols[k])).abs();
+                    let omega = omega / (denom + 1.0e-9);
+                    let angle = omega.magnitude();
+                    if angle < 1.0e-9 {
+                        break;
+                    }
+                    *q = (Quaternion::from_axis_angle(omega / angle, Rad(angle)) * *q).normalize();
+                }
+            });
+    }
+
+    /// global step: the Laplacian system `Σ_j w_ij (p'_i - p'_j) = Σ_j w_ij (R_i + R_j) e_ij / 2`
+    /// of the free vertices, by the conjugate gradient method.
+    fn solve_positions(&mut self) {
+        let rotations: Vec<Matrix3> = self.rotations.iter().map(|q| Matrix3::from(*q)).collect();
+        let (rest, handles, neighbors) = (&self.rest, &self.handles, &self.neighbors);
+        let rhs: Vec<Vector3> = (0..rest.len())
+            .map(|i| match handles[i] {
+                Some(_) => Vector3::zero(),
+                None => neighbors[i].iter().fold(Vector3::zero(), |sum, (j, w)| {
+                    let e = rest[i] - rest[*j];
+                    let mut sum = sum + (rotations[i] + rotations[*j]) * e * (w / 2.0);
+                    if let Some(p) = handles[*j] {
+                        sum += p.to_vec() * *w;
+                    }
+                    sum
+                }),
+            })
+            .collect();
+        // the Laplacian restricted to the free vertices
+        let laplacian = |x: &[Vector3]| -> Vec<Vector3> {
+            (0..x.len())
+                .map(|i| match handles[i] {
+                    Some(_) => Vector3::zero(),
+                    None => neighbors[i]
+                        .iter()
+                        .fold(Vector3::zero(), |sum, (j, w)| match handles[*j] {
+                            Some(_) => sum + x[i] * *w,
+                            None => sum + (x[i] - x[*j]) * *w,
+                        }),
+                })
+                .collect()
+        };
+        let dot =
+            |a: &[Vector3], b: &[Vector3]| -> f64 { a.iter().zip(b).map(|(a, b)| a.dot(*b)).sum() };
+
+        let mut x: Vec<Vector3> = self
+            .current
+            .iter()
+            .zip(handles)
+            .map(|(p, handle)| match handle {
+                Some(_) => Vector3::zero(),
+                None => p.to_vec(),
+            })
+            .collect();
+        let lx = laplacian(&x);
+        let mut r: Vec<Vector3> = rhs.iter().zip(&lx).map(|(b, lx)| b - lx).collect();
+        let mut d = r.clone();
+        let mut rr = dot(&r, &r);
+        let threshold = dot(&rhs, &rhs) * 1.0e-20;
+        for _ in 0..x.len() {
+            if rr <= threshold {
+                break;
+            }
+            let ld = laplacian(&d);
+            let alpha = rr / dot(&d, &ld);
+            x.iter_mut().zip(&d).for_each(|(x, d)| *x += d * alpha);
+            r.iter_mut().zip(&ld).for_each(|(r, ld)| *r -= ld * alpha);
+            let rr_new = dot(&r, &r);
+            let beta = rr_new / rr;
+            d.iter_mut().zip(&r).for_each(|(d, r)| *d = r + *d * beta);
+            rr = rr_new;
+        }
+        self.current
+            .iter_mut()
+            .zip(x)
+            .zip(handles)
+            .for_each(|((p, x), handle)| {
+                *p = match handle {
+                    Some(handle) => *handle,
+                    None => Point3::from_vec(x),
+                }
+            });
+    }
+}
+
+/// The lattice of the free-form deformation.
+///
+/// The space in the bounding box is parameterized by the trivariate Bernstein polynomials
+/// whose control points are the lattice points. Moving the control points deforms the space,
+/// and the meshes in it by [`Deformation::deform_by_lattice`]. A cage of the mesh is given
+/// as the lattice of the bounding box.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// let range = BoundingBox::from_iter([Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0)]);
+/// let mut lattice = Lattice::new(range, [1, 1, 2]);
+/// // the initial lattice does not deform the space.
+/// let p = Point3::new(0.2, 0.3, 0.4);
+/// assert!(lattice.transform_point(p).near(&p));
+///
+/// // swell the middle
+/// for (i, j) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+///     let p = lattice.control_point([i, j, 1]);
+///     let q = Point3::new(0.5, 0.5, 0.5) + (p - Point3::new(0.5, 0.5, 0.5)) * 2.0;
+///     lattice.set_control_point([i, j, 1], q);
+/// }
+/// let p = lattice.transform_point(Point3::new(1.0, 1.0, 0.5));
+/// assert!(p.near(&Point3::new(1.25, 1.25, 0.5)));
+/// // the points out of the bounding box are not moved.
+/// let p = Point3::new(2.0, 0.0, 0.0);
+/// assert_eq!(lattice.transform_point(p), p);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Lattice {
+    range: BoundingBox<Point3>,
+    divisions: [usize; 3],
+    control_points: Vec<Point3>,
+}
+
+impl Lattice {
+    /// Creates the lattice of `range` with `divisions` cells along each axis, which are
+    /// the degrees of the Bernstein polynomials. The control points are on the uniform grid.
+    /// # Panics
+    /// Panics if `range` is empty or some of `divisions` is zero.
+    pub fn new(range: BoundingBox<Point3>, divisions: [usize; 3]) -> Self {
+        assert!(!range.is_empty(), "the range is empty.");
+        assert!(
+            divisions.iter().all(|n| *n > 0),
+            "the divisions must be positive."
+        );
+        let (min, diag) = (*range.min(), range.diagonal());
+        let [l, m, n] = divisions;
+        let control_points = (0..=l)
+            .flat_map(|i| (0..=m).flat_map(move |j| (0..=n).map(move |k| (i, j, k))))
+            .map(|(i, j, k)| {
+                min + Vector3::new(
+                    diag.x * i as f64 / l as f64,
+                    diag.y * j as f64 / m as f64,
+                    diag.z * k as f64 / n as f64,
+                )
+            })
+            .collect();
+        Lattice {
+            range,
+            divisions,
+            control_points,
+        }
+    }
+
+    /// Returns the range of the deformation.
+    #[inline(always)]
+    pub fn range(&self) -> &BoundingBox<Point3> { &self.range }
+
+    /// Returns the numbers of the cells along the axes.
+    #[inline(always)]
+    pub fn divisions(&self) -> [usize; 3] { self.divisions }
+
+    #[inline(always)]
+    fn index(&self, [i, j, k]: [usize; 3]) -> usize {
+        let [l, m, n] = self.divisions;
+        assert!(i <= l && j <= m && k <= n, "the index is out of range.");
+        (i * (m + 1) + j) * (n + 1) + k
+    }
+
+    /// Returns the control point of the index `[i, j, k]`.
+    /// # Panics
+    /// Panics if the index is more than the divisions.
+    #[inline(always)]
+    pub fn control_point(&self, idx: [usize; 3]) -> Point3 { self.control_points[self.index(idx)] }
+
+    /// Moves the control point of the index `[i, j, k]`.
+    /// # Panics
+    /// Panics if the index is more than the divisions.
+    #[inline(always)]
+    pub fn set_control_point(&mut self, idx: [usize; 3], point: Point3) -> &mut Self {
+        let idx = self.index(idx);
+        self.control_points[idx] = point;
+        self
+    }
+
+    /// Returns the deformed point. The points out of the range are not moved.
+    pub fn transform_point(&self, point: Point3) -> Point3 {
+        let (min, diag) = (*self.range.min(), self.range.diagonal());
+        let t: Vec<f64> = (0..3)
+            .map(|i| match diag[i] > TOLERANCE {
+                true => (point[i] - min[i]) / diag[i],
+                false => 0.0,
+            })
+            .collect();
+        let outside = (0..3).any(|i| {
+            let tol = TOLERANCE / f64::max(diag[i], TOLERANCE);
+            t[i] < -tol || 1.0 + tol < t[i] || (diag[i] <= TOLERANCE && !point[i].near(&min[i]))
+        });
+        if outside {
+            return point;
+        }
+        let [l, m, n] = self.divisions;
+        let (bu, bv, bw) = (bernstein(l, t[0]), bernstein(m, t[1]), bernstein(n, t[2]));
+        let mut res = Vector3::zero();
+        for (i, bu) in bu.iter().enumerate() {
+            for (j, bv) in bv.iter().enumerate() {
+                for (k, bw) in bw.iter().enumerate() {
+                    let p = self.control_points[(i * (m + 1) + j) * (n + 1) + k];
+                    res += p.to_vec() * (bu * bv * bw);
+                }
+            }
+        }
+        Point3::from_vec(res)
+    }
+}
+
+/// the Bernstein polynomials of degree `n` at `t`
+fn bernstein(n: usize, t: f64) -> Vec<f64> {
+    let mut res = vec![0.0; n + 1];
+    res[0] = 1.0;
+    for i in 1..=n {
+        for j in (1..=i).rev() {
+            res[j] = res[j] * (1.0 - t) + res[j - 1] * t;
+        }
+        res[0] *= 1.0 - t;
+    }
+    res
+}
+
+/// Deformations of polygon meshes.
+pub trait Deformation {
+    /// Deforms the mesh by [`ArapDeformer`] with the handles `(position index, position)`.
+    /// # Remarks
+    /// To move the handles interactively, use [`ArapDeformer`] directly, which keeps
+    /// the precomputed weights and the last solution.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2], &[2, 1, 3]]),
+    /// );
+    /// // the translation is rigid.
+    /// let v = Vector3::new(1.0, 2.0, 3.0);
+    /// mesh.deform_arap(&[(0, Point3::new(0.0, 0.0, 0.0) + v), (3, Point3::new(1.0, 1.0, 0.0) + v)], 5);
+    /// assert!(mesh.positions()[1].near(&(Point3::new(1.0, 0.0, 0.0) + v)));
+    /// assert!(mesh.positions()[2].near(&(Point3::new(0.0, 1.0, 0.0) + v)));
+    /// ```
+    fn deform_arap(&mut self, handles: &[(usize, Point3)], iterations: usize) -> &mut Self;
+    /// Moves the positions by the free-form deformation of `lattice`.
+    /// The normals are not updated.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2]]),
+    /// );
+    /// let range = BoundingBox::from_iter([Point3::new(0.0, 0.0, -1.0), Point3::new(1.0, 1.0, 1.0)]);
+    /// let mut lattice = Lattice::new(range, [1, 1, 1]);
+    /// // shear
+    /// for (j, k) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+    ///     let p = lattice.control_point([1, j, k]);
+    ///     lattice.set_control_point([1, j, k], p + Vector3::new(0.0, 0.0, 1.0));
+    /// }
+    /// mesh.deform_by_lattice(&lattice);
+    /// assert!(mesh.positions()[1].near(&Point3::new(1.0, 0.0, 1.0)));
+    /// assert!(mesh.positions()[2].near(&Point3::new(0.0, 1.0, 0.0)));
+    /// ```
+    fn deform_by_lattice(&mut self, lattice: &Lattice) -> &mut Self;
+}
+
+impl Deformation for PolygonMesh {
+    fn deform_arap(&mut self, handles: &[(usize, Point3)], iterations: usize) -> &mut Self {
+        let mut deformer = ArapDeformer::new(self);
+        handles.iter().for_each(|(i, p)| {
+            deformer.pin(*i, *p);
+        });
+        deformer.solve(iterations).apply(self);
+        self
+    }
+    fn deform_by_lattice(&mut self, lattice: &Lattice) -> &mut Self {
+        self.debug_editor()
+            .attributes
+            .positions
+            .iter_mut()
+            .for_each(|p| *p = lattice.transform_point(*p));
+        self
+    }
+}
+
+/// the lower bound of the cotangent weights, which keeps the system positive definite
+const MIN_WEIGHT: f64 = 1.0e-3;
+/// the maximum number of the iterations of fitting a rotation
+const ROTATION_ITERATIONS: usize = 10;
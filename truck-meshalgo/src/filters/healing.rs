@@ -0,0 +1,497 @@
+use super::*;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use truck_base::disjoint_set::DisjointSet;
+
+/// The change log of [`Healing::heal_non_manifold`].
+///
+/// The indices of faces are the ones before healing, and the indices of positions are the ones after healing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HealingLog {
+    /// the indices of removed faces whose areas are zero
+    pub zero_area_faces: Vec<usize>,
+    /// the indices of removed faces which duplicate another preceding face
+    pub duplicate_faces: Vec<usize>,
+    /// the non-manifold edges which are split, represented by the pairs of position indices
+    pub split_edges: Vec<[usize; 2]>,
+    /// the pairs of the index of the non-manifold position and the index of its new copy
+    pub duplicated_vertices: Vec<(usize, usize)>,
+}
+
+impl HealingLog {
+    /// Returns `true` if the mesh has not been changed.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.zero_area_faces.is_empty()
+            && self.duplicate_faces.is_empty()
+            && self.split_edges.is_empty()
+            && self.duplicated_vertices.is_empty()
+    }
+}
+
+/// Filters for repairing non-manifold meshes
+pub trait Healing {
+    /// Makes the mesh manifold and returns the change log.
+    ///
+    /// The following operations are executed in order.
+    /// 1. Removes the faces whose areas are zero.
+    /// 1. Removes the faces which have the same vertices as another face up to rotation and orientation.
+    /// 1. Splits the edges shared by three or more faces. The faces around such an edge are connected
+    ///    in pairs bounding the same wedge.
+    /// 1. Duplicates the positions whose neighborhoods are not connected fans, one copy for each fan.
+    ///
+    /// Texture coordinates and normals are not changed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // two triangles sharing only one vertex, and a duplicated triangle
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(-1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, -1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[[0, 1, 2], [0, 3, 4], [1, 2, 0]]),
+    /// );
+    ///
+    /// let log = mesh.heal_non_manifold();
+    /// assert_eq!(log.duplicate_faces, vec![2]);
+    /// assert_eq!(log.duplicated_vertices, vec![(0, 5)]);
+    /// assert_eq!(mesh.faces().len(), 2);
+    /// assert_eq!(mesh.positions().len(), 6);
+    /// assert_eq!(mesh.positions()[5], mesh.positions()[0]);
+    /// ```
+    fn heal_non_manifold(&mut self) -> HealingLog;
+    /// Makes the winding of faces consistent in each connected component, and makes closed components
+    /// face outward.
+    ///
+    /// The orientation of the first face is propagated to the whole component across the manifold edges.
+    /// Then, each closed component is flipped if the sign of its volume does not match the parity of
+    /// the number of the closed components surrounding it, i.e. the inner shells of hollow solids face inward.
+    /// Open components keep the orientation of their first faces.
+    ///
+    /// Normals are not changed. Run [`NormalFilters::make_normal_compatible_to_face`] if needed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_topology::shell::ShellCondition;
+    /// let positions = (0..8)
+    ///     .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+    ///     .collect();
+    /// // a cube with mixed winding
+    /// let faces = Faces::from_iter(&[
+    ///     [0, 1, 3, 2],
+    ///     [0, 1, 5, 4],
+    ///     [0, 4, 6, 2],
+    ///     [7, 5, 1, 3],
+    ///     [7, 3, 2, 6],
+    ///     [7, 5, 4, 6],
+    /// ]);
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions,
+    ///         ..Default::default()
+    ///     },
+    ///     faces,
+    /// );
+    /// assert_eq!(mesh.shell_condition(), ShellCondition::Regular);
+    ///
+    /// mesh.fix_orientation();
+    /// assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    /// // the bottom face is flipped to face downward
+    /// let bottom: Vec<usize> = mesh.faces()[0].iter().map(|v| v.pos).collect();
+    /// assert_eq!(bottom, vec![2, 3, 1, 0]);
+    /// ```
+    fn fix_orientation(&mut self) -> &mut Self;
+    /// Runs [`OptimizingFilter::cleanup`], [`Healing::heal_non_manifold`], and
+    /// [`Healing::fix_orientation`] in order, and returns the report of the defects, the fixes,
+    /// and the residual defects.
+    ///
+    /// The attributes are not welded. Run [`OptimizingFilter::put_together_same_attrs`] before
+    /// repairing if needed. See [`RepairReport`] for the JSON export.
+    fn repair(&mut self) -> RepairReport;
+}
+
+impl Healing for PolygonMesh {
+    fn heal_non_manifold(&mut self) -> HealingLog {
+        let mut log = HealingLog::default();
+        let mut mesh = self.debug_editor();
+        let PolygonMeshEditor {
+            attributes: StandardAttributes { positions, .. },
+            faces,
+            ..
+        } = &mut mesh;
+
+        let mut registered = HashSet::default();
+        let mut new_faces = Faces::default();
+        faces.face_iter().enumerate().for_each(|(i, face)| {
+            if is_zero_area(positions, face) {
+                log.zero_area_faces.push(i);
+            } else if !registered.insert(face_key(face)) {
+                log.duplicate_faces.push(i);
+            } else {
+                new_faces.push(face);
+            }
+        });
+        **faces = new_faces;
+
+        let mut split_edges = HashSet::default();
+        loop {
+            let edge_map = edge_map(faces);
+            let connections = connections(positions, faces, &edge_map, &mut split_edges);
+            split_vertices(positions, faces, &connections, &mut log);
+            if !isolate_remaining_faces(positions, faces, &mut log) {
+                break;
+            }
+        }
+        log.split_edges = split_edges.into_iter().collect();
+        log.split_edges.sort();
+        log
+    }
+    fn fix_orientation(&mut self) -> &mut Self {
+        reorient(self);
+        self
+    }
+    fn repair(&mut self) -> RepairReport {
+        let mut report = RepairReport::default();
+        report.push_cleanup(&self.cleanup());
+        report.push_healing(&self.heal_non_manifold());
+        report.push_orientation(&reorient(self));
+        report.residuals = Some(RepairResiduals::measure(self));
+        report
+    }
+}
+
+/// Fixes the orientation as [`Healing::fix_orientation`], and returns the indices of the flipped faces.
+fn reorient(mesh: &mut PolygonMesh) -> Vec<usize> {
+    {
+        let mut mesh = mesh.debug_editor();
+        let PolygonMeshEditor {
+            attributes: StandardAttributes { positions, .. },
+            faces,
+            ..
+        } = &mut mesh;
+        let (components, mut flipped) = orient_components(faces);
+        let closed_components: Vec<&Vec<usize>> = components
+            .iter()
+            .filter_map(|(component, closed)| closed.then_some(component))
+            .collect();
+        let flips: Vec<bool> = closed_components
+            .iter()
+            .enumerate()
+            .map(|(i, component)| {
+                let point = positions[faces[component[0]][0].pos];
+                let depth = closed_components
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, other)| {
+                        *j != i && winding_number(positions, faces, other, point).abs() > 0.5
+                    })
+                    .count();
+                (signed_volume(positions, faces, component) < 0.0) != (depth % 2 == 1)
+            })
+            .collect();
+        closed_components
+            .into_iter()
+            .zip(flips)
+            .filter(|(_, flip)| *flip)
+            .for_each(|(component, _)| {
+                component.iter().for_each(|face_idx| {
+                    faces[*face_idx].reverse();
+                    flipped[*face_idx] = !flipped[*face_idx];
+                })
+            });
+        (0..flipped.len()).filter(|i| flipped[*i]).collect()
+    }
+}
+
+pub(super) fn is_zero_area(positions: &[Point3], face: &[Vertex]) -> bool {
+    let p = |v: &Vertex| positions[v.pos];
+    let (normal, max_length2) = face.iter().zip(face.iter().cycle().skip(1)).fold(
+        (Vector3::zero(), 0.0_f64),
+        |(normal, len2), (v0, v1)| {
+            let (p0, p1) = (p(v0), p(v1));
+            let normal = normal + p0.to_vec().cross(p1.to_vec());
+            (normal, f64::max(len2, p0.distance2(p1)))
+        },
+    );
+    // the height of the polygon: twice the area divided by the longest edge.
+    max_length2.so_small2() || (normal.magnitude() / max_length2.sqrt()).so_small()
+}
+
+/// The indices of positions rotated to begin at the minimum one, reversed if the reversed face is smaller.
+pub(super) fn face_key(face: &[Vertex]) -> Vec<usize> {
+    let len = face.len();
+    let (min_idx, _) = face
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, v)| v.pos)
+        .expect("empty face");
+    let forward: Vec<usize> = (0..len).map(|i| face[(min_idx + i) % len].pos).collect();
+    let backward: Vec<usize> = (0..len)
+        .map(|i| face[(min_idx + len - i) % len].pos)
+        .collect();
+    std::cmp::min(forward, backward)
+}
+
+/// The map from the edges to the incident faces with their orientations.
+pub(super) fn edge_map(faces: &Faces) -> HashMap<[usize; 2], Vec<(usize, bool)>> {
+    let mut edge_map = HashMap::<[usize; 2], Vec<(usize, bool)>>::default();
+    faces.face_iter().enumerate().for_each(|(i, face)| {
+        face.iter()
+            .zip(face.iter().cycle().skip(1))
+            .filter(|(v0, v1)| v0.pos != v1.pos)
+            .for_each(|(v0, v1)| {
+                let ori = v0.pos < v1.pos;
+                let key = if ori {
+                    [v0.pos, v1.pos]
+                } else {
+                    [v1.pos, v0.pos]
+                };
+                edge_map.entry(key).or_default().push((i, ori));
+            })
+    });
+    edge_map
+}
+
+/// The pairs of faces connected by edges.
+fn connections(
+    positions: &[Point3],
+    faces: &Faces,
+    edge_map: &HashMap<[usize; 2], Vec<(usize, bool)>>,
+    split_edges: &mut HashSet<[usize; 2]>,
+) -> Vec<([usize; 2], usize, usize)> {
+    edge_map
+        .iter()
+        .flat_map(|(edge, incidents)| match incidents.len() {
+            2 => vec![(*edge, incidents[0].0, incidents[1].0)],
+            len if len > 2 => {
+                split_edges.insert(*edge);
+                radial_pairs(positions, faces, *edge, incidents)
+                    .into_iter()
+                    .map(|(face0, face1)| (*edge, face0, face1))
+                    .collect()
+            }
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+/// Pairs the faces around a non-manifold edge which bound the same wedge.
+///
+/// The faces are sorted by the angle around the edge. A face running along the edge bounds the wedge
+/// on the side of the smaller angle, and the other faces bound the wedge on the side of the larger angle.
+fn radial_pairs(
+    positions: &[Point3],
+    faces: &Faces,
+    edge: [usize; 2],
+    incidents: &[(usize, bool)],
+) -> Vec<(usize, usize)> {
+    let axis = (positions[edge[1]] - positions[edge[0]]).normalize();
+    let u_axis = match axis.x.abs() < 0.5 {
+        true => axis.cross(Vector3::unit_x()).normalize(),
+        false => axis.cross(Vector3::unit_y()).normalize(),
+    };
+    let v_axis = axis.cross(u_axis);
+    let mut sorted: Vec<(f64, usize, bool)> = incidents
+        .iter()
+        .map(|(face_idx, ori)| {
+            let dir = if *ori { axis } else { -axis };
+            let wing = face_normal(positions, &faces[*face_idx]).cross(dir);
+            let angle = f64::atan2(wing.dot(v_axis), wing.dot(u_axis));
+            (angle, *face_idx, *ori)
+        })
+        .collect();
+    sorted.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(std::cmp::Ordering::Equal));
+    let len = sorted.len();
+    (0..len)
+        .filter_map(|i| {
+            let (_, face0, ori0) = sorted[i];
+            let (_, face1, ori1) = sorted[(i + 1) % len];
+            match !ori0 && ori1 {
+                true => Some((face0, face1)),
+                false => None,
+            }
+        })
+        .collect()
+}
+
+fn face_normal(positions: &[Point3], face: &[Vertex]) -> Vector3 {
+    face.iter()
+        .zip(face.iter().cycle().skip(1))
+        .fold(Vector3::zero(), |normal, (v0, v1)| {
+            normal + positions[v0.pos].to_vec().cross(positions[v1.pos].to_vec())
+        })
+}
+
+/// Duplicates positions so that the faces around each position form one connected fan.
+fn split_vertices(
+    positions: &mut Vec<Point3>,
+    faces: &mut Faces,
+    connections: &[([usize; 2], usize, usize)],
+    log: &mut HealingLog,
+) {
+    let offsets: Vec<usize> = faces
+        .face_iter()
+        .scan(0, |offset, face| {
+            let res = *offset;
+            *offset += face.len();
+            Some(res)
+        })
+        .collect();
+    let corner = |face_idx: usize, pos: usize, faces: &Faces| {
+        let local = faces[face_idx].iter().position(|v| v.pos == pos);
+        offsets[face_idx] + local.expect("inconsistent edge map")
+    };
+    let num_corners = faces.face_iter().map(|face| face.len()).sum();
+    let mut sets = DisjointSet::new(num_corners);
+    connections.iter().for_each(|(edge, face0, face1)| {
+        edge.iter().for_each(|pos| {
+            sets.union(corner(*face0, *pos, faces), corner(*face1, *pos, faces));
+        })
+    });
+
+    let mut first_roots = HashMap::<usize, usize>::default();
+    let mut new_indices = HashMap::<usize, usize>::default();
+    let mut corner_idx = 0;
+    faces.face_iter_mut().for_each(|face| {
+        face.iter_mut().for_each(|v| {
+            let root = sets.root(corner_idx);
+            corner_idx += 1;
+            if *first_roots.entry(v.pos).or_insert(root) != root {
+                v.pos = *new_indices.entry(root).or_insert_with(|| {
+                    positions.push(positions[v.pos]);
+                    log.duplicated_vertices.push((v.pos, positions.len() - 1));
+                    positions.len() - 1
+                });
+            }
+        })
+    });
+}
+
+/// If some edges are still shared by three or more faces, duplicates the positions of the faces which are not
+/// in the first pair around such edges. Returns `true` if some positions are duplicated.
+fn isolate_remaining_faces(
+    positions: &mut Vec<Point3>,
+    faces: &mut Faces,
+    log: &mut HealingLog,
+) -> bool {
+    let mut redundants = HashMap::<usize, Vec<usize>>::default();
+    edge_map(faces).into_iter().for_each(|(edge, incidents)| {
+        if incidents.len() > 2 {
+            let pair = radial_pairs(positions, faces, edge, &incidents)
+                .first()
+                .copied()
+                .unwrap_or((incidents[0].0, incidents[1].0));
+            incidents
+                .iter()
+                .filter(|(face_idx, _)| *face_idx != pair.0 && *face_idx != pair.1)
+                .for_each(|(face_idx, _)| redundants.entry(*face_idx).or_default().extend(edge));
+        }
+    });
+    let mut redundants: Vec<_> = redundants.into_iter().collect();
+    redundants.sort();
+    redundants.iter().for_each(|(face_idx, poses)| {
+        faces[*face_idx]
+            .iter_mut()
+            .filter(|v| poses.contains(&v.pos))
+            .for_each(|v| {
+                positions.push(positions[v.pos]);
+                log.duplicated_vertices.push((v.pos, positions.len() - 1));
+                v.pos = positions.len() - 1;
+            })
+    });
+    !redundants.is_empty()
+}
+
+/// Makes the winding of faces consistent in each connected component.
+/// Returns the components, the lists of face indices, with the flags whether they are closed or not,
+/// and the flags whether the faces are flipped or not.
+fn orient_components(faces: &mut Faces) -> (Vec<(Vec<usize>, bool)>, Vec<bool>) {
+    let mut adjacency = vec![Vec::<(usize, bool)>::new(); faces.len()];
+    let mut on_boundary = vec![false; faces.len()];
+    edge_map(faces).into_iter().for_each(|(_, incidents)| {
+        if incidents.len() == 2 {
+            let ((face0, ori0), (face1, ori1)) = (incidents[0], incidents[1]);
+            adjacency[face0].push((face1, ori0 == ori1));
+            adjacency[face1].push((face0, ori0 == ori1));
+        } else {
+            incidents
+                .iter()
+                .for_each(|(face_idx, _)| on_boundary[*face_idx] = true);
+        }
+    });
+    let mut flips: Vec<Option<bool>> = vec![None; faces.len()];
+    let mut components = Vec::new();
+    (0..faces.len()).for_each(|seed| {
+        if flips[seed].is_some() {
+            return;
+        }
+        flips[seed] = Some(false);
+        let (mut component, mut closed) = (Vec::new(), true);
+        let mut stack = vec![seed];
+        while let Some(face_idx) = stack.pop() {
+            component.push(face_idx);
+            closed &= !on_boundary[face_idx];
+            let flip = flips[face_idx].unwrap();
+            adjacency[face_idx]
+                .iter()
+                .for_each(|(adj, same_direction)| {
+                    if flips[*adj].is_none() {
+                        flips[*adj] = Some(flip != *same_direction);
+                        stack.push(*adj);
+                    }
+                });
+        }
+        components.push((component, closed));
+    });
+    let flips: Vec<bool> = flips.into_iter().map(|flip| flip == Some(true)).collect();
+    flips.iter().enumerate().for_each(|(face_idx, flip)| {
+        if *flip {
+            faces[face_idx].reverse();
+        }
+    });
+    (components, flips)
+}
+
+fn signed_volume(positions: &[Point3], faces: &Faces, component: &[usize]) -> f64 {
+    component
+        .iter()
+        .flat_map(|face_idx| {
+            let face = &faces[*face_idx];
+            (2..face.len()).map(move |i| [face[0].pos, face[i - 1].pos, face[i].pos])
+        })
+        .map(|[i, j, k]| {
+            let (p, q, r) = (positions[i], positions[j], positions[k]);
+            p.to_vec().dot(q.to_vec().cross(r.to_vec()))
+        })
+        .sum::<f64>()
+        / 6.0
+}
+
+/// The generalized winding number of the component around `point`.
+fn winding_number(positions: &[Point3], faces: &Faces, component: &[usize], point: Point3) -> f64 {
+    component
+        .iter()
+        .flat_map(|face_idx| {
+            let face = &faces[*face_idx];
+            (2..face.len()).map(move |i| [face[0].pos, face[i - 1].pos, face[i].pos])
+        })
+        .map(|[i, j, k]| {
+            let (a, b, c) = (
+                positions[i] - point,
+                positions[j] - point,
+                positions[k] - point,
+            );
+            let (la, lb, lc) = (a.magnitude(), b.magnitude(), c.magnitude());
+            let numer = a.dot(b.cross(c));
+            let denom = la * lb * lc + a.dot(b) * lc + a.dot(c) * lb + b.dot(c) * la;
+            2.0 * f64::atan2(numer, denom)
+        })
+        .sum::<f64>()
+        / (4.0 * std::f64::consts::PI)
+}
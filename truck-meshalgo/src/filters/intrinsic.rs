@@ -0,0 +1,224 @@
+use super::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::f64::consts::PI;
+
+/// Triangulation of a polygon mesh on its intrinsic metric, i.e. the edge lengths.
+///
+/// The edges are flipped intrinsically by [`IntrinsicTriangulation::flip_to_delaunay`]:
+/// the new edge is the geodesic on the unfolded pair of triangles, so the surface is
+/// never changed, and the intrinsic Delaunay triangulation always exists. The result cannot
+/// be written back to the mesh since the new edges are not straight in the space, but it gives
+/// the cotangent weights of the Laplacian which are always non-negative.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// // a folded strip of thin triangles
+/// let mut mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions: vec![
+///             Point3::new(0.0, 0.0, 0.0),
+///             Point3::new(2.0, 0.0, 0.0),
+///             Point3::new(1.0, 0.2, 0.0),
+///             Point3::new(1.0, 0.0, -0.2),
+///         ],
+///         ..Default::default()
+///     },
+///     Faces::from_iter(&[&[0, 1, 2], &[1, 0, 3]]),
+/// );
+/// let mut intrinsic = IntrinsicTriangulation::new(&mesh);
+/// assert!(!intrinsic.is_delaunay());
+/// // the cotangent weight of the edge [0, 1] is negative.
+/// assert!(intrinsic.cotan_weights().iter().any(|(_, w)| *w < 0.0));
+///
+/// assert_eq!(intrinsic.flip_to_delaunay(), 1);
+/// assert!(intrinsic.is_delaunay());
+/// assert!(intrinsic.cotan_weights().iter().all(|(_, w)| *w >= 0.0));
+/// // the new edge is the geodesic through the folding line.
+/// let length = intrinsic.edge_length(2, 3).unwrap();
+/// assert!(length.near(&0.4));
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntrinsicTriangulation {
+    triangles: Vec<[usize; 3]>,
+    lengths: HashMap<[usize; 2], f64>,
+    edge_triangles: HashMap<[usize; 2], Vec<usize>>,
+}
+
+#[inline(always)]
+fn edge(a: usize, b: usize) -> [usize; 2] { [usize::min(a, b), usize::max(a, b)] }
+
+/// the angle opposite to the edge of length `a` in the triangle with the edge lengths `a, b, c`
+#[inline(always)]
+fn opposite_angle(a: f64, b: f64, c: f64) -> f64 {
+    f64::acos(f64::clamp(
+        (b * b + c * c - a * a) / (2.0 * b * c),
+        -1.0,
+        1.0,
+    ))
+}
+
+impl IntrinsicTriangulation {
+    /// Creates the triangulation of `mesh` with its edge lengths.
+    ///
+    /// Polygons are triangulated and the faces are connected by the position indices.
+    /// Apply [`OptimizingFilter::put_together_same_attrs`] to separated meshes beforehand.
+    /// The degenerate triangles with the same vertices are skipped.
+    pub fn new(mesh: &PolygonMesh) -> Self {
+        let positions = mesh.positions();
+        let triangles: Vec<[usize; 3]> = mesh
+            .faces()
+            .triangle_iter()
+            .map(|tri| tri.map(|v| v.pos))
+            .filter(|tri| tri[0] != tri[1] && tri[1] != tri[2] && tri[2] != tri[0])
+            .collect();
+        let mut lengths = HashMap::default();
+        let mut edge_triangles = HashMap::<[usize; 2], Vec<usize>>::default();
+        triangles.iter().enumerate().for_each(|(i, tri)| {
+            (0..3).for_each(|k| {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                lengths.insert(edge(a, b), positions[a].distance(positions[b]));
+                edge_triangles.entry(edge(a, b)).or_default().push(i);
+            })
+        });
+        Self {
+            triangles,
+            lengths,
+            edge_triangles,
+        }
+    }
+
+    /// Returns the triangles by the position indices.
+    #[inline(always)]
+    pub fn triangles(&self) -> &[[usize; 3]] { &self.triangles }
+
+    /// Returns the intrinsic length of the edge between the positions `a` and `b`.
+    #[inline(always)]
+    pub fn edge_length(&self, a: usize, b: usize) -> Option<f64> {
+        self.lengths.get(&edge(a, b)).copied()
+    }
+
+    /// the angle of the triangle `i` opposite to the edge `[a, b]`
+    fn angle(&self, i: usize, [a, b]: [usize; 2]) -> f64 {
+        let c = self.triangles[i]
+            .into_iter()
+            .find(|v| *v != a && *v != b)
+            .unwrap();
+        let l = |v: usize, w: usize| self.lengths[&edge(v, w)];
+        opposite_angle(l(a, b), l(b, c), l(c, a))
+    }
+
+    fn is_locally_delaunay(&self, e: [usize; 2]) -> bool {
+        match self.edge_triangles[&e].as_slice() {
+            [t0, t1] => self.angle(*t0, e) + self.angle(*t1, e) <= PI + TOLERANCE,
+            _ => true,
+        }
+    }
+
+    /// Returns whether all inner edges satisfy the local Delaunay criterion.
+    pub fn is_delaunay(&self) -> bool {
+        self.edge_triangles
+            .keys()
+            .all(|e| self.is_locally_delaunay(*e))
+    }
+
+    /// Flips the edges intrinsically until all edges are locally Delaunay, and returns
+    /// the number of flips.
+    ///
+    /// The boundary edges and the non-manifold edges are not flipped. An edge is not flipped
+    /// either if the two vertices of the new edge have already been connected.
+    pub fn flip_to_delaunay(&mut self) -> usize {
+        let mut stack: Vec<[usize; 2]> = self.edge_triangles.keys().copied().collect();
+        stack.sort();
+        let mut count = 0;
+        // The flips may cycle by the rounding errors.
+        let mut remaining = 16 * self.triangles.len() + 16;
+        while let Some(e) = stack.pop() {
+            if remaining == 0 {
+                break;
+            }
+            if !self.edge_triangles.contains_key(&e) || self.is_locally_delaunay(e) {
+                continue;
+            }
+            if let Some(new_edges) = self.flip(e) {
+                remaining -= 1;
+                count += 1;
+                stack.extend(new_edges);
+            }
+        }
+        count
+    }
+
+    /// Flips the edge `e`, and returns the four edges of the quadrangle.
+    fn flip(&mut self, e: [usize; 2]) -> Option<[[usize; 2]; 4]> {
+        let (t0, t1) = match self.edge_triangles[&e].as_slice() {
+            [t0, t1] => (*t0, *t1),
+            _ => return None,
+        };
+        // orient so that the triangle `t0` has the edge from `a` to `b`
+        let tri = self.triangles[t0];
+        let k = tri.iter().position(|v| *v == e[0]).unwrap();
+        let (a, b) = match tri[(k + 1) % 3] == e[1] {
+            true => (e[0], e[1]),
+            false => (e[1], e[0]),
+        };
+        let opposite = |i: usize| {
+            self.triangles[i]
+                .into_iter()
+                .find(|v| *v != a && *v != b)
+                .unwrap()
+        };
+        let (c, d) = (opposite(t0), opposite(t1));
+        if c == d || self.lengths.contains_key(&edge(c, d)) {
+            return None;
+        }
+        // unfold the two triangles on the plane: a = (0, 0), b = (l_ab, 0)
+        let l = |v: usize, w: usize| self.lengths[&edge(v, w)];
+        let l_ab = l(a, b);
+        let place = |v: usize, sign: f64| {
+            let angle = opposite_angle(l(b, v), l_ab, l(a, v));
+            Vector2::new(angle.cos(), sign * angle.sin()) * l(a, v)
+        };
+        let (pc, pd) = (place(c, 1.0), place(d, -1.0));
+        let length = pc.distance(pd);
+        if length < TOLERANCE {
+            return None;
+        }
+
+        let (new0, new1) = ([a, d, c], [b, c, d]);
+        self.triangles[t0] = new0;
+        self.triangles[t1] = new1;
+        self.lengths.remove(&e);
+        self.edge_triangles.remove(&e);
+        self.lengths.insert(edge(c, d), length);
+        self.edge_triangles.insert(edge(c, d), vec![t0, t1]);
+        let mut replace = |e: [usize; 2], old: usize, new: usize| {
+            let tris = self.edge_triangles.get_mut(&e).unwrap();
+            tris.iter_mut()
+                .filter(|i| **i == old)
+                .for_each(|i| *i = new);
+        };
+        // The edge [a, d] moves from `t1` to `t0`, and [b, c] from `t0` to `t1`.
+        replace(edge(a, d), t1, t0);
+        replace(edge(b, c), t0, t1);
+        Some([edge(a, c), edge(c, b), edge(b, d), edge(d, a)])
+    }
+
+    /// Returns the cotangent weights `(cot α + cot β) / 2` of the edges, sorted by the edges,
+    /// where `α` and `β` are the opposite angles. The weights of the boundary edges have
+    /// only one term.
+    pub fn cotan_weights(&self) -> Vec<([usize; 2], f64)> {
+        let mut weights: Vec<([usize; 2], f64)> = self
+            .edge_triangles
+            .iter()
+            .map(|(e, tris)| {
+                let w = tris
+                    .iter()
+                    .map(|i| 0.5 / f64::tan(self.angle(*i, *e)))
+                    .sum::<f64>();
+                (*e, w)
+            })
+            .collect();
+        weights.sort_by_key(|(e, _)| *e);
+        weights
+    }
+}
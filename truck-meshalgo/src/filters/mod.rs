@@ -1,11 +1,31 @@
 use crate::*;
 
+mod components;
+mod deformation;
+mod healing;
+mod intrinsic;
 mod normal_filters;
+mod offsetting;
 mod optimizing;
+mod parameterization;
+mod remeshing;
+mod repair_report;
+mod simplification;
 mod structuring;
 mod subdivision;
 
+pub use components::ComponentFilters;
+pub use deformation::{ArapDeformer, Deformation, Lattice};
+pub use healing::{Healing, HealingLog};
+pub use intrinsic::IntrinsicTriangulation;
 pub use normal_filters::NormalFilters;
-pub use optimizing::OptimizingFilter;
+pub use offsetting::Offsetting;
+pub use optimizing::{CleanupSummary, OptimizingFilter};
+pub use parameterization::UVParameterization;
+pub use remeshing::Remeshing;
+pub use repair_report::{
+    DefectKind, RepairEntity, RepairFix, RepairPass, RepairRecord, RepairReport, RepairResiduals,
+};
+pub use simplification::Simplification;
 pub use structuring::StructuringFilter;
 pub use subdivision::Subdivision;
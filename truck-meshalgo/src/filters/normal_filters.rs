@@ -1,5 +1,6 @@
 use super::*;
 use rustc_hash::FxHashMap as HashMap;
+use truck_base::disjoint_set::DisjointSet;
 
 /// Filters for adding normals
 pub trait NormalFilters {
@@ -125,6 +126,56 @@ pub trait NormalFilters {
     /// assert!(mesh.normals()[v1.nor.unwrap()].near(&Vector3::new(2.0, 5.0, 0.0).normalize()));
     /// ```
     fn add_smooth_normals(&mut self, tol_ang: f64, overwrite: bool) -> &mut Self;
+    /// add the smooth normal vectors to the mesh, keeping the edges sharper than `crease_angle`.
+    /// # Details
+    /// The normal of each corner of the faces is the average of the normals of the faces around
+    /// the vertex, weighted by the angles of the corners. The normals are averaged across an edge
+    /// only if the angle between the normals of the two faces sharing the edge is less than or
+    /// equal to `crease_angle`, so the sharp edges remain hard. The boundary edges and the
+    /// non-manifold edges shared by more than two faces are always regarded as sharp.
+    /// # Arguments
+    /// - If `overwrite == true`, clear all normals and update all normals in vertices.
+    /// - If `overwrite == false`, add normals only for `nor` is `None`.
+    /// # Examples
+    /// Compare with the examples of [`add_smooth_normals`](./trait.NormalFilters.html#tymethod.add_smooth_normals).
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![    
+    ///             Point3::new(-5.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 2.0, -2.0),
+    ///             Point3::new(0.0, 2.0, 0.0),
+    ///             Point3::new(0.0, 2.0, 2.0),
+    ///             Point3::new(5.0, 0.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[
+    ///         &[0, 2, 1], &[0, 3, 2], &[1, 2, 4], &[2, 3, 4],
+    ///     ]),
+    /// );
+    ///
+    /// mesh.add_crease_normals(0.8, true);
+    /// let v0: StandardVertex = mesh.faces()[0][1];
+    /// let v1: StandardVertex = mesh.faces()[3][0];
+    ///
+    /// // those vertices are at position with the index 2.
+    /// assert_eq!(v0.pos, 2); assert_eq!(v1.pos, 2);
+    ///
+    /// // Normals are avaraged!
+    /// assert!(mesh.normals()[v0.nor.unwrap()].near(&Vector3::new(0.0, 1.0, 0.0)));
+    /// assert!(mesh.normals()[v1.nor.unwrap()].near(&Vector3::new(0.0, 1.0, 0.0)));
+    /// assert_eq!(v0.nor, v1.nor);
+    ///
+    /// // If the crease angle is enough little, the edges between the faces are sharp.
+    /// mesh.add_crease_normals(0.6, true); // Normals are overwritten!
+    /// let v0: StandardVertex = mesh.faces()[0][1];
+    /// let v1: StandardVertex = mesh.faces()[3][0];
+    /// assert!(mesh.normals()[v0.nor.unwrap()].near(&Vector3::new(-2.0, 5.0, 0.0).normalize()));
+    /// assert!(mesh.normals()[v1.nor.unwrap()].near(&Vector3::new(2.0, 5.0, 0.0).normalize()));
+    /// ```
+    fn add_crease_normals(&mut self, crease_angle: f64, overwrite: bool) -> &mut Self;
     /// Makes the orientation of faces compatible to the normal vectors.
     /// # Examples
     /// ```
@@ -286,6 +337,34 @@ impl NormalFilters for PolygonMesh {
         self.reflect_normal_clusters(vnmap, overwrite);
         self
     }
+    fn add_crease_normals(&mut self, crease_angle: f64, overwrite: bool) -> &mut Self {
+        let corners = smooth_corner_groups(self, crease_angle.cos());
+        let mut mesh = self.debug_editor();
+        let PolygonMeshEditor {
+            attributes: StandardAttributes { normals, .. },
+            faces,
+            ..
+        } = &mut mesh;
+        if overwrite {
+            normals.clear();
+        }
+        let mut group_normals = HashMap::<usize, usize>::default();
+        faces
+            .face_iter_mut()
+            .flatten()
+            .zip(corners.groups)
+            .for_each(|(v, group)| {
+                if v.nor.is_none() || overwrite {
+                    let idx = *group_normals.entry(group).or_insert_with(|| {
+                        normals.push(corners.normals[group]);
+                        normals.len() - 1
+                    });
+                    v.nor = Some(idx);
+                }
+            });
+        drop(mesh);
+        self
+    }
 }
 
 trait SubNormalFilter {
@@ -396,3 +475,88 @@ fn signup_vertex_normal(
         face[j].nor = Some(normals.len() - 1);
     }
 }
+
+/// The corners of the faces grouped by the smooth edges.
+struct CornerGroups {
+    /// the index of the group of each corner, in the order of `Faces::face_iter`
+    groups: Vec<usize>,
+    /// the normal of each group, indexed by the representative corner
+    normals: Vec<Vector3>,
+}
+
+fn smooth_corner_groups(mesh: &PolygonMesh, inf: f64) -> CornerGroups {
+    let positions = mesh.positions();
+    let face_normals: Vec<Vector3> = mesh
+        .face_iter()
+        .enumerate()
+        .map(|(i, face)| FaceNormal::new(positions, face, i).normal)
+        // the normals of the degenerate faces are not defined
+        .map(|normal| match normal.x.is_finite() {
+            true => normal,
+            false => Vector3::zero(),
+        })
+        .collect();
+    // the index of the first corner of each face
+    let offsets: Vec<usize> = mesh
+        .face_iter()
+        .scan(0, |offset, face| {
+            *offset += face.len();
+            Some(*offset - face.len())
+        })
+        .collect();
+    let len = mesh.face_iter().map(<[Vertex]>::len).sum::<usize>();
+
+    // the corners at the ends of each edge: (face, corner at the smaller, corner at the larger)
+    let mut edges = HashMap::<[usize; 2], Vec<(usize, usize, usize)>>::default();
+    mesh.face_iter().enumerate().for_each(|(i, face)| {
+        (0..face.len()).for_each(|j| {
+            let k = (j + 1) % face.len();
+            let (a, b) = (face[j].pos, face[k].pos);
+            let (ca, cb) = (offsets[i] + j, offsets[i] + k);
+            match a < b {
+                true => edges.entry([a, b]).or_default().push((i, ca, cb)),
+                false => edges.entry([b, a]).or_default().push((i, cb, ca)),
+            }
+        });
+    });
+
+    let mut sets = DisjointSet::new(len);
+    edges.into_iter().for_each(|([a, b], corners)| {
+        if a == b || corners.len() != 2 {
+            return;
+        }
+        let ((f0, a0, b0), (f1, a1, b1)) = (corners[0], corners[1]);
+        if face_normals[f0].dot(face_normals[f1]) >= inf {
+            sets.union(a0, a1);
+            sets.union(b0, b1);
+        }
+    });
+
+    let mut normals = vec![Vector3::zero(); len];
+    let groups: Vec<usize> = (0..len).map(|c| sets.root(c)).collect();
+    mesh.face_iter().enumerate().for_each(|(i, face)| {
+        (0..face.len()).for_each(|j| {
+            let p = positions[face[j].pos];
+            let prev = positions[face[(j + face.len() - 1) % face.len()].pos] - p;
+            let next = positions[face[(j + 1) % face.len()].pos] - p;
+            let angle = match prev.magnitude2() > 0.0 && next.magnitude2() > 0.0 {
+                true => prev.angle(next).0,
+                false => 0.0,
+            };
+            normals[groups[offsets[i] + j]] += face_normals[i] * angle;
+        });
+    });
+    // the corners of the degenerate faces
+    mesh.face_iter().enumerate().for_each(|(i, face)| {
+        (0..face.len()).for_each(|j| {
+            let group = groups[offsets[i] + j];
+            if normals[group].so_small() {
+                normals[group] = face_normals[i];
+            }
+        });
+    });
+    normals
+        .iter_mut()
+        .for_each(|normal| *normal = normal.normalize());
+    CornerGroups { groups, normals }
+}
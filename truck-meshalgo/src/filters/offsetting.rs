@@ -0,0 +1,228 @@
+use super::*;
+use crate::analyzers::Topology;
+
+/// offsetting and thickening polygon meshes
+pub trait Offsetting {
+    /// Moves the surface along its normals by `distance`.
+    /// # Details
+    /// Each position is moved along the angle-weighted average of the normals of the faces
+    /// around it. The length of the move is scaled so that the adjacent faces are moved
+    /// by `distance` as far as possible, i.e. `distance / cos` where `cos` is the minimum of
+    /// the cosines between the vertex normal and the face normals, and the scale is
+    /// limited to `4.0` at very sharp corners.
+    ///
+    /// After moving, the faces whose normals are reversed, which appear where the offset
+    /// distance is over the radius of curvature, are removed. The global self-intersections
+    /// are not resolved.
+    ///
+    /// # Remarks
+    /// - The faces are connected by the position indices. Apply
+    /// [`OptimizingFilter::put_together_same_attrs`] to separated meshes beforehand.
+    /// - The uv coordinates and normals are kept.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let positions = (0..8)
+    ///     .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+    ///     .collect();
+    /// let faces = Faces::from_iter(&[
+    ///     &[0, 2, 3, 1],
+    ///     &[0, 1, 5, 4],
+    ///     &[1, 3, 7, 5],
+    ///     &[3, 2, 6, 7],
+    ///     &[2, 0, 4, 6],
+    ///     &[4, 5, 7, 6],
+    /// ]);
+    /// let mut cube = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions,
+    ///         ..Default::default()
+    ///     },
+    ///     faces,
+    /// );
+    ///
+    /// cube.offset(0.5);
+    /// let bdd_box = cube.bounding_box();
+    /// assert!(bdd_box.min().near(&Point3::new(-0.5, -0.5, -0.5)));
+    /// assert!(bdd_box.max().near(&Point3::new(1.5, 1.5, 1.5)));
+    /// ```
+    fn offset(&mut self, distance: f64) -> &mut Self;
+    /// Thickens the surface to the closed shell with `thickness`.
+    /// # Details
+    /// The surface offset by `-thickness` in the same way as [`Offsetting::offset`] is
+    /// added with the reversed orientation, and the boundaries are stitched by quadrangles.
+    /// If `thickness` is negative, the shell is thickened in the direction of the normals,
+    /// and the orientation of the original surface is reversed instead, so that the
+    /// resulting shell is always outward.
+    ///
+    /// # Remarks
+    /// - The faces are connected by the position indices. Apply
+    /// [`OptimizingFilter::put_together_same_attrs`] to separated meshes beforehand.
+    /// - The faces reversed by the offset are not removed, since it breaks the closedness.
+    /// - The offset surface shares the uv coordinates with the original surface,
+    /// and the reversed surface has the reversed normals. The stitching faces have
+    /// neither uv coordinates nor normals.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_topology::shell::ShellCondition;
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2, 3]]),
+    /// );
+    ///
+    /// mesh.thicken(0.1);
+    /// assert_eq!(mesh.faces().len(), 6);
+    /// assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    /// let bdd_box = mesh.bounding_box();
+    /// assert!(bdd_box.min().near(&Point3::new(0.0, 0.0, -0.1)));
+    /// ```
+    fn thicken(&mut self, thickness: f64) -> &mut Self;
+}
+
+impl Offsetting for PolygonMesh {
+    fn offset(&mut self, distance: f64) -> &mut Self {
+        let mut mesh = self.debug_editor();
+        let PolygonMeshEditor {
+            attributes: StandardAttributes { positions, .. },
+            faces,
+            ..
+        } = &mut mesh;
+        let offset = offset_positions(positions, faces, distance);
+        let mut new_faces = Faces::default();
+        faces
+            .face_iter()
+            .filter(|face| {
+                let normal0 = newell_normal(positions, face);
+                let normal1 = newell_normal(&offset, face);
+                normal0.dot(normal1) > 0.0
+            })
+            .for_each(|face| new_faces.push(face));
+        *positions = offset;
+        **faces = new_faces;
+        drop(mesh);
+        self
+    }
+
+    fn thicken(&mut self, thickness: f64) -> &mut Self {
+        let boundaries = self.extract_boundaries();
+        let mut mesh = self.debug_editor();
+        let PolygonMeshEditor {
+            attributes: StandardAttributes {
+                positions, normals, ..
+            },
+            faces,
+            ..
+        } = &mut mesh;
+        let (pos_len, nor_len) = (positions.len(), normals.len());
+        let offset = offset_positions(positions, faces, -thickness);
+        positions.extend(offset);
+        let reversed_normals: Vec<Vector3> = normals.iter().map(|n| -*n).collect();
+        normals.extend(reversed_normals);
+
+        let reverse = |face: &[Vertex], pos_offset: usize| -> Vec<Vertex> {
+            face.iter()
+                .rev()
+                .map(|v| Vertex {
+                    pos: v.pos + pos_offset,
+                    uv: v.uv,
+                    nor: v.nor.map(|i| i + nor_len),
+                })
+                .collect()
+        };
+        let mut new_faces = Faces::default();
+        match thickness >= 0.0 {
+            true => faces.face_iter().for_each(|face| {
+                new_faces.push(face);
+                new_faces.push(reverse(face, pos_len));
+            }),
+            false => faces.face_iter().for_each(|face| {
+                new_faces.push(reverse(face, 0));
+                new_faces.push(
+                    face.iter()
+                        .map(|v| Vertex {
+                            pos: v.pos + pos_len,
+                            ..*v
+                        })
+                        .collect::<Vec<_>>(),
+                );
+            }),
+        }
+        boundaries.iter().for_each(|wire| {
+            let len = wire.len();
+            (0..len).for_each(|i| {
+                let (a, b) = (wire[i], wire[(i + 1) % len]);
+                match thickness >= 0.0 {
+                    true => new_faces.push([b, a, a + pos_len, b + pos_len]),
+                    false => new_faces.push([a, b, b + pos_len, a + pos_len]),
+                }
+            });
+        });
+        **faces = new_faces;
+        drop(mesh);
+        self
+    }
+}
+
+fn newell_normal(positions: &[Point3], face: &[Vertex]) -> Vector3 {
+    let len = face.len();
+    (0..len).fold(Vector3::zero(), |sum, i| {
+        let (p, q) = (positions[face[i].pos], positions[face[(i + 1) % len].pos]);
+        sum + Vector3::new(
+            (p.y - q.y) * (p.z + q.z),
+            (p.z - q.z) * (p.x + q.x),
+            (p.x - q.x) * (p.y + q.y),
+        )
+    })
+}
+
+fn offset_positions(positions: &[Point3], faces: &Faces, distance: f64) -> Vec<Point3> {
+    let face_normals: Vec<Vector3> = faces
+        .face_iter()
+        .map(|face| {
+            let normal = newell_normal(positions, face);
+            match normal.so_small() {
+                true => Vector3::zero(),
+                false => normal.normalize(),
+            }
+        })
+        .collect();
+    let mut vertex_normals = vec![Vector3::zero(); positions.len()];
+    faces.face_iter().zip(&face_normals).for_each(|(face, n)| {
+        let len = face.len();
+        (0..len).for_each(|i| {
+            let p = positions[face[i].pos];
+            let prev = positions[face[(i + len - 1) % len].pos] - p;
+            let next = positions[face[(i + 1) % len].pos] - p;
+            if !prev.so_small() && !next.so_small() {
+                vertex_normals[face[i].pos] += n * prev.angle(next).0;
+            }
+        });
+    });
+    vertex_normals.iter_mut().for_each(|n| {
+        *n = match n.so_small() {
+            true => Vector3::zero(),
+            false => n.normalize(),
+        }
+    });
+    let mut min_cosines = vec![1.0_f64; positions.len()];
+    faces.face_iter().zip(&face_normals).for_each(|(face, n)| {
+        face.iter().for_each(|v| {
+            let cos = vertex_normals[v.pos].dot(*n);
+            min_cosines[v.pos] = f64::min(min_cosines[v.pos], cos);
+        });
+    });
+    positions
+        .iter()
+        .zip(vertex_normals.iter().zip(min_cosines))
+        .map(|(p, (n, cos))| p + n * distance / f64::max(cos, 0.25))
+        .collect()
+}
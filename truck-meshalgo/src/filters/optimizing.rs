@@ -1,8 +1,41 @@
+use super::healing::{face_key, is_zero_area};
 use super::*;
-use rustc_hash::FxHashMap as HashMap;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use std::iter::Iterator;
 use std::ops::{Div, Mul};
 
+/// The summary of [`OptimizingFilter::cleanup`].
+///
+/// The indices of faces are the ones before cleaning up.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CleanupSummary {
+    /// the indices of faces which have the same position more than once and are removed or split
+    pub collapsed_faces: Vec<usize>,
+    /// the indices of faces which are removed, wholly or partially, since their areas are zero
+    pub zero_area_faces: Vec<usize>,
+    /// the indices of removed faces which duplicate another preceding face
+    pub duplicate_faces: Vec<usize>,
+    /// the number of removed positions
+    pub removed_positions: usize,
+    /// the number of removed texture coordinates
+    pub removed_uv_coords: usize,
+    /// the number of removed normal vectors
+    pub removed_normals: usize,
+}
+
+impl CleanupSummary {
+    /// Returns `true` if the mesh has not been changed.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.collapsed_faces.is_empty()
+            && self.zero_area_faces.is_empty()
+            && self.duplicate_faces.is_empty()
+            && self.removed_positions == 0
+            && self.removed_uv_coords == 0
+            && self.removed_normals == 0
+    }
+}
+
 /// Filters for optimizing data
 pub trait OptimizingFilter {
     /// remove all unused position, texture coordinates, and normal vectors.
@@ -94,6 +127,170 @@ pub trait OptimizingFilter {
     /// assert_eq!(mesh.positions().len(), 4);
     /// ```
     fn put_together_same_attrs(&mut self) -> &mut Self;
+    /// Merges the positions whose distances are less than `tolerance`, and re-indexes the faces.
+    /// The positions no longer referred by the faces are removed.
+    ///
+    /// Positions are bucketed by spatial hashing, so the cost is linear in the number of positions.
+    /// Texture coordinates and normal vectors are not changed. Use [`weld_attributes`] to merge them too.
+    /// `tolerance` smaller than `TOLERANCE` is replaced by `TOLERANCE`.
+    ///
+    /// [`weld_attributes`]: ./trait.OptimizingFilter.html#tymethod.weld_attributes
+    ///
+    /// # Remarks
+    /// Faces which become degenerate by welding are NOT removed.
+    /// One can remove such faces by running [`remove_degenerate_faces`] manually.
+    ///
+    /// [`remove_degenerate_faces`]: ./trait.OptimizingFilter.html#tymethod.remove_degenerate_faces
+    ///
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // triangle soup of a square, as read from STL
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.001),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2], &[3, 4, 5]]),
+    /// );
+    ///
+    /// mesh.weld(0.01);
+    /// assert_eq!(mesh.positions().len(), 4);
+    /// assert_eq!(mesh.faces()[1][0].pos, 0);
+    /// assert_eq!(mesh.faces()[1][1].pos, 2);
+    /// ```
+    fn weld(&mut self, tolerance: f64) -> &mut Self;
+    /// Merges the positions, texture coordinates, and normal vectors whose distances are
+    /// less than `tolerance`, respectively, and re-indexes the faces.
+    /// The attributes no longer referred by the faces are removed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///         ],
+    ///         uv_coords: vec![Vector2::new(0.0, 0.0), Vector2::new(0.0, 0.001)],
+    ///         normals: vec![Vector3::unit_z(), Vector3::new(0.0, 0.001, 1.0)],
+    ///     },
+    ///     Faces::from_iter(&[
+    ///         &[[0, 0, 0], [1, 0, 0], [2, 0, 0]],
+    ///         &[[3, 1, 1], [4, 1, 1], [5, 1, 1]],
+    ///     ]),
+    /// );
+    ///
+    /// mesh.weld_attributes(0.01);
+    /// assert_eq!(mesh.positions().len(), 4);
+    /// assert_eq!(mesh.uv_coords().len(), 1);
+    /// assert_eq!(mesh.normals().len(), 1);
+    /// ```
+    fn weld_attributes(&mut self, tolerance: f64) -> &mut Self;
+    /// Expands the vertices into the unique tuples of the position, the texture coordinate,
+    /// and the normal vector, so that each vertex has the same index for all attributes.
+    ///
+    /// The expanded mesh can be directly converted to the single-index vertex buffers for GPU.
+    /// The faces, including their order and the numbers of their vertices, are not changed.
+    /// Conversely, [`weld_attributes`] re-shares the attributes which agree within the tolerance.
+    ///
+    /// [`weld_attributes`]: ./trait.OptimizingFilter.html#tymethod.weld_attributes
+    ///
+    /// # Remarks
+    /// If only some vertices have texture coordinates or normals, the attributes of the other
+    /// vertices are filled by zero vectors, and their indices remain `None`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // two triangles with the normals of each face
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 1.0),
+    ///         ],
+    ///         normals: vec![Vector3::unit_z(), Vector3::new(1.0, 1.0, -1.0).normalize()],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[
+    ///         &[(0, None, Some(0)), (1, None, Some(0)), (2, None, Some(0))],
+    ///         &[(2, None, Some(1)), (1, None, Some(1)), (3, None, Some(1))],
+    ///     ]),
+    /// );
+    /// let original = mesh.clone();
+    ///
+    /// mesh.expand_vertices();
+    /// assert_eq!(mesh.positions().len(), 6);
+    /// assert_eq!(mesh.normals().len(), 6);
+    /// mesh.faces().face_iter().flatten().for_each(|v| {
+    ///     assert_eq!(v.nor, Some(v.pos));
+    ///     assert_eq!(v.uv, None);
+    /// });
+    ///
+    /// // re-shares the attributes
+    /// mesh.weld_attributes(TOLERANCE);
+    /// assert_eq!(mesh, original);
+    /// ```
+    fn expand_vertices(&mut self) -> &mut Self;
+    /// Removes degenerate geometry and returns the summary of removed elements.
+    ///
+    /// The following operations are executed in order.
+    /// 1. Removes the polygons which have the same position more than once, or splits them into
+    ///    non-degenerate polygons as [`remove_degenerate_faces`] does.
+    /// 1. Removes the polygons whose areas are zero.
+    /// 1. Removes the polygons which have the same positions as a preceding polygon up to rotation and orientation.
+    /// 1. Removes the positions, texture coordinates, and normal vectors which are no longer used.
+    ///
+    /// The indices of faces are the ones in the order of `Faces::face_iter`.
+    ///
+    /// [`remove_degenerate_faces`]: ./trait.OptimizingFilter.html#tymethod.remove_degenerate_faces
+    ///
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(2.0, 2.0, 0.0),
+    ///             Point3::new(5.0, 5.0, 5.0), // unused
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[
+    ///         [0, 1, 2].as_ref(),
+    ///         &[0, 2, 4], // zero area
+    ///         &[2, 1, 0], // duplicate
+    ///         &[0, 2, 3],
+    ///         &[0, 1, 1, 3], // collapsed quadrangle
+    ///     ]),
+    /// );
+    ///
+    /// let summary = mesh.cleanup();
+    /// assert_eq!(summary.zero_area_faces, vec![1]);
+    /// assert_eq!(summary.duplicate_faces, vec![2]);
+    /// assert_eq!(summary.collapsed_faces, vec![4]);
+    /// assert_eq!(summary.removed_positions, 2);
+    /// assert_eq!(mesh.faces().len(), 3);
+    /// assert_eq!(mesh.faces().tri_faces().len(), 3);
+    /// ```
+    fn cleanup(&mut self) -> CleanupSummary;
 }
 
 fn all_pos_mut(faces: &mut Faces) -> impl Iterator<Item = &mut usize> {
@@ -191,6 +388,142 @@ impl OptimizingFilter for PolygonMesh {
         drop(mesh);
         self
     }
+
+    fn weld(&mut self, tolerance: f64) -> &mut Self {
+        weld_positions(self, tolerance);
+        self
+    }
+
+    fn weld_attributes(&mut self, tolerance: f64) -> &mut Self {
+        weld_positions(self, tolerance);
+        let mut mesh = self.debug_editor();
+        let PolygonMeshEditor {
+            attributes: StandardAttributes {
+                uv_coords, normals, ..
+            },
+            faces,
+            ..
+        } = &mut mesh;
+        let uv_map = sub_weld(uv_coords, tolerance);
+        all_uv_mut(faces).for_each(|idx| *idx = uv_map[*idx]);
+        let nor_map = sub_weld(normals, tolerance);
+        all_nor_mut(faces).for_each(|idx| *idx = nor_map[*idx]);
+        drop(mesh);
+        self.remove_unused_attrs()
+    }
+
+    fn expand_vertices(&mut self) -> &mut Self {
+        let mut mesh = self.debug_editor();
+        let PolygonMeshEditor {
+            attributes:
+                StandardAttributes {
+                    positions,
+                    uv_coords,
+                    normals,
+                },
+            faces,
+            ..
+        } = &mut mesh;
+        let has_uv = faces.face_iter().flatten().any(|v| v.uv.is_some());
+        let has_nor = faces.face_iter().flatten().any(|v| v.nor.is_some());
+        let mut new_attrs = StandardAttributes::default();
+        let mut map = HashMap::<Vertex, usize>::default();
+        faces.face_iter_mut().flatten().for_each(|v| {
+            let idx = *map.entry(*v).or_insert_with(|| {
+                new_attrs.positions.push(positions[v.pos]);
+                if has_uv {
+                    let uv = v.uv.map_or_else(Vector2::zero, |i| uv_coords[i]);
+                    new_attrs.uv_coords.push(uv);
+                }
+                if has_nor {
+                    let nor = v.nor.map_or_else(Vector3::zero, |i| normals[i]);
+                    new_attrs.normals.push(nor);
+                }
+                new_attrs.positions.len() - 1
+            });
+            *v = Vertex {
+                pos: idx,
+                uv: v.uv.map(|_| idx),
+                nor: v.nor.map(|_| idx),
+            };
+        });
+        *positions = new_attrs.positions;
+        *uv_coords = new_attrs.uv_coords;
+        *normals = new_attrs.normals;
+        drop(mesh);
+        self
+    }
+
+    fn cleanup(&mut self) -> CleanupSummary {
+        let mut summary = CleanupSummary::default();
+        let mut mesh = self.debug_editor();
+        let PolygonMeshEditor {
+            attributes: StandardAttributes { positions, .. },
+            faces,
+            ..
+        } = &mut mesh;
+        let mut registered = HashSet::default();
+        let mut new_faces = Faces::default();
+        faces.face_iter().enumerate().for_each(|(i, face)| {
+            let polygons = match face.len() {
+                3 => match degenerate_triangle([face[0], face[1], face[2]]) {
+                    true => Vec::new(),
+                    false => vec![face.to_vec()],
+                },
+                4 => match degenerate_quadrangle([face[0], face[1], face[2], face[3]]) {
+                    QuadrangleType::TotallyDegenerate => Vec::new(),
+                    QuadrangleType::Triangle(tri) => vec![tri.to_vec()],
+                    QuadrangleType::NonDegenerate => vec![face.to_vec()],
+                },
+                _ => split_into_nondegenerate(face.to_vec()),
+            };
+            if polygons.len() != 1 || polygons[0].len() != face.len() {
+                summary.collapsed_faces.push(i);
+            }
+            let mut zero_area = false;
+            let mut duplicate = false;
+            polygons.into_iter().for_each(|polygon| {
+                if is_zero_area(positions, &polygon) {
+                    zero_area = true;
+                } else if !registered.insert(face_key(&polygon)) {
+                    duplicate = true;
+                } else {
+                    new_faces.push(polygon);
+                }
+            });
+            if zero_area {
+                summary.zero_area_faces.push(i);
+            }
+            if duplicate {
+                summary.duplicate_faces.push(i);
+            }
+        });
+        **faces = new_faces;
+        drop(mesh);
+        let lens = (
+            self.positions().len(),
+            self.uv_coords().len(),
+            self.normals().len(),
+        );
+        self.remove_unused_attrs();
+        summary.removed_positions = lens.0 - self.positions().len();
+        summary.removed_uv_coords = lens.1 - self.uv_coords().len();
+        summary.removed_normals = lens.2 - self.normals().len();
+        summary
+    }
+}
+
+fn weld_positions(mesh: &mut PolygonMesh, tolerance: f64) {
+    let mut mesh = mesh.debug_editor();
+    let PolygonMeshEditor {
+        attributes: StandardAttributes { positions, .. },
+        faces,
+        ..
+    } = &mut mesh;
+    let pos_map = sub_weld(positions, tolerance);
+    all_pos_mut(faces).for_each(|idx| *idx = pos_map[*idx]);
+    let idcs = sub_remove_unused_attrs(all_pos_mut(faces), positions.len());
+    *positions = idcs.iter().map(|i| positions[*i]).collect();
 }
 
 fn sub_remove_unused_attrs<'a, I: Iterator<Item = &'a mut usize>>(
@@ -223,6 +556,36 @@ fn sub_put_together_same_attrs<T: Copy + CastIntVector>(attrs: &[T]) -> Vec<usiz
     res
 }
 
+/// Returns the map from the indices of `attrs` to the indices of the representatives.
+fn sub_weld<T, const N: usize>(attrs: &[T], tolerance: f64) -> Vec<usize>
+where T: Copy + AsRef<[f64; N]> + MetricSpace<Metric = f64> {
+    let tolerance = f64::max(tolerance, TOLERANCE);
+    let cell = |attr: &T| attr.as_ref().map(|x| f64::floor(x / tolerance) as i64);
+    let mut map = HashMap::<[i64; N], Vec<usize>>::default();
+    attrs
+        .iter()
+        .enumerate()
+        .map(|(i, attr)| {
+            let key = cell(attr);
+            let neighbor = (0..3_usize.pow(N as u32)).find_map(|mut offset| {
+                let mut neighbor_key = key;
+                neighbor_key.iter_mut().for_each(|k| {
+                    *k += (offset % 3) as i64 - 1;
+                    offset /= 3;
+                });
+                map.get(&neighbor_key)?
+                    .iter()
+                    .find(|j| attrs[**j].distance2(*attr) < tolerance * tolerance)
+                    .copied()
+            });
+            neighbor.unwrap_or_else(|| {
+                map.entry(key).or_default().push(i);
+                i
+            })
+        })
+        .collect()
+}
+
 fn degenerate_triangle(tri: [Vertex; 3]) -> bool {
     tri[0].pos == tri[1].pos || tri[1].pos == tri[2].pos || tri[2].pos == tri[0].pos
 }
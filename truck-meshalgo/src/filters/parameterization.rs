@@ -0,0 +1,733 @@
+use super::*;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::f64::consts::PI;
+use truck_base::disjoint_set::DisjointSet;
+
+/// Automatic uv unwrapping
+pub trait UVParameterization {
+    /// Replaces the uv coordinates by the ones computed by the least squares conformal maps.
+    /// # Details
+    /// 1. The faces are divided into the charts by the region growing. A face is added to
+    /// the chart if it shares an edge with the chart and the angle between its normal and the
+    /// normal of the first face of the chart is not more than `chart_angle`.
+    /// 1. Each chart is flattened by the least squares conformal maps (LSCM), pinning the two
+    /// far vertices, and scaled so that the area is the same as the one in the space.
+    /// 1. The charts are packed in the atlas `[0, 1]^2` with margins, keeping the ratio of
+    /// the scales of the charts.
+    ///
+    /// The positions and normals are not changed, and each vertex is given the uv coordinate
+    /// of its chart.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    ///
+    /// // a curved strip
+    /// let positions = (0..20)
+    ///     .map(|i| {
+    ///         let t = (i / 2) as f64 / 9.0;
+    ///         Point3::new(f64::cos(t), f64::sin(t), (i % 2) as f64)
+    ///     })
+    ///     .collect();
+    /// let faces = (0..9)
+    ///     .map(|i| [2 * i, 2 * i + 2, 2 * i + 3, 2 * i + 1])
+    ///     .collect();
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions,
+    ///         ..Default::default()
+    ///     },
+    ///     faces,
+    /// );
+    ///
+    /// mesh.add_lscm_uv_coords(std::f64::consts::PI / 3.0);
+    /// // one chart
+    /// assert_eq!(mesh.uv_coords().len(), 20);
+    /// assert!(mesh.face_iter().flatten().all(|v| v.uv.is_some()));
+    /// assert!(mesh
+    ///     .uv_coords()
+    ///     .iter()
+    ///     .all(|uv| (0.0..=1.0).contains(&uv.x) && (0.0..=1.0).contains(&uv.y)));
+    /// ```
+    fn add_lscm_uv_coords(&mut self, chart_angle: f64) -> &mut Self;
+
+    /// Computes the seams, the edges along which the mesh is cut into the topological disks
+    /// so that each connected component can be unwrapped as one chart.
+    /// # Details
+    /// The faces are glued along the flattest edges first by the spanning tree of the faces,
+    /// so the seams run along the sharp edges, where the cuts are least visible. The remaining
+    /// edges are pruned to the cut graph, which consists of the loops around the handles and
+    /// the paths between the boundaries. The closed components of genus zero, which need no
+    /// loops, are cut along the path between the two far vertices, preferring the sharp edges.
+    ///
+    /// The edges are the sorted pairs of the indices of the positions. The boundary edges are
+    /// not contained, since the mesh is already cut there.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    ///
+    /// // the closed cube
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 1.0, 0.0),
+    ///             Point3::new(0.0, 0.0, 1.0),
+    ///             Point3::new(1.0, 0.0, 1.0),
+    ///             Point3::new(1.0, 1.0, 1.0),
+    ///             Point3::new(0.0, 1.0, 1.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[
+    ///         [3, 2, 1, 0], [0, 1, 5, 4], [1, 2, 6, 5],
+    ///         [2, 3, 7, 6], [3, 0, 4, 7], [4, 5, 6, 7],
+    ///     ]),
+    /// );
+    ///
+    /// let seams = mesh.auto_seams();
+    /// // the path between the opposite corners
+    /// assert_eq!(seams.len(), 3);
+    ///
+    /// mesh.add_lscm_uv_coords_with_seams(&seams);
+    /// // one chart, whose vertices inside the path are duplicated
+    /// assert_eq!(mesh.uv_coords().len(), 10);
+    /// ```
+    fn auto_seams(&self) -> Vec<[usize; 2]>;
+
+    /// Replaces the uv coordinates by the ones computed by the least squares conformal maps,
+    /// cutting the mesh along `seams`.
+    /// # Details
+    /// The charts are the regions of the faces connected without crossing the seams, and each
+    /// chart is flattened and packed as [`add_lscm_uv_coords`](UVParameterization::add_lscm_uv_coords).
+    /// The vertices on the seams are given the different uv coordinates on each side.
+    /// The seams are the pairs of the indices of the positions, e.g. the ones by
+    /// [`auto_seams`](UVParameterization::auto_seams).
+    fn add_lscm_uv_coords_with_seams(&mut self, seams: &[[usize; 2]]) -> &mut Self;
+}
+
+impl UVParameterization for PolygonMesh {
+    fn add_lscm_uv_coords(&mut self, chart_angle: f64) -> &mut Self {
+        let seams = HashSet::default();
+        let charts = create_charts(self, chart_angle, &seams);
+        set_lscm_uv_coords(self, charts, &seams)
+    }
+
+    fn auto_seams(&self) -> Vec<[usize; 2]> { auto_seams(self) }
+
+    fn add_lscm_uv_coords_with_seams(&mut self, seams: &[[usize; 2]]) -> &mut Self {
+        let seams: HashSet<[usize; 2]> = seams
+            .iter()
+            .map(|[a, b]| [usize::min(*a, *b), usize::max(*a, *b)])
+            .collect();
+        let charts = create_charts(self, PI, &seams);
+        set_lscm_uv_coords(self, charts, &seams)
+    }
+}
+
+/// Flattens and packs the charts, and replaces the uv coordinates of the mesh.
+fn set_lscm_uv_coords<'a>(
+    mesh: &'a mut PolygonMesh,
+    charts: Vec<Vec<usize>>,
+    seams: &HashSet<[usize; 2]>,
+) -> &'a mut PolygonMesh {
+    let faces: Vec<&[Vertex]> = mesh.face_iter().collect();
+    let charts: Vec<Chart> = charts
+        .into_iter()
+        .map(|chart| Chart::new(mesh.positions(), &faces, chart, seams))
+        .collect();
+    let uv_coords = pack(&charts);
+    let mut corner_uvs = vec![Vec::new(); faces.len()];
+    charts.iter().fold(0, |offset, chart| {
+        chart
+            .faces
+            .iter()
+            .zip(&chart.corners)
+            .for_each(|(i, corners)| {
+                corner_uvs[*i] = corners.iter().map(|k| offset + k).collect();
+            });
+        offset + chart.vertices.len()
+    });
+
+    let mut editor = mesh.debug_editor();
+    let PolygonMeshEditor {
+        attributes: StandardAttributes { uv_coords: uvs, .. },
+        faces,
+        ..
+    } = &mut editor;
+    *uvs = uv_coords;
+    faces
+        .face_iter_mut()
+        .zip(corner_uvs)
+        .for_each(|(face, corners)| {
+            face.iter_mut()
+                .zip(corners)
+                .for_each(|(v, uv)| v.uv = Some(uv));
+        });
+    drop(editor);
+    mesh
+}
+
+/// The charts grow through the edges other than the seams.
+fn create_charts(
+    mesh: &PolygonMesh,
+    chart_angle: f64,
+    seams: &HashSet<[usize; 2]>,
+) -> Vec<Vec<usize>> {
+    let positions = mesh.positions();
+    let normals: Vec<Vector3> = mesh
+        .face_iter()
+        .enumerate()
+        .map(|(i, face)| FaceNormal::new(positions, face, i).normal)
+        .collect();
+    let mut edge_faces = HashMap::<[usize; 2], Vec<usize>>::default();
+    mesh.face_iter().enumerate().for_each(|(i, face)| {
+        face.iter()
+            .zip(face.iter().cycle().skip(1))
+            .for_each(|(v0, v1)| {
+                let edge = [usize::min(v0.pos, v1.pos), usize::max(v0.pos, v1.pos)];
+                edge_faces.entry(edge).or_default().push(i);
+            })
+    });
+    let mut adjacency = vec![Vec::new(); normals.len()];
+    edge_faces
+        .iter()
+        .filter(|(edge, _)| !seams.contains(*edge))
+        .for_each(|(_, faces)| {
+            faces.iter().for_each(|i| {
+                faces
+                    .iter()
+                    .filter(|j| i != *j)
+                    .for_each(|j| adjacency[*i].push(*j))
+            })
+        });
+
+    let cos = match chart_angle < PI {
+        true => f64::cos(chart_angle),
+        false => f64::NEG_INFINITY,
+    };
+    let mut checked = vec![false; normals.len()];
+    let mut charts = Vec::new();
+    (0..normals.len()).for_each(|seed| {
+        if checked[seed] {
+            return;
+        }
+        checked[seed] = true;
+        let mut chart = vec![seed];
+        let mut queue = VecDeque::from([seed]);
+        while let Some(i) = queue.pop_front() {
+            adjacency[i].iter().for_each(|j| {
+                if !checked[*j] && normals[*j].dot(normals[seed]) >= cos {
+                    checked[*j] = true;
+                    chart.push(*j);
+                    queue.push_back(*j);
+                }
+            });
+        }
+        chart.sort();
+        charts.push(chart);
+    });
+    charts
+}
+
+#[derive(Clone, Debug)]
+struct Chart {
+    /// the indices of the positions
+    vertices: Vec<usize>,
+    /// the indices of the faces
+    faces: Vec<usize>,
+    /// the local indices of the vertices of the faces
+    corners: Vec<Vec<usize>>,
+    uv_coords: Vec<Vector2>,
+}
+
+impl Chart {
+    fn new(
+        positions: &[Point3],
+        faces: &[&[Vertex]],
+        chart: Vec<usize>,
+        seams: &HashSet<[usize; 2]>,
+    ) -> Self {
+        let wedges = seam_wedges(faces, &chart, seams);
+        let mut vertices = Vec::new();
+        let mut local_index = HashMap::<(usize, usize), usize>::default();
+        let mut triangles = Vec::new();
+        let corners = chart
+            .iter()
+            .map(|i| {
+                let face: Vec<usize> = faces[*i]
+                    .iter()
+                    .map(|v| {
+                        // the vertices on the seams are split into the wedges.
+                        let wedge = wedges.get(&(*i, v.pos)).copied().unwrap_or(usize::MAX);
+                        *local_index.entry((v.pos, wedge)).or_insert_with(|| {
+                            vertices.push(v.pos);
+                            vertices.len() - 1
+                        })
+                    })
+                    .collect();
+                (2..face.len()).for_each(|k| triangles.push([face[0], face[k - 1], face[k]]));
+                face
+            })
+            .collect();
+        let points: Vec<Point3> = vertices.iter().map(|i| positions[*i]).collect();
+        let uv_coords = lscm(&points, &triangles);
+        Self {
+            vertices,
+            faces: chart,
+            corners,
+            uv_coords,
+        }
+    }
+}
+
+/// Groups the corners of the faces at the vertices on the seams into the wedges, the fans of
+/// the faces connected without crossing the seams. Returns the map from the pairs of the faces
+/// and the positions to the indices of the wedges.
+fn seam_wedges(
+    faces: &[&[Vertex]],
+    chart: &[usize],
+    seams: &HashSet<[usize; 2]>,
+) -> HashMap<(usize, usize), usize> {
+    let seam_vertices: HashSet<usize> = seams.iter().flatten().copied().collect();
+    let mut wedges = HashMap::<(usize, usize), usize>::default();
+    let mut edge_faces = HashMap::<[usize; 2], Vec<usize>>::default();
+    chart.iter().for_each(|i| {
+        let face = faces[*i];
+        face.iter()
+            .zip(face.iter().cycle().skip(1))
+            .for_each(|(v0, v1)| {
+                if seam_vertices.contains(&v0.pos) {
+                    let len = wedges.len();
+                    wedges.entry((*i, v0.pos)).or_insert(len);
+                }
+                let edge = [usize::min(v0.pos, v1.pos), usize::max(v0.pos, v1.pos)];
+                if v0.pos != v1.pos && !seams.contains(&edge) {
+                    edge_faces.entry(edge).or_default().push(*i);
+                }
+            })
+    });
+    let mut sets = DisjointSet::new(wedges.len());
+    edge_faces.iter().for_each(|(edge, faces)| {
+        if let [f, g] = faces.as_slice() {
+            edge.iter().for_each(|pos| {
+                if let (Some(a), Some(b)) = (wedges.get(&(*f, *pos)), wedges.get(&(*g, *pos))) {
+                    sets.union(*a, *b);
+                }
+            })
+        }
+    });
+    wedges
+        .into_iter()
+        .map(|(corner, i)| (corner, sets.root(i)))
+        .collect()
+}
+
+/// Computes the seams by the spanning tree of the faces. See [`UVParameterization::auto_seams`].
+fn auto_seams(mesh: &PolygonMesh) -> Vec<[usize; 2]> {
+    let positions = mesh.positions();
+    let normals: Vec<Vector3> = mesh
+        .face_iter()
+        .enumerate()
+        .map(|(i, face)| FaceNormal::new(positions, face, i).normal)
+        .collect();
+    let mut edge_faces = HashMap::<[usize; 2], Vec<usize>>::default();
+    mesh.face_iter().enumerate().for_each(|(i, face)| {
+        face.iter()
+            .zip(face.iter().cycle().skip(1))
+            .filter(|(v0, v1)| v0.pos != v1.pos)
+            .for_each(|(v0, v1)| {
+                let edge = [usize::min(v0.pos, v1.pos), usize::max(v0.pos, v1.pos)];
+                edge_faces.entry(edge).or_default().push(i);
+            })
+    });
+    let mut edges: Vec<([usize; 2], Vec<usize>)> = edge_faces.into_iter().collect();
+    edges.sort();
+
+    // glue the faces along the flattest edges first
+    let angle = |faces: &[usize]| match faces {
+        [i, j] => {
+            let angle = normals[*i].angle(normals[*j]).0;
+            match angle.is_nan() {
+                true => 0.0,
+                false => angle,
+            }
+        }
+        _ => f64::INFINITY,
+    };
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|a, b| angle(&edges[*a].1).total_cmp(&angle(&edges[*b].1)));
+    let mut sets = DisjointSet::new(normals.len());
+    let mut cut = vec![true; edges.len()];
+    order.into_iter().for_each(|k| {
+        if let [i, j] = edges[k].1.as_slice() {
+            if sets.union(*i, *j) {
+                cut[k] = false;
+            }
+        }
+    });
+    // the edges of the cut graph, which spans all the vertices of the closed components
+    let cut_graph: Vec<usize> = (0..edges.len()).filter(|k| cut[*k]).collect();
+    let mut vertex_edges = HashMap::<usize, Vec<usize>>::default();
+    cut_graph.iter().for_each(|k| {
+        edges[*k]
+            .0
+            .iter()
+            .for_each(|v| vertex_edges.entry(*v).or_default().push(*k));
+    });
+
+    // prune the dangling edges
+    let mut pruned = vec![false; edges.len()];
+    let mut degrees: HashMap<usize, usize> =
+        vertex_edges.iter().map(|(v, es)| (*v, es.len())).collect();
+    let mut stack: Vec<usize> = degrees
+        .iter()
+        .filter(|(_, degree)| **degree == 1)
+        .map(|(v, _)| *v)
+        .collect();
+    stack.sort();
+    while let Some(v) = stack.pop() {
+        let edge = vertex_edges[&v].iter().find(|k| !pruned[**k]).copied();
+        if let Some(k) = edge {
+            pruned[k] = true;
+            edges[k].0.iter().for_each(|w| {
+                let degree = degrees.get_mut(w).unwrap();
+                *degree -= 1;
+                if *degree == 1 {
+                    stack.push(*w);
+                }
+            });
+        }
+    }
+    let mut seams: Vec<usize> = cut_graph
+        .iter()
+        .filter(|k| !pruned[**k] && edges[**k].1.len() > 1)
+        .copied()
+        .collect();
+
+    // the closed components without seams, i.e. of genus zero
+    let mut open = HashSet::<usize>::default();
+    edges.iter().for_each(|(_, faces)| {
+        if faces.len() != 2 {
+            faces.iter().for_each(|i| {
+                open.insert(sets.root(*i));
+            });
+        }
+    });
+    seams.iter().for_each(|k| {
+        let i = edges[*k].1[0];
+        open.insert(sets.root(i));
+    });
+    let mut components = HashMap::<usize, Vec<usize>>::default();
+    (0..edges.len()).for_each(|k| {
+        let component = sets.root(edges[k].1[0]);
+        if !open.contains(&component) {
+            components.entry(component).or_default().push(k);
+        }
+    });
+    let mut components: Vec<Vec<usize>> = components.into_values().collect();
+    components.sort();
+    components.into_iter().for_each(|component| {
+        let weight = |k: usize| {
+            let [v, w] = edges[k].0;
+            let visibility = f64::max(1.0 - angle(&edges[k].1) / PI, 0.05);
+            positions[v].distance(positions[w]) * visibility
+        };
+        seams.extend(seam_path(positions, &edges, &component, weight));
+    });
+    let mut seams: Vec<[usize; 2]> = seams.into_iter().map(|k| edges[k].0).collect();
+    seams.sort();
+    seams
+}
+
+/// The seam of the closed component of genus zero, the least visible path between the two far
+/// vertices. The edges of the component and the path are the indices of `edges`.
+fn seam_path(
+    positions: &[Point3],
+    edges: &[([usize; 2], Vec<usize>)],
+    component: &[usize],
+    weight: impl Fn(usize) -> f64,
+) -> Vec<usize> {
+    let mut adjacency = HashMap::<usize, Vec<(usize, usize)>>::default();
+    component.iter().for_each(|k| {
+        let [v, w] = edges[*k].0;
+        adjacency.entry(v).or_default().push((w, *k));
+        adjacency.entry(w).or_default().push((v, *k));
+    });
+    let length = |k: usize| {
+        let [v, w] = edges[k].0;
+        positions[v].distance(positions[w])
+    };
+    let start = edges[component[0]].0[0];
+    let (end0, _) = shortest_paths(&adjacency, start, length);
+    let (end1, _) = shortest_paths(&adjacency, end0, length);
+    let (_, parents) = shortest_paths(&adjacency, end0, weight);
+    let mut path = Vec::new();
+    let mut v = end1;
+    while let Some((w, k)) = parents.get(&v) {
+        path.push(*k);
+        v = *w;
+    }
+    path
+}
+
+/// The shortest paths from `source` by the Dijkstra method. Returns the farthest vertex and the
+/// map from the vertices to the previous vertices and edges on the paths.
+fn shortest_paths(
+    adjacency: &HashMap<usize, Vec<(usize, usize)>>,
+    source: usize,
+    weight: impl Fn(usize) -> f64,
+) -> (usize, HashMap<usize, (usize, usize)>) {
+    let mut distances = HashMap::<usize, f64>::default();
+    let mut parents = HashMap::<usize, (usize, usize)>::default();
+    let mut farthest = source;
+    // The bits of non-negative floats are in the same order as the floats.
+    let mut heap = BinaryHeap::from([Reverse((0.0_f64.to_bits(), source))]);
+    distances.insert(source, 0.0);
+    let mut fixed = HashSet::<usize>::default();
+    while let Some(Reverse((bits, v))) = heap.pop() {
+        if !fixed.insert(v) {
+            continue;
+        }
+        farthest = v;
+        let distance = f64::from_bits(bits);
+        adjacency[&v].iter().for_each(|(w, k)| {
+            let new_distance = distance + weight(*k);
+            let current = distances.get(w).copied().unwrap_or(f64::INFINITY);
+            if !fixed.contains(w) && new_distance < current {
+                distances.insert(*w, new_distance);
+                parents.insert(*w, (v, *k));
+                heap.push(Reverse((new_distance.to_bits(), *w)));
+            }
+        });
+    }
+    (farthest, parents)
+}
+
+/// the coordinates of the triangle on its plane, or `None` if the triangle is degenerate.
+fn local_triangle(p: [Point3; 3]) -> Option<[Vector2; 3]> {
+    let (e1, e2) = (p[1] - p[0], p[2] - p[0]);
+    let len = e1.magnitude();
+    let area2 = e1.cross(e2).magnitude();
+    if len.so_small() || area2.so_small2() {
+        return None;
+    }
+    let x = e2.dot(e1) / len;
+    let y = area2 / len;
+    Some([Vector2::zero(), Vector2::new(len, 0.0), Vector2::new(x, y)])
+}
+
+/// Flattens the triangles by the least squares conformal maps.
+fn lscm(points: &[Point3], triangles: &[[usize; 3]]) -> Vec<Vector2> {
+    let n = points.len();
+    let farthest = |i: usize| {
+        (0..n).fold(i, |j, k| {
+            match points[k].distance2(points[i]) > points[j].distance2(points[i]) {
+                true => k,
+                false => j,
+            }
+        })
+    };
+    let pin0 = farthest(0);
+    let pin1 = farthest(pin0);
+    let mut uv_coords = vec![Vector2::zero(); n];
+    if pin0 == pin1 {
+        return uv_coords;
+    }
+    uv_coords[pin1] = Vector2::new(points[pin0].distance(points[pin1]), 0.0);
+
+    // the index of the variable u of the vertex, and the one of v is the next.
+    let mut variable = vec![None; n];
+    (0..n)
+        .filter(|i| *i != pin0 && *i != pin1)
+        .enumerate()
+        .for_each(|(k, i)| variable[i] = Some(2 * k));
+    let dim = 2 * (n - 2);
+    let mut matrix = vec![HashMap::<usize, f64>::default(); dim];
+    let mut rhs = vec![0.0; dim];
+    triangles.iter().for_each(|tri| {
+        let local = match local_triangle(tri.map(|i| points[i])) {
+            Some(local) => local,
+            None => return,
+        };
+        let area2 = local[1].x * local[2].y;
+        let scale = 1.0 / f64::sqrt(area2);
+        // the residuals of the Cauchy-Riemann equations u_x = v_y and u_y = -v_x
+        let rows = [0, 1].map(|r| {
+            (0..3)
+                .flat_map(|j| {
+                    let e = local[(j + 2) % 3] - local[(j + 1) % 3];
+                    let (cu, cv) = match r {
+                        0 => (e.y, e.x),
+                        _ => (e.x, -e.y),
+                    };
+                    [(tri[j], 0, cu * scale), (tri[j], 1, cv * scale)]
+                })
+                .collect::<Vec<_>>()
+        });
+        rows.iter().for_each(|row| {
+            let constant: f64 = row
+                .iter()
+                .filter(|(i, _, _)| variable[*i].is_none())
+                .map(|(i, k, c)| c * uv_coords[*i][*k])
+                .sum();
+            let free: Vec<(usize, f64)> = row
+                .iter()
+                .filter_map(|(i, k, c)| Some((variable[*i]? + k, *c)))
+                .collect();
+            free.iter().for_each(|(a, ca)| {
+                free.iter().for_each(|(b, cb)| {
+                    *matrix[*a].entry(*b).or_insert(0.0) += ca * cb;
+                });
+                rhs[*a] -= ca * constant;
+            });
+        });
+    });
+    let matrix: Vec<Vec<(usize, f64)>> = matrix
+        .into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect();
+    let solution = conjugate_gradient(&matrix, &rhs);
+    (0..n).for_each(|i| {
+        if let Some(k) = variable[i] {
+            uv_coords[i] = Vector2::new(solution[k], solution[k + 1]);
+        }
+    });
+
+    // fix the orientation and the scale
+    let (area, uv_area) = triangles.iter().fold((0.0, 0.0), |(area, uv_area), tri| {
+        let p = tri.map(|i| points[i]);
+        let q = tri.map(|i| uv_coords[i]);
+        let uv_cross = (q[1] - q[0]).perp_dot(q[2] - q[0]);
+        (
+            area + (p[1] - p[0]).cross(p[2] - p[0]).magnitude() / 2.0,
+            uv_area + uv_cross / 2.0,
+        )
+    });
+    if uv_area < 0.0 {
+        uv_coords.iter_mut().for_each(|uv| uv.y = -uv.y);
+    }
+    if !uv_area.so_small() {
+        let scale = f64::sqrt(area / uv_area.abs());
+        uv_coords.iter_mut().for_each(|uv| *uv *= scale);
+    }
+    uv_coords
+}
+
+/// Solves the symmetric positive semi-definite sparse system by the Jacobi preconditioned
+/// conjugate gradient method.
+fn conjugate_gradient(matrix: &[Vec<(usize, f64)>], rhs: &[f64]) -> Vec<f64> {
+    let dim = rhs.len();
+    let multiply = |x: &[f64]| -> Vec<f64> {
+        matrix
+            .iter()
+            .map(|row| row.iter().map(|(j, a)| a * x[*j]).sum())
+            .collect()
+    };
+    let dot = |x: &[f64], y: &[f64]| -> f64 { x.iter().zip(y).map(|(a, b)| a * b).sum() };
+    let inverse_diagonal: Vec<f64> = matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let diagonal = row.iter().find(|(j, _)| *j == i).map(|(_, a)| *a);
+            match diagonal {
+                Some(a) if a > 0.0 => 1.0 / a,
+                _ => 0.0,
+            }
+        })
+        .collect();
+    let mut x = vec![0.0; dim];
+    let mut r = rhs.to_vec();
+    let mut z: Vec<f64> = r
+        .iter()
+        .zip(&inverse_diagonal)
+        .map(|(a, b)| a * b)
+        .collect();
+    let mut p = z.clone();
+    let mut rz = dot(&r, &z);
+    let tolerance = dot(rhs, rhs) * 1.0e-24;
+    for _ in 0..2 * dim + 10 {
+        if dot(&r, &r) <= tolerance {
+            break;
+        }
+        let ap = multiply(&p);
+        let pap = dot(&p, &ap);
+        if pap <= 0.0 {
+            break;
+        }
+        let alpha = rz / pap;
+        x.iter_mut().zip(&p).for_each(|(x, p)| *x += alpha * p);
+        r.iter_mut().zip(&ap).for_each(|(r, ap)| *r -= alpha * ap);
+        z = r
+            .iter()
+            .zip(&inverse_diagonal)
+            .map(|(a, b)| a * b)
+            .collect();
+        let new_rz = dot(&r, &z);
+        let beta = new_rz / rz;
+        rz = new_rz;
+        p.iter_mut().zip(&z).for_each(|(p, z)| *p = z + beta * *p);
+    }
+    x
+}
+
+/// Packs the charts by the shelf algorithm, and returns the uv coordinates of all charts.
+fn pack(charts: &[Chart]) -> Vec<Vector2> {
+    let boxes: Vec<BoundingBox<Point2>> = charts
+        .iter()
+        .map(|chart| {
+            chart
+                .uv_coords
+                .iter()
+                .map(|uv| Point2::from_vec(*uv))
+                .collect()
+        })
+        .collect();
+    let sizes: Vec<Vector2> = boxes
+        .iter()
+        .map(|bdd| match bdd.is_empty() {
+            true => Vector2::zero(),
+            false => bdd.diagonal(),
+        })
+        .collect();
+    let area: f64 = sizes.iter().map(|size| size.x * size.y).sum();
+    let margin = f64::sqrt(area) / 64.0;
+    let max_width = sizes.iter().fold(0.0, |w, size| f64::max(w, size.x));
+    let width = f64::max(f64::sqrt(area) * 1.2, max_width) + margin;
+
+    let mut order: Vec<usize> = (0..charts.len()).collect();
+    order.sort_by(|i, j| sizes[*j].y.partial_cmp(&sizes[*i].y).unwrap());
+    let mut origins = vec![Vector2::zero(); charts.len()];
+    let (mut cursor, mut shelf_height) = (Vector2::new(margin, margin), 0.0);
+    order.into_iter().for_each(|i| {
+        if cursor.x + sizes[i].x + margin > width && cursor.x > margin {
+            cursor = Vector2::new(margin, cursor.y + shelf_height + margin);
+            shelf_height = 0.0;
+        }
+        origins[i] = cursor;
+        cursor.x += sizes[i].x + margin;
+        shelf_height = f64::max(shelf_height, sizes[i].y);
+    });
+    let extent = f64::max(width, cursor.y + shelf_height + margin);
+    let scale = match extent.so_small() {
+        true => 1.0,
+        false => 1.0 / extent,
+    };
+    charts
+        .iter()
+        .zip(boxes.iter().zip(origins))
+        .flat_map(|(chart, (bdd, origin))| {
+            let min = match bdd.is_empty() {
+                true => Vector2::zero(),
+                false => bdd.min().to_vec(),
+            };
+            chart
+                .uv_coords
+                .iter()
+                .map(move |uv| (uv - min + origin) * scale)
+        })
+        .collect()
+}
@@ -0,0 +1,552 @@
+use super::*;
+use rustc_hash::FxHashSet as HashSet;
+use std::f64::consts::PI;
+
+/// remeshing algorithms
+pub trait Remeshing {
+    /// Incremental isotropic remeshing toward the edge length `target_length`.
+    /// # Details
+    /// In each of `iterations` steps, the following operations are applied.
+    /// 1. Split the edges longer than `4/3 * target_length` at their midpoints.
+    /// 1. Collapse the edges shorter than `4/5 * target_length` if no longer edges arise.
+    /// 1. Flip the edges if the valences of the vertices become closer to six.
+    /// 1. Move the vertices to the centroids of the neighbors in their tangent planes.
+    ///
+    /// The boundary edges, the non-manifold edges, and the edges whose dihedral angles are
+    /// more than `feature_angle` are the feature edges, which are never flipped and
+    /// collapsed only along themselves. The vertices on feature edges are not moved, and
+    /// the corners, the vertices with other than two feature edges, are never removed.
+    ///
+    /// # Remarks
+    /// - Polygons are triangulated and the faces are connected by the position indices.
+    /// Apply [`OptimizingFilter::put_together_same_attrs`] to separated meshes beforehand.
+    /// - The degenerate triangles with the same vertices are removed.
+    /// - The uv coordinates and normals are removed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    ///
+    /// // the thin triangles on the rectangle [0, 4] x [0, 1]
+    /// let positions = (0..18)
+    ///     .map(|i| Point3::new((i / 2) as f64 * 0.5, (i % 2) as f64, 0.0))
+    ///     .collect();
+    /// let faces = (0..8)
+    ///     .map(|i| [2 * i, 2 * i + 2, 2 * i + 3, 2 * i + 1])
+    ///     .collect();
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions,
+    ///         ..Default::default()
+    ///     },
+    ///     faces,
+    /// );
+    ///
+    /// mesh.isotropic_remeshing(0.2, std::f64::consts::PI / 6.0, 5);
+    /// let lengths: Vec<f64> = mesh
+    ///     .faces()
+    ///     .triangle_iter()
+    ///     .flat_map(|tri| {
+    ///         let p = tri.map(|v| mesh.positions()[v.pos]);
+    ///         [p[0].distance(p[1]), p[1].distance(p[2]), p[2].distance(p[0])]
+    ///     })
+    ///     .collect();
+    /// assert!(lengths.iter().all(|l| (0.1..0.4).contains(l)));
+    /// ```
+    fn isotropic_remeshing(
+        &mut self,
+        target_length: f64,
+        feature_angle: f64,
+        iterations: usize,
+    ) -> &mut Self;
+    /// Flips the edges until all edges satisfy the local Delaunay criterion, i.e. the sum of
+    /// the opposite angles of each inner edge is not more than `PI`.
+    ///
+    /// The feature edges, defined in the same way as [`Remeshing::isotropic_remeshing`], are
+    /// never flipped, and an edge is not flipped if the new edge would be a feature edge.
+    /// The positions are not moved, so that the shape is kept for nearly flat regions.
+    /// For the Delaunay triangulation on the intrinsic metric, use [`IntrinsicTriangulation`].
+    ///
+    /// # Remarks
+    /// - Polygons are triangulated and the faces are connected by the position indices.
+    /// Apply [`OptimizingFilter::put_together_same_attrs`] to separated meshes beforehand.
+    /// - The degenerate triangles with the same vertices are removed.
+    /// - The uv coordinates and normals are removed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // a square divided by the diagonal of the obtuse angles
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: vec![
+    ///             Point3::new(0.0, 0.0, 0.0),
+    ///             Point3::new(2.0, 0.0, 0.0),
+    ///             Point3::new(1.0, 0.2, 0.0),
+    ///             Point3::new(1.0, -0.2, 0.0),
+    ///         ],
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&[&[0, 1, 2], &[1, 0, 3]]),
+    /// );
+    /// mesh.delaunay_flip(std::f64::consts::PI / 6.0);
+    /// let faces = mesh.faces().tri_faces();
+    /// assert_eq!(faces.len(), 2);
+    /// // the long diagonal is flipped to the short one.
+    /// faces.iter().for_each(|tri| {
+    ///     let count = tri.iter().filter(|v| mesh.positions()[v.pos].x == 1.0).count();
+    ///     assert_eq!(count, 2);
+    /// });
+    /// ```
+    fn delaunay_flip(&mut self, feature_angle: f64) -> &mut Self;
+}
+
+impl Remeshing for PolygonMesh {
+    fn isotropic_remeshing(
+        &mut self,
+        target_length: f64,
+        feature_angle: f64,
+        iterations: usize,
+    ) -> &mut Self {
+        nonpositive_tolerance!(target_length, 0.0);
+        let mut remesher = Remesher::new(self, feature_angle);
+        let (high, low) = (target_length * 4.0 / 3.0, target_length * 4.0 / 5.0);
+        (0..iterations).for_each(|_| {
+            remesher.split_long_edges(high);
+            remesher.collapse_short_edges(low, high);
+            remesher.equalize_valences();
+            remesher.tangential_relaxation();
+        });
+        *self = remesher.into_mesh();
+        self
+    }
+    fn delaunay_flip(&mut self, feature_angle: f64) -> &mut Self {
+        let mut remesher = Remesher::new(self, feature_angle);
+        remesher.delaunay_flips(f64::cos(feature_angle));
+        *self = remesher.into_mesh();
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Remesher {
+    positions: Vec<Point3>,
+    /// `None` for the removed triangles
+    triangles: Vec<Option<[usize; 3]>>,
+    vertex_triangles: Vec<Vec<usize>>,
+    features: HashSet<[usize; 2]>,
+}
+
+#[inline(always)]
+fn edge(a: usize, b: usize) -> [usize; 2] { [usize::min(a, b), usize::max(a, b)] }
+
+impl Remesher {
+    fn new(mesh: &PolygonMesh, feature_angle: f64) -> Self {
+        let positions = mesh.positions().clone();
+        let triangles: Vec<Option<[usize; 3]>> = mesh
+            .faces()
+            .triangle_iter()
+            .map(|tri| tri.map(|v| v.pos))
+            // skip the triangles with the same vertices
+            .filter(|tri| tri[0] != tri[1] && tri[1] != tri[2] && tri[2] != tri[0])
+            .map(Some)
+            .collect();
+        let mut vertex_triangles = vec![Vec::new(); positions.len()];
+        triangles.iter().enumerate().for_each(|(i, tri)| {
+            tri.unwrap()
+                .iter()
+                .for_each(|v| vertex_triangles[*v].push(i));
+        });
+        let mut remesher = Self {
+            positions,
+            triangles,
+            vertex_triangles,
+            features: HashSet::default(),
+        };
+        let cos = f64::cos(feature_angle);
+        let features: HashSet<[usize; 2]> = remesher
+            .edges()
+            .into_iter()
+            .filter(|[a, b]| {
+                let tris = remesher.edge_triangles(*a, *b);
+                if tris.len() != 2 {
+                    return true;
+                }
+                let (n0, n1) = (remesher.normal(tris[0]), remesher.normal(tris[1]));
+                n0.normalize().dot(n1.normalize()) < cos
+            })
+            .collect();
+        remesher.features = features;
+        remesher
+    }
+
+    fn triangle(&self, i: usize) -> [usize; 3] { self.triangles[i].unwrap() }
+
+    /// the normal whose length is twice the area
+    fn normal(&self, i: usize) -> Vector3 {
+        let p = self.triangle(i).map(|v| self.positions[v]);
+        (p[1] - p[0]).cross(p[2] - p[0])
+    }
+
+    fn edges(&self) -> Vec<[usize; 2]> {
+        let mut edges: Vec<[usize; 2]> = self
+            .triangles
+            .iter()
+            .flatten()
+            .flat_map(|tri| {
+                [
+                    edge(tri[0], tri[1]),
+                    edge(tri[1], tri[2]),
+                    edge(tri[2], tri[0]),
+                ]
+            })
+            .collect();
+        edges.sort();
+        edges.dedup();
+        edges
+    }
+
+    fn edge_triangles(&self, a: usize, b: usize) -> Vec<usize> {
+        self.vertex_triangles[a]
+            .iter()
+            .filter(|i| self.triangle(**i).contains(&b))
+            .copied()
+            .collect()
+    }
+
+    fn neighbors(&self, v: usize) -> Vec<usize> { self.neighbors_of(&self.vertex_triangles[v], v) }
+
+    fn neighbors_of(&self, triangles: &[usize], v: usize) -> Vec<usize> {
+        let mut neighbors: Vec<usize> = triangles
+            .iter()
+            .flat_map(|i| self.triangle(*i))
+            .filter(|w| *w != v)
+            .collect();
+        neighbors.sort();
+        neighbors.dedup();
+        neighbors
+    }
+
+    fn is_feature_vertex(&self, v: usize) -> bool {
+        self.neighbors(v)
+            .into_iter()
+            .any(|w| self.features.contains(&edge(v, w)))
+    }
+
+    fn is_corner(&self, v: usize) -> bool {
+        let count = self
+            .neighbors(v)
+            .into_iter()
+            .filter(|w| self.features.contains(&edge(v, *w)))
+            .count();
+        count != 0 && count != 2
+    }
+
+    fn length(&self, a: usize, b: usize) -> f64 { self.positions[a].distance(self.positions[b]) }
+
+    fn split_long_edges(&mut self, high: f64) {
+        for _ in 0..64 {
+            let long_edges: Vec<[usize; 2]> = self
+                .edges()
+                .into_iter()
+                .filter(|[a, b]| self.length(*a, *b) > high)
+                .collect();
+            if long_edges.is_empty() {
+                return;
+            }
+            long_edges.into_iter().for_each(|[a, b]| self.split(a, b));
+        }
+    }
+
+    fn split(&mut self, a: usize, b: usize) {
+        let m = self.positions.len();
+        self.positions
+            .push(self.positions[a].midpoint(self.positions[b]));
+        self.vertex_triangles.push(Vec::new());
+        self.edge_triangles(a, b).into_iter().for_each(|i| {
+            let tri = self.triangle(i);
+            let c = tri.into_iter().find(|v| *v != a && *v != b).unwrap();
+            let new_tri = tri.map(|v| if v == a { m } else { v });
+            self.triangles[i] = Some(tri.map(|v| if v == b { m } else { v }));
+            let j = self.triangles.len();
+            self.triangles.push(Some(new_tri));
+            self.vertex_triangles[b].retain(|k| *k != i);
+            self.vertex_triangles[b].push(j);
+            self.vertex_triangles[c].push(j);
+            self.vertex_triangles[m].extend([i, j]);
+        });
+        if self.features.remove(&edge(a, b)) {
+            self.features.insert(edge(a, m));
+            self.features.insert(edge(m, b));
+        }
+    }
+
+    fn collapse_short_edges(&mut self, low: f64, high: f64) {
+        let mut short_edges: Vec<[usize; 2]> = self
+            .edges()
+            .into_iter()
+            .filter(|[a, b]| self.length(*a, *b) < low)
+            .collect();
+        short_edges.sort_by(|[a0, b0], [a1, b1]| {
+            self.length(*a0, *b0)
+                .partial_cmp(&self.length(*a1, *b1))
+                .unwrap()
+        });
+        short_edges.into_iter().for_each(|[a, b]| {
+            let tris = self.edge_triangles(a, b);
+            if tris.is_empty() || self.length(a, b) >= low {
+                return;
+            }
+            // a vertex on features can be removed only along the feature.
+            let removable = |v: usize| self.features.contains(&edge(a, b)) && !self.is_corner(v);
+            let (removed, kept) = match (self.is_feature_vertex(a), self.is_feature_vertex(b)) {
+                (false, _) => (a, b),
+                (true, false) => (b, a),
+                _ if removable(a) => (a, b),
+                _ if removable(b) => (b, a),
+                _ => return,
+            };
+            if self.can_collapse(removed, kept, tris.len(), high) {
+                self.collapse(removed, kept);
+            }
+        });
+    }
+
+    fn can_collapse(&self, removed: usize, kept: usize, num_triangles: usize, high: f64) -> bool {
+        let (neighbors0, neighbors1) = (self.neighbors(removed), self.neighbors(kept));
+        let common = neighbors0.iter().filter(|v| neighbors1.contains(v)).count();
+        // the link condition for keeping the manifold
+        if common != num_triangles || neighbors0.len() <= 2 || neighbors1.len() <= 2 {
+            return false;
+        }
+        // the features of the kept vertex must not be connected to the removed one.
+        if self.is_feature_vertex(removed) && self.is_feature_vertex(kept) {
+            let crossing = neighbors0.iter().any(|v| {
+                *v != kept
+                    && self.features.contains(&edge(removed, *v))
+                    && self.features.contains(&edge(kept, *v))
+            });
+            if crossing {
+                return false;
+            }
+        }
+        if neighbors0
+            .iter()
+            .any(|v| self.positions[*v].distance(self.positions[kept]) > high)
+        {
+            return false;
+        }
+        // no flipped triangles
+        self.vertex_triangles[removed].iter().all(|i| {
+            let tri = self.triangle(*i);
+            if tri.contains(&kept) {
+                return true;
+            }
+            let p = tri.map(|v| match v == removed {
+                true => self.positions[kept],
+                false => self.positions[v],
+            });
+            let normal = (p[1] - p[0]).cross(p[2] - p[0]);
+            normal.dot(self.normal(*i)) > 0.0
+        })
+    }
+
+    fn collapse(&mut self, removed: usize, kept: usize) {
+        let tris = std::mem::take(&mut self.vertex_triangles[removed]);
+        let feature_neighbors: Vec<usize> = self
+            .neighbors_of(&tris, removed)
+            .into_iter()
+            .filter(|v| self.features.remove(&edge(removed, *v)))
+            .collect();
+        tris.into_iter().for_each(|i| {
+            let tri = self.triangle(i);
+            match tri.contains(&kept) {
+                true => {
+                    tri.iter()
+                        .for_each(|v| self.vertex_triangles[*v].retain(|j| *j != i));
+                    self.triangles[i] = None;
+                }
+                false => {
+                    self.triangles[i] = Some(tri.map(|v| if v == removed { kept } else { v }));
+                    self.vertex_triangles[kept].push(i);
+                }
+            }
+        });
+        feature_neighbors.into_iter().for_each(|v| {
+            if v != kept {
+                self.features.insert(edge(kept, v));
+            }
+        });
+    }
+
+    /// Returns `(t0, t1, c, d)` for the flippable edge `[a, b]`, where the triangle `t0`
+    /// has the edge from `a` to `b`, `t1` is the other one, and `c` and `d` are the opposite
+    /// vertices of `t0` and `t1`.
+    fn flippable(&self, a: usize, b: usize) -> Option<(usize, usize, usize, usize)> {
+        if self.features.contains(&edge(a, b)) {
+            return None;
+        }
+        let tris = self.edge_triangles(a, b);
+        if tris.len() != 2 {
+            return None;
+        }
+        // orient so that the first triangle has the edge from `a` to `b`
+        let (t0, t1) = match self.triangle(tris[0]).iter().position(|v| *v == a) {
+            Some(k) if self.triangle(tris[0])[(k + 1) % 3] == b => (tris[0], tris[1]),
+            _ => (tris[1], tris[0]),
+        };
+        let opposite = |i: usize| {
+            self.triangle(i)
+                .into_iter()
+                .find(|v| *v != a && *v != b)
+                .unwrap()
+        };
+        let (c, d) = (opposite(t0), opposite(t1));
+        match c == d || !self.edge_triangles(c, d).is_empty() {
+            true => None,
+            false => Some((t0, t1, c, d)),
+        }
+    }
+
+    /// Flips the edge `[a, b]` to `[c, d]` by the output of [`Remesher::flippable`].
+    /// Returns `false` without flipping if some new triangle is reversed.
+    fn flip(&mut self, a: usize, b: usize, (t0, t1, c, d): (usize, usize, usize, usize)) -> bool {
+        let (new0, new1) = ([a, d, c], [b, c, d]);
+        let normal = |tri: [usize; 3]| {
+            let p = tri.map(|v| self.positions[v]);
+            (p[1] - p[0]).cross(p[2] - p[0])
+        };
+        let old = self.normal(t0) + self.normal(t1);
+        if normal(new0).dot(old) <= 0.0 || normal(new1).dot(old) <= 0.0 {
+            return false;
+        }
+        self.triangles[t0] = Some(new0);
+        self.triangles[t1] = Some(new1);
+        self.vertex_triangles[a].retain(|i| *i != t1);
+        self.vertex_triangles[b].retain(|i| *i != t0);
+        self.vertex_triangles[c].push(t1);
+        self.vertex_triangles[d].push(t0);
+        true
+    }
+
+    fn equalize_valences(&mut self) {
+        self.edges().into_iter().for_each(|[a, b]| {
+            let flippable = match self.flippable(a, b) {
+                Some(flippable) => flippable,
+                None => return,
+            };
+            let (_, _, c, d) = flippable;
+            let target = |v: usize| match self.is_feature_vertex(v) {
+                true => 4,
+                false => 6,
+            };
+            let deviation = |diff: [i64; 4]| -> i64 {
+                [a, b, c, d]
+                    .iter()
+                    .zip(diff)
+                    .map(|(v, diff)| {
+                        let valence = self.neighbors(*v).len() as i64 + diff;
+                        (valence - target(*v)).pow(2)
+                    })
+                    .sum()
+            };
+            if deviation([-1, -1, 1, 1]) >= deviation([0, 0, 0, 0]) {
+                return;
+            }
+            self.flip(a, b, flippable);
+        });
+    }
+
+    /// Flips the edges whose opposite angles are more than `PI` in total, until all edges
+    /// are locally Delaunay. The edges are not flipped if the new edges are feature edges
+    /// by `cos`, the cosine of the feature angle.
+    fn delaunay_flips(&mut self, cos: f64) {
+        let angle = |p: Point3, q: Point3, r: Point3| (q - p).angle(r - p).0;
+        let mut stack = self.edges();
+        // The flips may cycle on curved surfaces by the rounding errors.
+        let mut remaining = 16 * self.triangles.len() + 16;
+        while let Some([a, b]) = stack.pop() {
+            let flippable = match self.flippable(a, b) {
+                Some(flippable) => flippable,
+                None => continue,
+            };
+            let (_, _, c, d) = flippable;
+            let [pa, pb, pc, pd] = [a, b, c, d].map(|v| self.positions[v]);
+            if angle(pc, pa, pb) + angle(pd, pa, pb) <= PI + TOLERANCE {
+                continue;
+            }
+            let n0 = (pd - pa).cross(pc - pa);
+            let n1 = (pc - pb).cross(pd - pb);
+            if n0.so_small() || n1.so_small() || n0.normalize().dot(n1.normalize()) < cos {
+                continue;
+            }
+            if remaining == 0 {
+                break;
+            }
+            if self.flip(a, b, flippable) {
+                remaining -= 1;
+                stack.extend([edge(a, c), edge(c, b), edge(b, d), edge(d, a)]);
+            }
+        }
+    }
+
+    fn tangential_relaxation(&mut self) {
+        let moved: Vec<(usize, Point3)> = (0..self.positions.len())
+            .filter(|v| !self.vertex_triangles[*v].is_empty() && !self.is_feature_vertex(*v))
+            .filter_map(|v| {
+                let neighbors = self.neighbors(v);
+                let centroid = neighbors
+                    .iter()
+                    .fold(Vector3::zero(), |sum, w| sum + self.positions[*w].to_vec())
+                    / neighbors.len() as f64;
+                let normal = self.vertex_triangles[v]
+                    .iter()
+                    .fold(Vector3::zero(), |sum, i| sum + self.normal(*i));
+                if normal.so_small() {
+                    return None;
+                }
+                let normal = normal.normalize();
+                let diff = centroid - self.positions[v].to_vec();
+                Some((v, self.positions[v] + (diff - diff.dot(normal) * normal)))
+            })
+            .collect();
+        moved.into_iter().for_each(|(v, p)| {
+            let old = std::mem::replace(&mut self.positions[v], p);
+            let flipped = self.vertex_triangles[v].iter().any(|i| {
+                let normal = self.normal(*i);
+                let tri = self.triangle(*i);
+                let q = tri.map(|w| match w == v {
+                    true => old,
+                    false => self.positions[w],
+                });
+                normal.dot((q[1] - q[0]).cross(q[2] - q[0])) <= 0.0
+            });
+            if flipped {
+                self.positions[v] = old;
+            }
+        });
+    }
+
+    fn into_mesh(self) -> PolygonMesh {
+        let mut indices = vec![None; self.positions.len()];
+        let mut positions = Vec::new();
+        let faces: Faces = self
+            .triangles
+            .into_iter()
+            .flatten()
+            .map(|tri| {
+                tri.map(|v| {
+                    *indices[v].get_or_insert_with(|| {
+                        positions.push(self.positions[v]);
+                        positions.len() - 1
+                    })
+                })
+            })
+            .collect();
+        PolygonMesh::new(
+            StandardAttributes {
+                positions,
+                ..Default::default()
+            },
+            faces,
+        )
+    }
+}
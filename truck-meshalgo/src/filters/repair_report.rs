@@ -0,0 +1,245 @@
+use super::healing::{edge_map, is_zero_area};
+use super::*;
+use crate::analyzers::Topology;
+use serde::{Deserialize, Serialize};
+use truck_topology::shell::ShellCondition;
+
+/// The passes of repairing meshes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairPass {
+    /// [`OptimizingFilter::cleanup`]
+    Cleanup,
+    /// [`Healing::heal_non_manifold`]
+    NonManifold,
+    /// [`Healing::fix_orientation`]
+    Orientation,
+}
+
+/// The entities of meshes, identified by the indices at the time of the pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum RepairEntity {
+    /// the index of a face
+    Face(usize),
+    /// the pair of the indices of positions
+    Edge([usize; 2]),
+    /// the index of a position
+    Position(usize),
+}
+
+/// The kinds of the defects found by the passes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DefectKind {
+    /// the face has the same position more than once
+    CollapsedFace,
+    /// the area of the face is zero
+    ZeroAreaFace,
+    /// the face has the same positions as a preceding face
+    DuplicateFace,
+    /// the edge is shared by three or more faces
+    NonManifoldEdge,
+    /// the neighborhood of the position is not one connected fan
+    NonManifoldVertex,
+    /// the winding of the face is not consistent with the component or its closed component
+    /// faces inward
+    InconsistentOrientation,
+}
+
+/// The fixes applied to the defective entities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RepairFix {
+    /// the entity is removed
+    Removed,
+    /// the entity is split into non-degenerate or manifold pieces, possibly none
+    Split,
+    /// the position is duplicated
+    Duplicated {
+        /// the index of the new copy
+        copy: usize,
+    },
+    /// the winding of the face is reversed
+    Flipped,
+}
+
+/// A defect found by a repairing pass and the fix applied to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairRecord {
+    /// the pass finding the defect
+    pub pass: RepairPass,
+    /// the defective entity
+    pub entity: RepairEntity,
+    /// the kind of the defect
+    pub defect: DefectKind,
+    /// the applied fix
+    pub fix: RepairFix,
+}
+
+/// The defects remaining after the repair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairResiduals {
+    /// the shell condition of the mesh
+    pub shell_condition: ShellCondition,
+    /// the number of the boundary loops
+    pub boundaries: usize,
+    /// the number of the edges shared by three or more faces
+    pub non_manifold_edges: usize,
+    /// the number of the faces whose areas are zero
+    pub zero_area_faces: usize,
+}
+
+impl RepairResiduals {
+    /// Measures the defects of `mesh`.
+    pub fn measure(mesh: &PolygonMesh) -> Self {
+        let positions = mesh.positions();
+        RepairResiduals {
+            shell_condition: mesh.shell_condition(),
+            boundaries: mesh.extract_boundaries().len(),
+            non_manifold_edges: edge_map(mesh.faces())
+                .values()
+                .filter(|incidents| incidents.len() > 2)
+                .count(),
+            zero_area_faces: mesh
+                .face_iter()
+                .filter(|face| is_zero_area(positions, face))
+                .count(),
+        }
+    }
+}
+
+/// The machine-readable report of repairing a mesh, exported as JSON.
+///
+/// The indices of the entities are the ones at the time of each pass, i.e. the indices in the
+/// records of [`RepairPass::NonManifold`] are the ones after [`RepairPass::Cleanup`].
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// let mut mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions: vec![
+///             Point3::new(0.0, 0.0, 0.0),
+///             Point3::new(1.0, 0.0, 0.0),
+///             Point3::new(0.0, 1.0, 0.0),
+///         ],
+///         ..Default::default()
+///     },
+///     Faces::from_iter(&[[0, 1, 2], [2, 1, 0]]),
+/// );
+/// let report = mesh.repair();
+/// assert_eq!(report.count(DefectKind::DuplicateFace), 1);
+/// assert_eq!(
+///     report.records[0],
+///     RepairRecord {
+///         pass: RepairPass::Cleanup,
+///         entity: RepairEntity::Face(1),
+///         defect: DefectKind::DuplicateFace,
+///         fix: RepairFix::Removed,
+///     },
+/// );
+/// assert_eq!(report.residuals.unwrap().boundaries, 1);
+///
+/// let json = report.to_json();
+/// assert!(json.contains("\"defect\": \"duplicate_face\""));
+/// assert_eq!(RepairReport::from_json(&json).unwrap(), report);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairReport {
+    /// the defects and the fixes, in the order of the passes
+    pub records: Vec<RepairRecord>,
+    /// the number of the removed positions
+    pub removed_positions: usize,
+    /// the number of the removed texture coordinates
+    pub removed_uv_coords: usize,
+    /// the number of the removed normal vectors
+    pub removed_normals: usize,
+    /// the defects remaining after the repair, if measured
+    pub residuals: Option<RepairResiduals>,
+}
+
+impl RepairReport {
+    fn push(&mut self, pass: RepairPass, entity: RepairEntity, defect: DefectKind, fix: RepairFix) {
+        self.records.push(RepairRecord {
+            pass,
+            entity,
+            defect,
+            fix,
+        })
+    }
+    /// Appends the records of [`OptimizingFilter::cleanup`].
+    pub fn push_cleanup(&mut self, summary: &CleanupSummary) {
+        let pass = RepairPass::Cleanup;
+        let faces = [
+            (
+                &summary.collapsed_faces,
+                DefectKind::CollapsedFace,
+                RepairFix::Split,
+            ),
+            (
+                &summary.zero_area_faces,
+                DefectKind::ZeroAreaFace,
+                RepairFix::Removed,
+            ),
+            (
+                &summary.duplicate_faces,
+                DefectKind::DuplicateFace,
+                RepairFix::Removed,
+            ),
+        ];
+        faces.into_iter().for_each(|(indices, defect, fix)| {
+            indices
+                .iter()
+                .for_each(|i| self.push(pass, RepairEntity::Face(*i), defect, fix))
+        });
+        self.removed_positions += summary.removed_positions;
+        self.removed_uv_coords += summary.removed_uv_coords;
+        self.removed_normals += summary.removed_normals;
+    }
+    /// Appends the records of [`Healing::heal_non_manifold`].
+    pub fn push_healing(&mut self, log: &HealingLog) {
+        let pass = RepairPass::NonManifold;
+        let faces = [
+            (&log.zero_area_faces, DefectKind::ZeroAreaFace),
+            (&log.duplicate_faces, DefectKind::DuplicateFace),
+        ];
+        faces.into_iter().for_each(|(indices, defect)| {
+            indices
+                .iter()
+                .for_each(|i| self.push(pass, RepairEntity::Face(*i), defect, RepairFix::Removed))
+        });
+        log.split_edges.iter().for_each(|edge| {
+            let entity = RepairEntity::Edge(*edge);
+            self.push(pass, entity, DefectKind::NonManifoldEdge, RepairFix::Split)
+        });
+        log.duplicated_vertices.iter().for_each(|(i, copy)| {
+            let fix = RepairFix::Duplicated { copy: *copy };
+            self.push(
+                pass,
+                RepairEntity::Position(*i),
+                DefectKind::NonManifoldVertex,
+                fix,
+            )
+        });
+    }
+    /// Appends the records of the faces flipped by [`Healing::fix_orientation`].
+    pub fn push_orientation(&mut self, flipped_faces: &[usize]) {
+        flipped_faces.iter().for_each(|i| {
+            let (entity, defect) = (RepairEntity::Face(*i), DefectKind::InconsistentOrientation);
+            self.push(RepairPass::Orientation, entity, defect, RepairFix::Flipped)
+        });
+    }
+    /// Returns the number of the records of `defect`.
+    #[inline(always)]
+    pub fn count(&self, defect: DefectKind) -> usize {
+        self.records.iter().filter(|r| r.defect == defect).count()
+    }
+    /// Returns the pretty-printed JSON of the report.
+    #[inline(always)]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("the report is always serializable")
+    }
+    /// Parses the report from JSON.
+    #[inline(always)]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> { serde_json::from_str(json) }
+}
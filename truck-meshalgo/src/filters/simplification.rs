@@ -0,0 +1,549 @@
+use super::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// mesh simplification by the edge collapses
+pub trait Simplification {
+    /// Simplifies the mesh by the quadric error metrics until the number of triangles becomes
+    /// not more than `target_faces`, keeping the uv coordinates and the normals.
+    /// # Details
+    /// The edges are collapsed in the ascending order of the quadric errors [Garland, Heckbert 1997].
+    /// The collapsed vertex is placed at the point on the edge minimizing the error,
+    /// and the uv coordinates and the normals of the kept vertex are linearly interpolated
+    /// along the edge in the same way.
+    ///
+    /// The boundary edges and the seams, the edges between the triangles with the different
+    /// uv coordinates or normals on the same vertices, are preserved: a vertex on them
+    /// only moves along them, and the corners, the vertices with other than two such edges,
+    /// are never moved. The collapses which flip triangles or make the mesh non-manifold
+    /// are skipped, so the result may have more triangles than `target_faces`.
+    ///
+    /// # Remarks
+    /// - Polygons are triangulated and the faces are connected by the position indices.
+    /// Apply [`OptimizingFilter::put_together_same_attrs`] to separated meshes beforehand.
+    /// - The degenerate triangles with the same vertices are removed.
+    /// - The unused attributes are removed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the square [0, 1]^2 divided into 10 x 10 quadrangles, whose uv coordinates are `(x, y)`.
+    /// let n = 10;
+    /// let positions: Vec<Point3> = (0..=n)
+    ///     .flat_map(|i| (0..=n).map(move |j| Point3::new(i as f64, j as f64, 0.0) / n as f64))
+    ///     .collect();
+    /// let uv_coords = positions.iter().map(|p| Vector2::new(p.x, p.y)).collect();
+    /// let faces: Faces = (0..n * n)
+    ///     .map(|k| {
+    ///         let (i, j) = (k / n, k % n);
+    ///         let v = |i: usize, j: usize| [i * (n + 1) + j; 2];
+    ///         [v(i, j), v(i + 1, j), v(i + 1, j + 1), v(i, j + 1)]
+    ///     })
+    ///     .map(|face| face.map(|[pos, uv]| StandardVertex { pos, uv: Some(uv), nor: None }))
+    ///     .collect();
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes { positions, uv_coords, ..Default::default() },
+    ///     faces,
+    /// );
+    ///
+    /// mesh.simplify(20);
+    /// assert!(mesh.faces().len() <= 20);
+    /// // The uv coordinates are kept on the moved vertices.
+    /// mesh.faces().triangle_iter().flatten().for_each(|v| {
+    ///     let (p, uv) = (mesh.positions()[v.pos], mesh.uv_coords()[v.uv.unwrap()]);
+    ///     assert!(uv.near(&Vector2::new(p.x, p.y)));
+    /// });
+    /// ```
+    fn simplify(&mut self, target_faces: usize) -> &mut Self;
+    /// Simplifies the mesh by clustering the vertices in the cubic cells whose edges are `cell_size`.
+    /// # Details
+    /// The vertices in the same cell are merged into their centroid [Rossignac, Borrel 1993].
+    /// The triangles whose vertices are in less than three cells and the duplicated triangles
+    /// are removed. This runs in linear time, and is suitable for generating the levels of detail
+    /// far from the viewpoint for large meshes in real time, although the topology and the
+    /// sharp features are not preserved unlike [`Simplification::simplify`].
+    ///
+    /// # Remarks
+    /// - Polygons are triangulated.
+    /// - The uv coordinates and the normals are kept on each corner.
+    /// - The unused attributes are removed.
+    /// - Nothing is done if `cell_size` is not positive.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the square [0, 1]^2 divided into 10 x 10 quadrangles
+    /// let n = 10;
+    /// let positions: Vec<Point3> = (0..=n)
+    ///     .flat_map(|i| (0..=n).map(move |j| Point3::new(i as f64, j as f64, 0.0) / n as f64))
+    ///     .collect();
+    /// let faces: Faces = (0..n * n)
+    ///     .map(|k| {
+    ///         let (i, j) = (k / n, k % n);
+    ///         let v = |i: usize, j: usize| i * (n + 1) + j;
+    ///         [v(i, j), v(i + 1, j), v(i + 1, j + 1), v(i, j + 1)]
+    ///     })
+    ///     .collect();
+    /// let mut mesh = PolygonMesh::new(
+    ///     StandardAttributes { positions, ..Default::default() },
+    ///     faces,
+    /// );
+    ///
+    /// // The vertices are merged into 4 x 4 cells.
+    /// mesh.cluster_vertices(0.26);
+    /// assert_eq!(mesh.positions().len(), 16);
+    /// assert_eq!(mesh.faces().len(), 18);
+    /// ```
+    fn cluster_vertices(&mut self, cell_size: f64) -> &mut Self;
+}
+
+impl Simplification for PolygonMesh {
+    fn simplify(&mut self, target_faces: usize) -> &mut Self {
+        let mut simplifier = Simplifier::new(self);
+        simplifier.run(target_faces);
+        *self = simplifier.into_mesh();
+        self.remove_unused_attrs()
+    }
+    fn cluster_vertices(&mut self, cell_size: f64) -> &mut Self {
+        if cell_size > 0.0 {
+            *self = vertex_clustering(self, cell_size);
+            self.remove_unused_attrs();
+        }
+        self
+    }
+}
+
+fn vertex_clustering(mesh: &PolygonMesh, cell_size: f64) -> PolygonMesh {
+    let bdd_box: BoundingBox<Point3> = mesh.positions().iter().collect();
+    let origin = match bdd_box.is_empty() {
+        true => return mesh.clone(),
+        false => bdd_box.min(),
+    };
+    let mut cells = HashMap::<[i64; 3], usize>::default();
+    let mut sums = Vec::<(Vector3, usize)>::new();
+    let clusters: Vec<usize> = mesh
+        .positions()
+        .iter()
+        .map(|p| {
+            let cell = ((p - origin) / cell_size).map(|x| x.floor() as i64).into();
+            let idx = *cells.entry(cell).or_insert_with(|| {
+                sums.push((Vector3::zero(), 0));
+                sums.len() - 1
+            });
+            sums[idx].0 += p.to_vec();
+            sums[idx].1 += 1;
+            idx
+        })
+        .collect();
+    let positions = sums
+        .into_iter()
+        .map(|(sum, count)| Point3::from_vec(sum / count as f64))
+        .collect();
+    let mut triangles = HashMap::<[usize; 3], [Vertex; 3]>::default();
+    mesh.faces()
+        .triangulate(mesh.positions())
+        .triangle_iter()
+        .for_each(|tri| {
+            let tri = tri.map(|v| Vertex {
+                pos: clusters[v.pos],
+                ..v
+            });
+            let [a, b, c] = tri.map(|v| v.pos);
+            if a != b && b != c && c != a {
+                // The same triangle with the different start vertex is the duplicated one.
+                let i = (0..3).min_by_key(|&i| tri[i].pos).unwrap();
+                let key = [tri[i].pos, tri[(i + 1) % 3].pos, tri[(i + 2) % 3].pos];
+                triangles.entry(key).or_insert(tri);
+            }
+        });
+    let mut triangles: Vec<([usize; 3], [Vertex; 3])> = triangles.into_iter().collect();
+    // keep the order of the faces independent of the hashes
+    triangles.sort_by_key(|(key, _)| *key);
+    PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            uv_coords: mesh.uv_coords().clone(),
+            normals: mesh.normals().clone(),
+        },
+        triangles.into_iter().map(|(_, tri)| tri).collect(),
+    )
+}
+
+/// weight of the planes which keep the boundaries and the seams
+const CONSTRAINT_WEIGHT: f64 = 1.0e3;
+
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    cost: f64,
+    removed: usize,
+    kept: usize,
+    /// parameter of the new position from `removed` to `kept`
+    t: f64,
+    versions: [usize; 2],
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool { self.cost == other.cost }
+}
+impl Eq for Candidate {}
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for Candidate {
+    // reversed for the min-heap
+    fn cmp(&self, other: &Self) -> Ordering { other.cost.total_cmp(&self.cost) }
+}
+
+struct Simplifier {
+    positions: Vec<Point3>,
+    uv_coords: Vec<Vector2>,
+    normals: Vec<Vector3>,
+    /// `None` for the removed triangles
+    triangles: Vec<Option<[Vertex; 3]>>,
+    vertex_triangles: Vec<Vec<usize>>,
+    quadrics: Vec<Matrix4>,
+    versions: Vec<usize>,
+    num_triangles: usize,
+}
+
+#[inline(always)]
+fn plane_quadric(normal: Vector3, point: Point3, weight: f64) -> Matrix4 {
+    let v = normal.extend(-normal.dot(point.to_vec()));
+    Matrix4::from_cols(v * v.x, v * v.y, v * v.z, v * v.w) * weight
+}
+
+#[inline(always)]
+fn quadric_error(q: &Matrix4, p: Point3) -> f64 {
+    let v = p.to_homogeneous();
+    v.dot(q * v)
+}
+
+impl Simplifier {
+    fn new(mesh: &PolygonMesh) -> Self {
+        let faces = mesh.faces().triangulate(mesh.positions());
+        let triangles: Vec<Option<[Vertex; 3]>> = faces
+            .tri_faces()
+            .iter()
+            .filter(|tri| {
+                tri[0].pos != tri[1].pos && tri[1].pos != tri[2].pos && tri[2].pos != tri[0].pos
+            })
+            .map(|tri| Some(*tri))
+            .collect();
+        let positions = mesh.positions().clone();
+        let mut vertex_triangles = vec![Vec::new(); positions.len()];
+        triangles.iter().enumerate().for_each(|(i, tri)| {
+            tri.unwrap()
+                .iter()
+                .for_each(|v| vertex_triangles[v.pos].push(i));
+        });
+        let mut simplifier = Self {
+            quadrics: vec![Matrix4::zero(); positions.len()],
+            versions: vec![0; positions.len()],
+            num_triangles: triangles.len(),
+            positions,
+            uv_coords: mesh.uv_coords().clone(),
+            normals: mesh.normals().clone(),
+            triangles,
+            vertex_triangles,
+        };
+        simplifier.init_quadrics();
+        simplifier
+    }
+
+    fn triangle(&self, i: usize) -> [Vertex; 3] { self.triangles[i].unwrap() }
+
+    /// the normal whose length is twice the area
+    fn normal(&self, i: usize) -> Vector3 {
+        let p = self.triangle(i).map(|v| self.positions[v.pos]);
+        (p[1] - p[0]).cross(p[2] - p[0])
+    }
+
+    fn init_quadrics(&mut self) {
+        (0..self.triangles.len()).for_each(|i| {
+            let normal = self.normal(i);
+            let area = normal.magnitude() / 2.0;
+            if area < TOLERANCE * TOLERANCE {
+                return;
+            }
+            let tri = self.triangle(i);
+            let q = plane_quadric(normal.normalize(), self.positions[tri[0].pos], area);
+            tri.iter().for_each(|v| self.quadrics[v.pos] += q);
+        });
+        // the planes perpendicular to the boundaries and the seams
+        self.edges().into_iter().for_each(|[a, b]| {
+            let tris = self.edge_triangles(a, b);
+            if !self.is_constrained_edge(a, b, &tris) {
+                return;
+            }
+            let (pa, pb) = (self.positions[a], self.positions[b]);
+            tris.iter().for_each(|i| {
+                let normal = (pb - pa).cross(self.normal(*i));
+                if normal.so_small() {
+                    return;
+                }
+                let weight = CONSTRAINT_WEIGHT * pa.distance2(pb);
+                let q = plane_quadric(normal.normalize(), pa, weight);
+                self.quadrics[a] += q;
+                self.quadrics[b] += q;
+            });
+        });
+    }
+
+    fn edges(&self) -> Vec<[usize; 2]> {
+        let mut edges: Vec<[usize; 2]> = self
+            .triangles
+            .iter()
+            .flatten()
+            .flat_map(|tri| {
+                (0..3).map(move |k| {
+                    let (a, b) = (tri[k].pos, tri[(k + 1) % 3].pos);
+                    [usize::min(a, b), usize::max(a, b)]
+                })
+            })
+            .collect();
+        edges.sort();
+        edges.dedup();
+        edges
+    }
+
+    fn edge_triangles(&self, a: usize, b: usize) -> Vec<usize> {
+        self.vertex_triangles[a]
+            .iter()
+            .filter(|i| self.triangle(**i).iter().any(|v| v.pos == b))
+            .copied()
+            .collect()
+    }
+
+    fn neighbors(&self, v: usize) -> Vec<usize> {
+        let mut neighbors: Vec<usize> = self.vertex_triangles[v]
+            .iter()
+            .flat_map(|i| self.triangle(*i))
+            .map(|w| w.pos)
+            .filter(|w| *w != v)
+            .collect();
+        neighbors.sort();
+        neighbors.dedup();
+        neighbors
+    }
+
+    /// the corner of the triangle `i` on the position `v`
+    fn corner(&self, i: usize, v: usize) -> Vertex {
+        self.triangle(i).into_iter().find(|w| w.pos == v).unwrap()
+    }
+
+    /// whether the edge is a boundary, a non-manifold edge or a seam
+    fn is_constrained_edge(&self, a: usize, b: usize, tris: &[usize]) -> bool {
+        match tris {
+            [t0, t1] => {
+                let (a0, a1) = (self.corner(*t0, a), self.corner(*t1, a));
+                let (b0, b1) = (self.corner(*t0, b), self.corner(*t1, b));
+                a0 != a1 || b0 != b1
+            }
+            _ => true,
+        }
+    }
+
+    /// the number of the constrained edges on `v`
+    fn constrained_degree(&self, v: usize) -> usize {
+        self.neighbors(v)
+            .into_iter()
+            .filter(|w| self.is_constrained_edge(v, *w, &self.edge_triangles(v, *w)))
+            .count()
+    }
+
+    fn evaluate(&self, a: usize, b: usize) -> Option<Candidate> {
+        let tris = self.edge_triangles(a, b);
+        if tris.is_empty() || tris.len() > 2 {
+            return None;
+        }
+        let constrained = self.is_constrained_edge(a, b, &tris);
+        let (da, db) = (self.constrained_degree(a), self.constrained_degree(b));
+        // whether the vertex can move along the edge
+        let movable = |d: usize| match constrained {
+            true => d == 2,
+            false => d == 0,
+        };
+        let (ma, mb) = (movable(da), movable(db));
+        let q = self.quadrics[a] + self.quadrics[b];
+        let (pa, pb) = (self.positions[a], self.positions[b]);
+        // the error along the edge: A t^2 + 2 B t + C
+        let u = pa.to_homogeneous();
+        let w = (pb - pa).extend(0.0);
+        let (qa, qb) = (w.dot(q * w), u.dot(q * w));
+        let t = match (ma, mb) {
+            (false, false) => return None,
+            (true, false) => 1.0,
+            (false, true) => 0.0,
+            (true, true) => match qa > TOLERANCE * TOLERANCE {
+                true => f64::clamp(-qb / qa, 0.0, 1.0),
+                false => 0.5,
+            },
+        };
+        let cost = quadric_error(&q, pa + (pb - pa) * t);
+        // `t` is the parameter from `a` to `b`. The vertex at `t == 0` is kept.
+        let (removed, kept, t) = match t == 0.0 {
+            true => (b, a, 1.0),
+            false => (a, b, t),
+        };
+        Some(Candidate {
+            cost,
+            removed,
+            kept,
+            t,
+            versions: [self.versions[removed], self.versions[kept]],
+        })
+    }
+
+    /// Collapses `removed` into `kept`, placed at the parameter `t` from `removed` to `kept`.
+    fn collapse(&mut self, removed: usize, kept: usize, t: f64) -> bool {
+        let tris = self.edge_triangles(removed, kept);
+        // link condition
+        let mut opposite: Vec<usize> = tris
+            .iter()
+            .flat_map(|i| self.triangle(*i))
+            .map(|v| v.pos)
+            .filter(|v| *v != removed && *v != kept)
+            .collect();
+        opposite.sort();
+        let (nr, nk) = (self.neighbors(removed), self.neighbors(kept));
+        let common: Vec<usize> = nr.into_iter().filter(|v| nk.contains(v)).collect();
+        if common != opposite {
+            return false;
+        }
+
+        // the correspondence of the attributes on the collapsed edge
+        let mut uv_map = HashMap::<Option<usize>, Option<usize>>::default();
+        let mut nor_map = HashMap::<Option<usize>, Option<usize>>::default();
+        let consistent = tris.iter().all(|i| {
+            let (r, k) = (self.corner(*i, removed), self.corner(*i, kept));
+            *uv_map.entry(r.uv).or_insert(k.uv) == k.uv
+                && *nor_map.entry(r.nor).or_insert(k.nor) == k.nor
+        });
+        // An attribute of `kept` cannot be interpolated from two attributes of `removed`.
+        let injective = |map: &HashMap<Option<usize>, Option<usize>>| {
+            let mut values: Vec<_> = map.values().collect();
+            values.sort();
+            values.dedup();
+            values.len() == map.len()
+        };
+        if !consistent || (t != 1.0 && !(injective(&uv_map) && injective(&nor_map))) {
+            return false;
+        }
+        let others: Vec<usize> = self.vertex_triangles[removed]
+            .iter()
+            .filter(|i| !tris.contains(i))
+            .copied()
+            .collect();
+        // The attributes across the seams cannot be interpolated.
+        let interpolable = others.iter().all(|i| {
+            let r = self.corner(*i, removed);
+            uv_map.contains_key(&r.uv) && nor_map.contains_key(&r.nor)
+        });
+        if !interpolable {
+            return false;
+        }
+
+        let p = self.positions[removed] + (self.positions[kept] - self.positions[removed]) * t;
+        let moved = |v: usize| match v == removed || v == kept {
+            true => p,
+            false => self.positions[v],
+        };
+        let kept_others = self.vertex_triangles[kept]
+            .iter()
+            .filter(|i| !tris.contains(i));
+        let flipped = others.iter().chain(kept_others).any(|i| {
+            let old = self.normal(*i);
+            let q = self.triangle(*i).map(|v| moved(v.pos));
+            let new = (q[1] - q[0]).cross(q[2] - q[0]);
+            new.so_small() || old.dot(new) <= 0.0
+        });
+        if flipped {
+            return false;
+        }
+
+        self.positions[kept] = p;
+        uv_map.iter().for_each(|(r, k)| {
+            if let (Some(r), Some(k)) = (r, k) {
+                let (uv0, uv1) = (self.uv_coords[*r], self.uv_coords[*k]);
+                self.uv_coords[*k] = uv0 + (uv1 - uv0) * t;
+            }
+        });
+        nor_map.iter().for_each(|(r, k)| {
+            if let (Some(r), Some(k)) = (r, k) {
+                let (n0, n1) = (self.normals[*r], self.normals[*k]);
+                let n = n0 + (n1 - n0) * t;
+                if !n.so_small() {
+                    self.normals[*k] = n.normalize();
+                }
+            }
+        });
+        tris.iter().for_each(|i| {
+            self.triangle(*i).iter().for_each(|v| {
+                self.vertex_triangles[v.pos].retain(|j| j != i);
+            });
+            self.triangles[*i] = None;
+            self.num_triangles -= 1;
+        });
+        others.iter().for_each(|i| {
+            let tri = self.triangles[*i].as_mut().unwrap();
+            tri.iter_mut().filter(|v| v.pos == removed).for_each(|v| {
+                *v = Vertex {
+                    pos: kept,
+                    uv: uv_map[&v.uv],
+                    nor: nor_map[&v.nor],
+                }
+            });
+            self.vertex_triangles[kept].push(*i);
+        });
+        self.vertex_triangles[removed].clear();
+        let q = self.quadrics[removed];
+        self.quadrics[kept] += q;
+        self.versions[removed] += 1;
+        self.versions[kept] += 1;
+        true
+    }
+
+    fn run(&mut self, target_faces: usize) {
+        let mut heap: BinaryHeap<Candidate> = self
+            .edges()
+            .into_iter()
+            .filter_map(|[a, b]| self.evaluate(a, b))
+            .collect();
+        while self.num_triangles > target_faces {
+            let candidate = match heap.pop() {
+                Some(candidate) => candidate,
+                None => break,
+            };
+            let (removed, kept) = (candidate.removed, candidate.kept);
+            if candidate.versions != [self.versions[removed], self.versions[kept]] {
+                continue;
+            }
+            if self.collapse(removed, kept, candidate.t) {
+                // The constraints of the neighbors may change by the collapse.
+                self.neighbors(kept).into_iter().for_each(|v| {
+                    self.versions[v] += 1;
+                });
+                let mut edges: Vec<[usize; 2]> = self
+                    .neighbors(kept)
+                    .into_iter()
+                    .flat_map(|v| self.neighbors(v).into_iter().map(move |w| [v, w]))
+                    .map(|[v, w]| [usize::min(v, w), usize::max(v, w)])
+                    .collect();
+                edges.sort();
+                edges.dedup();
+                heap.extend(edges.into_iter().filter_map(|[a, b]| self.evaluate(a, b)));
+            }
+        }
+    }
+
+    fn into_mesh(self) -> PolygonMesh {
+        let faces: Faces = self.triangles.into_iter().flatten().collect();
+        PolygonMesh::new(
+            StandardAttributes {
+                positions: self.positions,
+                uv_coords: self.uv_coords,
+                normals: self.normals,
+            },
+            faces,
+        )
+    }
+}
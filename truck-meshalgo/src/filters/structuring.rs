@@ -3,6 +3,9 @@ use super::*;
 /// triangulation, quadrangulation, give a structure
 pub trait StructuringFilter {
     /// triangulate all n-gons
+    ///
+    /// The polygons are triangulated by ear clipping, so that concave polygons are also
+    /// triangulated correctly. See [`Faces::triangulate`].
     /// # Examples
     /// ```
     /// use truck_polymesh::*;
@@ -88,8 +91,8 @@ pub trait StructuringFilter {
 
 impl StructuringFilter for PolygonMesh {
     fn triangulate(&mut self) -> &mut Self {
-        let tri_faces = self.faces().triangle_iter().collect::<Vec<_>>();
-        *self.debug_editor().faces = Faces::from_tri_and_quad_faces(tri_faces, Vec::new());
+        let faces = self.faces().triangulate(self.positions());
+        *self.debug_editor().faces = faces;
         self
     }
     fn quadrangulate(&mut self, plane_tol: f64, score_tol: f64) -> &mut Self {
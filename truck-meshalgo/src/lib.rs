@@ -25,18 +25,41 @@ use truck_polymesh::{polygon_mesh::PolygonMeshEditor, StandardVertex as Vertex,
 ///
 /// - determines topological properties: connectivity, boundary extraction, or shell conditions (closed or oriented)
 /// - detects collisions between two meshes and extracts interference lines
-/// - investigates positional relations between mesh and point clouds.
+/// - investigates positional relations between mesh and point clouds
+/// - computes geodesic distances and paths on meshes
+/// - computes mass properties: volumes, areas, centroids and inertia tensors
+/// - measures Hausdorff and RMS distances between meshes
+/// - detects planes, spheres, cylinders and cones.
 pub mod analyzers;
+/// Converts polygon meshes into B-rep shells.
+#[cfg(feature = "brep")]
+pub mod brep_conversion;
 mod common;
+/// Extracts the networks of the feature curves from meshes.
+#[cfg(feature = "brep")]
+pub mod curve_network;
 /// Edits meshes. Add normals, optimizing data, and so on.
 pub mod filters;
+/// Reconstructs meshes from point clouds.
+pub mod reconstruction;
+/// Approximates the volumes swept by moving solids.
+pub mod swept_volume;
 /// Tessellates shapes.
 pub mod tessellation;
+/// Bridges closed meshes to tetrahedral meshers.
+pub mod tetrahedralization;
 
 /// This module contains all traits and re-exports `truck_polymesh`.
 pub mod prelude {
     pub use crate::analyzers::*;
+    #[cfg(feature = "brep")]
+    pub use crate::brep_conversion::*;
+    #[cfg(feature = "brep")]
+    pub use crate::curve_network::*;
     pub use crate::filters::*;
+    pub use crate::reconstruction::*;
     pub use crate::rexport_polymesh::*;
+    pub use crate::swept_volume::*;
     pub use crate::tessellation::*;
+    pub use crate::tetrahedralization::*;
 }
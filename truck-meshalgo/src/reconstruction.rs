@@ -0,0 +1,415 @@
+use crate::*;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use std::collections::{BinaryHeap, VecDeque};
+use std::f64::consts::PI;
+
+/// Reconstructs polygon meshes from point clouds.
+pub trait PointCloudReconstruction {
+    /// Estimates the normals of the points by the principal component analysis
+    /// of the `neighbors` nearest points.
+    /// # Details
+    /// The normal of each point is the eigenvector of the smallest eigenvalue of the covariance
+    /// matrix of the neighbors. The orientations are propagated along the neighbors, first to
+    /// the most parallel normals, from the point with the maximum x-coordinate, whose normal
+    /// is oriented to the positive x-direction. Hence, the normals of a closed surface are
+    /// outward as far as the points are dense enough.
+    ///
+    /// The zero vectors are returned if the number of points is less than three.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the points on the plane z = 0
+    /// let points: Vec<Point3> = (0..100)
+    ///     .map(|i| Point3::new((i % 10) as f64, (i / 10) as f64, 0.0))
+    ///     .collect();
+    /// let normals = points.estimate_normals(8);
+    /// assert!(normals.iter().all(|n| n.cross(Vector3::unit_z()).so_small()));
+    /// ```
+    fn estimate_normals(&self, neighbors: usize) -> Vec<Vector3>;
+    /// Reconstructs the triangle mesh by the ball-pivoting algorithm with the ball of `radius`.
+    /// # Details
+    /// A seed triangle, whose circumscribed ball of `radius` on the side of the normals contains
+    /// no other points, is found first. Then, the ball is pivoted around the edges on the front
+    /// until it touches another point, and the triangle is added if the topology of the mesh
+    /// remains manifold. The edges around which the ball touches nothing are the boundaries.
+    /// These are repeated while the seed triangles are found.
+    ///
+    /// The points should be sampled with intervals smaller than `radius`, and the normals,
+    /// e.g. by [`PointCloudReconstruction::estimate_normals`], need not be normalized.
+    ///
+    /// # Remarks
+    /// The positions and the normals of the mesh are the points and the normals, respectively,
+    /// with the same indices, including the points not used by any triangles.
+    ///
+    /// # Panics
+    /// Panics if the lengths of `self` and `normals` are different,
+    /// or `radius` is not more than `TOLERANCE`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the points on the rectangle [0, 9] x [0, 9]
+    /// let points: Vec<Point3> = (0..100)
+    ///     .map(|i| Point3::new((i % 10) as f64, (i / 10) as f64, 0.0))
+    ///     .collect();
+    /// let normals = vec![Vector3::unit_z(); 100];
+    /// let mesh = points.ball_pivoting(&normals, 0.8);
+    /// assert_eq!(mesh.faces().len(), 162);
+    /// assert_eq!(mesh.extract_boundaries()[0].len(), 36);
+    /// ```
+    fn ball_pivoting(&self, normals: &[Vector3], radius: f64) -> PolygonMesh;
+}
+
+impl PointCloudReconstruction for [Point3] {
+    fn estimate_normals(&self, neighbors: usize) -> Vec<Vector3> {
+        if self.len() < 3 {
+            return vec![Vector3::zero(); self.len()];
+        }
+        let neighbors = usize::clamp(neighbors, 2, self.len() - 1);
+        let bdd_box: BoundingBox<Point3> = self.iter().collect();
+        let cell = f64::max(bdd_box.diameter() / f64::sqrt(self.len() as f64), TOLERANCE);
+        let grid = Grid::new(self, cell);
+        let nearests: Vec<Vec<usize>> = (0..self.len())
+            .map(|i| grid.nearest_points(self, i, neighbors))
+            .collect();
+        let mut normals: Vec<Vector3> = nearests
+            .iter()
+            .enumerate()
+            .map(|(i, nearest)| {
+                let points = nearest.iter().chain(Some(&i)).map(|j| self[*j]);
+                let center = Point3::centroid(&points.clone().collect::<Vec<_>>());
+                let mut matrix = Matrix3::zero();
+                points.for_each(|p| {
+                    let v = p - center;
+                    matrix += Matrix3::from_cols(v * v.x, v * v.y, v * v.z);
+                });
+                smallest_eigenvector(matrix)
+            })
+            .collect();
+        orient_normals(self, &nearests, &mut normals);
+        normals
+    }
+
+    fn ball_pivoting(&self, normals: &[Vector3], radius: f64) -> PolygonMesh {
+        nonpositive_tolerance!(radius);
+        assert_eq!(
+            self.len(),
+            normals.len(),
+            "the numbers of the points and the normals are different."
+        );
+        let mut pivoter = BallPivoter::new(self, normals, radius);
+        (0..self.len()).for_each(|i| {
+            if !pivoter.used[i] && pivoter.find_seed(i) {
+                pivoter.expand_front();
+            }
+        });
+        let tri_faces = pivoter
+            .triangles
+            .into_iter()
+            .map(|tri| {
+                tri.map(|i| Vertex {
+                    pos: i,
+                    uv: None,
+                    nor: Some(i),
+                })
+            })
+            .collect();
+        PolygonMesh::new(
+            StandardAttributes {
+                positions: self.to_vec(),
+                uv_coords: Vec::new(),
+                normals: normals.to_vec(),
+            },
+            Faces::from_tri_and_quad_faces(tri_faces, Vec::new()),
+        )
+    }
+}
+
+/// uniform grid of point indices
+#[derive(Clone, Debug)]
+struct Grid {
+    cell: f64,
+    map: HashMap<[i64; 3], Vec<usize>>,
+}
+
+impl Grid {
+    fn new(points: &[Point3], cell: f64) -> Self {
+        let mut map = HashMap::<[i64; 3], Vec<usize>>::default();
+        points.iter().enumerate().for_each(|(i, p)| {
+            map.entry(Self::key(*p, cell)).or_default().push(i);
+        });
+        Grid { cell, map }
+    }
+
+    #[inline(always)]
+    fn key(p: Point3, cell: f64) -> [i64; 3] { [0, 1, 2].map(|i| f64::floor(p[i] / cell) as i64) }
+
+    /// Calls `f` for all indices of points whose distance from `center` is less than `radius`.
+    fn for_each_within(
+        &self,
+        points: &[Point3],
+        center: Point3,
+        radius: f64,
+        mut f: impl FnMut(usize),
+    ) {
+        let min = Self::key(center - Vector3::new(radius, radius, radius), self.cell);
+        let max = Self::key(center + Vector3::new(radius, radius, radius), self.cell);
+        for x in min[0]..=max[0] {
+            for y in min[1]..=max[1] {
+                for z in min[2]..=max[2] {
+                    if let Some(indices) = self.map.get(&[x, y, z]) {
+                        indices
+                            .iter()
+                            .filter(|i| points[**i].distance2(center) < radius * radius)
+                            .for_each(|i| f(*i));
+                    }
+                }
+            }
+        }
+    }
+
+    /// the `num` nearest points, except the point itself
+    fn nearest_points(&self, points: &[Point3], idx: usize, num: usize) -> Vec<usize> {
+        let center = points[idx];
+        let mut radius = self.cell;
+        loop {
+            let mut res = Vec::new();
+            self.for_each_within(points, center, radius, |i| {
+                if i != idx {
+                    res.push(i)
+                }
+            });
+            if res.len() >= num || res.len() + 1 == points.len() {
+                res.sort_by(|i, j| {
+                    let (d0, d1) = (points[*i].distance2(center), points[*j].distance2(center));
+                    d0.partial_cmp(&d1).unwrap()
+                });
+                res.truncate(num);
+                return res;
+            }
+            radius *= 2.0;
+        }
+    }
+}
+
+/// Orients the normals along the neighbors, first to the most parallel normals.
+fn orient_normals(points: &[Point3], nearests: &[Vec<usize>], normals: &mut [Vector3]) {
+    let mut visited = vec![false; points.len()];
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|i, j| points[*j].x.partial_cmp(&points[*i].x).unwrap());
+    for start in order {
+        if visited[start] {
+            continue;
+        }
+        if normals[start].x < 0.0 {
+            normals[start] = -normals[start];
+        }
+        visited[start] = true;
+        // The bits of non-negative floats are in the same order as the floats.
+        let mut heap = BinaryHeap::new();
+        let push_neighbors =
+            |heap: &mut BinaryHeap<(u64, usize, usize)>, normals: &[Vector3], i: usize| {
+                nearests[i].iter().for_each(|j| {
+                    let weight = f64::abs(normals[i].dot(normals[*j]));
+                    heap.push((weight.to_bits(), i, *j));
+                });
+            };
+        push_neighbors(&mut heap, normals, start);
+        while let Some((_, i, j)) = heap.pop() {
+            if visited[j] {
+                continue;
+            }
+            visited[j] = true;
+            if normals[i].dot(normals[j]) < 0.0 {
+                normals[j] = -normals[j];
+            }
+            push_neighbors(&mut heap, normals, j);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BallPivoter<'a> {
+    points: &'a [Point3],
+    normals: &'a [Vector3],
+    radius: f64,
+    grid: Grid,
+    used: Vec<bool>,
+    /// the directed edges of the triangles
+    edges: HashSet<[usize; 2]>,
+    /// the triangles as sorted indices
+    triangle_set: HashSet<[usize; 3]>,
+    /// the numbers of the boundary edges around the vertices
+    boundary_count: Vec<usize>,
+    /// the edges on the front, with the opposite vertices and the centers of the balls
+    front: HashMap<[usize; 2], (usize, Point3)>,
+    queue: VecDeque<[usize; 2]>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl<'a> BallPivoter<'a> {
+    fn new(points: &'a [Point3], normals: &'a [Vector3], radius: f64) -> Self {
+        BallPivoter {
+            points,
+            normals,
+            radius,
+            grid: Grid::new(points, 2.0 * radius),
+            used: vec![false; points.len()],
+            edges: Default::default(),
+            triangle_set: Default::default(),
+            boundary_count: vec![0; points.len()],
+            front: Default::default(),
+            queue: Default::default(),
+            triangles: Vec::new(),
+        }
+    }
+
+    /// the center of the ball on the side of the normals touching the three points
+    fn ball_center(&self, tri: [usize; 3]) -> Option<Point3> {
+        let [a, b, c] = tri.map(|i| self.points[i]);
+        let (ab, ac) = (b - a, c - a);
+        let normal = ab.cross(ac);
+        let norm2 = normal.magnitude2();
+        if norm2 < TOLERANCE2 * ab.magnitude2() * ac.magnitude2() {
+            return None;
+        }
+        let direction: Vector3 = tri.iter().map(|i| self.normals[*i]).sum();
+        if normal.dot(direction) <= 0.0 {
+            return None;
+        }
+        let circumcenter = a
+            + (normal.cross(ab) * ac.magnitude2() + ac.cross(normal) * ab.magnitude2())
+                / (2.0 * norm2);
+        let height2 = self.radius * self.radius - circumcenter.distance2(a);
+        match height2 >= 0.0 {
+            true => Some(circumcenter + normal / norm2.sqrt() * height2.sqrt()),
+            false => None,
+        }
+    }
+
+    fn is_empty_ball(&self, center: Point3, tri: [usize; 3]) -> bool {
+        let mut empty = true;
+        let radius = self.radius * (1.0 - 1.0e-9) - TOLERANCE;
+        self.grid
+            .for_each_within(self.points, center, radius, |i| empty &= tri.contains(&i));
+        empty
+    }
+
+    fn find_seed(&mut self, idx: usize) -> bool {
+        let mut neighbors = Vec::new();
+        let (points, used) = (self.points, &self.used);
+        self.grid
+            .for_each_within(points, points[idx], 2.0 * self.radius, |i| {
+                if i != idx && !used[i] {
+                    neighbors.push(i)
+                }
+            });
+        neighbors.sort_by(|i, j| {
+            let (d0, d1) = (
+                points[*i].distance2(points[idx]),
+                points[*j].distance2(points[idx]),
+            );
+            d0.partial_cmp(&d1).unwrap()
+        });
+        for (k, b) in neighbors.iter().enumerate() {
+            for c in &neighbors[k + 1..] {
+                for tri in [[idx, *b, *c], [idx, *c, *b]] {
+                    if let Some(center) = self.ball_center(tri) {
+                        if self.is_empty_ball(center, tri) {
+                            self.add_triangle(tri, center);
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn add_triangle(&mut self, tri: [usize; 3], center: Point3) {
+        let mut sorted = tri;
+        sorted.sort_unstable();
+        self.triangle_set.insert(sorted);
+        self.triangles.push(tri);
+        (0..3).for_each(|k| {
+            let (x, y, opposite) = (tri[k], tri[(k + 1) % 3], tri[(k + 2) % 3]);
+            self.used[x] = true;
+            self.edges.insert([x, y]);
+            if self.edges.contains(&[y, x]) {
+                self.boundary_count[x] -= 1;
+                self.boundary_count[y] -= 1;
+                self.front.remove(&[y, x]);
+            } else {
+                self.boundary_count[x] += 1;
+                self.boundary_count[y] += 1;
+                self.front.insert([x, y], (opposite, center));
+                self.queue.push_back([x, y]);
+            }
+        });
+    }
+
+    fn expand_front(&mut self) {
+        while let Some(edge) = self.queue.pop_front() {
+            let (opposite, center) = match self.front.get(&edge) {
+                Some(x) => *x,
+                None => continue,
+            };
+            match self.pivot(edge, opposite, center) {
+                Some((m, new_center)) if self.is_acceptable(edge, m) => {
+                    self.add_triangle([edge[1], edge[0], m], new_center)
+                }
+                // the boundary edge
+                _ => {
+                    self.front.remove(&edge);
+                }
+            }
+        }
+    }
+
+    /// Pivots the ball around `edge`, and returns the first touched point and the new center.
+    fn pivot(&self, edge: [usize; 2], opposite: usize, center: Point3) -> Option<(usize, Point3)> {
+        let [i, j] = edge;
+        let (p, q) = (self.points[i], self.points[j]);
+        let axis = (q - p).normalize();
+        let mid = p.midpoint(q);
+        let project = |v: Vector3| v - axis * axis.dot(v);
+        let start = project(center - mid);
+        let mut res: Option<(f64, usize, Point3)> = None;
+        self.grid
+            .for_each_within(self.points, mid, 2.0 * self.radius, |m| {
+                if m == i || m == j || m == opposite {
+                    return;
+                }
+                let tri = [j, i, m];
+                let mut sorted = tri;
+                sorted.sort_unstable();
+                if self.triangle_set.contains(&sorted) {
+                    return;
+                }
+                let new_center = match self.ball_center(tri) {
+                    Some(center) => center,
+                    None => return,
+                };
+                let end = project(new_center - mid);
+                let mut angle = f64::atan2(axis.dot(start.cross(end)), start.dot(end));
+                if angle < -1.0e-6 {
+                    angle += 2.0 * PI;
+                }
+                let nearer = match res {
+                    Some((min, ..)) => angle < min,
+                    None => true,
+                };
+                if nearer && self.is_empty_ball(new_center, tri) {
+                    res = Some((angle, m, new_center));
+                }
+            });
+        res.map(|(_, m, center)| (m, center))
+    }
+
+    /// Whether the triangle `[edge[1], edge[0], m]` keeps the mesh manifold.
+    fn is_acceptable(&self, edge: [usize; 2], m: usize) -> bool {
+        let [i, j] = edge;
+        (!self.used[m] || self.boundary_count[m] > 0)
+            && !self.edges.contains(&[i, m])
+            && !self.edges.contains(&[m, j])
+    }
+}
@@ -0,0 +1,285 @@
+use crate::filters::OptimizingFilter;
+use crate::tessellation::{MeshableShape, MeshableSurface, MeshedShape, PolylineableCurve};
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use truck_base::polygon;
+use truck_topology::Solid;
+
+/// Approximates the volumes swept by moving solids.
+///
+/// # Details
+/// The space is divided into the voxels of the given size. Along the lines of the grid points
+/// parallel to the z-axis, the crossings with the boundary are computed for each pose, and the
+/// signed distances along the lines, truncated by the voxel size, are united by the minimum over
+/// the poses. The boundary is extracted from the distance field by the marching tetrahedra.
+///
+/// The swept volume is the union of the sampled poses, so the poses should be sampled so densely
+/// that the displacements between the consecutive poses are smaller than the voxel size. The
+/// poses are assumed to be rigid motions.
+pub trait SweptVolume {
+    /// Returns the closed triangle mesh bounding the union of `self` transformed by `poses`.
+    ///
+    /// The empty mesh is returned if `poses` is empty.
+    /// # Panics
+    /// Panics if `voxel_size` is not more than `TOLERANCE`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    /// use truck_topology::shell::ShellCondition;
+    ///
+    /// // the unit cube moving along the x-axis by 2
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    /// let poses: Vec<Matrix4> = (0..=20)
+    ///     .map(|i| Matrix4::from_translation(Vector3::new(i as f64 * 0.1, 0.0, 0.0)))
+    ///     .collect();
+    ///
+    /// let envelope = cube.swept_volume(&poses, 0.1);
+    /// assert_eq!(envelope.shell_condition(), ShellCondition::Closed);
+    /// let volume = envelope.mass_properties().unwrap().volume;
+    /// assert!(f64::abs(volume - 3.0) < 0.1);
+    /// let bdd_box = envelope.bounding_box();
+    /// assert!(bdd_box.min().distance(Point3::new(0.0, 0.0, 0.0)) < 0.1);
+    /// assert!(bdd_box.max().distance(Point3::new(3.0, 1.0, 1.0)) < 0.1);
+    /// ```
+    fn swept_volume(&self, poses: &[Matrix4], voxel_size: f64) -> PolygonMesh;
+}
+
+impl SweptVolume for PolygonMesh {
+    fn swept_volume(&self, poses: &[Matrix4], voxel_size: f64) -> PolygonMesh {
+        assert!(
+            voxel_size > TOLERANCE,
+            "the voxel size must be more than TOLERANCE."
+        );
+        let bdd_box = self.bounding_box();
+        if poses.is_empty() || bdd_box.is_empty() {
+            return PolygonMesh::default();
+        }
+        let (min, max) = (*bdd_box.min(), *bdd_box.max());
+        let corners = (0..8).map(|i| {
+            let x = if i & 1 == 0 { min.x } else { max.x };
+            let y = if i & 2 == 0 { min.y } else { max.y };
+            let z = if i & 4 == 0 { min.z } else { max.z };
+            Point3::new(x, y, z)
+        });
+        let corners: Vec<Point3> = corners.collect();
+        let range: BoundingBox<Point3> = poses
+            .iter()
+            .flat_map(|pose| corners.iter().map(move |p| pose.transform_point(*p)))
+            .collect();
+        let mut grid = Grid::new(&range, voxel_size);
+        poses.iter().for_each(|pose| grid.unite(self, *pose));
+        grid.marching_tetrahedra()
+    }
+}
+
+/// The solid is tessellated by the tolerance of the quarter of `voxel_size`.
+impl<C: PolylineableCurve, S: MeshableSurface> SweptVolume for Solid<Point3, C, S> {
+    fn swept_volume(&self, poses: &[Matrix4], voxel_size: f64) -> PolygonMesh {
+        assert!(
+            voxel_size > TOLERANCE,
+            "the voxel size must be more than TOLERANCE."
+        );
+        let tol = f64::max(voxel_size / 4.0, 2.0 * TOLERANCE);
+        let mut mesh = self.triangulation(tol).to_polygon();
+        mesh.put_together_same_attrs();
+        mesh.swept_volume(poses, voxel_size)
+    }
+}
+
+/// the signed distances along the z-axis on the grid points
+#[derive(Clone, Debug)]
+struct Grid {
+    origin: Point3,
+    size: f64,
+    counts: [usize; 3],
+    values: Vec<f64>,
+}
+
+/// the number of the voxels padded around the range
+const PADDING: usize = 2;
+
+impl Grid {
+    fn new(range: &BoundingBox<Point3>, size: f64) -> Self {
+        // The origin is shifted by the distinct irrational ratios so that the lines of the grid
+        // points hardly pass through the edges of the triangles.
+        let shift = Vector3::new(
+            PADDING as f64 + f64::sqrt(2.0) - 1.0,
+            PADDING as f64 + f64::sqrt(3.0) - 1.0,
+            PADDING as f64 + f64::sqrt(5.0) - 2.0,
+        ) * size;
+        let origin = *range.min() - shift;
+        let diagonal = range.diagonal();
+        let count = |x: f64| (x / size).ceil() as usize + 2 * PADDING + 2;
+        let counts = [count(diagonal.x), count(diagonal.y), count(diagonal.z)];
+        Self {
+            origin,
+            size,
+            counts,
+            values: vec![size; counts[0] * counts[1] * counts[2]],
+        }
+    }
+
+    #[inline(always)]
+    fn index(&self, [i, j, k]: [usize; 3]) -> usize {
+        (k * self.counts[1] + j) * self.counts[0] + i
+    }
+
+    #[inline(always)]
+    fn point(&self, [i, j, k]: [usize; 3]) -> Point3 {
+        self.origin + Vector3::new(i as f64, j as f64, k as f64) * self.size
+    }
+
+    /// Unites the mesh transformed by `pose` to the field.
+    fn unite(&mut self, mesh: &PolygonMesh, pose: Matrix4) {
+        let [nx, ny, nz] = self.counts;
+        let positions: Vec<Point3> = mesh
+            .positions()
+            .iter()
+            .map(|p| pose.transform_point(*p))
+            .collect();
+        let mut columns = vec![Vec::<(f64, f64)>::new(); nx * ny];
+        mesh.faces().triangle_iter().for_each(|tri| {
+            let [a, b, c] = tri.map(|v| positions[v.pos]);
+            let [a2, b2, c2] = [a, b, c].map(|p| Point2::new(p.x, p.y));
+            let det = polygon::orient(a2, b2, c2);
+            if det == 0.0 {
+                return;
+            }
+            // Each line passing through the edges or the vertices of the triangles crosses just
+            // one of the adjacent triangles by the top-left rule.
+            let covers = |p: Point2, q: Point2, x: Point2| {
+                let (e, d) = (polygon::orient(p, q, x) * det, (q - p) * det.signum());
+                e > 0.0 || (e == 0.0 && (d.y < 0.0 || (d.y == 0.0 && d.x < 0.0)))
+            };
+            let grid_range = |x0: f64, x1: f64, x2: f64, o: f64, n: usize| {
+                let min = (f64::min(x0, f64::min(x1, x2)) - o) / self.size;
+                let max = (f64::max(x0, f64::max(x1, x2)) - o) / self.size;
+                let start = f64::clamp(min.ceil(), 0.0, n as f64) as usize;
+                let end = f64::clamp(max.floor() + 1.0, 0.0, n as f64) as usize;
+                start..end
+            };
+            let xs = grid_range(a.x, b.x, c.x, self.origin.x, nx);
+            let ys = grid_range(a.y, b.y, c.y, self.origin.y, ny);
+            ys.for_each(|j| {
+                xs.clone().for_each(|i| {
+                    let p = self.point([i, j, 0]);
+                    let x = Point2::new(p.x, p.y);
+                    if covers(b2, c2, x) && covers(c2, a2, x) && covers(a2, b2, x) {
+                        let w0 = polygon::orient(b2, c2, x) / det;
+                        let w1 = polygon::orient(c2, a2, x) / det;
+                        let z = w0 * a.z + w1 * b.z + (1.0 - w0 - w1) * c.z;
+                        // entering the solid if the normal is downward
+                        columns[j * nx + i].push((z, -det.signum()));
+                    }
+                })
+            })
+        });
+        columns.iter_mut().enumerate().for_each(|(idx, column)| {
+            if column.is_empty() {
+                return;
+            }
+            column.sort_by(|x, y| x.0.total_cmp(&y.0));
+            let (i, j) = (idx % nx, idx / nx);
+            let (mut winding, mut next) = (0.0, 0);
+            (0..nz).for_each(|k| {
+                let z = self.point([i, j, k]).z;
+                while next < column.len() && column[next].0 < z {
+                    winding += column[next].1;
+                    next += 1;
+                }
+                let below = next.checked_sub(1).map(|n| z - column[n].0);
+                let above = column.get(next).map(|c| c.0 - z);
+                let dist = f64::min(
+                    below.unwrap_or(f64::INFINITY),
+                    above.unwrap_or(f64::INFINITY),
+                );
+                let dist = f64::min(dist, self.size);
+                let value = match winding > 0.5 {
+                    true => -dist,
+                    false => dist,
+                };
+                let index = self.index([i, j, k]);
+                self.values[index] = f64::min(self.values[index], value);
+            })
+        });
+    }
+
+    /// Extracts the boundary of the domain where the values are negative.
+    fn marching_tetrahedra(&self) -> PolygonMesh {
+        // the paths from the corner 0 to the corner 7 of the voxel, by the bits of xyz.
+        const TETRAHEDRA: [[usize; 4]; 6] = [
+            [0, 1, 3, 7],
+            [0, 1, 5, 7],
+            [0, 2, 3, 7],
+            [0, 2, 6, 7],
+            [0, 4, 5, 7],
+            [0, 4, 6, 7],
+        ];
+        let [nx, ny, nz] = self.counts;
+        let mut positions = Vec::new();
+        let mut vertex_map = HashMap::<(usize, usize), usize>::default();
+        let mut triangles = Vec::<[usize; 3]>::new();
+        // The zeros are regarded as the outside so that the crossings are on the edges.
+        let value = |idx: usize| match self.values[idx] {
+            x if x < 0.0 => x,
+            x => f64::max(x, f64::MIN_POSITIVE),
+        };
+        for k in 0..nz - 1 {
+            for j in 0..ny - 1 {
+                for i in 0..nx - 1 {
+                    let corner = |bits: usize| {
+                        let pos = [i + (bits & 1), j + ((bits >> 1) & 1), k + (bits >> 2)];
+                        (self.index(pos), self.point(pos))
+                    };
+                    for tet in TETRAHEDRA {
+                        let vertices = tet.map(corner);
+                        let (inside, outside): (Vec<_>, Vec<_>) =
+                            vertices.into_iter().partition(|(idx, _)| value(*idx) < 0.0);
+                        if inside.is_empty() || outside.is_empty() {
+                            continue;
+                        }
+                        let mut crossing =
+                            |(i0, p0): (usize, Point3), (i1, p1): (usize, Point3)| {
+                                let key = (usize::min(i0, i1), usize::max(i0, i1));
+                                *vertex_map.entry(key).or_insert_with(|| {
+                                    let (v0, v1) = (value(i0), value(i1));
+                                    positions.push(p0 + (p1 - p0) * (v0 / (v0 - v1)));
+                                    positions.len() - 1
+                                })
+                            };
+                        let polygon: Vec<usize> = match (inside.len(), outside.len()) {
+                            (1, _) => outside.iter().map(|o| crossing(inside[0], *o)).collect(),
+                            (_, 1) => inside.iter().map(|i| crossing(*i, outside[0])).collect(),
+                            _ => vec![
+                                crossing(inside[0], outside[0]),
+                                crossing(inside[0], outside[1]),
+                                crossing(inside[1], outside[1]),
+                                crossing(inside[1], outside[0]),
+                            ],
+                        };
+                        let direction = outside[0].1 - inside[0].1;
+                        (2..polygon.len()).for_each(|n| {
+                            let tri = [polygon[0], polygon[n - 1], polygon[n]];
+                            let [p0, p1, p2] = tri.map(|idx| positions[idx]);
+                            match (p1 - p0).cross(p2 - p0).dot(direction) < 0.0 {
+                                true => triangles.push([tri[0], tri[2], tri[1]]),
+                                false => triangles.push(tri),
+                            }
+                        });
+                    }
+                }
+            }
+        }
+        PolygonMesh::new(
+            StandardAttributes {
+                positions,
+                ..Default::default()
+            },
+            Faces::from_iter(&triangles),
+        )
+    }
+}
@@ -0,0 +1,425 @@
+use super::triangulation::{trimming_tessellation, Polyline};
+use super::*;
+use rustc_hash::FxHashMap as HashMap;
+
+type MeshedShell = Shell<Point3, PolylineCurve, Option<PolygonMesh>>;
+type MeshedSolid = Solid<Point3, PolylineCurve, Option<PolygonMesh>>;
+
+/// The numbers of the elements reused from and tessellated into [`TessellationCache`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TessellationCacheStats {
+    /// the number of the edges whose polylines are reused
+    pub edge_hits: usize,
+    /// the number of the edges converted into polylines
+    pub edge_misses: usize,
+    /// the number of the faces whose meshes are reused
+    pub face_hits: usize,
+    /// the number of the faces tessellated
+    pub face_misses: usize,
+}
+
+/// Cache of the tessellations of edges and faces, keyed by their IDs.
+///
+/// Tessellating shapes through the cache, only the edges and faces which are new,
+/// invalidated, or tessellated with the other tolerance are tessellated, and the others
+/// are reused. The meshes of faces are also re-tessellated if their boundaries are changed
+/// or re-tessellated.
+///
+/// The new edges and faces whose geometries are identical to the cached ones, e.g. the copies
+/// of the shapes, reuse the cached tessellations. The geometries are identified by the keys of
+/// [`Quantize`] with `TOLERANCE`, and the faces also by the curves of their boundaries.
+///
+/// # Dirty tracking
+/// The IDs of edges and faces are not changed by [`Edge::set_curve`] and [`Face::set_surface`],
+/// but their generations are renewed, cf. [`Edge::generation`] and [`Face::generation`]. The
+/// cache records the generations and the points of the end vertices of edges, so the edges and
+/// faces edited in place and the edges whose vertices are moved by [`Vertex::set_point`] are
+/// dirty, i.e. re-tessellated, as well as the faces bounded by the dirty edges, and the others
+/// are reused.
+/// [`TessellationCache::is_edge_dirty`] and [`TessellationCache::is_face_dirty`] report them
+/// before tessellating.
+///
+/// The entries can also be removed explicitly by [`TessellationCache::invalidate_edge`] or
+/// [`TessellationCache::invalidate_face`]. Since the generations are unique, the entries of
+/// dropped shapes are never reused by the new shapes at the same addresses, but they remain
+/// until [`TessellationCache::clear`] or the invalidations.
+///
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+///
+/// // modeling a unit cube
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let mut cache = TessellationCache::new();
+/// let mesh = cache.triangulate_solid(&cube, 0.01).to_polygon();
+/// assert_eq!(cache.stats().face_misses, 6);
+///
+/// // re-tessellates only the face invalidated, e.g. after editing its surface in place.
+/// let shell = &cube.boundaries()[0];
+/// let top = shell.face_iter().last().unwrap();
+/// cache.invalidate_face(top.id());
+/// cache.reset_stats();
+/// let mesh1 = cache.triangulate_solid(&cube, 0.01).to_polygon();
+/// assert_eq!(cache.stats().face_hits, 5);
+/// assert_eq!(cache.stats().face_misses, 1);
+/// assert_eq!(mesh1.faces().len(), mesh.faces().len());
+///
+/// // invalidating an edge invalidates the two faces adjacent to it.
+/// let edge = top.boundaries()[0][0].clone();
+/// cache.invalidate_edge(edge.id());
+/// cache.reset_stats();
+/// cache.triangulate_solid(&cube, 0.01);
+/// assert_eq!(cache.stats().edge_misses, 1);
+/// assert_eq!(cache.stats().face_misses, 2);
+///
+/// // editing the curve of an edge in place makes it and the adjacent faces dirty.
+/// let edge = top.boundaries()[0][1].clone();
+/// edge.set_curve(edge.get_curve());
+/// assert!(cache.is_edge_dirty(&edge));
+/// assert_eq!(shell.face_iter().filter(|face| cache.is_face_dirty(face)).count(), 2);
+/// cache.reset_stats();
+/// cache.triangulate_solid(&cube, 0.01);
+/// assert_eq!(cache.stats().edge_misses, 1);
+/// assert_eq!(cache.stats().face_misses, 2);
+/// assert!(shell.face_iter().all(|face| !cache.is_face_dirty(face)));
+///
+/// // all elements are re-tessellated with the other tolerance.
+/// cache.reset_stats();
+/// cache.triangulate_solid(&cube, 0.02);
+/// assert_eq!(cache.stats().face_misses, 6);
+///
+/// // the copy of the cube reuses the tessellations of the original one.
+/// let copied = builder::transformed(&cube, Matrix4::identity());
+/// cache.reset_stats();
+/// cache.triangulate_solid(&copied, 0.02);
+/// assert_eq!(cache.stats().face_hits, 6);
+/// assert_eq!(cache.stats().face_misses, 0);
+/// ```
+#[derive(Debug)]
+pub struct TessellationCache<C, S> {
+    edges: HashMap<EdgeID<C>, CachedEdge>,
+    faces: HashMap<FaceID<S>, CachedFace<C>>,
+    /// the IDs of the last edges tessellated with the geometries
+    edge_keys: HashMap<QuantizedKey, EdgeID<C>>,
+    /// the IDs of the last faces tessellated with the geometries
+    face_keys: HashMap<QuantizedKey, FaceID<S>>,
+    generation: usize,
+    stats: TessellationCacheStats,
+}
+
+#[derive(Clone, Debug)]
+struct CachedEdge {
+    tol: f64,
+    /// the generation of the source edge
+    source: usize,
+    /// the points of the absolute front and back vertices of the source edge
+    ends: (Point3, Point3),
+    /// unique number for detecting re-tessellations of edges
+    generation: usize,
+    polyline: PolylineCurve,
+}
+
+impl CachedEdge {
+    /// Returns whether the curve and the vertices of `edge` are not changed after tessellated.
+    #[inline(always)]
+    fn is_fresh<C>(&self, edge: &Edge<Point3, C>) -> bool {
+        self.source == edge.generation() && self.ends == edge_ends(edge)
+    }
+}
+
+/// the points of the absolute front and back vertices of `edge`
+#[inline(always)]
+fn edge_ends<C>(edge: &Edge<Point3, C>) -> (Point3, Point3) {
+    (
+        edge.absolute_front().get_point(),
+        edge.absolute_back().get_point(),
+    )
+}
+
+#[derive(Debug)]
+struct CachedFace<C> {
+    tol: f64,
+    /// the generation of the source face
+    source: usize,
+    /// the IDs, orientations, and generations of the absolute boundaries
+    boundaries: Vec<Vec<(EdgeID<C>, bool, usize)>>,
+    polygon: Option<PolygonMesh>,
+}
+
+impl<C, S> Default for TessellationCache<C, S> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            edges: HashMap::default(),
+            faces: HashMap::default(),
+            edge_keys: HashMap::default(),
+            face_keys: HashMap::default(),
+            generation: 0,
+            stats: TessellationCacheStats::default(),
+        }
+    }
+}
+
+impl<C, S> TessellationCache<C, S> {
+    /// Creates an empty cache.
+    #[inline(always)]
+    pub fn new() -> Self { Self::default() }
+
+    /// Returns the numbers of the reused and tessellated elements after the last reset.
+    #[inline(always)]
+    pub fn stats(&self) -> TessellationCacheStats { self.stats }
+
+    /// Resets the statistics.
+    #[inline(always)]
+    pub fn reset_stats(&mut self) { self.stats = TessellationCacheStats::default(); }
+
+    /// Returns the numbers of the cached edges and faces.
+    #[inline(always)]
+    pub fn len(&self) -> (usize, usize) { (self.edges.len(), self.faces.len()) }
+
+    /// Returns whether the cache is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.edges.is_empty() && self.faces.is_empty() }
+
+    /// Removes all entries.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.edges.clear();
+        self.faces.clear();
+        self.edge_keys.clear();
+        self.face_keys.clear();
+    }
+
+    /// Removes the polyline of the edge. The faces bounded by the edge are re-tessellated
+    /// at the next time.
+    #[inline(always)]
+    pub fn invalidate_edge(&mut self, id: EdgeID<C>) { self.edges.remove(&id); }
+
+    /// Removes the mesh of the face.
+    #[inline(always)]
+    pub fn invalidate_face(&mut self, id: FaceID<S>) { self.faces.remove(&id); }
+
+    /// Returns whether the edge is not cached or edited after tessellated.
+    #[inline(always)]
+    pub fn is_edge_dirty(&self, edge: &Edge<Point3, C>) -> bool {
+        match self.edges.get(&edge.id()) {
+            Some(cached) => !cached.is_fresh(edge),
+            None => true,
+        }
+    }
+
+    /// Returns whether the face is not cached, edited after tessellated, or bounded by the dirty
+    /// or re-tessellated edges.
+    pub fn is_face_dirty(&self, face: &Face<Point3, C, S>) -> bool {
+        match self.faces.get(&face.id()) {
+            Some(cached) => {
+                cached.source != face.generation()
+                    || face
+                        .absolute_boundaries()
+                        .iter()
+                        .flat_map(Wire::edge_iter)
+                        .any(|edge| self.is_edge_dirty(edge))
+                    || cached.boundaries != self.boundary_keys(face)
+            }
+            None => true,
+        }
+    }
+
+    /// Tessellates the shell, reusing the cached edges and faces.
+    /// The result is the same as [`MeshableShape::triangulation`].
+    /// # Panics
+    /// `tol` must be more than `TOLERANCE`.
+    pub fn triangulate_shell(&mut self, shell: &Shell<Point3, C, S>, tol: f64) -> MeshedShell
+    where
+        C: PolylineableCurve + Quantize,
+        S: MeshableSurface + Quantize, {
+        nonpositive_tolerance!(tol);
+        let mut vmap = HashMap::default();
+        let mut edge_map = HashMap::default();
+        shell
+            .face_iter()
+            .map(|face| {
+                let wires: Vec<Wire<Point3, PolylineCurve>> = face
+                    .absolute_boundaries()
+                    .iter()
+                    .map(|wire| {
+                        wire.edge_iter()
+                            .map(|edge| {
+                                let new_edge = edge_map.entry(edge.id()).or_insert_with(|| {
+                                    let [v0, v1] = [edge.absolute_front(), edge.absolute_back()]
+                                        .map(|v| {
+                                            vmap.entry(v.id())
+                                                .or_insert_with(|| v.mapped(Point3::clone))
+                                                .clone()
+                                        });
+                                    let polyline = self.edge_polyline(edge, tol).polyline.clone();
+                                    Edge::debug_new(&v0, &v1, polyline)
+                                });
+                                match edge.orientation() {
+                                    true => new_edge.clone(),
+                                    false => new_edge.inverse(),
+                                }
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let polygon = self.face_polygon(face, &wires, tol);
+                let mut new_face = Face::debug_new(wires, polygon);
+                if !face.orientation() {
+                    new_face.invert();
+                }
+                new_face
+            })
+            .collect()
+    }
+
+    /// Tessellates the solid, reusing the cached edges and faces.
+    /// The result is the same as [`MeshableShape::triangulation`].
+    /// # Panics
+    /// `tol` must be more than `TOLERANCE`.
+    pub fn triangulate_solid(&mut self, solid: &Solid<Point3, C, S>, tol: f64) -> MeshedSolid
+    where
+        C: PolylineableCurve + Quantize,
+        S: MeshableSurface + Quantize, {
+        let boundaries = solid
+            .boundaries()
+            .iter()
+            .map(|shell| self.triangulate_shell(shell, tol))
+            .collect::<Vec<_>>();
+        Solid::new(boundaries)
+    }
+
+    fn edge_polyline(&mut self, edge: &Edge<Point3, C>, tol: f64) -> &CachedEdge
+    where C: PolylineableCurve + Quantize {
+        let id = edge.id();
+        match self.edges.get(&id) {
+            Some(cached) if cached.tol == tol && cached.is_fresh(edge) => self.stats.edge_hits += 1,
+            _ => {
+                self.generation += 1;
+                let curve = edge.get_curve();
+                let key = curve.quantized_key(TOLERANCE);
+                let identical = self.identical_edge(&key, id, tol).cloned();
+                let polyline = match identical {
+                    Some(cached) => {
+                        self.stats.edge_hits += 1;
+                        cached.polyline
+                    }
+                    None => {
+                        self.stats.edge_misses += 1;
+                        PolylineCurve::from_curve(&curve, curve.parameter_range(), tol)
+                    }
+                };
+                let cached = CachedEdge {
+                    tol,
+                    source: edge.generation(),
+                    ends: edge_ends(edge),
+                    generation: self.generation,
+                    polyline,
+                };
+                self.edges.insert(id, cached);
+                self.edge_keys.insert(key, id);
+            }
+        }
+        &self.edges[&id]
+    }
+
+    /// Returns the cached edge of the other ID with the geometry of `key`.
+    fn identical_edge(&self, key: &QuantizedKey, id: EdgeID<C>, tol: f64) -> Option<&CachedEdge> {
+        let other = self.edge_keys.get(key).filter(|other| **other != id)?;
+        self.edges.get(other).filter(|cached| cached.tol == tol)
+    }
+
+    /// the IDs, orientations, and generations of the tessellations of the absolute boundaries
+    fn boundary_keys<S0>(&self, face: &Face<Point3, C, S0>) -> Vec<Vec<(EdgeID<C>, bool, usize)>> {
+        face.absolute_boundaries()
+            .iter()
+            .map(|wire| {
+                wire.edge_iter()
+                    .map(|edge| {
+                        let generation = self.edges.get(&edge.id()).map_or(0, |e| e.generation);
+                        (edge.id(), edge.orientation(), generation)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn face_polygon(
+        &mut self,
+        face: &Face<Point3, C, S>,
+        wires: &[Wire<Point3, PolylineCurve>],
+        tol: f64,
+    ) -> Option<PolygonMesh>
+    where
+        C: Clone + Quantize,
+        S: MeshableSurface + Quantize,
+    {
+        let boundaries = self.boundary_keys(face);
+        match self.faces.get(&face.id()) {
+            Some(cached)
+                if cached.tol == tol
+                    && cached.source == face.generation()
+                    && cached.boundaries == boundaries =>
+            {
+                self.stats.face_hits += 1;
+                cached.polygon.clone()
+            }
+            _ => {
+                let surface = face.get_surface();
+                let key = face_key(face);
+                let identical = self
+                    .face_keys
+                    .get(&key)
+                    .filter(|other| **other != face.id())
+                    .and_then(|other| self.faces.get(other))
+                    .filter(|cached| cached.tol == tol);
+                let polygon = match identical {
+                    Some(cached) => {
+                        self.stats.face_hits += 1;
+                        cached.polygon.clone()
+                    }
+                    None => {
+                        self.stats.face_misses += 1;
+                        let mut polyline = Polyline::default();
+                        match wires.iter().all(|wire| {
+                            polyline
+                                .add_wire(&surface, wire.iter().map(|edge| edge.oriented_curve()))
+                        }) {
+                            true => Some(trimming_tessellation(&surface, &polyline, tol)),
+                            false => None,
+                        }
+                    }
+                };
+                let cached = CachedFace {
+                    tol,
+                    source: face.generation(),
+                    boundaries,
+                    polygon: polygon.clone(),
+                };
+                self.faces.insert(face.id(), cached);
+                self.face_keys.insert(key, face.id());
+                polygon
+            }
+        }
+    }
+}
+
+/// the key of the surface and the curves of the absolute boundaries of `face`
+fn face_key<C: Quantize + Clone, S: Quantize + Clone>(face: &Face<Point3, C, S>) -> QuantizedKey {
+    let mut key = face.get_surface().quantized_key(TOLERANCE);
+    let boundaries = face.absolute_boundaries();
+    key.push_tag(boundaries.len());
+    boundaries.iter().for_each(|wire| {
+        key.push_tag(wire.len());
+        wire.edge_iter().for_each(|edge| {
+            edge.orientation().quantize(TOLERANCE, &mut key);
+            edge.get_curve().quantize(TOLERANCE, &mut key);
+        })
+    });
+    key
+}
@@ -0,0 +1,305 @@
+use super::triangulation::{edge_polyline, trimming_tessellation, Polyline};
+use super::*;
+use std::ops::{Add, Mul, Sub};
+
+/// The values of the integrands, the elements of the vector spaces with the norms.
+pub trait Integrable:
+    Copy + Zero + Add<Output = Self> + Sub<Output = Self> + Mul<f64, Output = Self> {
+    /// the norm by which the errors of the integrals are estimated
+    fn norm(self) -> f64;
+}
+
+impl Integrable for f64 {
+    #[inline(always)]
+    fn norm(self) -> f64 { self.abs() }
+}
+
+macro_rules! impl_integrable {
+    ($($vector: ty),*) => {
+        $(impl Integrable for $vector {
+            #[inline(always)]
+            fn norm(self) -> f64 { self.magnitude() }
+        })*
+    };
+}
+impl_integrable!(Vector2, Vector3, Vector4);
+
+/// The maximum depth of the subdivision of the triangles of the parameter domain.
+const TRIANGLE_MAX_DEPTH: usize = 6;
+/// The maximum depth of the bisection of the intervals.
+const INTERVAL_MAX_DEPTH: usize = 20;
+
+/// the barycentric coordinates and the weights of the Gauss quadrature of degree five on triangles
+/// by Dunavant
+const TRIANGLE_GAUSS: [([f64; 3], f64); 7] = [
+    ([1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0], 0.225),
+    (
+        [0.059715871789770, 0.470142064105115, 0.470142064105115],
+        0.132394152788506,
+    ),
+    (
+        [0.470142064105115, 0.059715871789770, 0.470142064105115],
+        0.132394152788506,
+    ),
+    (
+        [0.470142064105115, 0.470142064105115, 0.059715871789770],
+        0.132394152788506,
+    ),
+    (
+        [0.797426985353087, 0.101286507323456, 0.101286507323456],
+        0.125939180544827,
+    ),
+    (
+        [0.101286507323456, 0.797426985353087, 0.101286507323456],
+        0.125939180544827,
+    ),
+    (
+        [0.101286507323456, 0.101286507323456, 0.797426985353087],
+        0.125939180544827,
+    ),
+];
+
+/// the nodes and the weights of the Gauss–Legendre quadrature of five points on `[-1, 1]`
+const LEGENDRE_GAUSS: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+
+/// Integrates `f` over the trimmed region of the face by the adaptive Gauss quadrature.
+///
+/// `f` is given the point on the face and the unit normal vector, which is oriented by the face.
+/// Returns `None` if the boundary of the face cannot be projected onto the surface.
+///
+/// # Details
+/// The parameter domain trimmed by the boundary is triangulated as
+/// [`MeshableShape::triangulation`], where the boundary is approximated by the polylines within
+/// `tol`. The integral over each triangle is computed by the Gauss quadrature of degree five,
+/// and the triangle is divided into four until the difference from the sum over the divided
+/// triangles is less than `tol`, distributed to the triangles by their areas.
+///
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+/// use std::f64::consts::PI;
+///
+/// // the disk of radius 2
+/// let v = builder::vertex(Point3::new(2.0, 0.0, 0.0));
+/// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+/// let disk = builder::try_attach_plane(&[circle]).unwrap();
+///
+/// // the area
+/// let area = integrate_over_face(&disk, |_, _| 1.0, 1.0e-4).unwrap();
+/// assert!(f64::abs(area - 4.0 * PI) < 1.0e-3);
+///
+/// // the force by the pressure `p = x^2`, acting opposite to the normal
+/// let force = integrate_over_face(&disk, |pt, normal| -normal * pt.x * pt.x, 1.0e-4).unwrap();
+/// assert!((force - Vector3::new(0.0, 0.0, -4.0 * PI)).magnitude() < 1.0e-3);
+/// ```
+pub fn integrate_over_face<C, S, T>(
+    face: &Face<Point3, C, S>,
+    f: impl Fn(Point3, Vector3) -> T,
+    tol: f64,
+) -> Option<T>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+    T: Integrable,
+{
+    nonpositive_tolerance!(tol);
+    face_integral(face, f, tol)
+}
+
+fn face_integral<C, S, T>(
+    face: &Face<Point3, C, S>,
+    f: impl Fn(Point3, Vector3) -> T,
+    tol: f64,
+) -> Option<T>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+    T: Integrable,
+{
+    let surface = face.get_surface();
+    let mesh_tol = f64::max(tol, TOLERANCE);
+    let mut polyline = Polyline::default();
+    let projected = face.absolute_boundaries().iter().all(|wire| {
+        let polylines = wire.edge_iter().map(|edge| {
+            let mut poly = edge_polyline(&edge.get_curve(), &[&surface], mesh_tol);
+            if !edge.orientation() {
+                poly.invert();
+            }
+            poly
+        });
+        polyline.add_wire(&surface, polylines)
+    });
+    if !projected {
+        return None;
+    }
+    let mesh = trimming_tessellation(&surface, &polyline, mesh_tol);
+    let sign = match face.orientation() {
+        true => 1.0,
+        false => -1.0,
+    };
+    let integrand = |uv: Point2| {
+        let (u, v) = (uv.x, uv.y);
+        let normal = surface.uder(u, v).cross(surface.vder(u, v));
+        let jacobian = normal.magnitude();
+        match jacobian.so_small() {
+            true => T::zero(),
+            false => f(surface.subs(u, v), normal * (sign / jacobian)) * jacobian,
+        }
+    };
+    let triangles: Vec<[Point2; 3]> = mesh
+        .faces()
+        .triangle_iter()
+        .map(|tri| tri.map(|v| Point2::from_vec(mesh.uv_coords()[v.uv.unwrap()])))
+        .collect();
+    let total_area: f64 = triangles.iter().map(|tri| triangle_area(*tri)).sum();
+    if total_area.so_small2() {
+        return Some(T::zero());
+    }
+    let res = triangles.into_iter().fold(T::zero(), |sum, tri| {
+        let tri_tol = tol * triangle_area(tri) / total_area;
+        let estimate = triangle_gauss(&integrand, tri);
+        sum + adaptive_triangle(&integrand, tri, estimate, tri_tol, TRIANGLE_MAX_DEPTH)
+    });
+    Some(res)
+}
+
+/// Integrates `f` over the solid by the adaptive Gauss quadrature.
+///
+/// Returns `None` if the boundary of a face cannot be projected onto the surface.
+///
+/// # Details
+/// The integral over the solid is converted into the integral over the boundary by the
+/// divergence theorem: the integrand is integrated along the `x` axis by the adaptive
+/// Gauss–Legendre quadrature, and the primitive multiplied by the `x` component of the normal
+/// is integrated over the faces by [`integrate_over_face`].
+///
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+///
+/// // the cube [0, 1]^3
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let volume = integrate_over_solid(&cube, |_| 1.0, 1.0e-4).unwrap();
+/// assert!(f64::abs(volume - 1.0) < 1.0e-4);
+///
+/// // the moment of inertia around the z axis
+/// let inertia = integrate_over_solid(&cube, |pt| pt.x * pt.x + pt.y * pt.y, 1.0e-4).unwrap();
+/// assert!(f64::abs(inertia - 2.0 / 3.0) < 1.0e-4);
+/// ```
+pub fn integrate_over_solid<C, S, T>(
+    solid: &Solid<Point3, C, S>,
+    f: impl Fn(Point3) -> T,
+    tol: f64,
+) -> Option<T>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+    T: Integrable,
+{
+    nonpositive_tolerance!(tol);
+    let bdb: BoundingBox<Point3> = solid
+        .boundaries()
+        .iter()
+        .flat_map(Shell::vertex_iter)
+        .map(|v| v.get_point())
+        .collect();
+    if bdb.is_empty() {
+        return Some(T::zero());
+    }
+    let x0 = bdb.center().x;
+    let faces = solid.boundaries().iter().flat_map(Shell::face_iter);
+    let len = faces.clone().count() as f64;
+    faces
+        .map(|face| {
+            let primitive = |pt: Point3, normal: Vector3| {
+                let g = |x: f64| f(Point3::new(x, pt.y, pt.z));
+                let estimate = legendre_gauss(&g, x0, pt.x);
+                adaptive_interval(&g, x0, pt.x, estimate, tol / len, INTERVAL_MAX_DEPTH) * normal.x
+            };
+            face_integral(face, primitive, tol / len)
+        })
+        .try_fold(T::zero(), |sum, integral| Some(sum + integral?))
+}
+
+fn triangle_area(tri: [Point2; 3]) -> f64 {
+    (tri[1] - tri[0]).perp_dot(tri[2] - tri[0]).abs() / 2.0
+}
+
+fn triangle_gauss<T: Integrable>(f: &impl Fn(Point2) -> T, tri: [Point2; 3]) -> T {
+    let sum = TRIANGLE_GAUSS
+        .iter()
+        .fold(T::zero(), |sum, (bary, weight)| {
+            let uv = Point2::from_vec(
+                tri[0].to_vec() * bary[0] + tri[1].to_vec() * bary[1] + tri[2].to_vec() * bary[2],
+            );
+            sum + f(uv) * *weight
+        });
+    sum * triangle_area(tri)
+}
+
+/// Divides the triangle into four until the sum over them is near to `estimate`.
+fn adaptive_triangle<T: Integrable>(
+    f: &impl Fn(Point2) -> T,
+    tri: [Point2; 3],
+    estimate: T,
+    tol: f64,
+    depth: usize,
+) -> T {
+    let mid = [0, 1, 2].map(|i| tri[i].midpoint(tri[(i + 1) % 3]));
+    let children = [
+        [tri[0], mid[0], mid[2]],
+        [mid[0], tri[1], mid[1]],
+        [mid[2], mid[1], tri[2]],
+        [mid[0], mid[1], mid[2]],
+    ];
+    let integrals = children.map(|child| triangle_gauss(f, child));
+    let sum = integrals.iter().fold(T::zero(), |sum, x| sum + *x);
+    if depth == 0 || (sum - estimate).norm() < tol {
+        return sum;
+    }
+    children
+        .into_iter()
+        .zip(integrals)
+        .fold(T::zero(), |sum, (child, integral)| {
+            sum + adaptive_triangle(f, child, integral, tol / 4.0, depth - 1)
+        })
+}
+
+fn legendre_gauss<T: Integrable>(f: &impl Fn(f64) -> T, t0: f64, t1: f64) -> T {
+    let (mid, half) = ((t0 + t1) / 2.0, (t1 - t0) / 2.0);
+    let sum = LEGENDRE_GAUSS.iter().fold(T::zero(), |sum, (x, weight)| {
+        sum + f(mid + half * x) * *weight
+    });
+    sum * half
+}
+
+/// Bisects the interval until the sum over the halves is near to `estimate`.
+fn adaptive_interval<T: Integrable>(
+    f: &impl Fn(f64) -> T,
+    t0: f64,
+    t1: f64,
+    estimate: T,
+    tol: f64,
+    depth: usize,
+) -> T {
+    let mid = (t0 + t1) / 2.0;
+    let (left, right) = (legendre_gauss(f, t0, mid), legendre_gauss(f, mid, t1));
+    let sum = left + right;
+    if depth == 0 || (sum - estimate).norm() < tol {
+        return sum;
+    }
+    adaptive_interval(f, t0, mid, left, tol / 2.0, depth - 1)
+        + adaptive_interval(f, mid, t1, right, tol / 2.0, depth - 1)
+}
@@ -132,13 +132,76 @@ pub trait MeshableShape {
     /// assert!(mesh.shell_condition() == ShellCondition::Closed);
     /// ```
     fn triangulation(&self, tol: f64) -> Self::MeshedShape;
+    /// Tessellates shapes by `tol`, except for the faces whose tolerances are overridden by
+    /// `face_tolerance`, e.g. finer on the visually important faces and coarser on the internal ones.
+    ///
+    /// `face_tolerance` is called with the indices of the faces: the positions in the faces of
+    /// all boundary shells in order. The edges are divided by the minimum tolerance of the adjacent
+    /// faces, so the meshes of the adjacent faces share the boundaries.
+    ///
+    /// # Panics
+    ///
+    /// `tol` and the overridden tolerances must be more than `TOLERANCE`.
+    ///
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// // the cylinder: the lateral faces, and the top and the bottom
+    /// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    /// let disk = builder::try_attach_plane(&[circle]).unwrap();
+    /// let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+    ///
+    /// let triangles = |meshed: &truck_topology::Solid<_, _, Option<PolygonMesh>>| {
+    ///     meshed.boundaries()[0]
+    ///         .face_iter()
+    ///         .map(|face| face.get_surface().unwrap().tri_faces().len())
+    ///         .collect::<Vec<_>>()
+    /// };
+    /// let coarse = triangles(&cylinder.triangulation(0.1));
+    /// // finer only on the first face
+    /// let meshed = cylinder.triangulation_with_face_tolerances(0.1, |i| (i == 0).then_some(0.01));
+    /// let overridden = triangles(&meshed);
+    /// assert!(overridden[0] > coarse[0]);
+    /// // the closed mesh without cracks
+    /// let mut mesh = meshed.to_polygon();
+    /// mesh.put_together_same_attrs();
+    /// assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    /// ```
+    fn triangulation_with_face_tolerances(
+        &self,
+        tol: f64,
+        face_tolerance: impl Fn(usize) -> Option<f64>,
+    ) -> Self::MeshedShape;
+}
+
+fn checked_tolerance(
+    face_tolerance: impl Fn(usize) -> Option<f64>,
+) -> impl Fn(usize) -> Option<f64> {
+    move |i| {
+        let tol = face_tolerance(i);
+        if let Some(tol) = tol {
+            nonpositive_tolerance!(tol);
+        }
+        tol
+    }
 }
 
 impl<C: PolylineableCurve, S: MeshableSurface> MeshableShape for Shell<Point3, C, S> {
     type MeshedShape = Shell<Point3, PolylineCurve, Option<PolygonMesh>>;
     fn triangulation(&self, tol: f64) -> Self::MeshedShape {
         nonpositive_tolerance!(tol);
-        triangulation::shell_tessellation(self, tol)
+        triangulation::shell_tessellation(self, tol, |_| None)
+    }
+    fn triangulation_with_face_tolerances(
+        &self,
+        tol: f64,
+        face_tolerance: impl Fn(usize) -> Option<f64>,
+    ) -> Self::MeshedShape {
+        nonpositive_tolerance!(tol);
+        triangulation::shell_tessellation(self, tol, checked_tolerance(face_tolerance))
     }
 }
 
@@ -152,13 +215,38 @@ impl<C: PolylineableCurve, S: MeshableSurface> MeshableShape for Solid<Point3, C
             .collect::<Vec<_>>();
         Solid::new(boundaries)
     }
+    fn triangulation_with_face_tolerances(
+        &self,
+        tol: f64,
+        face_tolerance: impl Fn(usize) -> Option<f64>,
+    ) -> Self::MeshedShape {
+        let mut offset = 0;
+        let boundaries = self
+            .boundaries()
+            .iter()
+            .map(|shell| {
+                let start = offset;
+                offset += shell.len();
+                shell.triangulation_with_face_tolerances(tol, |i| face_tolerance(start + i))
+            })
+            .collect::<Vec<_>>();
+        Solid::new(boundaries)
+    }
 }
 
 impl<C: PolylineableCurve, S: MeshableSurface> MeshableShape for CompressedShell<Point3, C, S> {
     type MeshedShape = CompressedShell<Point3, PolylineCurve, Option<PolygonMesh>>;
     fn triangulation(&self, tol: f64) -> Self::MeshedShape {
         nonpositive_tolerance!(tol);
-        triangulation::cshell_tessellation(self, tol)
+        triangulation::cshell_tessellation(self, tol, |_| None)
+    }
+    fn triangulation_with_face_tolerances(
+        &self,
+        tol: f64,
+        face_tolerance: impl Fn(usize) -> Option<f64>,
+    ) -> Self::MeshedShape {
+        nonpositive_tolerance!(tol);
+        triangulation::cshell_tessellation(self, tol, checked_tolerance(face_tolerance))
     }
 }
 
@@ -172,6 +260,33 @@ impl<C: PolylineableCurve, S: MeshableSurface> MeshableShape for CompressedSolid
             .collect::<Vec<_>>();
         CompressedSolid { boundaries }
     }
+    fn triangulation_with_face_tolerances(
+        &self,
+        tol: f64,
+        face_tolerance: impl Fn(usize) -> Option<f64>,
+    ) -> Self::MeshedShape {
+        let mut offset = 0;
+        let boundaries = self
+            .boundaries
+            .iter()
+            .map(|shell| {
+                let start = offset;
+                offset += shell.faces.len();
+                shell.triangulation_with_face_tolerances(tol, |i| face_tolerance(start + i))
+            })
+            .collect::<Vec<_>>();
+        CompressedSolid { boundaries }
+    }
 }
 
+mod cache;
+mod integration;
+mod picking;
 mod triangulation;
+mod watertight;
+mod wireframe;
+pub use cache::{TessellationCache, TessellationCacheStats};
+pub use integration::{integrate_over_face, integrate_over_solid, Integrable};
+pub use picking::{PickedElement, ShapePicker};
+pub use watertight::{WatertightReport, WatertightShape};
+pub use wireframe::WireframeExtraction;
@@ -0,0 +1,399 @@
+use super::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::fmt::{Debug, Formatter};
+
+/// The element of a shell picked by [`ShapePicker`].
+pub enum PickedElement<C, S> {
+    /// picked vertex
+    Vertex(VertexID<Point3>),
+    /// picked edge
+    Edge(EdgeID<C>),
+    /// picked face
+    Face(FaceID<S>),
+}
+
+impl<C, S> Clone for PickedElement<C, S> {
+    #[inline(always)]
+    fn clone(&self) -> Self { *self }
+}
+
+impl<C, S> Copy for PickedElement<C, S> {}
+
+impl<C, S> PartialEq for PickedElement<C, S> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Vertex(x), Self::Vertex(y)) => x == y,
+            (Self::Edge(x), Self::Edge(y)) => x == y,
+            (Self::Face(x), Self::Face(y)) => x == y,
+            _ => false,
+        }
+    }
+}
+
+impl<C, S> Eq for PickedElement<C, S> {}
+
+impl<C, S> Debug for PickedElement<C, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Vertex(id) => f.debug_tuple("Vertex").field(id).finish(),
+            Self::Edge(id) => f.debug_tuple("Edge").field(id).finish(),
+            Self::Face(id) => f.debug_tuple("Face").field(id).finish(),
+        }
+    }
+}
+
+/// Acceleration structure for picking the vertices, edges, and faces of shells by rays.
+///
+/// The shell is tessellated at the construction, and the vertices, the segments of the
+/// polylines of the edges, and the triangles of the faces are stored in a bounding volume
+/// hierarchy. The ray is given by its origin and direction, e.g. by `Camera::ray` of
+/// `truck-platform`.
+///
+/// # Snapping
+/// The vertices and the edges within the distance `snap_radius` from the ray are picked
+/// prior to the faces, and the vertices are prior to the edges. The elements hidden behind
+/// the nearest face hit by the ray, beyond `snap_radius`, are not picked.
+///
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+///
+/// // modeling a unit cube
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+/// let shell = &cube.boundaries()[0];
+/// let picker = ShapePicker::new(shell, 0.01);
+///
+/// // the ray toward the center of the top face
+/// let origin = Point3::new(0.5, 0.5, 2.0);
+/// let (element, point) = picker.pick(origin, -Vector3::unit_z(), 0.05).unwrap();
+/// assert!(point.near(&Point3::new(0.5, 0.5, 1.0)));
+/// match element {
+///     PickedElement::Face(id) => {
+///         let face = shell.face_iter().find(|face| face.id() == id).unwrap();
+///         assert!(face.get_surface().normal(0.0, 0.0).near(&Vector3::unit_z()));
+///     }
+///     _ => panic!("the face must be picked."),
+/// }
+///
+/// // the ray near the corner (1, 1, 1) snaps to the vertex.
+/// let origin = Point3::new(0.98, 0.99, 2.0);
+/// let (element, point) = picker.pick(origin, -Vector3::unit_z(), 0.05).unwrap();
+/// assert_eq!(point, Point3::new(1.0, 1.0, 1.0));
+/// assert!(matches!(element, PickedElement::Vertex(_)));
+///
+/// // the ray missing the cube
+/// assert!(picker.pick(Point3::new(2.0, 0.5, 2.0), -Vector3::unit_z(), 0.05).is_none());
+/// ```
+#[derive(Debug)]
+pub struct ShapePicker<C, S> {
+    vertices: Vec<(VertexID<Point3>, Point3)>,
+    edges: Vec<EdgeID<C>>,
+    faces: Vec<FaceID<S>>,
+    primitives: Vec<Primitive>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Primitive {
+    /// index of vertex
+    Vertex(usize),
+    /// index of edge and the segment
+    Segment(usize, [Point3; 2]),
+    /// index of face and the triangle
+    Triangle(usize, [Point3; 3]),
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    bdd_box: BoundingBox<Point3>,
+    /// the children or the range of the primitives
+    content: NodeContent,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NodeContent {
+    Inner(usize, usize),
+    Leaf(usize, usize),
+}
+
+const LEAF_SIZE: usize = 4;
+
+impl<C, S> ShapePicker<C, S> {
+    /// Tessellates the shell with the tolerance `tol` and constructs the picker.
+    /// # Panics
+    /// `tol` must be more than `TOLERANCE`.
+    pub fn new(shell: &Shell<Point3, C, S>, tol: f64) -> Self
+    where
+        C: PolylineableCurve,
+        S: MeshableSurface, {
+        let meshed = shell.triangulation(tol);
+        let mut vertex_map = HashMap::default();
+        let mut edge_map = HashMap::default();
+        let mut picker = ShapePicker {
+            vertices: Vec::new(),
+            edges: Vec::new(),
+            faces: Vec::new(),
+            primitives: Vec::new(),
+            nodes: Vec::new(),
+        };
+        shell
+            .face_iter()
+            .zip(meshed.face_iter())
+            .for_each(|(face, meshed_face)| {
+                let boundaries = face.absolute_boundaries().iter().flatten();
+                let meshed_boundaries = meshed_face.absolute_boundaries();
+                boundaries.zip(meshed_boundaries.iter().flatten()).for_each(
+                    |(edge, meshed_edge)| {
+                        [edge.absolute_front(), edge.absolute_back()]
+                            .into_iter()
+                            .for_each(|v| {
+                                vertex_map.entry(v.id()).or_insert_with(|| {
+                                    picker.vertices.push((v.id(), v.get_point()));
+                                    let idx = picker.vertices.len() - 1;
+                                    picker.primitives.push(Primitive::Vertex(idx));
+                                });
+                            });
+                        edge_map.entry(edge.id()).or_insert_with(|| {
+                            picker.edges.push(edge.id());
+                            let idx = picker.edges.len() - 1;
+                            let polyline = meshed_edge.get_curve();
+                            polyline.windows(2).for_each(|p| {
+                                picker
+                                    .primitives
+                                    .push(Primitive::Segment(idx, [p[0], p[1]]));
+                            });
+                        });
+                    },
+                );
+                picker.faces.push(face.id());
+                let idx = picker.faces.len() - 1;
+                if let Some(polygon) = meshed_face.get_surface() {
+                    polygon.faces().triangle_iter().for_each(|tri| {
+                        let tri = tri.map(|v| polygon.positions()[v.pos]);
+                        picker.primitives.push(Primitive::Triangle(idx, tri));
+                    });
+                }
+            });
+        let len = picker.primitives.len();
+        picker.build_node(0, len);
+        picker
+    }
+
+    /// Picks the element by the ray.
+    ///
+    /// Returns the picked element and the picked point: the position of the vertex,
+    /// the nearest point on the edge to the ray, or the intersection point with the face.
+    /// Returns `None` if the ray does not hit any element.
+    pub fn pick(
+        &self,
+        origin: Point3,
+        direction: Vector3,
+        snap_radius: f64,
+    ) -> Option<(PickedElement<C, S>, Point3)> {
+        let direction = direction.normalize();
+        // (distance from the ray, depth, index, point)
+        let mut vertex: Option<(f64, f64, usize, Point3)> = None;
+        let mut edge: Option<(f64, f64, usize, Point3)> = None;
+        let mut face: Option<(f64, usize, Point3)> = None;
+        let update = |current: &mut Option<(f64, f64, usize, Point3)>,
+                      new: (f64, f64, usize, Point3)| {
+            let nearer = match current {
+                Some((d, t, ..)) => (new.0, new.1) < (*d, *t),
+                None => true,
+            };
+            if nearer {
+                *current = Some(new);
+            }
+        };
+        self.for_each_primitive(
+            origin,
+            direction,
+            snap_radius,
+            |primitive| match primitive {
+                Primitive::Vertex(idx) => {
+                    let p = self.vertices[idx].1;
+                    let t = (p - origin).dot(direction);
+                    let dist = (origin + direction * t).distance(p);
+                    if t >= 0.0 && dist <= snap_radius {
+                        update(&mut vertex, (dist, t, idx, p));
+                    }
+                }
+                Primitive::Segment(idx, segment) => {
+                    if let Some((dist, t, p)) = ray_segment(origin, direction, segment) {
+                        if dist <= snap_radius {
+                            update(&mut edge, (dist, t, idx, p));
+                        }
+                    }
+                }
+                Primitive::Triangle(idx, triangle) => {
+                    if let Some(t) = ray_triangle(origin, direction, triangle) {
+                        let nearer = match face {
+                            Some((t0, ..)) => t < t0,
+                            None => true,
+                        };
+                        if nearer {
+                            face = Some((t, idx, origin + direction * t));
+                        }
+                    }
+                }
+            },
+        );
+        let depth = face.map_or(f64::INFINITY, |(t, ..)| t + snap_radius);
+        match (vertex, edge, face) {
+            (Some((_, t, idx, p)), ..) if t <= depth => {
+                Some((PickedElement::Vertex(self.vertices[idx].0), p))
+            }
+            (_, Some((_, t, idx, p)), _) if t <= depth => {
+                Some((PickedElement::Edge(self.edges[idx]), p))
+            }
+            (.., Some((_, idx, p))) => Some((PickedElement::Face(self.faces[idx]), p)),
+            _ => None,
+        }
+    }
+
+    /// Builds the node of the primitives in `start..end` and returns its index.
+    fn build_node(&mut self, start: usize, end: usize) -> usize {
+        let bdd_box: BoundingBox<Point3> = self.primitives[start..end]
+            .iter()
+            .flat_map(|primitive| self.primitive_points(*primitive))
+            .collect();
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            bdd_box: bdd_box.clone(),
+            content: NodeContent::Leaf(start, end),
+        });
+        if end - start > LEAF_SIZE {
+            let diagonal = bdd_box.diagonal();
+            let axis = (0..3)
+                .max_by(|i, j| diagonal[*i].partial_cmp(&diagonal[*j]).unwrap())
+                .unwrap();
+            let center = |primitive: &Primitive| -> f64 {
+                let points = self.primitive_points(*primitive);
+                points.iter().map(|p| p[axis]).sum::<f64>() / points.len() as f64
+            };
+            let mid = (start + end) / 2;
+            let mut centers: Vec<(f64, Primitive)> = self.primitives[start..end]
+                .iter()
+                .map(|primitive| (center(primitive), *primitive))
+                .collect();
+            centers.select_nth_unstable_by(mid - start, |a, b| a.0.partial_cmp(&b.0).unwrap());
+            self.primitives[start..end]
+                .iter_mut()
+                .zip(centers)
+                .for_each(|(primitive, (_, sorted))| *primitive = sorted);
+            let left = self.build_node(start, mid);
+            let right = self.build_node(mid, end);
+            self.nodes[idx].content = NodeContent::Inner(left, right);
+        }
+        idx
+    }
+
+    fn primitive_points(&self, primitive: Primitive) -> Vec<Point3> {
+        match primitive {
+            Primitive::Vertex(idx) => vec![self.vertices[idx].1],
+            Primitive::Segment(_, segment) => segment.to_vec(),
+            Primitive::Triangle(_, triangle) => triangle.to_vec(),
+        }
+    }
+
+    /// Calls `f` for the primitives in the nodes whose boxes expanded by `radius` meet the ray.
+    fn for_each_primitive(
+        &self,
+        origin: Point3,
+        direction: Vector3,
+        radius: f64,
+        mut f: impl FnMut(Primitive),
+    ) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![0];
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            if !ray_meets_box(origin, direction, &node.bdd_box, radius) {
+                continue;
+            }
+            match node.content {
+                NodeContent::Inner(left, right) => stack.extend([left, right]),
+                NodeContent::Leaf(start, end) => {
+                    self.primitives[start..end].iter().for_each(|p| f(*p))
+                }
+            }
+        }
+    }
+}
+
+/// slab test of the ray and the box expanded by `radius`
+fn ray_meets_box(
+    origin: Point3,
+    direction: Vector3,
+    bdd_box: &BoundingBox<Point3>,
+    radius: f64,
+) -> bool {
+    if bdd_box.is_empty() {
+        return false;
+    }
+    let (min, max) = (bdd_box.min(), bdd_box.max());
+    let (mut t0, mut t1) = (0.0, f64::INFINITY);
+    (0..3).all(|i| {
+        let (min, max) = (min[i] - radius, max[i] + radius);
+        if direction[i].abs() < f64::EPSILON {
+            return min <= origin[i] && origin[i] <= max;
+        }
+        let (s0, s1) = (
+            (min - origin[i]) / direction[i],
+            (max - origin[i]) / direction[i],
+        );
+        t0 = f64::max(t0, f64::min(s0, s1));
+        t1 = f64::min(t1, f64::max(s0, s1));
+        t0 <= t1
+    })
+}
+
+/// the distance between the ray and the segment, the depth, and the nearest point on the segment
+fn ray_segment(
+    origin: Point3,
+    direction: Vector3,
+    segment: [Point3; 2],
+) -> Option<(f64, f64, Point3)> {
+    let vec = segment[1] - segment[0];
+    let w = segment[0] - origin;
+    let (b, c) = (direction.dot(vec), vec.magnitude2());
+    let (d, e) = (direction.dot(w), vec.dot(w));
+    let denom = c - b * b;
+    // the parameter on the segment of the nearest point
+    let s = match denom > TOLERANCE2 * c && c > 0.0 {
+        true => f64::clamp((b * d - e) / denom, 0.0, 1.0),
+        false => 0.0,
+    };
+    let p = segment[0] + vec * s;
+    let t = (p - origin).dot(direction);
+    match t >= 0.0 {
+        true => Some(((origin + direction * t).distance(p), t, p)),
+        false => None,
+    }
+}
+
+/// the depth of the intersection by the Möller-Trumbore algorithm
+fn ray_triangle(origin: Point3, direction: Vector3, triangle: [Point3; 3]) -> Option<f64> {
+    let (e1, e2) = (triangle[1] - triangle[0], triangle[2] - triangle[0]);
+    let p = direction.cross(e2);
+    let det = e1.dot(p);
+    if det.abs() < TOLERANCE2 {
+        return None;
+    }
+    let s = origin - triangle[0];
+    let u = s.dot(p) / det;
+    let q = s.cross(e1);
+    let v = direction.dot(q) / det;
+    let t = e2.dot(q) / det;
+    match (0.0..=1.0).contains(&u) && v >= 0.0 && u + v <= 1.0 && t >= 0.0 {
+        true => Some(t),
+        false => None,
+    }
+}
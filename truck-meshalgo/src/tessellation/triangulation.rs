@@ -3,21 +3,56 @@
 use super::*;
 use crate::filters::NormalFilters;
 use rustc_hash::FxHashMap as HashMap;
+use truck_base::polygon::signed_area;
 
 type Cdt<V, K> = ConstrainedDelaunayTriangulation<V, K>;
 type MeshedShell = Shell<Point3, PolylineCurve, Option<PolygonMesh>>;
 type MeshedCShell = CompressedShell<Point3, PolylineCurve, Option<PolygonMesh>>;
+/// the parameter and the point of the curve, and the parameters of the adjacent surfaces
+type EdgeSample = (f64, Point3, Vec<Option<Point2>>);
 
-/// Tessellates faces
-pub(super) fn shell_tessellation<'a, C, S>(shell: &Shell<Point3, C, S>, tol: f64) -> MeshedShell
+/// Tessellates faces by `tol`, or by the tolerances overridden by the indices of the faces.
+/// The edges are divided by the minimum tolerance of the adjacent faces.
+pub(super) fn shell_tessellation<'a, C, S>(
+    shell: &Shell<Point3, C, S>,
+    tol: f64,
+    face_tolerance: impl Fn(usize) -> Option<f64>,
+) -> MeshedShell
 where
     C: PolylineableCurve + 'a,
-    S: MeshableSurface + 'a, {
+    S: MeshableSurface + 'a,
+{
+    let tolerances: Vec<f64> = (0..shell.len())
+        .map(|i| face_tolerance(i).unwrap_or(tol))
+        .collect();
+    let surfaces: Vec<S> = shell.face_iter().map(Face::get_surface).collect();
+    // the minimum tolerances of the adjacent faces, and the indices of the faces
+    let mut edge_faces = HashMap::<EdgeID<C>, (f64, Vec<usize>)>::default();
+    shell
+        .face_iter()
+        .zip(&tolerances)
+        .enumerate()
+        .for_each(|(i, (face, tol))| {
+            face.boundary_iters()
+                .into_iter()
+                .flatten()
+                .for_each(|edge| {
+                    let (edge_tol, faces) = edge_faces
+                        .entry(edge.id())
+                        .or_insert_with(|| (*tol, Vec::new()));
+                    *edge_tol = f64::min(*edge_tol, *tol);
+                    if !faces.contains(&i) {
+                        faces.push(i);
+                    }
+                })
+        });
     let mut vmap = HashMap::default();
     let mut edge_map = HashMap::default();
     shell
         .face_iter()
-        .map(|face| {
+        .zip(tolerances)
+        .zip(&surfaces)
+        .map(|((face, tol), surface)| {
             let wires: Vec<_> = face
                 .absolute_boundaries()
                 .iter()
@@ -35,9 +70,10 @@ where
                                     .entry(vb.id())
                                     .or_insert_with(|| vb.mapped(Point3::clone))
                                     .clone();
-                                let curve = edge.get_curve();
-                                let poly =
-                                    PolylineCurve::from_curve(&curve, curve.parameter_range(), tol);
+                                let (tol, faces) = &edge_faces[&edge.id()];
+                                let adjacents: Vec<&S> =
+                                    faces.iter().map(|i| &surfaces[*i]).collect();
+                                let poly = edge_polyline(&edge.get_curve(), &adjacents, *tol);
                                 Edge::debug_new(&v0, &v1, poly)
                             });
                             match edge.orientation() {
@@ -48,12 +84,11 @@ where
                         .collect()
                 })
                 .collect();
-            let surface = face.get_surface();
             let mut polyline = Polyline::default();
             let polygon = match wires.iter().all(|wire: &Wire<_, _>| {
-                polyline.add_wire(&surface, wire.iter().map(|edge| edge.oriented_curve()))
+                polyline.add_wire(surface, wire.iter().map(|edge| edge.oriented_curve()))
             }) {
-                true => Some(trimming_tessellation(&surface, &polyline, tol)),
+                true => Some(trimming_tessellation(surface, &polyline, tol)),
                 false => None,
             };
             let mut new_face = Face::debug_new(wires, polygon);
@@ -65,31 +100,61 @@ where
         .collect()
 }
 
-/// Tessellates faces
+/// Tessellates faces by `tol`, or by the tolerances overridden by the indices of the faces.
+/// The edges are divided by the minimum tolerance of the adjacent faces.
 pub(super) fn cshell_tessellation<'a, C, S>(
     shell: &CompressedShell<Point3, C, S>,
     tol: f64,
+    face_tolerance: impl Fn(usize) -> Option<f64>,
 ) -> MeshedCShell
 where
     C: PolylineableCurve + 'a,
     S: MeshableSurface + 'a,
 {
+    let tolerances: Vec<f64> = (0..shell.faces.len())
+        .map(|i| face_tolerance(i).unwrap_or(tol))
+        .collect();
+    // The edges not on any faces are divided by `tol`.
+    let mut edge_tolerances = vec![tol; shell.edges.len()];
+    let mut edge_faces = vec![Vec::new(); shell.edges.len()];
+    shell
+        .faces
+        .iter()
+        .zip(&tolerances)
+        .enumerate()
+        .for_each(|(i, (face, tol))| {
+            face.boundaries.iter().flatten().for_each(|edge_idx| {
+                if let Some(edge_tol) = edge_tolerances.get_mut(edge_idx.index) {
+                    let faces = &mut edge_faces[edge_idx.index];
+                    *edge_tol = match faces.is_empty() {
+                        true => *tol,
+                        false => f64::min(*edge_tol, *tol),
+                    };
+                    if !faces.contains(&i) {
+                        faces.push(i);
+                    }
+                }
+            })
+        });
     let vertices = shell.vertices.clone();
     let edges: Vec<_> = shell
         .edges
         .iter()
-        .map(|edge| {
-            let curve = &edge.curve;
+        .zip(edge_tolerances)
+        .zip(edge_faces)
+        .map(|((edge, tol), faces)| {
+            let adjacents: Vec<&S> = faces.iter().map(|i| &shell.faces[*i].surface).collect();
             CompressedEdge {
                 vertices: edge.vertices,
-                curve: PolylineCurve::from_curve(curve, curve.parameter_range(), tol),
+                curve: edge_polyline(&edge.curve, &adjacents, tol),
             }
         })
         .collect();
     let faces = shell
         .faces
         .iter()
-        .map(|face| {
+        .zip(tolerances)
+        .map(|(face, tol)| {
             let boundaries = face.boundaries.clone();
             let surface = &face.surface;
             let mut polyline = Polyline::default();
@@ -119,17 +184,169 @@ where
     }
 }
 
+/// the maximum depth of the refinement of the divisions of edges by the adjacent surfaces
+const EDGE_REFINEMENT_DEPTH: usize = 8;
+
+/// Divides the curve of an edge by `tol`, and refines the division until every chord is also
+/// within `tol` from the adjacent surfaces at the middle of the segment between the parameters
+/// of its ends.
+/// The triangles of the adjacent faces are spanned by the chords, so the deviation of the faces
+/// near the boundary is bounded, not only that of the curve.
+pub(super) fn edge_polyline<C, S>(curve: &C, surfaces: &[&S], tol: f64) -> PolylineCurve
+where
+    C: PolylineableCurve,
+    S: MeshableSurface, {
+    let (params, pts) = curve.parameter_division(curve.parameter_range(), tol);
+    let surfaces: Vec<&S> = surfaces
+        .iter()
+        .filter(|surface| !is_straight_on(curve, **surface, &params, &pts))
+        .copied()
+        .collect();
+    if surfaces.is_empty() {
+        return PolylineCurve(pts);
+    }
+    let surfaces = surfaces.as_slice();
+    let mut hints = vec![None; surfaces.len()];
+    let samples: Vec<EdgeSample> = params
+        .into_iter()
+        .zip(pts)
+        .map(|(t, pt)| {
+            let uvs = surfaces
+                .iter()
+                .zip(&mut hints)
+                .map(|(surface, hint)| {
+                    let uv = regular_parameter(*surface, pt, *hint);
+                    *hint = uv.map(Into::into).or(*hint);
+                    uv
+                })
+                .collect();
+            (t, pt, uvs)
+        })
+        .collect();
+    let mut res = vec![samples[0].1];
+    samples.windows(2).for_each(|pair| {
+        refine_edge_segment(
+            curve,
+            surfaces,
+            (&pair[0], &pair[1]),
+            tol,
+            EDGE_REFINEMENT_DEPTH,
+            &mut res,
+        )
+    });
+    PolylineCurve(res)
+}
+
+/// Whether the curve lies on `surface` exactly along the segment between the parameters of its
+/// ends, e.g. lines on planes, checked at the points and the middles of the division.
+/// The chords of such curves are as near to the surface as to the curve, so the parameters of
+/// the points are not searched for the refinement.
+fn is_straight_on<C, S>(curve: &C, surface: &S, params: &[f64], pts: &[Point3]) -> bool
+where
+    C: PolylineableCurve,
+    S: MeshableSurface, {
+    let (t0, t1) = (params[0], params[params.len() - 1]);
+    let uv0 = match regular_parameter(surface, pts[0], None) {
+        Some(uv0) => uv0,
+        None => return false,
+    };
+    let uv1 = match regular_parameter(surface, pts[pts.len() - 1], Some(uv0.into())) {
+        Some(uv1) => uv1,
+        None => return false,
+    };
+    let on_surface = |t: f64, pt: Point3| {
+        let uv = uv0 + (uv1 - uv0) * ((t - t0) / (t1 - t0));
+        surface.subs(uv.x, uv.y).near(&pt)
+    };
+    !(t1 - t0).so_small()
+        && params.iter().zip(pts).all(|(t, pt)| on_surface(*t, *pt))
+        && params.windows(2).all(|pair| {
+            let t = (pair[0] + pair[1]) / 2.0;
+            on_surface(t, curve.subs(t))
+        })
+}
+
+/// Pushes the points of the refined segment except the start point.
+fn refine_edge_segment<C, S>(
+    curve: &C,
+    surfaces: &[&S],
+    (start, end): (&EdgeSample, &EdgeSample),
+    tol: f64,
+    depth: usize,
+    res: &mut Vec<Point3>,
+) where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+{
+    let chord_len = start.1.distance(end.1);
+    // The deviation more than the chord is caused by the seams or the singular points.
+    let needs_refine = depth > 0
+        && surfaces
+            .iter()
+            .zip(&start.2)
+            .zip(&end.2)
+            .any(|((surface, uv0), uv1)| match (uv0, uv1) {
+                (Some(uv0), Some(uv1)) => {
+                    let mid = uv0.midpoint(*uv1);
+                    let dist = segment_distance(surface.subs(mid.x, mid.y), start.1, end.1);
+                    tol < dist && dist < chord_len
+                }
+                _ => false,
+            });
+    if !needs_refine {
+        res.push(end.1);
+        return;
+    }
+    let t = (start.0 + end.0) / 2.0;
+    let pt = curve.subs(t);
+    let uvs = surfaces
+        .iter()
+        .zip(&start.2)
+        .zip(&end.2)
+        .map(|((surface, uv0), uv1)| {
+            let hint = uv0.zip(*uv1).map(|(uv0, uv1)| uv0.midpoint(uv1).into());
+            regular_parameter(*surface, pt, hint)
+        })
+        .collect();
+    let mid = (t, pt, uvs);
+    refine_edge_segment(curve, surfaces, (start, &mid), tol, depth - 1, res);
+    refine_edge_segment(curve, surfaces, (&mid, end), tol, depth - 1, res);
+}
+
+/// Searches the parameter of `pt` on `surface`, except for the singular points, e.g. the apexes
+/// of cones, at which the parameters are not determined by the points.
+fn regular_parameter<S: MeshableSurface>(
+    surface: &S,
+    pt: Point3,
+    hint: Option<(f64, f64)>,
+) -> Option<Point2> {
+    let (u, v) = surface
+        .search_parameter(pt, hint, 100)
+        .or_else(|| surface.search_parameter(pt, None, 100))?;
+    let normal = surface.uder(u, v).cross(surface.vder(u, v));
+    match normal.so_small() {
+        true => None,
+        false => Some(Point2::new(u, v)),
+    }
+}
+
 /// polyline, not always connected
 #[derive(Debug, Default, Clone)]
-struct Polyline {
-    positions: Vec<Point2>,
+pub(super) struct Polyline {
+    pub(super) positions: Vec<Point2>,
     indices: Vec<[usize; 2]>,
 }
 
 impl Polyline {
     /// add an wire into polyline
-    fn add_wire<S>(&mut self, surface: &S, mut wire: impl Iterator<Item = PolylineCurve>) -> bool
-    where S: MeshableSurface {
+    pub(super) fn add_wire<S>(
+        &mut self,
+        surface: &S,
+        mut wire: impl Iterator<Item = PolylineCurve>,
+    ) -> bool
+    where
+        S: MeshableSurface,
+    {
         let mut counter = 0;
         let len = self.positions.len();
         let res = wire.all(|mut poly_edge| {
@@ -149,7 +366,7 @@ impl Polyline {
     }
 
     /// whether `c` is included in the domain with boundary = `self`.
-    fn include(&self, c: Point2) -> bool {
+    pub(super) fn include(&self, c: Point2) -> bool {
         let t = 2.0 * std::f64::consts::PI * HashGen::hash1(c);
         let r = Vector2::new(f64::cos(t), f64::sin(t));
         self.indices
@@ -175,14 +392,29 @@ impl Polyline {
             .unwrap_or(false)
     }
 
+    /// the closed loops by the indices of `positions`
+    fn loops(&self) -> Vec<Vec<usize>> {
+        let mut loops = Vec::<Vec<usize>>::new();
+        let mut current = Vec::new();
+        self.indices.iter().for_each(|a| {
+            current.push(a[0]);
+            if a[1] == current[0] {
+                loops.push(std::mem::take(&mut current));
+            }
+        });
+        loops
+    }
+
     /// Inserts points and adds constraint into triangulation.
-    fn insert_to(&self, triangulation: &mut Cdt<[f64; 2], impl DelaunayKernel<f64>>) {
+    /// Returns `false` if some boundary edges cannot be constrained.
+    fn insert_to(&self, triangulation: &mut Cdt<[f64; 2], impl DelaunayKernel<f64>>) -> bool {
         let poly2tri: Vec<usize> = self
             .positions
             .iter()
             .map(|pt| triangulation.insert((*pt).into()))
             .collect();
         let mut prev: Option<usize> = None;
+        let mut constrained = true;
         self.indices.iter().for_each(|a| {
             if let Some(p) = prev {
                 if triangulation.can_add_constraint(poly2tri[p], poly2tri[a[1]]) {
@@ -192,29 +424,90 @@ impl Polyline {
             } else if triangulation.can_add_constraint(poly2tri[a[0]], poly2tri[a[1]]) {
                 triangulation.add_constraint(poly2tri[a[0]], poly2tri[a[1]]);
             } else {
+                constrained = false;
                 prev = Some(a[0]);
             }
         });
+        constrained
     }
 }
 
 /// Tessellates one surface trimmed by polyline.
-fn trimming_tessellation<S>(surface: &S, polyline: &Polyline, tol: f64) -> PolygonMesh
+pub(super) fn trimming_tessellation<S>(surface: &S, polyline: &Polyline, tol: f64) -> PolygonMesh
 where S: MeshableSurface {
     let mut triangulation = Cdt::<[f64; 2], FloatKernel>::new();
-    polyline.insert_to(&mut triangulation);
-    insert_surface(&mut triangulation, surface, polyline, tol);
-    let mut mesh = triangulation_into_polymesh(
-        triangulation.vertices(),
-        triangulation.triangles(),
-        surface,
-        polyline,
-    );
+    let mut mesh = match polyline.insert_to(&mut triangulation) {
+        true => {
+            insert_surface(&mut triangulation, surface, polyline, tol);
+            triangulation_into_polymesh(
+                triangulation.vertices(),
+                triangulation.triangles(),
+                surface,
+                polyline,
+            )
+        }
+        // The constrained triangulation fails if the boundary touches itself on the domain.
+        false => ear_clipping_tessellation(surface, polyline),
+    };
     mesh.make_face_compatible_to_normal();
     mesh
 }
 
-/// Inserts parameter divisions into triangulation.
+/// Tessellates one surface trimmed by polyline only by the boundary points, by ear clipping.
+fn ear_clipping_tessellation(
+    surface: &impl ParametricSurface3D,
+    polyline: &Polyline,
+) -> PolygonMesh {
+    let mut loops = polyline.loops();
+    let to_points = |indices: &Vec<usize>| -> Vec<Point2> {
+        indices.iter().map(|i| polyline.positions[*i]).collect()
+    };
+    let area = |indices: &Vec<usize>| signed_area(&to_points(indices));
+    let outer_idx = (0..loops.len())
+        .max_by(|i, j| area(&loops[*i]).abs().total_cmp(&area(&loops[*j]).abs()))
+        .unwrap_or(0);
+    if loops.is_empty() {
+        return PolygonMesh::default();
+    }
+    let outer = loops.swap_remove(outer_idx);
+    let holes: Vec<Vec<Point2>> = loops.iter().map(to_points).collect();
+    let serial: Vec<usize> = outer
+        .iter()
+        .chain(loops.iter().flatten())
+        .copied()
+        .collect();
+    let tri_faces: Vec<[StandardVertex; 3]> =
+        truck_polymesh::triangulation::ear_clipping(&to_points(&outer), &holes)
+            .into_iter()
+            .map(|tri| tri.map(|i| [serial[i], serial[i], serial[i]].into()))
+            .collect();
+    let (positions, (uv_coords, normals)) = polyline
+        .positions
+        .iter()
+        .map(|uv| {
+            let (u, v) = (uv.x, uv.y);
+            (surface.subs(u, v), (uv.to_vec(), surface.normal(u, v)))
+        })
+        .unzip();
+    PolygonMesh::debug_new(
+        StandardAttributes {
+            positions,
+            uv_coords,
+            normals,
+        },
+        Faces::from_tri_and_quad_faces(tri_faces, Vec::new()),
+    )
+}
+
+/// The maximum depth of the parameter-space quadtree.
+const QUADTREE_MAX_DEPTH: u32 = 10;
+
+/// Inserts the corners of the adaptive parameter-space quadtree into triangulation.
+///
+/// The cells are divided, only in the directions in which the surface bends, until the surface
+/// is approximated by their corners within a half of `tol`, since the samples may miss
+/// the maximum deviation. The corners which are outside of the domain
+/// or too close to the boundary are not inserted, so that no slivers appear along the boundary.
 fn insert_surface(
     triangulation: &mut Cdt<[f64; 2], impl DelaunayKernel<f64>>,
     surface: &impl MeshableSurface,
@@ -222,16 +515,174 @@ fn insert_surface(
     tol: f64,
 ) {
     let bdb: BoundingBox<Point2> = polyline.positions.iter().collect();
-    let range = ((bdb.min()[0], bdb.max()[0]), (bdb.min()[1], bdb.max()[1]));
-    let (udiv, vdiv) = surface.parameter_division(range, tol);
-    udiv.into_iter()
-        .flat_map(|u| vdiv.iter().map(move |v| Point2::new(u, *v)))
-        .filter(|pt| polyline.include(*pt))
-        .for_each(|pt| {
-            triangulation.insert(pt.into());
+    let diag = bdb.diagonal();
+    if bdb.is_empty() || diag[0].so_small() || diag[1].so_small() {
+        return;
+    }
+    // The root cell is padded irregularly, so that the lattice points do not lie on
+    // the characteristic parameters, e.g. the midpoints of the domain.
+    let [s, t] = HashGen::hash2(*bdb.min());
+    let pad = Vector2::new(0.01 + 0.04 * s, 0.01 + 0.04 * t);
+    let quadtree = Quadtree {
+        surface,
+        polyline,
+        origin: bdb.min() - diag.mul_element_wise(pad),
+        diag: diag.mul_element_wise(Vector2::new(1.0, 1.0) + 2.0 * pad),
+        tol,
+    };
+    let segments: Vec<usize> = (0..polyline.indices.len()).collect();
+    let mut corners = HashMap::<[usize; 2], bool>::default();
+    quadtree.divide([0, 0], [0, 0], &segments, &mut corners);
+    corners
+        .into_iter()
+        .filter(|(_, keep)| *keep)
+        .for_each(|(key, _)| {
+            triangulation.insert(quadtree.point(key).into());
         });
 }
 
+/// Adaptive subdivision of the bounding box of the trimming loops.
+struct Quadtree<'a, S> {
+    surface: &'a S,
+    polyline: &'a Polyline,
+    origin: Point2,
+    diag: Vector2,
+    tol: f64,
+}
+
+impl<S: MeshableSurface> Quadtree<'_, S> {
+    /// the parameter of the lattice point `key` at the maximum depth
+    fn point(&self, key: [usize; 2]) -> Point2 {
+        let n = (1_usize << QUADTREE_MAX_DEPTH) as f64;
+        Point2::new(
+            self.origin[0] + self.diag[0] * key[0] as f64 / n,
+            self.origin[1] + self.diag[1] * key[1] as f64 / n,
+        )
+    }
+
+    /// Returns whether the cell should be divided in the u-direction and the v-direction.
+    fn division_directions(&self, p: Point2, q: Point2) -> [bool; 2] {
+        let subs = |s: f64, t: f64| {
+            self.surface
+                .subs(p[0] + (q[0] - p[0]) * s, p[1] + (q[1] - p[1]) * t)
+        };
+        const SAMPLES: [f64; 3] = [0.25, 0.5, 0.75];
+        const LINES: [f64; 3] = [0.0, 0.5, 1.0];
+        // whether the iso-parameter lines are approximated by their end points
+        let straight = |pt: &dyn Fn(f64, f64) -> Point3| {
+            LINES.iter().all(|&t| {
+                let (a, b) = (pt(0.0, t), pt(1.0, t));
+                SAMPLES
+                    .iter()
+                    .all(|&s| pt(s, t).distance(a + (b - a) * s) < self.tol / 2.0)
+            })
+        };
+        let udiv = !straight(&subs);
+        let vdiv = !straight(&|t, s| subs(s, t));
+        if udiv || vdiv {
+            return [udiv, vdiv];
+        }
+        // the twist of the cell
+        let c = [
+            subs(0.0, 0.0),
+            subs(1.0, 0.0),
+            subs(0.0, 1.0),
+            subs(1.0, 1.0),
+        ];
+        let bilinear = c[0].midpoint(c[1]).midpoint(c[2].midpoint(c[3]));
+        match subs(0.5, 0.5).distance(bilinear) < self.tol / 2.0 {
+            true => [false, false],
+            false => [true, true],
+        }
+    }
+
+    /// the boundary segments in `segments` whose bounding boxes meet the cell
+    fn segments_in_cell(&self, p: Point2, q: Point2, segments: &[usize]) -> Vec<usize> {
+        let positions = &self.polyline.positions;
+        segments
+            .iter()
+            .copied()
+            .filter(|&i| {
+                let [a, b] = self.polyline.indices[i].map(|j| positions[j]);
+                f64::min(a[0], b[0]) <= q[0]
+                    && f64::max(a[0], b[0]) >= p[0]
+                    && f64::min(a[1], b[1]) <= q[1]
+                    && f64::max(a[1], b[1]) >= p[1]
+            })
+            .collect()
+    }
+
+    /// Divides the cell `key` of `depth` recursively and registers the corners of the leaves.
+    fn divide(
+        &self,
+        key: [usize; 2],
+        depth: [u32; 2],
+        segments: &[usize],
+        corners: &mut HashMap<[usize; 2], bool>,
+    ) {
+        let width = depth.map(|d| 1_usize << (QUADTREE_MAX_DEPTH - d));
+        let (p, q) = (
+            self.point(key),
+            self.point([key[0] + width[0], key[1] + width[1]]),
+        );
+        let segments = self.segments_in_cell(p, q, segments);
+        if segments.is_empty() && !self.polyline.include(p.midpoint(q)) {
+            return;
+        }
+        let [udiv, vdiv] = self.division_directions(p, q);
+        let udiv = udiv && depth[0] < QUADTREE_MAX_DEPTH;
+        let vdiv = vdiv && depth[1] < QUADTREE_MAX_DEPTH;
+        if udiv || vdiv {
+            let half = [width[0] / 2 * udiv as usize, width[1] / 2 * vdiv as usize];
+            let depth = [depth[0] + udiv as u32, depth[1] + vdiv as u32];
+            let mut children = vec![key];
+            if udiv {
+                children.push([key[0] + half[0], key[1]]);
+            }
+            if vdiv {
+                children.push([key[0], key[1] + half[1]]);
+            }
+            if udiv && vdiv {
+                children.push([key[0] + half[0], key[1] + half[1]]);
+            }
+            children
+                .into_iter()
+                .for_each(|key| self.divide(key, depth, &segments, corners));
+            return;
+        }
+        let margin = f64::min(q[0] - p[0], q[1] - p[1]) / 4.0;
+        [[0, 0], [width[0], 0], [0, width[1]], [width[0], width[1]]]
+            .into_iter()
+            .for_each(|[i, j]| {
+                let corner = [key[0] + i, key[1] + j];
+                let pt = self.point(corner);
+                let keep = segments.is_empty() || {
+                    let positions = &self.polyline.positions;
+                    let far = segments.iter().all(|&i| {
+                        let [a, b] = self.polyline.indices[i].map(|j| positions[j]);
+                        segment_distance(pt, a, b) > margin
+                    });
+                    far && self.polyline.include(pt)
+                };
+                *corners.entry(corner).or_insert(true) &= keep;
+            });
+    }
+}
+
+/// the distance between the point `c` and the segment `ab`
+fn segment_distance<P>(c: P, a: P, b: P) -> f64
+where
+    P: EuclideanSpace<Scalar = f64>,
+    P::Diff: InnerSpace<Scalar = f64>, {
+    let ab = b - a;
+    let len2 = ab.magnitude2();
+    let t = match len2.so_small2() {
+        true => 0.0,
+        false => f64::clamp((c - a).dot(ab) / len2, 0.0, 1.0),
+    };
+    (c - (a + ab * t)).magnitude()
+}
+
 /// Converts triangulation into `PolygonMesh`.
 fn triangulation_into_polymesh<'a>(
     vertices: impl Iterator<Item = VertexHandle<'a, [f64; 2], CdtEdge>>,
@@ -283,3 +734,107 @@ fn triangulation_into_polymesh<'a>(
         Faces::from_tri_and_quad_faces(tri_faces, Vec::new()),
     )
 }
+
+#[test]
+fn ear_clipping_with_hole() {
+    let plane = truck_modeling::Plane::new(
+        Point3::origin(),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    );
+    let square = |min: f64, max: f64| {
+        PolylineCurve(vec![
+            Point3::new(min, min, 0.0),
+            Point3::new(max, min, 0.0),
+            Point3::new(max, max, 0.0),
+            Point3::new(min, max, 0.0),
+            Point3::new(min, min, 0.0),
+        ])
+    };
+    let mut polyline = Polyline::default();
+    assert!(polyline.add_wire(&plane, std::iter::once(square(0.0, 3.0))));
+    assert!(polyline.add_wire(&plane, std::iter::once(square(1.0, 2.0).inverse())));
+    assert_eq!(polyline.loops(), vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]);
+
+    let mesh = ear_clipping_tessellation(&plane, &polyline);
+    assert_eq!(mesh.tri_faces().len(), 8);
+    let area: f64 = mesh
+        .tri_faces()
+        .iter()
+        .map(|tri| {
+            let [a, b, c] = tri.map(|v| mesh.positions()[v.pos]);
+            (b - a).cross(c - a).z / 2.0
+        })
+        .sum();
+    assert!(area.near(&8.0));
+}
+
+#[test]
+fn quadtree_trimmed_sphere() {
+    let sphere = truck_modeling::Processor::<_, Matrix4>::new(truck_modeling::Sphere::new(
+        Point3::origin(),
+        1.0,
+    ));
+    let (center, radius) = (Point2::new(1.2, 1.0), 0.6);
+    const N: usize = 64;
+    let polyline = Polyline {
+        positions: (0..N)
+            .map(|i| {
+                let t = 2.0 * std::f64::consts::PI * i as f64 / N as f64;
+                center + radius * Vector2::new(f64::cos(t), f64::sin(t))
+            })
+            .collect(),
+        indices: (0..N).map(|i| [i, (i + 1) % N]).collect(),
+    };
+    let tol = 0.02;
+    let mesh = trimming_tessellation(&sphere, &polyline, tol);
+    assert!(mesh.positions().len() > N);
+    mesh.uv_coords().iter().for_each(|uv| {
+        assert!(uv.distance(center.to_vec()) < radius + TOLERANCE);
+    });
+    mesh.tri_faces().iter().for_each(|tri| {
+        let [a, b, c] = tri.map(|v| mesh.positions()[v.pos].to_vec());
+        let centroid = (a + b + c) / 3.0;
+        assert!(1.0 - centroid.magnitude() < tol, "{centroid:?}");
+    });
+}
+
+#[test]
+fn edge_refined_by_surfaces() {
+    use truck_modeling::*;
+    // the plane z = 0 whose parameter u is not proportional to x.
+    let surface = BSplineSurface::new(
+        (KnotVec::bezier_knot(2), KnotVec::bezier_knot(1)),
+        vec![
+            vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+            vec![Point3::new(0.1, 0.0, 0.0), Point3::new(0.1, 1.0, 0.0)],
+            vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+        ],
+    );
+    let v =
+        [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)].map(|(x, y)| builder::vertex(Point3::new(x, y, 0.0)));
+    let wire: Wire = vec![
+        builder::line(&v[0], &v[1]),
+        builder::line(&v[1], &v[2]),
+        builder::line(&v[2], &v[0]),
+    ]
+    .into();
+    let face = Face::new(vec![wire], Surface::BSplineSurface(surface));
+    let shell: Shell = vec![face].into();
+    let tol = 0.01;
+    let meshed = shell.triangulation(tol);
+    let boundary = &meshed[0].boundaries()[0];
+    // the lines on the parameter lines are not divided.
+    assert_eq!(boundary[0].get_curve().len(), 2);
+    assert_eq!(boundary[1].get_curve().len(), 2);
+    // the diagonal is divided since its preimage is curved.
+    let diagonal = boundary[2].get_curve();
+    assert!(diagonal.len() > 2);
+    diagonal.iter().for_each(|p| assert_near!(p.x, p.y));
+    let mesh = meshed[0].get_surface().unwrap();
+    mesh.tri_faces().iter().for_each(|tri| {
+        let [a, b, c] = tri.map(|v| mesh.positions()[v.pos]);
+        let centroid = Point3::from_vec((a.to_vec() + b.to_vec() + c.to_vec()) / 3.0);
+        assert!(centroid.y <= centroid.x + TOLERANCE, "{centroid:?}");
+    });
+}
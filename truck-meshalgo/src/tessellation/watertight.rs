@@ -0,0 +1,258 @@
+use super::*;
+use crate::analyzers::Topology;
+use rustc_hash::FxHashMap as HashMap;
+
+type MeshedCShell = CompressedShell<Point3, PolylineCurve, Option<PolygonMesh>>;
+
+/// The certificate of the watertightness of the mesh by [`WatertightShape::to_watertight_polygon`].
+///
+/// The mesh is closed and consistently oriented if and only if every edge of the mesh is
+/// shared by exactly two faces traversing it in the opposite directions, i.e. all the lists of
+/// the defective edges are empty. The edges are the pairs of the indices of positions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WatertightReport {
+    /// whether all the source shells are closed
+    pub source_closed: bool,
+    /// the number of the faces of the source shells which are not meshed
+    pub unmeshed_faces: usize,
+    /// the number of the positions on the boundaries of the faces moved onto the polylines of the edges
+    pub snapped_positions: usize,
+    /// the number of the faces removed since they collapse by the snapping
+    pub collapsed_faces: usize,
+    /// the edges shared by only one face
+    pub boundary_edges: Vec<[usize; 2]>,
+    /// the edges shared by three or more faces
+    pub non_manifold_edges: Vec<[usize; 2]>,
+    /// the edges shared by two faces traversing it in the same direction
+    pub inconsistent_edges: Vec<[usize; 2]>,
+}
+
+impl WatertightReport {
+    /// Returns whether the mesh is closed and consistently oriented.
+    #[inline(always)]
+    pub fn is_watertight(&self) -> bool {
+        self.boundary_edges.is_empty()
+            && self.non_manifold_edges.is_empty()
+            && self.inconsistent_edges.is_empty()
+    }
+
+    /// Verifies the edges of `mesh`, and sets the lists of the defective edges.
+    fn verify(&mut self, mesh: &PolygonMesh) {
+        let mut edges = HashMap::<[usize; 2], (usize, usize)>::default();
+        mesh.face_iter().for_each(|face| {
+            let len = face.len();
+            (0..len).for_each(|i| {
+                let (a, b) = (face[i].pos, face[(i + 1) % len].pos);
+                let entry = edges
+                    .entry([usize::min(a, b), usize::max(a, b)])
+                    .or_default();
+                match a < b {
+                    true => entry.0 += 1,
+                    false => entry.1 += 1,
+                }
+            });
+        });
+        let mut edges: Vec<_> = edges.into_iter().collect();
+        edges.sort();
+        edges.into_iter().for_each(|(edge, counts)| match counts {
+            (1, 1) => {}
+            (0, 1) | (1, 0) => self.boundary_edges.push(edge),
+            (0, 2) | (2, 0) => self.inconsistent_edges.push(edge),
+            _ => self.non_manifold_edges.push(edge),
+        });
+    }
+}
+
+/// Converts the tessellated shapes into the watertight meshes.
+pub trait WatertightShape {
+    /// Converts the tessellated shape into the polygon mesh, repairing the seams between faces,
+    /// and returns the mesh with the certificate of its watertightness.
+    ///
+    /// The positions on the boundaries of the meshes of faces are snapped to the points of the
+    /// polylines of the edges shared by the adjacent faces, so the meshes are sewn without the
+    /// welding by distances. If the source shell is closed and all the faces are meshed, the
+    /// returned mesh is closed and consistently oriented, which is verified edge by edge.
+    /// The positions of the different boundary shells of solids are not shared.
+    ///
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// // the cylinder, whose lateral faces are meshed coarsely
+    /// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    /// let disk = builder::try_attach_plane(&[circle]).unwrap();
+    /// let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+    ///
+    /// let (mesh, report) = cylinder.triangulation(0.1).to_watertight_polygon();
+    /// assert!(report.source_closed);
+    /// assert!(report.is_watertight());
+    /// assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    ///
+    /// // the open shell is not certified.
+    /// let mut shell = cylinder.into_boundaries().pop().unwrap();
+    /// shell.pop();
+    /// let (_, report) = shell.triangulation(0.1).to_watertight_polygon();
+    /// assert!(!report.source_closed);
+    /// assert!(!report.is_watertight());
+    /// ```
+    fn to_watertight_polygon(&self) -> (PolygonMesh, WatertightReport);
+}
+
+impl WatertightShape for MeshedCShell {
+    fn to_watertight_polygon(&self) -> (PolygonMesh, WatertightReport) {
+        let mut report = WatertightReport {
+            source_closed: true,
+            ..Default::default()
+        };
+        let mut mesh = PolygonMesh::default();
+        watertight_cshell(self, &mut mesh, &mut report);
+        report.verify(&mesh);
+        (mesh, report)
+    }
+}
+
+impl WatertightShape for Shell<Point3, PolylineCurve, Option<PolygonMesh>> {
+    #[inline(always)]
+    fn to_watertight_polygon(&self) -> (PolygonMesh, WatertightReport) {
+        self.compress().to_watertight_polygon()
+    }
+}
+
+impl WatertightShape for CompressedSolid<Point3, PolylineCurve, Option<PolygonMesh>> {
+    fn to_watertight_polygon(&self) -> (PolygonMesh, WatertightReport) {
+        let mut report = WatertightReport {
+            source_closed: true,
+            ..Default::default()
+        };
+        let mut mesh = PolygonMesh::default();
+        self.boundaries
+            .iter()
+            .for_each(|shell| watertight_cshell(shell, &mut mesh, &mut report));
+        report.verify(&mesh);
+        (mesh, report)
+    }
+}
+
+impl WatertightShape for Solid<Point3, PolylineCurve, Option<PolygonMesh>> {
+    #[inline(always)]
+    fn to_watertight_polygon(&self) -> (PolygonMesh, WatertightReport) {
+        self.compress().to_watertight_polygon()
+    }
+}
+
+/// Whether every edge is used once in each direction by the oriented faces.
+fn is_closed(shell: &MeshedCShell) -> bool {
+    let mut counts = vec![(0, 0); shell.edges.len()];
+    shell.faces.iter().for_each(|face| {
+        face.boundaries.iter().flatten().for_each(|edge| {
+            match edge.orientation == face.orientation {
+                true => counts[edge.index].0 += 1,
+                false => counts[edge.index].1 += 1,
+            }
+        })
+    });
+    counts.into_iter().all(|counts| counts == (1, 1))
+}
+
+/// Appends the mesh of `shell` to `mesh`, snapping the boundaries of faces to the polylines.
+fn watertight_cshell(shell: &MeshedCShell, mesh: &mut PolygonMesh, report: &mut WatertightReport) {
+    report.source_closed &= is_closed(shell);
+    let mut editor = mesh.debug_editor();
+    let PolygonMeshEditor {
+        attributes:
+            StandardAttributes {
+                positions,
+                uv_coords,
+                normals,
+            },
+        faces,
+        ..
+    } = &mut editor;
+
+    // the indices of the points of the polylines: the vertices, and the inner points of edges
+    let vertex_offset = positions.len();
+    positions.extend(&shell.vertices);
+    let edge_points: Vec<Vec<usize>> = shell
+        .edges
+        .iter()
+        .map(|edge| {
+            let len = edge.curve.len();
+            let mut indices = vec![vertex_offset + edge.vertices.0];
+            (1..len.saturating_sub(1)).for_each(|i| {
+                indices.push(positions.len());
+                positions.push(edge.curve[i]);
+            });
+            indices.push(vertex_offset + edge.vertices.1);
+            indices
+        })
+        .collect();
+
+    shell.faces.iter().for_each(|face| {
+        let poly = match (&face.surface, face.orientation) {
+            (None, _) => {
+                report.unmeshed_faces += 1;
+                return;
+            }
+            (Some(poly), true) => poly.clone(),
+            (Some(poly), false) => poly.inverse(),
+        };
+        let boundary_points: Vec<usize> = face
+            .boundaries
+            .iter()
+            .flatten()
+            .flat_map(|edge| edge_points[edge.index].iter().copied())
+            .collect();
+        let mut pos_map: Vec<Option<usize>> = vec![None; poly.positions().len()];
+        poly.extract_boundaries()
+            .into_iter()
+            .flatten()
+            .for_each(|i| {
+                let pt = poly.positions()[i];
+                let nearest = boundary_points
+                    .iter()
+                    .min_by(|a, b| {
+                        let da = positions[**a].distance2(pt);
+                        let db = positions[**b].distance2(pt);
+                        da.total_cmp(&db)
+                    })
+                    .copied();
+                if let Some(j) = nearest {
+                    if !positions[j].near(&pt) {
+                        report.snapped_positions += 1;
+                    }
+                    pos_map[i] = Some(j);
+                }
+            });
+        let pos_map: Vec<usize> = pos_map
+            .into_iter()
+            .zip(poly.positions())
+            .map(|(idx, pt)| {
+                idx.unwrap_or_else(|| {
+                    positions.push(*pt);
+                    positions.len() - 1
+                })
+            })
+            .collect();
+        let (uv_offset, nor_offset) = (uv_coords.len(), normals.len());
+        uv_coords.extend(poly.uv_coords());
+        normals.extend(poly.normals());
+        poly.face_iter().for_each(|poly_face| {
+            let new_face: Vec<StandardVertex> = poly_face
+                .iter()
+                .map(|v| StandardVertex {
+                    pos: pos_map[v.pos],
+                    uv: v.uv.map(|uv| uv + uv_offset),
+                    nor: v.nor.map(|nor| nor + nor_offset),
+                })
+                .collect();
+            let len = new_face.len();
+            let collapsed = (0..len).any(|i| new_face[i].pos == new_face[(i + 1) % len].pos);
+            match collapsed {
+                true => report.collapsed_faces += 1,
+                false => faces.push(new_face),
+            }
+        });
+    });
+}
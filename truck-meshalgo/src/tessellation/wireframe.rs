@@ -0,0 +1,204 @@
+use super::triangulation::Polyline;
+use super::*;
+use rustc_hash::FxHashSet as HashSet;
+
+/// Extracts the wireframes of shapes for rendering the edges over the shaded meshes.
+pub trait WireframeExtraction {
+    /// Returns the polylines of all edges. Each edge is converted to a polyline only once
+    /// even if it is shared by several faces.
+    /// # Panics
+    /// `tol` must be more than `TOLERANCE`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    ///
+    /// // modeling a unit cube
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    ///
+    /// let wireframe = cube.extract_wireframe(0.01);
+    /// assert_eq!(wireframe.len(), 12);
+    /// assert!(wireframe.iter().all(|poly| poly.len() == 2));
+    /// ```
+    fn extract_wireframe(&self, tol: f64) -> Vec<PolylineCurve> {
+        self.extract_wireframe_with_iso_lines(tol, (0, 0))
+    }
+    /// Returns the polylines of all edges and the iso-parameter lines of faces.
+    /// # Details
+    /// The iso-parameter lines divide the range of the parameters of each face into
+    /// `iso_lines.0 + 1` and `iso_lines.1 + 1` equal parts in the u- and v-directions,
+    /// respectively. The lines are trimmed by the boundaries of the face.
+    /// The iso-parameter lines of the faces whose boundaries cannot be projected
+    /// to the surfaces are skipped.
+    /// # Panics
+    /// `tol` must be more than `TOLERANCE`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    ///
+    /// // modeling a unit cube
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    ///
+    /// // 12 edges and 6 faces with 3 lines for each
+    /// let wireframe = cube.extract_wireframe_with_iso_lines(0.01, (1, 2));
+    /// assert_eq!(wireframe.len(), 12 + 6 * 3);
+    /// ```
+    fn extract_wireframe_with_iso_lines(
+        &self,
+        tol: f64,
+        iso_lines: (usize, usize),
+    ) -> Vec<PolylineCurve>;
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> WireframeExtraction for Shell<Point3, C, S> {
+    fn extract_wireframe_with_iso_lines(
+        &self,
+        tol: f64,
+        iso_lines: (usize, usize),
+    ) -> Vec<PolylineCurve> {
+        nonpositive_tolerance!(tol);
+        faces_wireframe(self.face_iter(), tol, iso_lines)
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> WireframeExtraction for Solid<Point3, C, S> {
+    fn extract_wireframe_with_iso_lines(
+        &self,
+        tol: f64,
+        iso_lines: (usize, usize),
+    ) -> Vec<PolylineCurve> {
+        nonpositive_tolerance!(tol);
+        faces_wireframe(self.face_iter(), tol, iso_lines)
+    }
+}
+
+fn faces_wireframe<'a, C, S>(
+    faces: impl Iterator<Item = &'a Face<Point3, C, S>>,
+    tol: f64,
+    iso_lines: (usize, usize),
+) -> Vec<PolylineCurve>
+where
+    C: PolylineableCurve + 'a,
+    S: MeshableSurface + 'a,
+{
+    let mut edge_set = HashSet::default();
+    let mut res = Vec::new();
+    faces.for_each(|face| {
+        let boundaries = face.absolute_boundaries();
+        boundaries.iter().flatten().for_each(|edge| {
+            if edge_set.insert(edge.id()) {
+                let curve = edge.get_curve();
+                res.push(PolylineCurve::from_curve(
+                    &curve,
+                    curve.parameter_range(),
+                    tol,
+                ));
+            }
+        });
+        if iso_lines != (0, 0) {
+            let surface = face.get_surface();
+            let mut polyline = Polyline::default();
+            let projected = boundaries.iter().all(|wire| {
+                let wire = wire.edge_iter().map(|edge| {
+                    let curve = edge.oriented_curve();
+                    PolylineCurve::from_curve(&curve, curve.parameter_range(), tol)
+                });
+                polyline.add_wire(&surface, wire)
+            });
+            if projected {
+                res.extend(face_iso_lines(&surface, &polyline, tol, iso_lines));
+            }
+        }
+    });
+    res
+}
+
+/// the iso-parameter lines of the surface trimmed by the boundary polyline
+fn face_iso_lines<S: MeshableSurface>(
+    surface: &S,
+    polyline: &Polyline,
+    tol: f64,
+    (u_lines, v_lines): (usize, usize),
+) -> Vec<PolylineCurve> {
+    let bdb: BoundingBox<Point2> = polyline.positions.iter().collect();
+    let range = ((bdb.min()[0], bdb.max()[0]), (bdb.min()[1], bdb.max()[1]));
+    let (udiv, vdiv) = surface.parameter_division(range, tol);
+    let (udiv, vdiv) = (refine(&udiv), refine(&vdiv));
+    let mut res = Vec::new();
+    (1..=u_lines).for_each(|i| {
+        let u = range.0 .0 + (range.0 .1 - range.0 .0) * i as f64 / (u_lines + 1) as f64;
+        let line = vdiv.iter().map(|v| Point2::new(u, *v)).collect::<Vec<_>>();
+        res.extend(trimmed_lines(surface, polyline, &line));
+    });
+    (1..=v_lines).for_each(|j| {
+        let v = range.1 .0 + (range.1 .1 - range.1 .0) * j as f64 / (v_lines + 1) as f64;
+        let line = udiv.iter().map(|u| Point2::new(*u, v)).collect::<Vec<_>>();
+        res.extend(trimmed_lines(surface, polyline, &line));
+    });
+    res
+}
+
+/// Divides the intervals so that there are at least 16 intervals,
+/// since the trimmed lines are detected at the division points.
+fn refine(division: &[f64]) -> Vec<f64> {
+    let n = 16 / usize::max(division.len(), 2) + 1;
+    let mut res: Vec<f64> = division
+        .windows(2)
+        .flat_map(|a| (0..n).map(move |k| a[0] + (a[1] - a[0]) * k as f64 / n as f64))
+        .collect();
+    res.extend(division.last());
+    res
+}
+
+/// Splits the line in the parameter space into the parts in the domain,
+/// and maps them onto the surface.
+fn trimmed_lines<S: MeshableSurface>(
+    surface: &S,
+    polyline: &Polyline,
+    line: &[Point2],
+) -> Vec<PolylineCurve> {
+    // the boundary point between `inner` and `outer` by bisection
+    let boundary_point = |mut inner: Point2, mut outer: Point2| {
+        (0..20).for_each(|_| {
+            let mid = inner.midpoint(outer);
+            match polyline.include(mid) {
+                true => inner = mid,
+                false => outer = mid,
+            }
+        });
+        inner
+    };
+    let mut res = Vec::new();
+    let mut current = Vec::new();
+    let mut prev: Option<(Point2, bool)> = None;
+    line.iter().for_each(|pt| {
+        let include = polyline.include(*pt);
+        match (prev, include) {
+            (Some((p, false)), true) => current.push(boundary_point(*pt, p)),
+            (Some((p, true)), false) => {
+                current.push(boundary_point(p, *pt));
+                res.push(std::mem::take(&mut current));
+            }
+            _ => {}
+        }
+        if include {
+            current.push(*pt);
+        }
+        prev = Some((*pt, include));
+    });
+    res.push(current);
+    res.into_iter()
+        .filter(|line| line.len() > 1)
+        .map(|line| {
+            let points = line.into_iter().map(|uv| surface.subs(uv.x, uv.y));
+            PolylineCurve(points.collect())
+        })
+        .collect()
+}
@@ -0,0 +1,252 @@
+use crate::analyzers::Topology;
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use thiserror::Error;
+use truck_topology::shell::ShellCondition;
+
+/// The target lengths of the edges of the tetrahedra at the points.
+///
+/// The constants are the uniform sizes, and the closures `Fn(Point3) -> f64` are the graded ones.
+pub trait SizingField {
+    /// Returns the target length of the edges at `point`.
+    fn size(&self, point: Point3) -> f64;
+}
+
+impl SizingField for f64 {
+    #[inline(always)]
+    fn size(&self, _: Point3) -> f64 { *self }
+}
+
+impl<F: Fn(Point3) -> f64> SizingField for F {
+    #[inline(always)]
+    fn size(&self, point: Point3) -> f64 { self(point) }
+}
+
+/// The input of the tetrahedral meshers: the closed triangle mesh and the sizing field.
+#[derive(Clone, Copy)]
+pub struct TetMeshInput<'a> {
+    /// the vertices of the boundary
+    pub positions: &'a [Point3],
+    /// the triangles of the boundary, oriented outward
+    pub triangles: &'a [[usize; 3]],
+    /// the target lengths of the edges
+    pub sizing: &'a dyn SizingField,
+}
+
+impl std::fmt::Debug for TetMeshInput<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TetMeshInput")
+            .field("positions", &self.positions)
+            .field("triangles", &self.triangles)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The volumetric mesh consisting of tetrahedra.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TetrahedralMesh {
+    /// the vertices, including the ones of the boundary at the same indices as in the input
+    pub positions: Vec<Point3>,
+    /// the indices of the vertices of the tetrahedra, positively oriented,
+    /// i.e. `(p1 - p0).cross(p2 - p0).dot(p3 - p0) > 0`
+    pub tetrahedra: Vec<[usize; 4]>,
+}
+
+impl TetrahedralMesh {
+    /// Returns the signed volume of the `i`th tetrahedron.
+    #[inline(always)]
+    pub fn tetrahedron_volume(&self, i: usize) -> f64 {
+        let [p0, p1, p2, p3] = self.tetrahedra[i].map(|j| self.positions[j]);
+        (p1 - p0).cross(p2 - p0).dot(p3 - p0) / 6.0
+    }
+    /// Returns the total volume of the tetrahedra.
+    #[inline(always)]
+    pub fn volume(&self) -> f64 {
+        (0..self.tetrahedra.len())
+            .map(|i| self.tetrahedron_volume(i))
+            .sum()
+    }
+    /// Returns whether all tetrahedra have positive volumes or not.
+    #[inline(always)]
+    pub fn is_positively_oriented(&self) -> bool {
+        (0..self.tetrahedra.len()).all(|i| self.tetrahedron_volume(i) > 0.0)
+    }
+    /// Returns the boundary, i.e. the triangles shared by no two tetrahedra, oriented outward.
+    ///
+    /// The returned mesh has all vertices of `self`, including the interior ones.
+    pub fn boundary(&self) -> PolygonMesh {
+        let mut count = HashMap::<[usize; 3], ([usize; 3], usize)>::default();
+        self.tetrahedra.iter().for_each(|&[a, b, c, d]| {
+            // outward faces of the positively oriented tetrahedron
+            [[a, c, b], [a, b, d], [a, d, c], [b, c, d]]
+                .into_iter()
+                .for_each(|tri| {
+                    let mut key = tri;
+                    key.sort_unstable();
+                    count.entry(key).or_insert((tri, 0)).1 += 1;
+                })
+        });
+        let mut triangles: Vec<[usize; 3]> = count
+            .into_values()
+            .filter_map(|(tri, n)| if n == 1 { Some(tri) } else { None })
+            .collect();
+        triangles.sort_unstable();
+        PolygonMesh::new(
+            StandardAttributes {
+                positions: self.positions.clone(),
+                ..Default::default()
+            },
+            Faces::from_iter(&triangles),
+        )
+    }
+}
+
+/// Errors occurred by the tetrahedralization.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum TetrahedralizationError {
+    /// The mesh does not bound any solid.
+    #[error("The mesh is not closed but {0:?}. Only closed meshes can be tetrahedralized.")]
+    NotClosed(ShellCondition),
+    /// The mesher failed.
+    #[error("The tetrahedral mesher failed: {0}")]
+    Mesher(String),
+}
+
+/// The backend of the tetrahedralization, e.g. the binding of TetGen.
+///
+/// The backends are implemented out of this crate or behind the cargo features,
+/// and [`Tetrahedralization::tetrahedralize`] validates the input and passes it to them.
+/// [`StarMesher`] is the simple backend in this crate.
+pub trait TetMesher {
+    /// Generates the tetrahedra filling the inside of `input`.
+    fn generate(&self, input: TetMeshInput<'_>)
+        -> Result<TetrahedralMesh, TetrahedralizationError>;
+}
+
+/// The tetrahedral mesher for the meshes star-shaped from their area centroids,
+/// e.g. convex meshes, which connects the boundary triangles to the centroid.
+///
+/// The sizing field is ignored. This mesher is intended for the tests of the pipelines and
+/// the coarse meshes of simple parts, and returns [`TetrahedralizationError::Mesher`] if the
+/// mesh is not star-shaped from the centroid.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StarMesher;
+
+impl TetMesher for StarMesher {
+    fn generate(
+        &self,
+        input: TetMeshInput<'_>,
+    ) -> Result<TetrahedralMesh, TetrahedralizationError> {
+        let triangle_points = |tri: &[usize; 3]| tri.map(|i| input.positions[i]);
+        let (sum, area) =
+            input
+                .triangles
+                .iter()
+                .fold((Vector3::zero(), 0.0), |(sum, total), tri| {
+                    let [p0, p1, p2] = triangle_points(tri);
+                    let area = (p1 - p0).cross(p2 - p0).magnitude() / 2.0;
+                    (
+                        sum + (p0.to_vec() + p1.to_vec() + p2.to_vec()) * area / 3.0,
+                        total + area,
+                    )
+                });
+        if area.so_small() {
+            return Err(TetrahedralizationError::Mesher("the area is zero".into()));
+        }
+        let center = Point3::from_vec(sum / area);
+        let mut positions = input.positions.to_vec();
+        let c = positions.len();
+        positions.push(center);
+        let mesh = TetrahedralMesh {
+            positions,
+            tetrahedra: input
+                .triangles
+                .iter()
+                .map(|&[a, b, d]| [c, a, b, d])
+                .collect(),
+        };
+        match mesh.is_positively_oriented() {
+            true => Ok(mesh),
+            false => Err(TetrahedralizationError::Mesher(
+                "the mesh is not star-shaped from the centroid".into(),
+            )),
+        }
+    }
+}
+
+/// Tetrahedralizes the solids bounded by closed polygon meshes.
+pub trait Tetrahedralization {
+    /// Fills the solid bounded by `self` with tetrahedra by `mesher`.
+    /// # Errors
+    /// Returns [`TetrahedralizationError::NotClosed`] if the shell condition of the mesh is not
+    /// [`ShellCondition::Closed`], and the errors of `mesher`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the regular octahedron
+    /// let positions = vec![
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(-1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, -1.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 1.0),
+    ///     Point3::new(0.0, 0.0, -1.0),
+    /// ];
+    /// let faces = [
+    ///     [0, 1, 4], [1, 2, 4], [2, 3, 4], [3, 0, 4],
+    ///     [1, 0, 5], [2, 1, 5], [3, 2, 5], [0, 3, 5],
+    /// ];
+    /// let mesh = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions: positions.clone(),
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&faces),
+    /// );
+    /// let tet = mesh.tetrahedralize(&StarMesher, &0.5).unwrap();
+    /// assert_eq!(tet.tetrahedra.len(), 8);
+    /// assert!(tet.volume().near(&(4.0 / 3.0)));
+    /// assert_eq!(tet.boundary().faces().len(), 8);
+    ///
+    /// // The open mesh cannot be tetrahedralized.
+    /// let open = PolygonMesh::new(
+    ///     StandardAttributes {
+    ///         positions,
+    ///         ..Default::default()
+    ///     },
+    ///     Faces::from_iter(&faces[..7]),
+    /// );
+    /// assert!(matches!(
+    ///     open.tetrahedralize(&StarMesher, &0.5),
+    ///     Err(TetrahedralizationError::NotClosed(_)),
+    /// ));
+    /// ```
+    fn tetrahedralize<M: TetMesher + ?Sized>(
+        &self,
+        mesher: &M,
+        sizing: &dyn SizingField,
+    ) -> Result<TetrahedralMesh, TetrahedralizationError>;
+}
+
+impl Tetrahedralization for PolygonMesh {
+    fn tetrahedralize<M: TetMesher + ?Sized>(
+        &self,
+        mesher: &M,
+        sizing: &dyn SizingField,
+    ) -> Result<TetrahedralMesh, TetrahedralizationError> {
+        let condition = self.shell_condition();
+        if condition != ShellCondition::Closed {
+            return Err(TetrahedralizationError::NotClosed(condition));
+        }
+        let triangles: Vec<[usize; 3]> = self
+            .faces()
+            .triangle_iter()
+            .map(|tri| tri.map(|v| v.pos))
+            .collect();
+        mesher.generate(TetMeshInput {
+            positions: self.positions(),
+            triangles: &triangles,
+            sizing,
+        })
+    }
+}
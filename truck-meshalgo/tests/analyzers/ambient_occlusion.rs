@@ -0,0 +1,77 @@
+use super::*;
+#[path = "../common/mod.rs"]
+mod common;
+
+#[test]
+fn convex_is_open() {
+    let mesh = common::shapes::sphere(Point3::new(1.0, 2.0, 3.0), 1.0, 16, 8);
+    let occlusion = mesh.vertex_occlusion(&Default::default());
+    assert_eq!(occlusion.len(), mesh.positions().len());
+    occlusion.iter().for_each(|o| assert!(o.so_small(), "{o}"));
+}
+
+#[test]
+fn inside_is_covered() {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 16, 8);
+    mesh.invert();
+    let occlusion = mesh.vertex_occlusion(&Default::default());
+    occlusion.iter().for_each(|o| assert!(o.near(&1.0), "{o}"));
+}
+
+#[test]
+fn max_distance() {
+    // the ground and the ceiling at the height 1
+    let mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions: vec![
+                Point3::new(-10.0, -10.0, 0.0),
+                Point3::new(10.0, -10.0, 0.0),
+                Point3::new(10.0, 10.0, 0.0),
+                Point3::new(-10.0, 10.0, 0.0),
+                Point3::new(-10.0, -10.0, 1.0),
+                Point3::new(10.0, -10.0, 1.0),
+                Point3::new(10.0, 10.0, 1.0),
+                Point3::new(-10.0, 10.0, 1.0),
+                Point3::new(0.0, 0.0, 0.0),
+            ],
+            ..Default::default()
+        },
+        Faces::from_iter(&[
+            [0, 1, 8].as_ref(),
+            &[1, 2, 8],
+            &[2, 3, 8],
+            &[3, 0, 8],
+            &[4, 7, 6, 5],
+        ]),
+    );
+    let options = AmbientOcclusionOptions {
+        samples: 256,
+        ..Default::default()
+    };
+    // Only the rays with the tangents more than 10 miss the ceiling.
+    let occlusion = mesh.vertex_occlusion(&options)[8];
+    assert!(f64::abs(occlusion - 100.0 / 101.0) < 0.01, "{occlusion}");
+
+    // The ceiling is farther than `max_distance` in all directions.
+    let options = AmbientOcclusionOptions {
+        max_distance: 0.9,
+        ..options
+    };
+    assert!(mesh.vertex_occlusion(&options)[8].so_small());
+
+    // Only the rays with the cosines not less than 1/2 reach the ceiling within the
+    // distance 2, and their ratio in the cosine-weighted rays is 1 - (1/2)^2.
+    let options = AmbientOcclusionOptions {
+        max_distance: 2.0,
+        ..options
+    };
+    let occlusion = mesh.vertex_occlusion(&options)[8];
+    assert!(f64::abs(occlusion - 0.75) < 0.02, "{occlusion}");
+}
+
+#[test]
+fn texture_without_uv() {
+    let mesh = common::shapes::sphere(Point3::origin(), 1.0, 16, 8);
+    let texture = mesh.occlusion_texture(8, 8, &Default::default());
+    assert_eq!(texture, vec![0.0; 64]);
+}
@@ -0,0 +1,66 @@
+use super::*;
+#[path = "../common/mod.rs"]
+mod common;
+
+#[test]
+fn concentric_spheres_distance() {
+    let sphere0 = common::shapes::sphere(Point3::origin(), 2.0, 64, 32);
+    let sphere1 = common::shapes::sphere(Point3::origin(), 2.1, 64, 32);
+    let dist = sphere0.distance_to(&sphere1);
+    assert!(f64::abs(dist.hausdorff - 0.1) < 0.01, "{dist:?}");
+    assert!(f64::abs(dist.rms - 0.1) < 0.01, "{dist:?}");
+    assert!(dist.rms <= dist.hausdorff);
+    let inverse = sphere1.distance_to(&sphere0);
+    assert!(inverse.forward.near(&dist.backward));
+    assert!(inverse.backward.near(&dist.forward));
+}
+
+#[test]
+fn distance_to_point_brute_force() {
+    let mesh = common::shapes::sphere(Point3::new(1.0, 2.0, 3.0), 1.5, 16, 8);
+    let positions = mesh.positions();
+    let triangles: Vec<[Point3; 3]> = mesh
+        .faces()
+        .triangle_iter()
+        .map(|tri| tri.map(|v| positions[v.pos]))
+        .collect();
+    // the distance to the nearest point of the triangles sampled densely
+    let brute_force = |pt: Point3| {
+        triangles
+            .iter()
+            .flat_map(|[p, q, r]| {
+                (0..=20).flat_map(move |i| {
+                    (0..=20 - i).map(move |j| {
+                        let (s, t) = (i as f64 / 20.0, j as f64 / 20.0);
+                        p + (q - p) * s + (r - p) * t
+                    })
+                })
+            })
+            .map(|x| x.distance(pt))
+            .fold(f64::INFINITY, f64::min)
+    };
+    (0..50).for_each(|i| {
+        let t = i as f64;
+        let pt = Point3::new(
+            1.0 + 3.0 * f64::sin(t * 1.3),
+            2.0 + 3.0 * f64::cos(t * 0.7),
+            3.0 + 2.0 * f64::sin(t * 2.9),
+        );
+        let dist = mesh.distance_to_point(pt);
+        let exact = brute_force(pt);
+        assert!(
+            dist <= exact + 1.0e-10 && exact - dist < 0.05,
+            "{dist} {exact}"
+        );
+    });
+}
+
+#[test]
+fn distance_to_empty_mesh() {
+    let mesh = common::shapes::sphere(Point3::origin(), 1.0, 8, 4);
+    let empty = PolygonMesh::default();
+    assert_eq!(empty.distance_to_point(Point3::origin()), f64::INFINITY);
+    let dist = mesh.distance_to(&empty);
+    assert_eq!(dist.forward, f64::INFINITY);
+    assert_eq!(dist.backward, 0.0);
+}
@@ -0,0 +1,70 @@
+use super::*;
+use std::f64::consts::PI;
+#[path = "../common/mod.rs"]
+mod common;
+
+#[test]
+fn geodesic_on_sphere() {
+    let sphere = common::shapes::sphere(Point3::origin(), 1.0, 64, 33);
+    // the north pole
+    let distances = sphere.geodesic_distances(0);
+    sphere
+        .positions()
+        .iter()
+        .zip(&distances)
+        .for_each(|(p, d)| {
+            let exact = f64::acos(p.z.clamp(-1.0, 1.0));
+            assert!((d - exact).abs() < 0.02, "{p:?} {d} {exact}");
+        });
+
+    // the path to a point on the equator runs along the meridian.
+    let target = 10 * 33 + 16;
+    let path = sphere.geodesic_path(&distances, target).unwrap();
+    assert!(path[0].near(&Point3::new(0.0, 0.0, 1.0)));
+    assert!(path[path.len() - 1].near(&sphere.positions()[target]));
+    let length: f64 = path.windows(2).map(|p| p[0].distance(p[1])).sum();
+    assert!((length - PI / 2.0).abs() < 0.02, "{length}");
+    let normal = Vector3::new(0.0, 0.0, 1.0).cross(sphere.positions()[target].to_vec());
+    path.iter()
+        .for_each(|p| assert!(p.to_vec().dot(normal).abs() < 0.02, "{p:?}"));
+}
+
+#[test]
+fn geodesic_on_plane() {
+    const N: usize = 20;
+    let positions = (0..=N)
+        .flat_map(|i| (0..=N).map(move |j| Point3::new(i as f64, j as f64, 0.0)))
+        .collect();
+    let faces = Faces::from_iter((0..N).flat_map(|i| {
+        (0..N).map(move |j| {
+            let k = i * (N + 1) + j;
+            [k, k + N + 1, k + N + 2, k + 1]
+        })
+    }));
+    let mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    );
+    let source = 3 * (N + 1) + 2;
+    let distances = mesh.geodesic_distances(source);
+    let origin = mesh.positions()[source];
+    mesh.positions().iter().zip(&distances).for_each(|(p, d)| {
+        assert!((d - p.distance(origin)).abs() < 0.1, "{p:?} {d}");
+    });
+
+    let target = 17 * (N + 1) + 19;
+    let path = mesh.geodesic_path(&distances, target).unwrap();
+    let dir = (mesh.positions()[target] - origin).normalize();
+    path.iter().for_each(|p| {
+        let v = p - origin;
+        assert!((v - v.dot(dir) * dir).magnitude() < 0.15, "{p:?}");
+    });
+
+    // unreachable position
+    let mut distances = mesh.geodesic_distances(source);
+    distances[target] = f64::INFINITY;
+    assert!(mesh.geodesic_path(&distances, target).is_none());
+}
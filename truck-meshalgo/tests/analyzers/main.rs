@@ -1,6 +1,16 @@
 use truck_meshalgo::prelude::*;
 
+mod ambient_occlusion;
 mod collision;
+mod distance;
+mod geodesic;
+mod mass_properties;
+mod morphing;
 mod point_cloud;
+mod primitives;
+mod quality;
+mod sampling;
+mod selection;
 mod splitting;
+mod symmetry;
 mod topology;
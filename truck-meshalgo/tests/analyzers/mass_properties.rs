@@ -0,0 +1,35 @@
+use super::*;
+use std::f64::consts::PI;
+use truck_topology::shell::ShellCondition;
+#[path = "../common/mod.rs"]
+mod common;
+
+#[test]
+fn sphere_mass_properties() {
+    let center = Point3::new(1.0, -2.0, 3.0);
+    let mut mesh = common::shapes::sphere(center, 2.0, 128, 64);
+    assert!(matches!(
+        mesh.mass_properties(),
+        Err(MassPropertiesError::NotClosed(_))
+    ));
+    mesh.weld(1.0e-6).remove_degenerate_faces();
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+
+    let props = mesh.mass_properties().unwrap();
+    let volume = 4.0 / 3.0 * PI * 8.0;
+    assert!(f64::abs(props.volume - volume) < volume * 0.01);
+    assert!(f64::abs(props.area - 16.0 * PI) < 16.0 * PI * 0.01);
+    assert!(props.centroid.distance(center) < 1.0e-6);
+    // the moment of inertia of the ball is 2/5 M R^2
+    let moment = 2.0 / 5.0 * volume * 4.0;
+    (0..3).for_each(|i| {
+        assert!(f64::abs(props.inertia[i][i] - moment) < moment * 0.02);
+        (0..3)
+            .filter(|j| *j != i)
+            .for_each(|j| assert!(props.inertia[i][j].abs() < moment * 1.0e-6));
+    });
+
+    // the inverted mesh has the negative volume
+    mesh.invert();
+    assert!(mesh.mass_properties().unwrap().volume.near(&-props.volume));
+}
@@ -0,0 +1,68 @@
+use super::*;
+#[path = "../common/mod.rs"]
+mod common;
+
+#[test]
+fn morph_spheres() {
+    let mut sphere0 = common::shapes::sphere(Point3::origin(), 1.0, 16, 8);
+    let mut sphere1 = common::shapes::sphere(Point3::new(1.0, 0.0, 0.0), 2.0, 16, 8);
+    sphere0.add_naive_normals(true);
+    sphere1.add_naive_normals(true);
+    for interpolation in [MorphInterpolation::Linear, MorphInterpolation::Smooth] {
+        let mesh = sphere0.morph(&sphere1, 0.5, interpolation).unwrap();
+        let center = Point3::new(0.5, 0.0, 0.0);
+        mesh.positions()
+            .iter()
+            .for_each(|p| assert!(p.distance(center).near(&1.5)));
+        assert_eq!(mesh.faces(), sphere0.faces());
+        assert_eq!(mesh.normals().len(), sphere0.normals().len());
+        mesh.normals()
+            .iter()
+            .zip(sphere0.normals())
+            .for_each(|(n, m)| assert!(n.near(m)));
+    }
+
+    let frames = sphere0
+        .morph_frames(&sphere1, 3, MorphInterpolation::Linear)
+        .unwrap();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].positions(), sphere0.positions());
+    assert_eq!(frames[2].positions(), sphere1.positions());
+}
+
+#[test]
+fn extrapolation() {
+    let sphere0 = common::shapes::sphere(Point3::origin(), 1.0, 16, 8);
+    let sphere1 = common::shapes::sphere(Point3::origin(), 2.0, 16, 8);
+    let mesh = sphere0
+        .morph(&sphere1, 2.0, MorphInterpolation::Linear)
+        .unwrap();
+    mesh.positions()
+        .iter()
+        .for_each(|p| assert!(p.to_vec().magnitude().near(&3.0)));
+    let mesh = sphere0
+        .morph(&sphere1, 2.0, MorphInterpolation::Smooth)
+        .unwrap();
+    assert_eq!(mesh.positions(), sphere1.positions());
+}
+
+#[test]
+fn different_attributes() {
+    let sphere0 = common::shapes::sphere(Point3::origin(), 1.0, 16, 8);
+    let mut sphere1 = sphere0.clone();
+    // an isolated vertex
+    sphere1
+        .debug_editor()
+        .attributes
+        .positions
+        .push(Point3::origin());
+    assert_eq!(
+        sphere0.morph(&sphere1, 0.5, MorphInterpolation::Linear),
+        Err(MorphingError::DifferentAttributes),
+    );
+    let sphere2 = common::shapes::sphere(Point3::origin(), 1.0, 8, 8);
+    assert_eq!(
+        sphere0.morph_frames(&sphere2, 4, MorphInterpolation::Linear),
+        Err(MorphingError::DifferentFaces),
+    );
+}
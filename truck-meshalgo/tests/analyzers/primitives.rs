@@ -0,0 +1,86 @@
+use super::*;
+use std::f64::consts::PI;
+use truck_modeling::{builder, Rad, Shell, Solid};
+#[path = "../common/mod.rs"]
+mod common;
+
+fn tessellate(shell: &Shell) -> PolygonMesh {
+    let mut mesh = shell.triangulation(0.001).to_polygon();
+    mesh.put_together_same_attrs().remove_degenerate_faces();
+    mesh
+}
+
+#[test]
+fn detect_sphere() {
+    let center = Point3::new(1.0, 2.0, 3.0);
+    let mut mesh = common::shapes::sphere(center, 2.0, 64, 32);
+    mesh.weld(1.0e-6).remove_degenerate_faces();
+    let primitives = mesh.detect_primitives(0.01, PI / 6.0);
+    assert_eq!(primitives.len(), 1);
+    assert_eq!(primitives[0].faces.len(), mesh.faces().len());
+    match primitives[0].primitive {
+        FittedPrimitive::Sphere {
+            center: c,
+            radius: r,
+        } => {
+            assert!(c.distance(center) < 1.0e-3);
+            assert!(f64::abs(r - 2.0) < 0.01);
+        }
+        primitive => panic!("{primitive:?}"),
+    }
+}
+
+#[test]
+fn detect_cylinder() {
+    let v = builder::vertex(Point3::new(1.5, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::new(0.0, 0.0, 3.0));
+    let mesh = tessellate(&cylinder.boundaries()[0]);
+    let primitives = mesh.detect_primitives(0.01, PI / 6.0);
+    assert_eq!(primitives.len(), 3);
+    let total: usize = primitives.iter().map(|p| p.faces.len()).sum();
+    assert_eq!(total, mesh.faces().len());
+    let planes = primitives
+        .iter()
+        .filter(|p| matches!(p.primitive, FittedPrimitive::Plane { .. }))
+        .count();
+    assert_eq!(planes, 2);
+    let side = primitives
+        .iter()
+        .find_map(|p| match p.primitive {
+            FittedPrimitive::Cylinder {
+                origin,
+                axis,
+                radius,
+            } => Some((origin, axis, radius)),
+            _ => None,
+        })
+        .unwrap();
+    assert!(side.1.cross(Vector3::unit_z()).magnitude() < 1.0e-3);
+    assert!(Vector2::new(side.0.x, side.0.y).magnitude() < 1.0e-3);
+    assert!(f64::abs(side.2 - 1.5) < 0.01);
+}
+
+#[test]
+fn detect_cone() {
+    let v0 = builder::vertex(Point3::new(0.0, 0.0, 2.0));
+    let v1 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let line = builder::line(&v0, &v1);
+    let shell: Shell = builder::rsweep(&line, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let mesh = tessellate(&shell);
+    let primitives = mesh.detect_primitives(0.01, PI / 6.0);
+    assert_eq!(primitives.len(), 1);
+    match primitives[0].primitive {
+        FittedPrimitive::Cone {
+            apex,
+            axis,
+            half_angle,
+        } => {
+            assert!(apex.distance(Point3::new(0.0, 0.0, 2.0)) < 0.01, "{apex:?}");
+            assert!(axis.distance(-Vector3::unit_z()) < 1.0e-3, "{axis:?}");
+            assert!(f64::abs(half_angle - f64::atan(0.5)) < 0.01);
+        }
+        primitive => panic!("{primitive:?}"),
+    }
+}
@@ -0,0 +1,150 @@
+use super::*;
+use std::f64::consts::PI;
+
+fn triangles(positions: Vec<Point3>) -> PolygonMesh {
+    let faces = (0..positions.len() / 3)
+        .map(|i| [3 * i, 3 * i + 1, 3 * i + 2])
+        .collect::<Vec<_>>();
+    PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        Faces::from_iter(&faces),
+    )
+}
+
+#[test]
+fn triangle_quality() {
+    let h = f64::sqrt(3.0) / 2.0;
+    let mesh = triangles(vec![
+        // equilateral
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.5, h, 0.0),
+        // right isosceles
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(1.0, 0.0, 1.0),
+        Point3::new(0.0, 1.0, 1.0),
+        // sliver
+        Point3::new(0.0, 0.0, 2.0),
+        Point3::new(1.0, 0.0, 2.0),
+        Point3::new(0.5, 0.01, 2.0),
+        // degenerate
+        Point3::new(0.0, 0.0, 3.0),
+        Point3::new(1.0, 0.0, 3.0),
+        Point3::new(2.0, 0.0, 3.0),
+    ]);
+    let report = mesh.quality_report();
+    let q = &report.faces;
+
+    assert!(q[0].aspect_ratio.near(&1.0));
+    assert!(q[0].skewness.so_small());
+    assert!(q[0].min_angle.near(&(PI / 3.0)));
+    assert!(q[0].max_angle.near(&(PI / 3.0)));
+
+    assert!(q[1].min_angle.near(&(PI / 4.0)));
+    assert!(q[1].max_angle.near(&(PI / 2.0)));
+    assert!(q[1].skewness.near(&0.25));
+    assert!(q[1].max_edge_length.near(&f64::sqrt(2.0)));
+
+    assert!(q[2].aspect_ratio > 50.0);
+    assert!(q[2].skewness > 0.95);
+
+    assert_eq!(q[3].aspect_ratio, f64::INFINITY);
+    assert_eq!(q[3].skewness, 1.0);
+
+    assert!(report.min_angle().so_small());
+    assert_eq!(report.max_aspect_ratio(), f64::INFINITY);
+
+    let thresholds = QualityThresholds {
+        min_angle: 20.0_f64.to_radians(),
+        max_aspect_ratio: 5.0,
+        ..Default::default()
+    };
+    assert_eq!(report.violations(&thresholds), vec![2, 3]);
+    assert!(report.violations(&Default::default()).is_empty());
+
+    // the aspect ratios of the degenerate faces are not counted.
+    let histogram = report.histogram(|q| q.aspect_ratio, 4);
+    assert_eq!(histogram.counts.iter().sum::<usize>(), 3);
+    assert_eq!(histogram.range.0, q[0].aspect_ratio);
+}
+
+#[test]
+fn edge_length_histogram() {
+    // a 4x4 grid of unit squares, split into triangles
+    let n = 4;
+    let positions = (0..=n)
+        .flat_map(|j| (0..=n).map(move |i| Point3::new(i as f64, j as f64, 0.0)))
+        .collect::<Vec<_>>();
+    let idx = |i: usize, j: usize| j * (n + 1) + i;
+    let faces = (0..n)
+        .flat_map(|j| (0..n).map(move |i| (i, j)))
+        .flat_map(|(i, j)| {
+            [
+                [idx(i, j), idx(i + 1, j), idx(i + 1, j + 1)],
+                [idx(i, j), idx(i + 1, j + 1), idx(i, j + 1)],
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        Faces::from_iter(&faces),
+    );
+    let report = mesh.quality_report();
+    assert_eq!(report.faces.len(), 32);
+    report.faces.iter().for_each(|q| {
+        assert!(q.min_angle.near(&(PI / 4.0)));
+        assert!(q.max_angle.near(&(PI / 2.0)));
+    });
+    // 40 axis-aligned edges of length 1 and 16 diagonals of length sqrt(2)
+    let histogram = &report.edge_lengths;
+    assert_eq!(histogram.counts.len(), QualityReport::HISTOGRAM_BINS);
+    assert!(histogram.range.0.near(&1.0));
+    assert!(histogram.range.1.near(&f64::sqrt(2.0)));
+    assert_eq!(histogram.counts[0], 40);
+    assert_eq!(histogram.counts[QualityReport::HISTOGRAM_BINS - 1], 16);
+    assert_eq!(histogram.counts.iter().sum::<usize>(), 56);
+    assert_eq!(format!("{histogram}").lines().count(), 10);
+}
+
+#[test]
+fn quad_warp_distribution() {
+    let h = 0.1;
+    let mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions: vec![
+                Point3::new(0.0, 0.0, h),
+                Point3::new(1.0, 0.0, -h),
+                Point3::new(1.0, 1.0, h),
+                Point3::new(0.0, 1.0, -h),
+                Point3::new(2.0, 0.0, 0.0),
+                Point3::new(2.0, 1.0, 0.0),
+                Point3::new(3.0, 0.0, 0.0),
+            ],
+            ..Default::default()
+        },
+        Faces::from_iter(&[[0, 1, 2, 3].as_ref(), &[1, 4, 5, 2], &[4, 6, 5], &[4, 6, 6]]),
+    );
+    let report = mesh.quality_report();
+    let warped = report.faces.iter().find(|q| q.warp > 0.0).unwrap();
+    assert_eq!(warped.num_vertices, 4);
+    assert!(warped.warp.near(&(h / f64::sqrt(1.0 + 4.0 * h * h))));
+
+    let quality = mesh.quality();
+    assert_eq!(quality.degenerate_faces, 1);
+    assert_eq!(quality.aspect_ratios.counts.iter().sum::<usize>(), 1);
+    assert_eq!(quality.quad_warps.counts.iter().sum::<usize>(), 2);
+    assert_eq!(quality.quad_warps.counts[0], 1);
+    assert_eq!(
+        quality.quad_warps.counts[QualityReport::HISTOGRAM_BINS - 1],
+        1
+    );
+    assert_eq!(quality.min_angles.counts.iter().sum::<usize>(), 4);
+    assert_eq!(quality.edge_lengths, report.edge_lengths);
+    assert!(format!("{quality}").contains("quadrangle warps"));
+}
@@ -0,0 +1,71 @@
+use super::*;
+use truck_modeling::{builder, Solid};
+
+fn cube(origin: Point3, size: f64) -> Solid {
+    let v = builder::vertex(origin);
+    let e = builder::tsweep(&v, Vector3::unit_x() * size);
+    let f = builder::tsweep(&e, Vector3::unit_y() * size);
+    builder::tsweep(&f, Vector3::unit_z() * size)
+}
+
+#[test]
+fn sample_interior_of_hollow_cube() {
+    let outer = cube(Point3::origin(), 2.0);
+    let mut inner = cube(Point3::new(0.5, 0.5, 0.5), 1.0)
+        .into_boundaries()
+        .pop()
+        .unwrap();
+    inner.face_iter_mut().for_each(|face| {
+        face.invert();
+    });
+    let mut shells = outer.into_boundaries();
+    shells.push(inner);
+    let hollow = Solid::new(shells);
+
+    let points = hollow.sample_interior(2000);
+    assert_eq!(points.len(), 2000);
+    let in_range = |x: f64, min: f64, max: f64| min < x && x < max;
+    points.iter().for_each(|p| {
+        assert!([p.x, p.y, p.z].iter().all(|x| in_range(*x, 0.0, 2.0)));
+        assert!(![p.x, p.y, p.z]
+            .iter()
+            .all(|x| in_range(*x, 0.5 - 1.0e-3, 1.5 + 1.0e-3)));
+    });
+    // uniform: the mean is the center, and the octants have the same numbers of points.
+    let mean = points
+        .iter()
+        .fold(Vector3::zero(), |sum, p| sum + p.to_vec())
+        / 2000.0;
+    assert!(mean.distance(Vector3::new(1.0, 1.0, 1.0)) < 0.05);
+    let octant = points
+        .iter()
+        .filter(|p| p.x < 1.0 && p.y < 1.0 && p.z < 1.0)
+        .count();
+    assert!(200 < octant && octant < 300);
+
+    // the seeds
+    assert_eq!(
+        hollow.sample_interior_with_seed(10, 7),
+        hollow.sample_interior_with_seed(10, 7)
+    );
+    assert_ne!(
+        hollow.sample_interior(10),
+        hollow.sample_interior_with_seed(10, 7)
+    );
+}
+
+#[test]
+fn sample_interior_of_open_mesh() {
+    let mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions: vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+            ],
+            ..Default::default()
+        },
+        Faces::from_iter(&[[0, 1, 2]]),
+    );
+    assert!(mesh.sample_interior(10).is_empty());
+}
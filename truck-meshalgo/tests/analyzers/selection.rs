@@ -0,0 +1,49 @@
+use super::*;
+use std::f64::consts::PI;
+use truck_modeling::{builder, Rad, Solid};
+
+fn cylinder() -> PolygonMesh {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::new(0.0, 0.0, 2.0));
+    let mut mesh = cylinder.boundaries()[0].triangulation(0.01).to_polygon();
+    mesh.put_together_same_attrs().remove_degenerate_faces();
+    mesh
+}
+
+#[test]
+fn select_cylinder_faces() {
+    let mesh = cylinder();
+    let positions = mesh.positions();
+    let centroid = |i: usize| {
+        let face = &mesh.faces()[i];
+        face.iter()
+            .fold(Vector3::zero(), |sum, v| sum + positions[v.pos].to_vec())
+            / face.len() as f64
+    };
+    let is_side = |i: usize| {
+        let c = centroid(i);
+        c.z > 1.0e-6 && c.z < 2.0 - 1.0e-6
+    };
+    let side: Vec<usize> = (0..mesh.faces().len()).filter(|i| is_side(*i)).collect();
+    let top: Vec<usize> = (0..mesh.faces().len())
+        .filter(|i| centroid(*i).z.near(&2.0))
+        .collect();
+
+    // tangent faces
+    assert_eq!(mesh.select_smooth(side[0], PI / 6.0), side);
+    assert_eq!(mesh.select_smooth(top[0], PI / 6.0), top);
+    assert_eq!(mesh.select_smooth(top[0], PI).len(), mesh.faces().len());
+
+    // predicate
+    assert_eq!(mesh.select_connected(side[0], is_side), side);
+    let upper = mesh.select_connected(top[0], |i| centroid(i).z > 1.0);
+    assert!(upper.iter().all(|i| centroid(*i).z > 1.0));
+    assert!(top.iter().all(|i| upper.contains(i)));
+    assert!(upper.len() > top.len() && upper.len() < mesh.faces().len());
+
+    assert!(mesh
+        .select_connected(mesh.faces().len(), |_| true)
+        .is_empty());
+}
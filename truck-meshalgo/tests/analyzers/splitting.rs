@@ -90,3 +90,51 @@ fn into_component_test() {
     let components = mesh.components(false);
     assert_eq!(components.len(), 1);
 }
+
+#[test]
+fn feature_segmentation_test() {
+    // closed cylinder with the polygonal caps
+    const N: usize = 32;
+    let positions = (0..2 * N)
+        .map(|i| {
+            let t = 2.0 * std::f64::consts::PI * (i % N) as f64 / N as f64;
+            Point3::new(f64::cos(t), f64::sin(t), (i / N) as f64)
+        })
+        .collect();
+    let side = (0..N).map(|i| vec![i, (i + 1) % N, (i + 1) % N + N, i + N]);
+    let bottom = (0..N).rev().collect::<Vec<_>>();
+    let top = (N..2 * N).collect::<Vec<_>>();
+    let faces = side
+        .chain([bottom, top])
+        .collect::<Vec<_>>()
+        .iter()
+        .map(|face| face.as_slice())
+        .collect();
+    let mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    );
+
+    let (labels, polylines) = mesh.feature_segmentation(std::f64::consts::PI / 6.0);
+    // side, bottom, top
+    assert_eq!(labels.len(), N + 2);
+    assert!(labels[..N].iter().all(|label| *label == 0));
+    assert_eq!(labels[N], 1);
+    assert_eq!(labels[N + 1], 2);
+    // two circles
+    assert_eq!(polylines.len(), 2);
+    polylines.iter().for_each(|polyline| {
+        assert_eq!(polyline.len(), N + 1);
+        assert_eq!(polyline[0], polyline[N]);
+        let z = mesh.positions()[polyline[0]].z;
+        assert!(polyline.iter().all(|i| mesh.positions()[*i].z == z));
+    });
+
+    // the smooth side is also separated by the smaller angle.
+    let (labels, polylines) = mesh.feature_segmentation(std::f64::consts::PI / 32.0);
+    assert_eq!(*labels.iter().max().unwrap(), N + 1);
+    assert_eq!(polylines.len(), 2 * N + N);
+}
@@ -0,0 +1,60 @@
+use super::*;
+#[path = "../common/mod.rs"]
+mod common;
+
+fn ellipsoid(center: Point3) -> PolygonMesh {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 32, 16);
+    mesh.positions_mut().iter_mut().for_each(|p| {
+        *p = center + Vector3::new(2.0 * p.x, p.y, 0.5 * p.z);
+    });
+    mesh
+}
+
+#[test]
+fn ellipsoid_symmetry() {
+    let center = Point3::new(1.0, 2.0, 3.0);
+    let mesh = ellipsoid(center);
+    let planes = mesh.detect_symmetry(1.0e-6);
+    assert_eq!(planes.len(), 3);
+    let mut normals = planes
+        .iter()
+        .map(|sym| {
+            assert!(sym.plane.origin.near(&center), "{sym:?}");
+            sym.plane.normal.map(f64::abs)
+        })
+        .collect::<Vec<_>>();
+    normals.sort_by(|a, b| b.x.total_cmp(&a.x).then(b.y.total_cmp(&a.y)));
+    assert!(normals[0].near(&Vector3::unit_x()));
+    assert!(normals[1].near(&Vector3::unit_y()));
+    assert!(normals[2].near(&Vector3::unit_z()));
+}
+
+#[test]
+fn mirrored_half_is_symmetric() {
+    let half = ellipsoid(Point3::new(-3.0, 0.0, 0.0));
+    let plane = MirrorPlane::new(Point3::origin(), Vector3::new(1.0, 0.0, 0.0));
+    let mut mesh = half.clone();
+    mesh.merge(half.mirror(&plane));
+    assert!(mesh.symmetry_distance(&plane).hausdorff.so_small());
+    let planes = mesh.detect_symmetry(1.0e-6);
+    assert!(planes
+        .iter()
+        .any(|sym| sym.plane.origin.near(&Point3::origin())
+            && sym.plane.normal.cross(Vector3::unit_x()).so_small()));
+
+    // the half moved a little is not symmetric
+    let mut moved = half.mirror(&plane);
+    moved
+        .positions_mut()
+        .iter_mut()
+        .for_each(|p| *p += Vector3::new(0.0, 0.0, 0.01));
+    let mut mesh = half.clone();
+    mesh.merge(moved);
+    let dist = mesh.symmetry_distance(&plane);
+    assert!(dist.hausdorff > 0.009, "{dist:?}");
+    assert!(mesh.detect_symmetry(1.0e-3).iter().all(|sym| !sym
+        .plane
+        .normal
+        .cross(Vector3::unit_x())
+        .so_small()));
+}
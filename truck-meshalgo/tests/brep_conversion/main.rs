@@ -0,0 +1,111 @@
+#![cfg(feature = "brep")]
+
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_modeling::*;
+#[path = "../common/mod.rs"]
+mod common;
+
+fn tessellate(solid: &Solid) -> PolygonMesh {
+    let (mut mesh, report) = solid.triangulation(0.001).to_watertight_polygon();
+    assert!(report.is_watertight());
+    mesh.put_together_same_attrs().remove_degenerate_faces();
+    mesh
+}
+
+fn volume(shell: Shell, tol: f64) -> f64 {
+    let solid = Solid::new(vec![shell]);
+    let (mesh, report) = solid.triangulation(tol).to_watertight_polygon();
+    assert!(report.is_watertight());
+    mesh.mass_properties().unwrap().volume
+}
+
+#[test]
+fn cylinder_to_brep() {
+    let v = builder::vertex(Point3::new(1.5, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::new(0.0, 0.0, 3.0));
+    let mesh = tessellate(&cylinder);
+
+    let shell = polygon_mesh_to_brep(&mesh, &Default::default()).unwrap();
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    // two disks and three sectors of the lateral face
+    assert_eq!(shell.len(), 5);
+    let planes = shell
+        .face_iter()
+        .filter(|face| matches!(face.get_surface(), Surface::Plane(_)))
+        .count();
+    assert_eq!(planes, 2);
+    let circles = shell
+        .edge_iter()
+        .filter(|edge| matches!(edge.get_curve(), Curve::IntersectionCurve(_)))
+        .count();
+    // the six arcs of the circles and the three seams of the sectors
+    assert_eq!(circles, 18);
+
+    let volume = volume(shell, 0.01);
+    assert!(f64::abs(volume - PI * 1.5 * 1.5 * 3.0) < 0.1, "{volume}");
+}
+
+#[test]
+fn separated_components_to_brep() {
+    let v = builder::vertex(Point3::new(-2.0, -2.0, 0.0));
+    let e = builder::tsweep(&v, Vector3::new(4.0, 0.0, 0.0));
+    let f = builder::tsweep(&e, Vector3::new(0.0, 4.0, 0.0));
+    let cube: Solid = builder::tsweep(&f, Vector3::new(0.0, 0.0, 1.0));
+    let mut mesh = tessellate(&cube);
+    let v = builder::vertex(Point3::new(1.0, 0.0, 3.0));
+    let circle = builder::rsweep(&v, Point3::new(0.0, 0.0, 3.0), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::new(0.0, 0.0, 1.0));
+    mesh.merge(tessellate(&cylinder));
+
+    let shell = polygon_mesh_to_brep(&mesh, &Default::default()).unwrap();
+    assert_eq!(shell.len(), 11);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert_eq!(shell.connected_components().len(), 2);
+}
+
+#[test]
+fn open_mesh_to_brep() {
+    let positions = (0..16)
+        .map(|i| {
+            let (x, y) = ((i % 4) as f64, (i / 4) as f64);
+            Point3::new(x, y, 0.1 * x * y)
+        })
+        .collect();
+    let faces: Vec<[usize; 3]> = (0..9)
+        .flat_map(|i| {
+            let i = i + i / 3;
+            [[i, i + 1, i + 5], [i, i + 5, i + 4]]
+        })
+        .collect();
+    let mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        Faces::from_iter(&faces),
+    );
+    let shell = polygon_mesh_to_brep(&mesh, &Default::default()).unwrap();
+    assert_eq!(shell.shell_condition(), ShellCondition::Oriented);
+    assert_eq!(shell.extract_boundaries().len(), 1);
+}
+
+#[test]
+fn sphere_to_brep() {
+    let mut mesh = common::shapes::sphere(Point3::new(1.0, 2.0, 3.0), 2.0, 32, 16);
+    mesh.weld(1.0e-6).remove_degenerate_faces();
+    let options = BRepConversionOptions {
+        tolerance: 0.05,
+        ..Default::default()
+    };
+    let shell = polygon_mesh_to_brep(&mesh, &options).unwrap();
+    // the six caps like the faces of the cube
+    assert_eq!(shell.len(), 6);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    let volume = volume(shell, 0.05);
+    let expected = 4.0 / 3.0 * PI * 8.0;
+    assert!(f64::abs(volume - expected) < expected * 0.05, "{volume}");
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "brep")]
+
+use truck_meshalgo::prelude::*;
+use truck_modeling::*;
+
+fn tessellate(solid: &Solid, tol: f64) -> PolygonMesh {
+    let (mut mesh, report) = solid.triangulation(tol).to_watertight_polygon();
+    assert!(report.is_watertight());
+    mesh.put_together_same_attrs().remove_degenerate_faces();
+    mesh
+}
+
+#[test]
+fn cylinder_curve_network() {
+    let v = builder::vertex(Point3::new(1.5, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::new(0.0, 0.0, 3.0));
+    let mesh = tessellate(&cylinder, 0.001);
+
+    let options = CurveNetworkOptions {
+        tolerance: 0.005,
+        ..Default::default()
+    };
+    let wires = feature_curve_network(&mesh, &options);
+    // the circles at the bottom and the top, which have no corners
+    assert_eq!(wires.len(), 2);
+    wires.iter().for_each(|wire| {
+        assert!(wire.is_closed());
+        assert_eq!(wire.len(), 2);
+        wire.edge_iter().for_each(|edge| {
+            let curve = edge.oriented_curve();
+            // not the polyline through all the points of the mesh
+            match &curve {
+                Curve::BSplineCurve(bsp) => assert!(bsp.control_points().len() < 10),
+                _ => panic!("the curve is not a B-spline curve."),
+            }
+            let (t0, t1) = curve.parameter_range();
+            (0..=20).for_each(|i| {
+                let pt = curve.subs(t0 + (t1 - t0) * i as f64 / 20.0);
+                let radius = f64::hypot(pt.x, pt.y);
+                assert!(f64::abs(radius - 1.5) < 0.01, "{pt:?}");
+                assert!(pt.z.near(&0.0) || pt.z.near(&3.0), "{pt:?}");
+            });
+        })
+    });
+}
+
+#[test]
+fn box_curve_network() {
+    let v = builder::vertex(Point3::new(-1.0, -2.0, 0.0));
+    let e = builder::tsweep(&v, Vector3::new(2.0, 0.0, 0.0));
+    let f = builder::tsweep(&e, Vector3::new(0.0, 4.0, 0.0));
+    let cube: Solid = builder::tsweep(&f, Vector3::new(0.0, 0.0, 1.0));
+    let mesh = tessellate(&cube, 0.01);
+
+    let wires = feature_curve_network(&mesh, &Default::default());
+    // the edges are split at the corners, which are the branch points of the chains.
+    assert_eq!(wires.len(), 12);
+    let vertices: std::collections::HashSet<_> = wires
+        .iter()
+        .flat_map(|wire| wire.vertex_iter())
+        .map(|v| v.id())
+        .collect();
+    assert_eq!(vertices.len(), 8);
+    wires.iter().flat_map(Wire::edge_iter).for_each(|edge| {
+        assert!(matches!(edge.get_curve(), Curve::Line(_)));
+        assert!(edge.is_geometric_consistent());
+    });
+}
+
+#[test]
+fn boundary_curve_network() {
+    // the saddle surface, whose boundary turns at the corners
+    const N: usize = 10;
+    let positions = (0..=N)
+        .flat_map(|j| (0..=N).map(move |i| (i as f64 / N as f64, j as f64 / N as f64)))
+        .map(|(x, y)| Point3::new(x, y, (x - 0.5) * (y - 0.5)))
+        .collect();
+    let faces: Vec<[usize; 3]> = (0..N * N)
+        .flat_map(|k| {
+            let i = k + k / N;
+            [[i, i + 1, i + N + 2], [i, i + N + 2, i + N + 1]]
+        })
+        .collect();
+    let mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        Faces::from_iter(&faces),
+    );
+
+    let wires = feature_curve_network(&mesh, &Default::default());
+    assert_eq!(wires.len(), 1);
+    let wire = &wires[0];
+    assert!(wire.is_closed());
+    // the four sides, which are straight
+    assert_eq!(wire.len(), 4);
+    wire.edge_iter().for_each(|edge| {
+        assert!(matches!(edge.get_curve(), Curve::Line(_)));
+    });
+}
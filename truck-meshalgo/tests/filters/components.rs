@@ -0,0 +1,62 @@
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+#[path = "../common/mod.rs"]
+mod common;
+
+fn sphere(center: Point3, division: usize) -> PolygonMesh {
+    let mut mesh = common::shapes::sphere(center, 1.0, division * 2, division);
+    mesh.weld(1.0e-6)
+        .remove_degenerate_faces()
+        .remove_unused_attrs();
+    mesh
+}
+
+fn spheres() -> (PolygonMesh, [usize; 3]) {
+    let mut mesh = PolygonMesh::default();
+    let sizes = [8, 16, 4].map(|division| {
+        let sphere = sphere(Point3::new(division as f64 * 3.0, 0.0, 0.0), division);
+        let len = sphere.faces().len();
+        mesh.merge(sphere);
+        len
+    });
+    (mesh, sizes)
+}
+
+#[test]
+fn split_spheres() {
+    let (mesh, sizes) = spheres();
+    let labels = mesh.component_labels(false);
+    assert_eq!(labels.len(), mesh.faces().len());
+    assert_eq!(labels.iter().max(), Some(&2));
+
+    let meshes = mesh.component_meshes(false);
+    assert_eq!(meshes.len(), 3);
+    meshes.iter().zip(sizes).for_each(|(sphere, size)| {
+        assert_eq!(sphere.faces().len(), size);
+        assert_eq!(sphere.shell_condition(), ShellCondition::Closed);
+        assert!(sphere
+            .face_iter()
+            .flatten()
+            .all(|v| v.pos < sphere.positions().len()));
+    });
+}
+
+#[test]
+fn filter_spheres() {
+    let (mesh, sizes) = spheres();
+
+    let mut largest = mesh.clone();
+    largest.keep_largest_component();
+    assert_eq!(largest.faces().len(), sizes[1]);
+    assert_eq!(largest.components(false).len(), 1);
+
+    let mut filtered = mesh.clone();
+    filtered.remove_small_components(sizes[2] + 1);
+    assert_eq!(filtered.faces().len(), sizes[0] + sizes[1]);
+    filtered.remove_small_components(0);
+    assert_eq!(filtered.faces().len(), sizes[0] + sizes[1]);
+
+    let mut empty = PolygonMesh::default();
+    empty.keep_largest_component().remove_small_components(1);
+    assert_eq!(empty.faces().len(), 0);
+}
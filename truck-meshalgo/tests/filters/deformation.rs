@@ -0,0 +1,90 @@
+use truck_meshalgo::prelude::*;
+#[path = "../common/mod.rs"]
+mod common;
+
+fn closed_sphere() -> PolygonMesh {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 16, 8);
+    mesh.weld(1.0e-6).remove_degenerate_faces();
+    mesh
+}
+
+#[test]
+fn arap_rigid_motion() {
+    let mesh = closed_sphere();
+    let rotation = Matrix3::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalize(), Rad(1.0));
+    let translation = Vector3::new(1.0, 2.0, 3.0);
+    let motion = |p: Point3| Point3::from_vec(rotation * p.to_vec() + translation);
+    let mut deformer = ArapDeformer::new(&mesh);
+    (0..mesh.positions().len()).step_by(8).for_each(|i| {
+        deformer.pin(i, motion(mesh.positions()[i]));
+    });
+    deformer.solve(30);
+    mesh.positions()
+        .iter()
+        .zip(deformer.positions())
+        .for_each(|(p, q)| assert!(motion(*p).distance(*q) < 1.0e-3, "{p:?} {q:?}"));
+}
+
+#[test]
+fn arap_interactive_drag() {
+    let mut mesh = closed_sphere();
+    let (bottom, top) =
+        mesh.positions()
+            .iter()
+            .enumerate()
+            .fold((0, 0), |(bottom, top), (i, p)| {
+                let positions = mesh.positions();
+                (
+                    if p.z < positions[bottom].z { i } else { bottom },
+                    if p.z > positions[top].z { i } else { top },
+                )
+            });
+    let bottom_point = mesh.positions()[bottom];
+    let top_point = mesh.positions()[top];
+    let mut deformer = ArapDeformer::new(&mesh);
+    deformer.pin(bottom, bottom_point);
+    for i in 1..=5 {
+        let handle = top_point + Vector3::unit_z() * (0.1 * i as f64);
+        deformer.pin(top, handle).solve(5);
+        assert_eq!(deformer.positions()[top], handle);
+        assert_eq!(deformer.positions()[bottom], bottom_point);
+    }
+    assert_eq!(deformer.handles().count(), 2);
+    deformer.apply(&mut mesh);
+    // stretched along the z-axis
+    let range: BoundingBox<Point3> = mesh.positions().iter().collect();
+    assert!(range.diagonal().z > 2.4);
+    assert!(range.diagonal().x < 2.0);
+
+    deformer.reset();
+    assert_eq!(deformer.handles().count(), 0);
+    assert_eq!(deformer.positions(), closed_sphere().positions().as_slice());
+}
+
+#[test]
+fn lattice_deformation() {
+    let mut mesh = closed_sphere();
+    let range: BoundingBox<Point3> = mesh.positions().iter().collect();
+    let mut lattice = Lattice::new(range, [2, 2, 2]);
+    let org = mesh.clone();
+    mesh.deform_by_lattice(&lattice);
+    mesh.positions()
+        .iter()
+        .zip(org.positions())
+        .for_each(|(p, q)| assert!(p.near(q)));
+
+    // flatten along the z-axis
+    for i in 0..=2 {
+        for j in 0..=2 {
+            for k in 0..=2 {
+                let p = lattice.control_point([i, j, k]);
+                lattice.set_control_point([i, j, k], Point3::new(p.x, p.y, p.z * 0.5));
+            }
+        }
+    }
+    mesh.deform_by_lattice(&lattice);
+    mesh.positions()
+        .iter()
+        .zip(org.positions())
+        .for_each(|(p, q)| assert!(p.near(&Point3::new(q.x, q.y, q.z * 0.5))));
+}
@@ -0,0 +1,151 @@
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+
+fn cube(origin: Point3) -> PolygonMesh {
+    let positions = (0..8)
+        .map(|i| origin + Vector3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+        .collect();
+    let faces = Faces::from_iter(&[
+        [0, 2, 3, 1],
+        [0, 1, 5, 4],
+        [0, 4, 6, 2],
+        [7, 5, 1, 3],
+        [7, 3, 2, 6],
+        [7, 6, 4, 5],
+    ]);
+    PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    )
+}
+
+#[test]
+fn heal_manifold_mesh() {
+    let mut mesh = cube(Point3::origin());
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    let log = mesh.heal_non_manifold();
+    assert!(log.is_empty(), "{log:?}");
+    assert_eq!(mesh.positions().len(), 8);
+    assert_eq!(mesh.faces().len(), 6);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+}
+
+#[test]
+fn heal_non_manifold_edge() {
+    // three triangles sharing the edge [0, 1], and a zero-area triangle
+    let mut mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions: vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+                Point3::new(0.0, -1.0, 0.0),
+                Point3::new(0.0, 0.0, 1.0),
+                Point3::new(2.0, 0.0, 0.0),
+            ],
+            ..Default::default()
+        },
+        Faces::from_iter(&[[0, 1, 2], [1, 0, 3], [0, 1, 4], [0, 5, 1]]),
+    );
+    assert_eq!(mesh.shell_condition(), ShellCondition::Irregular);
+    let log = mesh.heal_non_manifold();
+    assert_eq!(log.zero_area_faces, vec![3]);
+    assert!(log.duplicate_faces.is_empty());
+    assert_eq!(log.split_edges, vec![[0, 1]]);
+    assert_eq!(log.duplicated_vertices.len(), 2);
+    assert_eq!(mesh.faces().len(), 3);
+    assert_eq!(mesh.positions().len(), 8);
+    assert_ne!(mesh.shell_condition(), ShellCondition::Irregular);
+    log.duplicated_vertices.iter().for_each(|(i, j)| {
+        assert_eq!(mesh.positions()[*i], mesh.positions()[*j]);
+    });
+}
+
+#[test]
+fn heal_glued_cubes() {
+    // two cubes sharing one edge: the shared edge is non-manifold.
+    let mut mesh = cube(Point3::origin());
+    mesh.merge(cube(Point3::new(1.0, 1.0, 0.0)));
+    mesh.put_together_same_attrs().remove_unused_attrs();
+    assert_eq!(mesh.positions().len(), 14);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Irregular);
+    let log = mesh.heal_non_manifold();
+    assert_eq!(log.split_edges.len(), 1);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    assert_eq!(mesh.positions().len(), 16);
+}
+
+fn volume(mesh: &PolygonMesh) -> f64 {
+    mesh.faces()
+        .triangle_iter()
+        .map(|tri| {
+            let [p, q, r] = tri.map(|v| mesh.positions()[v.pos].to_vec());
+            p.dot(q.cross(r)) / 6.0
+        })
+        .sum()
+}
+
+#[test]
+fn fix_mixed_winding() {
+    let mut mesh = cube(Point3::origin());
+    mesh.editor().faces[1].reverse();
+    mesh.editor().faces[4].reverse();
+    assert_eq!(mesh.shell_condition(), ShellCondition::Regular);
+    mesh.fix_orientation();
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    assert!(volume(&mesh).near(&1.0));
+
+    mesh.invert();
+    assert!(volume(&mesh).near(&-1.0));
+    mesh.fix_orientation();
+    assert!(volume(&mesh).near(&1.0));
+}
+
+#[test]
+fn fix_hollow_cube() {
+    let mut mesh = cube(Point3::new(-0.5, -0.5, -0.5));
+    mesh.editor()
+        .attributes
+        .positions
+        .iter_mut()
+        .for_each(|p| *p = Point3::from_vec(p.to_vec() * 4.0));
+    mesh.merge(cube(Point3::new(-0.5, -0.5, -0.5)));
+    mesh.editor().faces[0].reverse();
+    mesh.fix_orientation();
+    assert!(volume(&mesh).near(&63.0));
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+}
+
+#[test]
+fn repair_report_of_glued_cubes() {
+    // two cubes sharing one edge, a reversed face, and a duplicated face.
+    let mut mesh = cube(Point3::origin());
+    mesh.merge(cube(Point3::new(1.0, 1.0, 0.0)));
+    mesh.put_together_same_attrs().remove_unused_attrs();
+    mesh.editor().faces[1].reverse();
+    let face: Vec<StandardVertex> = mesh.faces()[2].to_vec();
+    mesh.editor().faces.push(face);
+    let report = mesh.repair();
+    assert_eq!(report.count(DefectKind::DuplicateFace), 1);
+    assert_eq!(report.count(DefectKind::NonManifoldEdge), 1);
+    assert_eq!(report.count(DefectKind::NonManifoldVertex), 2);
+    let flipped: Vec<RepairEntity> = report
+        .records
+        .iter()
+        .filter(|record| record.pass == RepairPass::Orientation)
+        .map(|record| record.entity)
+        .collect();
+    assert_eq!(flipped, vec![RepairEntity::Face(1)]);
+    let residuals = report.residuals.unwrap();
+    assert_eq!(residuals.shell_condition, ShellCondition::Closed);
+    assert_eq!(residuals.boundaries, 0);
+    assert_eq!(residuals.non_manifold_edges, 0);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+
+    let json = report.to_json();
+    assert!(json.contains("\"shell_condition\": \"Closed\""), "{json}");
+    assert_eq!(RepairReport::from_json(&json).unwrap(), report);
+}
@@ -1,4 +1,11 @@
+mod components;
+mod deformation;
+mod healing;
 mod normal_filter;
+mod offsetting;
 mod optimizing;
+mod parameterization;
+mod remeshing;
+mod simplification;
 mod structuring;
 mod subdivision;
@@ -135,3 +135,45 @@ fn add_smooth_normals() {
         assert!(p0.distance(n0) > p1.distance(n1));
     }
 }
+
+#[test]
+fn add_crease_normals() {
+    let positions = (0..8)
+        .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+        .collect();
+    let faces = Faces::from_iter(&[
+        &[0, 2, 3, 1],
+        &[4, 5, 7, 6],
+        &[0, 1, 5, 4],
+        &[2, 6, 7, 3],
+        &[0, 4, 6, 2],
+        &[1, 3, 7, 5],
+    ]);
+    let mut cube = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    );
+
+    // All edges of the cube are sharp: each corner has its own normal.
+    cube.add_crease_normals(std::f64::consts::PI / 4.0, true);
+    assert_eq!(cube.normals().len(), 24);
+    cube.face_iter().for_each(|face| {
+        let center = face.iter().fold(Vector3::zero(), |sum, v| {
+            sum + cube.positions()[v.pos].to_vec()
+        }) / 4.0;
+        let expected = (center - Vector3::new(0.5, 0.5, 0.5)) * 2.0;
+        face.iter()
+            .for_each(|v| assert!(cube.normals()[v.nor.unwrap()].near(&expected)));
+    });
+
+    // All edges of the cube are smooth.
+    cube.add_crease_normals(std::f64::consts::PI * 0.6, true);
+    assert_eq!(cube.normals().len(), 8);
+    cube.face_iter().flatten().for_each(|v| {
+        let expected = (cube.positions()[v.pos] - Point3::new(0.5, 0.5, 0.5)).normalize();
+        assert!(cube.normals()[v.nor.unwrap()].near(&expected));
+    });
+}
@@ -0,0 +1,43 @@
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+#[path = "../common/mod.rs"]
+mod common;
+
+#[test]
+fn offset_sphere() {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 32, 16);
+    mesh.weld(1.0e-6).remove_degenerate_faces();
+    let num_faces = mesh.faces().len();
+    mesh.offset(0.5);
+    assert_eq!(mesh.faces().len(), num_faces);
+    mesh.positions().iter().for_each(|p| {
+        let dist = p.to_vec().magnitude();
+        assert!((1.45..1.55).contains(&dist), "{dist}");
+    });
+}
+
+#[test]
+fn thicken_hemisphere() {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 32, 16);
+    mesh.weld(1.0e-6).remove_degenerate_faces();
+    let faces: Faces = mesh
+        .face_iter()
+        .filter(|face| face.iter().all(|v| mesh.positions()[v.pos].z >= -1.0e-9))
+        .collect();
+    let mut mesh = PolygonMesh::new(mesh.attributes().clone(), faces);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Oriented);
+    let num_faces = mesh.faces().len();
+    let num_boundary = mesh.extract_boundaries()[0].len();
+
+    mesh.thicken(0.1);
+    assert_eq!(mesh.faces().len(), num_faces * 2 + num_boundary);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    mesh.positions().iter().for_each(|p| {
+        let dist = p.to_vec().magnitude();
+        assert!(dist.near(&1.0) || (0.89..0.91).contains(&dist), "{dist}");
+    });
+
+    let mut mesh = PolygonMesh::new(mesh.attributes().clone(), Faces::default());
+    mesh.thicken(-0.1);
+    assert!(mesh.faces().is_empty());
+}
@@ -190,3 +190,161 @@ fn put_together_same_attrs_test() {
     assert_eq!(mesh.uv_coords().len(), 18);
     assert_eq!(mesh.normals().len(), 17);
 }
+
+#[test]
+fn weld_test() {
+    // triangle soup of a 10x10 grid, whose vertices are perturbed across the hashing cells
+    const N: usize = 10;
+    let perturbed = |i: usize, j: usize, k: usize| {
+        let d = if k % 2 == 0 { 0.003 } else { -0.003 };
+        Point3::new(i as f64 + d, j as f64 - d, 0.0)
+    };
+    let mut positions = Vec::new();
+    let mut faces = Faces::default();
+    for i in 0..N {
+        for j in 0..N {
+            let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+            for tri in [[0, 1, 2], [0, 2, 3]] {
+                let len = positions.len();
+                tri.iter().for_each(|&k| {
+                    let (x, y) = corners[k];
+                    positions.push(perturbed(x, y, len + k));
+                });
+                faces.push([len, len + 1, len + 2]);
+            }
+        }
+    }
+    let mut mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    );
+    assert_eq!(mesh.positions().len(), 6 * N * N);
+    mesh.weld(0.01);
+    assert_eq!(mesh.positions().len(), (N + 1) * (N + 1));
+    assert_eq!(mesh.faces().len(), 2 * N * N);
+    assert!(mesh.faces().face_iter().flatten().all(|v| {
+        let p = mesh.positions()[v.pos];
+        (p.x - p.x.round()).abs() < 0.01 && (p.y - p.y.round()).abs() < 0.01
+    }));
+}
+
+#[test]
+fn cleanup_test() {
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.5, 0.0, 0.0),
+        Point3::new(3.0, 3.0, 3.0),
+    ];
+    let uv_coords = (0..6).map(|i| Vector2::new(i as f64, 0.0)).collect();
+    let normals = (0..6).map(|i| Vector3::new(0.0, i as f64, 1.0)).collect();
+    let faces = Faces::from_iter(&[
+        [[0, 0, 0], [1, 1, 1], [2, 2, 2]].as_ref(),
+        &[[0, 0, 0], [4, 4, 4], [1, 1, 1]],            // zero area
+        &[[1, 1, 1], [2, 2, 2], [0, 0, 0]],            // duplicate
+        &[[0, 0, 0], [2, 2, 2], [2, 2, 2], [3, 3, 3]], // collapsed to triangle
+        &[[0, 0, 0], [1, 1, 1], [0, 0, 0], [1, 1, 1]], // collapsed totally
+        &[[0, 0, 0], [2, 2, 2], [3, 3, 3], [0, 0, 0], [5, 5, 5]], // collapsed, and split into a triangle and a line
+    ]);
+    let mut mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            uv_coords,
+            normals,
+        },
+        faces,
+    );
+    let summary = mesh.cleanup();
+    assert_eq!(summary.zero_area_faces, vec![1, 5]);
+    assert_eq!(summary.duplicate_faces, vec![2, 5]);
+    assert_eq!(summary.collapsed_faces, vec![3, 4, 5]);
+    assert_eq!(summary.removed_positions, 2);
+    assert_eq!(summary.removed_uv_coords, 2);
+    assert_eq!(summary.removed_normals, 2);
+    assert_eq!(mesh.faces().len(), 2);
+    assert_eq!(mesh.positions().len(), 4);
+
+    assert!(mesh.cleanup().is_empty());
+}
+
+#[test]
+fn expand_vertices_test() {
+    // a cube whose faces have their own normals and uv coordinates
+    let positions: Vec<Point3> = (0..8)
+        .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+        .collect();
+    let uv_coords = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(1.0, 0.0),
+        Vector2::new(1.0, 1.0),
+        Vector2::new(0.0, 1.0),
+    ];
+    let normals = vec![
+        -Vector3::unit_z(),
+        Vector3::unit_z(),
+        -Vector3::unit_y(),
+        Vector3::unit_y(),
+        -Vector3::unit_x(),
+        Vector3::unit_x(),
+    ];
+    let quads = [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ];
+    let faces: Faces = quads
+        .iter()
+        .enumerate()
+        .map(|(i, quad)| {
+            let v = |j: usize| StandardVertex {
+                pos: quad[j],
+                uv: Some(j),
+                nor: Some(i),
+            };
+            [v(0), v(1), v(2), v(3)]
+        })
+        .collect();
+    let mut mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            uv_coords,
+            normals,
+        },
+        faces,
+    );
+    let original = mesh.clone();
+
+    mesh.expand_vertices();
+    assert_eq!(mesh.positions().len(), 24);
+    assert_eq!(mesh.uv_coords().len(), 24);
+    assert_eq!(mesh.normals().len(), 24);
+    assert_eq!(mesh.faces().quad_faces().len(), 6);
+    let iter = mesh
+        .face_iter()
+        .flatten()
+        .zip(original.face_iter().flatten());
+    iter.for_each(|(v, w)| {
+        assert_eq!((v.uv, v.nor), (Some(v.pos), Some(v.pos)));
+        assert_eq!(mesh.positions()[v.pos], original.positions()[w.pos]);
+        assert_eq!(mesh.uv_coords()[v.pos], original.uv_coords()[w.uv.unwrap()]);
+        assert_eq!(mesh.normals()[v.pos], original.normals()[w.nor.unwrap()]);
+    });
+
+    // The expanded vertices are not expanded more.
+    let expanded = mesh.clone();
+    mesh.expand_vertices();
+    assert_eq!(mesh, expanded);
+
+    mesh.weld_attributes(TOLERANCE);
+    assert_eq!(mesh.positions().len(), 8);
+    assert_eq!(mesh.uv_coords().len(), 4);
+    assert_eq!(mesh.normals().len(), 6);
+}
@@ -0,0 +1,189 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+#[path = "../common/mod.rs"]
+mod common;
+
+/// the angles of the triangles in the space and in the uv coordinates
+fn angle_pairs(mesh: &PolygonMesh) -> Vec<(f64, f64)> {
+    mesh.faces()
+        .triangle_iter()
+        .filter_map(|tri| {
+            let p = tri.map(|v| mesh.positions()[v.pos]);
+            let q = tri.map(|v| mesh.uv_coords()[v.uv.unwrap()]);
+            let area = (p[1] - p[0]).cross(p[2] - p[0]).magnitude();
+            match area > 1.0e-6 {
+                true => Some((p, q)),
+                false => None,
+            }
+        })
+        .flat_map(|(p, q)| {
+            (0..3).map(move |i| {
+                let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+                let angle = (p[j] - p[i]).angle(p[k] - p[i]).0;
+                let uv_angle = (q[j] - q[i]).angle(q[k] - q[i]).0;
+                (angle, uv_angle)
+            })
+        })
+        .collect()
+}
+
+fn in_atlas(mesh: &PolygonMesh) -> bool {
+    mesh.uv_coords()
+        .iter()
+        .all(|uv| (0.0..=1.0).contains(&uv.x) && (0.0..=1.0).contains(&uv.y))
+}
+
+#[test]
+fn lscm_plane() {
+    // a distorted planar grid
+    let positions = (0..100)
+        .map(|i| {
+            let (x, y) = ((i % 10) as f64, (i / 10) as f64);
+            Point3::new(x + 0.3 * f64::sin(y), y + 0.2 * f64::cos(x), 0.0)
+        })
+        .collect();
+    let faces = (0..81)
+        .map(|i| i + i / 9)
+        .flat_map(|i| [[i, i + 1, i + 11], [i, i + 11, i + 10]])
+        .collect();
+    let mut mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    );
+    mesh.add_lscm_uv_coords(PI / 4.0);
+    assert_eq!(mesh.uv_coords().len(), 100);
+    assert!(in_atlas(&mesh));
+    // planar meshes are mapped by similarities.
+    angle_pairs(&mesh)
+        .into_iter()
+        .for_each(|(a, b)| assert!((a - b).abs() < 1.0e-5, "{a} {b}"));
+}
+
+#[test]
+fn lscm_sphere() {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 32, 17);
+    mesh.add_lscm_uv_coords(PI / 3.0);
+    assert!(mesh.face_iter().flatten().all(|v| v.uv.is_some()));
+    assert!(in_atlas(&mesh));
+    let pairs = angle_pairs(&mesh);
+    let distortion = pairs.iter().map(|(a, b)| (a - b).abs()).sum::<f64>() / pairs.len() as f64;
+    assert!(distortion < 0.05, "{distortion}");
+
+    // no flipped triangles
+    mesh.faces().triangle_iter().for_each(|tri| {
+        let p = tri.map(|v| mesh.positions()[v.pos]);
+        let q = tri.map(|v| mesh.uv_coords()[v.uv.unwrap()]);
+        if (p[1] - p[0]).cross(p[2] - p[0]).magnitude() > 1.0e-6 {
+            assert!((q[1] - q[0]).perp_dot(q[2] - q[0]) > 0.0);
+        }
+    });
+}
+
+/// the torus whose positions are shared by the adjacent faces
+fn torus(udiv: usize, vdiv: usize) -> PolygonMesh {
+    let positions = (0..udiv)
+        .flat_map(move |i| {
+            (0..vdiv).map(move |j| {
+                let u = 2.0 * PI * i as f64 / udiv as f64;
+                let v = 2.0 * PI * j as f64 / vdiv as f64;
+                let r = 2.0 + f64::cos(v);
+                Point3::new(r * u.cos(), r * u.sin(), v.sin())
+            })
+        })
+        .collect();
+    let faces = Faces::from_iter((0..udiv).flat_map(move |i| {
+        (0..vdiv).map(move |j| {
+            let i1 = (i + 1) % udiv;
+            let j1 = (j + 1) % vdiv;
+            [i * vdiv + j, i1 * vdiv + j, i1 * vdiv + j1, i * vdiv + j1]
+        })
+    }));
+    PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    )
+}
+
+fn assert_unwrapped(mesh: &PolygonMesh, tol: f64) {
+    assert!(mesh.face_iter().flatten().all(|v| v.uv.is_some()));
+    assert!(in_atlas(mesh));
+    let pairs = angle_pairs(mesh);
+    let distortion = pairs.iter().map(|(a, b)| (a - b).abs()).sum::<f64>() / pairs.len() as f64;
+    assert!(distortion < tol, "{distortion}");
+    mesh.faces().triangle_iter().for_each(|tri| {
+        let p = tri.map(|v| mesh.positions()[v.pos]);
+        let q = tri.map(|v| mesh.uv_coords()[v.uv.unwrap()]);
+        if (p[1] - p[0]).cross(p[2] - p[0]).magnitude() > 1.0e-6 {
+            assert!((q[1] - q[0]).perp_dot(q[2] - q[0]) > 0.0);
+        }
+    });
+}
+
+#[test]
+fn auto_seams_sphere() {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 32, 17);
+    mesh.put_together_same_attrs().remove_degenerate_faces();
+    assert_eq!(
+        mesh.shell_condition(),
+        truck_topology::shell::ShellCondition::Closed
+    );
+    let seams = mesh.auto_seams();
+    // the path from the pole to the pole
+    assert_eq!(seams.len(), 16);
+    mesh.add_lscm_uv_coords_with_seams(&seams);
+    // one chart, cut along the path
+    let used: std::collections::HashSet<_> = mesh.face_iter().flatten().map(|v| v.pos).collect();
+    assert_eq!(mesh.uv_coords().len(), used.len() + seams.len() - 1);
+    assert_unwrapped(&mesh, 0.1);
+}
+
+#[test]
+fn auto_seams_torus() {
+    let mut mesh = torus(32, 16);
+    let seams = mesh.auto_seams();
+    // the cut graph of the torus, whose vertices of the branches are on the two loops
+    let mut degrees = std::collections::HashMap::<usize, usize>::new();
+    seams
+        .iter()
+        .flatten()
+        .for_each(|v| *degrees.entry(*v).or_default() += 1);
+    assert!(degrees.values().all(|degree| *degree >= 2));
+    mesh.add_lscm_uv_coords_with_seams(&seams);
+    assert_unwrapped(&mesh, 0.1);
+}
+
+#[test]
+fn auto_seams_tube() {
+    // the open tube, which is cut between the two boundaries
+    let positions = (0..32)
+        .flat_map(|i| {
+            let u = 2.0 * PI * i as f64 / 32.0;
+            (0..8).map(move |j| Point3::new(u.cos(), u.sin(), j as f64 / 4.0))
+        })
+        .collect();
+    let faces = Faces::from_iter((0..32).flat_map(|i| {
+        (0..7).map(move |j| {
+            let i1 = (i + 1) % 32;
+            [i * 8 + j, i1 * 8 + j, i1 * 8 + j + 1, i * 8 + j + 1]
+        })
+    }));
+    let mut mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    );
+    let seams = mesh.auto_seams();
+    assert_eq!(seams.len(), 7);
+    assert!(seams.iter().all(|[a, b]| a % 8 != b % 8));
+    mesh.add_lscm_uv_coords_with_seams(&seams);
+    assert_eq!(mesh.uv_coords().len(), 32 * 8 + 8);
+    assert_unwrapped(&mesh, 1.0e-3);
+}
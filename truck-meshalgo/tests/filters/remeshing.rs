@@ -0,0 +1,162 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+#[path = "../common/mod.rs"]
+mod common;
+
+fn edge_lengths(mesh: &PolygonMesh) -> Vec<f64> {
+    mesh.faces()
+        .triangle_iter()
+        .flat_map(|tri| {
+            let p = tri.map(|v| mesh.positions()[v.pos]);
+            [
+                p[0].distance(p[1]),
+                p[1].distance(p[2]),
+                p[2].distance(p[0]),
+            ]
+        })
+        .collect()
+}
+
+fn min_angles(mesh: &PolygonMesh) -> Vec<f64> {
+    mesh.faces()
+        .triangle_iter()
+        .map(|tri| {
+            let p = tri.map(|v| mesh.positions()[v.pos]);
+            (0..3)
+                .map(|i| {
+                    let (a, b) = (p[(i + 1) % 3] - p[i], p[(i + 2) % 3] - p[i]);
+                    a.angle(b).0
+                })
+                .fold(PI, f64::min)
+        })
+        .collect()
+}
+
+#[test]
+fn remeshing_cube() {
+    let positions = (0..8)
+        .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+        .collect();
+    let faces = Faces::from_iter(&[
+        [0, 2, 3, 1],
+        [0, 1, 5, 4],
+        [0, 4, 6, 2],
+        [7, 5, 1, 3],
+        [7, 3, 2, 6],
+        [7, 6, 4, 5],
+    ]);
+    let mut mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    );
+    mesh.isotropic_remeshing(0.2, PI / 6.0, 5);
+
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    assert!(edge_lengths(&mesh)
+        .into_iter()
+        .all(|l| (0.08..=0.27).contains(&l)));
+    // all vertices are on the cube
+    mesh.positions().iter().for_each(|p| {
+        assert!((0..3).all(|i| (-1.0e-9..=1.0 + 1.0e-9).contains(&p[i])));
+        assert!((0..3).any(|i| p[i].so_small() || p[i].near(&1.0)), "{p:?}");
+    });
+    // the corners are preserved
+    (0..8).for_each(|i| {
+        let corner = Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64);
+        assert!(mesh.positions().iter().any(|p| p.near(&corner)));
+    });
+}
+
+#[test]
+fn remeshing_sphere() {
+    // The triangles near the poles are thin.
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 64, 9);
+    mesh.put_together_same_attrs();
+    mesh.isotropic_remeshing(0.15, PI / 4.0, 8);
+
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    mesh.positions()
+        .iter()
+        .for_each(|p| assert!((0.95..=1.0 + 1.0e-9).contains(&p.to_vec().magnitude())));
+    let lengths = edge_lengths(&mesh);
+    let average = lengths.iter().sum::<f64>() / lengths.len() as f64;
+    assert!((0.12..0.18).contains(&average), "{average}");
+    let angles = min_angles(&mesh);
+    let good = angles.iter().filter(|a| **a > PI / 9.0).count();
+    assert!(good as f64 > 0.95 * angles.len() as f64);
+}
+
+/// the grid on `[0, 1]^2` perturbed deterministically, whose cells are divided by
+/// the alternating diagonals
+fn perturbed_grid(n: usize) -> PolygonMesh {
+    let positions = (0..(n + 1) * (n + 1))
+        .map(|k| {
+            let (i, j) = (k / (n + 1), k % (n + 1));
+            let (x, y) = (i as f64 / n as f64, j as f64 / n as f64);
+            let inner = 0 < i && i < n && 0 < j && j < n;
+            let (dx, dy) = match inner {
+                true => (f64::sin(k as f64 * 12.9898), f64::cos(k as f64 * 78.233)),
+                false => (0.0, 0.0),
+            };
+            Point3::new(x + 0.3 * dx / n as f64, y + 0.3 * dy / n as f64, 0.0)
+        })
+        .collect();
+    let faces = (0..n * n)
+        .flat_map(|k| {
+            let (i, j) = (k / n, k % n);
+            let v = |i: usize, j: usize| i * (n + 1) + j;
+            let (a, b, c, d) = (v(i, j), v(i + 1, j), v(i + 1, j + 1), v(i, j + 1));
+            match k % 2 == 0 {
+                true => [[a, b, c], [a, c, d]],
+                false => [[a, b, d], [b, c, d]],
+            }
+        })
+        .collect();
+    PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    )
+}
+
+#[test]
+fn delaunay_flip_plane() {
+    let mut mesh = perturbed_grid(10);
+    assert!(!IntrinsicTriangulation::new(&mesh).is_delaunay());
+    let min_angle = min_angles(&mesh).into_iter().fold(PI, f64::min);
+    let num_faces = mesh.faces().len();
+
+    mesh.delaunay_flip(PI / 6.0);
+    assert_eq!(mesh.faces().len(), num_faces);
+    assert!(IntrinsicTriangulation::new(&mesh).is_delaunay());
+    // The Delaunay triangulation maximizes the minimum angle.
+    let new_min_angle = min_angles(&mesh).into_iter().fold(PI, f64::min);
+    assert!(new_min_angle >= min_angle, "{new_min_angle} {min_angle}");
+    // The boundary is kept.
+    assert_eq!(mesh.extract_boundaries()[0].len(), 40);
+}
+
+#[test]
+fn delaunay_flip_sphere() {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 32, 16);
+    mesh.put_together_same_attrs().remove_degenerate_faces();
+    let num_faces = mesh.faces().triangle_iter().len();
+    mesh.delaunay_flip(PI / 6.0);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    assert_eq!(mesh.faces().len(), num_faces);
+    mesh.positions()
+        .iter()
+        .for_each(|p| assert!(p.to_vec().magnitude().near(&1.0)));
+
+    let mut intrinsic = IntrinsicTriangulation::new(&mesh);
+    intrinsic.flip_to_delaunay();
+    assert!(intrinsic.is_delaunay());
+    assert!(intrinsic.cotan_weights().iter().all(|(_, w)| *w > -1.0e-9));
+    assert_eq!(intrinsic.triangles().len(), num_faces);
+}
@@ -0,0 +1,123 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+#[path = "../common/mod.rs"]
+mod common;
+
+#[test]
+fn simplify_sphere() {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 64, 33);
+    mesh.put_together_same_attrs()
+        .remove_degenerate_faces()
+        .add_smooth_normals(PI / 3.0, true);
+    let num_faces = mesh.faces().triangle_iter().len();
+
+    mesh.simplify(num_faces / 8);
+    assert!(mesh.faces().len() <= num_faces / 8);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    mesh.positions()
+        .iter()
+        .for_each(|p| assert!(f64::abs(p.to_vec().magnitude() - 1.0) < 0.05));
+    // The normals are interpolated on the moved vertices.
+    mesh.faces().triangle_iter().flatten().for_each(|v| {
+        let (p, n) = (mesh.positions()[v.pos], mesh.normals()[v.nor.unwrap()]);
+        assert!(p.to_vec().normalize().dot(n) > 0.99);
+    });
+}
+
+#[test]
+fn cluster_sphere_vertices() {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 64, 33);
+    mesh.put_together_same_attrs()
+        .remove_degenerate_faces()
+        .add_smooth_normals(PI / 3.0, true);
+    let num_faces = mesh.faces().triangle_iter().len();
+
+    let cell_size = 0.2;
+    mesh.cluster_vertices(cell_size);
+    let num_clustered = mesh.faces().len();
+    assert!(num_clustered < num_faces / 4, "{num_clustered} {num_faces}");
+    // each vertex is the centroid of the vertices in a cell.
+    let max_dist = cell_size * f64::sqrt(3.0);
+    mesh.positions().iter().for_each(|p| {
+        let dist = p.to_vec().magnitude();
+        assert!(1.0 - max_dist < dist && dist <= 1.0 + TOLERANCE);
+    });
+    // the normals are kept on the corners.
+    mesh.faces().triangle_iter().flatten().for_each(|v| {
+        let (p, n) = (mesh.positions()[v.pos], mesh.normals()[v.nor.unwrap()]);
+        assert!(p.to_vec().normalize().dot(n) > 0.7);
+    });
+
+    // the coarser cells give the coarser mesh.
+    mesh.cluster_vertices(0.5);
+    assert!(mesh.faces().len() < num_clustered);
+}
+
+/// the rectangle `[0, 2] x [0, 1]` whose uv coordinates are `(x, y)` on the left half
+/// and `(x + 1, y)` on the right half
+fn seamed_rectangle(n: usize) -> PolygonMesh {
+    let positions: Vec<Point3> = (0..=2 * n)
+        .flat_map(|i| (0..=n).map(move |j| Point3::new(i as f64, j as f64, 0.0) / n as f64))
+        .collect();
+    let uv_coords: Vec<Vector2> = positions
+        .iter()
+        .map(|p| Vector2::new(p.x, p.y))
+        .chain(positions.iter().map(|p| Vector2::new(p.x + 1.0, p.y)))
+        .collect();
+    let offset = positions.len();
+    let faces: Faces = (0..2 * n * n)
+        .map(|k| {
+            let (i, j) = (k / n, k % n);
+            let uv_offset = if i < n { 0 } else { offset };
+            let v = |i: usize, j: usize| {
+                let pos = i * (n + 1) + j;
+                StandardVertex {
+                    pos,
+                    uv: Some(pos + uv_offset),
+                    nor: None,
+                }
+            };
+            [v(i, j), v(i + 1, j), v(i + 1, j + 1), v(i, j + 1)]
+        })
+        .collect();
+    PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            uv_coords,
+            ..Default::default()
+        },
+        faces,
+    )
+}
+
+#[test]
+fn simplify_keeps_seams() {
+    let mut mesh = seamed_rectangle(8);
+    mesh.simplify(16);
+    assert!(mesh.faces().len() <= 16);
+
+    let area: f64 = mesh
+        .faces()
+        .triangle_iter()
+        .map(|tri| {
+            let [a, b, c] = tri.map(|v| mesh.positions()[v.pos]);
+            (b - a).cross(c - a).z / 2.0
+        })
+        .sum();
+    assert!(area.near(&2.0));
+    mesh.faces().triangle_iter().for_each(|tri| {
+        let p = tri.map(|v| mesh.positions()[v.pos]);
+        let right = (p[0].x + p[1].x + p[2].x) / 3.0 > 1.0;
+        // no triangles stride over the seam
+        assert!(p.iter().all(|p| match right {
+            true => p.x >= 1.0 - TOLERANCE,
+            false => p.x <= 1.0 + TOLERANCE,
+        }));
+        tri.iter().zip(p).for_each(|(v, p)| {
+            let uv = mesh.uv_coords()[v.uv.unwrap()];
+            let shift = if right { 1.0 } else { 0.0 };
+            assert!(uv.near(&Vector2::new(p.x + shift, p.y)), "{uv:?} {p:?}");
+        });
+    });
+}
@@ -54,6 +54,51 @@ fn triangulate_test() {
     assert_eq!(tri_mesh.faces(), quad_mesh.faces());
 }
 
+#[test]
+fn triangulate_concave() {
+    // a comb-shaped polygon with 4 teeth in the xy-plane
+    let positions: Vec<Point3> = [
+        (0.0, 0.0),
+        (7.0, 0.0),
+        (7.0, 3.0),
+        (6.0, 3.0),
+        (6.0, 1.0),
+        (5.0, 1.0),
+        (5.0, 3.0),
+        (4.0, 3.0),
+        (4.0, 1.0),
+        (3.0, 1.0),
+        (3.0, 3.0),
+        (2.0, 3.0),
+        (2.0, 1.0),
+        (1.0, 1.0),
+        (1.0, 3.0),
+        (0.0, 3.0),
+    ]
+    .iter()
+    .map(|(x, y)| Point3::new(*x, *y, 0.0))
+    .collect();
+    let face: Vec<usize> = (0..positions.len()).collect();
+    let mut mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        Faces::from_iter([face]),
+    );
+    mesh.triangulate();
+    assert_eq!(mesh.faces().len(), 14);
+    let mut area = 0.0;
+    mesh.tri_faces().iter().for_each(|tri| {
+        let [a, b, c] = tri.map(|v| mesh.positions()[v.pos]);
+        let z = (b - a).cross(c - a).z;
+        // no triangles are flipped, i.e. outside the polygon
+        assert!(z > 0.0);
+        area += z / 2.0;
+    });
+    assert!(f64::abs(area - 15.0) < 1.0e-10);
+}
+
 #[test]
 fn quadrangulate_test() {
     // cube consisting quad faces
@@ -0,0 +1,41 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+
+/// the points on the unit sphere by the Fibonacci lattice
+fn fibonacci_sphere(num: usize) -> Vec<Point3> {
+    let golden_angle = PI * (3.0 - f64::sqrt(5.0));
+    (0..num)
+        .map(|i| {
+            let z = 1.0 - 2.0 * (i as f64 + 0.5) / num as f64;
+            let r = f64::sqrt(1.0 - z * z);
+            let theta = golden_angle * i as f64;
+            Point3::new(r * theta.cos(), r * theta.sin(), z)
+        })
+        .collect()
+}
+
+#[test]
+fn estimate_sphere_normals() {
+    let points = fibonacci_sphere(1000);
+    let normals = points.estimate_normals(10);
+    points.iter().zip(&normals).for_each(|(p, n)| {
+        assert!(n.magnitude().near(&1.0));
+        assert!(n.dot(p.to_vec()) > 0.99, "{p:?} {n:?}");
+    });
+}
+
+#[test]
+fn ball_pivoting_sphere() {
+    let points = fibonacci_sphere(1000);
+    let normals = points.estimate_normals(10);
+    let mesh = points.ball_pivoting(&normals, 0.15);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    // Euler's formula of the sphere: V - E + F = 2 and 3F = 2E
+    assert_eq!(mesh.faces().len(), 2 * points.len() - 4);
+    mesh.faces().triangle_iter().for_each(|tri| {
+        let p = tri.map(|v| mesh.positions()[v.pos]);
+        let normal = (p[1] - p[0]).cross(p[2] - p[0]);
+        assert!(normal.dot(p[0].to_vec()) > 0.0);
+    });
+}
@@ -0,0 +1,58 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_modeling::{builder, Solid};
+use truck_topology::shell::ShellCondition;
+
+#[test]
+fn stationary_sphere() {
+    let v = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+    let wire = builder::rsweep(&v, Point3::origin(), Vector3::unit_y(), Rad(PI));
+    let shell = builder::cone(&wire, Vector3::unit_z(), Rad(7.0));
+    let sphere = Solid::new(vec![shell]);
+
+    let mesh = sphere.swept_volume(&[Matrix4::identity()], 0.05);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    let volume = mesh.mass_properties().unwrap().volume;
+    assert!(f64::abs(volume - 4.0 * PI / 3.0) < 0.1, "{volume}");
+    mesh.positions().iter().for_each(|p| {
+        let dist = p.to_vec().magnitude();
+        assert!(f64::abs(dist - 1.0) < 0.05, "{p:?}");
+    });
+}
+
+#[test]
+fn rotating_rod() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::new(2.0, 0.0, 0.0));
+    let f = builder::tsweep(&e, Vector3::new(0.0, 0.2, 0.0));
+    let rod = builder::tsweep(&f, Vector3::new(0.0, 0.0, 0.2));
+    // the quarter turn around the z-axis by 1 degree
+    let poses: Vec<Matrix4> = (0..=90)
+        .map(|i| Matrix4::from_angle_z(Deg(i as f64)))
+        .collect();
+
+    let mesh = rod.swept_volume(&poses, 0.05);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    // the quarter of the disk with the radius 2 and the height 0.2, and the rod at the end
+    let volume = mesh.mass_properties().unwrap().volume;
+    let expected = PI * 4.0 / 4.0 * 0.2 + 2.0 * 0.2 * 0.2;
+    assert!(
+        f64::abs(volume - expected) < 0.05 * expected,
+        "{volume} {expected}"
+    );
+    mesh.positions().iter().for_each(|p| {
+        assert!(Point2::new(p.x, p.y).to_vec().magnitude() < f64::sqrt(4.04) + 0.05);
+        assert!(-0.05 < p.z && p.z < 0.25);
+    });
+}
+
+#[test]
+fn empty_poses() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let mesh = cube.swept_volume(&[], 0.1);
+    assert!(mesh.positions().is_empty());
+    assert_eq!(mesh.faces().len(), 0);
+}
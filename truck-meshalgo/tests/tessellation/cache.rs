@@ -0,0 +1,144 @@
+use super::*;
+
+#[test]
+fn cached_triangulation_is_same() {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+
+    let mut cache = TessellationCache::new();
+    let cached = cache.triangulate_solid(&cylinder, 0.01);
+    let stats = cache.stats();
+    assert_eq!(stats.face_hits + stats.edge_hits, 0);
+    assert_eq!(stats.face_misses, cylinder.face_iter().count());
+    assert_eq!(
+        stats.edge_misses,
+        cylinder.edge_iter().collect::<Vec<_>>().len() / 2
+    );
+    assert_eq!(cache.len(), (stats.edge_misses, stats.face_misses));
+
+    let direct = cylinder.triangulation(0.01);
+    cached
+        .face_iter()
+        .zip(direct.face_iter())
+        .for_each(|(face0, face1)| {
+            let (poly0, poly1) = (face0.get_surface().unwrap(), face1.get_surface().unwrap());
+            assert_eq!(poly0.positions(), poly1.positions());
+            assert_eq!(poly0.faces().len(), poly1.faces().len());
+            assert_eq!(face0.orientation(), face1.orientation());
+        });
+
+    // the second tessellation reuses all elements.
+    cache.reset_stats();
+    let reused = cache.triangulate_solid(&cylinder, 0.01).to_polygon();
+    let stats = cache.stats();
+    assert_eq!(stats.face_misses + stats.edge_misses, 0);
+    assert_eq!(reused.positions(), cached.to_polygon().positions());
+
+    // the side face is re-tessellated after the surface is replaced.
+    let shell = &cylinder.boundaries()[0];
+    let side = shell
+        .face_iter()
+        .find(|face| face.boundaries()[0].len() == 4)
+        .unwrap();
+    side.set_surface(side.get_surface());
+    cache.invalidate_face(side.id());
+    cache.reset_stats();
+    cache.triangulate_solid(&cylinder, 0.01);
+    assert_eq!(cache.stats().face_misses, 1);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn dirty_tracking_after_local_edit() {
+    // the cube punched by the square hole
+    let square = |min: f64, max: f64| {
+        let points =
+            [(min, min), (max, min), (max, max), (min, max)].map(|(x, y)| Point3::new(x, y, 0.0));
+        builder::polyline(&points, true)
+    };
+    let wires = [square(0.0, 3.0), square(1.0, 2.0).inverse()];
+    let solid = builder::try_tsweep_capped(&wires, Vector3::unit_z()).unwrap();
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.len(), 10);
+
+    let mut cache = TessellationCache::new();
+    cache.triangulate_solid(&solid, 0.01);
+    assert!(shell.face_iter().all(|face| !cache.is_face_dirty(face)));
+
+    // edits a lateral face of the hole and an edge of the outer boundary of the top face.
+    let hole_face = shell
+        .face_iter()
+        .find(|face| {
+            let pt = face.oriented_surface().subs(0.5, 0.5);
+            pt.x.near(&1.0) && pt.z.near(&0.5)
+        })
+        .unwrap();
+    hole_face.set_surface(hole_face.get_surface());
+    let top = shell.face_iter().last().unwrap();
+    let edge = top.boundaries()[0][0].clone();
+    edge.set_curve(edge.get_curve());
+
+    assert!(cache.is_edge_dirty(&edge));
+    let dirty_edges: Vec<_> = solid
+        .edge_iter()
+        .filter(|edge| cache.is_edge_dirty(edge))
+        .map(|edge| edge.id())
+        .collect();
+    assert!(dirty_edges.iter().all(|id| *id == edge.id()));
+    let dirty_faces = shell.face_iter().filter(|face| cache.is_face_dirty(face));
+    assert_eq!(dirty_faces.count(), 3);
+
+    cache.reset_stats();
+    let mesh = cache.triangulate_solid(&solid, 0.01).to_polygon();
+    let stats = cache.stats();
+    assert_eq!((stats.edge_misses, stats.face_misses), (1, 3));
+    assert_eq!(stats.face_hits, 7);
+    assert!(shell.face_iter().all(|face| !cache.is_face_dirty(face)));
+    let direct = solid.triangulation(0.01).to_polygon();
+    assert_eq!(mesh.positions().len(), direct.positions().len());
+
+    // the shapes replacing the dropped ones are never confused with them.
+    drop(solid);
+    (1..=3).for_each(|i| {
+        let size = i as f64;
+        let solid = builder::tsweep_solid(&square(0.0, size), Vector3::unit_z() * size).unwrap();
+        let mesh = cache.triangulate_solid(&solid, 0.01).to_polygon();
+        let bdb = mesh.positions().iter().collect::<BoundingBox<_>>();
+        assert_near!(*bdb.max(), Point3::new(size, size, size));
+    });
+}
+
+#[test]
+fn dirty_tracking_after_moving_vertex() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    let shell = &cube.boundaries()[0];
+
+    let mut cache = TessellationCache::new();
+    cache.triangulate_solid(&cube, 0.01);
+    assert!(cube.edge_iter().all(|edge| !cache.is_edge_dirty(&edge)));
+
+    // moving a vertex makes the three edges and the three faces adjacent to it dirty.
+    let vertex = cube.vertex_iter().last().unwrap();
+    vertex.set_point(vertex.get_point() + Vector3::new(0.1, 0.1, 0.1));
+    let dirty_edges = shell
+        .edge_iter()
+        .filter(|edge| cache.is_edge_dirty(edge))
+        .map(|edge| edge.id())
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(dirty_edges.len(), 3);
+    let dirty_faces = shell.face_iter().filter(|face| cache.is_face_dirty(face));
+    assert_eq!(dirty_faces.count(), 3);
+
+    cache.reset_stats();
+    cache.triangulate_solid(&cube, 0.01);
+    let stats = cache.stats();
+    assert_eq!((stats.edge_misses, stats.face_misses), (3, 3));
+    assert!(shell.face_iter().all(|face| !cache.is_face_dirty(face)));
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+#[test]
+fn memory_usage_by_tolerance() {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+
+    let compressed = cylinder.compress();
+    assert!(compressed.deep_size() < cylinder.deep_size());
+
+    let coarse = cylinder.triangulation(0.1);
+    let fine = cylinder.triangulation(0.001);
+    assert!(coarse.deep_size() < fine.deep_size());
+    assert!(coarse.compress().deep_size() < fine.compress().deep_size());
+
+    let (coarse, fine) = (coarse.to_polygon(), fine.to_polygon());
+    assert!(coarse.deep_size() < fine.deep_size());
+    let expected = fine.positions().len() * std::mem::size_of::<Point3>()
+        + fine.tri_faces().len() * std::mem::size_of::<[StandardVertex; 3]>();
+    assert!(fine.heap_size() >= expected);
+}
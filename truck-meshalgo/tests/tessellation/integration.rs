@@ -0,0 +1,64 @@
+use super::*;
+use std::f64::consts::PI;
+
+fn cylinder(radius: f64, height: f64) -> Solid {
+    let v = builder::vertex(Point3::new(radius, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    builder::tsweep(&disk, Vector3::new(0.0, 0.0, height))
+}
+
+#[test]
+fn punched_square_area() {
+    let v = builder::vertex(Point3::new(0.0, 0.0, 0.0));
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let square = builder::tsweep(&e, Vector3::unit_y());
+    let v = builder::vertex(Point3::new(0.75, 0.5, 0.0));
+    let circle = builder::rsweep(&v, Point3::new(0.5, 0.5, 0.0), -Vector3::unit_z(), Rad(7.0));
+    let mut boundaries = square.boundaries();
+    boundaries.push(circle);
+    let face = builder::try_attach_plane(&boundaries).unwrap();
+
+    let area = integrate_over_face(&face, |_, _| 1.0, 1.0e-4).unwrap();
+    let expected = 1.0 - PI / 16.0;
+    assert!(f64::abs(area - expected) < 1.0e-3, "{area} {expected}");
+    // the centroid is at the center by the symmetry.
+    let moment = integrate_over_face(&face, |pt, _| pt.to_vec(), 1.0e-4).unwrap();
+    assert!((moment / area - Vector3::new(0.5, 0.5, 0.0)).magnitude() < 1.0e-3);
+}
+
+#[test]
+fn cylinder_integrals() {
+    let cylinder = cylinder(1.0, 2.0);
+    let shell = &cylinder.boundaries()[0];
+    let area = shell
+        .face_iter()
+        .map(|face| integrate_over_face(face, |_, _| 1.0, 1.0e-4).unwrap())
+        .sum::<f64>();
+    assert!(f64::abs(area - 6.0 * PI) < 1.0e-2, "{area}");
+
+    // the flux of the field `(x, y, z)` is three times the volume.
+    let flux = shell
+        .face_iter()
+        .map(|face| integrate_over_face(face, |pt, normal| pt.to_vec().dot(normal), 1.0e-4))
+        .sum::<Option<f64>>()
+        .unwrap();
+    assert!(f64::abs(flux - 6.0 * PI) < 1.0e-2, "{flux}");
+    let volume = integrate_over_solid(&cylinder, |_| 1.0, 1.0e-4).unwrap();
+    assert!(f64::abs(volume - 2.0 * PI) < 1.0e-2, "{volume}");
+
+    // the moment of inertia around the z axis
+    let inertia = integrate_over_solid(&cylinder, |pt| pt.x * pt.x + pt.y * pt.y, 1.0e-4).unwrap();
+    assert!(f64::abs(inertia - PI) < 1.0e-2, "{inertia}");
+}
+
+#[test]
+fn inverted_face_integral() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let face = builder::tsweep(&e, Vector3::unit_y());
+    let normal = integrate_over_face(&face, |_, normal| normal, 1.0e-4).unwrap();
+    assert!(normal.near(&Vector3::unit_z()));
+    let normal = integrate_over_face(&face.inverse(), |_, normal| normal, 1.0e-4).unwrap();
+    assert!(normal.near(&-Vector3::unit_z()));
+}
@@ -1,4 +1,9 @@
 use truck_meshalgo::prelude::*;
 use truck_modeling::*;
 
+mod cache;
+mod deep_size;
+mod integration;
+mod picking;
 mod triangulation;
+mod wireframe;
@@ -0,0 +1,86 @@
+use super::*;
+
+fn unit_cube() -> Solid {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    builder::tsweep(&f, Vector3::unit_z())
+}
+
+#[test]
+fn pick_cube_elements() {
+    let cube = unit_cube();
+    let shell = &cube.boundaries()[0];
+    let picker = ShapePicker::new(shell, 0.01);
+    let dir = -Vector3::unit_z();
+
+    // the edge on y = 0 of the top face
+    let (element, point) = picker.pick(Point3::new(0.5, 0.02, 3.0), dir, 0.05).unwrap();
+    assert!(point.near(&Point3::new(0.5, 0.0, 1.0)), "{point:?}");
+    match element {
+        PickedElement::Edge(id) => {
+            let edge = shell.edge_iter().find(|edge| edge.id() == id).unwrap();
+            let (p, q) = (edge.front().get_point(), edge.back().get_point());
+            assert!(p.y.so_small() && q.y.so_small());
+            assert!(p.z.near(&1.0) && q.z.near(&1.0));
+        }
+        _ => panic!("the edge must be picked: {element:?}"),
+    }
+
+    // out of the snapping radius, the top face is picked.
+    let (element, _) = picker.pick(Point3::new(0.5, 0.2, 3.0), dir, 0.05).unwrap();
+    assert!(matches!(element, PickedElement::Face(_)));
+
+    // the vertex on the bottom is hidden by the top face.
+    let origin = Point3::new(0.5, 0.5, 3.0);
+    let (element, point) = picker
+        .pick(origin, Point3::origin() - origin, 0.05)
+        .unwrap();
+    assert!(
+        point.near(&Point3::new(1.0 / 6.0, 1.0 / 6.0, 1.0)),
+        "{point:?}"
+    );
+    assert!(matches!(element, PickedElement::Face(_)));
+
+    // the ray from the inside picks the elements of the nearest face.
+    let origin = Point3::new(0.5, 0.5, 0.5);
+    let (element, point) = picker.pick(origin, Vector3::unit_x(), 0.05).unwrap();
+    assert!(point.near(&Point3::new(1.0, 0.5, 0.5)));
+    let id = match element {
+        PickedElement::Face(id) => id,
+        _ => panic!("the face must be picked: {element:?}"),
+    };
+    let face = shell.face_iter().find(|face| face.id() == id).unwrap();
+    let bdd_box: BoundingBox<Point3> = face.boundaries()[0]
+        .vertex_iter()
+        .map(|v| v.get_point())
+        .collect();
+    assert!(bdd_box.min().x.near(&1.0) && bdd_box.max().x.near(&1.0));
+
+    // the ray backward from the cube
+    assert!(picker
+        .pick(Point3::new(0.5, 0.5, 3.0), -dir, 0.05)
+        .is_none());
+}
+
+#[test]
+fn pick_cylinder_side() {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+    let shell = &cylinder.boundaries()[0];
+    let picker = ShapePicker::new(shell, 0.001);
+
+    let (element, point) = picker
+        .pick(Point3::new(3.0, 0.1, 0.5), -Vector3::unit_x(), 0.01)
+        .unwrap();
+    assert!((point.x * point.x + point.y * point.y - 1.0).abs() < 0.01);
+    assert!(point.z.near(&0.5));
+    let id = match element {
+        PickedElement::Face(id) => id,
+        _ => panic!("the face must be picked: {element:?}"),
+    };
+    let face = shell.face_iter().find(|face| face.id() == id).unwrap();
+    assert_eq!(face.boundaries()[0].len(), 4);
+}
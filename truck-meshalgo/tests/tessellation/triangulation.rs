@@ -48,3 +48,116 @@ fn large_number_meshing() {
     let torus: Solid = serde_json::from_slice(json.as_slice()).unwrap();
     let _ = torus.triangulation(1.0).to_polygon();
 }
+
+#[test]
+fn dyn_geometry_meshing() {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+    // the geometries are given by the trait objects.
+    let cylinder = cylinder.mapped(
+        |point| *point,
+        |curve| DynCurve::new(curve.clone()),
+        |surface| DynSurface::new(surface.clone()),
+    );
+    let mut poly = cylinder.triangulation(0.01).to_polygon();
+    poly.put_together_same_attrs();
+    assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+    poly.positions().iter().for_each(|p| {
+        let r = Vector2::new(p.x, p.y).magnitude();
+        assert!(r < 1.0 + 1.0e-3, "{p:?}");
+        assert!((-1.0e-3..=1.0 + 1.0e-3).contains(&p.z), "{p:?}");
+    });
+}
+
+#[test]
+fn face_tolerance_overrides() {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+    let face_tolerance = |i: usize| [0, 2].contains(&i).then_some(0.005);
+
+    let meshed = cylinder.triangulation_with_face_tolerances(0.05, face_tolerance);
+    let mut poly = meshed.to_polygon();
+    poly.put_together_same_attrs();
+    assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+
+    // the compressed solid is tessellated in the same way.
+    let compressed = cylinder.compress();
+    let cmeshed = compressed.triangulation_with_face_tolerances(0.05, face_tolerance);
+    let cpoly = cmeshed.to_polygon();
+    assert_eq!(
+        cpoly.tri_faces().len(),
+        meshed.to_polygon().tri_faces().len()
+    );
+
+    // no overrides is the same as the uniform tolerance.
+    let uniform = cylinder.triangulation(0.05).to_polygon();
+    let overridden = cylinder.triangulation_with_face_tolerances(0.05, |_| None);
+    assert_eq!(
+        overridden.to_polygon().tri_faces().len(),
+        uniform.tri_faces().len()
+    );
+    assert!(poly.tri_faces().len() > uniform.tri_faces().len());
+}
+
+#[test]
+fn watertight_certification() {
+    // the cube punched by the cylinder, and the torus
+    let v = builder::vertex(Point3::new(-1.0, -1.0, 0.0));
+    let e = builder::tsweep(&v, Vector3::new(2.0, 0.0, 0.0));
+    let mut square = builder::tsweep(&e, Vector3::new(0.0, 2.0, 0.0));
+    let v = builder::vertex(Point3::new(0.5, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    square.add_boundary(circle.inverse());
+    let punched_cube: Solid = builder::tsweep(&square, Vector3::new(0.0, 0.0, 2.0));
+    let v = builder::vertex(Point3::new(1.5, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::new(1.0, 0.0, 0.0), Vector3::unit_y(), Rad(7.0));
+    let torus = Solid::new(vec![builder::rsweep(
+        &circle,
+        Point3::origin(),
+        Vector3::unit_z(),
+        Rad(7.0),
+    )]);
+    for (i, solid) in [punched_cube, torus].iter().enumerate() {
+        let (poly, report) = solid.triangulation(0.02).to_watertight_polygon();
+        assert!(report.source_closed, "not closed source: shape no. {i}");
+        assert!(
+            report.is_watertight(),
+            "not watertight: shape no. {i}\n{report:?}"
+        );
+        assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+    }
+}
+
+#[test]
+fn watertight_repairs_seam() {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+    let mut meshed = cylinder.triangulation(0.05);
+    // open a crack between the faces by shifting the boundary of a face mesh
+    let face = &meshed.boundaries()[0][0];
+    let mut poly = face.get_surface().unwrap();
+    let boundary = poly.extract_boundaries();
+    let editor = poly.editor();
+    boundary.iter().flatten().for_each(|&i| {
+        editor.attributes.positions[i] += Vector3::new(1.0e-3, -1.0e-3, 1.0e-3);
+    });
+    drop(editor);
+    face.set_surface(Some(poly));
+    let mut cracked = meshed.to_polygon();
+    cracked.put_together_same_attrs();
+    assert_ne!(cracked.shell_condition(), ShellCondition::Closed);
+
+    let (poly, report) = meshed.to_watertight_polygon();
+    assert!(report.snapped_positions > 0);
+    assert!(report.is_watertight(), "{report:?}");
+    assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+    meshed = cylinder.triangulation(0.05);
+    let (_, report) = meshed.to_watertight_polygon();
+    assert_eq!(report.snapped_positions, 0);
+}
@@ -0,0 +1,50 @@
+use super::*;
+use std::f64::consts::PI;
+
+fn square_wire(min: f64, max: f64) -> Wire {
+    let v = [
+        builder::vertex(Point3::new(min, min, 0.0)),
+        builder::vertex(Point3::new(max, min, 0.0)),
+        builder::vertex(Point3::new(max, max, 0.0)),
+        builder::vertex(Point3::new(min, max, 0.0)),
+    ];
+    (0..4)
+        .map(|i| builder::line(&v[i], &v[(i + 1) % 4]))
+        .collect()
+}
+
+#[test]
+fn punched_plane_iso_lines() {
+    let hole = square_wire(0.25, 0.75).inverse();
+    let face = builder::try_attach_plane(&[square_wire(0.0, 1.0), hole]).unwrap();
+    let shell: Shell = vec![face].into();
+    assert_eq!(shell.extract_wireframe(0.01).len(), 8);
+
+    // the iso-parameter line through the hole is divided into two lines.
+    let wireframe = shell.extract_wireframe_with_iso_lines(0.01, (1, 0));
+    assert_eq!(wireframe.len(), 10);
+    wireframe[8..].iter().for_each(|line| {
+        let (front, back) = (line[0], line[line.len() - 1]);
+        let length = front.distance(back);
+        assert!((0.25 - 1.0e-3..=0.25).contains(&length), "{length}");
+        line.iter().for_each(|p| assert!(p.z.so_small()));
+    });
+}
+
+#[test]
+fn cylinder_iso_lines() {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(2.0 * PI));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let cylinder = builder::tsweep(&disk, Vector3::unit_z());
+
+    let wireframe = cylinder.extract_wireframe_with_iso_lines(0.01, (3, 3));
+    let edges = cylinder.extract_wireframe(0.01);
+    assert!(wireframe.len() > edges.len());
+    wireframe.iter().flat_map(|line| line.iter()).for_each(|p| {
+        let r = Vector2::new(p.x, p.y).magnitude();
+        let on_disk = (p.z.so_small() || p.z.near(&1.0)) && r <= 1.0 + 1.0e-3;
+        let on_side = (-1.0e-3..=1.0 + 1.0e-3).contains(&p.z) && r > 1.0 - 0.01;
+        assert!(on_disk || on_side, "{p:?}");
+    });
+}
@@ -0,0 +1,83 @@
+use std::cell::Cell;
+use truck_meshalgo::prelude::*;
+use truck_modeling::{builder, Solid, Wire};
+#[path = "../common/mod.rs"]
+mod common;
+
+#[test]
+fn star_sphere() {
+    let mut mesh = common::shapes::sphere(Point3::new(1.0, 2.0, 3.0), 1.0, 16, 8);
+    mesh.weld(1.0e-6).remove_degenerate_faces();
+    let tet = mesh.tetrahedralize(&StarMesher, &0.1).unwrap();
+    assert!(tet.is_positively_oriented());
+    let volume = mesh.mass_properties().unwrap().volume;
+    assert!(tet.volume().near(&volume));
+    let boundary = tet.boundary();
+    assert_eq!(boundary.faces().len(), mesh.faces().triangle_iter().count());
+    assert!(boundary.mass_properties().unwrap().volume.near(&volume));
+}
+
+#[test]
+fn not_star_shaped() {
+    // the U-shaped solid, whose centroid is out of the solid.
+    let points = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(3.0, 0.0, 0.0),
+        Point3::new(3.0, 3.0, 0.0),
+        Point3::new(2.0, 3.0, 0.0),
+        Point3::new(2.0, 1.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(1.0, 3.0, 0.0),
+        Point3::new(0.0, 3.0, 0.0),
+    ];
+    let v = points.map(builder::vertex);
+    let wire: Wire = (0..v.len())
+        .map(|i| builder::line(&v[i], &v[(i + 1) % v.len()]))
+        .collect();
+    let face = builder::try_attach_plane(&[wire]).unwrap();
+    let solid: Solid = builder::tsweep(&face, Vector3::unit_z());
+    let mut mesh = solid.boundaries()[0].triangulation(0.01).to_polygon();
+    mesh.put_together_same_attrs().remove_degenerate_faces();
+    assert!(mesh.mass_properties().unwrap().volume.near(&7.0));
+    assert!(matches!(
+        mesh.tetrahedralize(&StarMesher, &0.1),
+        Err(TetrahedralizationError::Mesher(_)),
+    ));
+}
+
+/// the backend recording the input
+#[derive(Default)]
+struct Recorder {
+    triangles: Cell<usize>,
+    size: Cell<f64>,
+}
+
+impl TetMesher for Recorder {
+    fn generate(
+        &self,
+        input: TetMeshInput<'_>,
+    ) -> Result<TetrahedralMesh, TetrahedralizationError> {
+        self.triangles.set(input.triangles.len());
+        self.size.set(input.sizing.size(input.positions[0]));
+        Err(TetrahedralizationError::Mesher("not implemented".into()))
+    }
+}
+
+#[test]
+fn custom_backend() {
+    let mut mesh = common::shapes::sphere(Point3::origin(), 1.0, 16, 8);
+    mesh.weld(1.0e-6).remove_degenerate_faces();
+    let recorder = Recorder::default();
+    let sizing = |p: Point3| 0.1 + p.z;
+    let res = mesh.tetrahedralize(&recorder, &sizing);
+    assert_eq!(
+        res,
+        Err(TetrahedralizationError::Mesher("not implemented".into()))
+    );
+    assert_eq!(
+        recorder.triangles.get(),
+        mesh.faces().triangle_iter().count()
+    );
+    let top = mesh.positions()[0];
+    assert_eq!(recorder.size.get(), 0.1 + top.z);
+}
@@ -0,0 +1,527 @@
+use crate::*;
+use std::f64::consts::PI;
+use truck_base::eigen::symmetric_eigen;
+
+const SAMPLE_DIVISION: usize = 12;
+
+/// Analytic surfaces recognized from free-form surfaces by [`AnalyticSurface::recognize`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnalyticSurface {
+    /// plane
+    Plane {
+        /// a point on the plane
+        origin: Point3,
+        /// the unit normal vector, which has the same direction as the original surface
+        normal: Vector3,
+    },
+    /// cylinder
+    Cylinder {
+        /// a point on the axis
+        origin: Point3,
+        /// the unit direction of the axis
+        axis: Vector3,
+        /// the radius
+        radius: f64,
+    },
+    /// sphere
+    Sphere {
+        /// the center
+        center: Point3,
+        /// the radius
+        radius: f64,
+    },
+    /// cone
+    Cone {
+        /// the apex
+        apex: Point3,
+        /// the unit direction of the axis, toward which the cone opens
+        axis: Vector3,
+        /// the angle between the axis and the generating lines
+        half_angle: f64,
+    },
+    /// torus
+    Torus {
+        /// the center
+        center: Point3,
+        /// the unit direction of the axis
+        axis: Vector3,
+        /// the distance from the center to the centers of the tube
+        major_radius: f64,
+        /// the radius of the tube
+        minor_radius: f64,
+    },
+}
+
+/// Samples of a surface normalized in the unit ball.
+#[derive(Clone, Debug)]
+struct Samples {
+    center: Point3,
+    scale: f64,
+    points: Vec<Point3>,
+    normals: Vec<Vector3>,
+}
+
+impl Samples {
+    fn new<S>(surface: &S) -> Option<Self>
+    where S: ParametricSurface3D + BoundedSurface {
+        let ((u0, u1), (v0, v1)) = surface.parameter_range();
+        let (points, normals): (Vec<Point3>, Vec<Vector3>) = (0..=SAMPLE_DIVISION)
+            .flat_map(|i| (0..=SAMPLE_DIVISION).map(move |j| (i, j)))
+            .filter_map(|(i, j)| {
+                let u = u0 + (u1 - u0) * i as f64 / SAMPLE_DIVISION as f64;
+                let v = v0 + (v1 - v0) * j as f64 / SAMPLE_DIVISION as f64;
+                let normal = surface.normal(u, v);
+                match normal.magnitude2().is_finite() && normal.magnitude2() > 0.5 {
+                    true => Some((surface.subs(u, v), normal)),
+                    false => None,
+                }
+            })
+            .unzip();
+        if points.len() < 9 {
+            return None;
+        }
+        let center = points.iter().fold(Point3::origin(), |sum, p| {
+            sum + p.to_vec() / points.len() as f64
+        });
+        let scale = points
+            .iter()
+            .map(|p| p.distance(center))
+            .fold(0.0, f64::max);
+        if scale.so_small() {
+            return None;
+        }
+        let points = points
+            .into_iter()
+            .map(|p| Point3::from_vec((p - center) / scale))
+            .collect();
+        Some(Self {
+            center,
+            scale,
+            points,
+            normals,
+        })
+    }
+
+    fn all_within(&self, tol: f64, distance: impl Fn(Point3) -> f64) -> bool {
+        self.points.iter().all(|p| distance(*p).abs() < tol)
+    }
+
+    fn plane(&self, tol: f64) -> Option<(Point3, Vector3)> {
+        let normal = self.normals.iter().sum::<Vector3>();
+        if normal.so_small() {
+            return None;
+        }
+        let normal = normal.normalize();
+        let d = self
+            .points
+            .iter()
+            .map(|p| p.to_vec().dot(normal))
+            .sum::<f64>()
+            / self.points.len() as f64;
+        let ok = self.all_within(tol, |p| p.to_vec().dot(normal) - d);
+        ok.then(|| (Point3::from_vec(normal * d), normal))
+    }
+
+    /// The least squares point of the normal lines projected by `projection`.
+    fn nearest_point(&self, projection: impl Fn(Vector3) -> Matrix3) -> Option<Point3> {
+        let (a, b) = self.points.iter().zip(&self.normals).fold(
+            (Matrix3::zero(), Vector3::zero()),
+            |(a, b), (p, n)| {
+                let mat = projection(*n);
+                (a + mat, b + mat * p.to_vec())
+            },
+        );
+        Some(Point3::from_vec(a.invert()? * b))
+    }
+
+    fn sphere(&self, tol: f64) -> Option<(Point3, f64)> {
+        let center = self.nearest_point(|n| Matrix3::identity() - outer(n, n))?;
+        let radius =
+            self.points.iter().map(|p| p.distance(center)).sum::<f64>() / self.points.len() as f64;
+        let ok = radius < 1.0e3 && self.all_within(tol, |p| p.distance(center) - radius);
+        ok.then_some((center, radius))
+    }
+
+    fn cylinder(&self, tol: f64) -> Option<(Point3, Vector3, f64)> {
+        let n0 = self.normals[0];
+        let axis = self
+            .normals
+            .iter()
+            .map(|n| n0.cross(*n))
+            .max_by(|a, b| a.magnitude2().partial_cmp(&b.magnitude2()).unwrap())?;
+        if axis.magnitude() < 1.0e-3 {
+            return None;
+        }
+        let axis = axis.normalize();
+        if self.normals.iter().any(|n| n.dot(axis).abs() > 1.0e-2) {
+            return None;
+        }
+        // the axial coordinate of the origin is fixed to the one of the centroid.
+        let aat = outer(axis, axis);
+        let len = self.points.len() as f64;
+        let origin = self.nearest_point(|n| Matrix3::identity() - outer(n, n) - aat + aat / len)?;
+        let distance = |p: Point3| {
+            let r = p - origin;
+            (r - r.dot(axis) * axis).magnitude()
+        };
+        let radius =
+            self.points.iter().map(|p| distance(*p)).sum::<f64>() / self.points.len() as f64;
+        let ok = radius < 1.0e3 && self.all_within(tol, |p| distance(p) - radius);
+        ok.then_some((origin, axis, radius))
+    }
+
+    /// Finds the common line intersecting all normal lines by Plücker coordinates.
+    fn revolution_axis(&self) -> Option<(Point3, Vector3)> {
+        let mut mat = [[0.0; 6]; 6];
+        self.points.iter().zip(&self.normals).for_each(|(p, n)| {
+            let m = p.to_vec().cross(*n);
+            let row = [m.x, m.y, m.z, n.x, n.y, n.z];
+            (0..6).for_each(|i| (0..6).for_each(|j| mat[i][j] += row[i] * row[j]));
+        });
+        // the eigenvector of the smallest eigenvalue
+        let v = symmetric_eigen(mat).1[0];
+        let (axis, moment) = (
+            Vector3::new(v[0], v[1], v[2]),
+            Vector3::new(v[3], v[4], v[5]),
+        );
+        if axis.magnitude() < 1.0e-3 {
+            return None;
+        }
+        let (axis, moment) = (axis / axis.magnitude(), moment / axis.magnitude());
+        Some((Point3::from_vec(axis.cross(moment)), axis))
+    }
+
+    /// Returns the pairs of the axial coordinate and the distance from the axis.
+    fn profile(&self, origin: Point3, axis: Vector3) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|p| {
+                let r = p - origin;
+                let h = r.dot(axis);
+                (h, (r - h * axis).magnitude())
+            })
+            .collect()
+    }
+
+    fn cone(&self, tol: f64, origin: Point3, axis: Vector3) -> Option<(Point3, Vector3, f64)> {
+        let profile = self.profile(origin, axis);
+        let len = profile.len() as f64;
+        let (mh, mr) = profile
+            .iter()
+            .fold((0.0, 0.0), |(h, r), (h0, r0)| (h + h0 / len, r + r0 / len));
+        let (shh, shr) = profile.iter().fold((0.0, 0.0), |(shh, shr), (h, r)| {
+            (shh + (h - mh) * (h - mh), shr + (h - mh) * (r - mr))
+        });
+        if shh.so_small2() {
+            return None;
+        }
+        let k = shr / shh;
+        if k.abs() < 1.0e-3 {
+            return None;
+        }
+        let b = mr - k * mh;
+        let ok = profile
+            .iter()
+            .all(|(h, r)| ((r - (k * h + b)) / f64::sqrt(1.0 + k * k)).abs() < tol);
+        let apex = origin - b / k * axis;
+        let axis = if k > 0.0 { axis } else { -axis };
+        ok.then_some((apex, axis, f64::atan(k.abs())))
+    }
+
+    fn torus(&self, tol: f64, origin: Point3, axis: Vector3) -> Option<(Point3, f64, f64)> {
+        let profile = self.profile(origin, axis);
+        // algebraic circle fitting: h^2 + r^2 + d h + e r + f = 0
+        let (a, b) = profile
+            .iter()
+            .fold((Matrix3::zero(), Vector3::zero()), |(a, b), (h, r)| {
+                let row = Vector3::new(*h, *r, 1.0);
+                (a + outer(row, row), b - row * (h * h + r * r))
+            });
+        let x = a.invert()? * b;
+        let (hc, rc) = (-x[0] / 2.0, -x[1] / 2.0);
+        let minor_radius = f64::sqrt(hc * hc + rc * rc - x[2]);
+        if !minor_radius.is_finite() || rc.so_small() {
+            return None;
+        }
+        let ok = profile.iter().all(|(h, r)| {
+            (f64::sqrt((h - hc) * (h - hc) + (r - rc) * (r - rc)) - minor_radius).abs() < tol
+        });
+        ok.then_some((origin + hc * axis, rc, minor_radius))
+    }
+}
+
+fn outer(a: Vector3, b: Vector3) -> Matrix3 { Matrix3::from_cols(a * b.x, a * b.y, a * b.z) }
+
+/// Returns a unit vector perpendicular to `axis`.
+pub(super) fn perpendicular(axis: Vector3) -> Vector3 {
+    let v = if axis.x.abs() < 0.5 {
+        Vector3::unit_x()
+    } else {
+        Vector3::unit_y()
+    };
+    (v - v.dot(axis) * axis).normalize()
+}
+
+impl AnalyticSurface {
+    /// Recognizes the analytic surface whose distances from the sampled points of `surface`
+    /// are less than `tol`. The plane, sphere, cylinder, cone, and torus are tested in order.
+    /// # Examples
+    /// ```
+    /// use truck_modeling::*;
+    /// use truck_modeling::analytic::AnalyticSurface;
+    ///
+    /// // a part of the cylinder with radius 2 as a NURBS surface
+    /// let v = builder::vertex(Point3::new(2.0, 0.0, 0.0));
+    /// let edge = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(1.5));
+    /// let arc = edge[0].get_curve().lift_up();
+    /// let surface = NURBSSurface::new(BSplineSurface::new(
+    ///     (arc.knot_vec().clone(), KnotVec::bezier_knot(1)),
+    ///     arc.control_points()
+    ///         .iter()
+    ///         .map(|p| vec![*p, *p + Vector4::new(0.0, 0.0, 3.0 * p[3], 0.0)])
+    ///         .collect(),
+    /// ));
+    ///
+    /// match AnalyticSurface::recognize(&surface, 1.0e-6).unwrap() {
+    ///     AnalyticSurface::Cylinder { origin, axis, radius } => {
+    ///         assert_near!(radius, 2.0);
+    ///         assert!(axis.cross(Vector3::unit_z()).so_small());
+    ///         assert!(origin.x.so_small() && origin.y.so_small());
+    ///     }
+    ///     other => panic!("{other:?}"),
+    /// }
+    /// ```
+    pub fn recognize<S>(surface: &S, tol: f64) -> Option<Self>
+    where S: ParametricSurface3D + BoundedSurface {
+        let samples = Samples::new(surface)?;
+        let Samples { center, scale, .. } = samples;
+        let tol = tol / scale;
+        let point = |p: Point3| center + p.to_vec() * scale;
+        if let Some((origin, normal)) = samples.plane(tol) {
+            return Some(AnalyticSurface::Plane {
+                origin: point(origin),
+                normal,
+            });
+        }
+        if let Some((c, radius)) = samples.sphere(tol) {
+            return Some(AnalyticSurface::Sphere {
+                center: point(c),
+                radius: radius * scale,
+            });
+        }
+        if let Some((origin, axis, radius)) = samples.cylinder(tol) {
+            return Some(AnalyticSurface::Cylinder {
+                origin: point(origin),
+                axis,
+                radius: radius * scale,
+            });
+        }
+        let (origin, axis) = samples.revolution_axis()?;
+        if let Some((apex, axis, half_angle)) = samples.cone(tol, origin, axis) {
+            return Some(AnalyticSurface::Cone {
+                apex: point(apex),
+                axis,
+                half_angle,
+            });
+        }
+        samples
+            .torus(tol, origin, axis)
+            .map(|(c, major_radius, minor_radius)| AnalyticSurface::Torus {
+                center: point(c),
+                axis,
+                major_radius: major_radius * scale,
+                minor_radius: minor_radius * scale,
+            })
+    }
+
+    /// Creates the analytic `Surface` covering the sampled points of `surface`
+    /// whose normals are directed as `surface`.
+    ///
+    /// Planes are converted to `Surface::Plane`, and the others are converted to `Surface::RevolutedCurve`
+    /// of lines or circle arcs.
+    pub fn to_surface<S>(&self, surface: &S) -> Option<Surface>
+    where S: ParametricSurface3D + BoundedSurface {
+        let ((u0, u1), (v0, v1)) = surface.parameter_range();
+        let (u, v) = ((u0 + u1) / 2.0, (v0 + v1) / 2.0);
+        let (point, normal) = (surface.subs(u, v), surface.normal(u, v));
+        let axial_range = |origin: Point3, axis: Vector3| {
+            let samples = Samples::new(surface)?;
+            let (min, max) = samples
+                .points
+                .iter()
+                .map(|p| (samples.center + p.to_vec() * samples.scale - origin).dot(axis))
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), h| {
+                    (f64::min(min, h), f64::max(max, h))
+                });
+            let margin = (max - min) * 0.01 + TOLERANCE;
+            Some((min - margin, max + margin))
+        };
+        let revolution = |curve: Curve, origin: Point3, axis: Vector3| {
+            Surface::RevolutedCurve(Processor::new(RevolutedCurve::by_revolution(
+                curve, origin, axis,
+            )))
+        };
+        let mut res = match *self {
+            AnalyticSurface::Plane { origin, normal } => {
+                let u_axis = perpendicular(normal);
+                let v_axis = normal.cross(u_axis);
+                let origin = origin + (point - origin) - (point - origin).dot(normal) * normal;
+                return Some(Surface::Plane(Plane::new(
+                    origin,
+                    origin + u_axis,
+                    origin + v_axis,
+                )));
+            }
+            AnalyticSurface::Cylinder {
+                origin,
+                axis,
+                radius,
+            } => {
+                let (h0, h1) = axial_range(origin, axis)?;
+                let e = perpendicular(axis) * radius;
+                let line = Line(origin + h0 * axis + e, origin + h1 * axis + e);
+                revolution(Curve::Line(line), origin, axis)
+            }
+            AnalyticSurface::Cone {
+                apex,
+                axis,
+                half_angle,
+            } => {
+                let (h0, h1) = axial_range(apex, axis)?;
+                let e = perpendicular(axis) * f64::tan(half_angle);
+                let h0 = f64::max(h0, 0.0);
+                let line = Line(apex + h0 * (axis + e), apex + h1 * (axis + e));
+                revolution(Curve::Line(line), apex, axis)
+            }
+            AnalyticSurface::Sphere { center, radius } => {
+                let axis = Vector3::unit_z();
+                let e = perpendicular(axis);
+                let arc = geom_impls::circle_arc(
+                    (center + radius * axis).to_homogeneous(),
+                    center,
+                    axis.cross(e),
+                    Rad(PI),
+                );
+                revolution(Curve::NURBSCurve(NURBSCurve::new(arc)), center, axis)
+            }
+            AnalyticSurface::Torus {
+                center,
+                axis,
+                major_radius,
+                minor_radius,
+            } => {
+                let e = perpendicular(axis);
+                let tube_center = center + major_radius * e;
+                let rotation_axis = axis.cross(e);
+                let arc0 = geom_impls::circle_arc(
+                    (tube_center + minor_radius * e).to_homogeneous(),
+                    tube_center,
+                    rotation_axis,
+                    Rad(PI),
+                );
+                let mut arc1 = geom_impls::circle_arc(
+                    (tube_center - minor_radius * e).to_homogeneous(),
+                    tube_center,
+                    rotation_axis,
+                    Rad(PI),
+                );
+                arc1.knot_translate(1.0);
+                let circle = NURBSCurve::new(arc0)
+                    .try_concat(&NURBSCurve::new(arc1))
+                    .ok()?;
+                revolution(Curve::NURBSCurve(circle), center, axis)
+            }
+        };
+        let (s, t) = res.search_parameter(point, None, 100)?;
+        if res.normal(s, t).dot(normal) < 0.0 {
+            res.invert();
+        }
+        Some(res)
+    }
+}
+
+impl Surface {
+    /// Recognizes the analytic surface nearly coincident with `self` within `tol`.
+    ///
+    /// See [`AnalyticSurface::recognize`] for details.
+    pub fn recognize_analytic(&self, tol: f64) -> Option<AnalyticSurface> {
+        match self {
+            Surface::Plane(plane) => Some(AnalyticSurface::Plane {
+                origin: plane.origin(),
+                normal: plane.normal(),
+            }),
+            Surface::BSplineSurface(surface) => AnalyticSurface::recognize(surface, tol),
+            Surface::NURBSSurface(surface) => AnalyticSurface::recognize(surface, tol),
+            Surface::RevolutedCurve(surface) => AnalyticSurface::recognize(surface, tol),
+            Surface::Custom(surface) => AnalyticSurface::recognize(surface, tol),
+        }
+    }
+
+    /// Converts a B-spline or NURBS surface into the analytic surface nearly coincident with `self`
+    /// within `tol`. Returns `None` if `self` is already analytic or no analytic surface is recognized.
+    /// # Examples
+    /// ```
+    /// use truck_modeling::*;
+    ///
+    /// // a plane represented by a B-spline surface
+    /// let bsp = BSplineSurface::new(
+    ///     (KnotVec::bezier_knot(2), KnotVec::bezier_knot(1)),
+    ///     vec![
+    ///         vec![Point3::new(0.0, 0.0, 1.0), Point3::new(0.0, 1.0, 1.0)],
+    ///         vec![Point3::new(0.5, 0.0, 1.0), Point3::new(0.5, 1.0, 1.0)],
+    ///         vec![Point3::new(1.0, 0.0, 1.0), Point3::new(1.0, 1.0, 1.0)],
+    ///     ],
+    /// );
+    /// let surface = Surface::BSplineSurface(bsp);
+    /// let plane = surface.to_analytic(1.0e-6).unwrap();
+    /// assert!(matches!(plane, Surface::Plane(_)));
+    /// assert_near!(plane.normal(0.3, 0.4), surface.normal(0.5, 0.5));
+    /// ```
+    pub fn to_analytic(&self, tol: f64) -> Option<Surface> {
+        match self {
+            Surface::BSplineSurface(surface) => {
+                AnalyticSurface::recognize(surface, tol)?.to_surface(surface)
+            }
+            Surface::NURBSSurface(surface) => {
+                AnalyticSurface::recognize(surface, tol)?.to_surface(surface)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Replaces the B-spline and NURBS surfaces of `faces` by the analytic surfaces nearly coincident
+/// within `tol`, and returns the number of replaced surfaces.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+/// // imported from NURBS-only sources
+/// cube.face_iter().for_each(|face| match face.get_surface() {
+///     Surface::Plane(plane) => face.set_surface(Surface::BSplineSurface(plane.into_bspline())),
+///     _ => unreachable!(),
+/// });
+///
+/// assert_eq!(analytic::simplify_faces(cube.face_iter(), 1.0e-6), 6);
+/// assert!(cube.face_iter().all(|face| matches!(face.get_surface(), Surface::Plane(_))));
+/// assert!(cube.is_geometric_consistent());
+/// ```
+pub fn simplify_faces<'a, I: IntoIterator<Item = &'a Face>>(faces: I, tol: f64) -> usize {
+    faces
+        .into_iter()
+        .filter(|face| {
+            let surface = face.get_surface();
+            match surface.to_analytic(tol) {
+                Some(analytic) => {
+                    face.set_surface(analytic);
+                    true
+                }
+                None => false,
+            }
+        })
+        .count()
+}
@@ -0,0 +1,265 @@
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use serde::{Deserialize, Serialize};
+
+/// A topological element of a solid to which an annotation is attached.
+///
+/// `shell` is the index of the boundary shell, and `index` is the index of the element in
+/// the compressed shell, i.e. the order in which `Shell::compress` enumerates the elements.
+/// Hence, anchors remain valid through the serialization of the solid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Anchor {
+    /// a vertex of the solid
+    Vertex {
+        /// the index of the boundary shell
+        shell: usize,
+        /// the index of the vertex in the shell
+        index: usize,
+    },
+    /// an edge of the solid
+    Edge {
+        /// the index of the boundary shell
+        shell: usize,
+        /// the index of the edge in the shell
+        index: usize,
+    },
+    /// a face of the solid
+    Face {
+        /// the index of the boundary shell
+        shell: usize,
+        /// the index of the face in the shell
+        index: usize,
+    },
+}
+
+/// Lightweight product manufacturing information attached to a solid.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Annotation {
+    /// the distance between two topological elements
+    LinearDimension {
+        /// the element measured from
+        from: Anchor,
+        /// the element measured to
+        to: Anchor,
+        /// the nominal distance
+        value: f64,
+        /// the lower and upper deviations from the nominal value
+        tolerance: Option<(f64, f64)>,
+    },
+    /// the radius of a circular edge or a cylindrical face
+    Radius {
+        /// the annotated edge or face
+        anchor: Anchor,
+        /// the nominal radius
+        value: f64,
+        /// the lower and upper deviations from the nominal value
+        tolerance: Option<(f64, f64)>,
+    },
+    /// a text note
+    Note {
+        /// the annotated element
+        anchor: Anchor,
+        /// the position where the text is placed
+        position: Point3,
+        /// the text
+        text: String,
+    },
+}
+
+impl Annotation {
+    /// Returns the anchors referred by the annotation.
+    pub fn anchors(&self) -> Vec<Anchor> {
+        match self {
+            Annotation::LinearDimension { from, to, .. } => vec![*from, *to],
+            Annotation::Radius { anchor, .. } => vec![*anchor],
+            Annotation::Note { anchor, .. } => vec![*anchor],
+        }
+    }
+}
+
+/// A solid with annotations, serialized together as one json.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::annotation::*;
+///
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let mut annotated = AnnotatedSolid::new(cube);
+/// let faces: Vec<Face> = annotated.solid.face_iter().cloned().collect();
+/// let bottom = annotated.face_anchor(&faces[0]).unwrap();
+/// let top = annotated.face_anchor(&faces[5]).unwrap();
+/// annotated.annotations.push(Annotation::LinearDimension {
+///     from: bottom,
+///     to: top,
+///     value: 1.0,
+///     tolerance: Some((-0.01, 0.01)),
+/// });
+/// annotated.annotations.push(Annotation::Note {
+///     anchor: top,
+///     position: Point3::new(0.5, 0.5, 1.5),
+///     text: "polish".to_string(),
+/// });
+///
+/// let json = serde_json::to_vec(&annotated).unwrap();
+/// let restored: AnnotatedSolid = serde_json::from_slice(&json).unwrap();
+/// assert!(restored.is_valid());
+/// assert_eq!(restored.annotations, annotated.annotations);
+///
+/// // anchors refer the same elements after the round trip.
+/// let face = restored.face(top).unwrap();
+/// assert!(face
+///     .boundaries()[0]
+///     .vertex_iter()
+///     .all(|v| v.get_point().z.near(&1.0)));
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnnotatedSolid {
+    /// the annotated solid
+    pub solid: Solid,
+    /// the annotations
+    pub annotations: Vec<Annotation>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ShellIndices {
+    vertices: Vec<Vertex>,
+    edges: Vec<Edge>,
+    vmap: HashMap<VertexID, usize>,
+    emap: HashMap<EdgeID, usize>,
+}
+
+impl ShellIndices {
+    /// Enumerates vertices and edges in the same order as `Shell::compress`.
+    fn new(shell: &Shell) -> Self {
+        let mut res = Self::default();
+        shell
+            .iter()
+            .flat_map(Face::absolute_boundaries)
+            .flat_map(|wire| wire.iter())
+            .for_each(|edge| {
+                if res.emap.contains_key(&edge.id()) {
+                    return;
+                }
+                res.emap.insert(edge.id(), res.edges.len());
+                res.edges.push(edge.absolute_clone());
+                [edge.absolute_front(), edge.absolute_back()]
+                    .into_iter()
+                    .for_each(|v| {
+                        if !res.vmap.contains_key(&v.id()) {
+                            res.vmap.insert(v.id(), res.vertices.len());
+                            res.vertices.push(v.clone());
+                        }
+                    });
+            });
+        res
+    }
+}
+
+impl AnnotatedSolid {
+    /// constructor without annotations
+    #[inline(always)]
+    pub fn new(solid: Solid) -> Self {
+        Self {
+            solid,
+            annotations: Vec::new(),
+        }
+    }
+
+    fn indices(&self) -> Vec<ShellIndices> {
+        self.solid
+            .boundaries()
+            .iter()
+            .map(ShellIndices::new)
+            .collect()
+    }
+
+    /// Returns the anchor of `vertex`, or `None` if the solid does not contain `vertex`.
+    pub fn vertex_anchor(&self, vertex: &Vertex) -> Option<Anchor> {
+        self.indices()
+            .iter()
+            .enumerate()
+            .find_map(|(shell, indices)| {
+                let index = *indices.vmap.get(&vertex.id())?;
+                Some(Anchor::Vertex { shell, index })
+            })
+    }
+
+    /// Returns the anchor of `edge`, or `None` if the solid does not contain `edge`.
+    pub fn edge_anchor(&self, edge: &Edge) -> Option<Anchor> {
+        self.indices()
+            .iter()
+            .enumerate()
+            .find_map(|(shell, indices)| {
+                let index = *indices.emap.get(&edge.id())?;
+                Some(Anchor::Edge { shell, index })
+            })
+    }
+
+    /// Returns the anchor of `face`, or `None` if the solid does not contain `face`.
+    pub fn face_anchor(&self, face: &Face) -> Option<Anchor> {
+        self.solid
+            .boundaries()
+            .iter()
+            .enumerate()
+            .find_map(|(shell, faces)| {
+                let index = faces.iter().position(|f| f.id() == face.id())?;
+                Some(Anchor::Face { shell, index })
+            })
+    }
+
+    /// Returns the vertex referred by `anchor`.
+    pub fn vertex(&self, anchor: Anchor) -> Option<Vertex> {
+        match anchor {
+            Anchor::Vertex { shell, index } => {
+                let shell = self.solid.boundaries().get(shell)?;
+                ShellIndices::new(shell).vertices.get(index).cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the edge referred by `anchor`.
+    pub fn edge(&self, anchor: Anchor) -> Option<Edge> {
+        match anchor {
+            Anchor::Edge { shell, index } => {
+                let shell = self.solid.boundaries().get(shell)?;
+                ShellIndices::new(shell).edges.get(index).cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the face referred by `anchor`.
+    pub fn face(&self, anchor: Anchor) -> Option<Face> {
+        match anchor {
+            Anchor::Face { shell, index } => {
+                self.solid.boundaries().get(shell)?.get(index).cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether all anchors of annotations refer elements of the solid.
+    pub fn is_valid(&self) -> bool {
+        let indices = self.indices();
+        let shells = self.solid.boundaries();
+        self.annotations
+            .iter()
+            .flat_map(Annotation::anchors)
+            .all(|anchor| match anchor {
+                Anchor::Vertex { shell, index } => {
+                    shell < indices.len() && index < indices[shell].vertices.len()
+                }
+                Anchor::Edge { shell, index } => {
+                    shell < indices.len() && index < indices[shell].edges.len()
+                }
+                Anchor::Face { shell, index } => {
+                    shell < shells.len() && index < shells[shell].len()
+                }
+            })
+    }
+}
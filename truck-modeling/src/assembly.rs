@@ -0,0 +1,1029 @@
+use crate::*;
+use analytic::AnalyticSurface;
+use errors::Error;
+use truck_base::polygon;
+
+/// A joint connecting a component to its parent.
+///
+/// The joint parameter is the angle in radians for the revolute joints and the distance for
+/// the prismatic joints. The parameter is clamped into `limits` if given.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Joint {
+    /// the component is fixed to the parent
+    Fixed,
+    /// the component rotates around the axis
+    Revolute {
+        /// a point on the axis
+        origin: Point3,
+        /// the direction of the axis, the rotation is counterclockwise looking from its head
+        axis: Vector3,
+        /// the lower and upper limits of the angle
+        limits: Option<(f64, f64)>,
+    },
+    /// the component slides along the axis
+    Prismatic {
+        /// the direction of the sliding, normalized in the evaluation
+        axis: Vector3,
+        /// the lower and upper limits of the distance
+        limits: Option<(f64, f64)>,
+    },
+}
+
+impl Joint {
+    /// Returns the revolute joint without limits.
+    #[inline(always)]
+    pub const fn revolute(origin: Point3, axis: Vector3) -> Self {
+        Joint::Revolute {
+            origin,
+            axis,
+            limits: None,
+        }
+    }
+    /// Returns the prismatic joint without limits.
+    #[inline(always)]
+    pub const fn prismatic(axis: Vector3) -> Self { Joint::Prismatic { axis, limits: None } }
+    /// Returns the joint whose parameter is limited in `[lower, upper]`.
+    /// The fixed joint is returned as it is.
+    #[inline(always)]
+    pub fn with_limits(self, lower: f64, upper: f64) -> Self {
+        match self {
+            Joint::Fixed => Joint::Fixed,
+            Joint::Revolute { origin, axis, .. } => Joint::Revolute {
+                origin,
+                axis,
+                limits: Some((lower, upper)),
+            },
+            Joint::Prismatic { axis, .. } => Joint::Prismatic {
+                axis,
+                limits: Some((lower, upper)),
+            },
+        }
+    }
+    /// Returns the parameter clamped into the limits.
+    #[inline(always)]
+    pub fn clamp(&self, value: f64) -> f64 {
+        match self {
+            Joint::Fixed => 0.0,
+            Joint::Revolute { limits, .. } | Joint::Prismatic { limits, .. } => match limits {
+                Some((lower, upper)) => f64::clamp(value, *lower, *upper),
+                None => value,
+            },
+        }
+    }
+    /// Returns the relative motion of the component in the frame of the joint.
+    /// # Examples
+    /// ```
+    /// use truck_modeling::*;
+    /// use truck_modeling::assembly::Joint;
+    /// use std::f64::consts::PI;
+    ///
+    /// let hinge = Joint::revolute(Point3::new(1.0, 0.0, 0.0), Vector3::unit_z());
+    /// let pose = hinge.pose(PI / 2.0);
+    /// assert_near!(pose.transform_point(Point3::new(2.0, 0.0, 0.0)), Point3::new(1.0, 1.0, 0.0));
+    ///
+    /// let slider = Joint::prismatic(Vector3::new(0.0, 2.0, 0.0)).with_limits(0.0, 1.0);
+    /// let pose = slider.pose(3.0);
+    /// assert_near!(pose.transform_point(Point3::origin()), Point3::new(0.0, 1.0, 0.0));
+    /// ```
+    pub fn pose(&self, value: f64) -> Matrix4 {
+        let value = self.clamp(value);
+        match self {
+            Joint::Fixed => Matrix4::identity(),
+            Joint::Revolute { origin, axis, .. } => {
+                Matrix4::from_translation(origin.to_vec())
+                    * Matrix4::from_axis_angle(axis.normalize(), Rad(value))
+                    * Matrix4::from_translation(-origin.to_vec())
+            }
+            Joint::Prismatic { axis, .. } => Matrix4::from_translation(axis.normalize() * value),
+        }
+    }
+}
+
+/// A solid placed in an assembly.
+#[derive(Clone, Debug)]
+pub struct Component {
+    /// the name of the component
+    pub name: String,
+    /// the solid in the frame of the component
+    pub solid: Solid,
+    /// the index of the parent component, `None` for the roots
+    pub parent: Option<usize>,
+    /// the frame of the component in the frame of the parent at the parameter zero
+    pub placement: Matrix4,
+    /// the joint connecting the component to the parent
+    pub joint: Joint,
+}
+
+/// Components related by the joints, the transform chains from the roots.
+///
+/// The parents are always added before their children, so the poses are evaluated in one pass.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::assembly::*;
+/// use std::f64::consts::PI;
+///
+/// // the unit cube as the link
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let link: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // the crane: the base, the arm rotating around the z-axis, and the hook sliding down.
+/// let mut assembly = Assembly::new();
+/// let base = assembly.add_root("base", link.clone(), Matrix4::identity());
+/// let arm = assembly.add_child(
+///     base,
+///     "arm",
+///     link.clone(),
+///     Matrix4::from_translation(Vector3::new(0.0, 0.0, 1.0)),
+///     Joint::revolute(Point3::new(0.5, 0.5, 0.0), Vector3::unit_z()),
+/// );
+/// let hook = assembly.add_child(
+///     arm,
+///     "hook",
+///     link,
+///     Matrix4::from_translation(Vector3::new(1.0, 0.0, 0.0)),
+///     Joint::prismatic(-Vector3::unit_z()).with_limits(0.0, 0.5),
+/// );
+/// assert_eq!(assembly.find("hook"), Some(hook));
+///
+/// let values = [0.0, PI / 2.0, 1.0];
+/// let poses = assembly.poses(&values);
+/// // the hook is rotated around the center of the arm and slides down by the limit 0.5.
+/// let corner = poses[hook].transform_point(Point3::origin());
+/// assert_near!(corner, Point3::new(1.0, 1.0, 0.5));
+///
+/// let solids = assembly.posed_solids(&values);
+/// assert_eq!(solids.len(), 3);
+/// let bottom = solids[hook].vertex_iter().map(|v| v.get_point().z).fold(1.0, f64::min);
+/// assert_near!(bottom, 0.5);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Assembly {
+    components: Vec<Component>,
+}
+
+impl Assembly {
+    /// Returns the empty assembly.
+    #[inline(always)]
+    pub fn new() -> Self { Self::default() }
+    /// Returns the components.
+    #[inline(always)]
+    pub fn components(&self) -> &[Component] { &self.components }
+    /// Returns the number of the components.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.components.len() }
+    /// Returns `true` if there are no components.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.components.is_empty() }
+    /// Returns the index of the first component named `name`.
+    #[inline(always)]
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.components.iter().position(|c| c.name == name)
+    }
+    /// Adds the component placed in the global frame, and returns its index.
+    pub fn add_root(&mut self, name: impl Into<String>, solid: Solid, placement: Matrix4) -> usize {
+        self.components.push(Component {
+            name: name.into(),
+            solid,
+            parent: None,
+            placement,
+            joint: Joint::Fixed,
+        });
+        self.components.len() - 1
+    }
+    /// Adds the component connected to `parent` by `joint`, and returns its index.
+    ///
+    /// The joint is defined in the frame of the component placed by `placement`.
+    /// # Panics
+    /// Panics if `parent` is not the index of a component.
+    pub fn add_child(
+        &mut self,
+        parent: usize,
+        name: impl Into<String>,
+        solid: Solid,
+        placement: Matrix4,
+        joint: Joint,
+    ) -> usize {
+        assert!(parent < self.components.len(), "the parent does not exist.");
+        self.components.push(Component {
+            name: name.into(),
+            solid,
+            parent: Some(parent),
+            placement,
+            joint,
+        });
+        self.components.len() - 1
+    }
+    /// Returns the global transforms of the components by the joint parameters.
+    ///
+    /// `values[i]` is the parameter of the joint of the `i`th component. The missing parameters
+    /// are regarded as zero, and the parameters of the fixed joints are ignored.
+    pub fn poses(&self, values: &[f64]) -> Vec<Matrix4> {
+        let placements: Vec<Matrix4> = self.components.iter().map(|c| c.placement).collect();
+        self.poses_by(&placements, values)
+    }
+    /// the poses by the substituted placements
+    fn poses_by(&self, placements: &[Matrix4], values: &[f64]) -> Vec<Matrix4> {
+        let mut poses: Vec<Matrix4> = Vec::with_capacity(self.components.len());
+        self.components.iter().enumerate().for_each(|(i, c)| {
+            let value = values.get(i).copied().unwrap_or(0.0);
+            let local = placements[i] * c.joint.pose(value);
+            let pose = match c.parent {
+                Some(parent) => poses[parent] * local,
+                None => local,
+            };
+            poses.push(pose);
+        });
+        poses
+    }
+    /// Returns the solids placed by [`Assembly::poses`], e.g. for the interference checking.
+    pub fn posed_solids(&self, values: &[f64]) -> Vec<Solid> {
+        self.components
+            .iter()
+            .zip(self.poses(values))
+            .map(|(c, pose)| builder::transformed(&c.solid, pose))
+            .collect()
+    }
+    /// Returns the transforms of the exploded view, the poses by [`Assembly::poses`] followed by
+    /// the explosions.
+    ///
+    /// Each child moves by `distance` away from its parent, along the direction from the center
+    /// of the parent to its center, which is the normal of the contact for the stacked parts.
+    /// The children of the prismatic joints move along the axes if the centers coincide. The roots
+    /// and the other children move away from the center of the whole assembly. The movements are
+    /// accumulated along the transform chains, so the children move with their parents. The
+    /// centers are the ones of the bounding boxes of the vertices.
+    /// # Examples
+    /// ```
+    /// use truck_modeling::*;
+    /// use truck_modeling::assembly::*;
+    ///
+    /// // the stack of three unit cubes on the z-axis
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    /// let up = Matrix4::from_translation(Vector3::unit_z());
+    ///
+    /// let mut assembly = Assembly::new();
+    /// let base = assembly.add_root("base", cube.clone(), Matrix4::identity());
+    /// let middle = assembly.add_child(base, "middle", cube.clone(), up, Joint::Fixed);
+    /// let top = assembly.add_child(middle, "top", cube, up, Joint::Fixed);
+    ///
+    /// let poses = assembly.exploded_poses(&[], 0.5);
+    /// let origin = |i: usize| poses[i].transform_point(Point3::origin());
+    /// // the base moves away from the center, and the others move away from their parents.
+    /// assert_near!(origin(base), Point3::new(0.0, 0.0, -0.5));
+    /// assert_near!(origin(middle), Point3::new(0.0, 0.0, 1.0));
+    /// assert_near!(origin(top), Point3::new(0.0, 0.0, 2.5));
+    /// ```
+    pub fn exploded_poses(&self, values: &[f64], distance: f64) -> Vec<Matrix4> {
+        let poses = self.poses(values);
+        let centers: Vec<Point3> = self
+            .components
+            .iter()
+            .zip(&poses)
+            .map(|(c, pose)| {
+                let bdd_box: BoundingBox<Point3> = c
+                    .solid
+                    .boundaries()
+                    .iter()
+                    .flat_map(Shell::vertex_iter)
+                    .map(|v| pose.transform_point(v.get_point()))
+                    .collect();
+                match bdd_box.is_empty() {
+                    true => pose.transform_point(Point3::origin()),
+                    false => bdd_box.center(),
+                }
+            })
+            .collect();
+        let whole = match centers.is_empty() {
+            true => Point3::origin(),
+            false => {
+                centers
+                    .iter()
+                    .fold(Point3::origin(), |sum, p| sum + p.to_vec())
+                    / centers.len() as f64
+            }
+        };
+        let normalize = |vec: Vector3| match vec.so_small() {
+            true => None,
+            false => Some(vec.normalize()),
+        };
+        let mut offsets: Vec<Vector3> = Vec::with_capacity(self.components.len());
+        self.components.iter().enumerate().for_each(|(i, c)| {
+            let from_parent = c.parent.and_then(|p| {
+                normalize(centers[i] - centers[p]).or_else(|| match c.joint {
+                    Joint::Prismatic { axis, .. } => normalize(poses[i].transform_vector(axis)),
+                    _ => None,
+                })
+            });
+            let direction = from_parent
+                .or_else(|| normalize(centers[i] - whole))
+                .unwrap_or_else(Vector3::zero);
+            let base = match c.parent {
+                Some(p) => offsets[p],
+                None => Vector3::zero(),
+            };
+            offsets.push(base + direction * distance);
+        });
+        poses
+            .into_iter()
+            .zip(offsets)
+            .map(|(pose, offset)| Matrix4::from_translation(offset) * pose)
+            .collect()
+    }
+    /// Returns the pairs of the indices of the components touching each other and their
+    /// contacts by [`contact_faces`], in the poses by [`Assembly::poses`].
+    /// # Examples
+    /// ```
+    /// use truck_modeling::*;
+    /// use truck_modeling::assembly::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let v = builder::vertex(Point3::new(-1.0, -1.0, -1.0));
+    /// let e = builder::tsweep(&v, Vector3::new(2.0, 0.0, 0.0));
+    /// let f = builder::tsweep(&e, Vector3::new(0.0, 2.0, 0.0));
+    /// let table: Solid = builder::tsweep(&f, Vector3::unit_z());
+    /// let circle = builder::circle(Point3::origin(), Vector3::unit_z(), 0.5);
+    /// let disk = builder::try_attach_plane(&[circle]).unwrap();
+    /// let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+    ///
+    /// // the cylinder sliding up from the top of the table
+    /// let mut assembly = Assembly::new();
+    /// let base = assembly.add_root("table", table, Matrix4::identity());
+    /// assembly.add_child(
+    ///     base,
+    ///     "cylinder",
+    ///     cylinder,
+    ///     Matrix4::identity(),
+    ///     Joint::prismatic(Vector3::unit_z()),
+    /// );
+    ///
+    /// let contacts = assembly.contacts(&[0.0, 0.0], 1.0e-6);
+    /// assert_eq!(contacts.len(), 1);
+    /// assert_eq!(contacts[0].0, (0, 1));
+    /// // the area of the disk approximated by the polygon
+    /// let area = contacts[0].1[0].area;
+    /// assert!(f64::abs(area - PI * 0.25) < 0.01);
+    ///
+    /// assert!(assembly.contacts(&[0.0, 0.1], 1.0e-6).is_empty());
+    /// ```
+    pub fn contacts(&self, values: &[f64], tolerance: f64) -> Vec<((usize, usize), Vec<Contact>)> {
+        let solids = self.posed_solids(values);
+        (0..solids.len())
+            .flat_map(|i| (i + 1..solids.len()).map(move |j| (i, j)))
+            .filter_map(|(i, j)| {
+                let contacts = contact_faces(&solids[i], &solids[j], tolerance);
+                match contacts.is_empty() {
+                    true => None,
+                    false => Some(((i, j), contacts)),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A pair of the planar faces touching each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contact {
+    /// the indices of the faces in the orders of `Solid::face_iter` of the two solids
+    pub faces: (usize, usize),
+    /// the outward normal of the first face, i.e. the direction from the first solid to the
+    /// second one
+    pub normal: Vector3,
+    /// the area of the overlap of the faces
+    pub area: f64,
+}
+
+/// the number of the divisions of the curved boundaries of the faces
+const CONTACT_DIVISION: usize = 16;
+
+/// the plane of the face, the origin, the orthonormal axes and the normal, and the boundaries
+type PlanarFace = (Point3, [Vector3; 3], Vec<Vec<Point3>>);
+
+fn planar_face(face: &Face) -> Option<PlanarFace> {
+    let plane = match face.oriented_surface() {
+        Surface::Plane(plane) => plane,
+        _ => return None,
+    };
+    let normal = plane.normal();
+    let u_axis = plane.u_axis().normalize();
+    let boundaries = face
+        .boundaries()
+        .iter()
+        .map(|wire| {
+            wire.edge_iter()
+                .flat_map(|edge| {
+                    let curve = edge.oriented_curve();
+                    let (t0, t1) = curve.parameter_range();
+                    let division = match curve {
+                        Curve::Line(_) => 1,
+                        _ => CONTACT_DIVISION,
+                    };
+                    (0..division)
+                        .map(|i| curve.subs(t0 + (t1 - t0) * i as f64 / division as f64))
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        })
+        .collect();
+    Some((
+        plane.origin(),
+        [u_axis, normal.cross(u_axis), normal],
+        boundaries,
+    ))
+}
+
+/// the fan triangles from the origin with the signs, whose sum is the indicator of the region
+fn signed_fan(boundaries: &[Vec<Point2>]) -> Vec<(f64, [Point2; 3])> {
+    let area: f64 = boundaries.iter().map(|b| polygon::signed_area(b)).sum();
+    let sign = area.signum();
+    let origin = boundaries
+        .first()
+        .and_then(|b| b.first())
+        .copied()
+        .unwrap_or_else(Point2::origin);
+    boundaries
+        .iter()
+        .flat_map(|b| (0..b.len()).map(move |i| (b[i], b[(i + 1) % b.len()])))
+        .filter_map(|(p, q)| {
+            let orient = polygon::orient(origin, p, q);
+            match orient.so_small2() {
+                true => None,
+                false => Some((sign * orient.signum(), [origin, p, q])),
+            }
+        })
+        .collect()
+}
+
+/// the area of the intersection of two triangles by the Sutherland-Hodgman clipping
+fn triangle_intersection_area(mut tri0: [Point2; 3], mut tri1: [Point2; 3]) -> f64 {
+    [&mut tri0, &mut tri1].into_iter().for_each(|tri| {
+        if polygon::orient(tri[0], tri[1], tri[2]) < 0.0 {
+            tri.swap(1, 2);
+        }
+    });
+    let mut clipped = tri0.to_vec();
+    for i in 0..3 {
+        let (a, b) = (tri1[i], tri1[(i + 1) % 3]);
+        let input = std::mem::take(&mut clipped);
+        for j in 0..input.len() {
+            let (p, q) = (input[j], input[(j + 1) % input.len()]);
+            let (dp, dq) = (polygon::orient(a, b, p), polygon::orient(a, b, q));
+            if dp >= 0.0 {
+                clipped.push(p);
+            }
+            if (dp >= 0.0) != (dq >= 0.0) {
+                clipped.push(p + (q - p) * (dp / (dp - dq)));
+            }
+        }
+        if clipped.len() < 3 {
+            return 0.0;
+        }
+    }
+    polygon::signed_area(&clipped)
+}
+
+/// Returns the pairs of the planar faces of `solid0` and `solid1` touching each other.
+///
+/// The faces touch if they are on the same plane within `tolerance` with the opposite normals,
+/// and the areas of their overlaps are more than the square of `tolerance`. The curved
+/// boundaries of the faces are approximated by the polylines, so the areas are approximate
+/// for them.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::assembly::*;
+///
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::new(2.0, 0.0, 0.0));
+/// let f = builder::tsweep(&e, Vector3::new(0.0, 2.0, 0.0));
+/// let plate: Solid = builder::tsweep(&f, Vector3::unit_z());
+/// // the unit cube on the plate, sticking out of the side by half
+/// let v = builder::vertex(Point3::new(1.5, 0.5, 1.0));
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let contacts = contact_faces(&plate, &cube, 1.0e-6);
+/// assert_eq!(contacts.len(), 1);
+/// assert_near!(contacts[0].normal, Vector3::unit_z());
+/// assert_near!(contacts[0].area, 0.5);
+/// let top = plate.face_iter().nth(contacts[0].faces.0).unwrap();
+/// assert!(top.boundaries()[0].vertex_iter().all(|v| v.get_point().z.near(&1.0)));
+/// ```
+pub fn contact_faces(solid0: &Solid, solid1: &Solid, tolerance: f64) -> Vec<Contact> {
+    let faces0: Vec<_> = solid0.face_iter().map(planar_face).collect();
+    let faces1: Vec<_> = solid1.face_iter().map(planar_face).collect();
+    let bounding = |face: &PlanarFace| -> BoundingBox<Point3> { face.2.iter().flatten().collect() };
+    let boxes1: Vec<_> = faces1.iter().map(|f| f.as_ref().map(bounding)).collect();
+    let mut contacts = Vec::new();
+    faces0.iter().enumerate().for_each(|(i, face0)| {
+        let (origin, [u, v, n], boundaries0) = match face0 {
+            Some(face) => face,
+            None => return,
+        };
+        let mut box0 = bounding(face0.as_ref().unwrap());
+        box0.push(&(*box0.min() - Vector3::new(1.0, 1.0, 1.0) * tolerance));
+        box0.push(&(*box0.max() + Vector3::new(1.0, 1.0, 1.0) * tolerance));
+        let project = |p: &Point3| Point2::new((p - origin).dot(*u), (p - origin).dot(*v));
+        let fan0 = signed_fan(
+            &boundaries0
+                .iter()
+                .map(|b| b.iter().map(project).collect())
+                .collect::<Vec<_>>(),
+        );
+        faces1
+            .iter()
+            .zip(&boxes1)
+            .enumerate()
+            .for_each(|(j, (face1, box1))| {
+                let ((origin1, [_, _, n1], boundaries1), box1) = match (face1, box1) {
+                    (Some(face), Some(bdd)) => (face, bdd),
+                    _ => return,
+                };
+                let coplanar = (n + n1).magnitude() < tolerance
+                    && (origin1 - origin).dot(*n).abs() < tolerance;
+                if !coplanar || (&box0 ^ box1).is_empty() {
+                    return;
+                }
+                let fan1 = signed_fan(
+                    &boundaries1
+                        .iter()
+                        .map(|b| b.iter().map(project).collect())
+                        .collect::<Vec<_>>(),
+                );
+                let area: f64 = fan0
+                    .iter()
+                    .flat_map(|(s0, t0)| {
+                        fan1.iter()
+                            .map(move |(s1, t1)| s0 * s1 * triangle_intersection_area(*t0, *t1))
+                    })
+                    .sum();
+                if area > tolerance * tolerance {
+                    contacts.push(Contact {
+                        faces: (i, j),
+                        normal: *n,
+                        area,
+                    });
+                }
+            })
+    });
+    contacts
+}
+
+/// A geometric reference of a component for the mates, in the frame of the component.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Datum {
+    /// a point
+    Point(Point3),
+    /// an infinite line
+    Axis {
+        /// a point on the axis
+        origin: Point3,
+        /// the direction of the axis
+        direction: Vector3,
+    },
+    /// an infinite plane
+    Plane {
+        /// a point on the plane
+        origin: Point3,
+        /// the normal, usually outward from the solid
+        normal: Vector3,
+    },
+}
+
+impl Datum {
+    /// Returns the datum of the face: the plane of the planar faces, the axis of the cylinders,
+    /// cones and tori, or the center of the spheres, recognized within `tol`.
+    ///
+    /// The normals of the planes are outward from the solids.
+    /// # Examples
+    /// ```
+    /// use truck_modeling::*;
+    /// use truck_modeling::assembly::Datum;
+    ///
+    /// let circle = builder::circle(Point3::new(1.0, 2.0, 0.0), Vector3::unit_z(), 0.5);
+    /// let disk = builder::try_attach_plane(&[circle]).unwrap();
+    /// let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+    /// let datums: Vec<Datum> = cylinder
+    ///     .face_iter()
+    ///     .filter_map(|face| Datum::from_face(face, 1.0e-6))
+    ///     .collect();
+    /// assert_eq!(datums.len(), 4);
+    /// match datums[0] {
+    ///     Datum::Plane { normal, .. } => assert_near!(normal, -Vector3::unit_z()),
+    ///     _ => panic!("the bottom is planar."),
+    /// }
+    /// match datums[1] {
+    ///     Datum::Axis { origin, direction } => {
+    ///         assert_near!(direction.cross(Vector3::unit_z()).magnitude(), 0.0);
+    ///         assert_near!(Point2::new(origin.x, origin.y), Point2::new(1.0, 2.0));
+    ///     }
+    ///     _ => panic!("the side is cylindrical."),
+    /// }
+    /// ```
+    pub fn from_face(face: &Face, tol: f64) -> Option<Datum> {
+        match face.oriented_surface().recognize_analytic(tol)? {
+            AnalyticSurface::Plane { origin, normal } => Some(Datum::Plane { origin, normal }),
+            AnalyticSurface::Sphere { center, .. } => Some(Datum::Point(center)),
+            AnalyticSurface::Cylinder { origin, axis, .. }
+            | AnalyticSurface::Cone {
+                apex: origin, axis, ..
+            }
+            | AnalyticSurface::Torus {
+                center: origin,
+                axis,
+                ..
+            } => Some(Datum::Axis {
+                origin,
+                direction: axis,
+            }),
+        }
+    }
+    /// Returns the datum transformed by the rigid motion `matrix`. The directions are normalized.
+    pub fn transformed(&self, matrix: Matrix4) -> Datum {
+        match *self {
+            Datum::Point(point) => Datum::Point(matrix.transform_point(point)),
+            Datum::Axis { origin, direction } => Datum::Axis {
+                origin: matrix.transform_point(origin),
+                direction: matrix.transform_vector(direction).normalize(),
+            },
+            Datum::Plane { origin, normal } => Datum::Plane {
+                origin: matrix.transform_point(origin),
+                normal: matrix.transform_vector(normal).normalize(),
+            },
+        }
+    }
+    fn direction(&self) -> Option<Vector3> {
+        match self {
+            Datum::Point(_) => None,
+            Datum::Axis { direction, .. } => Some(*direction),
+            Datum::Plane { normal, .. } => Some(*normal),
+        }
+    }
+}
+
+/// The kinds of the mates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MateKind {
+    /// The points coincide, the point is on the axis or the plane, the axes are the same, the
+    /// axis is on the plane, or the planes face each other without the gap.
+    Coincident,
+    /// The axes are the same, or the points, e.g. the centers of the spheres, coincide.
+    Concentric,
+    /// The distance between the points, the point and the axis, or the parallel axes, the
+    /// signed distance of the point along the normal of the plane, or the gap of the planes
+    /// facing each other.
+    Distance(f64),
+    /// The angle in radians between the directions of the axes or the normals of the planes.
+    Angle(f64),
+}
+
+/// A constraint between the datums of two components.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mate {
+    /// the index of the first component and its datum
+    pub first: (usize, Datum),
+    /// the index of the second component and its datum
+    pub second: (usize, Datum),
+    /// the kind of the mate
+    pub kind: MateKind,
+}
+
+impl Mate {
+    /// Returns the mate of `kind` between `first` and `second`.
+    #[inline(always)]
+    pub const fn new(first: (usize, Datum), second: (usize, Datum), kind: MateKind) -> Self {
+        Mate {
+            first,
+            second,
+            kind,
+        }
+    }
+    /// Pushes the residuals, which vanish if the mate is satisfied, by the global poses.
+    fn residuals(&self, poses: &[Matrix4], residuals: &mut Vec<f64>) -> Result<()> {
+        use Datum::*;
+        let datum = |(i, datum): (usize, Datum)| match poses.get(i) {
+            Some(pose) => Ok(datum.transformed(*pose)),
+            None => Err(unsupported_mate("the components of the mates must exist.")),
+        };
+        let (d0, d1) = (datum(self.first)?, datum(self.second)?);
+        let mut push = |vec: Vector3| residuals.extend([vec.x, vec.y, vec.z]);
+        let unsupported = || Err(unsupported_mate("the pair of the datums is not supported."));
+        match self.kind {
+            MateKind::Coincident => match (d0, d1) {
+                (Point(p), Point(q)) => push(q - p),
+                (Point(p), Plane { origin, normal }) | (Plane { origin, normal }, Point(p)) => {
+                    residuals.push(normal.dot(p - origin))
+                }
+                (Point(p), Axis { origin, direction }) | (Axis { origin, direction }, Point(p)) => {
+                    push((p - origin).cross(direction))
+                }
+                (
+                    Plane {
+                        origin: o0,
+                        normal: n0,
+                    },
+                    Plane {
+                        origin: o1,
+                        normal: n1,
+                    },
+                ) => {
+                    push(n0 + n1);
+                    residuals.push(n0.dot(o1 - o0));
+                }
+                (
+                    Axis {
+                        origin: o0,
+                        direction: d0,
+                    },
+                    Axis {
+                        origin: o1,
+                        direction: d1,
+                    },
+                ) => {
+                    push(d0.cross(d1));
+                    push((o1 - o0).cross(d0));
+                }
+                (
+                    Plane { origin: o0, normal },
+                    Axis {
+                        origin: o1,
+                        direction,
+                    },
+                )
+                | (
+                    Axis {
+                        origin: o1,
+                        direction,
+                    },
+                    Plane { origin: o0, normal },
+                ) => residuals.extend([normal.dot(direction), normal.dot(o1 - o0)]),
+            },
+            MateKind::Concentric => match (d0, d1) {
+                (Point(p), Point(q)) => push(q - p),
+                (
+                    Axis {
+                        origin: o0,
+                        direction: d0,
+                    },
+                    Axis {
+                        origin: o1,
+                        direction: d1,
+                    },
+                ) => {
+                    push(d0.cross(d1));
+                    push((o1 - o0).cross(d0));
+                }
+                _ => return unsupported(),
+            },
+            MateKind::Distance(distance) => match (d0, d1) {
+                (Point(p), Point(q)) => residuals.push(p.distance(q) - distance),
+                (Point(p), Plane { origin, normal }) | (Plane { origin, normal }, Point(p)) => {
+                    residuals.push(normal.dot(p - origin) - distance)
+                }
+                (Point(p), Axis { origin, direction }) | (Axis { origin, direction }, Point(p)) => {
+                    residuals.push((p - origin).cross(direction).magnitude() - distance)
+                }
+                (
+                    Plane {
+                        origin: o0,
+                        normal: n0,
+                    },
+                    Plane {
+                        origin: o1,
+                        normal: n1,
+                    },
+                ) => {
+                    push(n0 + n1);
+                    residuals.push(n0.dot(o1 - o0) - distance);
+                }
+                (
+                    Axis {
+                        origin: o0,
+                        direction: d0,
+                    },
+                    Axis {
+                        origin: o1,
+                        direction: d1,
+                    },
+                ) => {
+                    push(d0.cross(d1));
+                    residuals.push((o1 - o0).cross(d0).magnitude() - distance);
+                }
+                _ => return unsupported(),
+            },
+            MateKind::Angle(angle) => match (d0.direction(), d1.direction()) {
+                (Some(u), Some(v)) => {
+                    residuals.push(f64::atan2(u.cross(v).magnitude(), u.dot(v)) - angle)
+                }
+                _ => return unsupported(),
+            },
+        }
+        Ok(())
+    }
+}
+
+fn unsupported_mate(message: &str) -> Error { Error::UnsupportedMate(message.to_string()) }
+
+/// the rigid motion by the translation and the rotation vector
+fn twist(x: &[f64]) -> Matrix4 {
+    let rotation = Vector3::new(x[3], x[4], x[5]);
+    let angle = rotation.magnitude();
+    let translation = Matrix4::from_translation(Vector3::new(x[0], x[1], x[2]));
+    match angle.so_small2() {
+        true => translation,
+        false => translation * Matrix4::from_axis_angle(rotation / angle, Rad(angle)),
+    }
+}
+
+/// Solves `matrix * x = vector` by the Gaussian elimination with the partial pivoting.
+fn solve_linear(mut matrix: Vec<Vec<f64>>, mut vector: Vec<f64>) -> Option<Vec<f64>> {
+    let n = vector.len();
+    for i in 0..n {
+        let pivot = (i..n).max_by(|a, b| matrix[*a][i].abs().total_cmp(&matrix[*b][i].abs()))?;
+        if matrix[pivot][i].abs() < f64::EPSILON {
+            return None;
+        }
+        matrix.swap(i, pivot);
+        vector.swap(i, pivot);
+        for j in i + 1..n {
+            let ratio = matrix[j][i] / matrix[i][i];
+            let (upper, lower) = matrix.split_at_mut(j);
+            lower[0][i..]
+                .iter_mut()
+                .zip(&upper[i][i..])
+                .for_each(|(x, y)| *x -= ratio * y);
+            vector[j] -= ratio * vector[i];
+        }
+    }
+    for i in (0..n).rev() {
+        let sum: f64 = (i + 1..n).map(|k| matrix[i][k] * vector[k]).sum();
+        vector[i] = (vector[i] - sum) / matrix[i][i];
+    }
+    Some(vector)
+}
+
+/// the maximum number of the iterations of the mate solver
+const MATE_ITERATIONS: usize = 200;
+
+impl Assembly {
+    fn mate_residuals(&self, mates: &[Mate], placements: &[Matrix4]) -> Result<Vec<f64>> {
+        let poses = self.poses_by(placements, &[]);
+        let mut residuals = Vec::new();
+        for mate in mates {
+            mate.residuals(&poses, &mut residuals)?;
+        }
+        Ok(residuals)
+    }
+    /// Moves the placements of the components except `grounded` so that `mates` are satisfied
+    /// in the poses at the joint parameters zero.
+    ///
+    /// The mates are solved by the Levenberg-Marquardt method from the current placements, so
+    /// the placements near the solution give the expected one, e.g. the flipped solution for the
+    /// planes facing each other may be found from the flipped placements. The children move
+    /// with their parents.
+    /// # Failures
+    /// Returns [`errors::Error::UnsupportedMate`] if the components do not exist, the pair of
+    /// the datums is not supported by the kind of the mate, or the mates are not satisfied
+    /// within `TOLERANCE` by the iterations, e.g. the mates are inconsistent.
+    /// # Examples
+    /// ```
+    /// use truck_modeling::*;
+    /// use truck_modeling::assembly::*;
+    ///
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    ///
+    /// // the second cube placed roughly
+    /// let mut assembly = Assembly::new();
+    /// let base = assembly.add_root("base", cube.clone(), Matrix4::identity());
+    /// let placement = Matrix4::from_translation(Vector3::new(0.3, -0.2, 1.4))
+    ///     * Matrix4::from_angle_z(Rad(0.3))
+    ///     * Matrix4::from_angle_x(Rad(0.2));
+    /// let top = assembly.add_root("top", cube, placement);
+    ///
+    /// let plane = |origin: Point3, normal: Vector3| Datum::Plane { origin, normal };
+    /// let axis = |origin: Point3, direction: Vector3| Datum::Axis { origin, direction };
+    /// let mates = [
+    ///     // put the top cube on the base, and the corners together
+    ///     Mate::new(
+    ///         (base, plane(Point3::new(0.0, 0.0, 1.0), Vector3::unit_z())),
+    ///         (top, plane(Point3::origin(), -Vector3::unit_z())),
+    ///         MateKind::Coincident,
+    ///     ),
+    ///     // align the x-axes
+    ///     Mate::new(
+    ///         (base, axis(Point3::origin(), Vector3::unit_x())),
+    ///         (top, axis(Point3::origin(), Vector3::unit_x())),
+    ///         MateKind::Angle(0.0),
+    ///     ),
+    ///     Mate::new(
+    ///         (base, Datum::Point(Point3::new(0.0, 0.0, 1.0))),
+    ///         (top, Datum::Point(Point3::origin())),
+    ///         MateKind::Coincident,
+    ///     ),
+    /// ];
+    /// assembly.solve_mates(&mates, &[base]).unwrap();
+    ///
+    /// let pose = assembly.poses(&[])[top];
+    /// assert_near!(pose.transform_point(Point3::new(1.0, 1.0, 1.0)), Point3::new(1.0, 1.0, 2.0));
+    /// assert_near!(pose.transform_vector(Vector3::unit_x()), Vector3::unit_x());
+    ///
+    /// // the inconsistent mates are not solved, and the placements are kept.
+    /// let corners = ((base, Datum::Point(Point3::origin())), (top, Datum::Point(Point3::origin())));
+    /// let inconsistent = [
+    ///     Mate::new(corners.0, corners.1, MateKind::Distance(1.0)),
+    ///     Mate::new(corners.0, corners.1, MateKind::Distance(2.0)),
+    /// ];
+    /// assert!(assembly.solve_mates(&inconsistent, &[base]).is_err());
+    /// assert_near!(assembly.poses(&[])[top], pose);
+    /// // the points have no directions.
+    /// let angle = [Mate::new(corners.0, corners.1, MateKind::Angle(0.0))];
+    /// assert!(assembly.solve_mates(&angle, &[base]).is_err());
+    /// ```
+    pub fn solve_mates(&mut self, mates: &[Mate], grounded: &[usize]) -> Result<()> {
+        let free: Vec<usize> = (0..self.len()).filter(|i| !grounded.contains(i)).collect();
+        let dim = free.len() * 6;
+        let mut placements: Vec<Matrix4> = self.components.iter().map(|c| c.placement).collect();
+        let moved = |placements: &[Matrix4], x: &[f64]| {
+            let mut placements = placements.to_vec();
+            free.iter().enumerate().for_each(|(k, i)| {
+                placements[*i] = placements[*i] * twist(&x[6 * k..6 * k + 6]);
+            });
+            placements
+        };
+        let cost = |residuals: &[f64]| residuals.iter().map(|r| r * r).sum::<f64>();
+        let mut residuals = self.mate_residuals(mates, &placements)?;
+        let mut lambda = 1.0e-3;
+        for _ in 0..MATE_ITERATIONS {
+            if residuals.iter().all(|r| r.abs() < TOLERANCE) || dim == 0 {
+                break;
+            }
+            const DELTA: f64 = 1.0e-7;
+            let mut jacobian = vec![vec![0.0; dim]; residuals.len()];
+            for j in 0..dim {
+                let mut x = vec![0.0; dim];
+                x[j] = DELTA;
+                let perturbed = self.mate_residuals(mates, &moved(&placements, &x))?;
+                perturbed
+                    .iter()
+                    .zip(&residuals)
+                    .enumerate()
+                    .for_each(|(i, (r1, r0))| {
+                        jacobian[i][j] = (r1 - r0) / DELTA;
+                    });
+            }
+            let normal: Vec<Vec<f64>> = (0..dim)
+                .map(|a| {
+                    (0..dim)
+                        .map(|b| jacobian.iter().map(|row| row[a] * row[b]).sum())
+                        .collect()
+                })
+                .collect();
+            let gradient: Vec<f64> = (0..dim)
+                .map(|a| {
+                    -jacobian
+                        .iter()
+                        .zip(&residuals)
+                        .map(|(row, r)| row[a] * r)
+                        .sum::<f64>()
+                })
+                .collect();
+            let mut improved = false;
+            while lambda < 1.0e10 {
+                let mut damped = normal.clone();
+                (0..dim).for_each(|a| damped[a][a] += lambda * (1.0 + damped[a][a]));
+                if let Some(x) = solve_linear(damped, gradient.clone()) {
+                    let candidate = moved(&placements, &x);
+                    let new_residuals = self.mate_residuals(mates, &candidate)?;
+                    if cost(&new_residuals) < cost(&residuals) {
+                        placements = candidate;
+                        residuals = new_residuals;
+                        lambda = f64::max(lambda / 10.0, 1.0e-12);
+                        improved = true;
+                        break;
+                    }
+                }
+                lambda *= 10.0;
+            }
+            if !improved {
+                break;
+            }
+        }
+        if residuals.iter().any(|r| r.abs() >= TOLERANCE) {
+            return Err(unsupported_mate("the mates are not satisfied."));
+        }
+        self.components
+            .iter_mut()
+            .zip(placements)
+            .for_each(|(c, placement)| c.placement = placement);
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::*;
 use errors::Error;
+pub use fillet::RadiusLaw;
 const PI: Rad<f64> = Rad(std::f64::consts::PI);
 
 /// Creates and returns a vertex by a three dimensional point.
@@ -39,6 +40,55 @@ pub fn line(vertex0: &Vertex, vertex1: &Vertex) -> Edge {
     Edge::new(vertex0, vertex1, Curve::Line(Line(pt0, pt1)))
 }
 
+/// Returns the wire of the lines through `points`, closed by the line from the last point to
+/// the first one if `closed` is `true`.
+///
+/// The consecutive edges share their vertices. The consecutive coincident points are regarded
+/// as one point, and so is the last point coinciding with the first one of the closed wire.
+/// The empty wire is returned if there are not two distinct points.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// let points = [
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// ];
+/// let open = builder::polyline(&points, false);
+/// assert_eq!(open.len(), 3);
+/// assert!(open.is_continuous() && !open.is_closed());
+///
+/// let square = builder::polyline(&points, true);
+/// assert_eq!(square.len(), 4);
+/// assert!(square.is_closed());
+/// assert_eq!(square.vertex_iter().count(), 4);
+/// let face = builder::try_attach_plane(&[square]).unwrap();
+/// assert_near!(face.oriented_surface().normal(0.5, 0.5), Vector3::unit_z());
+/// ```
+pub fn polyline(points: &[Point3], closed: bool) -> Wire {
+    let mut vertices: Vec<Vertex> = Vec::with_capacity(points.len());
+    points.iter().for_each(|p| match vertices.last() {
+        Some(v) if v.get_point().near(p) => {}
+        _ => vertices.push(vertex(*p)),
+    });
+    if closed && vertices.len() > 2 {
+        let (first, last) = (&vertices[0], &vertices[vertices.len() - 1]);
+        if first.get_point().near(&last.get_point()) {
+            vertices.pop();
+        }
+    }
+    if vertices.len() < 2 {
+        return Wire::new();
+    }
+    let mut wire: Wire = vertices.windows(2).map(|v| line(&v[0], &v[1])).collect();
+    if closed {
+        wire.push_back(line(&vertices[vertices.len() - 1], &vertices[0]));
+    }
+    wire
+}
+
 /// Returns a circle arc from `vertex0` to `vertex1` via `transit`.
 /// # Examples
 /// ```
@@ -66,6 +116,145 @@ pub fn circle_arc(vertex0: &Vertex, vertex1: &Vertex, transit: Point3) -> Edge {
     Edge::new(vertex0, vertex1, Curve::NURBSCurve(NURBSCurve::new(curve)))
 }
 
+/// Returns the elliptic arc from `vertex0` to `vertex1` on the ellipse with `center`.
+///
+/// The length of `major_axis` is the semi-major axis, and the semi-minor axis is its length
+/// multiplied by `ratio`. The ellipse is on the plane spanned by `major_axis` and the vertices,
+/// and the arc is the shorter one of the two arcs between the vertices. Divide the arcs longer
+/// than the half of the ellipse, e.g. by [`Wire::subdivide_equal`].
+/// # Panics
+/// Panics if the vertices are not on the ellipse, the vertices are the same, or both of them
+/// are on the major axis.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the quarter of the ellipse x^2 / 4 + y^2 = 1 on the xy-plane
+/// let vertex0 = builder::vertex(Point3::new(0.0, -1.0, 0.0));
+/// let vertex1 = builder::vertex(Point3::new(2.0, 0.0, 0.0));
+/// let arc = builder::ellipse_arc(
+///     &vertex0,
+///     &vertex1,
+///     Point3::origin(),
+///     Vector3::new(2.0, 0.0, 0.0),
+///     0.5,
+/// );
+/// assert!(arc.is_geometric_consistent());
+/// let curve = arc.oriented_curve();
+/// let (t0, t1) = curve.parameter_range();
+/// for i in 0..=10 {
+///     let p = curve.subs(t0 + (t1 - t0) * i as f64 / 10.0);
+///     assert_near!(p.x * p.x / 4.0 + p.y * p.y, 1.0);
+///     assert!(p.x >= -TOLERANCE && p.y <= TOLERANCE && p.z.so_small());
+/// }
+/// ```
+pub fn ellipse_arc(
+    vertex0: &Vertex,
+    vertex1: &Vertex,
+    center: Point3,
+    major_axis: Vector3,
+    ratio: f64,
+) -> Edge {
+    let (pt0, pt1) = (vertex0.get_point(), vertex1.get_point());
+    let curve = geom_impls::ellipse_arc(pt0, pt1, center, major_axis, ratio);
+    Edge::new(vertex0, vertex1, Curve::NURBSCurve(NURBSCurve::new(curve)))
+}
+
+/// Returns the circle arc with `center` and `radius` on the plane perpendicular to `axis`,
+/// from `start_angle` to `end_angle` counterclockwise around `axis`.
+///
+/// The angle zero is the direction of the x-axis rotated by the rotation bringing the z-axis
+/// to `axis` along the great circle, e.g. the x-axis if `axis` is the z-axis, and the negative
+/// z-axis if `axis` is the x-axis. The arc is clockwise if `end_angle` is less than `start_angle`.
+/// # Panics
+/// Panics if `axis` or `radius` is zero, or if the difference of the angles is zero or not less
+/// than a turn.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use std::f64::consts::PI;
+///
+/// // the three quarters of the circle on the yz-plane from the negative z-axis
+/// let arc = builder::arc_by_center(
+///     Point3::new(1.0, 0.0, 0.0),
+///     Vector3::unit_x(),
+///     2.0,
+///     Rad(0.0),
+///     Rad(1.5 * PI),
+/// );
+/// assert_near!(arc.front().get_point(), Point3::new(1.0, 0.0, -2.0));
+/// assert_near!(arc.back().get_point(), Point3::new(1.0, -2.0, 0.0));
+/// let curve = arc.oriented_curve();
+/// let (t0, t1) = curve.parameter_range();
+/// (0..=10).for_each(|i| {
+///     let p = curve.subs(t0 + (t1 - t0) * i as f64 / 10.0);
+///     assert_near!(p.x, 1.0);
+///     assert_near!(p.y * p.y + p.z * p.z, 4.0);
+/// });
+/// // the arc passes through the positive y-axis.
+/// let sqrt2 = f64::sqrt(2.0);
+/// assert_near!(curve.subs((t0 + t1) / 2.0), Point3::new(1.0, sqrt2, sqrt2));
+/// ```
+pub fn arc_by_center<R: Into<Rad<f64>>>(
+    center: Point3,
+    axis: Vector3,
+    radius: f64,
+    start_angle: R,
+    end_angle: R,
+) -> Edge {
+    let (start_angle, end_angle) = (start_angle.into(), end_angle.into());
+    let angle = (end_angle - start_angle).0.abs();
+    assert!(
+        !axis.magnitude().so_small() && !radius.so_small(),
+        "the axis and the radius must not be zero."
+    );
+    assert!(
+        !angle.so_small() && angle < 2.0 * PI.0,
+        "the angle of the arc must be between zero and a turn."
+    );
+    let curve =
+        geom_impls::circle_arc_by_center(center, axis.normalize(), radius, start_angle, end_angle);
+    let v0 = vertex(Point3::from_homogeneous(curve.front()));
+    let v1 = vertex(Point3::from_homogeneous(curve.back()));
+    Edge::new(&v0, &v1, Curve::NURBSCurve(NURBSCurve::new(curve)))
+}
+
+/// Returns the circle with `center` and `radius` on the plane perpendicular to `axis`,
+/// counterclockwise around `axis`.
+///
+/// The circle consists of two semicircles, and the seam, the front vertex of the wire, is at
+/// the angle zero of [`arc_by_center`]. Rotate the circle around the axis to move the seam.
+/// # Panics
+/// Panics if `axis` or `radius` is zero.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// let circle = builder::circle(Point3::new(0.0, 0.0, 1.0), Vector3::unit_z(), 2.0);
+/// assert_eq!(circle.len(), 2);
+/// assert!(circle.is_closed());
+/// assert_near!(circle.front_vertex().unwrap().get_point(), Point3::new(2.0, 0.0, 1.0));
+///
+/// let disk = builder::try_attach_plane(&[circle]).unwrap();
+/// assert_near!(disk.oriented_surface().normal(0.0, 0.0), Vector3::unit_z());
+/// ```
+pub fn circle(center: Point3, axis: Vector3, radius: f64) -> Wire {
+    assert!(
+        !axis.magnitude().so_small() && !radius.so_small(),
+        "the axis and the radius must not be zero."
+    );
+    let axis = axis.normalize();
+    let curve0 = geom_impls::circle_arc_by_center(center, axis, radius, Rad(0.0), PI);
+    let curve1 = geom_impls::circle_arc_by_center(center, axis, radius, PI, PI * 2.0);
+    let v0 = vertex(Point3::from_homogeneous(curve0.front()));
+    let v1 = vertex(Point3::from_homogeneous(curve0.back()));
+    vec![
+        Edge::new(&v0, &v1, Curve::NURBSCurve(NURBSCurve::new(curve0))),
+        Edge::new(&v1, &v0, Curve::NURBSCurve(NURBSCurve::new(curve1))),
+    ]
+    .into()
+}
+
 /// Returns a Bezier curve from `vertex0` to `vertex1` with inter control points `inter_points`.
 /// # Examples
 /// ```
@@ -142,6 +331,148 @@ pub fn homotopy(edge0: &Edge, edge1: &Edge) -> Face {
     )
 }
 
+/// Creates the Coons patch, the bilinearly blended surface bounded by four edges.
+///
+/// The edges are the boundary of the face in this order, and the boundary of the surface coincides
+/// with the curves of the edges exactly, so the four-sided holes which cannot be filled by
+/// [`homotopy`] are closed without gaps.
+/// # Panics
+/// Panics if the edges do not form a closed wire, or if the edges contain intersection curves.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// let v0 = builder::vertex(Point3::new(0.0, 0.0, 0.0));
+/// let v1 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+/// let v2 = builder::vertex(Point3::new(1.0, 1.0, 0.0));
+/// let v3 = builder::vertex(Point3::new(0.0, 1.0, 0.0));
+/// let edge0 = builder::circle_arc(&v0, &v1, Point3::new(0.5, 0.0, 0.2));
+/// let edge1 = builder::bezier(&v1, &v2, vec![Point3::new(1.2, 0.5, 0.5)]);
+/// let edge2 = builder::circle_arc(&v2, &v3, Point3::new(0.5, 1.0, -0.2));
+/// let edge3 = builder::line(&v3, &v0);
+/// let face = builder::coons_patch(&edge0, &edge1, &edge2, &edge3);
+///
+/// // the boundary of the surface is the edges.
+/// let surface = face.oriented_surface();
+/// const N: usize = 10;
+/// for i in 0..=N {
+///     let t = i as f64 / N as f64;
+///     let (pt0, pt1) = (surface.subs(t, 0.0), surface.subs(1.0, t));
+///     let (pt2, pt3) = (surface.subs(1.0 - t, 1.0), surface.subs(0.0, 1.0 - t));
+///     assert!(edge0.get_curve().search_parameter(pt0, None, 10).is_some());
+///     assert!(edge1.get_curve().search_parameter(pt1, None, 10).is_some());
+///     assert!(edge2.get_curve().search_parameter(pt2, None, 10).is_some());
+///     assert!(edge3.get_curve().search_parameter(pt3, None, 10).is_some());
+/// }
+/// ```
+pub fn coons_patch(edge0: &Edge, edge1: &Edge, edge2: &Edge, edge3: &Edge) -> Face {
+    let wire: Wire = vec![edge0.clone(), edge1.clone(), edge2.clone(), edge3.clone()].into();
+    assert!(wire.is_closed(), "the edges do not form a closed wire.");
+    let curve0 = edge0.oriented_curve().lift_up();
+    let curve1 = edge1.oriented_curve().lift_up();
+    let mut curve2 = edge2.oriented_curve().lift_up();
+    let mut curve3 = edge3.oriented_curve().lift_up();
+    curve2.invert();
+    curve3.invert();
+    let surface = geom_impls::coons_surface(curve0, curve1, curve2, curve3);
+    Face::new(
+        vec![wire],
+        Surface::NURBSSurface(NURBSSurface::new(surface)),
+    )
+}
+
+/// Creates the shell of the ruled faces between the corresponding edges of two wires.
+///
+/// The `i`-th face is bounded by the `i`-th edges of the wires and the lines between their ends,
+/// which are shared by the adjacent faces. If both wires are closed, the shell is a tube without
+/// caps. The faces between two coplanar lines are planes, and the others are the homotopies as
+/// [`homotopy`].
+/// # Panics
+/// Panics if the numbers of the edges of the wires are different, or if the edges contain
+/// intersection curves.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_topology::shell::ShellCondition;
+///
+/// // transition from a square to a smaller rotated square
+/// let square = builder::polyline(
+///     &[
+///         Point3::new(-1.0, -1.0, 0.0),
+///         Point3::new(1.0, -1.0, 0.0),
+///         Point3::new(1.0, 1.0, 0.0),
+///         Point3::new(-1.0, 1.0, 0.0),
+///     ],
+///     true,
+/// );
+/// let diamond = builder::polyline(
+///     &[
+///         Point3::new(0.0, -0.5, 1.0),
+///         Point3::new(0.5, 0.0, 1.0),
+///         Point3::new(0.0, 0.5, 1.0),
+///         Point3::new(-0.5, 0.0, 1.0),
+///     ],
+///     true,
+/// );
+/// let shell = builder::ruled(&square, &diamond);
+/// assert_eq!(shell.len(), 4);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Oriented);
+/// assert_eq!(shell.extract_boundaries().len(), 2);
+/// assert!(shell.is_geometric_consistent());
+///
+/// // the tube is closed by the caps.
+/// let mut shell = shell;
+/// shell.push(builder::try_attach_plane(&[square.inverse()]).unwrap());
+/// shell.push(builder::try_attach_plane(&[diamond]).unwrap());
+/// Solid::new(vec![shell]);
+/// ```
+pub fn ruled(wire0: &Wire, wire1: &Wire) -> Shell {
+    assert_eq!(
+        wire0.len(),
+        wire1.len(),
+        "the numbers of the edges of the wires are different."
+    );
+    let closed = wire0.is_closed() && wire1.is_closed();
+    let mut rungs: Vec<Edge> = wire0
+        .edge_iter()
+        .zip(wire1.edge_iter())
+        .map(|(edge0, edge1)| line(edge0.front(), edge1.front()))
+        .collect();
+    if !closed {
+        if let (Some(v0), Some(v1)) = (wire0.back_vertex(), wire1.back_vertex()) {
+            rungs.push(line(v0, v1));
+        }
+    }
+    let len = rungs.len();
+    wire0
+        .edge_iter()
+        .zip(wire1.edge_iter())
+        .enumerate()
+        .map(|(i, (edge0, edge1))| {
+            let boundary: Wire = vec![
+                edge0.clone(),
+                rungs[(i + 1) % len].clone(),
+                edge1.inverse(),
+                rungs[i].inverse(),
+            ]
+            .into();
+            let curve0 = edge0.oriented_curve();
+            let curve1 = edge1.oriented_curve();
+            Face::new(vec![boundary], ruled_face_surface(curve0, curve1))
+        })
+        .collect()
+}
+
+fn ruled_face_surface(curve0: Curve, curve1: Curve) -> Surface {
+    if let (Curve::Line(line0), Curve::Line(line1)) = (&curve0, &curve1) {
+        let normal = (line0.1 - line0.0).cross(line1.0 - line0.0);
+        if !normal.so_small() && normal.normalize().dot(line1.1 - line0.0).so_small() {
+            return Surface::Plane(Plane::new(line0.0, line0.1, line1.0));
+        }
+    }
+    let surface = BSplineSurface::homotopy(curve0.lift_up(), curve1.lift_up());
+    Surface::NURBSSurface(NURBSSurface::new(surface))
+}
+
 /// Creates a cone by R-sweeping.
 /// # Examples
 /// ```
@@ -349,6 +680,257 @@ pub fn scaled<T: Mapped<Point3, Curve, Surface>>(elem: &T, origin: Point3, scala
     transformed(elem, mat2 * mat1 * mat0)
 }
 
+/// The elements which can be mirrored by [`mirrored`].
+pub trait Mirror: Mapped<Point3, Curve, Surface> {
+    /// Inverts the faces reversed by the reflection. Nothing is done for the vertices, the edges,
+    /// and the wires.
+    #[doc(hidden)]
+    fn invert_faces(&mut self) {}
+}
+
+impl Mirror for Vertex {}
+impl Mirror for Edge {}
+impl Mirror for Wire {}
+impl Mirror for Face {
+    fn invert_faces(&mut self) { self.invert(); }
+}
+impl Mirror for Shell {
+    fn invert_faces(&mut self) {
+        self.face_iter_mut().for_each(|face| {
+            face.invert();
+        })
+    }
+}
+impl Mirror for Solid {
+    fn invert_faces(&mut self) { self.not() }
+}
+
+/// Returns the mirror image of a vertex, edge, wire, face, shell or solid across the plane
+/// through `origin` with `normal`.
+///
+/// The faces of the image face outward, i.e. they are inverted after the reflection, which
+/// reverses the orientations of the surfaces.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let image = builder::mirrored(&cube, Point3::origin(), Vector3::unit_x());
+/// let shell = &image.boundaries()[0];
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// // every face faces outward from the center of the image.
+/// let center = Point3::new(-1.5, 0.5, 0.5);
+/// shell.face_iter().for_each(|face| {
+///     let surface = face.oriented_surface();
+///     let pt = surface.subs(0.5, 0.5);
+///     assert!(surface.normal(0.5, 0.5).dot(pt - center) > 0.0);
+/// });
+/// ```
+#[inline(always)]
+pub fn mirrored<T: Mirror>(elem: &T, origin: Point3, normal: Vector3) -> T {
+    let mut res = transformed(elem, mirror::reflection(origin, normal));
+    res.invert_faces();
+    res
+}
+
+/// Mirrors `shell` across the plane through `origin` with `normal`, and sews the image to the
+/// original along the boundary edges on the plane, e.g. builds a closed shell of a symmetric part
+/// from its half.
+///
+/// The returned shell consists of the faces of `shell` followed by those of the image. The
+/// boundary edges of `shell` whose curves lie on the plane, and their vertices, are shared by
+/// the both sides; the other vertices and edges of the image are new.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the unit cube without the face on the yz-plane
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+/// let mut half: Shell = cube.into_boundaries().pop().unwrap();
+/// half.retain(|face| {
+///     let pt = face.oriented_surface().subs(0.5, 0.5);
+///     !pt.x.so_small()
+/// });
+/// assert_eq!(half.shell_condition(), ShellCondition::Oriented);
+///
+/// // the cuboid [-1, 1] x [0, 1] x [0, 1] with the split faces
+/// let whole = builder::mirrored_and_sewn(&half, Point3::origin(), Vector3::unit_x());
+/// assert_eq!(whole.len(), 10);
+/// assert_eq!(whole.shell_condition(), ShellCondition::Closed);
+/// // the 8 corners and the 4 vertices on the yz-plane
+/// let vertices: std::collections::HashSet<_> = whole.vertex_iter().map(|v| v.id()).collect();
+/// assert_eq!(vertices.len(), 12);
+/// ```
+#[inline(always)]
+pub fn mirrored_and_sewn(shell: &Shell, origin: Point3, normal: Vector3) -> Shell {
+    mirror::mirrored_and_sewn(shell, origin, normal)
+}
+
+/// The elements which can be projected onto faces by [`project`].
+pub trait Projection: Sized {
+    /// Returns the element projected onto `surface`.
+    #[doc(hidden)]
+    fn projected(&self, surface: &Surface) -> Result<Self>;
+}
+
+impl Projection for Edge {
+    fn projected(&self, surface: &Surface) -> Result<Self> {
+        project::project_edge(self, surface, &mut Default::default())
+    }
+}
+impl Projection for Wire {
+    fn projected(&self, surface: &Surface) -> Result<Self> {
+        let mut vertices = Default::default();
+        self.edge_iter()
+            .map(|edge| project::project_edge(edge, surface, &mut vertices))
+            .collect()
+    }
+}
+
+/// Projects an edge or a wire onto the surface of `face` along the normals of the surface, e.g.
+/// for the parting lines and the boundaries of trimming.
+///
+/// Each point is moved to the nearest point on the surface. The projections onto the planes are
+/// exact, and the others are the cubic B-spline curves fitted within `1.0e-5`. The projected
+/// edges of a wire share the projected vertices. The boundaries of `face` are not considered.
+/// # Errors
+/// Returns [`Error::UnsupportedProjection`] if a point is not projected onto the surface.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use std::f64::consts::PI;
+///
+/// // the half cylinder of radius 1 around the z-axis
+/// let v0 = builder::vertex(Point3::new(1.0, 0.0, -1.0));
+/// let v1 = builder::vertex(Point3::new(1.0, 0.0, 1.0));
+/// let line = builder::line(&v0, &v1);
+/// let shell = builder::rsweep(&line, Point3::origin(), Vector3::unit_z(), Rad(-PI));
+/// let face = &shell[0];
+///
+/// // the line outside the cylinder is projected radially.
+/// let v0 = builder::vertex(Point3::new(2.0, -1.0, 0.5));
+/// let v1 = builder::vertex(Point3::new(2.0, 1.0, 0.5));
+/// let line = builder::line(&v0, &v1);
+/// let edge = builder::project(&line, face).unwrap();
+/// assert_near!(edge.front().get_point(), Point3::new(2.0, -1.0, 0.5 * f64::sqrt(5.0)) / f64::sqrt(5.0));
+/// let curve = edge.oriented_curve();
+/// let (t0, t1) = curve.parameter_range();
+/// (0..=10).for_each(|i| {
+///     let pt = curve.subs(t0 + (t1 - t0) * i as f64 / 10.0);
+///     assert!(f64::abs(f64::hypot(pt.x, pt.y) - 1.0) < 1.0e-4);
+///     assert!(f64::abs(pt.z - 0.5) < 1.0e-4);
+/// });
+///
+/// // the square wire onto the plane z = x, whose edges share the vertices
+/// let square = builder::polyline(
+///     &[
+///         Point3::new(0.0, 0.0, 0.0),
+///         Point3::new(1.0, 0.0, 0.0),
+///         Point3::new(1.0, 1.0, 0.0),
+///         Point3::new(0.0, 1.0, 0.0),
+///     ],
+///     true,
+/// );
+/// let plane = builder::try_attach_plane(&[builder::polyline(
+///     &[
+///         Point3::new(-1.0, -1.0, -1.0),
+///         Point3::new(2.0, -1.0, 2.0),
+///         Point3::new(2.0, 2.0, 2.0),
+///         Point3::new(-1.0, 2.0, -1.0),
+///     ],
+///     true,
+/// )])
+/// .unwrap();
+/// let wire = builder::project(&square, &plane).unwrap();
+/// assert!(wire.is_closed());
+/// assert_near!(wire[1].front().get_point(), Point3::new(0.5, 0.0, 0.5));
+/// ```
+#[inline(always)]
+pub fn project<T: Projection>(elem: &T, face: &Face) -> Result<T> {
+    elem.projected(&face.get_surface())
+}
+
+/// Returns `count` copies of a vertex, edge, wire, face, shell or solid arranged along
+/// `direction` at intervals of `spacing`, e.g. the arrays of the slots of grilles.
+///
+/// The first element is `elem` itself, which shares the topology and the geometry with `elem`.
+/// The others are translated by `spacing` times the normalized `direction` one after another.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let cubes = builder::linear_pattern(&cube, Vector3::new(2.0, 0.0, 0.0), 4, 1.5);
+/// assert_eq!(cubes.len(), 4);
+/// assert!(cubes[0].boundaries()[0][0].is_same(&cube.boundaries()[0][0]));
+/// cubes.iter().enumerate().for_each(|(i, cube)| {
+///     let bdb: BoundingBox<Point3> = cube.vertex_iter().map(|v| v.get_point()).collect();
+///     assert_near!(*bdb.min(), Point3::new(1.5 * i as f64, 0.0, 0.0));
+/// });
+/// ```
+pub fn linear_pattern<T: Mapped<Point3, Curve, Surface> + Clone>(
+    elem: &T,
+    direction: Vector3,
+    count: usize,
+    spacing: f64,
+) -> Vec<T> {
+    let step = direction.normalize() * spacing;
+    (0..count)
+        .map(|i| match i {
+            0 => elem.clone(),
+            _ => translated(elem, step * i as f64),
+        })
+        .collect()
+}
+
+/// Returns `count` copies of a vertex, edge, wire, face, shell or solid arranged around the axis
+/// through `origin` at regular angles, e.g. the holes of the bolt circles.
+///
+/// The first element is `elem` itself, which shares the topology and the geometry with `elem`.
+/// The others are rotated by `2π / count` one after another, counterclockwise around `axis`.
+/// # Remarks
+/// `axis` must be normalized.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the bolt circle of the six holes
+/// let hole = builder::circle(Point3::new(2.0, 0.0, 0.0), Vector3::unit_z(), 0.3);
+/// let holes = builder::circular_pattern(&hole, Point3::origin(), Vector3::unit_z(), 6);
+/// assert_eq!(holes.len(), 6);
+/// holes.iter().enumerate().for_each(|(i, hole)| {
+///     let bdb: BoundingBox<Point3> = hole.vertex_iter().map(|v| v.get_point()).collect();
+///     let angle = std::f64::consts::PI / 3.0 * i as f64;
+///     let center = bdb.center();
+///     assert_near!(center, Point3::new(2.0 * f64::cos(angle), 2.0 * f64::sin(angle), 0.0));
+/// });
+/// ```
+pub fn circular_pattern<T: Mapped<Point3, Curve, Surface> + Clone>(
+    elem: &T,
+    origin: Point3,
+    axis: Vector3,
+    count: usize,
+) -> Vec<T> {
+    let angle = PI * 2.0 / count as f64;
+    (0..count)
+        .map(|i| match i {
+            0 => elem.clone(),
+            _ => rotated(elem, origin, axis, angle * i as f64),
+        })
+        .collect()
+}
+
 /// Sweeps a vertex, an edge, a wire, a face, or a shell by a vector.
 /// # Examples
 /// ```
@@ -393,25 +975,231 @@ pub fn tsweep<T: Sweep<Point3, Curve, Surface>>(elem: &T, vector: Vector3) -> T:
         &move |curve| curve.transformed(trsl),
         &move |surface| surface.transformed(trsl),
         &move |pt0, pt1| Curve::Line(Line(*pt0, *pt1)),
-        &move |curve0, curve1| match (curve0, curve1) {
-            (Curve::Line(line), Curve::Line(_)) => {
-                Surface::Plane(Plane::new(line.0, line.1, line.0 + vector))
-            }
-            (Curve::BSplineCurve(curve0), Curve::BSplineCurve(curve1)) => {
-                Surface::BSplineSurface(BSplineSurface::homotopy(curve0.clone(), curve1.clone()))
-            }
-            (Curve::NURBSCurve(curve0), Curve::NURBSCurve(curve1)) => {
-                Surface::NURBSSurface(NURBSSurface::new(BSplineSurface::homotopy(
-                    curve0.non_rationalized().clone(),
-                    curve1.non_rationalized().clone(),
-                )))
-            }
-            (Curve::IntersectionCurve(_), Curve::IntersectionCurve(_)) => unimplemented!(),
-            _ => unreachable!(),
-        },
+        &ruled_surface,
     )
 }
 
+/// The profiles of [`try_tsweep_with_taper`], i.e. the edges, the wires, and the planar faces.
+pub trait TaperProfile: Sweep<Point3, Curve, Surface> {
+    /// Returns the wires of the profile, and whether the left sides of the wires seen from the
+    /// end of `axis` are inside.
+    #[doc(hidden)]
+    fn taper_wires(&self, axis: Vector3) -> Result<(Vec<Wire>, bool)>;
+}
+
+impl TaperProfile for Edge {
+    #[inline(always)]
+    fn taper_wires(&self, _: Vector3) -> Result<(Vec<Wire>, bool)> {
+        Ok((vec![Wire::from(vec![self.clone()])], true))
+    }
+}
+
+impl TaperProfile for Wire {
+    #[inline(always)]
+    fn taper_wires(&self, _: Vector3) -> Result<(Vec<Wire>, bool)> {
+        Ok((vec![self.clone()], true))
+    }
+}
+
+impl TaperProfile for Face {
+    fn taper_wires(&self, axis: Vector3) -> Result<(Vec<Wire>, bool)> {
+        let normal = match self.oriented_surface() {
+            Surface::Plane(plane) => plane.normal(),
+            _ => {
+                return Err(Error::UnsupportedTaper(
+                    "the face must be planar.".to_string(),
+                ))
+            }
+        };
+        match normal.cross(axis).so_small() {
+            true => Ok((self.boundaries(), normal.dot(axis) > 0.0)),
+            false => Err(Error::UnsupportedTaper(
+                "the face must be perpendicular to the vector.".to_string(),
+            )),
+        }
+    }
+}
+
+/// Sweeps an edge, a wire, or a planar face by a vector, offsetting the profile inward
+/// linearly along the sweep direction by the taper angle.
+/// # Details
+/// Each edge of the profile is offset in its plane by `vector.magnitude() * tan(angle)` toward
+/// the inside, hence the side faces are drafted by exactly `angle`. The lines are translated,
+/// the circle arcs are replaced by the concentric ones, and the vertices are moved to the
+/// intersections of the offset edges. The inside of a face is the face itself, and that of an
+/// edge or a wire is the left side seen from the end of `vector`, e.g. the inside of the
+/// counterclockwise loops. The positive `angle` makes bosses narrowing toward the end, and the
+/// negative one makes them widening toward the end.
+/// # Errors
+/// Returns [`Error::UnsupportedTaper`] if
+/// - the profile is not on a plane perpendicular to `vector`, or `vector` is zero,
+/// - the profile has edges other than lines and circle arcs, e.g. the intersection curves,
+/// - `angle` is not less than the right angle, or
+/// - an edge or the whole profile collapses by the taper.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the disk with the radius 1
+/// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+/// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+/// let disk = builder::try_attach_plane(&[circle]).unwrap();
+///
+/// // the conical boss drafted by 10 degrees
+/// let boss: Solid = builder::try_tsweep_with_taper(&disk, Vector3::unit_z(), Deg(10.0)).unwrap();
+/// assert_eq!(boss.boundaries()[0].shell_condition(), ShellCondition::Closed);
+/// assert!(boss.is_geometric_consistent());
+/// let radius = 1.0 - f64::tan(10.0_f64.to_radians());
+/// boss.boundaries()[0].vertex_iter().for_each(|v| {
+///     let p = v.get_point();
+///     let r = f64::hypot(p.x, p.y);
+///     assert!(r.near(&1.0) && p.z.near(&0.0) || r.near(&radius) && p.z.near(&1.0));
+/// });
+///
+/// // the rectangle 2 x 1, whose edges are offset by the same distance
+/// let rect = builder::try_attach_plane(&[builder::polyline(
+///     &[
+///         Point3::new(0.0, 0.0, 0.0),
+///         Point3::new(2.0, 0.0, 0.0),
+///         Point3::new(2.0, 1.0, 0.0),
+///         Point3::new(0.0, 1.0, 0.0),
+///     ],
+///     true,
+/// )])
+/// .unwrap();
+/// let angle = Rad(f64::atan(0.25));
+/// let block: Solid = builder::try_tsweep_with_taper(&rect, Vector3::unit_z(), angle).unwrap();
+/// assert!(block.is_geometric_consistent());
+/// block.boundaries()[0].vertex_iter().for_each(|v| {
+///     let p = v.get_point();
+///     let (x, y) = match p.z.near(&1.0) {
+///         true => ([0.25, 1.75], [0.25, 0.75]),
+///         false => ([0.0, 2.0], [0.0, 1.0]),
+///     };
+///     assert!(x.iter().any(|x| p.x.near(x)) && y.iter().any(|y| p.y.near(y)));
+/// });
+///
+/// // The rectangle collapses by the steep taper.
+/// let steep = Rad(f64::atan(0.5));
+/// assert!(builder::try_tsweep_with_taper(&rect, Vector3::unit_z(), steep).is_err());
+///
+/// // the taper with the zero angle is the same as `tsweep`.
+/// let cylinder: Solid = builder::try_tsweep_with_taper(&disk, Vector3::unit_z(), Deg(0.0)).unwrap();
+/// let expected: Solid = builder::tsweep(&disk, Vector3::unit_z());
+/// let vertices0 = cylinder.boundaries()[0].vertex_iter();
+/// let vertices1 = expected.boundaries()[0].vertex_iter();
+/// vertices0.zip(vertices1).for_each(|(v0, v1)| assert_near!(v0.get_point(), v1.get_point()));
+/// ```
+pub fn try_tsweep_with_taper<T, R>(elem: &T, vector: Vector3, angle: R) -> Result<T::Swept>
+where
+    T: TaperProfile,
+    R: Into<Rad<f64>>, {
+    taper::tsweep_with_taper(elem, vector, angle.into())
+}
+
+/// the ruled surface between `curve0` and its affine image `curve1`
+pub(super) fn ruled_surface(curve0: &Curve, curve1: &Curve) -> Surface {
+    match (curve0, curve1) {
+        (Curve::Line(line0), Curve::Line(line1)) => {
+            Surface::Plane(Plane::new(line0.0, line0.1, line1.0))
+        }
+        (Curve::BSplineCurve(curve0), Curve::BSplineCurve(curve1)) => {
+            Surface::BSplineSurface(BSplineSurface::homotopy(curve0.clone(), curve1.clone()))
+        }
+        (Curve::NURBSCurve(curve0), Curve::NURBSCurve(curve1)) => {
+            Surface::NURBSSurface(NURBSSurface::new(BSplineSurface::homotopy(
+                curve0.non_rationalized().clone(),
+                curve1.non_rationalized().clone(),
+            )))
+        }
+        (Curve::IntersectionCurve(_), Curve::IntersectionCurve(_)) => unimplemented!(),
+        (Curve::Custom(_), Curve::Custom(_)) => Surface::NURBSSurface(NURBSSurface::new(
+            BSplineSurface::homotopy(curve0.clone().lift_up(), curve1.clone().lift_up()),
+        )),
+        _ => unreachable!(),
+    }
+}
+
+/// Sweeps the closed planar wires by a vector, and returns the solid capped by the planes
+/// at both ends.
+///
+/// The first cap is attached by [`try_attach_plane`], i.e. the first wire is the outer
+/// boundary and the others are the holes, and it is inverted if its normal is opposite to
+/// `vector`. So, the orientations of the wires do not matter as long as they are consistent.
+/// # Errors
+/// Returns the errors of [`try_attach_plane`], e.g. [`Error::WireNotInOnePlane`].
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use std::f64::consts::PI;
+///
+/// // the closed wire, not a face
+/// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+/// let circle: Wire = builder::rsweep(&v, Point3::origin(), -Vector3::unit_z(), Rad(2.0 * PI));
+/// let cylinder: Solid = builder::try_tsweep_capped(&[circle], Vector3::unit_z()).unwrap();
+/// let shell = &cylinder.boundaries()[0];
+/// assert_eq!(shell.len(), 4);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// // the bottom cap faces downward.
+/// let normal = shell[0].oriented_surface().normal(0.0, 0.0);
+/// assert_near!(normal, -Vector3::unit_z());
+///
+/// // the non-planar wire cannot be capped.
+/// let v = [
+///     builder::vertex(Point3::new(0.0, 0.0, 0.0)),
+///     builder::vertex(Point3::new(1.0, 0.0, 0.0)),
+///     builder::vertex(Point3::new(1.0, 1.0, 0.0)),
+///     builder::vertex(Point3::new(0.0, 1.0, 1.0)),
+/// ];
+/// let wire: Wire = (0..4).map(|i| builder::line(&v[i], &v[(i + 1) % 4])).collect();
+/// assert_eq!(
+///     builder::try_tsweep_capped(&[wire], Vector3::unit_z()),
+///     Err(errors::Error::WireNotInOnePlane),
+/// );
+/// ```
+pub fn try_tsweep_capped(wires: &[Wire], vector: Vector3) -> Result<Solid> {
+    let face = try_attach_plane(wires)?;
+    let normal = face.oriented_surface().normal(0.0, 0.0);
+    let face = match normal.dot(vector) < 0.0 {
+        true => face.inverse(),
+        false => face,
+    };
+    Ok(tsweep(&face, vector))
+}
+
+/// Extrudes a closed planar wire by `vector`, and returns the solid capped by the planar faces
+/// at both ends.
+///
+/// This is [`try_tsweep_capped`] for the profiles without holes. The wire may be oriented in
+/// either direction, and `vector` need not be perpendicular to the plane of the wire.
+/// # Errors
+/// Returns the errors of [`try_attach_plane`], e.g. the wire is not closed or not planar.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// let points = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)].map(|(x, y)| Point3::new(x, y, 0.0));
+/// let rectangle = builder::polyline(&points, true);
+/// let cuboid = builder::tsweep_solid(&rectangle, Vector3::new(0.0, 0.0, 3.0)).unwrap();
+/// let shell = &cuboid.boundaries()[0];
+/// assert_eq!(shell.len(), 6);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+///
+/// // the downward extrusion of the inverted wire is also closed, with the caps facing outward.
+/// let cuboid = builder::tsweep_solid(&rectangle.inverse(), Vector3::new(0.0, 0.0, -3.0)).unwrap();
+/// let shell = &cuboid.boundaries()[0];
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// assert_near!(shell[0].oriented_surface().normal(0.5, 0.5), Vector3::unit_z());
+///
+/// // the open wire cannot be capped.
+/// let open = builder::polyline(&points, false);
+/// assert!(builder::tsweep_solid(&open, Vector3::unit_z()).is_err());
+/// ```
+#[inline(always)]
+pub fn tsweep_solid(wire: &Wire, vector: Vector3) -> Result<Solid> {
+    try_tsweep_capped(std::slice::from_ref(wire), vector)
+}
+
 /// Sweeps a vertex, an edge, a wire, a face, or a shell by the rotation.
 /// # Details
 /// If the absolute value of `angle` is more than 2π rad, then the result is closed shape.
@@ -512,15 +1300,67 @@ pub fn rsweep<T: ClosedSweep<Point3, Curve, Surface>, R: Into<Rad<f64>>>(
     origin: Point3,
     axis: Vector3,
     angle: R,
+) -> T::Swept {
+    rsweep_with_division(elem, origin, axis, angle, 0)
+}
+
+/// Sweeps a vertex, an edge, a wire, a face, or a shell by the rotation,
+/// dividing the swept surfaces into `division` segments of the same angle.
+///
+/// [`rsweep`] divides the sweep into the least number of segments, i.e. one segment for the
+/// angles less than a half turn and two segments for the others. Some formats and toolchains
+/// require the surfaces within a quarter turn, and they can be made by the larger divisions.
+/// # Remarks
+/// - `axis` must be normalized. If not, panics occurs in debug mode.
+/// - Each segment must be at most a half turn, and `division` is raised to the number of
+///   segments by [`rsweep`] if it is less.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// const PI: Rad<f64> = Rad(std::f64::consts::PI);
+///
+/// let v: Vertex = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+/// let e: Edge = builder::tsweep(&v, Vector3::unit_z());
+/// // the cylinder consisting of four quarters
+/// let cylinder: Shell = builder::rsweep_with_division(
+///     &e,
+///     Point3::origin(),
+///     Vector3::unit_z(),
+///     PI * 2.0,
+///     4,
+/// );
+/// assert_eq!(cylinder.len(), 4);
+/// // the first segment is the quarter from the x-axis to the y-axis.
+/// let surface = cylinder[0].oriented_surface();
+/// assert_near!(surface.subs(0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+/// assert_near!(surface.subs(0.0, PI.0 / 2.0), Point3::new(0.0, 1.0, 0.0));
+///
+/// // the division is raised to the least one.
+/// let half: Shell = builder::rsweep_with_division(
+///     &e,
+///     Point3::origin(),
+///     Vector3::unit_z(),
+///     PI * 1.5,
+///     1,
+/// );
+/// assert_eq!(half.len(), 2);
+/// ```
+pub fn rsweep_with_division<T: ClosedSweep<Point3, Curve, Surface>, R: Into<Rad<f64>>>(
+    elem: &T,
+    origin: Point3,
+    axis: Vector3,
+    angle: R,
+    division: usize,
 ) -> T::Swept {
     debug_assert!(axis.magnitude().near(&1.0));
     let angle = angle.into();
     if angle.0.abs() < 2.0 * PI.0 {
-        partial_rsweep(elem, origin, axis, angle)
+        let least = if angle.0.abs() < PI.0 { 1 } else { 2 };
+        partial_rsweep(elem, origin, axis, angle, usize::max(division, least))
     } else if angle.0 > 0.0 {
-        whole_rsweep(elem, origin, axis)
+        whole_rsweep(elem, origin, axis, usize::max(division, 2))
     } else {
-        whole_rsweep(elem, origin, -axis)
+        whole_rsweep(elem, origin, -axis, usize::max(division, 2))
     }
 }
 
@@ -529,8 +1369,8 @@ fn partial_rsweep<T: MultiSweep<Point3, Curve, Surface>>(
     origin: Point3,
     axis: Vector3,
     angle: Rad<f64>,
+    division: usize,
 ) -> T::Swept {
-    let division = if angle.0.abs() < PI.0 { 1 } else { 2 };
     let mat0 = Matrix4::from_translation(-origin.to_vec());
     let mat1 = Matrix4::from_axis_angle(axis, angle / division as f64);
     let mat2 = Matrix4::from_translation(origin.to_vec());
@@ -562,9 +1402,11 @@ fn whole_rsweep<T: ClosedSweep<Point3, Curve, Surface>>(
     elem: &T,
     origin: Point3,
     axis: Vector3,
+    division: usize,
 ) -> T::Swept {
+    let angle = PI * 2.0 / division as f64;
     let mat0 = Matrix4::from_translation(-origin.to_vec());
-    let mat1 = Matrix4::from_axis_angle(axis, PI);
+    let mat1 = Matrix4::from_axis_angle(axis, angle);
     let mat2 = Matrix4::from_translation(origin.to_vec());
     let trsl = mat2 * mat1 * mat0;
     elem.closed_sweep(
@@ -576,7 +1418,7 @@ fn whole_rsweep<T: ClosedSweep<Point3, Curve, Surface>>(
                 pt.to_homogeneous(),
                 origin,
                 axis,
-                PI,
+                angle,
             )))
         },
         &move |curve, _| {
@@ -586,21 +1428,1641 @@ fn whole_rsweep<T: ClosedSweep<Point3, Curve, Surface>>(
                 axis,
             )))
         },
-        2,
+        division,
     )
 }
 
-#[test]
-fn partial_torus() {
-    let v = vertex(Point3::new(0.5, 0.0, 0.0));
-    let w = rsweep(&v, Point3::new(0.75, 0.0, 0.0), Vector3::unit_y(), Rad(7.0));
-    let face = try_attach_plane(&[w]).unwrap();
-    let torus = rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(2.0));
-    assert!(torus.is_geometric_consistent());
-    let torus = rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(5.0));
-    assert!(torus.is_geometric_consistent());
-    let torus = rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(-2.0));
-    assert!(torus.is_geometric_consistent());
-    let torus = rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(-5.0));
-    assert!(torus.is_geometric_consistent());
+/// The profiles of [`revolve_solid`], i.e. the planar faces and the closed planar wires.
+pub trait RevolutionProfile {
+    /// Returns the planar face bounded by the profile.
+    fn try_into_face(&self) -> Result<Face>;
+}
+
+impl RevolutionProfile for Face {
+    #[inline(always)]
+    fn try_into_face(&self) -> Result<Face> { Ok(self.clone()) }
+}
+
+impl RevolutionProfile for Wire {
+    #[inline(always)]
+    fn try_into_face(&self) -> Result<Face> { try_attach_plane(std::slice::from_ref(self)) }
+}
+
+/// Revolves a planar face or a closed planar wire, and returns the solid.
+/// # Details
+/// If the absolute value of `angle` is less than 2π rad, the solid is capped by the profile
+/// and the rotated one, and otherwise the solid is the closed revolution. The profile is
+/// inverted if its normal is opposite to the revolving direction, so the orientation of the
+/// profile does not matter. The closed wire is attached a plane by [`try_attach_plane`].
+/// # Remarks
+/// `axis` must be normalized. If not, panics occurs in debug mode.
+/// # Errors
+/// Returns the errors of [`try_attach_plane`], e.g. [`Error::WireNotInOnePlane`].
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the square on the xz-plane, apart from the z-axis
+/// let v = [(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0)]
+///     .map(|(x, z)| builder::vertex(Point3::new(x, 0.0, z)));
+/// let wire: Wire = (0..4).map(|i| builder::line(&v[i], &v[(i + 1) % 4])).collect();
+///
+/// // a quarter of the ring, capped at both ends
+/// let quarter = builder::revolve_solid(&wire, Point3::origin(), Vector3::unit_z(), Deg(90.0)).unwrap();
+/// let shell = &quarter.boundaries()[0];
+/// assert_eq!(shell.len(), 6);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// // the cap on the xz-plane faces outward, whichever orientation the wire has.
+/// let caps_outward = |solid: &Solid| {
+///     solid.boundaries()[0].face_iter().any(|face| {
+///         let normal = face.oriented_surface().normal(0.5, 0.5);
+///         normal.near(&-Vector3::unit_y())
+///     })
+/// };
+/// assert!(caps_outward(&quarter));
+/// let inverse = builder::revolve_solid(&wire.inverse(), Point3::origin(), Vector3::unit_z(), Deg(90.0)).unwrap();
+/// assert!(caps_outward(&inverse));
+///
+/// // the whole ring, without caps
+/// let face = builder::try_attach_plane(&[wire]).unwrap();
+/// let ring = builder::revolve_solid(&face, Point3::origin(), Vector3::unit_z(), Deg(360.0)).unwrap();
+/// assert_eq!(ring.boundaries()[0].len(), 8);
+/// assert_eq!(ring.boundaries()[0].shell_condition(), ShellCondition::Closed);
+///
+/// // the open wire cannot be revolved into a solid.
+/// let open: Wire = (0..3).map(|i| builder::line(&v[i], &v[i + 1])).collect();
+/// assert!(builder::revolve_solid(&open, Point3::origin(), Vector3::unit_z(), Deg(90.0)).is_err());
+/// ```
+pub fn revolve_solid<T: RevolutionProfile, R: Into<Rad<f64>>>(
+    profile: &T,
+    origin: Point3,
+    axis: Vector3,
+    angle: R,
+) -> Result<Solid> {
+    let angle = angle.into();
+    let face = profile.try_into_face()?;
+    let normal = face.oriented_surface().normal(0.0, 0.0);
+    // the revolving direction at the farthest vertex from the axis
+    let direction = face
+        .boundaries()
+        .iter()
+        .flat_map(Wire::vertex_iter)
+        .map(|v| axis.cross(v.get_point() - origin) * angle.0.signum())
+        .max_by(|a, b| a.magnitude2().total_cmp(&b.magnitude2()))
+        .unwrap_or_else(Vector3::zero);
+    let face = match normal.dot(direction) < 0.0 {
+        true => face.inverse(),
+        false => face,
+    };
+    Ok(rsweep(&face, origin, axis, angle))
+}
+
+/// Sweeps a vertex, an edge, a wire, a face, or a shell by the screw motion, i.e. the rotation
+/// around the axis with the translation along the axis, and returns the helical shape.
+///
+/// The element moves by `pitch` along `axis` per turn, and turns `turns` times around the axis
+/// through `origin`. The positive pitches give the right-handed helices, as the threads of
+/// the usual screws. The swept curves and surfaces are divided into the segments of at most
+/// a quarter turn, and approximated by the cubic interpolation of the motion.
+/// # Remarks
+/// - `axis` must be normalized. If not, panics occurs in debug mode.
+/// - `turns` must be positive. If not, panics occurs in debug mode.
+/// - The curves and the surfaces of the element must not contain intersection curves.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use std::f64::consts::PI;
+///
+/// // the spring of three turns
+/// let v = builder::vertex(Point3::new(2.2, 0.0, 0.0));
+/// let circle = builder::rsweep(&v, Point3::new(2.0, 0.0, 0.0), Vector3::unit_y(), Rad(2.0 * PI));
+/// let profile: Face = builder::try_attach_plane(&[circle]).unwrap();
+/// let spring: Solid = builder::helix_sweep(&profile, Point3::origin(), Vector3::unit_z(), 1.0, 3.0);
+/// let shell = &spring.boundaries()[0];
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// // the ends of the spring are two faces, and 12 quarter turns of the two halves of the circle.
+/// assert_eq!(shell.len(), 2 + 12 * 2);
+///
+/// // the outermost point of the profile after the first quarter turn
+/// let point = shell[1].oriented_surface().subs(0.0, 1.0);
+/// assert_near!(point, Point3::new(0.0, 2.2, 0.25));
+/// ```
+pub fn helix_sweep<T: MultiSweep<Point3, Curve, Surface>>(
+    elem: &T,
+    origin: Point3,
+    axis: Vector3,
+    pitch: f64,
+    turns: f64,
+) -> T::Swept {
+    debug_assert!(axis.magnitude().near(&1.0));
+    debug_assert!(turns > 0.0);
+    let division = usize::max(f64::ceil(turns * 4.0) as usize, 1);
+    let angle = 2.0 * PI.0 * turns / division as f64;
+    let distance = pitch * turns / division as f64;
+    let mat0 = Matrix4::from_translation(-origin.to_vec());
+    let mat1 = Matrix4::from_axis_angle(axis, Rad(angle));
+    let mat2 = Matrix4::from_translation(origin.to_vec() + axis * distance);
+    let trsl = mat2 * mat1 * mat0;
+    let sections = pipe::screw_sections(origin, axis, angle, distance, HELIX_DIVISION);
+    elem.multi_sweep(
+        &move |pt| trsl.transform_point(*pt),
+        &move |curve| curve.transformed(trsl),
+        &move |surface| surface.transformed(trsl),
+        &|pt, _| pipe::sweep_point(*pt, &sections),
+        &|curve, _| pipe::sweep_curve(curve, &sections),
+        division,
+    )
+}
+
+/// the number of the segments of the cubic interpolation in a quarter turn of the helical sweep
+const HELIX_DIVISION: usize = 16;
+
+/// Returns the helix starting from `start`, which turns `turns` times around the axis through
+/// `origin` and moves by `pitch` along `axis` per turn.
+///
+/// This is the helical sweep of the vertex at `start`. cf. [`helix_sweep`]
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use std::f64::consts::PI;
+/// let helix: Wire = builder::helix(
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::origin(),
+///     Vector3::unit_z(),
+///     0.5,
+///     2.5,
+/// );
+/// assert_eq!(helix.len(), 10);
+/// assert_near!(helix.back_vertex().unwrap().get_point(), Point3::new(-1.0, 0.0, 1.25));
+/// // the points are on the helix.
+/// helix.edge_iter().for_each(|edge| {
+///     let curve = edge.oriented_curve();
+///     (0..=10).for_each(|i| {
+///         let p = curve.subs(i as f64 / 10.0);
+///         assert_near!(p.x * p.x + p.y * p.y, 1.0);
+///         let angle = f64::atan2(p.y, p.x);
+///         let turns = (p.z / 0.5 - angle / (2.0 * PI)).round();
+///         assert_near!(p.z, 0.5 * (turns + angle / (2.0 * PI)));
+///     });
+/// });
+/// ```
+pub fn helix(start: Point3, origin: Point3, axis: Vector3, pitch: f64, turns: f64) -> Wire {
+    helix_sweep(&vertex(start), origin, axis, pitch, turns)
+}
+
+/// Sweeps the wire `profile` along the wire `path`, and returns the lateral shell.
+///
+/// The profile is moved rigidly along the path by the rotation minimizing frame, i.e. the
+/// frame does not twist around the tangent, starting from the first point of the path.
+/// The profile is usually put at the first point in the plane perpendicular to the path.
+/// The lateral faces are divided at the vertices of the path and approximated by the
+/// cubic interpolation along the curved edges.
+/// # Remarks
+/// - The path should be tangent-continuous. At the corners, the sections at the vertices
+///   are perpendicular to the average of the tangents, and the pipe is pinched.
+/// - If the path is closed, the twist of the frame is distributed along the path so that
+///   the pipe is closed.
+/// # Panics
+/// Panics if the path is empty or not continuous, or if the profile contains intersection curves.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use std::f64::consts::PI;
+///
+/// // the path bent by a quarter circle
+/// let v = [
+///     builder::vertex(Point3::new(0.0, 0.0, 0.0)),
+///     builder::vertex(Point3::new(0.0, 0.0, 2.0)),
+///     builder::vertex(Point3::new(2.0, 0.0, 4.0)),
+///     builder::vertex(Point3::new(4.0, 0.0, 4.0)),
+/// ];
+/// let transit = Point3::new(2.0 - f64::sqrt(2.0), 0.0, 2.0 + f64::sqrt(2.0));
+/// let path: Wire = vec![
+///     builder::line(&v[0], &v[1]),
+///     builder::circle_arc(&v[1], &v[2], transit),
+///     builder::line(&v[2], &v[3]),
+/// ]
+/// .into();
+/// // the circle of radius 0.5 perpendicular to the path
+/// let v = builder::vertex(Point3::new(0.5, 0.0, 0.0));
+/// let circle: Wire = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(2.0 * PI));
+///
+/// let tube: Shell = builder::pipe(&circle, &path);
+/// assert_eq!(tube.len(), 6);
+/// assert_eq!(tube.shell_condition(), ShellCondition::Oriented);
+/// // the bent part is a part of the torus.
+/// let surface = tube[2].oriented_surface();
+/// for i in 0..=10 {
+///     for j in 0..=10 {
+///         let p = surface.subs(i as f64 / 10.0, j as f64 / 10.0);
+///         let r = f64::hypot(p.x - 2.0, p.z - 2.0) - 2.0;
+///         assert_near!(r * r + p.y * p.y, 0.25);
+///     }
+/// }
+/// ```
+pub fn pipe(profile: &Wire, path: &Wire) -> Shell {
+    let profiles = [profile.clone()];
+    let last = |matrix| vec![transformed(profile, matrix)];
+    pipe::pipe_faces(&profiles, path, last)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Sweeps the face `profile` along the wire `path`, and returns the solid.
+///
+/// The solid consists of the inverted profile, the lateral faces by [`pipe`], and the moved
+/// profile at the end of the path. The normal of the profile should be along the path,
+/// as in [`tsweep`]. If the path is closed, the profile is not included, and the solid is
+/// bounded by the lateral shells of the boundaries of the profile.
+/// # Panics
+/// Panics if the path is empty or not continuous, or if the profile contains intersection curves.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the S-shaped path
+/// let v = [
+///     builder::vertex(Point3::new(0.0, 0.0, 0.0)),
+///     builder::vertex(Point3::new(0.0, 0.0, 2.0)),
+///     builder::vertex(Point3::new(0.0, 0.0, 4.0)),
+/// ];
+/// let path: Wire = vec![
+///     builder::circle_arc(&v[0], &v[1], Point3::new(1.0, 0.0, 1.0)),
+///     builder::circle_arc(&v[1], &v[2], Point3::new(-1.0, 0.0, 3.0)),
+/// ]
+/// .into();
+/// // the square profile of the channel, whose normal is along the path
+/// let v = builder::vertex(Point3::new(0.0, -0.25, 0.0));
+/// let e = builder::tsweep(&v, Vector3::new(0.0, 0.5, 0.0));
+/// let profile: Face = builder::tsweep(&e, Vector3::new(0.25, 0.0, 0.0));
+/// let profile = builder::translated(&profile, Vector3::new(-0.125, 0.0, 0.0));
+///
+/// let channel: Solid = builder::pipe_solid(&profile, &path);
+/// let shell = &channel.boundaries()[0];
+/// assert_eq!(shell.len(), 2 + 4 * 2);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// ```
+pub fn pipe_solid(profile: &Face, path: &Wire) -> Solid {
+    let mut cap = None;
+    let last = |matrix| {
+        let face = transformed(profile, matrix);
+        let boundaries = face.boundaries();
+        cap = Some(face);
+        boundaries
+    };
+    let faces = pipe::pipe_faces(&profile.boundaries(), path, last);
+    match cap {
+        Some(cap) => {
+            let mut shell = Shell::new();
+            shell.push(profile.inverse());
+            shell.extend(faces.into_iter().flatten());
+            shell.push(cap);
+            Solid::new(vec![shell])
+        }
+        None => Solid::new(faces.into_iter().map(Shell::from).collect()),
+    }
+}
+
+/// Rounds the straight edges `edges` between the planar faces of `solid` by the rolling ball of
+/// radius `radius`.
+///
+/// This is not a general fillet of B-rep solids, but the fillet of the box-like polyhedral
+/// corners. The planar faces adjacent to the edges are retrimmed along the contact lines of the
+/// ball, and the fillet faces are the circular cylinders between them. At the vertices of three
+/// filleted edges, the corners are the spherical patches, and at the vertices of two filleted
+/// edges, the cylinders meet along the quarters of the ellipses.
+///
+/// The supported configurations are restricted as follows:
+/// - the filleted edges are convex lines between planes,
+/// - the vertices of the filleted edges are shared by three edges,
+/// - at each vertex, one, two or three edges are filleted, and
+///   - if one, the third face is perpendicular to the filleted edge, and
+///   - if two or three, the three faces are perpendicular to each other.
+///
+/// Moreover, `radius` must be so small that the contact lines do not cross each other.
+/// # Errors
+/// Returns [`Error::UnsupportedFillet`] with the reason if the configuration is not supported,
+/// e.g. [`UnsupportedFilletReason::NotBetweenPlanes`] if an edge is adjacent to a curved face.
+///
+/// [`UnsupportedFilletReason::NotBetweenPlanes`]: errors::UnsupportedFilletReason::NotBetweenPlanes
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the unit cube
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // round the vertical edges
+/// let edges: Vec<EdgeID> = cube
+///     .edge_iter()
+///     .filter(|edge| {
+///         let dir = edge.back().get_point() - edge.front().get_point();
+///         dir.x.so_small() && dir.y.so_small()
+///     })
+///     .map(|edge| edge.id())
+///     .collect::<std::collections::HashSet<_>>()
+///     .into_iter()
+///     .collect();
+/// assert_eq!(edges.len(), 4);
+/// let rounded = builder::planar_fillet(&cube, &edges, 0.25).unwrap();
+/// let shell = &rounded.boundaries()[0];
+/// assert_eq!(shell.len(), 6 + 4);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// // the fillet faces are the quarters of the cylinders.
+/// let fillet = shell.face_iter().find(|face| {
+///     matches!(face.get_surface(), Surface::NURBSSurface(_))
+/// }).unwrap();
+/// let surface = fillet.oriented_surface();
+/// let p = surface.subs(0.3, 0.6);
+/// let center = Point3::new(
+///     if p.x < 0.5 { 0.25 } else { 0.75 },
+///     if p.y < 0.5 { 0.25 } else { 0.75 },
+///     p.z,
+/// );
+/// assert_near!(p.distance(center), 0.25);
+/// // the normal faces outward.
+/// assert!(surface.normal(0.3, 0.6).dot(p - center) > 0.0);
+///
+/// // round two edges sharing a vertex
+/// let boundary = &cube.boundaries()[0][0].boundaries()[0];
+/// let edges: Vec<EdgeID> = boundary.edge_iter().take(2).map(|edge| edge.id()).collect();
+/// let rounded = builder::planar_fillet(&cube, &edges, 0.25).unwrap();
+/// let shell = &rounded.boundaries()[0];
+/// assert_eq!(shell.len(), 6 + 2);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// // the fillets meet along the curve lying on the both cylinders.
+/// let fillets: Vec<&Face> = shell
+///     .face_iter()
+///     .filter(|face| matches!(face.get_surface(), Surface::NURBSSurface(_)))
+///     .collect();
+/// let miter = fillets[0].boundaries()[0]
+///     .edge_iter()
+///     .find(|edge0| fillets[1].boundaries()[0].edge_iter().any(|edge1| edge0.id() == edge1.id()))
+///     .unwrap()
+///     .get_curve();
+/// (0..=10).for_each(|i| {
+///     let p = miter.subs(i as f64 / 10.0);
+///     fillets.iter().for_each(|face| {
+///         let surface = face.get_surface();
+///         let (u, v) = surface.search_nearest_parameter(p, None, 100).unwrap();
+///         assert_near!(surface.subs(u, v), p);
+///     });
+/// });
+/// ```
+pub fn planar_fillet(solid: &Solid, edges: &[EdgeID], radius: f64) -> Result<Solid> {
+    let edges = edges.iter().map(|id| (*id, fillet::Profile::round(radius)));
+    fillet::blend(solid, edges.collect())
+}
+
+/// Rounds the straight edges between the planar faces of `solid` by the rolling balls whose
+/// radii vary along the edges by the radius laws.
+///
+/// The parameters of the radius laws run from `0.0` at the absolute front vertex of the edge to
+/// `1.0` at the absolute back vertex, and the radii are interpolated linearly between them.
+/// The supported configurations are the same as [`planar_fillet`], and the radii of the three edges
+/// must be equal at their common vertex. At the vertices of two edges, the radii of the two
+/// edges must be the same constant.
+/// # Errors
+/// Returns [`Error::UnsupportedFillet`] with the reason if the configuration is not supported or
+/// the radius law is invalid.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the unit cube
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // the vertical edges, whose radii grow upward
+/// use builder::RadiusLaw;
+/// let edges: Vec<(EdgeID, RadiusLaw)> = cube
+///     .edge_iter()
+///     .filter(|edge| edge.absolute_front().get_point().z.so_small())
+///     .filter(|edge| edge.absolute_back().get_point().z.near(&1.0))
+///     .map(|edge| (edge.id(), RadiusLaw::Table(vec![(0.0, 0.1), (0.5, 0.3), (1.0, 0.2)])))
+///     .collect::<std::collections::HashMap<_, _>>()
+///     .into_iter()
+///     .collect();
+/// assert_eq!(edges.len(), 4);
+/// let rounded = builder::planar_variable_fillet(&cube, &edges).unwrap();
+/// let shell = &rounded.boundaries()[0];
+/// assert_eq!(shell.len(), 6 + 4);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+///
+/// // the radius at the middle of the edge is 0.3.
+/// let fillet = shell.face_iter().find(|face| {
+///     matches!(face.get_surface(), Surface::NURBSSurface(_))
+/// }).unwrap();
+/// let surface = fillet.oriented_surface();
+/// let ((u0, u1), (v0, v1)) = surface.parameter_range();
+/// let p = surface.subs((u0 + u1) / 2.0, (v0 + v1) / 2.0);
+/// assert_near!(p.z, 0.5);
+/// let center = Point3::new(
+///     if p.x < 0.5 { 0.3 } else { 0.7 },
+///     if p.y < 0.5 { 0.3 } else { 0.7 },
+///     0.5,
+/// );
+/// assert_near!(p.distance(center), 0.3);
+///
+/// // the parameters must increase from 0 to 1.
+/// let laws = vec![(edges[0].0, RadiusLaw::Table(vec![(0.0, 0.1), (0.7, 0.2)]))];
+/// assert!(matches!(
+///     builder::planar_variable_fillet(&cube, &laws),
+///     Err(errors::Error::UnsupportedFillet(errors::UnsupportedFilletReason::InvalidRadiusLaw)),
+/// ));
+/// ```
+pub fn planar_variable_fillet(solid: &Solid, edges: &[(EdgeID, RadiusLaw)]) -> Result<Solid> {
+    let edges = edges
+        .iter()
+        .map(|(id, law)| Ok((*id, fillet::Profile::variable(law.clone())?)))
+        .collect::<Result<Vec<_>>>()?;
+    fillet::blend(solid, edges)
+}
+
+/// Chamfers the straight edges `edges` between the planar faces of `solid` by the planes cutting
+/// the adjacent faces at the distances `distance1` and `distance2` from the edges.
+///
+/// `distance1` is the distance on the face whose boundary contains the edge in its absolute
+/// direction, and `distance2` is the one on the other face. At the vertices of three chamfered
+/// edges, the corners are cut by the triangles. At the vertices of two chamfered edges, the
+/// chamfers meet along the lines, and the chamfers must cut the third edge at the same point.
+/// The supported configurations are the same as [`planar_fillet`].
+/// # Errors
+/// Returns [`Error::UnsupportedFillet`] with the reason if the configuration is not supported.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the unit cube
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // chamfer all edges
+/// let mut edges: Vec<EdgeID> = Vec::new();
+/// cube.edge_iter().for_each(|edge| {
+///     if !edges.contains(&edge.id()) {
+///         edges.push(edge.id());
+///     }
+/// });
+/// let chamfered = builder::planar_chamfer(&cube, &edges, 0.1, 0.1).unwrap();
+/// let shell = &chamfered.boundaries()[0];
+/// assert_eq!(shell.len(), 6 + 12 + 8);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// // all faces are planes facing outward.
+/// shell.face_iter().for_each(|face| {
+///     let surface = face.oriented_surface();
+///     assert!(matches!(surface, Surface::Plane(_)));
+///     let p = surface.subs(0.5, 0.5);
+///     assert!(surface.normal(0.5, 0.5).dot(p - Point3::new(0.5, 0.5, 0.5)) > 0.0);
+/// });
+///
+/// // chamfer the edges of the top face, which meet along the lines at the corners
+/// let top = &cube.boundaries()[0][5].boundaries()[0];
+/// let edges: Vec<EdgeID> = top.edge_iter().map(|edge| edge.id()).collect();
+/// let chamfered = builder::planar_chamfer(&cube, &edges, 0.1, 0.1).unwrap();
+/// let shell = &chamfered.boundaries()[0];
+/// assert_eq!(shell.len(), 6 + 4);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// ```
+pub fn planar_chamfer(
+    solid: &Solid,
+    edges: &[EdgeID],
+    distance1: f64,
+    distance2: f64,
+) -> Result<Solid> {
+    let profile = fillet::Profile::Chamfer(distance1, distance2);
+    fillet::blend(
+        solid,
+        edges.iter().map(|id| (*id, profile.clone())).collect(),
+    )
+}
+
+/// Rounds the corners of `wire` at the vertices by the tangent circle arcs of the radii.
+///
+/// The edges at the rounded corners are trimmed and replaced by the new lines, and the others
+/// are shared with `wire`. The corners are usually on one plane, e.g. the corners of the
+/// profiles before sweeping.
+/// # Failures
+/// Returns [`errors::Error::UnsupportedFillet`] with the reason if
+/// - the wire is not continuous, or a vertex is not the junction of two edges in the wire,
+/// - the edges at the corners are not lines, or the corners are straight or folded,
+/// - a radius is not positive, or the radii are so large that the edges vanish.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the rectangle with the rounded corners
+/// let rectangle = builder::polyline(
+///     &[
+///         Point3::new(0.0, 0.0, 0.0),
+///         Point3::new(2.0, 0.0, 0.0),
+///         Point3::new(2.0, 1.0, 0.0),
+///         Point3::new(0.0, 1.0, 0.0),
+///     ],
+///     true,
+/// );
+/// let radii: Vec<(VertexID, f64)> = rectangle.vertex_iter().map(|v| (v.id(), 0.25)).collect();
+/// let rounded = builder::fillet_wire(&rectangle, &radii).unwrap();
+/// assert_eq!(rounded.len(), 8);
+/// assert!(rounded.is_closed());
+///
+/// // the arc at the origin is the quarter of the circle.
+/// let arc = rounded.edge_iter().last().unwrap();
+/// assert_near!(arc.front().get_point(), Point3::new(0.0, 0.25, 0.0));
+/// assert_near!(arc.back().get_point(), Point3::new(0.25, 0.0, 0.0));
+/// let curve = arc.oriented_curve();
+/// let (t0, t1) = curve.parameter_range();
+/// (0..=10).for_each(|i| {
+///     let p = curve.subs(t0 + (t1 - t0) * i as f64 / 10.0);
+///     assert_near!(p.distance(Point3::new(0.25, 0.25, 0.0)), 0.25);
+/// });
+///
+/// // the rounded profile makes a solid.
+/// let face = builder::try_attach_plane(&[rounded]).unwrap();
+/// let solid = builder::tsweep(&face, Vector3::unit_z());
+/// assert_eq!(solid.boundaries()[0].shell_condition(), ShellCondition::Closed);
+///
+/// // the radii larger than the half of the shorter side are not supported.
+/// let radii: Vec<(VertexID, f64)> = rectangle.vertex_iter().map(|v| (v.id(), 0.6)).collect();
+/// assert!(matches!(
+///     builder::fillet_wire(&rectangle, &radii),
+///     Err(errors::Error::UnsupportedFillet(errors::UnsupportedFilletReason::TooLarge)),
+/// ));
+/// ```
+pub fn fillet_wire(wire: &Wire, vertex_radii: &[(VertexID, f64)]) -> Result<Wire> {
+    fillet::blend_wire(wire, vertex_radii, true)
+}
+
+/// Bevels the corners of `wire` at the vertices by the lines cutting the edges at the
+/// distances from the corners.
+///
+/// The failures are the same as [`fillet_wire`].
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the octagon from the square
+/// let square = builder::polyline(
+///     &[
+///         Point3::new(0.0, 0.0, 0.0),
+///         Point3::new(3.0, 0.0, 0.0),
+///         Point3::new(3.0, 3.0, 0.0),
+///         Point3::new(0.0, 3.0, 0.0),
+///     ],
+///     true,
+/// );
+/// let distances: Vec<(VertexID, f64)> = square.vertex_iter().map(|v| (v.id(), 1.0)).collect();
+/// let octagon = builder::chamfer_wire(&square, &distances).unwrap();
+/// assert_eq!(octagon.len(), 8);
+/// assert!(octagon.is_closed());
+/// let lengths: Vec<f64> = octagon
+///     .edge_iter()
+///     .map(|edge| {
+///         assert!(matches!(edge.oriented_curve(), Curve::Line(_)));
+///         edge.front().get_point().distance(edge.back().get_point())
+///     })
+///     .collect();
+/// assert_eq!(lengths.iter().filter(|l| (**l).near(&1.0)).count(), 4);
+/// assert_eq!(lengths.iter().filter(|l| (**l).near(&f64::sqrt(2.0))).count(), 4);
+/// ```
+pub fn chamfer_wire(wire: &Wire, vertex_distances: &[(VertexID, f64)]) -> Result<Wire> {
+    fillet::blend_wire(wire, vertex_distances, false)
+}
+
+/// Offsets the boundaries of the polyhedral `solid` by the signed `distance`: thickens the solid
+/// if `distance` is positive, and shrinks it if negative.
+///
+/// This is not a general offset of B-rep solids: the solids with curved faces or curved edges
+/// are rejected. The faces are moved along their normals by `distance`. Along the edges and around the
+/// vertices where the offset faces separate, i.e. the convex ones for thickening and the concave
+/// ones for shrinking, the gaps are filled by the pipes and the spheres of the radius
+/// `distance.abs()`. At the others, the offset faces are extended to their intersections.
+///
+/// The supported solids are the following:
+/// - all faces are planes, and all edges are lines,
+/// - the adjacent faces are not coplanar,
+/// - all edges at each vertex are separated, or all are intersected, and
+/// - the vertices of the intersected edges are shared by three faces.
+///
+/// Moreover, the intersected edges must not vanish by the offset.
+/// # Errors
+/// Returns [`Error::UnsupportedOffset`] with the reason if the configuration is not supported,
+/// e.g. [`UnsupportedOffsetReason::NotPlanarFace`] if `solid` has a curved face.
+///
+/// [`UnsupportedOffsetReason::NotPlanarFace`]: errors::UnsupportedOffsetReason::NotPlanarFace
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the unit cube
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // the clearance envelope: 6 faces, 12 pipes, and 8 spheres
+/// let envelope = builder::polyhedral_offset(&cube, 0.25).unwrap();
+/// let shell = &envelope.boundaries()[0];
+/// assert_eq!(shell.len(), 6 + 12 + 8);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// // all points of the boundary are at the distance 0.25 from the cube.
+/// shell.face_iter().for_each(|face| {
+///     let surface = face.oriented_surface();
+///     let p = surface.subs(0.5, 0.5);
+///     let q = Point3::new(p.x.clamp(0.0, 1.0), p.y.clamp(0.0, 1.0), p.z.clamp(0.0, 1.0));
+///     assert_near!(p.distance(q), 0.25);
+///     assert!(surface.normal(0.5, 0.5).dot(p - q) > 0.0);
+/// });
+///
+/// // the shrunk cube is the cube again.
+/// let shrunk = builder::polyhedral_offset(&cube, -0.25).unwrap();
+/// let shell = &shrunk.boundaries()[0];
+/// assert_eq!(shell.len(), 6);
+/// shell.vertex_iter().for_each(|v| {
+///     let p = v.get_point();
+///     assert!([p.x, p.y, p.z].iter().all(|x| x.near(&0.25) || x.near(&0.75)));
+/// });
+///
+/// // the cube vanishes.
+/// assert!(matches!(
+///     builder::polyhedral_offset(&cube, -0.5),
+///     Err(errors::Error::UnsupportedOffset(errors::UnsupportedOffsetReason::Vanishing)),
+/// ));
+/// ```
+pub fn polyhedral_offset(solid: &Solid, distance: f64) -> Result<Solid> {
+    offset::polyhedral_offset(solid, distance)
+}
+
+/// Returns the offset of the closed planar wire of lines by `distance`.
+///
+/// The positive `distance` expands the region bounded by the wire, and the negative one shrinks
+/// it. The offset lines are connected by the arcs around the convex corners and trimmed at the
+/// concave ones. The edges vanishing by the offset are removed. The returned wire has new vertices
+/// and the same orientation as `wire`.
+/// # Errors
+/// Returns [`Error::UnsupportedOffset`] with the reason if
+/// - `wire` is not closed, not planar, or has edges other than lines,
+/// - the offset wire vanishes or intersects itself, or
+/// - an arc is required between edges which are not adjacent in `wire`.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the counter-clockwise unit square
+/// let wire = builder::polyline(
+///     &[
+///         Point3::new(0.0, 0.0, 0.0),
+///         Point3::new(1.0, 0.0, 0.0),
+///         Point3::new(1.0, 1.0, 0.0),
+///         Point3::new(0.0, 1.0, 0.0),
+///     ],
+///     true,
+/// );
+///
+/// // expanded by the lines and the quarter arcs
+/// let expanded = builder::offset_wire(&wire, 0.25).unwrap();
+/// assert_eq!(expanded.len(), 8);
+/// assert!(expanded.is_closed());
+/// expanded.vertex_iter().for_each(|v| {
+///     let p = v.get_point();
+///     let q = Point3::new(p.x.clamp(0.0, 1.0), p.y.clamp(0.0, 1.0), 0.0);
+///     assert_near!(p.distance(q), 0.25);
+/// });
+///
+/// // shrunk to the smaller square
+/// let shrunk = builder::offset_wire(&wire, -0.25).unwrap();
+/// assert_eq!(shrunk.len(), 4);
+/// shrunk.vertex_iter().for_each(|v| {
+///     let p = v.get_point();
+///     assert!(p.x.near(&0.25) || p.x.near(&0.75));
+///     assert!(p.y.near(&0.25) || p.y.near(&0.75));
+/// });
+///
+/// // the square vanishes.
+/// assert!(matches!(
+///     builder::offset_wire(&wire, -0.5),
+///     Err(errors::Error::UnsupportedOffset(errors::UnsupportedOffsetReason::Vanishing)),
+/// ));
+/// ```
+pub fn offset_wire(wire: &Wire, distance: f64) -> Result<Wire> {
+    offset::offset_wire(wire, distance)
+}
+
+/// Tilts the planar `faces` of `solid` by `angle` for the draft of molding, and retrims the
+/// adjacent faces.
+///
+/// The parting plane passes through `origin` and is perpendicular to `direction`, the direction
+/// of pulling the mold. Each face is rotated about its intersection with the parting plane so
+/// that its normal leans to `direction`, i.e. the outer walls are narrowed on the side of
+/// `direction` and widened on the other side. The vertices of the drafted faces are moved to the
+/// intersections of the planes of their faces, and the other faces keep their surfaces.
+///
+/// Now, the supported configurations are the following:
+/// - the drafted faces are planes which are not parallel to the parting plane,
+/// - the vertices of the drafted faces are shared by three planar faces, and
+/// - the edges at the moved vertices are lines, which must not vanish by the draft.
+/// # Errors
+/// Returns [`Error::UnsupportedDraft`] if the configuration is not supported.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the unit cube
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // drafts the side walls by 5 degrees, parted at the bottom
+/// let walls: Vec<FaceID> = cube.boundaries()[0]
+///     .face_iter()
+///     .filter(|face| face.oriented_surface().normal(0.5, 0.5).z.so_small())
+///     .map(|face| face.id())
+///     .collect();
+/// let drafted = builder::draft(&cube, &walls, Point3::origin(), Vector3::unit_z(), Deg(5.0)).unwrap();
+/// assert!(drafted.is_geometric_consistent());
+/// let shift = f64::tan(5.0_f64.to_radians());
+/// drafted.boundaries()[0].vertex_iter().for_each(|v| {
+///     let p = v.get_point();
+///     let inset = p.z * shift;
+///     assert!([p.x, p.y].iter().all(|x| x.near(&inset) || x.near(&(1.0 - inset))));
+/// });
+///
+/// // the top face cannot be drafted.
+/// let top = cube.boundaries()[0]
+///     .face_iter()
+///     .find(|face| face.oriented_surface().normal(0.5, 0.5).z > 0.5)
+///     .unwrap()
+///     .id();
+/// assert!(matches!(
+///     builder::draft(&cube, &[top], Point3::origin(), Vector3::unit_z(), Deg(5.0)),
+///     Err(errors::Error::UnsupportedDraft(_)),
+/// ));
+/// ```
+pub fn draft<R: Into<Rad<f64>>>(
+    solid: &Solid,
+    faces: &[FaceID],
+    origin: Point3,
+    direction: Vector3,
+    angle: R,
+) -> Result<Solid> {
+    draft::draft(solid, faces, origin, direction, angle.into())
+}
+
+#[test]
+fn ellipse_arcs_around_the_ellipse() {
+    // the tilted ellipse with the semi-axes 3 and 1
+    let center = Point3::new(1.0, 2.0, 3.0);
+    let (u_axis, v_axis) = (
+        Vector3::new(1.0, 1.0, 0.0).normalize(),
+        Vector3::new(-1.0, 1.0, 1.0).normalize(),
+    );
+    let point = |t: f64| center + u_axis * 3.0 * t.cos() + v_axis * t.sin();
+    let v: Vec<Vertex> = (0..5)
+        .map(|i| vertex(point(PI.0 * i as f64 / 2.5)))
+        .collect();
+    let wire: Wire = (0..5)
+        .map(|i| ellipse_arc(&v[i], &v[(i + 1) % 5], center, u_axis * 3.0, 1.0 / 3.0))
+        .collect();
+    assert!(wire.is_closed());
+    wire.edge_iter().enumerate().for_each(|(i, edge)| {
+        assert!(edge.is_geometric_consistent());
+        let curve = edge.oriented_curve();
+        let (t0, t1) = curve.parameter_range();
+        let mid = PI.0 * (i as f64 + 0.5) / 2.5;
+        assert_near!(curve.subs((t0 + t1) / 2.0), point(mid));
+        (0..=10).for_each(|j| {
+            let vec = curve.subs(t0 + (t1 - t0) * j as f64 / 10.0) - center;
+            let (x, y) = (vec.dot(u_axis) / 3.0, vec.dot(v_axis));
+            assert_near!(x * x + y * y, 1.0);
+        });
+    });
+    // the arc from the last vertex to the first one in reverse goes back through the shorter way.
+    let edge = ellipse_arc(&v[0], &v[4], center, u_axis * 3.0, 1.0 / 3.0);
+    let curve = edge.oriented_curve();
+    let (t0, t1) = curve.parameter_range();
+    assert_near!(curve.subs((t0 + t1) / 2.0), point(-PI.0 / 5.0));
+}
+
+#[test]
+fn draft_walls_at_middle_parting_plane() {
+    // the box [0, 2] x [0, 1] x [0, 1]
+    let v = vertex(Point3::origin());
+    let e = tsweep(&v, Vector3::unit_x() * 2.0);
+    let f = tsweep(&e, Vector3::unit_y());
+    let cube: Solid = tsweep(&f, Vector3::unit_z());
+    let walls = |normal: Vector3| -> Vec<FaceID> {
+        cube.boundaries()[0]
+            .face_iter()
+            .filter(|face| face.oriented_surface().normal(0.5, 0.5).near(&normal))
+            .map(|face| face.id())
+            .collect()
+    };
+    let faces = [walls(Vector3::unit_x()), walls(-Vector3::unit_y())].concat();
+    let origin = Point3::new(0.0, 0.0, 0.5);
+    let drafted = draft(&cube, &faces, origin, Vector3::unit_z(), Deg(10.0)).unwrap();
+    assert!(drafted.is_geometric_consistent());
+    let shell = &drafted.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    let shift = f64::tan(10.0_f64.to_radians()) / 2.0;
+    shell.vertex_iter().for_each(|v| {
+        let p = v.get_point();
+        let shift = if p.z > 0.5 { shift } else { -shift };
+        assert!(p.x.so_small() || p.x.near(&(2.0 - shift)));
+        assert!(p.y.near(&shift) || p.y.near(&1.0));
+    });
+    // the input is not changed.
+    cube.boundaries()[0].vertex_iter().for_each(|v| {
+        let p = v.get_point();
+        assert!(p.x.so_small() || p.x.near(&2.0));
+    });
+
+    // the top edges of the side walls vanish.
+    let faces = [walls(Vector3::unit_y()), walls(-Vector3::unit_y())].concat();
+    assert!(matches!(
+        draft(&cube, &faces, origin, Vector3::unit_z(), Deg(70.0)),
+        Err(Error::UnsupportedDraft(_)),
+    ));
+}
+
+#[test]
+fn subdivide_circle_arcs() {
+    // the quarter arcs, whose parameters are not proportional to the arc lengths
+    let v = [
+        vertex(Point3::new(1.0, 0.0, 0.0)),
+        vertex(Point3::new(0.0, 1.0, 0.0)),
+        vertex(Point3::new(-1.0, 0.0, 0.0)),
+    ];
+    let transit = |a: Rad<f64>| Point3::new(a.cos(), a.sin(), 0.0);
+    let wire: Wire = vec![
+        circle_arc(&v[0], &v[1], transit(PI / 4.0)),
+        circle_arc(&v[2], &v[1], transit(PI * 3.0 / 4.0)).inverse(),
+    ]
+    .into();
+    let pieces = wire.subdivide_equal(6).unwrap();
+    assert_eq!(pieces.len(), 6);
+    assert_eq!(pieces[2].back_vertex(), Some(&v[1]));
+    pieces.iter().enumerate().for_each(|(i, piece)| {
+        assert!(piece.is_geometric_consistent());
+        let angle = PI * (i + 1) as f64 / 6.0;
+        assert_near!(piece.back_vertex().unwrap().get_point(), transit(angle));
+    });
+    assert_eq!(pieces[5].back_vertex(), Some(&v[2]));
+}
+
+#[test]
+fn coons_patch_of_cut_arcs() {
+    // the arcs cut from the circle, whose weights at the ends are not one
+    let arc = |p: Point3, q: Point3, transit: Point3| {
+        let edge = circle_arc(&vertex(p), &vertex(q), transit);
+        let curve = edge.get_curve();
+        let (t0, t1) = curve.parameter_range();
+        let mut curve = match curve {
+            Curve::NURBSCurve(curve) => curve,
+            _ => unreachable!(),
+        };
+        let curve = curve.cut((t0 + t1) / 3.0);
+        let p = curve.front();
+        Edge::new(&vertex(p), &vertex(q), Curve::NURBSCurve(curve))
+    };
+    let edge0 = arc(
+        Point3::new(-0.5, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.5, 0.0, 0.5),
+    );
+    let edge3 = line(&vertex(Point3::new(0.0, 1.0, 0.0)), edge0.front());
+    let v2 = vertex(Point3::new(1.0, 1.0, 0.0));
+    let edge1 = bezier(edge0.back(), &v2, vec![Point3::new(1.5, 0.5, 0.0)]);
+    let edge2 = bezier(&v2, edge3.front(), vec![Point3::new(0.5, 1.0, 1.0)]);
+    let face = coons_patch(&edge0, &edge1, &edge2, &edge3);
+    let surface = face.oriented_surface();
+    let curve = edge0.oriented_curve();
+    (0..=10).for_each(|i| {
+        let pt = surface.subs(i as f64 / 10.0, 0.0);
+        let t = curve.search_nearest_parameter(pt, None, 100).unwrap();
+        assert_near!(curve.subs(t), pt);
+    });
+}
+
+#[test]
+fn project_circle_onto_cylinder() {
+    let line = line(
+        &vertex(Point3::new(1.0, 0.0, -1.0)),
+        &vertex(Point3::new(1.0, 0.0, 1.0)),
+    );
+    let shell = rsweep(
+        &line,
+        Point3::origin(),
+        Vector3::unit_z(),
+        Rad(-std::f64::consts::PI),
+    );
+    let circle = circle(Point3::new(2.0, 0.0, 0.0), Vector3::unit_x(), 0.5);
+    let wire = project(&circle, &shell[0]).unwrap();
+    assert_eq!(wire.len(), circle.len());
+    assert!(wire.is_closed());
+    wire.edge_iter()
+        .zip(circle.edge_iter())
+        .for_each(|(edge, orig)| {
+            let (curve, orig) = (edge.oriented_curve(), orig.oriented_curve());
+            assert!(matches!(curve, Curve::BSplineCurve(_)));
+            let (t0, t1) = curve.parameter_range();
+            (0..=20).for_each(|i| {
+                let t = t0 + (t1 - t0) * i as f64 / 20.0;
+                // the radial projection onto the cylinder
+                let p = orig.subs(t);
+                let expected =
+                    Point3::new(p.x, p.y, 0.0) / f64::hypot(p.x, p.y) + p.z * Vector3::unit_z();
+                assert!(curve.subs(t).distance(expected) < 1.0e-4);
+            });
+        });
+}
+
+#[test]
+fn ruled_between_open_wires() {
+    let wire0 = polyline(
+        &[
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+        ],
+        false,
+    );
+    let v0 = vertex(Point3::new(0.0, 0.0, 1.0));
+    let v1 = vertex(Point3::new(1.0, 0.0, 1.0));
+    let v2 = vertex(Point3::new(2.0, 0.0, 1.0));
+    let wire1: Wire = vec![
+        circle_arc(&v0, &v1, Point3::new(0.5, 0.3, 1.0)),
+        line(&v1, &v2),
+    ]
+    .into();
+    let shell = ruled(&wire0, &wire1);
+    assert_eq!(shell.len(), 2);
+    assert_eq!(shell.shell_condition(), ShellCondition::Oriented);
+    assert_eq!(shell.extract_boundaries().len(), 1);
+    assert!(shell.is_geometric_consistent());
+    assert!(matches!(shell[0].get_surface(), Surface::NURBSSurface(_)));
+    assert!(matches!(shell[1].get_surface(), Surface::Plane(_)));
+    // the faces share the line between the middle vertices.
+    let rung = &shell[0].boundaries()[0][1];
+    assert_eq!(rung.id(), shell[1].boundaries()[0][3].id());
+}
+
+#[test]
+fn partial_torus() {
+    let v = vertex(Point3::new(0.5, 0.0, 0.0));
+    let w = rsweep(&v, Point3::new(0.75, 0.0, 0.0), Vector3::unit_y(), Rad(7.0));
+    let face = try_attach_plane(&[w]).unwrap();
+    let torus = rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(2.0));
+    assert!(torus.is_geometric_consistent());
+    let torus = rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(5.0));
+    assert!(torus.is_geometric_consistent());
+    let torus = rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(-2.0));
+    assert!(torus.is_geometric_consistent());
+    let torus = rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(-5.0));
+    assert!(torus.is_geometric_consistent());
+}
+
+#[test]
+fn divided_torus() {
+    let v = vertex(Point3::new(0.5, 0.0, 0.0));
+    let w = rsweep_with_division(
+        &v,
+        Point3::new(0.75, 0.0, 0.0),
+        Vector3::unit_y(),
+        Rad(7.0),
+        3,
+    );
+    assert_eq!(w.len(), 3);
+    let face = try_attach_plane(&[w]).unwrap();
+    let torus = rsweep_with_division(&face, Point3::origin(), Vector3::unit_z(), Rad(5.0), 4);
+    assert!(torus.is_geometric_consistent());
+    assert_eq!(torus.boundaries()[0].len(), 3 * 4 + 2);
+    let torus = rsweep_with_division(&face, Point3::origin(), Vector3::unit_z(), Rad(-7.0), 6);
+    assert!(torus.is_geometric_consistent());
+    assert_eq!(torus.boundaries()[0].len(), 3 * 6);
+}
+
+#[test]
+fn pipe_along_closed_path() {
+    let v = [
+        vertex(Point3::new(3.0, 0.0, 0.0)),
+        vertex(Point3::new(-3.0, 0.0, 0.0)),
+    ];
+    let path: Wire = vec![
+        circle_arc(&v[0], &v[1], Point3::new(0.0, 3.0, 0.0)),
+        circle_arc(&v[1], &v[0], Point3::new(0.0, -3.0, 0.0)),
+    ]
+    .into();
+    let v = vertex(Point3::new(3.5, 0.0, 0.0));
+    let circle = rsweep(&v, Point3::new(3.0, 0.0, 0.0), Vector3::unit_y(), Rad(7.0));
+    let face = try_attach_plane(&[circle]).unwrap();
+    let torus = pipe_solid(&face, &path);
+    let shell = &torus.boundaries()[0];
+    assert_eq!(shell.len(), 4);
+    shell.iter().for_each(|face| {
+        let surface = face.oriented_surface();
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let p = surface.subs(i as f64 / 10.0, j as f64 / 10.0);
+                let r = f64::hypot(p.x, p.y) - 3.0;
+                assert_near!(r * r + p.z * p.z, 0.25);
+            }
+        }
+    });
+}
+
+#[test]
+fn branched_sweep() {
+    let v = [
+        vertex(Point3::new(-1.0, 0.0, 0.0)),
+        vertex(Point3::new(0.0, 0.0, 0.0)),
+        vertex(Point3::new(1.0, 0.0, 0.0)),
+        vertex(Point3::new(0.0, 1.0, 0.0)),
+    ];
+    let wires: Vec<Wire> = vec![
+        vec![line(&v[0], &v[1]), line(&v[1], &v[2])].into(),
+        vec![line(&v[1], &v[3])].into(),
+    ];
+    let ribs: Shell = tsweep(&wires, Vector3::unit_z());
+    assert_eq!(ribs.len(), 3);
+    assert!(ribs.is_connected());
+    assert!(ribs.is_geometric_consistent());
+    assert_eq!(ribs.shell_condition(), ShellCondition::Irregular);
+    let ribs: Shell = rsweep(
+        &wires,
+        Point3::new(0.0, -1.0, 0.0),
+        Vector3::unit_x(),
+        Rad(1.0),
+    );
+    assert_eq!(ribs.len(), 3);
+    assert!(ribs.is_connected());
+}
+
+#[test]
+fn capped_tsweep_orientation() {
+    let v = vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    [circle.clone(), circle.inverse()].iter().for_each(|wire| {
+        let solid =
+            try_tsweep_capped(std::slice::from_ref(wire), Vector3::new(0.0, 0.0, -2.0)).unwrap();
+        let shell = &solid.boundaries()[0];
+        assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+        let normal = shell[0].oriented_surface().normal(0.0, 0.0);
+        assert_near!(normal, Vector3::unit_z());
+    });
+}
+
+#[test]
+fn helix_sweep_of_square() {
+    // the square profile whose normal is along the motion
+    let v = vertex(Point3::new(1.0, 0.0, 0.0));
+    let e = tsweep(&v, Vector3::new(1.0, 0.0, 0.0));
+    let profile: Face = tsweep(&e, Vector3::new(0.0, 0.0, 0.5));
+    let profile = profile.inverse();
+    let normal = profile.oriented_surface().normal(0.0, 0.0);
+    assert_near!(normal, Vector3::unit_y());
+    let thread = helix_sweep(&profile, Point3::origin(), Vector3::unit_z(), 1.0, 1.5);
+    assert!(thread.is_geometric_consistent());
+    let shell = &thread.boundaries()[0];
+    assert_eq!(shell.len(), 2 + 6 * 4);
+    // the outer faces face outward.
+    let outer: Vec<_> = shell
+        .iter()
+        .filter(|face| {
+            let point = face.oriented_surface().subs(0.5, 0.5);
+            point.to_vec().truncate().magnitude().near(&2.0)
+        })
+        .collect();
+    assert_eq!(outer.len(), 6);
+    outer.iter().for_each(|face| {
+        let surface = face.oriented_surface();
+        let radial = surface.subs(0.5, 0.5).to_vec().truncate().extend(0.0) / 2.0;
+        let normal = surface.normal(0.5, 0.5);
+        assert!(normal.dot(radial) > 0.99, "{normal:?}");
+    });
+}
+
+#[test]
+fn fillet_all_edges_of_cube() {
+    let v = vertex(Point3::origin());
+    let e = tsweep(&v, Vector3::unit_x());
+    let f = tsweep(&e, Vector3::unit_y());
+    let cube: Solid = tsweep(&f, Vector3::unit_z());
+    let mut edges: Vec<EdgeID> = cube.edge_iter().map(|edge| edge.id()).collect();
+    edges.sort_by_key(|id| cube.edge_iter().position(|edge| edge.id() == *id));
+    edges.dedup();
+    assert_eq!(edges.len(), 12);
+    let rounded = planar_fillet(&cube, &edges, 0.2).unwrap();
+    let shell = &rounded.boundaries()[0];
+    assert_eq!(shell.len(), 6 + 12 + 8);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert!(shell.is_geometric_consistent());
+    // the fillet faces and the corners are on the rounded cube.
+    shell.iter().skip(6).for_each(|face| {
+        let surface = face.oriented_surface();
+        let p = surface.subs(0.5, 0.5);
+        let center = Point3::new(
+            f64::clamp(p.x, 0.2, 0.8),
+            f64::clamp(p.y, 0.2, 0.8),
+            f64::clamp(p.z, 0.2, 0.8),
+        );
+        assert_near!(p.distance(center), 0.2);
+        assert!(surface.normal(0.5, 0.5).dot(p - center) > 0.0);
+    });
+}
+
+#[test]
+fn variable_fillet_all_edges_of_cube() {
+    let v = vertex(Point3::origin());
+    let e = tsweep(&v, Vector3::unit_x());
+    let f = tsweep(&e, Vector3::unit_y());
+    let cube: Solid = tsweep(&f, Vector3::unit_z());
+    let mut edges: Vec<EdgeID> = cube.edge_iter().map(|edge| edge.id()).collect();
+    edges.sort_by_key(|id| cube.edge_iter().position(|edge| edge.id() == *id));
+    edges.dedup();
+    // the radii are equal at the corners, and narrow in the middles of the edges.
+    let law = RadiusLaw::Table(vec![(0.0, 0.2), (0.5, 0.1), (1.0, 0.2)]);
+    let laws: Vec<_> = edges.iter().map(|id| (*id, law.clone())).collect();
+    let rounded = planar_variable_fillet(&cube, &laws).unwrap();
+    let shell = &rounded.boundaries()[0];
+    assert_eq!(shell.len(), 6 + 12 + 8);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert!(shell.is_geometric_consistent());
+
+    // the radii at the corners must be equal.
+    let law = RadiusLaw::Linear(0.1, 0.2);
+    let laws: Vec<_> = edges.iter().map(|id| (*id, law.clone())).collect();
+    assert!(matches!(
+        planar_variable_fillet(&cube, &laws),
+        Err(Error::UnsupportedFillet(
+            errors::UnsupportedFilletReason::DifferentProfiles
+        )),
+    ));
+}
+
+#[test]
+fn offset_hollow_cube() {
+    let v = vertex(Point3::origin());
+    let e = tsweep(&v, Vector3::unit_x());
+    let f = tsweep(&e, Vector3::unit_y());
+    let cube: Solid = tsweep(&f, Vector3::unit_z());
+    let outer = scaled(
+        &cube.boundaries()[0],
+        Point3::origin(),
+        Vector3::new(3.0, 3.0, 3.0),
+    );
+    let inner: Shell = translated(&cube.boundaries()[0], Vector3::new(1.0, 1.0, 1.0))
+        .face_iter()
+        .map(|face| face.inverse())
+        .collect();
+    let hollow = Solid::new(vec![outer, inner]);
+    // the outer shell is rounded, and the cavity is shrunk with the sharp corners.
+    let thick = polyhedral_offset(&hollow, 0.25).unwrap();
+    let [outer, inner] = [&thick.boundaries()[0], &thick.boundaries()[1]];
+    assert_eq!(outer.len(), 6 + 12 + 8);
+    assert_eq!(inner.len(), 6);
+    assert!(thick.is_geometric_consistent());
+    thick.boundaries().iter().for_each(|shell| {
+        assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    });
+    inner.vertex_iter().for_each(|v| {
+        let p = v.get_point();
+        assert!([p.x, p.y, p.z]
+            .iter()
+            .all(|x| x.near(&1.25) || x.near(&1.75)));
+    });
+
+    // the saddle vertices of the L-shaped solid are not supported.
+    let v: Vec<Vertex> = [
+        (0.0, 0.0),
+        (2.0, 0.0),
+        (2.0, 1.0),
+        (1.0, 1.0),
+        (1.0, 2.0),
+        (0.0, 2.0),
+    ]
+    .into_iter()
+    .map(|(x, y)| vertex(Point3::new(x, y, 0.0)))
+    .collect();
+    let wire: Wire = (0..6).map(|i| line(&v[i], &v[(i + 1) % 6])).collect();
+    let l_shaped = try_tsweep_capped(&[wire], Vector3::unit_z()).unwrap();
+    assert!(matches!(
+        polyhedral_offset(&l_shaped, 0.25),
+        Err(Error::UnsupportedOffset(
+            errors::UnsupportedOffsetReason::SaddleVertex
+        )),
+    ));
+}
+
+#[test]
+fn asymmetric_chamfer() {
+    let v = vertex(Point3::origin());
+    let e = tsweep(&v, Vector3::unit_x());
+    let f = tsweep(&e, Vector3::unit_y());
+    let cube: Solid = tsweep(&f, Vector3::unit_z());
+    // the vertical edges
+    let mut edges: Vec<EdgeID> = Vec::new();
+    cube.edge_iter().for_each(|edge| {
+        let dir = edge.back().get_point() - edge.front().get_point();
+        if dir.x.so_small() && dir.y.so_small() && !edges.contains(&edge.id()) {
+            edges.push(edge.id());
+        }
+    });
+    assert_eq!(edges.len(), 4);
+    let chamfered = planar_chamfer(&cube, &edges, 0.1, 0.3).unwrap();
+    let shell = &chamfered.boundaries()[0];
+    assert_eq!(shell.len(), 6 + 4);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert!(shell.is_geometric_consistent());
+    // the top and the bottom are the octagons whose sides are cut by 0.1 and 0.3.
+    let top = shell
+        .face_iter()
+        .find(|face| {
+            face.oriented_surface()
+                .normal(0.5, 0.5)
+                .near(&Vector3::unit_z())
+        })
+        .unwrap();
+    let boundary = &top.boundaries()[0];
+    assert_eq!(boundary.len(), 8);
+    let mut lengths: Vec<f64> = boundary
+        .edge_iter()
+        .map(|edge| edge.front().get_point().distance(edge.back().get_point()))
+        .collect();
+    lengths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_near!(lengths[0], f64::hypot(0.1, 0.3));
+    assert_near!(lengths[3], f64::hypot(0.1, 0.3));
+    assert_near!(lengths[4], 0.6);
+    assert_near!(lengths[7], 0.6);
+}
+
+#[test]
+fn tsweep_custom_curve() {
+    let bspcurve = BSplineCurve::new(
+        KnotVec::bezier_knot(2),
+        vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.5, 1.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+        ],
+    );
+    let curve = Curve::Custom(DynCurve::new(bspcurve.clone()));
+    let v0 = vertex(Point3::new(0.0, 0.0, 0.0));
+    let v1 = vertex(Point3::new(1.0, 0.0, 0.0));
+    let edge = Edge::new(&v0, &v1, curve);
+    let face: Face = tsweep(&edge, Vector3::unit_z());
+    assert!(face.is_geometric_consistent());
+    // the lateral surface is approximated by the points and the derivatives of the curve.
+    let surface = face.oriented_surface();
+    (0..=10).for_each(|i| {
+        let t = i as f64 / 10.0;
+        let point = bspcurve.subs(t) + Vector3::unit_z() * 0.5;
+        let (u, v) = surface.search_parameter(point, None, 100).unwrap();
+        assert_near!(surface.subs(u, v), point);
+    });
+}
+
+#[test]
+fn polyline_with_coincident_points() {
+    let points = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 0.0),
+    ];
+    let wire = polyline(&points, true);
+    assert_eq!(wire.len(), 3);
+    assert!(wire.is_closed() && wire.is_simple());
+    assert_eq!(wire[0].front(), wire[2].back());
+    let wire = polyline(&points, false);
+    assert_eq!(wire.len(), 3);
+    assert!(wire.is_continuous() && !wire.is_closed());
+    assert!(polyline(&points[1..3], true).is_empty());
+    assert!(polyline(&[], false).is_empty());
+}
+
+#[test]
+fn arcs_and_circles_by_center() {
+    let center = Point3::new(1.0, 2.0, 3.0);
+    let axis = Vector3::new(1.0, -2.0, 2.0);
+    let on_circle = |curve: &Curve| {
+        let (t0, t1) = curve.parameter_range();
+        (0..=20).for_each(|i| {
+            let p = curve.subs(t0 + (t1 - t0) * i as f64 / 20.0);
+            assert_near!((p - center).dot(axis), 0.0);
+            assert_near!((p - center).magnitude(), 0.5);
+        });
+    };
+
+    let ccw = arc_by_center(center, axis, 0.5, Rad(1.0), Rad(2.0));
+    let cw = arc_by_center(center, axis, 0.5, Rad(2.0), Rad(1.0));
+    on_circle(&ccw.oriented_curve());
+    on_circle(&cw.oriented_curve());
+    assert_near!(ccw.front().get_point(), cw.back().get_point());
+    assert_near!(ccw.back().get_point(), cw.front().get_point());
+    let (t0, t1) = ccw.oriented_curve().parameter_range();
+    let (p, d) = (ccw.front().get_point(), ccw.oriented_curve().der(t0));
+    assert!((p - center).cross(d).dot(axis) > 0.0);
+    assert_near!(ccw.oriented_curve().subs(t1), ccw.back().get_point());
+
+    let circle = circle(center, axis, 0.5);
+    circle
+        .edge_iter()
+        .for_each(|edge| on_circle(&edge.oriented_curve()));
+    let disk = try_attach_plane(&[circle]).unwrap();
+    let cylinder = tsweep(&disk, axis);
+    assert!(cylinder.is_geometric_consistent());
+    assert_eq!(
+        cylinder.boundaries()[0].shell_condition(),
+        ShellCondition::Closed
+    );
+}
+
+#[test]
+fn fillet_open_wire() {
+    let points = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+    ];
+    let mut wire = polyline(&points, false);
+    let end = wire.back_vertex().unwrap().clone();
+    let arc_end = vertex(Point3::new(2.0, 2.0, 0.0));
+    wire.push_back(circle_arc(&end, &arc_end, Point3::new(1.5, 1.9, 0.0)));
+    let corner = wire[0].back().id();
+    let rounded = fillet_wire(&wire, &[(corner, 0.5)]).unwrap();
+    assert_eq!(rounded.len(), 4);
+    assert!(rounded.is_continuous());
+    assert_near!(rounded[1].front().get_point(), Point3::new(0.5, 0.0, 0.0));
+    assert_near!(rounded[1].back().get_point(), Point3::new(1.0, 0.5, 0.0));
+    // the edge not at the corner is shared.
+    assert_eq!(rounded[3].id(), wire[2].id());
+    assert_eq!(rounded[0].front(), wire[0].front());
+
+    // the ends of the open wire are not corners.
+    let front = wire[0].front().id();
+    assert!(fillet_wire(&wire, &[(front, 0.1)]).is_err());
+    // the corner between the line and the arc is not supported.
+    let junction = wire[1].back().id();
+    assert!(chamfer_wire(&wire, &[(junction, 0.1)]).is_err());
+}
+
+#[test]
+fn offset_concave_wires() {
+    let l_shape = polyline(
+        &[
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(2.0, 1.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(1.0, 2.0, 0.0),
+            Point3::new(0.0, 2.0, 0.0),
+        ],
+        true,
+    );
+    // the arcs at the five convex corners
+    let expanded = offset_wire(&l_shape, 0.25).unwrap();
+    assert_eq!(expanded.len(), 11);
+    assert!(expanded.is_closed());
+    // the arc at the concave corner
+    let shrunk = offset_wire(&l_shape, -0.25).unwrap();
+    assert_eq!(shrunk.len(), 7);
+    assert!(shrunk
+        .vertex_iter()
+        .any(|v| v.get_point().near(&Point3::new(1.0, 0.75, 0.0))));
+    assert!(offset_wire(&l_shape, -0.6).is_err());
+
+    // the slot narrower than the offset is filled.
+    let slotted = polyline(
+        &[
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(3.0, 0.0, 0.0),
+            Point3::new(3.0, 3.0, 0.0),
+            Point3::new(1.7, 3.0, 0.0),
+            Point3::new(1.7, 1.0, 0.0),
+            Point3::new(1.3, 1.0, 0.0),
+            Point3::new(1.3, 3.0, 0.0),
+            Point3::new(0.0, 3.0, 0.0),
+        ],
+        true,
+    );
+    let filled = offset_wire(&slotted, 0.25)
+        .map_err(|e| e.to_string())
+        .unwrap();
+    assert!(filled.is_closed());
+    filled.vertex_iter().for_each(|v| {
+        let p = v.get_point();
+        assert!(!(1.3 < p.x && p.x < 1.7 && p.y < 3.0));
+    });
+    // the wire is planar but not on the xy-plane.
+    let rotated = transformed(&slotted, Matrix4::from_angle_x(Rad(1.0)));
+    assert_eq!(offset_wire(&rotated, 0.25).unwrap().len(), filled.len());
+}
+
+#[test]
+fn tapered_tsweep_of_rectangle() {
+    // the rectangle [-2, 2] x [-1, 1] on the tilted plane
+    let rot = Matrix4::from_angle_x(Deg(30.0));
+    let v = [(-2.0, -1.0), (2.0, -1.0), (2.0, 1.0), (-2.0, 1.0)]
+        .map(|(x, y)| vertex(rot.transform_point(Point3::new(x, y, 0.0))));
+    let wire: Wire = (0..4).map(|i| line(&v[i], &v[(i + 1) % 4])).collect();
+    let face = try_attach_plane(&[wire]).unwrap();
+    let normal = rot.transform_vector(Vector3::unit_z());
+    let height = 2.0;
+
+    // widening toward the end
+    let solid: Solid = try_tsweep_with_taper(&face, normal * height, Deg(-15.0)).unwrap();
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.len(), 6);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert!(solid.is_geometric_consistent());
+    // Each edge is offset by the same distance.
+    let offset = height * f64::tan(15.0_f64.to_radians());
+    let inv = rot.invert().unwrap();
+    shell.vertex_iter().for_each(|v| {
+        let p = inv.transform_point(v.get_point());
+        let offset = match p.z.near(&0.0) {
+            true => 0.0,
+            false => {
+                assert_near!(p.z, height);
+                offset
+            }
+        };
+        assert_near!(p.x.abs(), 2.0 + offset);
+        assert_near!(p.y.abs(), 1.0 + offset);
+    });
+    // the side faces are planes, orientated outward.
+    shell.face_iter().for_each(|face| {
+        let surface = face.oriented_surface();
+        assert!(matches!(surface, Surface::Plane(_)));
+        let p = inv.transform_point(surface.subs(0.5, 0.5));
+        let n = inv.transform_vector(surface.normal(0.5, 0.5));
+        if !n.z.abs().near(&1.0) {
+            assert!(n.x * p.x + n.y * p.y > 0.0);
+        }
+    });
+}
+
+#[test]
+fn revolve_solid_with_hole() {
+    // the square with a square hole on the xz-plane
+    let square = |min: f64, max: f64| -> Wire {
+        let v = [(min, min), (max, min), (max, max), (min, max)]
+            .map(|(x, z)| vertex(Point3::new(x, 0.0, z)));
+        (0..4).map(|i| line(&v[i], &v[(i + 1) % 4])).collect()
+    };
+    let face = try_attach_plane(&[square(1.0, 4.0), square(2.0, 3.0).inverse()]).unwrap();
+
+    // revolves into the negative direction.
+    [face.clone(), face.inverse()].iter().for_each(|face| {
+        let solid = revolve_solid(face, Point3::origin(), Vector3::unit_z(), Deg(-120.0)).unwrap();
+        assert_eq!(solid.boundaries().len(), 1);
+        let shell = &solid.boundaries()[0];
+        assert_eq!(shell.len(), 2 + 8);
+        assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+        let cap = shell
+            .face_iter()
+            .find(|face| {
+                face.boundaries()[0]
+                    .vertex_iter()
+                    .all(|v| v.get_point().y.so_small())
+            })
+            .unwrap();
+        assert_eq!(cap.boundaries().len(), 2);
+        assert_near!(cap.oriented_surface().normal(0.5, 0.5), Vector3::unit_y());
+    });
+}
+
+#[test]
+fn mirrored_and_sewn_half_cylinder() {
+    // the half of the cylinder in y >= 0, without the face on the xz-plane
+    let v = vertex(Point3::new(1.0, 0.0, 0.0));
+    let arc: Wire = rsweep(&v, Point3::origin(), Vector3::unit_z(), PI);
+    let mut wire = arc.clone();
+    wire.push_back(line(
+        arc.back_vertex().unwrap(),
+        arc.front_vertex().unwrap(),
+    ));
+    let face = try_attach_plane(&[wire]).unwrap();
+    let solid: Solid = tsweep(&face, Vector3::unit_z());
+    let mut half = solid.into_boundaries().pop().unwrap();
+    let len = half.len();
+    half.retain(|face| {
+        let surface = face.oriented_surface();
+        !surface.normal(0.5, 0.5).near(&-Vector3::unit_y())
+    });
+    assert_eq!(half.len(), len - 1);
+
+    let whole = mirrored_and_sewn(&half, Point3::origin(), Vector3::unit_y());
+    assert_eq!(whole.len(), 2 * half.len());
+    assert_eq!(whole.shell_condition(), ShellCondition::Closed);
+    // every face faces outward from the axis.
+    whole.face_iter().for_each(|face| {
+        let surface = face.oriented_surface();
+        let p = surface.subs(0.5, 0.5);
+        let n = surface.normal(0.5, 0.5);
+        match n.z.abs().near(&1.0) {
+            true => assert!(n.z * (p.z - 0.5) > 0.0),
+            false => assert!(n.x * p.x + n.y * p.y > 0.0),
+        }
+    });
+
+    // the mirror image of the solid is also closed, and faces outward.
+    let image = mirrored(
+        &Solid::new(vec![whole]),
+        Point3::new(0.0, 0.0, 2.0),
+        Vector3::unit_z(),
+    );
+    let shell = &image.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    shell.face_iter().for_each(|face| {
+        let surface = face.oriented_surface();
+        let p = surface.subs(0.5, 0.5);
+        let n = surface.normal(0.5, 0.5);
+        assert!((3.0..=4.0).contains(&p.z));
+        match n.z.abs().near(&1.0) {
+            true => assert!(n.z * (p.z - 3.5) > 0.0),
+            false => assert!(n.x * p.x + n.y * p.y > 0.0),
+        }
+    });
+}
+
+#[test]
+fn plate_with_bolt_circle() {
+    let outer = circle(Point3::origin(), Vector3::unit_z(), 4.0);
+    let hole = circle(Point3::new(2.5, 0.0, 0.0), Vector3::unit_z(), 0.5).inverse();
+    let holes = circular_pattern(&hole, Point3::origin(), Vector3::unit_z(), 8);
+    // the copies are separate from each other.
+    let ids: std::collections::HashSet<_> = holes
+        .iter()
+        .flat_map(Wire::edge_iter)
+        .map(Edge::id)
+        .collect();
+    assert_eq!(ids.len(), 8 * hole.len());
+
+    let mut wires = vec![outer];
+    wires.extend(holes);
+    let plate = try_tsweep_capped(&wires, Vector3::unit_z()).unwrap();
+    let shell = &plate.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert_eq!(shell[0].boundaries().len(), 9);
+
+    // the row of the plates along the direction, which is normalized
+    let plates = linear_pattern(&plate, Vector3::new(0.0, 3.0, 4.0), 3, 10.0);
+    plates.iter().enumerate().for_each(|(i, plate)| {
+        let center = plate
+            .vertex_iter()
+            .map(|v| v.get_point())
+            .collect::<BoundingBox<_>>()
+            .center();
+        let expected = Point3::new(0.0, 6.0, 8.0) * i as f64 + Vector3::new(0.0, 0.0, 0.5);
+        assert_near!(center, expected);
+    });
+    assert!(linear_pattern(&plate, Vector3::unit_x(), 0, 1.0).is_empty());
+}
+
+#[test]
+fn taper_line_and_arc() {
+    // the half disk, whose corners are not smooth
+    let v = [(-1.0, 0.0), (1.0, 0.0)].map(|(x, y)| vertex(Point3::new(x, y, 0.0)));
+    let wire: Wire = vec![
+        line(&v[0], &v[1]),
+        circle_arc(&v[1], &v[0], Point3::new(0.0, 1.0, 0.0)),
+    ]
+    .into();
+    let face = try_attach_plane(&[wire.clone()]).unwrap();
+    // the top line is at y = 0.1, and the top arc is on the circle with the radius 0.9.
+    let x = f64::sqrt(0.9 * 0.9 - 0.1 * 0.1);
+    [face.clone(), face.inverse()].into_iter().for_each(|face| {
+        let solid: Solid =
+            try_tsweep_with_taper(&face, Vector3::unit_z(), Rad(0.1_f64.atan())).unwrap();
+        assert_eq!(
+            solid.boundaries()[0].shell_condition(),
+            ShellCondition::Closed
+        );
+        assert!(solid.is_geometric_consistent());
+        solid.vertex_iter().for_each(|v| {
+            let p = v.get_point();
+            match p.z.near(&1.0) {
+                true => assert!(p.x.abs().near(&x) && p.y.near(&0.1)),
+                false => assert!(p.x.abs().near(&1.0) && p.y.near(&0.0)),
+            }
+        });
+    });
+    // The inside of the wire is the left side seen from the end of the vector.
+    let shell: Shell =
+        try_tsweep_with_taper(&wire, -Vector3::unit_z(), Rad(0.1_f64.atan())).unwrap();
+    shell.vertex_iter().for_each(|v| {
+        let p = v.get_point();
+        assert!(p.z.near(&0.0) || p.y.near(&-0.1));
+    });
 }
@@ -174,3 +174,30 @@ impl<P: Clone, C: Clone, S: Clone> ClosedSweep<P, C, S> for Shell<P, C, S> {
             .collect()
     }
 }
+
+impl<P: Clone, C: Clone, S: Clone> ClosedSweep<P, C, S> for Vec<Wire<P, C>> {
+    fn closed_sweep<
+        FP: Fn(&P) -> P,
+        FC: Fn(&C) -> C,
+        FS: Fn(&S) -> S,
+        CP: Fn(&P, &P) -> C,
+        CE: Fn(&C, &C) -> S,
+    >(
+        &self,
+        point_mapping: &FP,
+        curve_mapping: &FC,
+        surface_mapping: &FS,
+        connect_points: &CP,
+        connect_curves: &CE,
+        division: usize,
+    ) -> Self::Swept {
+        merge_wires(self).closed_sweep(
+            point_mapping,
+            curve_mapping,
+            surface_mapping,
+            connect_points,
+            connect_curves,
+            division,
+        )
+    }
+}
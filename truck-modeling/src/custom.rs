@@ -0,0 +1,239 @@
+use crate::*;
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::RwLock;
+
+/// The tolerance of the divisions of the approximations of the user-defined geometries,
+/// e.g. in [`Curve::lift_up`].
+pub const APPROXIMATION_TOLERANCE: f64 = 1.0e-3;
+
+/// The hook serializing the user-defined geometries of the type `T`.
+///
+/// The user-defined geometries [`Curve::Custom`] and [`Surface::Custom`] are serialized by
+/// the first registered hook returning `Some` with its name, and deserialized by the hook
+/// of the same name.
+#[derive(Clone, Copy, Debug)]
+pub struct CustomHook<T> {
+    /// the name identifying the geometries in the serialized data
+    pub name: &'static str,
+    /// Returns the data of the geometry, or `None` if the geometry is not the one of the hook.
+    pub serialize: fn(&T) -> Option<String>,
+    /// Restores the geometry from the data.
+    pub deserialize: fn(&str) -> Option<T>,
+}
+
+static CURVE_HOOKS: RwLock<Vec<CustomHook<DynCurve>>> = RwLock::new(Vec::new());
+static SURFACE_HOOKS: RwLock<Vec<CustomHook<DynSurface>>> = RwLock::new(Vec::new());
+
+fn register<T>(hooks: &RwLock<Vec<CustomHook<T>>>, hook: CustomHook<T>) {
+    let mut hooks = hooks.write().unwrap();
+    match hooks.iter_mut().find(|h| h.name == hook.name) {
+        Some(registered) => *registered = hook,
+        None => hooks.push(hook),
+    }
+}
+
+/// Registers the hook of the user-defined curves. The hook of the same name is replaced.
+#[inline(always)]
+pub fn register_curve_hook(hook: CustomHook<DynCurve>) { register(&CURVE_HOOKS, hook) }
+
+/// Registers the hook of the user-defined surfaces. The hook of the same name is replaced.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // Here, the NURBS surfaces play the role of the surfaces given by a plug-in.
+/// custom::register_surface_hook(custom::CustomHook {
+///     name: "nurbs",
+///     serialize: |surface| {
+///         let surface = surface.downcast_ref::<NURBSSurface<Vector4>>()?;
+///         serde_json::to_string(surface).ok()
+///     },
+///     deserialize: |data| {
+///         let surface: NURBSSurface<Vector4> = serde_json::from_str(data).ok()?;
+///         Some(DynSurface::new(surface))
+///     },
+/// });
+///
+/// let bspsurface = BSplineSurface::new(
+///     (KnotVec::bezier_knot(1), KnotVec::bezier_knot(1)),
+///     vec![
+///         vec![Vector4::new(0.0, 0.0, 0.0, 1.0), Vector4::new(0.0, 1.0, 0.0, 1.0)],
+///         vec![Vector4::new(1.0, 0.0, 0.0, 1.0), Vector4::new(1.0, 1.0, 1.0, 1.0)],
+///     ],
+/// );
+/// let surface = Surface::Custom(DynSurface::new(NURBSSurface::new(bspsurface)));
+/// let json = serde_json::to_string(&surface).unwrap();
+/// let restored: Surface = serde_json::from_str(&json).unwrap();
+/// assert_eq!(restored, surface);
+///
+/// // The surfaces without hooks cannot be serialized.
+/// let plane = Plane::new(Point3::origin(), Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0));
+/// let surface = Surface::Custom(DynSurface::new(plane));
+/// assert!(serde_json::to_string(&surface).is_err());
+/// ```
+#[inline(always)]
+pub fn register_surface_hook(hook: CustomHook<DynSurface>) { register(&SURFACE_HOOKS, hook) }
+
+#[derive(Serialize, Deserialize)]
+struct CustomData {
+    name: String,
+    data: String,
+}
+
+fn serialize<T: std::fmt::Debug, S: Serializer>(
+    hooks: &RwLock<Vec<CustomHook<T>>>,
+    geometry: &T,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    let hooks = hooks.read().unwrap();
+    let data = hooks.iter().find_map(|hook| {
+        (hook.serialize)(geometry).map(|data| CustomData {
+            name: hook.name.to_string(),
+            data,
+        })
+    });
+    match data {
+        Some(data) => data.serialize(serializer),
+        None => Err(S::Error::custom(format!(
+            "no hook serializes the user-defined geometry: {geometry:?}"
+        ))),
+    }
+}
+
+fn deserialize<'de, T, D: Deserializer<'de>>(
+    hooks: &RwLock<Vec<CustomHook<T>>>,
+    deserializer: D,
+) -> std::result::Result<T, D::Error> {
+    let CustomData { name, data } = CustomData::deserialize(deserializer)?;
+    let hooks = hooks.read().unwrap();
+    match hooks.iter().find(|hook| hook.name == name) {
+        Some(hook) => (hook.deserialize)(&data).ok_or_else(|| {
+            D::Error::custom(format!("the hook \"{name}\" cannot deserialize the data."))
+        }),
+        None => Err(D::Error::custom(format!(
+            "the hook \"{name}\" is not registered."
+        ))),
+    }
+}
+
+pub(crate) mod curve_serde {
+    use super::*;
+    pub fn serialize<S: Serializer>(
+        curve: &DynCurve,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        super::serialize(&CURVE_HOOKS, curve, serializer)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<DynCurve, D::Error> {
+        super::deserialize(&CURVE_HOOKS, deserializer)
+    }
+}
+
+pub(crate) mod surface_serde {
+    use super::*;
+    pub fn serialize<S: Serializer>(
+        surface: &DynSurface,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        super::serialize(&SURFACE_HOOKS, surface, serializer)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<DynSurface, D::Error> {
+        super::deserialize(&SURFACE_HOOKS, deserializer)
+    }
+}
+
+/// the knot vector of the piecewise cubic Hermite interpolation at `params`
+pub(crate) fn hermite_knot_vec(params: &[f64]) -> KnotVec {
+    let (first, last) = (params[0], params[params.len() - 1]);
+    let mut knots = vec![first; 4];
+    params[1..params.len() - 1]
+        .iter()
+        .for_each(|t| knots.extend([*t; 3]));
+    knots.extend([last; 4]);
+    KnotVec::from(knots)
+}
+
+/// Returns the cubic B-spline curve interpolating the points and the derivatives of `curve`
+/// at the parameters of the division of `curve` by `tol`.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// let circle = Processor::<_, Matrix4>::new(UnitCircle::<Point3>::new());
+/// let bspcurve = custom::curve_approximation(&circle, 0.01);
+/// for i in 0..=100 {
+///     let t = 2.0 * std::f64::consts::PI * i as f64 / 100.0;
+///     assert!(bspcurve.subs(t).distance(circle.subs(t)) < 1.0e-4);
+/// }
+/// ```
+pub fn curve_approximation<C>(curve: &C, tol: f64) -> BSplineCurve<Point3>
+where C: ParametricCurve3D + BoundedCurve + ParameterDivision1D<Point = Point3> {
+    let (params, _) = curve.parameter_division(curve.parameter_range(), tol);
+    let mut points = vec![curve.subs(params[0])];
+    params.windows(2).for_each(|t| {
+        let h = t[1] - t[0];
+        points.push(curve.subs(t[0]) + curve.der(t[0]) * (h / 3.0));
+        points.push(curve.subs(t[1]) - curve.der(t[1]) * (h / 3.0));
+        points.push(curve.subs(t[1]));
+    });
+    BSplineCurve::new(hermite_knot_vec(&params), points)
+}
+
+/// Returns the bicubic B-spline surface interpolating the points and the derivatives of
+/// `surface` at the parameters of the division of `surface` by `tol`.
+pub fn surface_approximation<S>(surface: &S, tol: f64) -> BSplineSurface<Point3>
+where S: ParametricSurface3D + BoundedSurface + ParameterDivision2D {
+    let (us, vs) = surface.parameter_division(surface.parameter_range(), tol);
+    // the distances to the adjacent parameters before and after the `i`th parameter
+    let steps = |params: &[f64], i: usize, a: i32| match a {
+        -1 => params[i] - params[i - 1],
+        1 => params[i + 1] - params[i],
+        _ => 0.0,
+    };
+    let signs = |len: usize, i: usize| {
+        let first = if i == 0 { 0 } else { -1 };
+        let last = if i + 1 == len { 0 } else { 1 };
+        first..=last
+    };
+    let control_points = (0..us.len())
+        .flat_map(|i| signs(us.len(), i).map(move |a| (i, a)))
+        .map(|(i, a)| {
+            let hu = steps(&us, i, a) * a as f64 / 3.0;
+            (0..vs.len())
+                .flat_map(|j| signs(vs.len(), j).map(move |b| (j, b)))
+                .map(|(j, b)| {
+                    let hv = steps(&vs, j, b) * b as f64 / 3.0;
+                    let (u, v) = (us[i], vs[j]);
+                    surface.subs(u, v)
+                        + surface.uder(u, v) * hu
+                        + surface.vder(u, v) * hv
+                        + surface.uvder(u, v) * (hu * hv)
+                })
+                .collect()
+        })
+        .collect();
+    BSplineSurface::new(
+        (hermite_knot_vec(&us), hermite_knot_vec(&vs)),
+        control_points,
+    )
+}
+
+/// Returns whether the sampled points of `curve` are on `surface`.
+pub(crate) fn include_by_sampling(surface: &Surface, curve: &Curve) -> bool {
+    const N: usize = 16;
+    let (t0, t1) = curve.parameter_range();
+    let mut hint = None;
+    (0..=N).all(|i| {
+        let point = curve.subs(t0 + (t1 - t0) * i as f64 / N as f64);
+        match surface.search_nearest_parameter(point, hint, 100) {
+            Some((u, v)) => {
+                hint = Some((u, v));
+                surface.subs(u, v).near(&point)
+            }
+            None => false,
+        }
+    })
+}
@@ -0,0 +1,178 @@
+use crate::*;
+use errors::Error;
+use rustc_hash::FxHashMap as HashMap;
+
+fn unsupported(message: &str) -> Error { Error::UnsupportedDraft(message.to_string()) }
+
+/// the oriented normal and the constant term of the equation of the plane
+fn plane_equation(surface: &Surface) -> Option<(Vector3, f64)> {
+    match surface {
+        Surface::Plane(plane) => {
+            let normal = plane.normal();
+            Some((normal, normal.dot(plane.origin().to_vec())))
+        }
+        _ => None,
+    }
+}
+
+/// the rotation of the planar face about its intersection with the parting plane
+fn hinge_rotation(
+    surface: &Surface,
+    origin: Point3,
+    direction: Vector3,
+    angle: Rad<f64>,
+) -> Result<Matrix4> {
+    let plane = match surface {
+        Surface::Plane(plane) => plane,
+        _ => return Err(unsupported("only the planar faces can be drafted.")),
+    };
+    let normal = plane.normal();
+    let axis = normal.cross(direction);
+    if axis.magnitude().so_small() {
+        return Err(unsupported(
+            "the drafted faces must not be parallel to the parting plane.",
+        ));
+    }
+    let axis = axis.normalize();
+    // the direction on the face ascending along `direction`
+    let slope = axis.cross(normal);
+    let point = plane.origin();
+    let hinge = point + slope * (direction.dot(origin - point) / direction.dot(slope));
+    Ok(Matrix4::from_translation(hinge.to_vec())
+        * Matrix4::from_axis_angle(axis, angle)
+        * Matrix4::from_translation(-hinge.to_vec()))
+}
+
+/// Tilts `faces` of `solid`. See [`builder::draft`] for the details.
+pub(super) fn draft(
+    solid: &Solid,
+    faces: &[FaceID],
+    origin: Point3,
+    direction: Vector3,
+    angle: Rad<f64>,
+) -> Result<Solid> {
+    if direction.magnitude().so_small() {
+        return Err(unsupported("the direction must not be zero."));
+    }
+    let direction = direction.normalize();
+    let all_faces: Vec<&Face> = solid
+        .boundaries()
+        .iter()
+        .flat_map(Shell::face_iter)
+        .collect();
+    if faces
+        .iter()
+        .any(|id| all_faces.iter().all(|face| face.id() != *id))
+    {
+        return Err(unsupported("the drafted faces must be in the solid."));
+    }
+
+    let mut planes = Vec::with_capacity(all_faces.len());
+    let mut rotations = HashMap::<usize, Matrix4>::default();
+    for (i, face) in all_faces.iter().enumerate() {
+        let surface = face.oriented_surface();
+        let mut plane = plane_equation(&surface);
+        if faces.contains(&face.id()) {
+            let rotation = hinge_rotation(&surface, origin, direction, angle)?;
+            let (normal, constant) = plane.unwrap();
+            let point = Point3::from_vec(normal * constant);
+            let normal = rotation.transform_vector(normal);
+            plane = Some((normal, normal.dot(rotation.transform_point(point).to_vec())));
+            rotations.insert(i, rotation);
+        }
+        planes.push(plane);
+    }
+
+    let mut vertex_faces = HashMap::<VertexID, Vec<usize>>::default();
+    all_faces.iter().enumerate().for_each(|(i, face)| {
+        face.boundaries().iter().flatten().for_each(|edge| {
+            let adjacent = vertex_faces.entry(edge.front().id()).or_default();
+            if !adjacent.contains(&i) {
+                adjacent.push(i);
+            }
+        })
+    });
+    let mut moved = HashMap::<VertexID, Point3>::default();
+    for (id, adjacent) in &vertex_faces {
+        if adjacent.iter().all(|i| !rotations.contains_key(i)) {
+            continue;
+        }
+        let [i, j, k] = match adjacent.as_slice() {
+            [i, j, k] => [*i, *j, *k],
+            _ => {
+                return Err(unsupported(
+                    "the vertices of the drafted faces must be shared by three faces.",
+                ))
+            }
+        };
+        let [(n0, c0), (n1, c1), (n2, c2)] = match [planes[i], planes[j], planes[k]] {
+            [Some(p0), Some(p1), Some(p2)] => [p0, p1, p2],
+            _ => {
+                return Err(unsupported(
+                    "the faces adjacent to the drafted faces must be planar.",
+                ))
+            }
+        };
+        let matrix = Matrix3::from_cols(n0, n1, n2).transpose();
+        let inverse = matrix.invert().ok_or_else(|| {
+            unsupported("the faces at the vertices of the drafted faces must be independent.")
+        })?;
+        moved.insert(*id, Point3::from_vec(inverse * Vector3::new(c0, c1, c2)));
+    }
+
+    for edge in all_faces
+        .iter()
+        .flat_map(|face| face.boundaries())
+        .flatten()
+    {
+        let (v0, v1) = (edge.front(), edge.back());
+        let (q0, q1) = match (moved.get(&v0.id()), moved.get(&v1.id())) {
+            (None, None) => continue,
+            (q0, q1) => (
+                q0.copied().unwrap_or_else(|| v0.get_point()),
+                q1.copied().unwrap_or_else(|| v1.get_point()),
+            ),
+        };
+        if !matches!(edge.get_curve(), Curve::Line(_)) {
+            return Err(unsupported(
+                "the edges of the retrimmed faces must be lines.",
+            ));
+        }
+        if (q1 - q0).dot(v1.get_point() - v0.get_point()) < TOLERANCE {
+            return Err(unsupported("the angle is so large that the edges vanish."));
+        }
+    }
+
+    let drafted = solid.topological_clone();
+    let new_faces: Vec<&Face> = drafted
+        .boundaries()
+        .iter()
+        .flat_map(Shell::face_iter)
+        .collect();
+    let edge_pairs = || {
+        all_faces
+            .iter()
+            .zip(&new_faces)
+            .flat_map(|(face, new)| face.boundaries().into_iter().zip(new.boundaries()))
+            .flat_map(|(wire, new)| wire.into_iter().zip(new))
+    };
+    edge_pairs().for_each(|(edge, new)| {
+        if let Some(point) = moved.get(&edge.front().id()) {
+            new.front().set_point(*point);
+        }
+    });
+    edge_pairs().for_each(|(edge, new)| {
+        if moved.contains_key(&edge.front().id()) || moved.contains_key(&edge.back().id()) {
+            let (p0, p1) = (
+                new.absolute_front().get_point(),
+                new.absolute_back().get_point(),
+            );
+            new.set_curve(Curve::Line(Line(p0, p1)));
+        }
+    });
+    rotations.into_iter().for_each(|(i, rotation)| {
+        let face = new_faces[i];
+        face.set_surface(face.get_surface().transformed(rotation));
+    });
+    Ok(drafted)
+}
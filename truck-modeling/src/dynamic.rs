@@ -0,0 +1,484 @@
+use crate::errors::Error;
+use crate::*;
+use derive_more::From;
+use thiserror::Error as ThisError;
+
+/// Dynamically typed topological shape.
+///
+/// The builder API is generic over the topological types, which is hard to expose to scripting
+/// front ends such as PyO3 or rhai. `AnyShape` erases the type and is downcast by `as_*` or
+/// `into_*` methods.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::dynamic::AnyShape;
+/// let shape = AnyShape::from(builder::vertex(Point3::origin()));
+/// assert_eq!(shape.type_name(), "Vertex");
+/// assert!(shape.is_vertex());
+/// assert!(shape.as_edge().is_none());
+/// assert_eq!(shape.into_vertex().unwrap().get_point(), Point3::origin());
+/// ```
+#[derive(Clone, Debug, From)]
+pub enum AnyShape {
+    /// vertex
+    Vertex(Vertex),
+    /// edge
+    Edge(Edge),
+    /// wire
+    Wire(Wire),
+    /// face
+    Face(Face),
+    /// shell
+    Shell(Shell),
+    /// solid
+    Solid(Solid),
+}
+
+macro_rules! downcast {
+    ($variant: ident, $is: ident, $as: ident, $into: ident, $name: expr) => {
+        #[doc = concat!("Returns whether the shape is ", $name, ".")]
+        #[inline(always)]
+        pub fn $is(&self) -> bool { matches!(self, AnyShape::$variant(_)) }
+        #[doc = concat!("Returns the reference to ", $name, " if the shape is ", $name, ".")]
+        #[inline(always)]
+        pub fn $as(&self) -> Option<&$variant> {
+            match self {
+                AnyShape::$variant(x) => Some(x),
+                _ => None,
+            }
+        }
+        #[doc = concat!("Returns ", $name, " if the shape is ", $name, ".")]
+        #[inline(always)]
+        pub fn $into(self) -> Option<$variant> {
+            match self {
+                AnyShape::$variant(x) => Some(x),
+                _ => None,
+            }
+        }
+    };
+}
+
+macro_rules! for_each_shape {
+    ($shape: expr, $x: ident => $expr: expr) => {
+        match $shape {
+            AnyShape::Vertex($x) => AnyShape::from($expr),
+            AnyShape::Edge($x) => AnyShape::from($expr),
+            AnyShape::Wire($x) => AnyShape::from($expr),
+            AnyShape::Face($x) => AnyShape::from($expr),
+            AnyShape::Shell($x) => AnyShape::from($expr),
+            AnyShape::Solid($x) => AnyShape::from($expr),
+        }
+    };
+}
+
+impl AnyShape {
+    /// Returns the name of the type, e.g. `"Vertex"` or `"Solid"`.
+    #[inline(always)]
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AnyShape::Vertex(_) => "Vertex",
+            AnyShape::Edge(_) => "Edge",
+            AnyShape::Wire(_) => "Wire",
+            AnyShape::Face(_) => "Face",
+            AnyShape::Shell(_) => "Shell",
+            AnyShape::Solid(_) => "Solid",
+        }
+    }
+    downcast!(Vertex, is_vertex, as_vertex, into_vertex, "a vertex");
+    downcast!(Edge, is_edge, as_edge, into_edge, "an edge");
+    downcast!(Wire, is_wire, as_wire, into_wire, "a wire");
+    downcast!(Face, is_face, as_face, into_face, "a face");
+    downcast!(Shell, is_shell, as_shell, into_shell, "a shell");
+    downcast!(Solid, is_solid, as_solid, into_solid, "a solid");
+}
+
+/// Dynamically typed arguments and returned values of [`call`].
+#[derive(Clone, Debug, From)]
+pub enum Value {
+    /// a number, used as scalars and angles in radians
+    Number(f64),
+    /// a point or a vector
+    Vector([f64; 3]),
+    /// a shape
+    Shape(AnyShape),
+    /// a list of values
+    List(Vec<Value>),
+}
+
+impl Value {
+    /// Returns the name of the type, e.g. `"Number"`, or the type of the shape.
+    #[inline(always)]
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Number(_) => "Number",
+            Value::Vector(_) => "Vector",
+            Value::Shape(shape) => shape.type_name(),
+            Value::List(_) => "List",
+        }
+    }
+    /// Returns the shape if the value is a shape.
+    #[inline(always)]
+    pub fn into_shape(self) -> Option<AnyShape> {
+        match self {
+            Value::Shape(shape) => Some(shape),
+            _ => None,
+        }
+    }
+}
+
+impl From<Point3> for Value {
+    #[inline(always)]
+    fn from(p: Point3) -> Value { Value::Vector(p.into()) }
+}
+
+impl From<Vector3> for Value {
+    #[inline(always)]
+    fn from(v: Vector3) -> Value { Value::Vector(v.into()) }
+}
+
+macro_rules! impl_value_from_shape {
+    ($($type: ident),*) => {
+        $(impl From<$type> for Value {
+            #[inline(always)]
+            fn from(x: $type) -> Value { Value::Shape(x.into()) }
+        })*
+    };
+}
+impl_value_from_shape!(Vertex, Edge, Wire, Face, Shell, Solid);
+
+/// Errors occurred by [`call`].
+#[derive(Debug, PartialEq, Eq, ThisError)]
+pub enum DynamicError {
+    /// the operation is not in [`OPERATIONS`].
+    #[error("unknown operation: {0}")]
+    UnknownOperation(String),
+    /// the number of the arguments is wrong.
+    #[error("{operation} requires {expected} arguments, but {actual} were given.")]
+    WrongNumberOfArguments {
+        /// the name of the operation
+        operation: &'static str,
+        /// the number of the required arguments
+        expected: usize,
+        /// the number of the given arguments
+        actual: usize,
+    },
+    /// the type of an argument is wrong.
+    #[error("the argument {index} of {operation} must be {expected}, but {actual} was given.")]
+    WrongArgumentType {
+        /// the name of the operation
+        operation: &'static str,
+        /// the index of the argument
+        index: usize,
+        /// the name of the required type
+        expected: &'static str,
+        /// the name of the given type
+        actual: &'static str,
+    },
+    /// wrapper of modeling error
+    #[error(transparent)]
+    Modeling(#[from] Error),
+}
+
+/// The names of the operations called by [`call`] and their arguments.
+///
+/// Each operation is the function of [`builder`] of the same name.
+pub const OPERATIONS: &[(&str, &str)] = &[
+    ("vertex", "(point: Vector) -> Vertex"),
+    ("line", "(v0: Vertex, v1: Vertex) -> Edge"),
+    (
+        "circle_arc",
+        "(v0: Vertex, v1: Vertex, transit: Vector) -> Edge",
+    ),
+    ("bezier", "(v0: Vertex, v1: Vertex, inter: List) -> Edge"),
+    ("homotopy", "(e0: Edge, e1: Edge) -> Face"),
+    ("wire", "(edges: List) -> Wire"),
+    ("try_attach_plane", "(wires: List) -> Face"),
+    ("shell", "(faces: List) -> Shell"),
+    ("solid", "(shells: List) -> Solid"),
+    ("clone", "(shape: Shape) -> Shape"),
+    ("translated", "(shape: Shape, vector: Vector) -> Shape"),
+    (
+        "rotated",
+        "(shape: Shape, origin: Vector, axis: Vector, angle: Number) -> Shape",
+    ),
+    (
+        "scaled",
+        "(shape: Shape, origin: Vector, scalars: Vector) -> Shape",
+    ),
+    ("tsweep", "(shape: Shape, vector: Vector) -> Shape | List"),
+    (
+        "rsweep",
+        "(shape: Shape, origin: Vector, axis: Vector, angle: Number) -> Shape | List",
+    ),
+];
+
+type DynResult<T> = std::result::Result<T, DynamicError>;
+
+struct Args<'a> {
+    operation: &'static str,
+    args: &'a [Value],
+}
+
+impl<'a> Args<'a> {
+    fn new(operation: &'static str, args: &'a [Value], expected: usize) -> DynResult<Self> {
+        match args.len() == expected {
+            true => Ok(Args { operation, args }),
+            false => Err(DynamicError::WrongNumberOfArguments {
+                operation,
+                expected,
+                actual: args.len(),
+            }),
+        }
+    }
+    fn type_error(&self, index: usize, expected: &'static str) -> DynamicError {
+        DynamicError::WrongArgumentType {
+            operation: self.operation,
+            index,
+            expected,
+            actual: self.args[index].type_name(),
+        }
+    }
+    fn number(&self, index: usize) -> DynResult<f64> {
+        match &self.args[index] {
+            Value::Number(x) => Ok(*x),
+            _ => Err(self.type_error(index, "Number")),
+        }
+    }
+    fn vector(&self, index: usize) -> DynResult<Vector3> {
+        match &self.args[index] {
+            Value::Vector(x) => Ok((*x).into()),
+            _ => Err(self.type_error(index, "Vector")),
+        }
+    }
+    fn point(&self, index: usize) -> DynResult<Point3> { self.vector(index).map(Point3::from_vec) }
+    fn shape(&self, index: usize) -> DynResult<&'a AnyShape> {
+        match &self.args[index] {
+            Value::Shape(x) => Ok(x),
+            _ => Err(self.type_error(index, "Shape")),
+        }
+    }
+    fn list(&self, index: usize) -> DynResult<&'a [Value]> {
+        match &self.args[index] {
+            Value::List(x) => Ok(x),
+            _ => Err(self.type_error(index, "List")),
+        }
+    }
+    fn vertex(&self, index: usize) -> DynResult<&'a Vertex> {
+        self.shape(index)?
+            .as_vertex()
+            .ok_or_else(|| self.type_error(index, "Vertex"))
+    }
+    fn edge(&self, index: usize) -> DynResult<&'a Edge> {
+        self.shape(index)?
+            .as_edge()
+            .ok_or_else(|| self.type_error(index, "Edge"))
+    }
+    /// the elements of the list, downcast by `downcast`.
+    fn list_of<T: Clone>(
+        &self,
+        index: usize,
+        expected: &'static str,
+        downcast: impl Fn(&AnyShape) -> Option<&T>,
+    ) -> DynResult<Vec<T>> {
+        let error = || DynamicError::WrongArgumentType {
+            operation: self.operation,
+            index,
+            expected,
+            actual: "List",
+        };
+        self.list(index)?
+            .iter()
+            .map(|value| match value {
+                Value::Shape(shape) => downcast(shape).cloned().ok_or_else(error),
+                _ => Err(error()),
+            })
+            .collect()
+    }
+}
+
+fn solids_into_value(solids: Vec<truck_topology::Result<Solid>>) -> DynResult<Value> {
+    let solids = solids
+        .into_iter()
+        .map(|solid| Ok(Value::from(solid.map_err(Error::from)?)))
+        .collect::<DynResult<Vec<_>>>()?;
+    Ok(Value::List(solids))
+}
+
+/// Calls the operation of [`builder`] by the name, for scripting bridges.
+///
+/// The names and the signatures are listed in [`OPERATIONS`]. Sweeping a shell returns
+/// the list of solids.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::dynamic::*;
+/// let v = call("vertex", &[Value::Vector([0.0, 0.0, 0.0])]).unwrap();
+/// let e = call("tsweep", &[v, Value::Vector([1.0, 0.0, 0.0])]).unwrap();
+/// let f = call("tsweep", &[e, Value::Vector([0.0, 1.0, 0.0])]).unwrap();
+/// let cube = call("tsweep", &[f, Value::Vector([0.0, 0.0, 1.0])]).unwrap();
+/// let cube: Solid = cube.into_shape().unwrap().into_solid().unwrap();
+/// assert_eq!(cube.boundaries()[0].len(), 6);
+///
+/// let res = call("tsweep", &[Value::Number(1.0), Value::Vector([0.0, 0.0, 1.0])]);
+/// assert!(matches!(res, Err(DynamicError::WrongArgumentType { index: 0, .. })));
+/// let res = call("extrude", &[]);
+/// assert!(matches!(res, Err(DynamicError::UnknownOperation(_))));
+/// ```
+pub fn call(operation: &str, args: &[Value]) -> DynResult<Value> {
+    match operation {
+        "vertex" => {
+            let args = Args::new("vertex", args, 1)?;
+            Ok(builder::vertex(args.point(0)?).into())
+        }
+        "line" => {
+            let args = Args::new("line", args, 2)?;
+            Ok(builder::line(args.vertex(0)?, args.vertex(1)?).into())
+        }
+        "circle_arc" => {
+            let args = Args::new("circle_arc", args, 3)?;
+            let (v0, v1, transit) = (args.vertex(0)?, args.vertex(1)?, args.point(2)?);
+            Ok(builder::circle_arc(v0, v1, transit).into())
+        }
+        "bezier" => {
+            let args = Args::new("bezier", args, 3)?;
+            let inter = args
+                .list(2)?
+                .iter()
+                .map(|value| match value {
+                    Value::Vector(x) => Ok(Point3::from(*x)),
+                    _ => Err(args.type_error(2, "List of Vector")),
+                })
+                .collect::<DynResult<Vec<_>>>()?;
+            Ok(builder::bezier(args.vertex(0)?, args.vertex(1)?, inter).into())
+        }
+        "homotopy" => {
+            let args = Args::new("homotopy", args, 2)?;
+            Ok(builder::homotopy(args.edge(0)?, args.edge(1)?).into())
+        }
+        "wire" => {
+            let args = Args::new("wire", args, 1)?;
+            let edges = args.list_of(0, "List of Edge", AnyShape::as_edge)?;
+            Ok(Wire::from(edges).into())
+        }
+        "try_attach_plane" => {
+            let args = Args::new("try_attach_plane", args, 1)?;
+            let wires = args.list_of(0, "List of Wire", AnyShape::as_wire)?;
+            Ok(builder::try_attach_plane(&wires)?.into())
+        }
+        "shell" => {
+            let args = Args::new("shell", args, 1)?;
+            let faces = args.list_of(0, "List of Face", AnyShape::as_face)?;
+            Ok(Shell::from(faces).into())
+        }
+        "solid" => {
+            let args = Args::new("solid", args, 1)?;
+            let shells = args.list_of(0, "List of Shell", AnyShape::as_shell)?;
+            Ok(Solid::try_new(shells).map_err(Error::from)?.into())
+        }
+        "clone" => {
+            let args = Args::new("clone", args, 1)?;
+            let shape = args.shape(0)?;
+            Ok(for_each_shape!(shape, x => builder::clone(x)).into())
+        }
+        "translated" => {
+            let args = Args::new("translated", args, 2)?;
+            let (shape, vector) = (args.shape(0)?, args.vector(1)?);
+            Ok(for_each_shape!(shape, x => builder::translated(x, vector)).into())
+        }
+        "rotated" => {
+            let args = Args::new("rotated", args, 4)?;
+            let (shape, origin, axis) = (args.shape(0)?, args.point(1)?, args.vector(2)?);
+            let angle = Rad(args.number(3)?);
+            Ok(for_each_shape!(shape, x => builder::rotated(x, origin, axis, angle)).into())
+        }
+        "scaled" => {
+            let args = Args::new("scaled", args, 3)?;
+            let (shape, origin, scalars) = (args.shape(0)?, args.point(1)?, args.vector(2)?);
+            Ok(for_each_shape!(shape, x => builder::scaled(x, origin, scalars)).into())
+        }
+        "tsweep" => {
+            let args = Args::new("tsweep", args, 2)?;
+            let vector = args.vector(1)?;
+            match args.shape(0)? {
+                AnyShape::Vertex(x) => Ok(builder::tsweep(x, vector).into()),
+                AnyShape::Edge(x) => Ok(builder::tsweep(x, vector).into()),
+                AnyShape::Wire(x) => Ok(builder::tsweep(x, vector).into()),
+                AnyShape::Face(x) => Ok(builder::tsweep(x, vector).into()),
+                AnyShape::Shell(x) => solids_into_value(builder::tsweep(x, vector)),
+                AnyShape::Solid(_) => Err(args.type_error(0, "Vertex, Edge, Wire, Face or Shell")),
+            }
+        }
+        "rsweep" => {
+            let args = Args::new("rsweep", args, 4)?;
+            let (origin, axis) = (args.point(1)?, args.vector(2)?.normalize());
+            let angle = Rad(args.number(3)?);
+            match args.shape(0)? {
+                AnyShape::Vertex(x) => Ok(builder::rsweep(x, origin, axis, angle).into()),
+                AnyShape::Edge(x) => Ok(builder::rsweep(x, origin, axis, angle).into()),
+                AnyShape::Wire(x) => Ok(builder::rsweep(x, origin, axis, angle).into()),
+                AnyShape::Face(x) => Ok(builder::rsweep(x, origin, axis, angle).into()),
+                AnyShape::Shell(x) => solids_into_value(builder::rsweep(x, origin, axis, angle)),
+                AnyShape::Solid(_) => Err(args.type_error(0, "Vertex, Edge, Wire, Face or Shell")),
+            }
+        }
+        _ => Err(DynamicError::UnknownOperation(operation.to_string())),
+    }
+}
+
+#[test]
+fn call_operations() {
+    let v0 = call("vertex", &[Value::Vector([1.0, 0.0, 0.0])]).unwrap();
+    let v1 = call("vertex", &[Value::Vector([-1.0, 0.0, 0.0])]).unwrap();
+    let arc0 = call(
+        "circle_arc",
+        &[v0.clone(), v1.clone(), Value::Vector([0.0, 1.0, 0.0])],
+    )
+    .unwrap();
+    let arc1 = call("circle_arc", &[v1, v0, Value::Vector([0.0, -1.0, 0.0])]).unwrap();
+    let wire = call("wire", &[Value::List(vec![arc0, arc1])]).unwrap();
+    let disk = call("try_attach_plane", &[Value::List(vec![wire])]).unwrap();
+    assert_eq!(disk.type_name(), "Face");
+    let cylinder = call("tsweep", &[disk, Value::Vector([0.0, 0.0, 1.0])]).unwrap();
+    let moved = call(
+        "rotated",
+        &[
+            cylinder,
+            Value::Vector([0.0, 0.0, 0.0]),
+            Value::Vector([1.0, 0.0, 0.0]),
+            Value::Number(std::f64::consts::PI),
+        ],
+    )
+    .unwrap();
+    let solid = moved.into_shape().unwrap().into_solid().unwrap();
+    assert!(solid.is_geometric_consistent());
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.len(), 4);
+    shell
+        .vertex_iter()
+        .for_each(|v| assert!(v.get_point()[2] < TOLERANCE));
+
+    let res = call("line", &[Value::Number(0.0)]);
+    assert_eq!(
+        res.unwrap_err(),
+        DynamicError::WrongNumberOfArguments {
+            operation: "line",
+            expected: 2,
+            actual: 1,
+        }
+    );
+    let v = call("vertex", &[Value::Vector([0.0; 3])]).unwrap();
+    let res = call("homotopy", &[v.clone(), v]);
+    assert_eq!(
+        res.unwrap_err(),
+        DynamicError::WrongArgumentType {
+            operation: "homotopy",
+            index: 0,
+            expected: "Edge",
+            actual: "Vertex",
+        }
+    );
+    for (name, _) in OPERATIONS {
+        let res = call(name, &[]);
+        assert!(!matches!(res, Err(DynamicError::UnknownOperation(_))));
+    }
+}
@@ -10,6 +10,136 @@ pub enum Error {
     /// cf. [`builder::try_attach_plane`](../builder/fn.try_attach_plane.html)
     #[error("cannot attach a plane to a wire that is not on one plane.")]
     WireNotInOnePlane,
+    /// tried to fillet or chamfer edges or corners which are not supported.
+    /// cf. [`builder::planar_fillet`](../builder/fn.planar_fillet.html), [`builder::planar_chamfer`](../builder/fn.planar_chamfer.html),
+    /// [`builder::fillet_wire`](../builder/fn.fillet_wire.html)
+    #[error("cannot fillet or chamfer the edges: {0}")]
+    UnsupportedFillet(UnsupportedFilletReason),
+    /// tried to offset a solid or a wire which is not supported.
+    /// cf. [`builder::polyhedral_offset`](../builder/fn.polyhedral_offset.html), [`builder::offset_wire`](../builder/fn.offset_wire.html)
+    #[error("cannot offset the shape: {0}")]
+    UnsupportedOffset(UnsupportedOffsetReason),
+    /// tried to draft faces which are not supported.
+    /// cf. [`builder::draft`](../builder/fn.draft.html)
+    #[error("cannot draft the faces: {0}")]
+    UnsupportedDraft(String),
+    /// tried to sweep a profile with the taper which is not supported.
+    /// cf. [`builder::try_tsweep_with_taper`](../builder/fn.try_tsweep_with_taper.html)
+    #[error("cannot sweep the profile with the taper: {0}")]
+    UnsupportedTaper(String),
+    /// tried to solve mates which are not supported or not satisfied.
+    /// cf. [`Assembly::solve_mates`](../assembly/struct.Assembly.html#method.solve_mates)
+    #[error("cannot solve the mates: {0}")]
+    UnsupportedMate(String),
+    /// tried to project an edge or a wire which cannot be projected onto the face.
+    /// cf. [`builder::project`](../builder/fn.project.html)
+    #[error("cannot project the element onto the face: {0}")]
+    UnsupportedProjection(String),
+    /// tried to convert a sketch which does not form loops to a face.
+    /// cf. [`Sketch::to_face`](../sketch/struct.Sketch.html#method.to_face)
+    #[error("cannot convert the sketch to a face: {0}")]
+    InvalidSketch(String),
+    /// tried to read a font which is broken or not supported.
+    /// cf. [`text::Font`](../text/struct.Font.html)
+    #[cfg(feature = "text")]
+    #[error("cannot read the font: {0}")]
+    InvalidFont(String),
+}
+
+/// The reasons why the edges of solids or the corners of wires cannot be filleted or chamfered.
+/// cf. [`Error::UnsupportedFillet`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum UnsupportedFilletReason {
+    /// the parameters of the radius law do not increase from 0 to 1.
+    #[error("the parameters of the radius law must increase from 0 to 1.")]
+    InvalidRadiusLaw,
+    /// a radius or a distance is not positive.
+    #[error("the radii and the distances must be positive.")]
+    NonPositiveSize,
+    /// the edge is not shared by two faces.
+    #[error("the edge is not shared by two faces.")]
+    NotManifoldEdge,
+    /// the edge is not a line.
+    #[error("only the lines are supported.")]
+    NotLine,
+    /// the faces adjacent to the edge are not planes.
+    #[error("only the edges between planes are supported.")]
+    NotBetweenPlanes,
+    /// the edge is concave or flat.
+    #[error("only the convex edges are supported.")]
+    NotConvex,
+    /// the vertex of the edge is not shared by three edges.
+    #[error("the vertices must be shared by three edges.")]
+    NotTrihedralVertex,
+    /// the face at the end of the edge is not perpendicular to the edge.
+    #[error("the other face at the end of the edge must be perpendicular to it.")]
+    NotPerpendicularEnd,
+    /// the faces at the vertex of two or three blended edges are not perpendicular to each other.
+    #[error("the faces at the vertex of two or three edges must be perpendicular to each other.")]
+    NotPerpendicularCorner,
+    /// the edges at the vertex are not blended by the same profile.
+    #[error("the edges at the vertex must be blended by the same profile.")]
+    DifferentProfiles,
+    /// the wire is not continuous.
+    #[error("the wire must be continuous.")]
+    NotContinuousWire,
+    /// the vertex is not the junction of two edges in the wire.
+    #[error("the vertices must be the corners of the wire.")]
+    NotWireCorner,
+    /// the corner of the wire is straight or folded.
+    #[error("the corners must not be straight or folded.")]
+    FlatCorner,
+    /// the radii or the distances are so large that the edges vanish.
+    #[error("the radii or the distances are so large that the edges vanish.")]
+    TooLarge,
+}
+
+/// The reasons why the solids or the wires cannot be offset.
+/// cf. [`Error::UnsupportedOffset`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum UnsupportedOffsetReason {
+    /// the solid has a face which is not a plane.
+    #[error("only the planar faces are supported.")]
+    NotPlanarFace,
+    /// the edge is not shared by two faces.
+    #[error("the edge is not shared by two faces.")]
+    NotManifoldEdge,
+    /// the solid or the wire has an edge which is not a line.
+    #[error("only the lines are supported.")]
+    NotLine,
+    /// the faces adjacent to the edge are coplanar.
+    #[error("the faces must not be coplanar at the edges.")]
+    CoplanarFaces,
+    /// the vertex has both the edges separated and the ones intersected by the offset.
+    #[error("the vertices must not be saddles.")]
+    SaddleVertex,
+    /// the sharp vertex is not shared by three faces.
+    #[error("the sharp vertices must be shared by three faces.")]
+    NotTrihedralVertex,
+    /// the planes of the faces at the sharp vertex are not independent.
+    #[error("the faces at the sharp vertices must be independent.")]
+    DependentFaces,
+    /// the distance is so large that the edges or the wire vanish.
+    #[error("the distance is so large that the shape vanishes.")]
+    Vanishing,
+    /// the wire is not closed.
+    #[error("the wire must be closed.")]
+    NotClosedWire,
+    /// the wire does not bound a region.
+    #[error("the wire must bound a region.")]
+    NotRegion,
+    /// the wire is not on a plane.
+    #[error("the wire must be planar.")]
+    NotPlanarWire,
+    /// the wire is folded back.
+    #[error("the wire must not be folded.")]
+    FoldedWire,
+    /// the offset curves of the edges which are not adjacent are separated.
+    #[error("the offset curves are separated.")]
+    SeparatedCurves,
+    /// the offset wire intersects itself.
+    #[error("the offset wire intersects itself.")]
+    SelfIntersection,
 }
 
 #[test]
@@ -27,6 +157,55 @@ fn print_messages() {
     )
     .unwrap();
     writeln!(&mut std::io::stderr(), "{}\n", Error::WireNotInOnePlane).unwrap();
+    writeln!(
+        &mut std::io::stderr(),
+        "{}\n",
+        Error::UnsupportedFillet(UnsupportedFilletReason::NotLine)
+    )
+    .unwrap();
+    writeln!(
+        &mut std::io::stderr(),
+        "{}\n",
+        Error::UnsupportedOffset(UnsupportedOffsetReason::NotPlanarFace)
+    )
+    .unwrap();
+    writeln!(
+        &mut std::io::stderr(),
+        "{}\n",
+        Error::UnsupportedDraft("only the planar faces can be drafted.".to_string())
+    )
+    .unwrap();
+    writeln!(
+        &mut std::io::stderr(),
+        "{}\n",
+        Error::UnsupportedTaper("the profile collapses by the taper.".to_string())
+    )
+    .unwrap();
+    writeln!(
+        &mut std::io::stderr(),
+        "{}\n",
+        Error::UnsupportedMate("the mates are not satisfied.".to_string())
+    )
+    .unwrap();
+    writeln!(
+        &mut std::io::stderr(),
+        "{}\n",
+        Error::UnsupportedProjection("a point is not projected onto the surface.".to_string())
+    )
+    .unwrap();
+    writeln!(
+        &mut std::io::stderr(),
+        "{}\n",
+        Error::InvalidSketch("the sketch has no loops.".to_string())
+    )
+    .unwrap();
+    #[cfg(feature = "text")]
+    writeln!(
+        &mut std::io::stderr(),
+        "{}\n",
+        Error::InvalidFont("the font has no Unicode character map.".to_string())
+    )
+    .unwrap();
     writeln!(
         &mut std::io::stderr(),
         "*******************************************************"
@@ -0,0 +1,894 @@
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::f64::consts::PI;
+use truck_base::disjoint_set::DisjointSet;
+
+const CURVE_DIVISION: usize = 8;
+const SURFACE_DIVISION: usize = 8;
+const ANGLE_BINS: usize = 72;
+
+/// Simple machining features recognized by [`recognize`].
+#[derive(Clone, Debug)]
+pub enum Feature {
+    /// a cylindrical hole passing through the solid
+    ThroughHole {
+        /// the center of one opening
+        origin: Point3,
+        /// the unit direction from `origin` to the other opening
+        axis: Vector3,
+        /// the radius of the hole
+        radius: f64,
+        /// the length of the hole
+        depth: f64,
+        /// the cylindrical faces of the hole
+        faces: Vec<FaceID>,
+    },
+    /// a cylindrical hole with a planar bottom
+    BlindHole {
+        /// the center of the opening
+        origin: Point3,
+        /// the unit direction from the opening to the bottom
+        axis: Vector3,
+        /// the radius of the hole
+        radius: f64,
+        /// the distance from the opening to the bottom
+        depth: f64,
+        /// the cylindrical faces of the hole
+        faces: Vec<FaceID>,
+        /// the bottom face
+        bottom: FaceID,
+    },
+    /// a depression with a planar floor surrounded by walls
+    Pocket {
+        /// the floor face
+        floor: FaceID,
+        /// the outer normal of the floor, the unit direction from the floor to the opening
+        normal: Vector3,
+        /// the height of the highest wall measured from the floor
+        depth: f64,
+        /// the faces adjacent to the outer boundary of the floor
+        walls: Vec<FaceID>,
+    },
+    /// a cylindrical blend which is tangent to the adjacent faces
+    Fillet {
+        /// the fillet face
+        face: FaceID,
+        /// a point on the axis of the cylinder
+        origin: Point3,
+        /// the unit direction of the axis of the cylinder
+        axis: Vector3,
+        /// the radius of the fillet
+        radius: f64,
+        /// `true` if the fillet rounds a convex edge, `false` if it fills a concave corner
+        convex: bool,
+    },
+}
+
+/// A hole on a planar face recognized by [`planar_holes`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanarHole {
+    /// the planar face
+    pub face: FaceID,
+    /// the index of the inner boundary of the hole in the boundaries of the face
+    pub boundary: usize,
+    /// the outer unit normal of the face
+    pub normal: Vector3,
+    /// the shape of the hole
+    pub shape: HoleShape,
+}
+
+/// The shapes of the holes on planar faces.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HoleShape {
+    /// a circular hole
+    Circle {
+        /// the center of the circle
+        center: Point3,
+        /// the diameter of the circle
+        diameter: f64,
+    },
+    /// a slot, i.e. two semicircles connected by two parallel lines
+    Slot {
+        /// the centers of the semicircles
+        centers: [Point3; 2],
+        /// the diameter of the semicircles
+        width: f64,
+        /// the overall length, i.e. the distance between the centers plus the width
+        length: f64,
+    },
+    /// the other shapes
+    Other,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum FittedSurface {
+    Plane {
+        origin: Point3,
+        normal: Vector3,
+    },
+    Cylinder {
+        origin: Point3,
+        axis: Vector3,
+        radius: f64,
+        concave: bool,
+    },
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Convexity {
+    Convex,
+    Concave,
+    Smooth,
+}
+
+#[derive(Clone, Debug)]
+struct FaceInfo {
+    id: FaceID,
+    surface: FittedSurface,
+    boundary_points: Vec<Point3>,
+    samples: Vec<(Point3, Vector3)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Adjacency {
+    other: usize,
+    convexity: Option<Convexity>,
+    midpoint: Point3,
+}
+
+/// Recognizes through holes, blind holes, pockets, and fillets on `solid`.
+///
+/// The surfaces of faces are classified into planes and cylinders by sampling, so features built by
+/// NURBS surfaces are also recognized. The boundary of `solid` is assumed to be oriented outward.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::features::Feature;
+/// use std::f64::consts::PI;
+///
+/// let circle = |radius: f64, z: f64| -> Wire {
+///     let v = builder::vertex(Point3::new(radius, 0.0, z));
+///     builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(2.0 * PI))
+/// };
+/// let (outer, inner) = (circle(2.0, 0.0), circle(1.0, 0.0));
+/// let annulus: Face = builder::try_attach_plane(&[outer, inner.inverse()]).unwrap();
+///
+/// // a washer has a through hole.
+/// let washer: Solid = builder::tsweep(&annulus, Vector3::unit_z());
+/// let features = features::recognize(&washer);
+/// assert_eq!(features.len(), 1);
+/// match &features[0] {
+///     Feature::ThroughHole { radius, depth, axis, .. } => {
+///         assert_near!(*radius, 1.0);
+///         assert_near!(*depth, 1.0);
+///         assert!(axis.cross(Vector3::unit_z()).so_small());
+///     }
+///     _ => panic!("not a through hole"),
+/// }
+///
+/// // put the washer on a disk, then the hole becomes blind.
+/// let disk: Face = builder::try_attach_plane(&[inner]).unwrap();
+/// let lower: Solid = builder::tsweep(&Shell::from(vec![annulus, disk]), Vector3::unit_z())
+///     .pop()
+///     .unwrap()
+///     .unwrap();
+/// let mut shell: Shell = lower.into_boundaries().pop().unwrap();
+/// // the annulus on the ceiling of the lower part
+/// let glued: Face = shell
+///     .iter()
+///     .find(|face| {
+///         let boundaries = face.boundaries();
+///         boundaries.len() == 2 && boundaries[0].vertex_iter().all(|v| v.get_point().z.near(&1.0))
+///     })
+///     .unwrap()
+///     .clone();
+/// let upper: Solid = builder::tsweep(&glued, Vector3::unit_z());
+/// shell.extend(upper.into_boundaries().pop().unwrap());
+/// shell.retain(|face| face.id() != glued.id());
+/// let cup = Solid::new(vec![shell]);
+///
+/// let features = features::recognize(&cup);
+/// assert_eq!(features.len(), 1);
+/// match &features[0] {
+///     Feature::BlindHole { origin, axis, radius, depth, .. } => {
+///         assert_near!(*origin, Point3::new(0.0, 0.0, 2.0));
+///         assert_near!(*axis, -Vector3::unit_z());
+///         assert_near!(*radius, 1.0);
+///         assert_near!(*depth, 1.0);
+///     }
+///     _ => panic!("not a blind hole"),
+/// }
+/// ```
+pub fn recognize(solid: &Solid) -> Vec<Feature> {
+    let faces: Vec<&Face> = solid.face_iter().collect();
+    let infos: Vec<FaceInfo> = faces.iter().map(|face| face_info(face)).collect();
+    let adjacency = adjacency(&faces);
+
+    let mut features = Vec::new();
+    let groups = cylinder_groups(&infos, &adjacency);
+    let mut tube_faces = vec![false; faces.len()];
+    let mut hole_bottoms = vec![false; faces.len()];
+    groups.iter().for_each(|group| {
+        let (origin, axis, radius) = match infos[group[0]].surface {
+            FittedSurface::Cylinder {
+                origin,
+                axis,
+                radius,
+                ..
+            } => (origin, axis, radius),
+            _ => return,
+        };
+        if !covers_whole_circle(&infos, group, origin, axis) {
+            return;
+        }
+        group.iter().for_each(|i| tube_faces[*i] = true);
+        if let Some(hole) = hole(&infos, &adjacency, group, origin, axis, radius) {
+            if let Feature::BlindHole { bottom, .. } = &hole {
+                let idx = infos.iter().position(|info| info.id == *bottom).unwrap();
+                hole_bottoms[idx] = true;
+            }
+            features.push(hole);
+        }
+    });
+
+    infos.iter().enumerate().for_each(|(i, info)| {
+        if hole_bottoms[i] {
+            return;
+        }
+        if let Some(pocket) = pocket(&faces, &infos, &adjacency, i) {
+            features.push(pocket);
+        }
+        if tube_faces[i] {
+            return;
+        }
+        if let FittedSurface::Cylinder {
+            origin,
+            axis,
+            radius,
+            concave,
+        } = info.surface
+        {
+            let tangent_neighbors = adjacency[i]
+                .iter()
+                .filter(|adj| adj.convexity == Some(Convexity::Smooth))
+                .filter(|adj| !same_cylinder(&info.surface, &infos[adj.other].surface))
+                .count();
+            if tangent_neighbors >= 2 {
+                features.push(Feature::Fillet {
+                    face: info.id,
+                    origin,
+                    axis,
+                    radius,
+                    convex: !concave,
+                });
+            }
+        }
+    });
+    features
+}
+
+fn face_info(face: &Face) -> FaceInfo {
+    let surface = face.oriented_surface();
+    let boundary_points: Vec<Point3> = face
+        .boundaries()
+        .iter()
+        .flat_map(|wire| wire.iter())
+        .flat_map(|edge| {
+            let curve = edge.oriented_curve();
+            let (t0, t1) = curve.parameter_range();
+            (0..CURVE_DIVISION)
+                .map(move |i| curve.subs(t0 + (t1 - t0) * i as f64 / CURVE_DIVISION as f64))
+        })
+        .collect();
+    let uvs: Vec<(f64, f64)> = boundary_points
+        .iter()
+        .filter_map(|pt| surface.search_parameter(*pt, None, 100))
+        .collect();
+    let samples = match uvs.is_empty() {
+        true => Vec::new(),
+        false => {
+            let (u0, u1, v0, v1) = uvs.iter().fold(
+                (
+                    f64::INFINITY,
+                    f64::NEG_INFINITY,
+                    f64::INFINITY,
+                    f64::NEG_INFINITY,
+                ),
+                |(u0, u1, v0, v1), (u, v)| (u0.min(*u), u1.max(*u), v0.min(*v), v1.max(*v)),
+            );
+            (0..=SURFACE_DIVISION)
+                .flat_map(|i| (0..=SURFACE_DIVISION).map(move |j| (i, j)))
+                .map(|(i, j)| {
+                    let u = u0 + (u1 - u0) * i as f64 / SURFACE_DIVISION as f64;
+                    let v = v0 + (v1 - v0) * j as f64 / SURFACE_DIVISION as f64;
+                    (surface.subs(u, v), surface.normal(u, v))
+                })
+                .filter(|(_, normal)| normal.magnitude().near(&1.0))
+                .collect()
+        }
+    };
+    FaceInfo {
+        id: face.id(),
+        surface: fit_surface(&samples),
+        boundary_points,
+        samples,
+    }
+}
+
+fn fit_surface(samples: &[(Point3, Vector3)]) -> FittedSurface {
+    let (p0, n0) = match samples.first() {
+        Some(sample) => *sample,
+        None => return FittedSurface::Other,
+    };
+    if samples
+        .iter()
+        .all(|(p, n)| n.near(&n0) && (p - p0).dot(n0).so_small())
+    {
+        return FittedSurface::Plane {
+            origin: p0,
+            normal: n0,
+        };
+    }
+    let (p1, n1) = *samples
+        .iter()
+        .max_by(|x, y| {
+            let (a, b) = (n0.cross(x.1).magnitude2(), n0.cross(y.1).magnitude2());
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap();
+    let axis = n0.cross(n1);
+    if axis.so_small() {
+        return FittedSurface::Other;
+    }
+    let axis = axis.normalize();
+    if !samples.iter().all(|(_, n)| n.dot(axis).so_small()) {
+        return FittedSurface::Other;
+    }
+    // the intersection of the normal lines at p0 and p1, projected to the plane perpendicular to the axis.
+    let s = (p1 - p0).cross(n1).dot(axis) / n0.cross(n1).dot(axis);
+    let origin = p0 + s * n0;
+    let radius = s.abs();
+    let concave = s > 0.0;
+    let on_cylinder = samples.iter().all(|(p, n)| {
+        let radial = (p - origin) - (p - origin).dot(axis) * axis;
+        let dist = radial.magnitude();
+        let outward = if concave {
+            -radial / dist
+        } else {
+            radial / dist
+        };
+        f64::abs(dist - radius) < TOLERANCE * f64::max(radius, 1.0) && n.near(&outward)
+    });
+    match on_cylinder {
+        true => FittedSurface::Cylinder {
+            origin,
+            axis,
+            radius,
+            concave,
+        },
+        false => FittedSurface::Other,
+    }
+}
+
+fn same_cylinder(surface0: &FittedSurface, surface1: &FittedSurface) -> bool {
+    match (surface0, surface1) {
+        (
+            FittedSurface::Cylinder {
+                origin: o0,
+                axis: a0,
+                radius: r0,
+                concave: c0,
+            },
+            FittedSurface::Cylinder {
+                origin: o1,
+                axis: a1,
+                radius: r1,
+                concave: c1,
+            },
+        ) => {
+            let diff = o1 - o0;
+            c0 == c1
+                && a0.cross(*a1).so_small()
+                && (diff - diff.dot(*a0) * a0).so_small()
+                && f64::abs(r0 - r1) < TOLERANCE * f64::max(*r0, 1.0)
+        }
+        _ => false,
+    }
+}
+
+/// For each face, the list of the faces sharing edges with it.
+fn adjacency(faces: &[&Face]) -> Vec<Vec<Adjacency>> {
+    let mut edge_map = HashMap::<EdgeID, Vec<(usize, Edge)>>::default();
+    faces.iter().enumerate().for_each(|(i, face)| {
+        face.boundaries()
+            .iter()
+            .flat_map(|wire| wire.iter())
+            .for_each(|edge| {
+                edge_map
+                    .entry(edge.id())
+                    .or_default()
+                    .push((i, edge.clone()))
+            })
+    });
+    let mut adjacency = vec![Vec::new(); faces.len()];
+    edge_map.into_iter().for_each(|(_, incidents)| {
+        if let [(i, edge), (j, _)] = incidents.as_slice() {
+            let curve = edge.oriented_curve();
+            let (t0, t1) = curve.parameter_range();
+            let midpoint = curve.subs((t0 + t1) / 2.0);
+            let convexity = convexity(&curve, faces[*i], faces[*j]);
+            adjacency[*i].push(Adjacency {
+                other: *j,
+                convexity,
+                midpoint,
+            });
+            adjacency[*j].push(Adjacency {
+                other: *i,
+                convexity,
+                midpoint,
+            });
+        }
+    });
+    adjacency
+}
+
+/// The convexity of the edge whose curve `curve` is oriented along the boundary of `face0`.
+fn convexity(curve: &Curve, face0: &Face, face1: &Face) -> Option<Convexity> {
+    let (t0, t1) = curve.parameter_range();
+    let t = (t0 + t1) / 2.0;
+    let (pt, der) = (curve.subs(t), curve.der(t));
+    let normal = |face: &Face| {
+        let surface = face.oriented_surface();
+        let (u, v) = surface.search_parameter(pt, None, 100)?;
+        Some(surface.normal(u, v))
+    };
+    let cross = normal(face0)?.cross(normal(face1)?);
+    Some(match cross.so_small() {
+        true => Convexity::Smooth,
+        false if cross.dot(der) > 0.0 => Convexity::Convex,
+        false => Convexity::Concave,
+    })
+}
+
+/// The groups of concave cylindrical faces on the same cylinder connected by smooth edges.
+fn cylinder_groups(infos: &[FaceInfo], adjacency: &[Vec<Adjacency>]) -> Vec<Vec<usize>> {
+    let is_concave_cylinder = |i: usize| {
+        matches!(
+            infos[i].surface,
+            FittedSurface::Cylinder { concave: true, .. }
+        )
+    };
+    let mut sets = DisjointSet::new(infos.len());
+    adjacency.iter().enumerate().for_each(|(i, adjs)| {
+        adjs.iter()
+            .filter(|adj| adj.convexity == Some(Convexity::Smooth))
+            .filter(|adj| same_cylinder(&infos[i].surface, &infos[adj.other].surface))
+            .for_each(|adj| {
+                sets.union(i, adj.other);
+            })
+    });
+    let mut groups = HashMap::<usize, Vec<usize>>::default();
+    (0..infos.len())
+        .filter(|i| is_concave_cylinder(*i))
+        .for_each(|i| groups.entry(sets.root(i)).or_default().push(i));
+    let mut groups: Vec<Vec<usize>> = groups.into_values().collect();
+    groups.sort();
+    groups
+}
+
+fn covers_whole_circle(infos: &[FaceInfo], group: &[usize], origin: Point3, axis: Vector3) -> bool {
+    let u_axis = match axis.x.abs() < 0.5 {
+        true => axis.cross(Vector3::unit_x()).normalize(),
+        false => axis.cross(Vector3::unit_y()).normalize(),
+    };
+    let v_axis = axis.cross(u_axis);
+    let mut bins = [false; ANGLE_BINS];
+    group.iter().for_each(|i| {
+        let mut angles: Vec<f64> = infos[*i]
+            .boundary_points
+            .iter()
+            .map(|pt| {
+                let vec = pt - origin;
+                let angle = f64::atan2(vec.dot(v_axis), vec.dot(u_axis));
+                if angle < 0.0 {
+                    angle + 2.0 * PI
+                } else {
+                    angle
+                }
+            })
+            .collect();
+        angles.sort_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+        let len = angles.len();
+        // the face covers the complement of the largest gap between angles.
+        let (start, gap) = (0..len)
+            .map(|k| {
+                let next = if k + 1 == len {
+                    angles[0] + 2.0 * PI
+                } else {
+                    angles[k + 1]
+                };
+                ((k + 1) % len, next - angles[k])
+            })
+            .fold((0, 0.0), |(s, g), (k, gap)| match gap > g {
+                true => (k, gap),
+                false => (s, g),
+            });
+        let (start, width) = (angles[start], 2.0 * PI - gap);
+        bins.iter_mut().enumerate().for_each(|(k, bin)| {
+            let center = 2.0 * PI * (k as f64 + 0.5) / ANGLE_BINS as f64;
+            let offset = (center - start).rem_euclid(2.0 * PI);
+            *bin |= offset <= width + TOLERANCE;
+        });
+    });
+    bins.iter().all(|bin| *bin)
+}
+
+fn hole(
+    infos: &[FaceInfo],
+    adjacency: &[Vec<Adjacency>],
+    group: &[usize],
+    origin: Point3,
+    axis: Vector3,
+    radius: f64,
+) -> Option<Feature> {
+    let height = |pt: Point3| (pt - origin).dot(axis);
+    let (t_min, t_max) = group
+        .iter()
+        .flat_map(|i| &infos[*i].boundary_points)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(t0, t1), pt| {
+            (t0.min(height(*pt)), t1.max(height(*pt)))
+        });
+    let mut ends = [Vec::new(), Vec::new()];
+    group.iter().flat_map(|i| &adjacency[*i]).for_each(|adj| {
+        if !group.contains(&adj.other) {
+            let t = height(adj.midpoint);
+            let end = if t - t_min < t_max - t { 0 } else { 1 };
+            ends[end].push(*adj);
+        }
+    });
+    let all_convex = |end: &[Adjacency]| {
+        !end.is_empty()
+            && end
+                .iter()
+                .all(|adj| adj.convexity == Some(Convexity::Convex))
+    };
+    let faces: Vec<FaceID> = group.iter().map(|i| infos[*i].id).collect();
+    let depth = t_max - t_min;
+    match (all_convex(&ends[0]), all_convex(&ends[1])) {
+        (true, true) => Some(Feature::ThroughHole {
+            origin: origin + t_min * axis,
+            axis,
+            radius,
+            depth,
+            faces,
+        }),
+        (true, false) => Some(Feature::BlindHole {
+            origin: origin + t_min * axis,
+            axis,
+            radius,
+            depth,
+            bottom: infos[hole_bottom(infos, adjacency, group, &ends[1], axis)?].id,
+            faces,
+        }),
+        (false, true) => Some(Feature::BlindHole {
+            origin: origin + t_max * axis,
+            axis: -axis,
+            radius,
+            depth,
+            bottom: infos[hole_bottom(infos, adjacency, group, &ends[0], axis)?].id,
+            faces,
+        }),
+        (false, false) => None,
+    }
+}
+
+/// Returns the index of the planar face closing the end of the hole.
+fn hole_bottom(
+    infos: &[FaceInfo],
+    adjacency: &[Vec<Adjacency>],
+    group: &[usize],
+    end: &[Adjacency],
+    axis: Vector3,
+) -> Option<usize> {
+    let bottom = end.first()?.other;
+    let closing = end
+        .iter()
+        .all(|adj| adj.other == bottom && adj.convexity == Some(Convexity::Concave));
+    let perpendicular = match infos[bottom].surface {
+        FittedSurface::Plane { normal, .. } => normal.cross(axis).so_small(),
+        _ => false,
+    };
+    let surrounded = adjacency[bottom]
+        .iter()
+        .all(|adj| group.contains(&adj.other));
+    match closing && perpendicular && surrounded {
+        true => Some(bottom),
+        false => None,
+    }
+}
+
+fn pocket(
+    faces: &[&Face],
+    infos: &[FaceInfo],
+    adjacency: &[Vec<Adjacency>],
+    i: usize,
+) -> Option<Feature> {
+    let (origin, normal) = match infos[i].surface {
+        FittedSurface::Plane { origin, normal } => (origin, normal),
+        _ => return None,
+    };
+    let boundaries = faces[i].boundaries();
+    let outer = boundaries.iter().max_by(|w0, w1| {
+        let (a0, a1) = (wire_area(w0, normal), wire_area(w1, normal));
+        a0.partial_cmp(&a1).unwrap_or(std::cmp::Ordering::Equal)
+    })?;
+    let mut walls = Vec::new();
+    for edge in outer.iter() {
+        let curve = edge.oriented_curve();
+        let (t0, t1) = curve.parameter_range();
+        let midpoint = curve.subs((t0 + t1) / 2.0);
+        let adj = adjacency[i]
+            .iter()
+            .find(|adj| adj.midpoint.near(&midpoint))?;
+        match adj.convexity {
+            Some(Convexity::Convex) | None => return None,
+            Some(convexity) => walls.push((adj.other, convexity)),
+        }
+    }
+    if !walls
+        .iter()
+        .any(|(_, convexity)| *convexity == Convexity::Concave)
+    {
+        return None;
+    }
+    let mut walls_ids = Vec::<FaceID>::new();
+    walls.iter().for_each(|(j, _)| {
+        if !walls_ids.contains(&infos[*j].id) {
+            walls_ids.push(infos[*j].id);
+        }
+    });
+    let depth = walls
+        .iter()
+        .flat_map(|(j, _)| &infos[*j].samples)
+        .map(|(pt, _)| (pt - origin).dot(normal))
+        .fold(0.0, f64::max);
+    Some(Feature::Pocket {
+        floor: infos[i].id,
+        normal,
+        depth,
+        walls: walls_ids,
+    })
+}
+
+/// Recognizes the circular and slot holes, the inner boundaries of the planar faces in `faces`.
+///
+/// The outer boundary of a face is the one with the largest area, and the others are the
+/// boundaries of holes. The edges of the boundaries are classified into lines and circular
+/// arcs within the distance `tol`, and the non-planar faces are skipped.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::features::HoleShape;
+/// use std::f64::consts::PI;
+///
+/// // the square plate with a circular hole and a slot
+/// let v = builder::vertex(Point3::new(-5.0, -5.0, 0.0));
+/// let e = builder::tsweep(&v, Vector3::new(10.0, 0.0, 0.0));
+/// let square: Face = builder::tsweep(&e, Vector3::new(0.0, 10.0, 0.0));
+/// let v = builder::vertex(Point3::new(-2.0, 1.0, 0.0));
+/// let circle = builder::rsweep(&v, Point3::new(-3.0, 1.0, 0.0), Vector3::unit_z(), Rad(2.0 * PI));
+/// let p = [
+///     Point3::new(1.0, -1.0, 0.0),
+///     Point3::new(3.0, -1.0, 0.0),
+///     Point3::new(3.0, 1.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+/// ];
+/// let v: Vec<Vertex> = p.iter().map(|p| builder::vertex(*p)).collect();
+/// let slot: Wire = vec![
+///     builder::line(&v[0], &v[1]),
+///     builder::circle_arc(&v[1], &v[2], Point3::new(4.0, 0.0, 0.0)),
+///     builder::line(&v[2], &v[3]),
+///     builder::circle_arc(&v[3], &v[0], Point3::new(0.0, 0.0, 0.0)),
+/// ]
+/// .into();
+/// let mut boundaries = square.boundaries();
+/// boundaries.push(circle.inverse());
+/// boundaries.push(slot.inverse());
+/// let plate = builder::try_attach_plane(&boundaries).unwrap();
+///
+/// let holes = features::planar_holes([&plate], 1.0e-6);
+/// assert_eq!(holes.len(), 2);
+/// match holes[0].shape {
+///     HoleShape::Circle { center, diameter } => {
+///         assert_near!(center, Point3::new(-3.0, 1.0, 0.0));
+///         assert_near!(diameter, 2.0);
+///     }
+///     _ => panic!("not a circle"),
+/// }
+/// match holes[1].shape {
+///     HoleShape::Slot { centers, width, length } => {
+///         assert_near!(centers[0].midpoint(centers[1]), Point3::new(2.0, 0.0, 0.0));
+///         assert_near!(width, 2.0);
+///         assert_near!(length, 4.0);
+///     }
+///     _ => panic!("not a slot"),
+/// }
+/// ```
+pub fn planar_holes<'a, I: IntoIterator<Item = &'a Face>>(faces: I, tol: f64) -> Vec<PlanarHole> {
+    faces
+        .into_iter()
+        .flat_map(|face| {
+            let normal = match face_info(face).surface {
+                FittedSurface::Plane { normal, .. } => normal,
+                _ => return Vec::new(),
+            };
+            let boundaries = face.boundaries();
+            let outer = (0..boundaries.len()).max_by(|i, j| {
+                let (a0, a1) = (
+                    wire_area(&boundaries[*i], normal),
+                    wire_area(&boundaries[*j], normal),
+                );
+                a0.partial_cmp(&a1).unwrap_or(std::cmp::Ordering::Equal)
+            });
+            (0..boundaries.len())
+                .filter(|i| Some(*i) != outer)
+                .map(|i| PlanarHole {
+                    face: face.id(),
+                    boundary: i,
+                    normal,
+                    shape: hole_shape(&boundaries[i], tol),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Segment {
+    Line(Point3, Point3),
+    Arc { center: Point3, radius: f64 },
+}
+
+fn hole_shape(wire: &Wire, tol: f64) -> HoleShape {
+    let mut segments = Vec::<Segment>::new();
+    for edge in wire.iter() {
+        let segment = match classify_edge(&edge.oriented_curve(), tol) {
+            Some(segment) => segment,
+            None => return HoleShape::Other,
+        };
+        match segments
+            .last_mut()
+            .and_then(|last| merge(last, &segment, tol))
+        {
+            Some(merged) => *segments.last_mut().unwrap() = merged,
+            None => segments.push(segment),
+        }
+    }
+    if segments.len() > 1 {
+        let (first, last) = (segments[0], segments[segments.len() - 1]);
+        if let Some(merged) = merge(&last, &first, tol) {
+            segments[0] = merged;
+            segments.pop();
+        }
+    }
+    match segments.as_slice() {
+        [Segment::Arc { center, radius }] => HoleShape::Circle {
+            center: *center,
+            diameter: 2.0 * radius,
+        },
+        [s0, s1, s2, s3] => {
+            let (arcs, lines) = match (s0, s1) {
+                (Segment::Arc { .. }, Segment::Line(..)) => ([s0, s2], [s1, s3]),
+                _ => ([s1, s3], [s0, s2]),
+            };
+            match (arcs, lines) {
+                (
+                    [Segment::Arc {
+                        center: c0,
+                        radius: r0,
+                    }, Segment::Arc {
+                        center: c1,
+                        radius: r1,
+                    }],
+                    [Segment::Line(p0, p1), Segment::Line(q0, q1)],
+                ) if f64::abs(r0 - r1) < tol
+                    && f64::abs(p0.distance(*p1) - c0.distance(*c1)) < tol
+                    && f64::abs(q0.distance(*q1) - c0.distance(*c1)) < tol =>
+                {
+                    HoleShape::Slot {
+                        centers: [*c0, *c1],
+                        width: r0 + r1,
+                        length: c0.distance(*c1) + r0 + r1,
+                    }
+                }
+                _ => HoleShape::Other,
+            }
+        }
+        _ => HoleShape::Other,
+    }
+}
+
+/// Classifies the curve into a line or a circular arc by sampling.
+fn classify_edge(curve: &Curve, tol: f64) -> Option<Segment> {
+    const DIVISION: usize = 4 * CURVE_DIVISION;
+    let (t0, t1) = curve.parameter_range();
+    let points: Vec<Point3> = (0..=DIVISION)
+        .map(|i| curve.subs(t0 + (t1 - t0) * i as f64 / DIVISION as f64))
+        .collect();
+    let (p0, p1) = (points[0], points[DIVISION]);
+    let dir = p1 - p0;
+    if dir.magnitude() > tol {
+        let dir = dir.normalize();
+        let on_line = points.iter().all(|p| {
+            let v = p - p0;
+            (v - v.dot(dir) * dir).magnitude() < tol
+        });
+        if on_line {
+            return Some(Segment::Line(p0, p1));
+        }
+    }
+    let (a, b, c) = (points[0], points[DIVISION / 3], points[2 * DIVISION / 3]);
+    let (u, v) = (b - a, c - a);
+    let w = u.cross(v);
+    if w.so_small2() {
+        return None;
+    }
+    // the circumcenter of the triangle abc
+    let center = a + (v * u.magnitude2() - u * v.magnitude2()).cross(w) / (2.0 * w.magnitude2());
+    let normal = w.normalize();
+    let radius = center.distance(a);
+    let on_circle = points.iter().all(|p| {
+        f64::abs(p.distance(center) - radius) < tol && (p - center).dot(normal).abs() < tol
+    });
+    match on_circle {
+        true => Some(Segment::Arc { center, radius }),
+        false => None,
+    }
+}
+
+/// Merges the consecutive collinear lines or the arcs of the same circle.
+fn merge(segment0: &Segment, segment1: &Segment, tol: f64) -> Option<Segment> {
+    match (segment0, segment1) {
+        (Segment::Line(p0, p1), Segment::Line(q0, q1)) => {
+            let (d0, d1) = ((p1 - p0).normalize(), (q1 - q0).normalize());
+            match d0.cross(d1).magnitude() < tol && d0.dot(d1) > 0.0 && p1.distance(*q0) < tol {
+                true => Some(Segment::Line(*p0, *q1)),
+                false => None,
+            }
+        }
+        (
+            Segment::Arc {
+                center: c0,
+                radius: r0,
+            },
+            Segment::Arc {
+                center: c1,
+                radius: r1,
+            },
+        ) => match c0.distance(*c1) < tol && f64::abs(r0 - r1) < tol {
+            true => Some(*segment0),
+            false => None,
+        },
+        _ => None,
+    }
+}
+
+/// The area of the wire projected to the plane perpendicular to `normal`.
+fn wire_area(wire: &Wire, normal: Vector3) -> f64 {
+    let points: Vec<Point3> = wire
+        .iter()
+        .flat_map(|edge| {
+            let curve = edge.oriented_curve();
+            let (t0, t1) = curve.parameter_range();
+            (0..CURVE_DIVISION)
+                .map(move |i| curve.subs(t0 + (t1 - t0) * i as f64 / CURVE_DIVISION as f64))
+        })
+        .collect();
+    let area = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(p, q)| p.to_vec().cross(q.to_vec()).dot(normal))
+        .sum::<f64>();
+    f64::abs(area) / 2.0
+}
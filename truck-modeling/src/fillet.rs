@@ -0,0 +1,853 @@
+use crate::*;
+use errors::{Error, UnsupportedFilletReason as Reason};
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+/// The radii of a variable-radius fillet along the absolute direction of the edge.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RadiusLaw {
+    /// the radii at the start and at the end, interpolated linearly
+    Linear(f64, f64),
+    /// the pairs of the parameters from `0.0` to `1.0` and the radii, interpolated linearly
+    Table(Vec<(f64, f64)>),
+}
+
+impl RadiusLaw {
+    fn into_table(self) -> Result<Vec<(f64, f64)>> {
+        let table = match self {
+            RadiusLaw::Linear(r0, r1) => vec![(0.0, r0), (1.0, r1)],
+            RadiusLaw::Table(table) => table,
+        };
+        let increasing = table.windows(2).all(|w| w[0].0 + TOLERANCE < w[1].0);
+        let ends = table.len() >= 2 && table[0].0.so_small() && table[table.len() - 1].0.near(&1.0);
+        if !increasing || !ends {
+            return Err(unsupported(Reason::InvalidRadiusLaw));
+        }
+        if table.iter().any(|(_, r)| *r < TOLERANCE) {
+            return Err(unsupported(Reason::NonPositiveSize));
+        }
+        Ok(table)
+    }
+}
+
+/// the cross sections of the blends
+#[derive(Clone, Debug)]
+pub(super) enum Profile {
+    /// the fillet by the rolling ball whose radii are interpolated linearly between the pairs
+    /// of the parameters along the edge and the radii
+    Round(Vec<(f64, f64)>),
+    /// the chamfer by the distances on the first face and the second one
+    Chamfer(f64, f64),
+}
+
+impl Profile {
+    /// the fillet of the constant radius
+    #[inline(always)]
+    pub(super) fn round(radius: f64) -> Self { Profile::Round(vec![(0.0, radius), (1.0, radius)]) }
+
+    /// the fillet of the radius law
+    #[inline(always)]
+    pub(super) fn variable(law: RadiusLaw) -> Result<Self> { Ok(Profile::Round(law.into_table()?)) }
+
+    /// the parameters of the cross sections along the edge
+    fn params(&self) -> Vec<f64> {
+        match self {
+            Profile::Round(table) => table.iter().map(|(t, _)| *t).collect(),
+            Profile::Chamfer(..) => vec![0.0, 1.0],
+        }
+    }
+
+    /// whether the radius is constant along the edge
+    fn is_constant(&self) -> bool {
+        match self {
+            Profile::Round(table) => table.iter().all(|(_, r)| r.near(&table[0].1)),
+            Profile::Chamfer(..) => true,
+        }
+    }
+
+    fn radius(&self, t: f64) -> f64 {
+        match self {
+            Profile::Round(table) => {
+                let k = table
+                    .windows(2)
+                    .position(|w| t <= w[1].0)
+                    .unwrap_or(table.len() - 2);
+                let ((t0, r0), (t1, r1)) = (table[k], table[k + 1]);
+                r0 + (r1 - r0) * (t - t0) / (t1 - t0)
+            }
+            Profile::Chamfer(..) => 0.0,
+        }
+    }
+}
+
+/// the corners at the vertices of the blended edges
+#[derive(Clone, Copy, Debug)]
+enum Corner {
+    /// only one edge is blended, and the other face is perpendicular to it.
+    Single { edge: EdgeID, face: usize },
+    /// two edges are blended, and the faces are perpendicular to each other. The blends meet
+    /// along the miter from the point on the third edge to the point on the common face.
+    Double {
+        edges: [EdgeID; 2],
+        face: usize,
+        third: EdgeID,
+    },
+    /// all three edges are blended, and the faces are perpendicular to each other.
+    Triple([EdgeID; 3]),
+}
+
+/// the points on the faces and the edges replacing the vertices
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Key {
+    Edge(VertexID, EdgeID),
+    Face(VertexID, usize),
+}
+
+/// the blend along an edge: the faces, the directions into the faces, and the profile
+#[derive(Clone, Debug)]
+struct Ball {
+    /// the face containing the edge in the same direction, and the other face
+    faces: [usize; 2],
+    normals: [Vector3; 2],
+    inwards: [Vector3; 2],
+    /// the absolute front vertex of the edge, the parameter `0.0` of the profile
+    front: VertexID,
+    profile: Profile,
+}
+
+impl Ball {
+    /// the parameter of the profile at the end `v` of the edge
+    fn end_param(&self, v: &Vertex) -> f64 {
+        match v.id() == self.front {
+            true => 0.0,
+            false => 1.0,
+        }
+    }
+    /// the distances from the edge to the contact lines at the parameter `t`
+    fn offsets(&self, t: f64) -> [f64; 2] {
+        match self.profile {
+            Profile::Round(_) => {
+                let (a, b) = self.center_coordinates(t);
+                let cos = self.inwards[0].dot(self.inwards[1]);
+                [a + b * cos, b + a * cos]
+            }
+            Profile::Chamfer(d0, d1) => [d0, d1],
+        }
+    }
+    /// the coordinates of the center of the ball by `inwards` at the parameter `t`
+    fn center_coordinates(&self, t: f64) -> (f64, f64) {
+        let radius = self.profile.radius(t);
+        let a = -radius / self.inwards[0].dot(self.normals[1]);
+        let b = -radius / self.inwards[1].dot(self.normals[0]);
+        (a, b)
+    }
+    /// the point inside of the blend face at the parameter `t`, e.g. the center of the ball
+    fn inner(&self, point: Point3, t: f64) -> Point3 {
+        match self.profile {
+            Profile::Round(_) => {
+                let (a, b) = self.center_coordinates(t);
+                point + self.inwards[0] * a + self.inwards[1] * b
+            }
+            Profile::Chamfer(..) => self.contact(point, 0, t) + (self.contact(point, 1, t) - point),
+        }
+    }
+    fn contact(&self, point: Point3, i: usize, t: f64) -> Point3 {
+        point + self.inwards[i] * self.offsets(t)[i]
+    }
+}
+
+fn unsupported(reason: Reason) -> Error { Error::UnsupportedFillet(reason) }
+
+pub(super) fn arc_curve(point0: Point3, point1: Point3, center: Point3) -> Curve {
+    let (v0, v1) = (point0 - center, point1 - center);
+    let axis = v0.cross(v1).normalize();
+    let arc = geom_impls::circle_arc(point0.to_homogeneous(), center, axis, v0.angle(v1));
+    Curve::NURBSCurve(NURBSCurve::new(arc))
+}
+
+/// Makes the normal of `surface` at `(u, v)` point away from `center`.
+pub(super) fn outward(mut surface: Surface, (u, v): (f64, f64), center: Point3) -> Surface {
+    if surface.normal(u, v).dot(surface.subs(u, v) - center) < 0.0 {
+        surface.invert();
+    }
+    surface
+}
+
+struct ShellFillet<'a> {
+    faces: Vec<(Vec<Wire>, Surface, Vector3)>,
+    balls: HashMap<EdgeID, Ball>,
+    corners: HashMap<VertexID, Corner>,
+    /// the other two edges at the vertices of the `Single` corners in the faces
+    corner_edges: HashMap<(VertexID, usize), EdgeID>,
+    filleted: &'a HashMap<EdgeID, Profile>,
+    vertices: HashMap<Key, Vertex>,
+    edges: HashMap<EdgeID, Edge>,
+    lines: HashMap<(EdgeID, usize), Edge>,
+    arcs: HashMap<(EdgeID, VertexID), Edge>,
+    miters: HashMap<VertexID, Edge>,
+}
+
+impl<'a> ShellFillet<'a> {
+    fn new(shell: &Shell, filleted: &'a HashMap<EdgeID, Profile>) -> Result<Self> {
+        let faces: Vec<(Vec<Wire>, Surface, Vector3)> = shell
+            .face_iter()
+            .map(|face| {
+                let surface = face.oriented_surface();
+                let normal = surface.normal(0.0, 0.0);
+                (face.boundaries(), surface, normal)
+            })
+            .collect();
+        let mut edge_faces = HashMap::<EdgeID, Vec<(usize, Edge)>>::default();
+        let mut vertex_edges = HashMap::<VertexID, HashSet<EdgeID>>::default();
+        faces.iter().enumerate().for_each(|(i, (wires, _, _))| {
+            wires.iter().flatten().for_each(|edge| {
+                edge_faces
+                    .entry(edge.id())
+                    .or_default()
+                    .push((i, edge.clone()));
+                [edge.front(), edge.back()].into_iter().for_each(|v| {
+                    vertex_edges.entry(v.id()).or_default().insert(edge.id());
+                });
+            })
+        });
+        let mut balls = HashMap::default();
+        for (id, adjacent) in &edge_faces {
+            let profile = match filleted.get(id) {
+                Some(profile) => profile.clone(),
+                None => continue,
+            };
+            let ((f0, e0), (f1, _)) = match adjacent.as_slice() {
+                [a, b] if a.1.orientation() => (a, b),
+                [a, b] => (b, a),
+                _ => return Err(unsupported(Reason::NotManifoldEdge)),
+            };
+            if !matches!(e0.get_curve(), Curve::Line(_)) {
+                return Err(unsupported(Reason::NotLine));
+            }
+            let is_plane = |i: usize| matches!(faces[i].1, Surface::Plane(_));
+            if !is_plane(*f0) || !is_plane(*f1) {
+                return Err(unsupported(Reason::NotBetweenPlanes));
+            }
+            let dir = (e0.back().get_point() - e0.front().get_point()).normalize();
+            let normals = [faces[*f0].2, faces[*f1].2];
+            let inwards = [normals[0].cross(dir), normals[1].cross(-dir)];
+            if inwards[0].dot(normals[1]) > -TOLERANCE {
+                return Err(unsupported(Reason::NotConvex));
+            }
+            let ball = Ball {
+                faces: [*f0, *f1],
+                normals,
+                inwards,
+                front: e0.front().id(),
+                profile,
+            };
+            balls.insert(*id, ball);
+        }
+        let mut corners = HashMap::default();
+        let mut corner_edges = HashMap::default();
+        for (id, ball) in &balls {
+            let edge = &edge_faces[id][0].1;
+            for v in [edge.front(), edge.back()] {
+                let edges = &vertex_edges[&v.id()];
+                if edges.len() != 3 {
+                    return Err(unsupported(Reason::NotTrihedralVertex));
+                }
+                let count = edges.iter().filter(|e| balls.contains_key(*e)).count();
+                let faces_at = |e: &EdgeID| edge_faces[e].iter().map(|(i, _)| *i);
+                let orthogonal = || {
+                    let normals: Vec<Vector3> = edges
+                        .iter()
+                        .flat_map(faces_at)
+                        .collect::<HashSet<_>>()
+                        .into_iter()
+                        .map(|i| faces[i].2)
+                        .collect();
+                    normals.len() == 3
+                        && (0..3).all(|i| normals[i].dot(normals[(i + 1) % 3]).so_small())
+                };
+                let corner = match count {
+                    1 => {
+                        let face = edges
+                            .iter()
+                            .flat_map(faces_at)
+                            .find(|i| !ball.faces.contains(i))
+                            .unwrap();
+                        let dir = edge.back().get_point() - edge.front().get_point();
+                        if !faces[face].2.cross(dir.normalize()).so_small() {
+                            return Err(unsupported(Reason::NotPerpendicularEnd));
+                        }
+                        edges.iter().filter(|e| *e != id).for_each(|e| {
+                            faces_at(e).filter(|i| *i != face).for_each(|i| {
+                                corner_edges.insert((v.id(), i), *e);
+                            })
+                        });
+                        Corner::Single { edge: *id, face }
+                    }
+                    2 => {
+                        if !orthogonal() {
+                            return Err(unsupported(Reason::NotPerpendicularCorner));
+                        }
+                        let mut pair = edges.iter().copied().filter(|e| balls.contains_key(e));
+                        let pair = [(); 2].map(|_| pair.next().unwrap());
+                        let third = *edges.iter().find(|e| !balls.contains_key(*e)).unwrap();
+                        let (ball0, ball1) = (&balls[&pair[0]], &balls[&pair[1]]);
+                        let face = *ball0
+                            .faces
+                            .iter()
+                            .find(|i| ball1.faces.contains(i))
+                            .unwrap();
+                        // the contact points on the other faces are the same point on the third edge.
+                        let contact = |ball: &Ball| {
+                            let i = (ball.faces[0] == face) as usize;
+                            ball.contact(v.get_point(), i, ball.end_param(v))
+                        };
+                        let same = match (&ball0.profile, &ball1.profile) {
+                            (Profile::Round(_), Profile::Round(_)) => {
+                                ball0.profile.is_constant() && ball1.profile.is_constant()
+                            }
+                            (Profile::Chamfer(..), Profile::Chamfer(..)) => true,
+                            _ => false,
+                        };
+                        if !same || !contact(ball0).near(&contact(ball1)) {
+                            return Err(unsupported(Reason::DifferentProfiles));
+                        }
+                        Corner::Double {
+                            edges: pair,
+                            face,
+                            third,
+                        }
+                    }
+                    _ => {
+                        if !orthogonal() {
+                            return Err(unsupported(Reason::NotPerpendicularCorner));
+                        }
+                        let mut triple = edges.iter().copied();
+                        let triple = [(); 3].map(|_| triple.next().unwrap());
+                        let profiles = triple.map(|e| {
+                            let ball = &balls[&e];
+                            (
+                                ball.profile.radius(ball.end_param(v)),
+                                ball.profile.params().len(),
+                            )
+                        });
+                        let same = match triple.map(|e| &balls[&e].profile) {
+                            [Profile::Round(_), Profile::Round(_), Profile::Round(_)] => {
+                                (1..3).all(|i| profiles[i].0.near(&profiles[0].0))
+                            }
+                            [Profile::Chamfer(..), Profile::Chamfer(..), Profile::Chamfer(..)] => {
+                                true
+                            }
+                            _ => false,
+                        };
+                        if !same {
+                            return Err(unsupported(Reason::DifferentProfiles));
+                        }
+                        Corner::Triple(triple)
+                    }
+                };
+                corners.insert(v.id(), corner);
+            }
+        }
+        Ok(Self {
+            faces,
+            balls,
+            corners,
+            corner_edges,
+            filleted,
+            vertices: HashMap::default(),
+            edges: HashMap::default(),
+            lines: HashMap::default(),
+            arcs: HashMap::default(),
+            miters: HashMap::default(),
+        })
+    }
+
+    /// the center of the sphere at the vertex of three rounded edges, or the point inside of
+    /// the triangle at the vertex of three chamfered edges
+    fn corner_center(&self, v: &Vertex, edges: [EdgeID; 3]) -> Point3 {
+        let point = v.get_point();
+        let mut contacts = HashMap::<usize, Vector3>::default();
+        edges.iter().for_each(|id| {
+            let ball = &self.balls[id];
+            let offsets = ball.offsets(ball.end_param(v));
+            (0..2).for_each(|i| {
+                *contacts.entry(ball.faces[i]).or_insert_with(Vector3::zero) +=
+                    ball.inwards[i] * offsets[i];
+            })
+        });
+        let sum = contacts.values().fold(Vector3::zero(), |sum, v| sum + v);
+        match self.balls[&edges[0]].profile {
+            // the center is the sum of the contact points minus the vertex twice.
+            Profile::Round(_) => point + sum / 2.0,
+            // the mirror of the vertex by the centroid of the triangle
+            Profile::Chamfer(..) => point + sum * (2.0 / 3.0),
+        }
+    }
+
+    /// the contact point on the face at the vertex of two or three edges
+    fn corner_contact(&self, v: &Vertex, edges: &[EdgeID], face: usize) -> Point3 {
+        edges.iter().fold(v.get_point(), |point, id| {
+            let ball = &self.balls[id];
+            match ball.faces.iter().position(|f| *f == face) {
+                Some(i) => point + ball.inwards[i] * ball.offsets(ball.end_param(v))[i],
+                None => point,
+            }
+        })
+    }
+
+    /// the vertex on the face `face` replacing the vertex `v` of the blended edge `edge`
+    fn contact_vertex(&mut self, v: &Vertex, edge: EdgeID, face: usize) -> Vertex {
+        let ball = &self.balls[&edge];
+        let i = (ball.faces[1] == face) as usize;
+        let (key, point) = match self.corners[&v.id()] {
+            Corner::Single { .. } => {
+                let other = self.corner_edges[&(v.id(), face)];
+                let point = ball.contact(v.get_point(), i, ball.end_param(v));
+                (Key::Edge(v.id(), other), point)
+            }
+            Corner::Double {
+                edges,
+                face: common,
+                third,
+            } => match face == common {
+                true => (
+                    Key::Face(v.id(), face),
+                    self.corner_contact(v, &edges, face),
+                ),
+                false => {
+                    let point = ball.contact(v.get_point(), i, ball.end_param(v));
+                    (Key::Edge(v.id(), third), point)
+                }
+            },
+            Corner::Triple(edges) => {
+                let point = self.corner_contact(v, &edges, face);
+                (Key::Face(v.id(), face), point)
+            }
+        };
+        self.vertices
+            .entry(key)
+            .or_insert_with(|| Vertex::new(point))
+            .clone()
+    }
+
+    /// the vertex at the end `v` of the edge `edge`
+    fn end_vertex(&mut self, v: &Vertex, edge: EdgeID) -> Vertex {
+        match self.corners.get(&v.id()) {
+            None => v.clone(),
+            Some(Corner::Triple(_)) => unreachable!(),
+            Some(Corner::Single { edge: filleted, .. }) => {
+                let filleted = *filleted;
+                let face = self.edge_faces_except(v, edge);
+                self.contact_vertex(v, filleted, face)
+            }
+            // the point on the third edge, shared by the two blends
+            Some(Corner::Double { edges, face, .. }) => {
+                let (filleted, common) = (edges[0], *face);
+                let faces = self.balls[&filleted].faces;
+                let face = match faces[0] == common {
+                    true => faces[1],
+                    false => faces[0],
+                };
+                self.contact_vertex(v, filleted, face)
+            }
+        }
+    }
+
+    /// the face containing `edge` and the blended edge at `v`
+    fn edge_faces_except(&self, v: &Vertex, edge: EdgeID) -> usize {
+        self.corner_edges
+            .iter()
+            .find(|((w, _), e)| *w == v.id() && **e == edge)
+            .map(|((_, face), _)| *face)
+            .unwrap()
+    }
+
+    /// the edge not filleted, shortened at the corners
+    fn shortened(&mut self, edge: &Edge) -> Edge {
+        if let Some(new) = self.edges.get(&edge.id()) {
+            return new.clone();
+        }
+        let (v0, v1) = (edge.absolute_front(), edge.absolute_back());
+        let new = match (
+            self.corners.contains_key(&v0.id()),
+            self.corners.contains_key(&v1.id()),
+        ) {
+            (false, false) => edge.absolute_clone(),
+            _ => {
+                let w0 = self.end_vertex(v0, edge.id());
+                let w1 = self.end_vertex(v1, edge.id());
+                Edge::new(&w0, &w1, Curve::Line(Line(w0.get_point(), w1.get_point())))
+            }
+        };
+        self.edges.insert(edge.id(), new.clone());
+        new
+    }
+
+    /// the contact line of the blend of `edge` on the face, in the absolute direction
+    fn contact_line(&mut self, edge: &Edge, face: usize) -> Edge {
+        if let Some(line) = self.lines.get(&(edge.id(), face)) {
+            return line.clone();
+        }
+        let (v0, v1) = (edge.absolute_front(), edge.absolute_back());
+        let w0 = self.contact_vertex(v0, edge.id(), face);
+        let w1 = self.contact_vertex(v1, edge.id(), face);
+        let (p0, p1) = (w0.get_point(), w1.get_point());
+        let ball = &self.balls[&edge.id()];
+        let params = ball.profile.params();
+        let curve = match params.len() {
+            2 => Curve::Line(Line(p0, p1)),
+            // the contact points of the sections are interpolated linearly.
+            _ => {
+                let i = (ball.faces[1] == face) as usize;
+                let len = params.len();
+                let points = params
+                    .iter()
+                    .enumerate()
+                    .map(|(k, t)| match k {
+                        0 => p0,
+                        _ if k + 1 == len => p1,
+                        _ => ball.contact(
+                            v0.get_point() + (v1.get_point() - v0.get_point()) * *t,
+                            i,
+                            *t,
+                        ),
+                    })
+                    .collect();
+                Curve::BSplineCurve(BSplineCurve::new(polyline_knot_vec(&params), points))
+            }
+        };
+        let line = Edge::new(&w0, &w1, curve);
+        self.lines.insert((edge.id(), face), line.clone());
+        line
+    }
+
+    /// the cross section of the blend of `edge` at `v`, from the first face to the second one
+    fn arc(&mut self, edge: &Edge, v: &Vertex) -> Edge {
+        if let Some(arc) = self.arcs.get(&(edge.id(), v.id())) {
+            return arc.clone();
+        }
+        let ball = self.balls[&edge.id()].clone();
+        let w0 = self.contact_vertex(v, edge.id(), ball.faces[0]);
+        let w1 = self.contact_vertex(v, edge.id(), ball.faces[1]);
+        let (p0, p1) = (w0.get_point(), w1.get_point());
+        let curve = match (&ball.profile, self.corners[&v.id()]) {
+            (_, Corner::Double { .. }) => {
+                let arc = self.miter(v, &w0, &w1);
+                self.arcs.insert((edge.id(), v.id()), arc.clone());
+                return arc;
+            }
+            (Profile::Chamfer(..), _) => Curve::Line(Line(p0, p1)),
+            (_, Corner::Single { .. }) => {
+                arc_curve(p0, p1, ball.inner(v.get_point(), ball.end_param(v)))
+            }
+            (_, Corner::Triple(edges)) => arc_curve(p0, p1, self.corner_center(v, edges)),
+        };
+        let arc = Edge::new(&w0, &w1, curve);
+        self.arcs.insert((edge.id(), v.id()), arc.clone());
+        arc
+    }
+
+    /// the miter at the vertex of two blended edges, from `w0` to `w1`
+    fn miter(&mut self, v: &Vertex, w0: &Vertex, w1: &Vertex) -> Edge {
+        let miter = match self.miters.get(&v.id()) {
+            Some(miter) => miter.clone(),
+            None => {
+                let (p0, p1) = (w0.get_point(), w1.get_point());
+                let edge = match self.corners[&v.id()] {
+                    Corner::Double { edges, .. } => edges[0],
+                    _ => unreachable!(),
+                };
+                let curve = match self.balls[&edge].profile {
+                    // the quarter of the ellipse cut from the cylinders by the bisecting plane,
+                    // whose conjugate radii are from the intersection of the axes to the ends.
+                    Profile::Round(_) => {
+                        let weight = 0.5_f64.sqrt();
+                        let control_points = vec![
+                            p0.to_homogeneous(),
+                            v.get_point().to_homogeneous() * weight,
+                            p1.to_homogeneous(),
+                        ];
+                        let curve = BSplineCurve::new(KnotVec::bezier_knot(2), control_points);
+                        Curve::NURBSCurve(NURBSCurve::new(curve))
+                    }
+                    Profile::Chamfer(..) => Curve::Line(Line(p0, p1)),
+                };
+                let miter = Edge::new(w0, w1, curve);
+                self.miters.insert(v.id(), miter.clone());
+                miter
+            }
+        };
+        match miter.front() == w0 {
+            true => miter,
+            false => miter.inverse(),
+        }
+    }
+
+    fn planar_face(&mut self, i: usize) -> Face {
+        let (wires, surface, _) = self.faces[i].clone();
+        let wires = wires
+            .iter()
+            .map(|wire| {
+                let mut new_wire = Wire::new();
+                wire.edge_iter().for_each(|edge| {
+                    let new = match self.filleted.contains_key(&edge.id()) {
+                        true => self.contact_line(edge, i),
+                        false => self.shortened(edge),
+                    };
+                    match edge.orientation() {
+                        true => new_wire.push_back(new),
+                        false => new_wire.push_back(new.inverse()),
+                    }
+                    if let Some(Corner::Single {
+                        edge: filleted,
+                        face,
+                    }) = self.corners.get(&edge.back().id()).copied()
+                    {
+                        if face == i {
+                            let filleted = self.find_edge(filleted);
+                            let arc = self.arc(&filleted, edge.back());
+                            let end = new_wire.back_vertex().unwrap().id();
+                            match arc.front().id() == end {
+                                true => new_wire.push_back(arc),
+                                false => new_wire.push_back(arc.inverse()),
+                            }
+                        }
+                    }
+                });
+                new_wire
+            })
+            .collect();
+        Face::new(wires, surface)
+    }
+
+    fn find_edge(&self, id: EdgeID) -> Edge {
+        self.faces
+            .iter()
+            .flat_map(|(wires, _, _)| wires.iter().flatten())
+            .find(|edge| edge.id() == id)
+            .unwrap()
+            .absolute_clone()
+    }
+
+    fn fillet_face(&mut self, edge: &Edge) -> Face {
+        let ball = self.balls[&edge.id()].clone();
+        let (v0, v1) = (edge.absolute_front(), edge.absolute_back());
+        let line0 = self.contact_line(edge, ball.faces[0]);
+        let line1 = self.contact_line(edge, ball.faces[1]);
+        let arc0 = self.arc(edge, v0);
+        let arc1 = self.arc(edge, v1);
+        let (p0, p1) = (v0.get_point(), v1.get_point());
+        // the miters are not the sections, and the blends run through the vertices.
+        let is_double = |v: &Vertex| matches!(self.corners[&v.id()], Corner::Double { .. });
+        let (double0, double1) = (is_double(v0), is_double(v1));
+        let surface = match &ball.profile {
+            Profile::Round(_) => {
+                // the arcs of the sections are interpolated linearly.
+                let params = ball.profile.params();
+                let len = params.len();
+                let sections: Vec<BSplineCurve<Vector4>> = params
+                    .iter()
+                    .enumerate()
+                    .map(|(k, t)| match k {
+                        0 if !double0 => arc0.get_curve().lift_up(),
+                        _ if k + 1 == len && !double1 => arc1.get_curve().lift_up(),
+                        _ => {
+                            let point = p0 + (p1 - p0) * *t;
+                            let (q0, q1) = (ball.contact(point, 0, *t), ball.contact(point, 1, *t));
+                            arc_curve(q0, q1, ball.inner(point, *t)).lift_up()
+                        }
+                    })
+                    .collect();
+                let knot_vecs = (sections[0].knot_vec().clone(), polyline_knot_vec(&params));
+                let control_points = (0..sections[0].control_points().len())
+                    .map(|i| sections.iter().map(|arc| *arc.control_point(i)).collect())
+                    .collect();
+                let surface = BSplineSurface::new(knot_vecs, control_points);
+                Surface::NURBSSurface(NURBSSurface::new(surface))
+            }
+            Profile::Chamfer(..) => {
+                let (p, q) = (arc0.front().get_point(), arc0.back().get_point());
+                Surface::Plane(Plane::new(p, q, line0.back().get_point()))
+            }
+        };
+        let center = ball.inner(p0.midpoint(p1), 0.5);
+        let surface = outward(surface, (0.5, 0.5), center);
+        let wire: Wire = vec![line0.inverse(), arc0, line1, arc1.inverse()].into();
+        Face::new(vec![wire], surface)
+    }
+
+    fn corner_face(&mut self, v: &Vertex) -> Face {
+        let mut arcs: Vec<Edge> = self
+            .balls
+            .keys()
+            .copied()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .filter_map(|id| {
+                let edge = self.find_edge(id);
+                let arc = match (edge.front() == v, edge.back() == v) {
+                    (true, _) => self.arc(&edge, v).inverse(),
+                    (_, true) => self.arc(&edge, v),
+                    _ => return None,
+                };
+                Some(arc)
+            })
+            .collect();
+        let mut wire = Wire::new();
+        while !arcs.is_empty() {
+            let idx = match wire.back_vertex() {
+                None => 0,
+                Some(back) => arcs.iter().position(|arc| arc.front() == back).unwrap(),
+            };
+            wire.push_back(arcs.remove(idx));
+        }
+        let edges = match self.corners[&v.id()] {
+            Corner::Triple(edges) => edges,
+            _ => unreachable!(),
+        };
+        let center = self.corner_center(v, edges);
+        let pole = wire[0].front().get_point();
+        let surface = match self.balls[&edges[0]].profile {
+            Profile::Round(_) => {
+                let curve = wire[0].oriented_curve();
+                let axis = (pole - center).normalize();
+                let revolution = RevolutedCurve::by_revolution(curve.inverse(), center, axis);
+                Surface::RevolutedCurve(Processor::new(revolution))
+            }
+            Profile::Chamfer(..) => {
+                let (p, q) = (wire[1].front().get_point(), wire[2].front().get_point());
+                Surface::Plane(Plane::new(pole, p, q))
+            }
+        };
+        let surface = outward(surface, (0.5, 0.5), center);
+        Face::new(vec![wire], surface)
+    }
+
+    fn shell(mut self) -> Shell {
+        let mut shell: Shell = (0..self.faces.len()).map(|i| self.planar_face(i)).collect();
+        let mut ids: Vec<EdgeID> = self.balls.keys().copied().collect();
+        ids.sort_by_key(|id| {
+            self.faces
+                .iter()
+                .flat_map(|(wires, _, _)| wires.iter().flatten())
+                .position(|edge| edge.id() == *id)
+        });
+        ids.iter().for_each(|id| {
+            let edge = self.find_edge(*id);
+            let face = self.fillet_face(&edge);
+            shell.push(face);
+        });
+        let mut triples: Vec<Vertex> = Vec::new();
+        ids.iter().for_each(|id| {
+            let edge = self.find_edge(*id);
+            [edge.front().clone(), edge.back().clone()]
+                .into_iter()
+                .for_each(|v| {
+                    let triple = matches!(self.corners[&v.id()], Corner::Triple(_));
+                    if triple && !triples.contains(&v) {
+                        triples.push(v);
+                    }
+                })
+        });
+        triples.iter().for_each(|v| {
+            let face = self.corner_face(v);
+            shell.push(face);
+        });
+        shell
+    }
+}
+
+/// the knot vector of the piecewise linear interpolation at `params`
+fn polyline_knot_vec(params: &[f64]) -> KnotVec {
+    let mut knots = vec![params[0]];
+    knots.extend(params);
+    knots.push(params[params.len() - 1]);
+    KnotVec::from(knots)
+}
+
+/// Rounds or chamfers the edges of `solid` by their profiles.
+pub(super) fn blend(solid: &Solid, edges: Vec<(EdgeID, Profile)>) -> Result<Solid> {
+    let filleted: HashMap<EdgeID, Profile> = edges.into_iter().collect();
+    let shells = solid
+        .boundaries()
+        .iter()
+        .map(|shell| Ok(ShellFillet::new(shell, &filleted)?.shell()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Solid::try_new(shells)?)
+}
+
+/// Rounds or bevels the corners of `wire`. See [`builder::fillet_wire`] and
+/// [`builder::chamfer_wire`] for the details.
+pub(super) fn blend_wire(wire: &Wire, corners: &[(VertexID, f64)], round: bool) -> Result<Wire> {
+    if !wire.is_continuous() {
+        return Err(unsupported(Reason::NotContinuousWire));
+    }
+    let edges: Vec<&Edge> = wire.edge_iter().collect();
+    let direction = |edge: &Edge| match edge.oriented_curve() {
+        Curve::Line(Line(p, q)) if !p.near(&q) => Ok((q - p).normalize()),
+        _ => Err(unsupported(Reason::NotLine)),
+    };
+    // the trimmed points of the previous edge and the next one, and the curve between them
+    let mut blends = HashMap::<VertexID, (Vertex, Vertex, Curve)>::default();
+    let mut setbacks = vec![(0.0, 0.0); edges.len()];
+    for (id, value) in corners {
+        if *value < TOLERANCE {
+            return Err(unsupported(Reason::NonPositiveSize));
+        }
+        let len = edges.len();
+        let i =
+            (0..len).find(|i| edges[*i].back().id() == *id && (wire.is_closed() || *i + 1 < len));
+        let (i, j) = match i {
+            Some(i) => (i, (i + 1) % len),
+            None => return Err(unsupported(Reason::NotWireCorner)),
+        };
+        let point = edges[i].back().get_point();
+        let (d0, d1) = (-direction(edges[i])?, direction(edges[j])?);
+        let half = d0.angle(d1) / 2.0;
+        if half.0.so_small() || (half.0 * 2.0).near(&std::f64::consts::PI) {
+            return Err(unsupported(Reason::FlatCorner));
+        }
+        let setback = match round {
+            true => value / half.tan(),
+            false => *value,
+        };
+        let (q0, q1) = (point + d0 * setback, point + d1 * setback);
+        let curve = match round {
+            true => {
+                let center = point + (d0 + d1).normalize() * (value / half.sin());
+                arc_curve(q0, q1, center)
+            }
+            false => Curve::Line(Line(q0, q1)),
+        };
+        setbacks[i].1 = setback;
+        setbacks[j].0 = setback;
+        blends.insert(*id, (Vertex::new(q0), Vertex::new(q1), curve));
+    }
+    let vanish = edges.iter().zip(&setbacks).any(|(edge, (s0, s1))| {
+        let length = edge.front().get_point().distance(edge.back().get_point());
+        s0 + s1 > length - TOLERANCE
+    });
+    if vanish {
+        return Err(unsupported(Reason::TooLarge));
+    }
+    let mut new_wire = Wire::new();
+    edges.iter().enumerate().for_each(|(i, edge)| {
+        if setbacks[i] == (0.0, 0.0) {
+            new_wire.push_back((*edge).clone());
+            return;
+        }
+        let front = match blends.get(&edge.front().id()) {
+            Some((_, v1, _)) if setbacks[i].0 > 0.0 => v1.clone(),
+            _ => edge.front().clone(),
+        };
+        let back = match blends.get(&edge.back().id()) {
+            Some((v0, _, _)) if setbacks[i].1 > 0.0 => v0.clone(),
+            _ => edge.back().clone(),
+        };
+        new_wire.push_back(builder::line(&front, &back));
+        if setbacks[i].1 > 0.0 {
+            let (v0, v1, curve) = &blends[&edge.back().id()];
+            new_wire.push_back(Edge::new(v0, v1, curve.clone()));
+        }
+    });
+    Ok(new_wire)
+}
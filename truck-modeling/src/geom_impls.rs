@@ -63,6 +63,113 @@ pub(super) fn circle_arc(
     curve
 }
 
+/// The directions of the angles `0` and `PI / 2` on the plane perpendicular to the normalized
+/// `axis`, rotated from the x- and y-axis by the rotation bringing the z-axis to `axis` in the
+/// same way as [`circle_arc`].
+pub(super) fn circle_frame(axis: Vector3) -> (Vector3, Vector3) {
+    let rotation = if !Tolerance::near(&(axis[2] * axis[2]), &1.0) {
+        let axis_axis = Vector3::new(-axis[1], axis[0], 0.0).normalize();
+        Matrix3::from_axis_angle(axis_axis, Rad(axis[2].acos()))
+    } else if axis[2] > 0.0 {
+        Matrix3::identity()
+    } else {
+        Matrix3::from_axis_angle(Vector3::unit_y(), Rad(PI))
+    };
+    let x = rotation * Vector3::unit_x();
+    (x, axis.cross(x))
+}
+
+/// The arc of the circle from `angle0` to `angle1`. See [`builder::arc_by_center`].
+pub(super) fn circle_arc_by_center(
+    center: Point3,
+    axis: Vector3,
+    radius: f64,
+    angle0: Rad<f64>,
+    angle1: Rad<f64>,
+) -> BSplineCurve<Vector4> {
+    let (x, y) = circle_frame(axis);
+    let angle = angle1 - angle0;
+    let division = if angle.0.abs() < PI { 1 } else { 2 };
+    let point = center + radius * (x * angle0.0.cos() + y * angle0.0.sin());
+    let mut curve = circle_arc(
+        point.to_homogeneous(),
+        center,
+        axis,
+        angle / division as f64,
+    );
+    (1..division).for_each(|i| {
+        let point = *curve.control_points().last().unwrap();
+        let mut piece = circle_arc(point, center, axis, angle / division as f64);
+        piece.knot_translate(i as f64);
+        curve = curve.concat(&piece);
+    });
+    curve.knot_normalize();
+    curve
+}
+
+/// The shorter arc of the ellipse from `point0` to `point1`. See [`builder::ellipse_arc`].
+pub(super) fn ellipse_arc(
+    point0: Point3,
+    point1: Point3,
+    center: Point3,
+    major_axis: Vector3,
+    ratio: f64,
+) -> BSplineCurve<Vector4> {
+    let semi_major = major_axis.magnitude();
+    assert!(
+        !semi_major.so_small() && ratio > 0.0,
+        "the axes of the ellipse must not be zero."
+    );
+    let semi_minor = semi_major * ratio;
+    let u_axis = major_axis / semi_major;
+    let (vec0, vec1) = (point0 - center, point1 - center);
+    let normal = match u_axis.cross(vec0).so_small() {
+        true => u_axis.cross(vec1),
+        false => u_axis.cross(vec0),
+    };
+    assert!(
+        !normal.so_small(),
+        "the plane of the ellipse is not determined by the vertices on the major axis."
+    );
+    let mut normal = normal.normalize();
+    let mut v_axis = normal.cross(u_axis);
+    let angle = |vec: Vector3, v_axis: Vector3, normal: Vector3| {
+        let (x, y) = (vec.dot(u_axis) / semi_major, vec.dot(v_axis) / semi_minor);
+        assert!(
+            vec.dot(normal).so_small() && f64::hypot(x, y).near(&1.0),
+            "the vertices must be on the ellipse."
+        );
+        f64::atan2(y, x)
+    };
+    let start = angle(vec0, v_axis, normal);
+    let mut sweep = (angle(vec1, v_axis, normal) - start).rem_euclid(2.0 * PI);
+    if sweep > PI + TOLERANCE {
+        normal = -normal;
+        v_axis = -v_axis;
+        sweep = 2.0 * PI - sweep;
+    }
+    let start = angle(vec0, v_axis, normal);
+    assert!(!sweep.so_small(), "the vertices must be different.");
+    let unit = Point3::new(start.cos(), start.sin(), 0.0);
+    let mut curve = circle_arc(
+        unit.to_homogeneous(),
+        Point3::origin(),
+        Vector3::unit_z(),
+        Rad(sweep),
+    );
+    let affine = Matrix4::from_cols(
+        (u_axis * semi_major).extend(0.0),
+        (v_axis * semi_minor).extend(0.0),
+        normal.extend(0.0),
+        center.to_homogeneous(),
+    );
+    curve.transform_control_points(|pt| *pt = affine * *pt);
+    let last = curve.control_points().len() - 1;
+    *curve.control_point_mut(0) = point0.to_homogeneous();
+    *curve.control_point_mut(last) = point1.to_homogeneous();
+    curve
+}
+
 fn closed_polyline_orientation(pts: &[Point3]) -> bool {
     pts.windows(2).fold(0.0, |sum, pt| {
         sum + (pt[1][0] + pt[0][0]) * (pt[1][1] - pt[0][1])
@@ -106,6 +213,101 @@ pub(super) fn attach_plane(mut pts: Vec<Point3>) -> Option<Plane> {
     Some(plane)
 }
 
+/// Reparametrizes the homogeneous curve so that the weights at the ends are `1.0`.
+///
+/// The parameter is transformed by the Möbius transformation fixing `0.0` and `1.0`,
+/// which is applied to each Bézier segment by scaling the control points.
+fn unit_end_weights(mut curve: BSplineCurve<Vector4>) -> BSplineCurve<Vector4> {
+    curve.knot_normalize();
+    let len = curve.control_points().len();
+    let w0 = curve.control_point(0)[3];
+    let ratio = curve.control_point(len - 1)[3] / w0;
+    curve.transform_control_points(|pt| *pt /= w0);
+    if ratio.near(&1.0) {
+        return curve;
+    }
+    let degree = curve.degree();
+    let lambda = ratio.powf(1.0 / degree as f64);
+    // the new parameter at `t`, and the denominator at the new parameter `s`
+    let param = |t: f64| lambda * t / (1.0 - t + lambda * t);
+    let denom = |s: f64| s + lambda * (1.0 - s);
+    let mut knots = vec![0.0; degree + 1];
+    let mut control_points = Vec::new();
+    curve
+        .bezier_decomposition()
+        .into_iter()
+        .enumerate()
+        .for_each(|(k, bezier)| {
+            let (t0, t1) = bezier.parameter_range();
+            let (s0, s1) = (param(t0), param(t1));
+            let (d0, d1) = (denom(s0), denom(s1));
+            let points = bezier.control_points().iter().enumerate().map(|(i, pt)| {
+                *pt * d0.powi((degree - i) as i32) * d1.powi(i as i32) / lambda.powi(degree as i32)
+            });
+            match k {
+                0 => control_points.extend(points),
+                _ => {
+                    knots.resize(knots.len() + degree, s0);
+                    control_points.extend(points.skip(1));
+                }
+            }
+        });
+    knots.resize(knots.len() + degree + 1, 1.0);
+    BSplineCurve::new(KnotVec::from(knots), control_points)
+}
+
+/// The bilinearly blended Coons surface. `curve0` and `curve2` are the boundaries at `v = 0`
+/// and `v = 1`, and `curve3` and `curve1` are the ones at `u = 0` and `u = 1`, respectively.
+///
+/// The curves are homogeneous, and all the weights at the corners are normalized to `1.0`,
+/// so that the boundaries of the surface are the curves exactly.
+pub(super) fn coons_surface(
+    curve0: BSplineCurve<Vector4>,
+    curve1: BSplineCurve<Vector4>,
+    curve2: BSplineCurve<Vector4>,
+    curve3: BSplineCurve<Vector4>,
+) -> BSplineSurface<Vector4> {
+    let [mut curve0, mut curve1, mut curve2, mut curve3] =
+        [curve0, curve1, curve2, curve3].map(unit_end_weights);
+    curve0.syncro_degree(&mut curve2);
+    curve0.syncro_knots(&mut curve2);
+    curve1.syncro_degree(&mut curve3);
+    curve1.syncro_knots(&mut curve3);
+    // The linear functions are reproduced by the Greville abscissae.
+    let greville = |curve: &BSplineCurve<Vector4>| -> Vec<f64> {
+        let degree = curve.degree();
+        (0..curve.control_points().len())
+            .map(|i| (1..=degree).map(|j| curve.knot(i + j)).sum::<f64>() / degree as f64)
+            .collect()
+    };
+    let (us, vs) = (greville(&curve0), greville(&curve1));
+    let n = us.len();
+    let p00 = *curve0.control_point(0);
+    let p10 = *curve0.control_point(n - 1);
+    let p01 = *curve2.control_point(0);
+    let p11 = *curve2.control_point(n - 1);
+    let control_points = us
+        .iter()
+        .enumerate()
+        .map(|(i, u)| {
+            vs.iter()
+                .enumerate()
+                .map(|(j, v)| {
+                    let ruled0 = curve0.control_point(i) * (1.0 - v) + curve2.control_point(i) * *v;
+                    let ruled1 = curve3.control_point(j) * (1.0 - u) + curve1.control_point(j) * *u;
+                    let bilinear = (p00 * (1.0 - u) + p10 * *u) * (1.0 - v)
+                        + (p01 * (1.0 - u) + p11 * *u) * *v;
+                    ruled0 + ruled1 - bilinear
+                })
+                .collect()
+        })
+        .collect();
+    BSplineSurface::new(
+        (curve0.knot_vec().clone(), curve1.knot_vec().clone()),
+        control_points,
+    )
+}
+
 #[cfg(test)]
 mod geom_impl_test {
     use super::*;
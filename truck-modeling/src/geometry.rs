@@ -10,6 +10,7 @@ pub use truck_polymesh::PolylineCurve;
 #[derive(
     Clone,
     Debug,
+    PartialEq,
     Serialize,
     Deserialize,
     From,
@@ -33,6 +34,7 @@ pub enum Leader {
 #[derive(
     Clone,
     Debug,
+    PartialEq,
     Serialize,
     Deserialize,
     From,
@@ -54,6 +56,9 @@ pub enum Curve {
     NURBSCurve(NURBSCurve<Vector4>),
     /// intersection curve
     IntersectionCurve(IntersectionCurve<Leader, Surface>),
+    /// user-defined curve, serialized by the hooks in [`custom`](crate::custom)
+    #[serde(with = "custom::curve_serde")]
+    Custom(DynCurve),
 }
 
 macro_rules! derive_curve_method {
@@ -63,6 +68,7 @@ macro_rules! derive_curve_method {
             Curve::BSplineCurve(got) => $method(got, $($ver), *),
             Curve::NURBSCurve(got) => $method(got, $($ver), *),
             Curve::IntersectionCurve(got) => $method(got, $($ver), *),
+            Curve::Custom(got) => $method(got, $($ver), *),
         }
     };
 }
@@ -74,6 +80,7 @@ macro_rules! derive_curve_self_method {
             Curve::BSplineCurve(got) => Curve::BSplineCurve($method(got, $($ver), *)),
             Curve::NURBSCurve(got) => Curve::NURBSCurve($method(got, $($ver), *)),
             Curve::IntersectionCurve(got) => Curve::IntersectionCurve($method(got, $($ver), *)),
+            Curve::Custom(got) => Curve::Custom($method(got, $($ver), *)),
         }
     };
 }
@@ -102,6 +109,49 @@ impl Transformed<Matrix4> for Curve {
     }
 }
 
+impl DeepSize for Leader {
+    fn heap_size(&self) -> usize {
+        match self {
+            Leader::Polyline(x) => x.heap_size(),
+            Leader::BSpline(x) => x.heap_size(),
+        }
+    }
+}
+
+impl Quantize for Leader {
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        match self {
+            Leader::Polyline(x) => {
+                key.push_tag(0);
+                x.quantize(tol, key);
+            }
+            Leader::BSpline(x) => {
+                key.push_tag(1);
+                x.quantize(tol, key);
+            }
+        }
+    }
+}
+
+impl DeepSize for Curve {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { derive_curve_method!(self, DeepSize::heap_size,) }
+}
+
+/// The variants are distinguished by the tags at the beginning of the keys.
+impl Quantize for Curve {
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        key.push_tag(match self {
+            Curve::Line(_) => 0,
+            Curve::BSplineCurve(_) => 1,
+            Curve::NURBSCurve(_) => 2,
+            Curve::IntersectionCurve(_) => 3,
+            Curve::Custom(_) => 4,
+        });
+        derive_curve_method!(self, Quantize::quantize, tol, key)
+    }
+}
+
 impl From<IntersectionCurve<PolylineCurve<Point3>, Surface>> for Curve {
     fn from(x: IntersectionCurve<PolylineCurve<Point3>, Surface>) -> Curve {
         Curve::IntersectionCurve(x.change_leader(Leader::Polyline))
@@ -125,6 +175,11 @@ impl Curve {
             Curve::IntersectionCurve(_) => {
                 unimplemented!("intersection curve cannot connect by homotopy")
             }
+            Curve::Custom(curve) => Curve::BSplineCurve(custom::curve_approximation(
+                &curve,
+                custom::APPROXIMATION_TOLERANCE,
+            ))
+            .lift_up(),
         }
     }
     /// Make the leaders of `IntersectionCurve`s B-spline curves.
@@ -153,11 +208,13 @@ impl Curve {
 #[derive(
     Clone,
     Debug,
+    PartialEq,
     Serialize,
     Deserialize,
     From,
     TryInto,
     ParametricSurface,
+    BoundedSurface,
     ParameterDivision2D,
     Invertible,
     SearchParameterD2,
@@ -171,6 +228,9 @@ pub enum Surface {
     NURBSSurface(NURBSSurface<Vector4>),
     /// revoluted curve
     RevolutedCurve(Processor<RevolutedCurve<Curve>, Matrix4>),
+    /// user-defined surface, serialized by the hooks in [`custom`](crate::custom)
+    #[serde(with = "custom::surface_serde")]
+    Custom(DynSurface),
 }
 
 macro_rules! derive_surface_method {
@@ -180,6 +240,7 @@ macro_rules! derive_surface_method {
             Self::BSplineSurface(got) => $method(got, $($ver), *),
             Self::NURBSSurface(got) => $method(got, $($ver), *),
             Self::RevolutedCurve(got) => $method(got, $($ver), *),
+            Self::Custom(got) => $method(got, $($ver), *),
         }
     };
 }
@@ -191,6 +252,7 @@ macro_rules! derive_surface_self_method {
             Self::BSplineSurface(got) => Self::BSplineSurface($method(got, $($ver), *)),
             Self::NURBSSurface(got) => Self::NURBSSurface($method(got, $($ver), *)),
             Self::RevolutedCurve(got) => Self::RevolutedCurve($method(got, $($ver), *)),
+            Self::Custom(got) => Self::Custom($method(got, $($ver), *)),
         }
     };
 }
@@ -211,6 +273,25 @@ impl Transformed<Matrix4> for Surface {
     }
 }
 
+impl DeepSize for Surface {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { derive_surface_method!(self, DeepSize::heap_size,) }
+}
+
+/// The variants are distinguished by the tags at the beginning of the keys.
+impl Quantize for Surface {
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) {
+        key.push_tag(match self {
+            Surface::Plane(_) => 0,
+            Surface::BSplineSurface(_) => 1,
+            Surface::NURBSSurface(_) => 2,
+            Surface::RevolutedCurve(_) => 3,
+            Surface::Custom(_) => 4,
+        });
+        derive_surface_method!(self, Quantize::quantize, tol, key)
+    }
+}
+
 impl IncludeCurve<Curve> for Surface {
     #[inline(always)]
     fn include(&self, curve: &Curve) -> bool {
@@ -220,18 +301,21 @@ impl IncludeCurve<Curve> for Surface {
                 Curve::BSplineCurve(curve) => surface.include(curve),
                 Curve::NURBSCurve(curve) => surface.include(curve),
                 Curve::IntersectionCurve(_) => unimplemented!(),
+                Curve::Custom(_) => custom::include_by_sampling(self, curve),
             },
             Surface::NURBSSurface(surface) => match curve {
                 Curve::Line(curve) => surface.include(&curve.to_bspline()),
                 Curve::BSplineCurve(curve) => surface.include(curve),
                 Curve::NURBSCurve(curve) => surface.include(curve),
                 Curve::IntersectionCurve(_) => unimplemented!(),
+                Curve::Custom(_) => custom::include_by_sampling(self, curve),
             },
             Surface::Plane(surface) => match curve {
                 Curve::Line(curve) => surface.include(&curve.to_bspline()),
                 Curve::BSplineCurve(curve) => surface.include(curve),
                 Curve::NURBSCurve(curve) => surface.include(curve),
                 Curve::IntersectionCurve(_) => unimplemented!(),
+                Curve::Custom(_) => custom::include_by_sampling(self, curve),
             },
             Surface::RevolutedCurve(surface) => match surface.entity_curve() {
                 Curve::Line(curve) => self.include(&Curve::BSplineCurve(curve.to_bspline())),
@@ -246,6 +330,7 @@ impl IncludeCurve<Curve> for Surface {
                         Curve::BSplineCurve(curve) => surface.include(curve),
                         Curve::NURBSCurve(curve) => surface.include(curve),
                         Curve::IntersectionCurve(_) => unimplemented!(),
+                        Curve::Custom(_) => custom::include_by_sampling(self, curve),
                     }
                 }
                 Curve::NURBSCurve(entity_curve) => {
@@ -259,10 +344,13 @@ impl IncludeCurve<Curve> for Surface {
                         Curve::BSplineCurve(curve) => surface.include(curve),
                         Curve::NURBSCurve(curve) => surface.include(curve),
                         Curve::IntersectionCurve(_) => unimplemented!(),
+                        Curve::Custom(_) => custom::include_by_sampling(self, curve),
                     }
                 }
                 Curve::IntersectionCurve(_) => unimplemented!(),
+                Curve::Custom(_) => custom::include_by_sampling(self, curve),
             },
+            Surface::Custom(_) => custom::include_by_sampling(self, curve),
         }
     }
 }
@@ -291,6 +379,7 @@ impl SearchNearestParameter<D2> for Surface {
                 };
                 algo::surface::search_nearest_parameter(rotted, point, hint, trials)
             }
+            Surface::Custom(surface) => surface.search_nearest_parameter(point, hint, trials),
         }
     }
 }
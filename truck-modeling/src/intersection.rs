@@ -0,0 +1,741 @@
+use crate::analytic::{perpendicular, AnalyticSurface};
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::f64::consts::PI;
+#[cfg(feature = "interval")]
+use truck_geometry::interval;
+
+const GRID_DIVISION: usize = 32;
+
+/// The kinds of analytic surfaces, the keys for dispatching intersection routines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SurfaceKind {
+    /// plane
+    Plane,
+    /// cylinder
+    Cylinder,
+    /// sphere
+    Sphere,
+    /// cone
+    Cone,
+    /// torus
+    Torus,
+}
+
+impl AnalyticSurface {
+    /// Returns the kind of the surface.
+    #[inline(always)]
+    pub fn kind(&self) -> SurfaceKind {
+        match self {
+            AnalyticSurface::Plane { .. } => SurfaceKind::Plane,
+            AnalyticSurface::Cylinder { .. } => SurfaceKind::Cylinder,
+            AnalyticSurface::Sphere { .. } => SurfaceKind::Sphere,
+            AnalyticSurface::Cone { .. } => SurfaceKind::Cone,
+            AnalyticSurface::Torus { .. } => SurfaceKind::Torus,
+        }
+    }
+}
+
+/// Unbounded curves given by the analytic intersection routines.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnalyticCurve {
+    /// line
+    Line {
+        /// a point on the line
+        origin: Point3,
+        /// the unit direction of the line
+        direction: Vector3,
+    },
+    /// circle
+    Circle {
+        /// the center
+        center: Point3,
+        /// the unit normal of the plane including the circle
+        axis: Vector3,
+        /// the radius
+        radius: f64,
+    },
+    /// ellipse
+    Ellipse {
+        /// the center
+        center: Point3,
+        /// the vector from the center to the farthest point
+        major_axis: Vector3,
+        /// the vector from the center to the nearest point, perpendicular to `major_axis`
+        minor_axis: Vector3,
+    },
+}
+
+impl AnalyticCurve {
+    /// Returns the point at the parameter `t`.
+    ///
+    /// The parameter of a line is the distance from `origin`, and the one of a circle or an ellipse is the angle.
+    pub fn subs(&self, t: f64) -> Point3 {
+        match *self {
+            AnalyticCurve::Line { origin, direction } => origin + t * direction,
+            AnalyticCurve::Circle {
+                center,
+                axis,
+                radius,
+            } => {
+                let e = perpendicular(axis);
+                center + radius * (f64::cos(t) * e + f64::sin(t) * axis.cross(e))
+            }
+            AnalyticCurve::Ellipse {
+                center,
+                major_axis,
+                minor_axis,
+            } => center + f64::cos(t) * major_axis + f64::sin(t) * minor_axis,
+        }
+    }
+
+    /// Creates the bounded `Curve` on the parameter range `range`.
+    ///
+    /// Circles and ellipses are converted to exact NURBS curves.
+    pub fn to_curve(&self, (t0, t1): (f64, f64)) -> Curve {
+        let (center, u, v) = match *self {
+            AnalyticCurve::Line { .. } => return Curve::Line(Line(self.subs(t0), self.subs(t1))),
+            AnalyticCurve::Circle {
+                center,
+                axis,
+                radius,
+            } => {
+                let e = perpendicular(axis);
+                (center, radius * e, radius * axis.cross(e))
+            }
+            AnalyticCurve::Ellipse {
+                center,
+                major_axis,
+                minor_axis,
+            } => (center, major_axis, minor_axis),
+        };
+        // the arc of the unit circle mapped by the affine transformation
+        let division = usize::max(f64::ceil((t1 - t0).abs() / (PI / 2.0)) as usize, 1);
+        let angle = (t1 - t0) / division as f64;
+        let arc = (0..division)
+            .map(|i| {
+                let t = t0 + angle * i as f64;
+                let mut arc = geom_impls::circle_arc(
+                    Vector4::new(f64::cos(t), f64::sin(t), 0.0, 1.0),
+                    Point3::origin(),
+                    Vector3::unit_z(),
+                    Rad(angle),
+                );
+                arc.knot_translate(i as f64);
+                NURBSCurve::new(arc)
+            })
+            .reduce(|arc0, arc1| arc0.try_concat(&arc1).unwrap())
+            .unwrap();
+        let w = u.cross(v).normalize();
+        let mat = Matrix4::from_cols(
+            u.extend(0.0),
+            v.extend(0.0),
+            w.extend(0.0),
+            center.to_homogeneous(),
+        );
+        Curve::NURBSCurve(arc.transformed(mat))
+    }
+}
+
+/// The result of the intersection of two surfaces.
+#[derive(Clone, Debug)]
+pub enum SurfaceIntersection {
+    /// The two surfaces coincide.
+    Coincident,
+    /// The intersection consists of the analytic curves. The empty vector means no intersection.
+    Analytic(Vec<AnalyticCurve>),
+    /// The intersection curves traced numerically.
+    Numerical(Vec<Curve>),
+}
+
+/// The intersection routine specialized for a pair of analytic surfaces.
+/// Returns `None` if the routine does not treat the configuration.
+pub type AnalyticRoutine =
+    fn(&AnalyticSurface, &AnalyticSurface, f64) -> Option<SurfaceIntersection>;
+
+/// The intersection routine for the surfaces without any specialized routines.
+pub type FallbackRoutine = fn(&Surface, &Surface, f64) -> Option<SurfaceIntersection>;
+
+/// Double-dispatch of the intersection of surfaces.
+///
+/// Both surfaces are recognized as analytic surfaces, and the routine registered for the pair of
+/// their kinds is called. If no routine treats the pair, the fallback routine is called.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::analytic::AnalyticSurface;
+/// use truck_modeling::intersection::*;
+///
+/// // the intersection of a plane and a sphere, which is not registered by default
+/// fn plane_sphere(
+///     plane: &AnalyticSurface,
+///     sphere: &AnalyticSurface,
+///     tol: f64,
+/// ) -> Option<SurfaceIntersection> {
+///     match (*plane, *sphere) {
+///         (
+///             AnalyticSurface::Plane { origin, normal },
+///             AnalyticSurface::Sphere { center, radius },
+///         ) => {
+///             let h = (center - origin).dot(normal);
+///             let curves = match h.abs() < radius - tol {
+///                 true => vec![AnalyticCurve::Circle {
+///                     center: center - h * normal,
+///                     axis: normal,
+///                     radius: f64::sqrt(radius * radius - h * h),
+///                 }],
+///                 false => Vec::new(),
+///             };
+///             Some(SurfaceIntersection::Analytic(curves))
+///         }
+///         _ => None,
+///     }
+/// }
+///
+/// let mut dispatcher = IntersectionDispatcher::default();
+/// dispatcher.register(SurfaceKind::Plane, SurfaceKind::Sphere, plane_sphere);
+///
+/// let v = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+/// let wire = builder::rsweep(&v, Point3::origin(), Vector3::unit_y(), Rad(std::f64::consts::PI));
+/// let shell = builder::rsweep(&wire, Point3::origin(), Vector3::unit_z(), Rad(1.0));
+/// let sphere = shell[0].get_surface();
+/// let plane = Surface::Plane(Plane::new(
+///     Point3::new(0.0, 0.0, 0.6),
+///     Point3::new(1.0, 0.0, 0.6),
+///     Point3::new(0.0, 1.0, 0.6),
+/// ));
+///
+/// // the registered routine is also used for the pair in the reversed order.
+/// match dispatcher.intersect(&sphere, &plane, 1.0e-6).unwrap() {
+///     SurfaceIntersection::Analytic(curves) => match curves[..] {
+///         [AnalyticCurve::Circle { center, radius, .. }] => {
+///             assert_near!(center, Point3::new(0.0, 0.0, 0.6));
+///             assert_near!(radius, 0.8);
+///         }
+///         _ => panic!("{curves:?}"),
+///     },
+///     other => panic!("{other:?}"),
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntersectionDispatcher {
+    routines: HashMap<(SurfaceKind, SurfaceKind), AnalyticRoutine>,
+    fallback: Option<FallbackRoutine>,
+}
+
+impl Default for IntersectionDispatcher {
+    /// The dispatcher with the routines for the pairs of planes and cylinders,
+    /// and the numerical fallback [`numerical_intersection`].
+    fn default() -> Self {
+        let mut res = Self::empty();
+        res.register(SurfaceKind::Plane, SurfaceKind::Plane, plane_plane);
+        res.register(SurfaceKind::Plane, SurfaceKind::Cylinder, plane_cylinder);
+        res.register(
+            SurfaceKind::Cylinder,
+            SurfaceKind::Cylinder,
+            cylinder_cylinder,
+        );
+        res.fallback = Some(numerical_intersection);
+        res
+    }
+}
+
+impl IntersectionDispatcher {
+    /// The dispatcher without any routines.
+    #[inline(always)]
+    pub fn empty() -> Self {
+        Self {
+            routines: HashMap::default(),
+            fallback: None,
+        }
+    }
+
+    /// Registers the routine for the pair of kinds, and returns the replaced routine.
+    ///
+    /// The first argument of `routine` is the surface of `kind0`, and the second is of `kind1`.
+    /// The routine is also used for the pair in the reversed order, unless another routine is registered for it.
+    #[inline(always)]
+    pub fn register(
+        &mut self,
+        kind0: SurfaceKind,
+        kind1: SurfaceKind,
+        routine: AnalyticRoutine,
+    ) -> Option<AnalyticRoutine> {
+        self.routines.insert((kind0, kind1), routine)
+    }
+
+    /// Sets the fallback routine, and returns the replaced one.
+    #[inline(always)]
+    pub fn set_fallback(&mut self, fallback: Option<FallbackRoutine>) -> Option<FallbackRoutine> {
+        std::mem::replace(&mut self.fallback, fallback)
+    }
+
+    /// Computes the intersection of `surface0` and `surface1`.
+    ///
+    /// Returns `None` if neither the specialized routines nor the fallback treats the surfaces.
+    pub fn intersect(
+        &self,
+        surface0: &Surface,
+        surface1: &Surface,
+        tol: f64,
+    ) -> Option<SurfaceIntersection> {
+        let analytic = surface0
+            .recognize_analytic(tol)
+            .zip(surface1.recognize_analytic(tol));
+        let specialized = analytic.and_then(|(analytic0, analytic1)| {
+            let (kind0, kind1) = (analytic0.kind(), analytic1.kind());
+            match self.routines.get(&(kind0, kind1)) {
+                Some(routine) => routine(&analytic0, &analytic1, tol),
+                None => self.routines.get(&(kind1, kind0))?(&analytic1, &analytic0, tol),
+            }
+        });
+        specialized.or_else(|| self.fallback?(surface0, surface1, tol))
+    }
+}
+
+impl Surface {
+    /// Computes the intersection of `self` and `other` by [`IntersectionDispatcher::default`].
+    /// # Examples
+    /// ```
+    /// use truck_modeling::*;
+    /// use truck_modeling::intersection::*;
+    ///
+    /// // a cylinder with radius 1 along the z-axis
+    /// let v = builder::vertex(Point3::new(1.0, 0.0, -1.0));
+    /// let e = builder::tsweep(&v, Vector3::new(0.0, 0.0, 2.0));
+    /// let shell = builder::rsweep(&e, Point3::origin(), Vector3::unit_z(), Rad(1.0));
+    /// let cylinder = shell[0].get_surface();
+    ///
+    /// // the slanted plane cuts the cylinder along an ellipse
+    /// let plane = Surface::Plane(Plane::new(
+    ///     Point3::origin(),
+    ///     Point3::new(1.0, 0.0, 1.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ));
+    /// let curves = match cylinder.intersect(&plane, 1.0e-6).unwrap() {
+    ///     SurfaceIntersection::Analytic(curves) => curves,
+    ///     other => panic!("{other:?}"),
+    /// };
+    /// match curves[..] {
+    ///     [AnalyticCurve::Ellipse { center, major_axis, minor_axis }] => {
+    ///         assert_near!(center, Point3::origin());
+    ///         assert_near!(major_axis.magnitude(), f64::sqrt(2.0));
+    ///         assert_near!(minor_axis.magnitude(), 1.0);
+    ///     }
+    ///     _ => panic!("{curves:?}"),
+    /// }
+    ///
+    /// // the exact NURBS representation of the ellipse
+    /// let curve = curves[0].to_curve((0.0, 2.0 * std::f64::consts::PI));
+    /// let (t0, t1) = curve.parameter_range();
+    /// (0..=10).for_each(|i| {
+    ///     let p = curve.subs(t0 + (t1 - t0) * i as f64 / 10.0);
+    ///     assert_near!(p.x * p.x + p.y * p.y, 1.0);
+    ///     assert_near!(p.z, p.x);
+    /// });
+    ///
+    /// // a B-spline bump intersected with a plane falls back to the numerical tracing
+    /// let bump = Surface::BSplineSurface(BSplineSurface::new(
+    ///     (KnotVec::bezier_knot(2), KnotVec::bezier_knot(2)),
+    ///     vec![
+    ///         vec![Point3::new(-1.0, -1.0, 0.0), Point3::new(-1.0, 0.0, 0.0), Point3::new(-1.0, 1.0, 0.0)],
+    ///         vec![Point3::new(0.0, -1.0, 0.0), Point3::new(0.0, 0.0, 2.0), Point3::new(0.0, 1.0, 0.0)],
+    ///         vec![Point3::new(1.0, -1.0, 0.0), Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+    ///     ],
+    /// ));
+    /// let plane = Surface::Plane(Plane::new(
+    ///     Point3::new(0.0, 0.0, 0.2),
+    ///     Point3::new(1.0, 0.0, 0.2),
+    ///     Point3::new(0.0, 1.0, 0.2),
+    /// ));
+    /// match bump.intersect(&plane, 1.0e-6).unwrap() {
+    ///     SurfaceIntersection::Numerical(curves) => {
+    ///         assert_eq!(curves.len(), 1);
+    ///         let (t0, t1) = curves[0].parameter_range();
+    ///         (0..=10).for_each(|i| {
+    ///             let p = curves[0].subs(t0 + (t1 - t0) * i as f64 / 10.0);
+    ///             assert_near!(p.z, 0.2);
+    ///             let (u, v) = bump.search_nearest_parameter(p, None, 100).unwrap();
+    ///             assert_near!(bump.subs(u, v), p);
+    ///         });
+    ///     }
+    ///     other => panic!("{other:?}"),
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn intersect(&self, other: &Surface, tol: f64) -> Option<SurfaceIntersection> {
+        IntersectionDispatcher::default().intersect(self, other, tol)
+    }
+}
+
+fn plane_plane(
+    surface0: &AnalyticSurface,
+    surface1: &AnalyticSurface,
+    tol: f64,
+) -> Option<SurfaceIntersection> {
+    let (o0, n0, o1, n1) = match (*surface0, *surface1) {
+        (
+            AnalyticSurface::Plane {
+                origin: o0,
+                normal: n0,
+            },
+            AnalyticSurface::Plane {
+                origin: o1,
+                normal: n1,
+            },
+        ) => (o0, n0, o1, n1),
+        _ => return None,
+    };
+    let direction = n0.cross(n1);
+    if direction.so_small() {
+        return match (o1 - o0).dot(n0).abs() < tol {
+            true => Some(SurfaceIntersection::Coincident),
+            false => Some(SurfaceIntersection::Analytic(Vec::new())),
+        };
+    }
+    let (h0, h1, c) = (o0.to_vec().dot(n0), o1.to_vec().dot(n1), n0.dot(n1));
+    let origin = Point3::from_vec(((h0 - h1 * c) * n0 + (h1 - h0 * c) * n1) / (1.0 - c * c));
+    let line = AnalyticCurve::Line {
+        origin,
+        direction: direction.normalize(),
+    };
+    Some(SurfaceIntersection::Analytic(vec![line]))
+}
+
+fn plane_cylinder(
+    surface0: &AnalyticSurface,
+    surface1: &AnalyticSurface,
+    tol: f64,
+) -> Option<SurfaceIntersection> {
+    let (o, n, c, a, r) = match (*surface0, *surface1) {
+        (
+            AnalyticSurface::Plane { origin, normal },
+            AnalyticSurface::Cylinder {
+                origin: c,
+                axis,
+                radius,
+            },
+        ) => (origin, normal, c, axis, radius),
+        _ => return None,
+    };
+    let cos = n.dot(a);
+    if cos.so_small() {
+        // the axis is parallel to the plane
+        let d = (c - o).dot(n);
+        let foot = c - d * n;
+        let lines = if d.abs() > r + tol {
+            Vec::new()
+        } else if (d.abs() - r).abs() < tol {
+            vec![AnalyticCurve::Line {
+                origin: foot,
+                direction: a,
+            }]
+        } else {
+            let w = f64::sqrt(r * r - d * d) * a.cross(n);
+            vec![
+                AnalyticCurve::Line {
+                    origin: foot + w,
+                    direction: a,
+                },
+                AnalyticCurve::Line {
+                    origin: foot - w,
+                    direction: a,
+                },
+            ]
+        };
+        return Some(SurfaceIntersection::Analytic(lines));
+    }
+    let center = c + (o - c).dot(n) / cos * a;
+    let curve = if (cos.abs() - 1.0).so_small() {
+        AnalyticCurve::Circle {
+            center,
+            axis: a,
+            radius: r,
+        }
+    } else {
+        let minor = a.cross(n).normalize();
+        let major = n.cross(minor);
+        AnalyticCurve::Ellipse {
+            center,
+            major_axis: r / cos.abs() * major,
+            minor_axis: r * minor,
+        }
+    };
+    Some(SurfaceIntersection::Analytic(vec![curve]))
+}
+
+fn cylinder_cylinder(
+    surface0: &AnalyticSurface,
+    surface1: &AnalyticSurface,
+    tol: f64,
+) -> Option<SurfaceIntersection> {
+    let (c0, a0, r0, c1, a1, r1) = match (*surface0, *surface1) {
+        (
+            AnalyticSurface::Cylinder {
+                origin: c0,
+                axis: a0,
+                radius: r0,
+            },
+            AnalyticSurface::Cylinder {
+                origin: c1,
+                axis: a1,
+                radius: r1,
+            },
+        ) => (c0, a0, r0, c1, a1, r1),
+        _ => return None,
+    };
+    // the intersections of the cylinders with skew axes are not planar in general.
+    if !a0.cross(a1).so_small() {
+        return None;
+    }
+    let vec = (c1 - c0) - (c1 - c0).dot(a0) * a0;
+    let d = vec.magnitude();
+    if d < tol {
+        return match (r0 - r1).abs() < tol {
+            true => Some(SurfaceIntersection::Coincident),
+            false => Some(SurfaceIntersection::Analytic(Vec::new())),
+        };
+    }
+    if d > r0 + r1 + tol || d < (r0 - r1).abs() - tol {
+        return Some(SurfaceIntersection::Analytic(Vec::new()));
+    }
+    // the intersection of the circles on the section
+    let u = vec / d;
+    let x = (d * d + r0 * r0 - r1 * r1) / (2.0 * d);
+    let h2 = r0 * r0 - x * x;
+    let line = |origin: Point3| AnalyticCurve::Line {
+        origin,
+        direction: a0,
+    };
+    let lines = if h2 < tol * tol {
+        vec![line(c0 + x * u)]
+    } else {
+        let w = f64::sqrt(h2) * a0.cross(u);
+        vec![line(c0 + x * u + w), line(c0 + x * u - w)]
+    };
+    Some(SurfaceIntersection::Analytic(lines))
+}
+
+fn bounded_range(surface: &Surface) -> Option<((f64, f64), (f64, f64))> {
+    match surface {
+        Surface::Plane(_) => None,
+        Surface::BSplineSurface(surface) => Some(surface.parameter_range()),
+        Surface::NURBSSurface(surface) => Some(surface.parameter_range()),
+        Surface::RevolutedCurve(surface) => Some(surface.parameter_range()),
+        Surface::Custom(surface) => Some(surface.parameter_range()),
+    }
+}
+
+/// horizontal or vertical, and the index of the grid point at the start.
+type GridEdge = (bool, usize, usize);
+
+/// Traces the intersection curves numerically.
+///
+/// The zero set of the signed distance to one surface is traced by marching squares
+/// on the parameter domain of the other bounded surface, and the traced polylines
+/// are refined onto both surfaces as the leaders of `IntersectionCurve`s.
+/// Returns `None` if both surfaces are planes. With the feature `interval`, returns `None` also if
+/// no curves are traced but the surfaces are not certified to be disjoint, e.g. near the tangencies.
+pub fn numerical_intersection(
+    surface0: &Surface,
+    surface1: &Surface,
+    tol: f64,
+) -> Option<SurfaceIntersection> {
+    let (surface0, surface1) = match bounded_range(surface0) {
+        Some(_) => (surface0, surface1),
+        None => (surface1, surface0),
+    };
+    let ((u0, u1), (v0, v1)) = bounded_range(surface0)?;
+    const N: usize = GRID_DIVISION;
+    let uv = |i: usize, j: usize| {
+        Point2::new(
+            u0 + (u1 - u0) * i as f64 / N as f64,
+            v0 + (v1 - v0) * j as f64 / N as f64,
+        )
+    };
+    // the points and the pairs of the signed and unsigned distances
+    let mut grid = vec![vec![(Point3::origin(), None); N + 1]; N + 1];
+    let mut hint = None;
+    for (i, row) in grid.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            let p = uv(i, j);
+            let pt = surface0.subs(p.x, p.y);
+            hint = surface1.search_nearest_parameter(pt, hint, 100);
+            let value = hint.map(|(s, t)| {
+                let q = surface1.subs(s, t);
+                ((pt - q).dot(surface1.normal(s, t)), pt.distance(q))
+            });
+            *entry = (pt, value);
+        }
+    }
+    let crossing = |(i0, j0): (usize, usize), (i1, j1): (usize, usize)| {
+        let (pt0, value0) = grid[i0][j0];
+        let (pt1, value1) = grid[i1][j1];
+        let ((f0, d0), (f1, d1)) = value0.zip(value1)?;
+        // the foot points on the boundary may change the sign without crossing.
+        let valid = (f0 < 0.0) != (f1 < 0.0) && d0 + d1 < 2.0 * pt0.distance(pt1) + tol;
+        let t = f0 / (f0 - f1);
+        valid.then(|| uv(i0, j0) + (uv(i1, j1) - uv(i0, j0)) * t)
+    };
+    let mut crossings = HashMap::<GridEdge, Point2>::default();
+    (0..=N).for_each(|i| {
+        (0..=N).for_each(|j| {
+            if i < N {
+                if let Some(p) = crossing((i, j), (i + 1, j)) {
+                    crossings.insert((true, i, j), p);
+                }
+            }
+            if j < N {
+                if let Some(p) = crossing((i, j), (i, j + 1)) {
+                    crossings.insert((false, i, j), p);
+                }
+            }
+        })
+    });
+    let mut adjacency = HashMap::<GridEdge, Vec<GridEdge>>::default();
+    let mut connect = |e0: GridEdge, e1: GridEdge| {
+        adjacency.entry(e0).or_default().push(e1);
+        adjacency.entry(e1).or_default().push(e0);
+    };
+    (0..N).for_each(|i| {
+        (0..N).for_each(|j| {
+            let edges = [
+                (true, i, j),
+                (false, i + 1, j),
+                (true, i, j + 1),
+                (false, i, j),
+            ];
+            let edges: Vec<GridEdge> = edges
+                .into_iter()
+                .filter(|e| crossings.contains_key(e))
+                .collect();
+            match edges.len() {
+                2 => connect(edges[0], edges[1]),
+                4 => {
+                    connect(edges[0], edges[1]);
+                    connect(edges[2], edges[3]);
+                }
+                _ => {}
+            }
+        })
+    });
+    // open chains are traced from their ends, and then closed loops.
+    let mut starts: Vec<GridEdge> = adjacency.keys().copied().collect();
+    starts.sort_by_key(|e| (adjacency[e].len() != 1, *e));
+    let mut visited = rustc_hash::FxHashSet::default();
+    let mut chains = Vec::new();
+    starts.into_iter().for_each(|start| {
+        if !visited.insert(start) {
+            return;
+        }
+        let mut chain = vec![start];
+        let mut current = start;
+        while let Some(next) = adjacency[&current].iter().find(|e| !visited.contains(*e)) {
+            visited.insert(*next);
+            chain.push(*next);
+            current = *next;
+        }
+        if chain.len() > 2 && adjacency[&current].contains(&start) {
+            chain.push(start);
+        }
+        chains.push(chain);
+    });
+    let curves: Vec<Curve> = chains
+        .into_iter()
+        .filter_map(|chain| {
+            let params: Vec<Point2> = chain.iter().map(|e| crossings[e]).collect();
+            let points: Vec<Point3> = params.iter().map(|p| surface0.subs(p.x, p.y)).collect();
+            let len = points.len();
+            let polyline: Vec<Point3> = (0..len)
+                .filter_map(|k| {
+                    let dir = points[usize::min(k + 1, len - 1)] - points[k.saturating_sub(1)];
+                    let hint0 = Some((params[k].x, params[k].y));
+                    let (pt, _, _) = double_projection(
+                        surface0,
+                        hint0,
+                        surface1,
+                        None,
+                        points[k],
+                        dir.normalize(),
+                        100,
+                    )?;
+                    Some(pt)
+                })
+                .collect();
+            if polyline.len() < 2 {
+                return None;
+            }
+            let curve = IntersectionCurve::new_unchecked(
+                Box::new(surface0.clone()),
+                Box::new(surface1.clone()),
+                Leader::Polyline(PolylineCurve(polyline)),
+                tol,
+            );
+            Some(Curve::IntersectionCurve(curve))
+        })
+        .collect();
+    // the tracing misses the tangencies where the signed distance does not change its sign.
+    #[cfg(feature = "interval")]
+    if curves.is_empty() && !certified_disjoint(surface0, surface1) {
+        return None;
+    }
+    Some(SurfaceIntersection::Numerical(curves))
+}
+
+#[cfg(feature = "interval")]
+const CERTIFICATION_DEPTH: usize = 6;
+
+#[cfg(feature = "interval")]
+impl interval::CurveEnclosure for Curve {
+    /// Intersection curves and user-defined curves cannot be enclosed.
+    fn enclosure(&self, t: interval::Interval) -> Option<BoundingBox<Point3>> {
+        match self {
+            Curve::Line(line) => line.enclosure(t),
+            Curve::BSplineCurve(curve) => curve.enclosure(t),
+            Curve::NURBSCurve(curve) => curve.enclosure(t),
+            Curve::IntersectionCurve(_) | Curve::Custom(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "interval")]
+impl interval::SurfaceEnclosure for Surface {
+    /// User-defined surfaces cannot be enclosed.
+    fn enclosure(
+        &self,
+        u: interval::Interval,
+        v: interval::Interval,
+    ) -> Option<BoundingBox<Point3>> {
+        match self {
+            Surface::Plane(plane) => plane.enclosure(u, v),
+            Surface::BSplineSurface(surface) => surface.enclosure(u, v),
+            Surface::NURBSSurface(surface) => surface.enclosure(u, v),
+            Surface::RevolutedCurve(surface) => surface.enclosure(u, v),
+            Surface::Custom(_) => None,
+        }
+    }
+}
+
+/// Certifies that the bounded `surface0` and `surface1` have no common points.
+#[cfg(feature = "interval")]
+fn certified_disjoint(surface0: &Surface, surface1: &Surface) -> bool {
+    use interval::*;
+    let range = |((u0, u1), (v0, v1))| (Interval::new(u0, u1), Interval::new(v0, v1));
+    let range0 = match bounded_range(surface0) {
+        Some(range0) => range(range0),
+        None => return false,
+    };
+    match (surface1, bounded_range(surface1)) {
+        (Surface::Plane(plane), _) => {
+            separated_from_plane(surface0, range0, plane, CERTIFICATION_DEPTH)
+        }
+        (_, Some(range1)) => surfaces_disjoint(
+            surface0,
+            range0,
+            surface1,
+            range(range1),
+            CERTIFICATION_DEPTH,
+        ),
+        _ => false,
+    }
+}
@@ -17,7 +17,9 @@
 
 /// re-export `truck_base`.
 pub mod base {
-    pub use truck_base::{assert_near, assert_near2, bounding_box::*, cgmath64::*, tolerance::*};
+    pub use truck_base::{
+        assert_near, assert_near2, bounding_box::*, cgmath64::*, deep_size::DeepSize, tolerance::*,
+    };
     pub use truck_geotrait::*;
 }
 pub use base::*;
@@ -150,13 +152,39 @@ pub use topo_traits::*;
 /// `Result` with crate's errors.
 pub type Result<T> = std::result::Result<T, errors::Error>;
 
+/// recognition and conversion of analytic surfaces
+pub mod analytic;
+/// lightweight annotations attached to solids
+pub mod annotation;
+/// kinematic assemblies: components connected by revolute and prismatic joints
+pub mod assembly;
 /// the building model utility API
 pub mod builder;
 mod closed_sweep;
+/// user-defined geometries: serde hooks and approximations
+pub mod custom;
+mod draft;
+/// dynamically typed shapes and string-keyed operations for scripting bridges
+pub mod dynamic;
 /// declare errors
 pub mod errors;
+/// recognition of simple machining features
+pub mod features;
+mod fillet;
 mod geom_impls;
+/// double-dispatch intersection of surfaces
+pub mod intersection;
 mod mapped;
+mod mirror;
 mod multi_sweep;
+mod offset;
+mod pipe;
+mod project;
+/// planar sketches of lines, arcs and splines placed on planes
+pub mod sketch;
 mod sweep;
+mod taper;
+/// the outlines of the texts by TrueType and OpenType fonts
+#[cfg(feature = "text")]
+pub mod text;
 mod topo_impls;
@@ -0,0 +1,82 @@
+use crate::*;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+/// the number of the sample points for checking whether a curve is on the mirror plane
+const PLANE_SAMPLES: usize = 4;
+
+/// Returns the matrix of the reflection across the plane through `origin` with `normal`.
+pub(super) fn reflection(origin: Point3, normal: Vector3) -> Matrix4 {
+    let n = normal.normalize();
+    let outer = Matrix3::from_cols(n * n.x, n * n.y, n * n.z);
+    Matrix4::from_translation(origin.to_vec())
+        * Matrix4::from(Matrix3::identity() - outer * 2.0)
+        * Matrix4::from_translation(-origin.to_vec())
+}
+
+/// Returns the shell with the faces of `shell` and of their mirror images, in which the
+/// boundary edges of `shell` on the mirror plane are shared by the both sides.
+pub(super) fn mirrored_and_sewn(shell: &Shell, origin: Point3, normal: Vector3) -> Shell {
+    let mat = reflection(origin, normal);
+    let n = normal.normalize();
+    let on_plane = |pt: Point3| (pt - origin).dot(n).so_small();
+    let mut res = shell.topological_clone();
+    let seams: HashSet<EdgeID> = res
+        .extract_boundaries()
+        .iter()
+        .flat_map(Wire::edge_iter)
+        .filter(|edge| {
+            let curve = edge.get_curve();
+            let (t0, t1) = curve.parameter_range();
+            (0..=PLANE_SAMPLES).all(|i| {
+                let t = t0 + (t1 - t0) * i as f64 / PLANE_SAMPLES as f64;
+                on_plane(curve.subs(t))
+            })
+        })
+        .map(Edge::id)
+        .collect();
+
+    let mut vmap = HashMap::<VertexID, Vertex>::default();
+    let mut emap = HashMap::<EdgeID, Edge>::default();
+    res.edge_iter()
+        .filter(|edge| seams.contains(&edge.id()))
+        .for_each(|edge| {
+            let edge = edge.absolute_clone();
+            vmap.insert(edge.front().id(), edge.front().clone());
+            vmap.insert(edge.back().id(), edge.back().clone());
+            emap.insert(edge.id(), edge);
+        });
+    let mut mirror_vertex = |v: &Vertex| {
+        vmap.entry(v.id())
+            .or_insert_with(|| Vertex::new(mat.transform_point(v.get_point())))
+            .clone()
+    };
+    let mut mirror_edge = |edge: &Edge| {
+        let new_edge = emap.entry(edge.id()).or_insert_with(|| {
+            let v0 = mirror_vertex(edge.absolute_front());
+            let v1 = mirror_vertex(edge.absolute_back());
+            Edge::debug_new(&v0, &v1, edge.get_curve().transformed(mat))
+        });
+        match edge.orientation() {
+            true => new_edge.clone(),
+            false => new_edge.inverse(),
+        }
+    };
+    let mirrored: Vec<Face> = res
+        .face_iter()
+        .map(|face| {
+            let wires: Vec<Wire> = face
+                .absolute_boundaries()
+                .iter()
+                .map(|wire| wire.edge_iter().map(&mut mirror_edge).collect())
+                .collect();
+            let mut new_face = Face::debug_new(wires, face.get_surface().transformed(mat));
+            // The reflection reverses the orientation of the surface.
+            if face.orientation() {
+                new_face.invert();
+            }
+            new_face
+        })
+        .collect();
+    res.extend(mirrored);
+    res
+}
@@ -0,0 +1,646 @@
+use crate::*;
+use errors::{Error, UnsupportedOffsetReason as Reason};
+use fillet::{arc_curve, outward};
+use rustc_hash::FxHashMap as HashMap;
+
+fn unsupported(reason: Reason) -> Error { Error::UnsupportedOffset(reason) }
+
+/// how the offset faces are connected along an edge or around a vertex
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Blend {
+    /// by the pipe around the edge or the sphere around the vertex
+    Round,
+    /// by the intersection of the offset planes
+    Sharp,
+}
+
+struct ShellOffset {
+    distance: f64,
+    faces: Vec<(Vec<Wire>, Surface, Vector3)>,
+    /// the face containing the edge in the same direction, the other face, and the blend
+    edge_faces: HashMap<EdgeID, ([usize; 2], Blend)>,
+    /// the blends around the vertices, and the points of the sharp vertices
+    corners: HashMap<VertexID, (Blend, Point3)>,
+    /// the offset vertices, by the original vertex and the face for the round corners
+    vertices: HashMap<(VertexID, Option<usize>), Vertex>,
+    /// the offset edges, by the original edge and the face for the round edges
+    edges: HashMap<(EdgeID, Option<usize>), Edge>,
+    arcs: HashMap<(EdgeID, VertexID), Edge>,
+}
+
+impl ShellOffset {
+    fn new(shell: &Shell, distance: f64) -> Result<Self> {
+        let faces: Vec<(Vec<Wire>, Surface, Vector3)> = shell
+            .face_iter()
+            .map(|face| {
+                let surface = face.oriented_surface();
+                let normal = surface.normal(0.0, 0.0);
+                (face.boundaries(), surface, normal)
+            })
+            .collect();
+        if faces
+            .iter()
+            .any(|(_, surface, _)| !matches!(surface, Surface::Plane(_)))
+        {
+            return Err(unsupported(Reason::NotPlanarFace));
+        }
+        let mut adjacency = HashMap::<EdgeID, Vec<(usize, Edge)>>::default();
+        let mut vertex_faces = HashMap::<VertexID, (Point3, Vec<usize>)>::default();
+        faces.iter().enumerate().for_each(|(i, (wires, _, _))| {
+            wires.iter().flatten().for_each(|edge| {
+                adjacency
+                    .entry(edge.id())
+                    .or_default()
+                    .push((i, edge.clone()));
+                let v = edge.front();
+                let (_, adjacent) = vertex_faces
+                    .entry(v.id())
+                    .or_insert_with(|| (v.get_point(), Vec::new()));
+                if !adjacent.contains(&i) {
+                    adjacent.push(i);
+                }
+            })
+        });
+        let mut edge_faces = HashMap::default();
+        let mut vertex_blends = HashMap::<VertexID, Vec<Blend>>::default();
+        for (id, adjacent) in &adjacency {
+            let ((f0, e0), (f1, _)) = match adjacent.as_slice() {
+                [a, b] if a.1.orientation() => (a, b),
+                [a, b] => (b, a),
+                _ => return Err(unsupported(Reason::NotManifoldEdge)),
+            };
+            if !matches!(e0.get_curve(), Curve::Line(_)) {
+                return Err(unsupported(Reason::NotLine));
+            }
+            let dir = (e0.back().get_point() - e0.front().get_point()).normalize();
+            let inward = faces[*f0].2.cross(dir);
+            let convexity = inward.dot(faces[*f1].2);
+            if convexity.so_small() {
+                return Err(unsupported(Reason::CoplanarFaces));
+            }
+            let blend = match (convexity < 0.0) == (distance > 0.0) {
+                true => Blend::Round,
+                false => Blend::Sharp,
+            };
+            edge_faces.insert(*id, ([*f0, *f1], blend));
+            [e0.front(), e0.back()].into_iter().for_each(|v| {
+                vertex_blends.entry(v.id()).or_default().push(blend);
+            });
+        }
+        let mut corners = HashMap::default();
+        for (id, blends) in vertex_blends {
+            let (point, adjacent) = &vertex_faces[&id];
+            // the normal and the constant term of the equation of the offset plane
+            let plane = |i: usize| {
+                let normal = faces[i].2;
+                (normal, normal.dot(point.to_vec()) + distance)
+            };
+            let corner = match blends.iter().all(|blend| *blend == blends[0]) {
+                false => return Err(unsupported(Reason::SaddleVertex)),
+                true if blends[0] == Blend::Round => (Blend::Round, Point3::origin()),
+                true => match adjacent.as_slice() {
+                    [i, j, k] => {
+                        let [(n0, c0), (n1, c1), (n2, c2)] = [*i, *j, *k].map(plane);
+                        let matrix = Matrix3::from_cols(n0, n1, n2).transpose();
+                        let inverse = matrix
+                            .invert()
+                            .ok_or_else(|| unsupported(Reason::DependentFaces))?;
+                        (
+                            Blend::Sharp,
+                            Point3::from_vec(inverse * Vector3::new(c0, c1, c2)),
+                        )
+                    }
+                    _ => return Err(unsupported(Reason::NotTrihedralVertex)),
+                },
+            };
+            corners.insert(id, corner);
+        }
+        let offset = ShellOffset {
+            distance,
+            faces,
+            edge_faces,
+            corners,
+            vertices: HashMap::default(),
+            edges: HashMap::default(),
+            arcs: HashMap::default(),
+        };
+        offset.check_sharp_edges()?;
+        Ok(offset)
+    }
+
+    /// Checks that the sharp edges are not reversed by the offset.
+    fn check_sharp_edges(&self) -> Result<()> {
+        let reversed = self
+            .faces
+            .iter()
+            .flat_map(|(wires, _, _)| wires.iter().flatten())
+            .any(|edge| match self.edge_faces[&edge.id()].1 {
+                Blend::Round => false,
+                Blend::Sharp => {
+                    let (v0, v1) = (edge.front(), edge.back());
+                    let (q0, q1) = (self.corners[&v0.id()].1, self.corners[&v1.id()].1);
+                    (q1 - q0).dot(v1.get_point() - v0.get_point()) < TOLERANCE
+                }
+            });
+        match reversed {
+            true => Err(unsupported(Reason::Vanishing)),
+            false => Ok(()),
+        }
+    }
+
+    fn vertex(&mut self, v: &Vertex, face: usize) -> Vertex {
+        let (blend, point) = self.corners[&v.id()];
+        let (key, point) = match blend {
+            Blend::Round => {
+                let normal = self.faces[face].2;
+                ((v.id(), Some(face)), v.get_point() + normal * self.distance)
+            }
+            Blend::Sharp => ((v.id(), None), point),
+        };
+        self.vertices
+            .entry(key)
+            .or_insert_with(|| Vertex::new(point))
+            .clone()
+    }
+
+    /// the offset of `edge` on `face`, in the absolute direction
+    fn edge(&mut self, edge: &Edge, face: usize) -> Edge {
+        let face = match self.edge_faces[&edge.id()].1 {
+            Blend::Round => Some(face),
+            Blend::Sharp => None,
+        };
+        if let Some(new) = self.edges.get(&(edge.id(), face)) {
+            return new.clone();
+        }
+        let (v0, v1) = (edge.absolute_front(), edge.absolute_back());
+        let face_idx = face.unwrap_or(self.edge_faces[&edge.id()].0[0]);
+        let w0 = self.vertex(v0, face_idx);
+        let w1 = self.vertex(v1, face_idx);
+        let line = Edge::new(&w0, &w1, Curve::Line(Line(w0.get_point(), w1.get_point())));
+        self.edges.insert((edge.id(), face), line.clone());
+        line
+    }
+
+    /// the arc around `v` from the offset of the first face to the one of the second face
+    fn arc(&mut self, edge: &Edge, v: &Vertex) -> Edge {
+        if let Some(arc) = self.arcs.get(&(edge.id(), v.id())) {
+            return arc.clone();
+        }
+        let [f0, f1] = self.edge_faces[&edge.id()].0;
+        let w0 = self.vertex(v, f0);
+        let w1 = self.vertex(v, f1);
+        let curve = arc_curve(w0.get_point(), w1.get_point(), v.get_point());
+        let arc = Edge::new(&w0, &w1, curve);
+        self.arcs.insert((edge.id(), v.id()), arc.clone());
+        arc
+    }
+
+    /// Makes the normal of the blend surface point out of the offset solid.
+    fn orient(&self, surface: Surface, center: Point3) -> Surface {
+        let mut surface = outward(surface, (0.5, 0.5), center);
+        if self.distance < 0.0 {
+            surface.invert();
+        }
+        surface
+    }
+
+    fn planar_face(&mut self, i: usize) -> Face {
+        let (wires, surface, normal) = self.faces[i].clone();
+        let wires = wires
+            .iter()
+            .map(|wire| {
+                wire.edge_iter()
+                    .map(|edge| {
+                        let new = self.edge(edge, i);
+                        match edge.orientation() {
+                            true => new,
+                            false => new.inverse(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let translation = Matrix4::from_translation(normal * self.distance);
+        Face::new(wires, surface.transformed(translation))
+    }
+
+    fn find_edge(&self, id: EdgeID) -> Edge {
+        self.faces
+            .iter()
+            .flat_map(|(wires, _, _)| wires.iter().flatten())
+            .find(|edge| edge.id() == id)
+            .unwrap()
+            .absolute_clone()
+    }
+
+    fn pipe_face(&mut self, edge: &Edge) -> Face {
+        let [f0, f1] = self.edge_faces[&edge.id()].0;
+        let (v0, v1) = (edge.absolute_front(), edge.absolute_back());
+        let line0 = self.edge(edge, f0);
+        let line1 = self.edge(edge, f1);
+        let arc0 = self.arc(edge, v0);
+        let arc1 = self.arc(edge, v1);
+        let (curve0, curve1) = (arc0.get_curve().lift_up(), arc1.get_curve().lift_up());
+        let surface = NURBSSurface::new(BSplineSurface::homotopy(curve0, curve1));
+        let center = v0.get_point().midpoint(v1.get_point());
+        let surface = self.orient(Surface::NURBSSurface(surface), center);
+        let wire: Wire = vec![line0.inverse(), arc0, line1, arc1.inverse()].into();
+        Face::new(vec![wire], surface)
+    }
+
+    fn sphere_face(&mut self, v: &Vertex, ids: &[EdgeID]) -> Face {
+        let mut arcs: Vec<Edge> = ids
+            .iter()
+            .filter_map(|id| {
+                let edge = self.find_edge(*id);
+                match (edge.front() == v, edge.back() == v) {
+                    (true, _) => Some(self.arc(&edge, v).inverse()),
+                    (_, true) => Some(self.arc(&edge, v)),
+                    _ => None,
+                }
+            })
+            .collect();
+        let mut wire = Wire::new();
+        while !arcs.is_empty() {
+            let idx = match wire.back_vertex() {
+                None => 0,
+                Some(back) => arcs.iter().position(|arc| arc.front() == back).unwrap(),
+            };
+            wire.push_back(arcs.remove(idx));
+        }
+        let center = v.get_point();
+        let pole = wire[0].front().get_point();
+        let curve = wire[0].oriented_curve();
+        let axis = (pole - center).normalize();
+        let revolution = RevolutedCurve::by_revolution(curve.inverse(), center, axis);
+        let surface = self.orient(Surface::RevolutedCurve(Processor::new(revolution)), center);
+        Face::new(vec![wire], surface)
+    }
+
+    fn shell(mut self) -> Shell {
+        let mut shell: Shell = (0..self.faces.len()).map(|i| self.planar_face(i)).collect();
+        let mut ids: Vec<EdgeID> = Vec::new();
+        let mut round_vertices: Vec<Vertex> = Vec::new();
+        self.faces
+            .iter()
+            .flat_map(|(wires, _, _)| wires.iter().flatten())
+            .for_each(|edge| {
+                if self.edge_faces[&edge.id()].1 == Blend::Round && !ids.contains(&edge.id()) {
+                    ids.push(edge.id());
+                    [edge.front(), edge.back()].into_iter().for_each(|v| {
+                        if !round_vertices.contains(v) {
+                            round_vertices.push(v.clone());
+                        }
+                    });
+                }
+            });
+        ids.iter().for_each(|id| {
+            let edge = self.find_edge(*id);
+            let face = self.pipe_face(&edge);
+            shell.push(face);
+        });
+        round_vertices.iter().for_each(|v| {
+            let face = self.sphere_face(v, &ids);
+            shell.push(face);
+        });
+        shell
+    }
+}
+
+/// Offsets the boundaries of the polyhedral `solid` by `distance`.
+pub(super) fn polyhedral_offset(solid: &Solid, distance: f64) -> Result<Solid> {
+    if distance.so_small() {
+        return Ok(solid.clone());
+    }
+    let shells = solid
+        .boundaries()
+        .iter()
+        .map(|shell| Ok(ShellOffset::new(shell, distance)?.shell()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Solid::try_new(shells)?)
+}
+
+/// the offset of an edge or a vertex of the planar wire
+#[derive(Clone, Copy, Debug)]
+enum Element {
+    /// the offset line of the edge, parametrized by the length from `origin`
+    Line {
+        origin: Point2,
+        direction: Vector2,
+        length: f64,
+    },
+    /// the arc around the vertex, parametrized by the ratio of the angle to `sweep`
+    Arc {
+        center: Point2,
+        start: f64,
+        sweep: f64,
+    },
+}
+
+/// the number of the divisions of the arcs in the check of the self-intersections
+const ARC_DIVISION: usize = 8;
+
+impl Element {
+    fn point(self, distance: f64, t: f64) -> Point2 {
+        match self {
+            Element::Line {
+                origin, direction, ..
+            } => origin + direction * t,
+            Element::Arc {
+                center,
+                start,
+                sweep,
+            } => {
+                let angle = start + sweep * t;
+                center + Vector2::new(angle.cos(), angle.sin()) * distance
+            }
+        }
+    }
+
+    fn parameter(self, distance: f64, p: Point2) -> f64 {
+        match self {
+            Element::Line {
+                origin, direction, ..
+            } => (p - origin).dot(direction),
+            Element::Arc {
+                center,
+                start,
+                sweep,
+            } => {
+                let normal = (p - center) / distance;
+                let (cos, sin) = (start.cos(), start.sin());
+                let (x, y) = (
+                    normal.x * cos + normal.y * sin,
+                    normal.y * cos - normal.x * sin,
+                );
+                f64::atan2(y, x) / sweep
+            }
+        }
+    }
+
+    fn range(self) -> (f64, f64) {
+        match self {
+            Element::Line { length, .. } => (0.0, length),
+            Element::Arc { .. } => (0.0, 1.0),
+        }
+    }
+
+    /// the length of the part between the parameters `t0` and `t1`, negative if reversed
+    fn length(self, distance: f64, t0: f64, t1: f64) -> f64 {
+        match self {
+            Element::Line { .. } => t1 - t0,
+            Element::Arc { sweep, .. } => (t1 - t0) * f64::abs(sweep * distance),
+        }
+    }
+
+    /// the intersections of the line or the circle including `self` and `other`
+    fn intersections(self, other: Self, distance: f64) -> Vec<Point2> {
+        let radius = distance.abs();
+        let line_circle = |origin: Point2, direction: Vector2, center: Point2| {
+            let b = direction.dot(origin - center);
+            let disc = b * b - (origin - center).magnitude2() + radius * radius;
+            match disc {
+                _ if disc < -TOLERANCE2 => Vec::new(),
+                _ => {
+                    let h = f64::sqrt(f64::max(disc, 0.0));
+                    vec![origin + direction * (-b - h), origin + direction * (-b + h)]
+                }
+            }
+        };
+        match (self, other) {
+            (
+                Element::Line {
+                    origin: p,
+                    direction: u,
+                    ..
+                },
+                Element::Line {
+                    origin: q,
+                    direction: v,
+                    ..
+                },
+            ) => {
+                let det = u.perp_dot(v);
+                match det.so_small() {
+                    true => Vec::new(),
+                    false => vec![p + u * ((q - p).perp_dot(v) / det)],
+                }
+            }
+            (
+                Element::Line {
+                    origin, direction, ..
+                },
+                Element::Arc { center, .. },
+            )
+            | (
+                Element::Arc { center, .. },
+                Element::Line {
+                    origin, direction, ..
+                },
+            ) => line_circle(origin, direction, center),
+            (Element::Arc { center: c0, .. }, Element::Arc { center: c1, .. }) => {
+                let half = (c1 - c0) / 2.0;
+                let disc = radius * radius - half.magnitude2();
+                match disc {
+                    _ if disc < -TOLERANCE2 || half.so_small() => Vec::new(),
+                    _ => {
+                        let h = f64::sqrt(f64::max(disc, 0.0));
+                        let perp = Vector2::new(-half.y, half.x).normalize() * h;
+                        vec![c0 + half + perp, c0 + half - perp]
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn segments_intersect((p0, p1): (Point2, Point2), (q0, q1): (Point2, Point2)) -> bool {
+    use truck_base::polygon::orient;
+    let (d0, d1) = (orient(p0, p1, q0), orient(p0, p1, q1));
+    let (d2, d3) = (orient(q0, q1, p0), orient(q0, q1, p1));
+    d0 * d1 < 0.0 && d2 * d3 < 0.0
+}
+
+/// Offsets the closed planar wire of lines. See [`builder::offset_wire`] for the details.
+pub(super) fn offset_wire(wire: &Wire, distance: f64) -> Result<Wire> {
+    if !wire.is_closed() || wire.is_empty() {
+        return Err(unsupported(Reason::NotClosedWire));
+    }
+    let mut points = Vec::<Point3>::new();
+    for edge in wire.edge_iter() {
+        match edge.oriented_curve() {
+            Curve::Line(Line(p, _)) => match points.last() {
+                Some(q) if q.near(&p) => {}
+                _ => points.push(p),
+            },
+            _ => return Err(unsupported(Reason::NotLine)),
+        }
+    }
+    if points.len() > 1 && points[0].near(&points[points.len() - 1]) {
+        points.pop();
+    }
+    let origin = points[0];
+    let normal = (0..points.len()).fold(Vector3::zero(), |sum, i| {
+        let (p, q) = (points[i] - origin, points[(i + 1) % points.len()] - origin);
+        sum + p.cross(q)
+    });
+    if points.len() < 3 || normal.so_small() {
+        return Err(unsupported(Reason::NotRegion));
+    }
+    let normal = normal.normalize();
+    if points.iter().any(|p| !normal.dot(p - origin).so_small()) {
+        return Err(unsupported(Reason::NotPlanarWire));
+    }
+    if distance.so_small() {
+        return Ok(builder::clone(wire));
+    }
+    // The wire is counter-clockwise in the plane coordinates.
+    let u_axis = analytic::perpendicular(normal);
+    let v_axis = normal.cross(u_axis);
+    let to_2d = |p: &Point3| Point2::new((p - origin).dot(u_axis), (p - origin).dot(v_axis));
+    let to_3d = |p: Point2| origin + u_axis * p.x + v_axis * p.y;
+    let points: Vec<Point2> = points.iter().map(to_2d).collect();
+    let len = points.len();
+    let vectors: Vec<Vector2> = (0..len)
+        .map(|i| points[(i + 1) % len] - points[i])
+        .collect();
+    let outward = |i: usize| {
+        let dir = vectors[i].normalize();
+        Vector2::new(dir.y, -dir.x)
+    };
+
+    // the arcs around the vertices turning to the offset side, and the lines of the edges
+    let mut elements = Vec::<Element>::new();
+    for i in 0..len {
+        let (n0, n1) = (outward((i + len - 1) % len), outward(i));
+        let turn = n0.perp_dot(n1);
+        if !turn.so_small() && turn * distance > 0.0 {
+            elements.push(Element::Arc {
+                center: points[i],
+                start: f64::atan2(n0.y, n0.x),
+                sweep: f64::atan2(turn, n0.dot(n1)),
+            });
+        } else if turn.so_small() && n0.dot(n1) < 0.0 {
+            return Err(unsupported(Reason::FoldedWire));
+        }
+        elements.push(Element::Line {
+            origin: points[i] + n1 * distance,
+            direction: vectors[i].normalize(),
+            length: vectors[i].magnitude(),
+        });
+    }
+
+    // Trims the consecutive elements, and removes the most reversed one until no one is reversed.
+    let count = elements.len();
+    let mut active: Vec<usize> = (0..count).collect();
+    let ranges = loop {
+        let n = active.len();
+        if n < 2 {
+            return Err(unsupported(Reason::Vanishing));
+        }
+        let mut ranges: Vec<(f64, f64)> = active.iter().map(|i| elements[*i].range()).collect();
+        let mut separated = None;
+        for k in 0..n {
+            let (i, j) = (active[(k + n - 1) % n], active[k]);
+            let (a, b) = (elements[i], elements[j]);
+            let intersections = a.intersections(b, distance);
+            // The arcs are tangent to the adjacent lines, and the collinear lines are connected.
+            let smooth = matches!((a, b), (Element::Arc { .. }, _) | (_, Element::Arc { .. }))
+                || intersections.is_empty();
+            if (i + 1) % count == j && smooth {
+                continue;
+            }
+            let intersection = intersections
+                .into_iter()
+                .map(|p| (a.parameter(distance, p), b.parameter(distance, p)))
+                .min_by(|x, y| x.0.total_cmp(&y.0));
+            match (intersection, a, b) {
+                (Some((t, s)), _, _) => {
+                    ranges[(k + n - 1) % n].1 = t;
+                    ranges[k].0 = s;
+                }
+                // the region between the parallel lines vanishes.
+                (None, Element::Line { .. }, Element::Line { .. }) => {
+                    separated = Some(k);
+                    break;
+                }
+                _ => return Err(unsupported(Reason::SeparatedCurves)),
+            }
+        }
+        if let Some(k) = separated {
+            active.remove(k);
+            continue;
+        }
+        let reversed = (0..n)
+            .map(|k| {
+                let (t0, t1) = ranges[k];
+                (k, elements[active[k]].length(distance, t0, t1))
+            })
+            .min_by(|x, y| x.1.total_cmp(&y.1));
+        match reversed {
+            Some((k, length)) if length < -TOLERANCE => {
+                active.remove(k);
+            }
+            _ => break ranges,
+        }
+    };
+
+    // the trimmed elements, without the vanishing ones
+    let pieces: Vec<(Element, f64, f64)> = active
+        .iter()
+        .zip(ranges)
+        .map(|(i, (t0, t1))| (elements[*i], t0, t1))
+        .filter(|(element, t0, t1)| {
+            let (p, q) = (element.point(distance, *t0), element.point(distance, *t1));
+            !p.near(&q)
+        })
+        .collect();
+    let segments: Vec<(Point2, Point2)> = pieces
+        .iter()
+        .flat_map(|(element, t0, t1)| {
+            let division = match element {
+                Element::Line { .. } => 1,
+                Element::Arc { .. } => ARC_DIVISION,
+            };
+            let point = move |i: usize| {
+                let t = t0 + (t1 - t0) * i as f64 / division as f64;
+                element.point(distance, t)
+            };
+            (0..division).map(move |i| (point(i), point(i + 1)))
+        })
+        .collect();
+    let area = segments
+        .iter()
+        .fold(0.0, |sum, (p, q)| sum + p.to_vec().perp_dot(q.to_vec()));
+    if area < TOLERANCE {
+        return Err(unsupported(Reason::Vanishing));
+    }
+    let m = segments.len();
+    let crossing = (0..m).any(|i| {
+        (i + 2..m)
+            .filter(|j| (j + 1) % m != i)
+            .any(|j| segments_intersect(segments[i], segments[j]))
+    });
+    if crossing {
+        return Err(unsupported(Reason::SelfIntersection));
+    }
+
+    let vertices: Vec<Vertex> = pieces
+        .iter()
+        .map(|(element, t0, _)| builder::vertex(to_3d(element.point(distance, *t0))))
+        .collect();
+    let new_wire = pieces
+        .iter()
+        .enumerate()
+        .map(|(i, (element, t0, t1))| {
+            let (v0, v1) = (&vertices[i], &vertices[(i + 1) % vertices.len()]);
+            let (p, q) = (element.point(distance, *t0), element.point(distance, *t1));
+            let curve = match element {
+                Element::Line { .. } => Curve::Line(Line(to_3d(p), to_3d(q))),
+                Element::Arc { center, .. } => arc_curve(to_3d(p), to_3d(q), to_3d(*center)),
+            };
+            Edge::new(v0, v1, curve)
+        })
+        .collect();
+    Ok(new_wire)
+}
@@ -0,0 +1,312 @@
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+
+/// the maximum turning angle of the path in a segment of the lateral surfaces
+const MAX_TURNING: f64 = std::f64::consts::PI / 64.0;
+/// the minimum number of the segments of the lateral surfaces along a curved edge
+const MIN_DIVISION: usize = 4;
+
+/// The rigid motion of the profile at a point of the path, and its derivative.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Section {
+    matrix: Matrix4,
+    /// the derivative of `matrix` by the parameter of the path edge
+    derivative: Matrix4,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PathSample {
+    point: Point3,
+    tangent: Vector3,
+    /// the derivative of the point by the parameter of the path edge
+    velocity: Vector3,
+    /// the angular velocity of the rotation minimizing frame by the parameter
+    omega: Vector3,
+}
+
+/// Samples the path edges with the points, the tangents and the rotation minimizing frames,
+/// and returns the sections at the samples for each edge.
+fn sections(path: &Wire) -> Vec<Vec<Section>> {
+    let mut samples: Vec<Vec<PathSample>> = path
+        .edge_iter()
+        .map(|edge| {
+            let curve = edge.oriented_curve();
+            let (t0, t1) = curve.parameter_range();
+            let division = match curve {
+                Curve::Line(_) => 1,
+                _ => {
+                    const N: usize = 16;
+                    let tangents: Vec<Vector3> = (0..=N)
+                        .map(|j| curve.der(t0 + (t1 - t0) * j as f64 / N as f64))
+                        .collect();
+                    let turning: f64 = tangents.windows(2).map(|v| v[0].angle(v[1]).0).sum();
+                    usize::max(f64::ceil(turning / MAX_TURNING) as usize, MIN_DIVISION)
+                }
+            };
+            (0..=division)
+                .map(|j| {
+                    let t = t0 + (t1 - t0) * j as f64 / division as f64;
+                    let (velocity, acceleration) = (curve.der(t), curve.der2(t));
+                    PathSample {
+                        point: curve.subs(t),
+                        tangent: velocity.normalize(),
+                        velocity: velocity * (t1 - t0),
+                        omega: velocity.cross(acceleration) / velocity.magnitude2() * (t1 - t0),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    // the sections at the vertices are shared by the adjacent edges.
+    let len = samples.len();
+    let closed = path.is_closed();
+    (0..len).for_each(|k| {
+        if k + 1 == len && !closed {
+            return;
+        }
+        let next = (k + 1) % len;
+        let tangent = samples[k].last().unwrap().tangent + samples[next][0].tangent;
+        if !tangent.so_small() {
+            samples[k].last_mut().unwrap().tangent = tangent.normalize();
+            samples[next][0].tangent = tangent.normalize();
+        }
+    });
+    // the rotation minimizing frames by the double reflection method
+    let first = samples[0][0];
+    let axis = [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()]
+        .into_iter()
+        .min_by(|a, b| {
+            let (x, y) = (a.dot(first.tangent).abs(), b.dot(first.tangent).abs());
+            x.partial_cmp(&y).unwrap()
+        })
+        .unwrap();
+    let mut normal = (axis - axis.dot(first.tangent) * first.tangent).normalize();
+    let mut previous = first;
+    let mut length = 0.0;
+    let mut frames: Vec<Vec<(Vector3, f64)>> = samples
+        .iter()
+        .map(|edge_samples| {
+            edge_samples
+                .iter()
+                .map(|sample| {
+                    let v1 = sample.point - previous.point;
+                    let c1 = v1.magnitude2();
+                    let (normal_l, tangent_l) = match c1.so_small2() {
+                        true => (normal, previous.tangent),
+                        false => (
+                            normal - v1 * (2.0 * v1.dot(normal) / c1),
+                            previous.tangent - v1 * (2.0 * v1.dot(previous.tangent) / c1),
+                        ),
+                    };
+                    let v2 = sample.tangent - tangent_l;
+                    let c2 = v2.magnitude2();
+                    normal = match c2.so_small2() {
+                        true => normal_l,
+                        false => normal_l - v2 * (2.0 * v2.dot(normal_l) / c2),
+                    };
+                    length += f64::sqrt(c1);
+                    previous = *sample;
+                    (normal, length)
+                })
+                .collect()
+        })
+        .collect();
+    // the closed path: the twist is distributed along the path.
+    if closed && length > 0.0 {
+        let end = normal;
+        let twist = f64::atan2(
+            end.cross(frames[0][0].0).dot(first.tangent),
+            end.dot(frames[0][0].0),
+        );
+        samples
+            .iter_mut()
+            .zip(&mut frames)
+            .for_each(|(samples, frames)| {
+                samples
+                    .iter_mut()
+                    .zip(frames)
+                    .for_each(|(sample, (normal, s))| {
+                        let rot =
+                            Matrix3::from_axis_angle(sample.tangent, Rad(twist * *s / length));
+                        *normal = rot * *normal;
+                        sample.omega +=
+                            sample.tangent * (twist * sample.velocity.magnitude() / length);
+                    })
+            });
+    }
+    let rotation = |tangent: Vector3, normal: Vector3| {
+        let normal = (normal - normal.dot(tangent) * tangent).normalize();
+        Matrix3::from_cols(tangent, normal, tangent.cross(normal))
+    };
+    let inverse = rotation(first.tangent, frames[0][0].0).transpose();
+    let origin = first.point.to_vec();
+    samples
+        .iter()
+        .zip(&frames)
+        .map(|(samples, frames)| {
+            samples
+                .iter()
+                .zip(frames)
+                .map(|(sample, (normal, _))| {
+                    let linear = rotation(sample.tangent, *normal) * inverse;
+                    let matrix = Matrix4::from_translation(sample.point.to_vec())
+                        * Matrix4::from(linear)
+                        * Matrix4::from_translation(-origin);
+                    let derivative =
+                        motion_derivative(linear, origin, sample.omega, sample.velocity);
+                    Section { matrix, derivative }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the derivative of the rigid motion `x -> p + linear * (x - origin)`, where
+/// `omega` is the angular velocity and `velocity` is the derivative of `p`.
+fn motion_derivative(
+    linear: Matrix3,
+    origin: Vector3,
+    omega: Vector3,
+    velocity: Vector3,
+) -> Matrix4 {
+    let w = omega;
+    let skew = Matrix3::new(0.0, w.z, -w.y, -w.z, 0.0, w.x, w.y, -w.x, 0.0);
+    let linear_der = skew * linear;
+    let translation_der = velocity - linear_der * origin;
+    Matrix4::from_cols(
+        linear_der.x.extend(0.0),
+        linear_der.y.extend(0.0),
+        linear_der.z.extend(0.0),
+        translation_der.extend(0.0),
+    )
+}
+
+/// Returns the sections of the screw motion around the axis through `origin`, which rotates
+/// by `angle` and moves by `distance` along `axis`, divided into `division` segments.
+pub(super) fn screw_sections(
+    origin: Point3,
+    axis: Vector3,
+    angle: f64,
+    distance: f64,
+    division: usize,
+) -> Vec<Section> {
+    let origin = origin.to_vec();
+    (0..=division)
+        .map(|j| {
+            let t = j as f64 / division as f64;
+            let linear = Matrix3::from_axis_angle(axis, Rad(angle * t));
+            let matrix = Matrix4::from_translation(origin + axis * (distance * t))
+                * Matrix4::from(linear)
+                * Matrix4::from_translation(-origin);
+            let omega = axis * angle;
+            // the velocity of the image of `origin`, on the axis
+            let derivative = motion_derivative(linear, origin, omega, axis * distance);
+            Section { matrix, derivative }
+        })
+        .collect()
+}
+
+/// Returns the curve of the point moved by the sections.
+pub(super) fn sweep_point(point: Point3, sections: &[Section]) -> Curve {
+    let knot_vec = hermite_knot_vec(sections.len() - 1);
+    let curve = BSplineCurve::new(knot_vec, hermite_points(sections, point.to_homogeneous()));
+    Curve::NURBSCurve(NURBSCurve::new(curve))
+}
+
+/// Returns the surface swept by the curve moved by the sections.
+///
+/// The first parameter is the one of the curve and the second one is the one of the sections.
+pub(super) fn sweep_curve(curve: &Curve, sections: &[Section]) -> Surface {
+    let curve = curve.clone().lift_up();
+    let control_points = curve
+        .control_points()
+        .iter()
+        .map(|point| hermite_points(sections, *point))
+        .collect();
+    let knot_vecs = (
+        curve.knot_vec().clone(),
+        hermite_knot_vec(sections.len() - 1),
+    );
+    let surface = BSplineSurface::new(knot_vecs, control_points);
+    Surface::NURBSSurface(NURBSSurface::new(surface))
+}
+
+/// the cubic Hermite interpolation of the control points `point` moved by the sections
+fn hermite_points(sections: &[Section], point: Vector4) -> Vec<Vector4> {
+    let h = 1.0 / (sections.len() - 1) as f64;
+    let mut points = vec![sections[0].matrix * point];
+    sections.windows(2).for_each(|pair| {
+        let (s0, s1) = (pair[0], pair[1]);
+        points.push((s0.matrix + s0.derivative * (h / 3.0)) * point);
+        points.push((s1.matrix - s1.derivative * (h / 3.0)) * point);
+        points.push(s1.matrix * point);
+    });
+    points
+}
+
+/// the knot vector of the piecewise cubic Bézier curves in `hermite_points`
+fn hermite_knot_vec(division: usize) -> KnotVec {
+    let mut knots = vec![0.0; 4];
+    (1..division).for_each(|j| knots.extend([j as f64 / division as f64; 3]));
+    knots.extend([1.0; 4]);
+    KnotVec::from(knots)
+}
+
+/// Sweeps the wires `profiles` along `path`, and returns the lateral faces of each wire.
+///
+/// If the path is not closed, the last sections are created by `last` from the matrix
+/// moving the profiles to the end of the path.
+pub(super) fn pipe_faces(
+    profiles: &[Wire],
+    path: &Wire,
+    last: impl FnOnce(Matrix4) -> Vec<Wire>,
+) -> Vec<Vec<Face>> {
+    assert!(
+        !path.is_empty() && path.is_continuous(),
+        "the path must be a non-empty continuous wire."
+    );
+    let sections = sections(path);
+    let len = sections.len();
+    let mut wires = vec![profiles.to_vec()];
+    (1..len).for_each(|k| {
+        let matrix = sections[k][0].matrix;
+        let section = profiles
+            .iter()
+            .map(|wire| builder::transformed(wire, matrix))
+            .collect();
+        wires.push(section);
+    });
+    match path.is_closed() {
+        true => wires.push(profiles.to_vec()),
+        false => wires.push(last(sections[len - 1].last().unwrap().matrix)),
+    }
+    let mut rails = HashMap::<VertexID, Edge>::default();
+    let mut rail = |k: usize, v0: &Vertex, v1: &Vertex| {
+        rails
+            .entry(v0.id())
+            .or_insert_with(|| Edge::new(v0, v1, sweep_point(v0.get_point(), &sections[k])))
+            .clone()
+    };
+    (0..profiles.len())
+        .map(|i| {
+            let mut faces = Vec::new();
+            (0..len).for_each(|k| {
+                let (wire0, wire1) = (&wires[k][i], &wires[k + 1][i]);
+                let edges = profiles[i].edge_iter().zip(wire0).zip(wire1);
+                edges.for_each(|((edge, edge0), edge1)| {
+                    // the sections move the profile at the start of the path.
+                    let surface = sweep_curve(&edge.oriented_curve(), &sections[k]);
+                    let wire: Wire = vec![
+                        edge0.clone(),
+                        rail(k, edge0.back(), edge1.back()),
+                        edge1.inverse(),
+                        rail(k, edge0.front(), edge1.front()).inverse(),
+                    ]
+                    .into();
+                    faces.push(Face::new(vec![wire], surface));
+                })
+            });
+            faces
+        })
+        .collect()
+}
@@ -0,0 +1,199 @@
+use crate::*;
+use errors::Error;
+use rustc_hash::FxHashMap as HashMap;
+
+/// the distance within which the projected curves are fitted
+const PROJECTION_TOLERANCE: f64 = 1.0e-5;
+/// the maximum depth of the bisection of the intervals of the fitted curves
+const MAX_DEPTH: usize = 16;
+/// the number of the trials of the search of the nearest points
+const TRIALS: usize = 100;
+
+fn unsupported(message: &str) -> Error { Error::UnsupportedProjection(message.to_string()) }
+
+/// Returns the point on `surface` nearest to `point`, and its parameter.
+fn project_point<H: Into<SPHint2D>>(
+    surface: &Surface,
+    point: Point3,
+    hint: H,
+) -> Result<((f64, f64), Point3)> {
+    let (u, v) = surface
+        .search_nearest_parameter(point, hint, TRIALS)
+        .ok_or_else(|| unsupported("a point is not projected onto the surface."))?;
+    Ok(((u, v), surface.subs(u, v)))
+}
+
+/// Returns the projected vertex, which is shared by the edges through the vertex.
+pub(super) fn project_vertex(
+    vertex: &Vertex,
+    surface: &Surface,
+    vertices: &mut HashMap<VertexID, Vertex>,
+) -> Result<Vertex> {
+    if let Some(v) = vertices.get(&vertex.id()) {
+        return Ok(v.clone());
+    }
+    let (_, point) = project_point(surface, vertex.get_point(), None)?;
+    let v = Vertex::new(point);
+    vertices.insert(vertex.id(), v.clone());
+    Ok(v)
+}
+
+/// Returns the projected edge, whose vertices are shared by the edges through them.
+pub(super) fn project_edge(
+    edge: &Edge,
+    surface: &Surface,
+    vertices: &mut HashMap<VertexID, Vertex>,
+) -> Result<Edge> {
+    let v0 = project_vertex(edge.absolute_front(), surface, vertices)?;
+    let v1 = project_vertex(edge.absolute_back(), surface, vertices)?;
+    let mut curve = match surface {
+        Surface::Plane(plane) => project_onto_plane(&edge.get_curve(), plane),
+        _ => project_curve(&edge.get_curve(), surface)?,
+    };
+    if let Curve::BSplineCurve(bspcurve) = &mut curve {
+        // The ends are the projected vertices exactly.
+        let len = bspcurve.control_points().len();
+        *bspcurve.control_point_mut(0) = v0.get_point();
+        *bspcurve.control_point_mut(len - 1) = v1.get_point();
+    }
+    let res = Edge::new(&v0, &v1, curve);
+    match edge.orientation() {
+        true => Ok(res),
+        false => Ok(res.inverse()),
+    }
+}
+
+/// The orthogonal projection onto the plane, which is exact since it is affine.
+fn project_onto_plane(curve: &Curve, plane: &Plane) -> Curve {
+    let origin = plane.origin();
+    let normal = plane.normal();
+    let project = |p: Point3| p - normal * (p - origin).dot(normal);
+    match curve {
+        Curve::Line(line) => Curve::Line(Line(project(line.0), project(line.1))),
+        Curve::BSplineCurve(curve) => {
+            let mut curve = curve.clone();
+            curve.transform_control_points(|p| *p = project(*p));
+            Curve::BSplineCurve(curve)
+        }
+        Curve::IntersectionCurve(_) => {
+            let bspcurve = custom::curve_approximation(curve, PROJECTION_TOLERANCE);
+            project_onto_plane(&Curve::BSplineCurve(bspcurve), plane)
+        }
+        _ => {
+            let mut curve = curve.clone().lift_up();
+            curve.transform_control_points(|p| {
+                let vec = (p.truncate() - origin.to_vec() * p.w).dot(normal);
+                *p -= (normal * vec).extend(0.0);
+            });
+            Curve::NURBSCurve(NURBSCurve::new(curve))
+        }
+    }
+}
+
+/// A sample of the projection: the parameter of the curve, the parameter of the surface, the
+/// projected point and its derivative.
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    t: f64,
+    uv: (f64, f64),
+    point: Point3,
+    der: Vector3,
+}
+
+/// Returns the projected sample at `t`. The derivative of the parameter of the surface is the
+/// solution of the normal equation of the derivative of the curve, which is exact if the curve is
+/// on the surface.
+fn sample(curve: &Curve, surface: &Surface, t: f64, hint: Option<(f64, f64)>) -> Result<Sample> {
+    let (uv, point) = project_point(surface, curve.subs(t), hint)?;
+    let (uder, vder) = (surface.uder(uv.0, uv.1), surface.vder(uv.0, uv.1));
+    let der = curve.der(t);
+    let matrix = Matrix2::new(
+        uder.dot(uder),
+        uder.dot(vder),
+        uder.dot(vder),
+        vder.dot(vder),
+    );
+    let der = match matrix.invert() {
+        Some(inv) if !matrix.determinant().so_small2() => {
+            let duv = inv * Vector2::new(uder.dot(der), vder.dot(der));
+            uder * duv.x + vder * duv.y
+        }
+        _ => der,
+    };
+    Ok(Sample { t, uv, point, der })
+}
+
+/// the point of the cubic Hermite interpolation between the samples at `t`
+fn hermite(s0: &Sample, s1: &Sample, t: f64) -> Point3 {
+    let h = s1.t - s0.t;
+    let x = (t - s0.t) / h;
+    let (y, z) = (1.0 - x, x);
+    let p = [
+        s0.point.to_vec(),
+        s0.point.to_vec() + s0.der * (h / 3.0),
+        s1.point.to_vec() - s1.der * (h / 3.0),
+        s1.point.to_vec(),
+    ];
+    Point3::from_vec(
+        p[0] * (y * y * y)
+            + p[1] * (3.0 * y * y * z)
+            + p[2] * (3.0 * y * z * z)
+            + p[3] * (z * z * z),
+    )
+}
+
+/// Bisects the interval between the samples until the Hermite interpolation is near to the
+/// projection, and pushes the samples after `s0`.
+fn bisect(
+    curve: &Curve,
+    surface: &Surface,
+    s0: Sample,
+    s1: Sample,
+    depth: usize,
+    samples: &mut Vec<Sample>,
+) -> Result<()> {
+    let hint = ((s0.uv.0 + s1.uv.0) / 2.0, (s0.uv.1 + s1.uv.1) / 2.0);
+    let mid = sample(curve, surface, (s0.t + s1.t) / 2.0, Some(hint))?;
+    let near = |t: f64, point: Point3| hermite(&s0, &s1, t).distance(point) < PROJECTION_TOLERANCE;
+    let fitted = depth == 0
+        || near(mid.t, mid.point)
+            && [0.25, 0.75].iter().all(|x| {
+                let t = s0.t + (s1.t - s0.t) * x;
+                let hint = (
+                    s0.uv.0 + (s1.uv.0 - s0.uv.0) * x,
+                    s0.uv.1 + (s1.uv.1 - s0.uv.1) * x,
+                );
+                match project_point(surface, curve.subs(t), hint) {
+                    Ok((_, point)) => near(t, point),
+                    Err(_) => false,
+                }
+            });
+    if fitted {
+        samples.push(s1);
+        Ok(())
+    } else {
+        bisect(curve, surface, s0, mid, depth - 1, samples)?;
+        bisect(curve, surface, mid, s1, depth - 1, samples)
+    }
+}
+
+/// The projection onto the surface, fitted by the cubic B-spline curve.
+fn project_curve(curve: &Curve, surface: &Surface) -> Result<Curve> {
+    let (params, _) = curve.parameter_division(curve.parameter_range(), PROJECTION_TOLERANCE);
+    let mut samples = vec![sample(curve, surface, params[0], None)?];
+    for t in &params[1..] {
+        let s0 = samples[samples.len() - 1];
+        let s1 = sample(curve, surface, *t, Some(s0.uv))?;
+        bisect(curve, surface, s0, s1, MAX_DEPTH, &mut samples)?;
+    }
+    let params: Vec<f64> = samples.iter().map(|s| s.t).collect();
+    let mut points = vec![samples[0].point];
+    samples.windows(2).for_each(|s| {
+        let h = s[1].t - s[0].t;
+        points.push(s[0].point + s[0].der * (h / 3.0));
+        points.push(s[1].point - s[1].der * (h / 3.0));
+        points.push(s[1].point);
+    });
+    let curve = BSplineCurve::new(custom::hermite_knot_vec(&params), points);
+    Ok(Curve::BSplineCurve(curve))
+}
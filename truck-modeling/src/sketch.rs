@@ -0,0 +1,792 @@
+use crate::*;
+use errors::Error;
+use intersection::{AnalyticCurve, SurfaceIntersection};
+use std::ops::Range;
+
+/// The curve of a segment of [`Sketch`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SketchCurve {
+    /// the line between the end points
+    Line,
+    /// the circle arc between the end points passing through the transit point
+    Arc(Point2),
+    /// the B-spline curve whose first and last control points are the end points
+    Spline(BSplineCurve<Point2>),
+    /// the NURBS curve between the end points, e.g. the projection of an existing curve
+    NURBS(NURBSCurve<Vector3>),
+}
+
+/// A planar sketch: points and curves between them in the local coordinates of a plane.
+///
+/// The points are shared by the curves, and the sketch is converted to the wires or the face
+/// whose vertices are shared in the same way. The coordinates `(x, y)` are placed at
+/// `origin + x * u + y * v` of a [`Plane`], where `origin` is the origin of the plane, `u` is the
+/// normalized u-axis, and `v` is the unit vector perpendicular to `u` in the plane.
+///
+/// The segments can be marked as references, e.g. the curves derived from the existing geometry
+/// by [`Sketch::derive`], which are the guides for the dimensions and are not converted to the
+/// wires or the face.
+/// # Examples
+/// ```
+/// use truck_modeling::{sketch::Sketch, *};
+///
+/// // the plate with a slot
+/// let mut sketch = Sketch::new();
+/// sketch.add_rectangle(Point2::new(0.0, 0.0), Point2::new(4.0, 2.0));
+/// sketch.add_slot(Point2::new(1.0, 1.0), Point2::new(3.0, 1.0), 0.5);
+///
+/// // placed on the yz-plane
+/// let plane = Plane::new(Point3::origin(), Point3::new(0.0, 1.0, 0.0), Point3::new(0.0, 0.0, 1.0));
+/// let face = sketch.to_face(&plane).unwrap();
+/// assert_eq!(face.boundaries().len(), 2);
+/// assert_eq!(face.boundaries()[1].len(), 4);
+/// assert_near!(face.oriented_surface().normal(0.5, 0.5), Vector3::unit_x());
+/// let plate: Solid = builder::tsweep(&face, Vector3::new(0.5, 0.0, 0.0));
+/// assert!(plate.is_geometric_consistent());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Sketch {
+    points: Vec<Point2>,
+    segments: Vec<([usize; 2], SketchCurve)>,
+    references: Vec<bool>,
+}
+
+/// the number of the divisions of the edges in the computation of the areas of the loops
+const AREA_DIVISION: usize = 16;
+/// the number of the divisions of the edges in the computation of the sections of the faces
+const SECTION_DIVISION: usize = 16;
+
+impl Sketch {
+    /// Returns the empty sketch.
+    #[inline(always)]
+    pub fn new() -> Self { Self::default() }
+    /// Returns the points.
+    #[inline(always)]
+    pub fn points(&self) -> &[Point2] { &self.points }
+    /// Returns the segments: the indices of the end points and the curves.
+    #[inline(always)]
+    pub fn segments(&self) -> &[([usize; 2], SketchCurve)] { &self.segments }
+    /// Returns whether the `idx`-th segment is a reference.
+    #[inline(always)]
+    pub fn is_reference(&self, idx: usize) -> bool { self.references[idx] }
+    /// Marks the `idx`-th segment as a reference or not.
+    #[inline(always)]
+    pub fn set_reference(&mut self, idx: usize, reference: bool) {
+        self.references[idx] = reference;
+    }
+
+    /// Adds `point` and returns its index.
+    ///
+    /// The index of the existing point is returned if `point` is near to it, so the curves
+    /// through the same position are connected.
+    pub fn add_point(&mut self, point: Point2) -> usize {
+        match self.points.iter().position(|p| p.near(&point)) {
+            Some(idx) => idx,
+            None => {
+                self.points.push(point);
+                self.points.len() - 1
+            }
+        }
+    }
+
+    fn add_segment(&mut self, p0: Point2, p1: Point2, curve: SketchCurve) -> usize {
+        let ends = [self.add_point(p0), self.add_point(p1)];
+        self.segments.push((ends, curve));
+        self.references.push(false);
+        self.segments.len() - 1
+    }
+
+    /// Adds the line from `p0` to `p1`, and returns the index of the segment.
+    /// # Panics
+    /// Panics if `p0` and `p1` are near.
+    pub fn add_line(&mut self, p0: Point2, p1: Point2) -> usize {
+        assert!(
+            !p0.near(&p1),
+            "the end points of the line must be distinct."
+        );
+        self.add_segment(p0, p1, SketchCurve::Line)
+    }
+
+    /// Adds the circle arc from `p0` to `p1` passing through `transit`, and returns the index of
+    /// the segment.
+    /// # Panics
+    /// Panics if the three points are collinear.
+    pub fn add_arc(&mut self, p0: Point2, p1: Point2, transit: Point2) -> usize {
+        let area = truck_base::polygon::orient(p0, transit, p1);
+        assert!(
+            !area.so_small() && !p0.near(&p1),
+            "the points of the arc must not be collinear."
+        );
+        self.add_segment(p0, p1, SketchCurve::Arc(transit))
+    }
+
+    /// Adds the cubic, or lower if there are not enough points, clamped uniform B-spline curve
+    /// with `control_points`, and returns the index of the segment.
+    /// # Panics
+    /// Panics if there are not two control points or the end points are near.
+    pub fn add_spline(&mut self, control_points: &[Point2]) -> usize {
+        assert!(
+            control_points.len() >= 2,
+            "the spline must have two control points."
+        );
+        let (p0, p1) = (control_points[0], control_points[control_points.len() - 1]);
+        assert!(
+            !p0.near(&p1),
+            "the end points of the spline must be distinct."
+        );
+        let degree = usize::min(3, control_points.len() - 1);
+        let knot_vec = KnotVec::uniform_knot(degree, control_points.len() - degree);
+        let curve = BSplineCurve::new(knot_vec, control_points.to_vec());
+        self.add_segment(p0, p1, SketchCurve::Spline(curve))
+    }
+
+    /// Adds the closed polygon with the vertices `points`, and returns the range of the indices
+    /// of the segments.
+    ///
+    /// The consecutive near points are regarded as one point.
+    pub fn add_polygon(&mut self, points: &[Point2]) -> Range<usize> {
+        let start = self.segments.len();
+        (0..points.len()).for_each(|i| {
+            let (p0, p1) = (points[i], points[(i + 1) % points.len()]);
+            if !p0.near(&p1) {
+                self.add_line(p0, p1);
+            }
+        });
+        start..self.segments.len()
+    }
+
+    /// Adds the axis-aligned rectangle with the diagonal corners, counterclockwise from
+    /// `corner0`, and returns the range of the indices of the segments.
+    pub fn add_rectangle(&mut self, corner0: Point2, corner1: Point2) -> Range<usize> {
+        let (p, q) = (corner0, corner1);
+        let points = [p, Point2::new(q.x, p.y), q, Point2::new(p.x, q.y)];
+        match (q.x - p.x) * (q.y - p.y) > 0.0 {
+            true => self.add_polygon(&points),
+            false => self.add_polygon(&[points[0], points[3], points[2], points[1]]),
+        }
+    }
+
+    /// Adds the counterclockwise regular polygon with `sides` vertices on the circle of `radius`
+    /// around `center`, the first vertex in the x-direction from `center`, and returns the range
+    /// of the indices of the segments.
+    /// # Panics
+    /// Panics if `sides` is less than 3.
+    pub fn add_regular_polygon(
+        &mut self,
+        center: Point2,
+        radius: f64,
+        sides: usize,
+    ) -> Range<usize> {
+        assert!(sides >= 3, "the polygon must have three sides.");
+        let points: Vec<Point2> = (0..sides)
+            .map(|i| {
+                let angle = 2.0 * std::f64::consts::PI * i as f64 / sides as f64;
+                center + Vector2::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect();
+        self.add_polygon(&points)
+    }
+
+    /// Adds the counterclockwise circle of `radius` around `center` by two semicircles, and
+    /// returns the range of the indices of the segments.
+    pub fn add_circle(&mut self, center: Point2, radius: f64) -> Range<usize> {
+        let start = self.segments.len();
+        let (x, y) = (Vector2::unit_x() * radius, Vector2::unit_y() * radius);
+        self.add_arc(center + x, center - x, center + y);
+        self.add_arc(center - x, center + x, center - y);
+        start..self.segments.len()
+    }
+
+    /// Adds the counterclockwise slot: the stadium whose semicircles of `radius` are around
+    /// `center0` and `center1`, and returns the range of the indices of the segments.
+    /// # Panics
+    /// Panics if the centers are near.
+    pub fn add_slot(&mut self, center0: Point2, center1: Point2, radius: f64) -> Range<usize> {
+        assert!(!center0.near(&center1), "the centers must be distinct.");
+        let start = self.segments.len();
+        let dir = (center1 - center0).normalize() * radius;
+        let left = Vector2::new(-dir.y, dir.x);
+        self.add_line(center0 - left, center1 - left);
+        self.add_arc(center1 - left, center1 + left, center1 + dir);
+        self.add_line(center1 + left, center0 + left);
+        self.add_arc(center0 + left, center0 - left, center0 - dir);
+        start..self.segments.len()
+    }
+
+    /// Returns the wires of the chains of the segments placed on `plane`.
+    ///
+    /// The chains are split at the points shared by other than two segments. The closed chains
+    /// follow the direction of their first segments. The reference segments are skipped.
+    /// # Examples
+    /// ```
+    /// use truck_modeling::{sketch::Sketch, *};
+    ///
+    /// let mut sketch = Sketch::new();
+    /// // the open chain of a line and a spline
+    /// sketch.add_line(Point2::new(0.0, 0.0), Point2::new(1.0, 0.0));
+    /// sketch.add_spline(&[
+    ///     Point2::new(1.0, 0.0),
+    ///     Point2::new(2.0, 1.0),
+    ///     Point2::new(3.0, -1.0),
+    ///     Point2::new(4.0, 0.0),
+    /// ]);
+    /// // the circle
+    /// sketch.add_circle(Point2::new(2.0, 3.0), 1.0);
+    ///
+    /// // placed on the plane z = 1
+    /// let plane = Plane::new(
+    ///     Point3::new(0.0, 0.0, 1.0),
+    ///     Point3::new(1.0, 0.0, 1.0),
+    ///     Point3::new(0.0, 1.0, 1.0),
+    /// );
+    /// let wires = sketch.to_wires(&plane);
+    /// assert_eq!(wires.len(), 2);
+    /// assert_eq!(wires[0].len(), 2);
+    /// assert!(!wires[0].is_closed());
+    /// assert_near!(wires[0].back_vertex().unwrap().get_point(), Point3::new(4.0, 0.0, 1.0));
+    /// assert_eq!(wires[1].len(), 2);
+    /// assert!(wires[1].is_closed());
+    /// wires[1].edge_iter().for_each(|edge| {
+    ///     let p = edge.oriented_curve().subs(0.5);
+    ///     assert_near!(p.distance(Point3::new(2.0, 3.0, 1.0)), 1.0);
+    /// });
+    /// ```
+    pub fn to_wires(&self, plane: &Plane) -> Vec<Wire> {
+        let (origin, u, v) = frame(plane);
+        let vertices: Vec<Vertex> = self
+            .points
+            .iter()
+            .map(|p| Vertex::new(origin + u * p.x + v * p.y))
+            .collect();
+        let edges: Vec<Edge> = self
+            .segments
+            .iter()
+            .map(|([i, j], curve)| {
+                let (v0, v1) = (&vertices[*i], &vertices[*j]);
+                let to_3d = |p: Point2| origin + u * p.x + v * p.y;
+                match curve {
+                    SketchCurve::Line => builder::line(v0, v1),
+                    SketchCurve::Arc(transit) => builder::circle_arc(v0, v1, to_3d(*transit)),
+                    SketchCurve::Spline(curve) => {
+                        let control_points = curve.control_points().iter().map(|p| to_3d(*p));
+                        let curve =
+                            BSplineCurve::new(curve.knot_vec().clone(), control_points.collect());
+                        Edge::new(v0, v1, Curve::BSplineCurve(curve))
+                    }
+                    SketchCurve::NURBS(curve) => {
+                        let curve = curve.non_rationalized();
+                        let control_points = curve.control_points().iter().map(|p| {
+                            let vec = origin.to_vec() * p.z + u * p.x + v * p.y;
+                            vec.extend(p.z)
+                        });
+                        let curve =
+                            BSplineCurve::new(curve.knot_vec().clone(), control_points.collect());
+                        Edge::new(v0, v1, Curve::NURBSCurve(NURBSCurve::new(curve)))
+                    }
+                }
+            })
+            .collect();
+        let mut incidences = vec![Vec::<usize>::new(); self.points.len()];
+        self.segments
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !self.references[*idx])
+            .for_each(|(idx, (ends, _))| {
+                incidences[ends[0]].push(idx);
+                incidences[ends[1]].push(idx);
+            });
+        let mut used = self.references.clone();
+        let walk = |start: usize, first: usize, used: &mut Vec<bool>| {
+            let mut wire = Wire::new();
+            let (mut point, mut idx) = (start, first);
+            loop {
+                used[idx] = true;
+                let [i, j] = self.segments[idx].0;
+                match i == point {
+                    true => wire.push_back(edges[idx].clone()),
+                    false => wire.push_back(edges[idx].inverse()),
+                }
+                point = if i == point { j } else { i };
+                if incidences[point].len() != 2 {
+                    break;
+                }
+                match incidences[point].iter().find(|idx| !used[**idx]) {
+                    Some(next) => idx = *next,
+                    None => break,
+                }
+            }
+            wire
+        };
+        let mut wires = Vec::new();
+        // the open chains from the branch points and the end points
+        for (point, incident) in incidences.iter().enumerate() {
+            if incident.len() == 2 {
+                continue;
+            }
+            for idx in incident.iter().copied() {
+                if !used[idx] {
+                    wires.push(walk(point, idx, &mut used));
+                }
+            }
+        }
+        // the closed chains
+        for idx in 0..self.segments.len() {
+            if !used[idx] {
+                wires.push(walk(self.segments[idx].0[0], idx, &mut used));
+            }
+        }
+        wires
+    }
+
+    /// Returns the face placed on `plane` bounded by the loops of the segments.
+    ///
+    /// The loop with the largest area is the outer boundary and the others are the holes. The
+    /// loops are reoriented so that the normal of the face is the normal of `plane`. The holes
+    /// are assumed to be inside the outer boundary and not to intersect each other. The reference
+    /// segments are skipped.
+    /// # Errors
+    /// Returns [`Error::InvalidSketch`] if the sketch has no loops, or the segments do not form
+    /// loops, i.e. some points are shared by other than two segments.
+    pub fn to_face(&self, plane: &Plane) -> Result<Face> {
+        let mut degrees = vec![0; self.points.len()];
+        self.segments
+            .iter()
+            .zip(&self.references)
+            .filter(|(_, reference)| !**reference)
+            .for_each(|(([i, j], _), _)| {
+                degrees[*i] += 1;
+                degrees[*j] += 1;
+            });
+        if degrees.iter().any(|d| *d != 0 && *d != 2) {
+            return Err(Error::InvalidSketch(
+                "the segments must form loops.".to_string(),
+            ));
+        }
+        let (origin, u, v) = frame(plane);
+        let to_2d = |p: Point3| Point2::new((p - origin).dot(u), (p - origin).dot(v));
+        let mut wires: Vec<(Wire, f64)> = self
+            .to_wires(plane)
+            .into_iter()
+            .map(|wire| {
+                let points: Vec<Point2> = wire
+                    .edge_iter()
+                    .flat_map(|edge| {
+                        let curve = edge.oriented_curve();
+                        let (t0, t1) = curve.parameter_range();
+                        (0..AREA_DIVISION).map(move |i| {
+                            let t = t0 + (t1 - t0) * i as f64 / AREA_DIVISION as f64;
+                            to_2d(curve.subs(t))
+                        })
+                    })
+                    .collect();
+                let area = (0..points.len()).fold(0.0, |sum, i| {
+                    let (p, q) = (points[i], points[(i + 1) % points.len()]);
+                    sum + p.to_vec().perp_dot(q.to_vec()) / 2.0
+                });
+                (wire, area)
+            })
+            .collect();
+        let outer = wires
+            .iter()
+            .enumerate()
+            .max_by(|(_, x), (_, y)| x.1.abs().total_cmp(&y.1.abs()))
+            .map(|(idx, _)| idx);
+        let outer = match outer {
+            Some(idx) if !wires[idx].1.so_small() => idx,
+            _ => return Err(Error::InvalidSketch("the sketch has no loops.".to_string())),
+        };
+        wires.swap(0, outer);
+        let wires: Vec<Wire> = wires
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (wire, area))| match (idx == 0) == (area > 0.0) {
+                true => wire,
+                false => wire.inverse(),
+            })
+            .collect();
+        let surface = Plane::new(origin, origin + u, origin + v);
+        Ok(Face::new(wires, Surface::Plane(surface)))
+    }
+
+    /// Returns the sketch of the references derived from the existing geometry: the projections
+    /// of `edges` onto `plane` and the sections of `faces` by `plane`, e.g. the guides for the
+    /// dimensions of the new features relative to the selected edges and faces of a solid.
+    ///
+    /// cf. [`Sketch::add_projected_edges`] and [`Sketch::add_sections`].
+    /// # Examples
+    /// ```
+    /// use truck_modeling::{sketch::Sketch, *};
+    ///
+    /// // the cylinder of radius 1 and height 2 around the z-axis
+    /// let circle = builder::circle(Point3::origin(), Vector3::unit_z(), 1.0);
+    /// let disk = builder::try_attach_plane(&[circle]).unwrap();
+    /// let cylinder: Solid = builder::tsweep(&disk, Vector3::new(0.0, 0.0, 2.0));
+    /// let shell = &cylinder.boundaries()[0];
+    ///
+    /// // the sketch on the plane z = 1 derived from the lateral faces
+    /// let plane = Plane::new(
+    ///     Point3::new(0.0, 0.0, 1.0),
+    ///     Point3::new(1.0, 0.0, 1.0),
+    ///     Point3::new(0.0, 1.0, 1.0),
+    /// );
+    /// let lateral: Vec<Face> = shell
+    ///     .face_iter()
+    ///     .filter(|face| !matches!(face.get_surface(), Surface::Plane(_)))
+    ///     .cloned()
+    ///     .collect();
+    /// let mut sketch = Sketch::derive(&plane, &[], &lateral);
+    /// // the section is the circle of radius 1 on the plane.
+    /// assert!(!sketch.segments().is_empty());
+    /// sketch.segments().iter().for_each(|([i, j], _)| {
+    ///     assert_near!(sketch.points()[*i].to_vec().magnitude(), 1.0);
+    ///     assert_near!(sketch.points()[*j].to_vec().magnitude(), 1.0);
+    /// });
+    ///
+    /// // the boss on the cylinder dimensioned by the section, which is not a part of the face
+    /// sketch.add_circle(Point2::new(0.0, 0.0), 0.5);
+    /// let face = sketch.to_face(&plane).unwrap();
+    /// assert_eq!(face.boundaries().len(), 1);
+    /// assert_eq!(face.boundaries()[0].len(), 2);
+    /// ```
+    pub fn derive(plane: &Plane, edges: &[Edge], faces: &[Face]) -> Self {
+        let mut sketch = Self::new();
+        sketch.add_projected_edges(edges, plane);
+        sketch.add_sections(faces, plane);
+        sketch
+    }
+
+    /// Adds the projections of `edges` onto `plane` as the reference segments, and returns the
+    /// range of the indices of the segments.
+    ///
+    /// The lines are projected to the lines and the other curves to the NURBS curves by
+    /// [`project_curve`]. The intersection curves are approximated by the B-spline curves before
+    /// the projections. The edges projected to the points, e.g. the lines perpendicular to
+    /// `plane`, are skipped.
+    /// # Examples
+    /// ```
+    /// use truck_modeling::{sketch::*, *};
+    ///
+    /// // the edges of the unit cube
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    /// let mut ids = std::collections::HashSet::new();
+    /// let edges: Vec<Edge> = cube.edge_iter().filter(|edge| ids.insert(edge.id())).collect();
+    /// assert_eq!(edges.len(), 12);
+    ///
+    /// // projected onto the xy-plane: the vertical edges are skipped, and the bottom and the top
+    /// // edges are projected to the same positions.
+    /// let plane = Plane::new(Point3::origin(), Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0));
+    /// let mut sketch = Sketch::new();
+    /// let range = sketch.add_projected_edges(&edges, &plane);
+    /// assert_eq!(range.len(), 8);
+    /// assert_eq!(sketch.points().len(), 4);
+    /// assert!(range.clone().all(|idx| sketch.is_reference(idx)));
+    /// assert!(range.clone().all(|idx| sketch.segments()[idx].1 == SketchCurve::Line));
+    /// // the references are not the wires.
+    /// assert!(sketch.to_wires(&plane).is_empty());
+    /// ```
+    pub fn add_projected_edges(&mut self, edges: &[Edge], plane: &Plane) -> Range<usize> {
+        let start = self.segments.len();
+        edges
+            .iter()
+            .for_each(|edge| self.add_reference(&edge.oriented_curve(), plane));
+        start..self.segments.len()
+    }
+
+    /// Adds the sections of `faces` by `plane` as the reference segments, and returns the range
+    /// of the indices of the segments.
+    ///
+    /// The intersection curves of the surfaces and `plane` are split at the points where the
+    /// boundaries of the faces cross `plane`, and the pieces inside the faces are added as
+    /// [`Sketch::add_projected_edges`]. The boundaries of the faces on `plane` are added instead
+    /// of the sections. The faces whose sections are not computed are skipped.
+    /// # Examples
+    /// ```
+    /// use truck_modeling::{sketch::*, *};
+    ///
+    /// // the unit cube
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    /// let faces: Vec<Face> = cube.face_iter().cloned().collect();
+    ///
+    /// // the section by the plane z = 0.5 is the square.
+    /// let plane = Plane::new(
+    ///     Point3::new(0.0, 0.0, 0.5),
+    ///     Point3::new(1.0, 0.0, 0.5),
+    ///     Point3::new(0.0, 1.0, 0.5),
+    /// );
+    /// let mut sketch = Sketch::new();
+    /// let range = sketch.add_sections(&faces, &plane);
+    /// assert_eq!(range.len(), 4);
+    /// assert_eq!(sketch.points().len(), 4);
+    /// assert!(range.clone().all(|idx| sketch.segments()[idx].1 == SketchCurve::Line));
+    ///
+    /// // the references become the wire if they are not references.
+    /// range.for_each(|idx| sketch.set_reference(idx, false));
+    /// let wires = sketch.to_wires(&plane);
+    /// assert_eq!(wires.len(), 1);
+    /// assert!(wires[0].is_closed());
+    /// ```
+    pub fn add_sections(&mut self, faces: &[Face], plane: &Plane) -> Range<usize> {
+        let start = self.segments.len();
+        faces.iter().for_each(|face| {
+            let surface = face.get_surface();
+            match surface.intersect(&Surface::Plane(*plane), TOLERANCE) {
+                Some(SurfaceIntersection::Coincident) => {
+                    let edges: Vec<Edge> = face.boundary_iters().into_iter().flatten().collect();
+                    self.add_projected_edges(&edges, plane);
+                }
+                Some(intersection) => {
+                    if let Some(pieces) = section_pieces(face, &surface, intersection, plane) {
+                        pieces
+                            .iter()
+                            .for_each(|curve| self.add_reference(curve, plane));
+                    }
+                }
+                None => {}
+            }
+        });
+        start..self.segments.len()
+    }
+
+    fn add_reference(&mut self, curve: &Curve, plane: &Plane) {
+        let sketch_curve = match curve {
+            Curve::Line(_) => SketchCurve::Line,
+            Curve::IntersectionCurve(_) => {
+                let bspcurve = custom::curve_approximation(curve, custom::APPROXIMATION_TOLERANCE);
+                let curve = Curve::BSplineCurve(bspcurve);
+                match project_curve(&curve, plane) {
+                    Some(curve) => SketchCurve::NURBS(curve),
+                    None => return,
+                }
+            }
+            _ => match project_curve(curve, plane) {
+                Some(curve) => SketchCurve::NURBS(curve),
+                None => return,
+            },
+        };
+        let (origin, u, v) = frame(plane);
+        let to_2d = |p: Point3| Point2::new((p - origin).dot(u), (p - origin).dot(v));
+        let (p0, p1) = (to_2d(curve.front()), to_2d(curve.back()));
+        if !p0.near(&p1) {
+            let idx = self.add_segment(p0, p1, sketch_curve);
+            self.references[idx] = true;
+        }
+    }
+}
+
+/// Projects `curve` onto `plane` orthogonally, and returns the projected curve in the local
+/// coordinates `(u, v)` of the plane, as the NURBS curve whose control points are the homogeneous
+/// coordinates `(u * w, v * w, w)`.
+///
+/// The projections of lines, B-spline curves and NURBS curves are exact since the projection is
+/// affine: the control points are projected and the knots and the weights are kept, so the
+/// projected curve has the same parameters as `curve`. The projections of the custom curves are
+/// not exact: they are approximated by the B-spline curves within
+/// [`custom::APPROXIMATION_TOLERANCE`] by [`Curve::lift_up`] before the projection.
+///
+/// Returns `None` if `curve` is an intersection curve, whose projection is not a NURBS curve.
+/// # Examples
+/// ```
+/// use truck_modeling::{sketch, *};
+/// use std::f64::consts::PI;
+///
+/// // the unit circle on the plane tilted by 60 degrees around the x-axis
+/// let wire = builder::circle(
+///     Point3::origin(),
+///     Vector3::new(0.0, -f64::sin(PI / 3.0), 0.5),
+///     1.0,
+/// );
+/// let xy_plane = Plane::new(Point3::origin(), Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0));
+/// wire.edge_iter().for_each(|edge| {
+///     let curve3d = edge.oriented_curve();
+///     let curve2d = sketch::project_curve(&curve3d, &xy_plane).unwrap();
+///     assert_eq!(curve2d.parameter_range(), curve3d.parameter_range());
+///     let (t0, t1) = curve2d.parameter_range();
+///     (0..=10).for_each(|i| {
+///         let t = t0 + (t1 - t0) * i as f64 / 10.0;
+///         // the ellipse whose minor radius is cos 60 degrees
+///         let p = curve2d.subs(t);
+///         assert_near!(p.x * p.x + p.y * p.y / 0.25, 1.0);
+///         let q = curve3d.subs(t);
+///         assert_near!(p, Point2::new(q.x, q.y));
+///     });
+/// });
+/// ```
+pub fn project_curve(curve: &Curve, plane: &Plane) -> Option<NURBSCurve<Vector3>> {
+    if matches!(curve, Curve::IntersectionCurve(_)) {
+        return None;
+    }
+    let (origin, u, v) = frame(plane);
+    let curve = curve.clone().lift_up();
+    let control_points = curve
+        .control_points()
+        .iter()
+        .map(|p| {
+            let vec = p.truncate() - origin.to_vec() * p.w;
+            Vector3::new(vec.dot(u), vec.dot(v), p.w)
+        })
+        .collect();
+    let curve = BSplineCurve::new(curve.knot_vec().clone(), control_points);
+    Some(NURBSCurve::new(curve))
+}
+
+/// Projects the oriented curve of `edge` onto `plane` as [`project_curve`].
+#[inline(always)]
+pub fn project_edge(edge: &Edge, plane: &Plane) -> Option<NURBSCurve<Vector3>> {
+    project_curve(&edge.oriented_curve(), plane)
+}
+
+/// Projects the edges of `wire` onto `plane` as [`project_curve`], in the order of the wire.
+///
+/// Returns `None` if some edges are not projected.
+/// # Examples
+/// ```
+/// use truck_modeling::{sketch, *};
+///
+/// // the square in the plane z = x
+/// let wire = builder::polyline(
+///     &[
+///         Point3::new(0.0, 0.0, 0.0),
+///         Point3::new(1.0, 0.0, 1.0),
+///         Point3::new(1.0, 1.0, 1.0),
+///         Point3::new(0.0, 1.0, 0.0),
+///     ],
+///     true,
+/// );
+/// // projected onto the yz-plane whose local coordinates are (y, z)
+/// let plane = Plane::new(Point3::origin(), Point3::new(0.0, 1.0, 0.0), Point3::new(0.0, 0.0, 1.0));
+/// let curves = sketch::project_wire(&wire, &plane).unwrap();
+/// assert_eq!(curves.len(), 4);
+/// assert_near!(curves[0].front(), Point2::new(0.0, 0.0));
+/// assert_near!(curves[0].back(), Point2::new(0.0, 1.0));
+/// assert_near!(curves[1].back(), Point2::new(1.0, 1.0));
+/// assert_near!(curves[3].back(), curves[0].front());
+/// ```
+pub fn project_wire(wire: &Wire, plane: &Plane) -> Option<Vec<NURBSCurve<Vector3>>> {
+    wire.edge_iter()
+        .map(|edge| project_edge(edge, plane))
+        .collect()
+}
+
+/// Returns the pieces of the intersection curves inside `face`, or `None` if the boundaries of
+/// `face` are not mapped to the parameter domain of `surface`.
+fn section_pieces(
+    face: &Face,
+    surface: &Surface,
+    intersection: SurfaceIntersection,
+    plane: &Plane,
+) -> Option<Vec<Curve>> {
+    // the boundaries on the parameter domain, and the points where they cross the plane
+    let (origin, normal) = (plane.origin(), plane.normal());
+    let distance = |p: Point3| (p - origin).dot(normal);
+    let mut crossings = Vec::<Point3>::new();
+    let mut samples = Vec::<Point3>::new();
+    let mut hint = None;
+    let mut polygons = Vec::<Vec<Point2>>::new();
+    for wire in face.absolute_boundaries() {
+        let mut polygon = Vec::new();
+        for edge in wire.edge_iter() {
+            let curve = edge.oriented_curve();
+            let (t0, t1) = curve.parameter_range();
+            let param = |i: usize| t0 + (t1 - t0) * i as f64 / SECTION_DIVISION as f64;
+            for i in 0..SECTION_DIVISION {
+                let point = curve.subs(param(i));
+                let (u, v) = surface.search_parameter(point, hint, 100)?;
+                hint = Some((u, v));
+                polygon.push(Point2::new(u, v));
+                samples.push(point);
+                let (mut a, mut b) = (param(i), param(i + 1));
+                let (da, db) = (distance(curve.subs(a)), distance(curve.subs(b)));
+                if (da < 0.0) == (db < 0.0) {
+                    continue;
+                }
+                // the bisection of the crossing
+                (0..64).for_each(|_| {
+                    let mid = (a + b) / 2.0;
+                    match (distance(curve.subs(mid)) < 0.0) == (da < 0.0) {
+                        true => a = mid,
+                        false => b = mid,
+                    }
+                });
+                crossings.push(curve.subs((a + b) / 2.0));
+            }
+        }
+        polygons.push(polygon);
+    }
+    let inside = |point: Point3| {
+        let uv = match surface.search_parameter(point, None, 100) {
+            Some((u, v)) => Point2::new(u, v),
+            None => return false,
+        };
+        polygons
+            .iter()
+            .any(|polygon| truck_base::polygon::on_boundary(polygon, uv))
+            || polygons
+                .iter()
+                .map(|polygon| truck_base::polygon::winding_number(polygon, uv))
+                .sum::<i32>()
+                != 0
+    };
+    let curves = match intersection {
+        SurfaceIntersection::Analytic(curves) => curves
+            .into_iter()
+            .map(|curve| match curve {
+                AnalyticCurve::Line {
+                    origin, direction, ..
+                } => {
+                    let params = samples.iter().map(|p| (p - origin).dot(direction));
+                    let t0 = params.clone().fold(f64::INFINITY, f64::min) - 1.0;
+                    let t1 = params.fold(f64::NEG_INFINITY, f64::max) + 1.0;
+                    curve.to_curve((t0, t1))
+                }
+                _ => curve.to_curve((0.0, 2.0 * std::f64::consts::PI)),
+            })
+            .collect(),
+        SurfaceIntersection::Numerical(curves) => curves,
+        SurfaceIntersection::Coincident => Vec::new(),
+    };
+    let mut pieces = Vec::new();
+    curves.into_iter().for_each(|mut curve| {
+        let (t0, t1) = curve.parameter_range();
+        let mut params: Vec<f64> = crossings
+            .iter()
+            .filter_map(|p| curve.search_nearest_parameter(*p, None, 100))
+            .filter(|t| t0 + TOLERANCE < *t && *t < t1 - TOLERANCE)
+            .collect();
+        // the closed curves are split at least once.
+        if curve.front().near(&curve.back()) {
+            params.push((t0 + t1) / 2.0);
+        }
+        params.sort_by(f64::total_cmp);
+        params.dedup_by(|a, b| (*a).near(b));
+        // The parameters of the rest may change by cutting, e.g. the lines.
+        let points: Vec<Point3> = params.into_iter().map(|t| curve.subs(t)).collect();
+        let mut split = Vec::new();
+        points.into_iter().for_each(|p| {
+            if let Some(t) = curve.search_nearest_parameter(p, None, 100) {
+                let rest = curve.cut(t);
+                split.push(std::mem::replace(&mut curve, rest));
+            }
+        });
+        split.push(curve);
+        split.into_iter().for_each(|piece| {
+            let (s0, s1) = piece.parameter_range();
+            if inside(piece.subs((s0 + s1) / 2.0)) {
+                pieces.push(piece);
+            }
+        });
+    });
+    Some(pieces)
+}
+
+/// the origin and the orthonormal axes of the placement
+fn frame(plane: &Plane) -> (Point3, Vector3, Vector3) {
+    let u = plane.u_axis().normalize();
+    let v = plane.v_axis();
+    (plane.origin(), u, (v - u * v.dot(u)).normalize())
+}
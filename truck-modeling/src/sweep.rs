@@ -244,6 +244,65 @@ impl<P: Clone, C: Clone, S: Clone> Sweep<P, C, S> for Face<P, C, S> {
     }
 }
 
+impl<P: Clone, C: Clone, S: Clone> Sweep<P, C, S> for Vec<Wire<P, C>> {
+    type Swept = Shell<P, C, S>;
+    /// Transforms the wires sharing vertices, e.g. the graph with branches, and creates
+    /// a non-manifold shell by connecting vertices and edges.
+    ///
+    /// The faces swept from the edges at a branching vertex share the edge swept from the vertex.
+    /// The edges contained in several wires are swept only once.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// use truck_modeling::topo_traits::*;
+    /// use shell::ShellCondition;
+    /// // T-junction
+    /// let v = Vertex::news(&[1, 2, 3, 4]);
+    /// let wires = vec![
+    ///     Wire::from(vec![Edge::new(&v[0], &v[1], 100), Edge::new(&v[1], &v[2], 110)]),
+    ///     Wire::from(vec![Edge::new(&v[1], &v[3], 120)]),
+    /// ];
+    /// let shell = wires.sweep(
+    ///     &move |i: &usize| *i + 4,
+    ///     &move |j: &usize| *j + 100,
+    ///     &usize::clone,
+    ///     &move |i: &usize, j: &usize| *i * 10 + j,
+    ///     &move |i: &usize, j: &usize| *i + *j,
+    /// );
+    /// assert_eq!(shell.len(), 3);
+    /// assert!(shell.is_connected());
+    /// assert_eq!(shell.shell_condition(), ShellCondition::Irregular);
+    ///
+    /// // the three faces share the edge swept from the branching vertex.
+    /// let edge = &shell[0].boundaries()[0][1];
+    /// assert_eq!(edge.get_curve(), 26);
+    /// assert_eq!(shell[1].boundaries()[0][3].id(), edge.id());
+    /// assert_eq!(shell[2].boundaries()[0][3].id(), edge.id());
+    /// ```
+    fn sweep<
+        FP: Fn(&P) -> P,
+        FC: Fn(&C) -> C,
+        FS: Fn(&S) -> S,
+        CP: Fn(&P, &P) -> C,
+        CC: Fn(&C, &C) -> S,
+    >(
+        &self,
+        point_mapping: &FP,
+        curve_mapping: &FC,
+        surface_mapping: &FS,
+        connect_points: &CP,
+        connect_curves: &CC,
+    ) -> Self::Swept {
+        merge_wires(self).sweep(
+            point_mapping,
+            curve_mapping,
+            surface_mapping,
+            connect_points,
+            connect_curves,
+        )
+    }
+}
+
 impl<P: Clone, C: Clone, S: Clone> Sweep<P, C, S> for Shell<P, C, S> {
     type Swept = Vec<Result<Solid<P, C, S>>>;
     /// Transforms a shell and tries to create solids by connecting vertices, edges and faces.
@@ -0,0 +1,364 @@
+use crate::*;
+use builder::TaperProfile;
+use errors::Error;
+use rustc_hash::FxHashMap as HashMap;
+use std::f64::consts::PI;
+
+fn unsupported(message: &str) -> Error { Error::UnsupportedTaper(message.to_string()) }
+
+fn collapsed() -> Error { unsupported("the profile collapses by the taper.") }
+
+/// the number of the segments of the samples checking the circle arcs
+const CIRCLE_DIVISION: usize = 8;
+
+/// the offset of an edge in the coordinates of the plane of the profile
+#[derive(Clone, Copy, Debug)]
+enum Offset {
+    Line { origin: Point2, direction: Vector2 },
+    Circle { center: Point2, radius: f64 },
+}
+
+impl Offset {
+    fn intersections(self, other: Offset) -> Vec<Point2> {
+        match (self, other) {
+            (
+                Offset::Line {
+                    origin: o0,
+                    direction: d0,
+                },
+                Offset::Line {
+                    origin: o1,
+                    direction: d1,
+                },
+            ) => {
+                let cross = d0.perp_dot(d1);
+                match cross.so_small() {
+                    true => Vec::new(),
+                    false => vec![o0 + d0 * ((o1 - o0).perp_dot(d1) / cross)],
+                }
+            }
+            (Offset::Line { origin, direction }, Offset::Circle { center, radius })
+            | (Offset::Circle { center, radius }, Offset::Line { origin, direction }) => {
+                let foot = origin + direction * (center - origin).dot(direction);
+                let h2 = radius * radius - (center - foot).magnitude2();
+                match h2 < -TOLERANCE2 {
+                    true => Vec::new(),
+                    false => {
+                        let h = f64::sqrt(f64::max(h2, 0.0));
+                        vec![foot + direction * h, foot - direction * h]
+                    }
+                }
+            }
+            (
+                Offset::Circle {
+                    center: c0,
+                    radius: r0,
+                },
+                Offset::Circle {
+                    center: c1,
+                    radius: r1,
+                },
+            ) => {
+                let dist = c0.distance(c1);
+                if dist.so_small() {
+                    return Vec::new();
+                }
+                let dir = (c1 - c0) / dist;
+                let a = (r0 * r0 - r1 * r1 + dist * dist) / (2.0 * dist);
+                let h2 = r0 * r0 - a * a;
+                match h2 < -TOLERANCE2 {
+                    true => Vec::new(),
+                    false => {
+                        let perp = Vector2::new(-dir.y, dir.x) * f64::sqrt(f64::max(h2, 0.0));
+                        vec![c0 + dir * a + perp, c0 + dir * a - perp]
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// an edge of the profile in the direction of its wire
+struct TaperEdge {
+    edge: Edge,
+    offset: Offset,
+    /// the unit tangents at the front and the back
+    tangents: [Vector2; 2],
+    /// the unit normals toward the inside at the front and the back
+    normals: [Vector2; 2],
+}
+
+/// the coordinates of the plane of the profile
+struct Frame {
+    origin: Point3,
+    axis: Vector3,
+    u_axis: Vector3,
+    v_axis: Vector3,
+}
+
+impl Frame {
+    fn new(origin: Point3, axis: Vector3) -> Self {
+        let u_axis = analytic::perpendicular(axis);
+        Self {
+            origin,
+            axis,
+            u_axis,
+            v_axis: axis.cross(u_axis),
+        }
+    }
+    fn to_2d(&self, pt: Point3) -> Result<Point2> {
+        let v = pt - self.origin;
+        match v.dot(self.axis).so_small() {
+            true => Ok(Point2::new(v.dot(self.u_axis), v.dot(self.v_axis))),
+            false => Err(unsupported(
+                "the profile must be on the plane perpendicular to the vector.",
+            )),
+        }
+    }
+    fn vector_2d(&self, v: Vector3) -> Vector2 {
+        Vector2::new(v.dot(self.u_axis), v.dot(self.v_axis))
+    }
+    fn to_3d(&self, pt: Point2) -> Point3 { self.origin + self.u_axis * pt.x + self.v_axis * pt.y }
+}
+
+/// the center and the radius of the circle through the points
+fn circle_through(pts: &[Point2]) -> Option<(Point2, f64)> {
+    let (a, b, c) = (pts[0], pts[pts.len() / 3], pts[2 * pts.len() / 3]);
+    let (ab, ac) = (b - a, c - a);
+    let det = 2.0 * ab.perp_dot(ac);
+    if det.so_small2() {
+        return None;
+    }
+    let center = a + Vector2::new(
+        ac.y * ab.magnitude2() - ab.y * ac.magnitude2(),
+        ab.x * ac.magnitude2() - ac.x * ab.magnitude2(),
+    ) / det;
+    let radius = center.distance(a);
+    match pts.iter().all(|pt| center.distance(*pt).near(&radius)) {
+        true => Some((center, radius)),
+        false => None,
+    }
+}
+
+fn samples(curve: &Curve) -> impl Iterator<Item = Point3> + '_ {
+    let (t0, t1) = curve.parameter_range();
+    (0..=CIRCLE_DIVISION).map(move |i| {
+        let t = t0 + (t1 - t0) * i as f64 / CIRCLE_DIVISION as f64;
+        curve.subs(t)
+    })
+}
+
+impl TaperEdge {
+    /// `side` is `1.0` if the left side of the edge seen from the axis is inside, and `-1.0` if not.
+    fn new(edge: &Edge, frame: &Frame, side: f64, distance: f64) -> Result<Self> {
+        let curve = edge.oriented_curve();
+        let (t0, t1) = curve.parameter_range();
+        let tangent = |t: f64| frame.vector_2d(curve.der(t)).normalize();
+        let tangents = [tangent(t0), tangent(t1)];
+        let normals = tangents.map(|t| Vector2::new(-t.y, t.x) * side);
+        let front = frame.to_2d(curve.subs(t0))?;
+        let offset = match &curve {
+            Curve::Line(_) => Offset::Line {
+                origin: front + normals[0] * distance,
+                direction: tangents[0],
+            },
+            Curve::BSplineCurve(_) | Curve::NURBSCurve(_) => {
+                let pts = samples(&curve)
+                    .map(|pt| frame.to_2d(pt))
+                    .collect::<Result<Vec<_>>>()?;
+                let (center, radius) = circle_through(&pts).ok_or_else(|| {
+                    unsupported("only the lines and the circle arcs can be tapered.")
+                })?;
+                let radius = match normals[0].dot(center - front) > 0.0 {
+                    true => radius - distance,
+                    false => radius + distance,
+                };
+                if radius < TOLERANCE {
+                    return Err(collapsed());
+                }
+                Offset::Circle { center, radius }
+            }
+            Curve::IntersectionCurve(_) => {
+                return Err(unsupported("the intersection curves cannot be tapered."))
+            }
+            Curve::Custom(_) => {
+                return Err(unsupported(
+                    "only the lines and the circle arcs can be tapered.",
+                ))
+            }
+        };
+        Ok(Self {
+            edge: edge.clone(),
+            offset,
+            tangents,
+            normals,
+        })
+    }
+}
+
+/// The offset point of the vertex between `prev` and `next`, the nearest intersection of the
+/// offset edges.
+fn corner(pt: Point2, prev: &TaperEdge, next: &TaperEdge, distance: f64) -> Result<Point2> {
+    let (t0, t1) = (prev.tangents[1], next.tangents[0]);
+    if t0.perp_dot(t1).so_small() && t0.dot(t1) > 0.0 {
+        return Ok(pt + next.normals[0] * distance);
+    }
+    prev.offset
+        .intersections(next.offset)
+        .into_iter()
+        .min_by(|p, q| pt.distance2(*p).total_cmp(&pt.distance2(*q)))
+        .ok_or_else(collapsed)
+}
+
+/// the difference of the angles in `(-PI, PI]`
+fn angle_diff(angle: f64) -> f64 {
+    let angle = angle.rem_euclid(2.0 * PI);
+    match angle > PI {
+        true => angle - 2.0 * PI,
+        false => angle,
+    }
+}
+
+/// The arc of the circle `(center, radius)` from `front` to `back`, turning around the center
+/// in the same direction and by about the same angle as `curve`.
+fn offset_arc(
+    curve: &Curve,
+    frame: &Frame,
+    (center, radius): (Point2, f64),
+    (front, back): (Point2, Point2),
+) -> Result<Curve> {
+    let pts = samples(curve)
+        .map(|pt| frame.to_2d(pt))
+        .collect::<Result<Vec<_>>>()?;
+    let turn = pts
+        .windows(2)
+        .map(|pair| (pair[0] - center).perp_dot(pair[1] - center))
+        .sum::<f64>();
+    let axis = match turn > 0.0 {
+        true => frame.axis,
+        false => -frame.axis,
+    };
+    // the angles in the frame of the circle arcs turning counterclockwise around `axis`
+    let (x, y) = geom_impls::circle_frame(axis);
+    let (x, y) = (frame.vector_2d(x), frame.vector_2d(y));
+    let angle = |pt: Point2| f64::atan2((pt - center).dot(y), (pt - center).dot(x));
+    let sweep = pts
+        .windows(2)
+        .map(|pair| angle_diff(angle(pair[1]) - angle(pair[0])))
+        .sum::<f64>();
+    let start = angle(pts[0]) + angle_diff(angle(front) - angle(pts[0]));
+    let end = start + sweep + angle_diff(angle(back) - start - sweep);
+    if end - start < TOLERANCE {
+        return Err(collapsed());
+    }
+    let arc =
+        geom_impls::circle_arc_by_center(frame.to_3d(center), axis, radius, Rad(start), Rad(end));
+    Ok(Curve::NURBSCurve(NURBSCurve::new(arc)))
+}
+
+/// Sweeps the profile tapered by `angle`. See [`builder::try_tsweep_with_taper`] for the details.
+pub(super) fn tsweep_with_taper<T: TaperProfile>(
+    elem: &T,
+    vector: Vector3,
+    angle: Rad<f64>,
+) -> Result<T::Swept> {
+    if vector.so_small() {
+        return Err(unsupported("the vector must not be zero."));
+    }
+    if angle.0.abs() >= PI / 2.0 {
+        return Err(unsupported(
+            "the taper angle must be less than the right angle.",
+        ));
+    }
+    let axis = vector.normalize();
+    let (wires, left_inside) = elem.taper_wires(axis)?;
+    let origin = match wires.iter().find_map(Wire::front_vertex) {
+        Some(v) => v.get_point(),
+        None => return Err(unsupported("the profile has no edges.")),
+    };
+    let frame = Frame::new(origin, axis);
+    let side = if left_inside { 1.0 } else { -1.0 };
+    let distance = vector.magnitude() * angle.0.tan();
+
+    let mut points = HashMap::<VertexID, Point2>::default();
+    let mut offsets = HashMap::<EdgeID, Offset>::default();
+    for wire in &wires {
+        let edges = wire
+            .edge_iter()
+            .map(|edge| TaperEdge::new(edge, &frame, side, distance))
+            .collect::<Result<Vec<_>>>()?;
+        let len = edges.len();
+        for (i, next) in edges.iter().enumerate() {
+            let vertex = next.edge.front();
+            let pt = frame.to_2d(vertex.get_point())?;
+            let new_pt = match wire.is_closed() || i > 0 {
+                true => corner(pt, &edges[(i + len - 1) % len], next, distance)?,
+                false => pt + next.normals[0] * distance,
+            };
+            if points.insert(vertex.id(), new_pt).is_some() {
+                return Err(unsupported("the wires must not share vertices."));
+            }
+        }
+        if let Some(last) = edges.last().filter(|_| !wire.is_closed()) {
+            let vertex = last.edge.back();
+            let pt = frame.to_2d(vertex.get_point())? + last.normals[1] * distance;
+            if points.insert(vertex.id(), pt).is_some() {
+                return Err(unsupported("the wires must not share vertices."));
+            }
+        }
+        edges.iter().for_each(|edge| {
+            offsets.insert(edge.edge.id(), edge.offset);
+        });
+    }
+
+    let mut curves = Vec::<(Curve, Curve)>::new();
+    for edge in wires.iter().flat_map(Wire::edge_iter) {
+        let curve = edge.get_curve();
+        let front = points[&edge.absolute_front().id()];
+        let back = points[&edge.absolute_back().id()];
+        let new_curve = match offsets[&edge.id()] {
+            Offset::Line { .. } => {
+                let (p, q) = (
+                    frame.to_2d(edge.absolute_front().get_point())?,
+                    frame.to_2d(edge.absolute_back().get_point())?,
+                );
+                if (back - front).dot(q - p) <= 0.0 || front.distance(back).so_small() {
+                    return Err(collapsed());
+                }
+                Curve::Line(Line(frame.to_3d(front), frame.to_3d(back)))
+            }
+            Offset::Circle { center, radius } => {
+                offset_arc(&curve, &frame, (center, radius), (front, back))?
+            }
+        };
+        curves.push((
+            curve,
+            new_curve.transformed(Matrix4::from_translation(vector)),
+        ));
+    }
+    let points: Vec<(Point3, Point3)> = wires
+        .iter()
+        .flat_map(Wire::vertex_iter)
+        .map(|v| (v.get_point(), frame.to_3d(points[&v.id()]) + vector))
+        .collect();
+    let trsl = Matrix4::from_translation(vector);
+    Ok(elem.sweep(
+        &|pt| {
+            let (_, new_pt) = points
+                .iter()
+                .find(|(p, _)| p == pt)
+                .expect("unknown vertex");
+            *new_pt
+        },
+        &|curve| {
+            let (_, new_curve) = curves
+                .iter()
+                .find(|(c, _)| c == curve)
+                .expect("unknown curve");
+            new_curve.clone()
+        },
+        &move |surface| surface.transformed(trsl),
+        &move |pt0, pt1| Curve::Line(Line(*pt0, *pt1)),
+        &builder::ruled_surface,
+    ))
+}
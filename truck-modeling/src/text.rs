@@ -0,0 +1,404 @@
+use crate::*;
+use errors::Error;
+use ttf_parser::{Face as FontFace, GlyphId, OutlineBuilder};
+
+/// A font with the TrueType or CFF outlines, read from the bytes of a TTF or OTF file.
+///
+/// The tables of the font are parsed by [`ttf_parser`].
+/// # Examples
+/// ```no_run
+/// use truck_modeling::{text::Font, *};
+///
+/// let font = Font::from_bytes(std::fs::read("DejaVuSans.ttf").unwrap()).unwrap();
+/// // the outlines of the label, 5 units high per em, on the xy-plane
+/// let glyphs = font.text_wires("No. 42", 5.0);
+/// // the engraved label: the faces of the glyphs swept downward
+/// let faces: Vec<Face> = glyphs
+///     .iter()
+///     .filter(|wires| !wires.is_empty())
+///     .map(|wires| builder::try_attach_plane(wires).unwrap())
+///     .collect();
+/// let tools: Vec<Solid> = faces
+///     .iter()
+///     .map(|face| builder::tsweep(face, -Vector3::unit_z()))
+///     .collect();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Font {
+    data: Vec<u8>,
+    /// whether the outer contours are clockwise, i.e. the outlines are TrueType ones
+    clockwise: bool,
+}
+
+/// the control points of the Bézier segments of a contour in the font units
+type Contour = Vec<Vec<Point2>>;
+
+fn invalid(message: &str) -> Error { Error::InvalidFont(message.to_string()) }
+
+impl Font {
+    /// Reads the font from the bytes of a TTF or OTF file.
+    /// # Errors
+    /// Returns [`Error::InvalidFont`] if the data is broken, or the font has no TrueType or CFF
+    /// outlines or no Unicode character map.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        let face = FontFace::parse(&data, 0).map_err(|error| invalid(&format!("{error}.")))?;
+        let tables = face.tables();
+        let clockwise = match (tables.glyf.is_some(), tables.cff.is_some()) {
+            (true, _) => true,
+            (false, true) => false,
+            _ => return Err(invalid("the font has no TrueType or CFF outlines.")),
+        };
+        let unicode = tables.cmap.is_some_and(|cmap| {
+            cmap.subtables
+                .into_iter()
+                .any(|subtable| subtable.is_unicode())
+        });
+        if !unicode {
+            return Err(invalid("the font has no Unicode character map."));
+        }
+        Ok(Font { data, clockwise })
+    }
+
+    /// the face parsed from the data checked by `from_bytes`
+    #[inline(always)]
+    fn face(&self) -> FontFace<'_> { FontFace::parse(&self.data, 0).unwrap() }
+
+    /// Returns the number of the font units per em.
+    #[inline(always)]
+    pub fn units_per_em(&self) -> u16 { self.face().units_per_em() }
+
+    /// Returns the index of the glyph of `ch`, `0` for the missing glyph if not in the font.
+    #[inline(always)]
+    pub fn glyph_index(&self, ch: char) -> u16 {
+        self.face().glyph_index(ch).unwrap_or(GlyphId(0)).0
+    }
+
+    /// Returns the closed wires of the outlines of the glyphs of `text` on the xy-plane, grouped
+    /// by the characters.
+    ///
+    /// The em is scaled to `size`, the text starts from the origin on the baseline `y = 0`, and
+    /// the lines separated by `'\n'` go down by the line height of the font. The edges are the
+    /// lines and the quadratic or cubic Bézier curves of the outlines. The outer wires of the
+    /// glyphs are counterclockwise and the wires of the holes are clockwise, so the wires of each
+    /// glyph without separated parts, e.g. `o` but not `i`, form a face by
+    /// [`builder::try_attach_plane`]. The groups of the whitespaces and the broken glyphs are
+    /// empty.
+    pub fn text_wires(&self, text: &str, size: f64) -> Vec<Vec<Wire>> {
+        let face = self.face();
+        let scale = size / face.units_per_em() as f64;
+        let line_height = face.ascender() as f64 - face.descender() as f64 + face.line_gap() as f64;
+        let mut cursor = Vector2::zero();
+        text.chars()
+            .map(|ch| {
+                if ch == '\n' {
+                    cursor = Vector2::new(0.0, cursor.y - line_height * scale);
+                    return Vec::new();
+                }
+                let glyph = face.glyph_index(ch).unwrap_or(GlyphId(0));
+                let origin = cursor;
+                cursor.x += face.glyph_hor_advance(glyph).unwrap_or(0) as f64 * scale;
+                let mut outline = Outline {
+                    contours: Vec::new(),
+                    current: Point2::origin(),
+                };
+                if face.outline_glyph(glyph, &mut outline).is_none() {
+                    return Vec::new();
+                }
+                let to_3d = |p: Point2| {
+                    let p = Point2::from_vec(p.to_vec() * scale + origin);
+                    Point3::new(p.x, p.y, 0.0)
+                };
+                outline
+                    .contours
+                    .iter()
+                    .filter_map(|contour| contour_wire(contour, to_3d, self.clockwise))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// the outline of a glyph, built by [`FontFace::outline_glyph`]
+#[derive(Debug)]
+struct Outline {
+    contours: Vec<Contour>,
+    current: Point2,
+}
+
+impl Outline {
+    fn push(&mut self, points: &[Point2]) {
+        let segment = std::iter::once(self.current).chain(points.iter().copied());
+        if let Some(contour) = self.contours.last_mut() {
+            contour.push(segment.collect());
+        }
+        self.current = points[points.len() - 1];
+    }
+}
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.contours.push(Vec::new());
+        self.current = Point2::new(x as f64, y as f64);
+    }
+    fn line_to(&mut self, x: f32, y: f32) { self.push(&[Point2::new(x as f64, y as f64)]) }
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.push(&[
+            Point2::new(x1 as f64, y1 as f64),
+            Point2::new(x as f64, y as f64),
+        ])
+    }
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push(&[
+            Point2::new(x1 as f64, y1 as f64),
+            Point2::new(x2 as f64, y2 as f64),
+            Point2::new(x as f64, y as f64),
+        ])
+    }
+    fn close(&mut self) {
+        let start = match self.contours.last().and_then(|contour| contour.first()) {
+            Some(segment) => segment[0],
+            None => return,
+        };
+        if self.current != start {
+            self.push(&[start]);
+        }
+    }
+}
+
+/// Returns the counterclockwise wire of the outer contour or the clockwise wire of the hole,
+/// or `None` if the contour is degenerate.
+fn contour_wire(
+    contour: &Contour,
+    to_3d: impl Fn(Point2) -> Point3,
+    clockwise: bool,
+) -> Option<Wire> {
+    // the segments whose start and end are the same points are dropped.
+    let segments: Vec<Vec<Point3>> = contour
+        .iter()
+        .map(|segment| segment.iter().copied().map(&to_3d).collect::<Vec<_>>())
+        .filter(|points| points.iter().any(|p| !p.near(&points[0])))
+        .collect();
+    if segments.len() < 2 {
+        return None;
+    }
+    let vertices: Vec<Vertex> = segments
+        .iter()
+        .map(|points| Vertex::new(points[0]))
+        .collect();
+    let wire: Wire = (0..segments.len())
+        .map(|i| {
+            let (v0, v1) = (&vertices[i], &vertices[(i + 1) % vertices.len()]);
+            match segments[i].len() {
+                2 => builder::line(v0, v1),
+                len => {
+                    let mut control_points = segments[i].clone();
+                    control_points[len - 1] = v1.get_point();
+                    let curve = BSplineCurve::new(KnotVec::bezier_knot(len - 1), control_points);
+                    Edge::new(v0, v1, Curve::BSplineCurve(curve))
+                }
+            }
+        })
+        .collect();
+    match clockwise {
+        true => Some(wire.inverse()),
+        false => Some(wire),
+    }
+}
+
+#[cfg(test)]
+fn test_font() -> Vec<u8> {
+    let be16 = |x: i32| (x as u16).to_be_bytes().to_vec();
+    // the simple glyph of the contours of the points with the flags of on-curve
+    let glyph = |contours: &[&[(i32, i32, bool)]]| {
+        let mut bytes = be16(contours.len() as i32);
+        bytes.extend([0u8; 8]);
+        let mut end = -1;
+        contours.iter().for_each(|contour| {
+            end += contour.len() as i32;
+            bytes.extend(be16(end));
+        });
+        bytes.extend(be16(0));
+        let points: Vec<_> = contours.iter().flat_map(|contour| contour.iter()).collect();
+        bytes.extend(points.iter().map(|(_, _, on)| *on as u8));
+        let (mut x, mut y) = (0, 0);
+        points.iter().for_each(|(px, _, _)| {
+            bytes.extend(be16(px - x));
+            x = *px;
+        });
+        points.iter().for_each(|(_, py, _)| {
+            bytes.extend(be16(py - y));
+            y = *py;
+        });
+        if bytes.len() % 2 == 1 {
+            bytes.push(0);
+        }
+        bytes
+    };
+    // the clockwise square with the counterclockwise hole, and the diamond of off-curve points
+    let square = glyph(&[
+        &[
+            (0, 0, true),
+            (0, 500, true),
+            (500, 500, true),
+            (500, 0, true),
+        ],
+        &[
+            (100, 100, true),
+            (400, 100, true),
+            (400, 400, true),
+            (100, 400, true),
+        ],
+    ]);
+    let round = glyph(&[&[
+        (0, 0, false),
+        (0, 500, false),
+        (500, 500, false),
+        (500, 0, false),
+    ]]);
+    let glyf = [square.clone(), round.clone()].concat();
+    let loca = [0, 0, square.len(), square.len() + round.len()]
+        .iter()
+        .flat_map(|x| be16(*x as i32 / 2))
+        .collect::<Vec<_>>();
+    let mut head = vec![0u8; 54];
+    head[18..20].copy_from_slice(&be16(1000));
+    let mut hhea = vec![0u8; 36];
+    hhea[4..6].copy_from_slice(&be16(800));
+    hhea[6..8].copy_from_slice(&be16(-200));
+    hhea[34..36].copy_from_slice(&be16(3));
+    let hmtx = [be16(600), be16(0)].concat().repeat(3);
+    let maxp = [be16(0), be16(0x5000), be16(3)].concat();
+    // 'A' and 'B' to the glyphs 1 and 2
+    let cmap = [
+        [0, 1, 3, 1].map(be16).concat(),
+        0x0000_000Cu32.to_be_bytes().to_vec(),
+        [4, 32, 0, 4, 4, 1, 0].map(be16).concat(),
+        [66, 0xFFFF, 0, 65, 0xFFFF, 1 - 65, 1, 0, 0]
+            .map(be16)
+            .concat(),
+    ]
+    .concat();
+    let tables: [(&[u8; 4], Vec<u8>); 7] = [
+        (b"cmap", cmap),
+        (b"glyf", glyf),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"loca", loca),
+        (b"maxp", maxp),
+    ];
+    let mut font = [0x0001_0000u32.to_be_bytes().to_vec(), be16(7), vec![0; 6]].concat();
+    let mut offset = 12 + 16 * tables.len();
+    tables.iter().for_each(|(tag, table)| {
+        font.extend(tag.iter());
+        font.extend([0; 4]);
+        font.extend((offset as u32).to_be_bytes());
+        font.extend((table.len() as u32).to_be_bytes());
+        offset += (table.len() + 3) / 4 * 4;
+    });
+    tables.iter().for_each(|(_, table)| {
+        font.extend(table);
+        font.extend(vec![0; (4 - table.len() % 4) % 4]);
+    });
+    font
+}
+
+#[test]
+fn text_outlines() {
+    let font = Font::from_bytes(test_font()).unwrap();
+    assert_eq!(font.units_per_em(), 1000);
+    assert_eq!(font.glyph_index('A'), 1);
+    assert_eq!(font.glyph_index('B'), 2);
+    assert_eq!(font.glyph_index('C'), 0);
+    assert!(Font::from_bytes(test_font()[..100].to_vec()).is_err());
+
+    let glyphs = font.text_wires("AB\nCA", 2.0);
+    assert_eq!(
+        glyphs.iter().map(Vec::len).collect::<Vec<_>>(),
+        [2, 1, 0, 0, 2]
+    );
+    // the square with the hole is a face whose normal is the z-axis.
+    let face = builder::try_attach_plane(&glyphs[0]).unwrap();
+    assert_near!(face.oriented_surface().normal(0.5, 0.5), Vector3::unit_z());
+    let area = |wire: &Wire| {
+        let points: Vec<Point3> = wire.vertex_iter().map(|v| v.get_point()).collect();
+        (0..points.len()).fold(0.0, |sum, i| {
+            let (p, q) = (points[i], points[(i + 1) % points.len()]);
+            sum + (p.x * q.y - p.y * q.x) / 2.0
+        })
+    };
+    assert_near!(area(&glyphs[0][0]), 1.0);
+    assert_near!(area(&glyphs[0][1]), -0.36);
+    // the quadratic curves through the implied points, after the advance of 1.2
+    let round = &glyphs[1][0];
+    assert_eq!(round.len(), 4);
+    assert!(round.is_closed());
+    round.vertex_iter().for_each(|v| {
+        let p = v.get_point() - Vector3::new(1.7, 0.5, 0.0);
+        assert_near!(p.x.abs() + p.y.abs(), 0.5);
+    });
+    // the next line by the line height 2.0, after the missing glyph
+    let bdb: BoundingBox<Point3> = glyphs[4][0].vertex_iter().map(|v| v.get_point()).collect();
+    assert_near!(*bdb.min(), Point3::new(1.2, -2.0, 0.0));
+    assert_near!(*bdb.max(), Point3::new(2.2, -1.0, 0.0));
+}
+
+#[test]
+fn real_font_outlines() {
+    // DejaVu Sans Mono, cf. `resources/font/LICENSE`
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../resources/font/DejaVuSansMono.ttf"
+    );
+    let font = Font::from_bytes(std::fs::read(path).unwrap()).unwrap();
+    assert_eq!(font.units_per_em(), 2048);
+    assert_ne!(font.glyph_index('o'), 0);
+    assert_eq!(font.glyph_index('\u{10FFFD}'), 0);
+
+    let glyphs = font.text_wires("oi8 \nB", 1.0);
+    assert_eq!(
+        glyphs.iter().map(Vec::len).collect::<Vec<_>>(),
+        [2, 2, 3, 0, 0, 3]
+    );
+    let area = |wire: &Wire| {
+        let points: Vec<Point3> = wire.vertex_iter().map(|v| v.get_point()).collect();
+        (0..points.len()).fold(0.0, |sum, i| {
+            let (p, q) = (points[i], points[(i + 1) % points.len()]);
+            sum + (p.x * q.y - p.y * q.x) / 2.0
+        })
+    };
+    // the outer wire of `o` is counterclockwise, the hole is clockwise, and they are curved.
+    let mut areas: Vec<f64> = glyphs[0].iter().map(area).collect();
+    areas.sort_by(f64::total_cmp);
+    assert!(areas[0] < 0.0 && areas[1] > 0.0);
+    assert!(glyphs[0].iter().flatten().any(|edge| {
+        matches!(edge.get_curve(), Curve::BSplineCurve(curve) if curve.degree() == 2)
+    }));
+    // the separated parts of `i` are both outer wires.
+    assert!(glyphs[1].iter().all(|wire| area(wire) > 0.0));
+
+    // `8` with the two holes is swept into a closed solid.
+    let face = builder::try_attach_plane(&glyphs[2]).unwrap();
+    assert_near!(face.oriented_surface().normal(0.5, 0.5), Vector3::unit_z());
+    let solid = builder::tsweep(&face, Vector3::unit_z());
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+
+    // the glyphs are placed by the advances of the monospace font and the line height.
+    let face = font.face();
+    let scale = 1.0 / 2048.0;
+    let advance = face
+        .glyph_hor_advance(GlyphId(font.glyph_index('8')))
+        .unwrap() as f64;
+    let bdb = |wires: &[Wire]| -> BoundingBox<Point3> {
+        wires
+            .iter()
+            .flat_map(Wire::vertex_iter)
+            .map(|v| v.get_point())
+            .collect()
+    };
+    let (first, third) = (bdb(&glyphs[0]), bdb(&font.text_wires("  o", 1.0)[2]));
+    assert_near!(third.min().x - first.min().x, 2.0 * advance * scale);
+    let line_height = face.ascender() as f64 - face.descender() as f64 + face.line_gap() as f64;
+    assert_near!(bdb(&glyphs[5]).min().y, -line_height * scale);
+}
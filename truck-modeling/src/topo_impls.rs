@@ -1,4 +1,4 @@
-use rustc_hash::FxHashMap as HashMap;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use truck_topology::*;
 
 pub(super) fn create_edge<P: Clone, C: Clone, CP: Fn(&P, &P) -> C>(
@@ -9,6 +9,18 @@ pub(super) fn create_edge<P: Clone, C: Clone, CP: Fn(&P, &P) -> C>(
     connect_points(&v0.get_point(), &v1.get_point())
 }
 
+/// Merges the wires sharing vertices, e.g. the branches of a graph, into one sequence of edges.
+/// The edges contained in several wires are merged into the first ones.
+pub(super) fn merge_wires<P, C>(wires: &[Wire<P, C>]) -> Wire<P, C> {
+    let mut ids = HashSet::<EdgeID<C>>::default();
+    wires
+        .iter()
+        .flatten()
+        .filter(|edge| ids.insert(edge.id()))
+        .cloned()
+        .collect()
+}
+
 pub(super) fn connect_vertices<P: Clone, C: Clone, CP: Fn(&P, &P) -> C>(
     v0: &Vertex<P>,
     v1: &Vertex<P>,
@@ -1,4 +1,5 @@
 use crate::*;
+use truck_base::bounding_box::BoundingBox;
 
 impl Ray {
     /// Returns the origin of the ray
@@ -370,6 +371,154 @@ impl Camera {
     }
 }
 
+impl StandardView {
+    /// Returns the pair of the normalized direction from the target to the eye
+    /// and the upward direction of the screen.
+    /// # Examples
+    /// ```
+    /// use truck_base::cgmath64::*;
+    /// use truck_platform::*;
+    /// let (eye_dir, up) = StandardView::Top.directions();
+    /// assert_eq!(eye_dir, Vector3::unit_y());
+    /// assert_eq!(up, -Vector3::unit_z());
+    /// ```
+    pub fn directions(self) -> (Vector3, Vector3) {
+        match self {
+            StandardView::Front => (Vector3::unit_z(), Vector3::unit_y()),
+            StandardView::Back => (-Vector3::unit_z(), Vector3::unit_y()),
+            StandardView::Top => (Vector3::unit_y(), -Vector3::unit_z()),
+            StandardView::Bottom => (-Vector3::unit_y(), Vector3::unit_z()),
+            StandardView::Left => (-Vector3::unit_x(), Vector3::unit_y()),
+            StandardView::Right => (Vector3::unit_x(), Vector3::unit_y()),
+            StandardView::Isometric => (Vector3::new(1.0, 1.0, 1.0).normalize(), Vector3::unit_y()),
+        }
+    }
+}
+
+impl Camera {
+    /// Returns the camera matrix, which looks at the target from the eye.
+    ///
+    /// This is the inverse of `Matrix4::look_at_rh`.
+    /// # Examples
+    /// ```
+    /// use truck_base::{assert_near, cgmath64::*, tolerance::*};
+    /// use truck_platform::*;
+    /// let matrix = Camera::look_at(
+    ///     Point3::new(1.0, 1.0, 1.0),
+    ///     Point3::origin(),
+    ///     Vector3::unit_y(),
+    /// );
+    /// let camera = Camera::perspective_camera(matrix, Rad(1.0), 0.1, 10.0);
+    /// assert_near!(camera.position(), Point3::new(1.0, 1.0, 1.0));
+    /// assert_near!(camera.eye_direction(), -Vector3::new(1.0, 1.0, 1.0).normalize());
+    /// ```
+    #[inline(always)]
+    pub fn look_at(eye: Point3, target: Point3, up: Vector3) -> Matrix4 {
+        Matrix4::look_at_rh(eye, target, up)
+            .invert()
+            .expect("the eye must not be the target and the up must not be the eye direction")
+    }
+
+    /// Creates a perspective camera which frames the bounding box from the standard view.
+    ///
+    /// The whole bounding sphere of the box is in the view volume with the aspect ratio `as_rat`.
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use truck_base::{bounding_box::BoundingBox, cgmath64::*};
+    /// use truck_platform::*;
+    /// let bdd_box: BoundingBox<Point3> = [
+    ///     Point3::new(-1.0, 0.0, 2.0),
+    ///     Point3::new(3.0, 2.0, 5.0),
+    /// ].iter().collect();
+    /// let camera = Camera::fitted_perspective_camera(&bdd_box, StandardView::Isometric, Rad(PI / 4.0), 1.5);
+    /// let projection = camera.projection(1.5);
+    /// for x in [-1.0, 3.0] {
+    ///     for y in [0.0, 2.0] {
+    ///         for z in [2.0, 5.0] {
+    ///             let p = projection.transform_point(Point3::new(x, y, z));
+    ///             assert!(-1.0 <= p.x && p.x <= 1.0);
+    ///             assert!(-1.0 <= p.y && p.y <= 1.0);
+    ///             assert!(0.0 <= p.z && p.z <= 1.0);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn fitted_perspective_camera<R: Into<Rad<f64>>>(
+        bdd_box: &BoundingBox<Point3>,
+        view: StandardView,
+        field_of_view: R,
+        as_rat: f64,
+    ) -> Camera {
+        let (center, radius) = bounding_sphere(bdd_box);
+        let fov = field_of_view.into().0;
+        let half_angle = f64::atan(f64::tan(fov / 2.0) * f64::min(as_rat, 1.0));
+        let distance = radius / f64::sin(half_angle);
+        let (eye_dir, up) = view.directions();
+        let matrix = Camera::look_at(center + eye_dir * distance, center, up);
+        // The depth is in [0, 1] only in the farther side of the harmonic mean of the clips.
+        let near_clip = (distance - radius) / 2.0;
+        Camera::perspective_camera(matrix, Rad(fov), near_clip, distance + radius * 1.01)
+    }
+
+    /// Creates a parallel camera which frames the bounding box from the standard view.
+    ///
+    /// The whole bounding sphere of the box is in the view volume with the aspect ratio `as_rat`.
+    /// # Examples
+    /// ```
+    /// use truck_base::{bounding_box::BoundingBox, cgmath64::*};
+    /// use truck_platform::*;
+    /// let bdd_box: BoundingBox<Point3> = [
+    ///     Point3::new(-1.0, 0.0, 2.0),
+    ///     Point3::new(3.0, 2.0, 5.0),
+    /// ].iter().collect();
+    /// let camera = Camera::fitted_parallel_camera(&bdd_box, StandardView::Front, 0.5);
+    /// let projection = camera.projection(0.5);
+    /// for x in [-1.0, 3.0] {
+    ///     for y in [0.0, 2.0] {
+    ///         for z in [2.0, 5.0] {
+    ///             let p = projection.transform_point(Point3::new(x, y, z));
+    ///             assert!(-1.0 <= p.x && p.x <= 1.0);
+    ///             assert!(-1.0 <= p.y && p.y <= 1.0);
+    ///             assert!(0.0 <= p.z && p.z <= 1.0);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn fitted_parallel_camera(
+        bdd_box: &BoundingBox<Point3>,
+        view: StandardView,
+        as_rat: f64,
+    ) -> Camera {
+        let (center, radius) = bounding_sphere(bdd_box);
+        let screen_size = 2.0 * radius / f64::min(as_rat, 1.0);
+        let distance = 2.0 * radius;
+        let (eye_dir, up) = view.directions();
+        let matrix = Camera::look_at(center + eye_dir * distance, center, up);
+        Camera::parallel_camera(
+            matrix,
+            screen_size,
+            distance - radius * 1.01,
+            distance + radius * 1.01,
+        )
+    }
+}
+
+/// Returns the center and the radius of the bounding sphere.
+/// The unit sphere is returned for the empty box.
+fn bounding_sphere(bdd_box: &BoundingBox<Point3>) -> (Point3, f64) {
+    match bdd_box.is_empty() {
+        true => (Point3::origin(), 1.0),
+        false => {
+            let radius = bdd_box.diameter() / 2.0;
+            match radius > 0.0 {
+                true => (bdd_box.center(), radius),
+                false => (bdd_box.center(), 1.0),
+            }
+        }
+    }
+}
+
 impl Default for Camera {
     #[inline(always)]
     fn default() -> Camera {
@@ -150,6 +150,27 @@ pub enum ProjectionType {
     Parallel,
 }
 
+/// the standard views for fitting cameras to shapes
+///
+/// The y-axis is the upward direction of the world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardView {
+    /// looks from the positive z-axis
+    Front,
+    /// looks from the negative z-axis
+    Back,
+    /// looks from the positive y-axis
+    Top,
+    /// looks from the negative y-axis
+    Bottom,
+    /// looks from the negative x-axis
+    Left,
+    /// looks from the positive x-axis
+    Right,
+    /// looks from the direction `(1, 1, 1)`
+    Isometric,
+}
+
 /// Camera
 ///
 /// A [`Scene`](./struct.Scene.html) holds only one `Camera`.
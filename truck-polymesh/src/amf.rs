@@ -0,0 +1,166 @@
+use crate::*;
+use std::io::{BufWriter, Write};
+type Result<T> = std::result::Result<T, errors::Error>;
+
+/// A material of AMF
+#[derive(Clone, Debug, PartialEq)]
+pub struct AMFMaterial {
+    /// the name of the material
+    pub name: String,
+    /// the color in RGBA, each component in `[0, 1]`
+    pub color: [f64; 4],
+}
+
+/// Writes the mesh as one object of AMF without materials.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions: vec![
+///             Point3::new(0.0, 0.0, 0.0),
+///             Point3::new(1.0, 0.0, 0.0),
+///             Point3::new(0.0, 1.0, 0.0),
+///             Point3::new(0.0, 0.0, 1.0),
+///         ],
+///         ..Default::default()
+///     },
+///     Faces::from_iter(&[&[0, 2, 1], &[0, 1, 3], &[0, 3, 2], &[1, 2, 3]]),
+/// );
+/// let mut amf = Vec::new();
+/// amf::write(&mesh, &mut amf).unwrap();
+/// let amf = String::from_utf8(amf).unwrap();
+/// assert_eq!(amf.matches("<vertex>").count(), 4);
+/// assert_eq!(amf.matches("<triangle>").count(), 4);
+/// ```
+#[inline(always)]
+pub fn write<W: Write>(mesh: &PolygonMesh, writer: W) -> Result<()> {
+    write_with_materials(mesh, &[], &[], writer)
+}
+
+/// Writes the mesh as one object of AMF, whose faces are divided into the volumes by materials.
+/// # Arguments
+/// - `face_materials`: the indices of the materials of the faces in the order of
+///   [`Faces::face_iter`]. If this is empty, the whole mesh is written as one volume
+///   without material.
+/// - `materials`: the materials, which are written with the ids from 1.
+/// # Errors
+/// Returns an error if the length of `face_materials` is not the number of faces or
+/// some index is out of range of `materials`.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use amf::AMFMaterial;
+/// let mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions: vec![
+///             Point3::new(0.0, 0.0, 0.0),
+///             Point3::new(1.0, 0.0, 0.0),
+///             Point3::new(0.0, 1.0, 0.0),
+///             Point3::new(0.0, 0.0, 1.0),
+///         ],
+///         ..Default::default()
+///     },
+///     Faces::from_iter(&[&[0, 2, 1], &[0, 1, 3], &[0, 3, 2], &[1, 2, 3]]),
+/// );
+/// let materials = [
+///     AMFMaterial {
+///         name: "red".to_string(),
+///         color: [1.0, 0.0, 0.0, 1.0],
+///     },
+///     AMFMaterial {
+///         name: "blue".to_string(),
+///         color: [0.0, 0.0, 1.0, 1.0],
+///     },
+/// ];
+/// let mut amf = Vec::new();
+/// amf::write_with_materials(&mesh, &[0, 0, 1, 0], &materials, &mut amf).unwrap();
+/// let amf = String::from_utf8(amf).unwrap();
+/// assert!(amf.contains("<volume materialid=\"1\">"));
+/// assert!(amf.contains("<volume materialid=\"2\">"));
+/// assert_eq!(amf.matches("<triangle>").count(), 4);
+/// ```
+pub fn write_with_materials<W: Write>(
+    mesh: &PolygonMesh,
+    face_materials: &[usize],
+    materials: &[AMFMaterial],
+    writer: W,
+) -> Result<()> {
+    let num_faces = mesh.faces().len();
+    if !face_materials.is_empty() && face_materials.len() != num_faces {
+        return Err(invalid_input("The number of materials is not the one of faces.").into());
+    }
+    if face_materials.iter().any(|i| *i >= materials.len()) {
+        return Err(invalid_input("The index of the material is out of range.").into());
+    }
+    let mut volumes = vec![Vec::new(); materials.len().max(1)];
+    mesh.face_iter().enumerate().for_each(|(i, face)| {
+        let volume = face_materials.get(i).copied().unwrap_or(0);
+        (2..face.len()).for_each(|k| {
+            volumes[volume].push([face[0].pos, face[k - 1].pos, face[k].pos]);
+        });
+    });
+
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+    writer.write_all(b"<amf unit=\"millimeter\" version=\"1.1\">\n")?;
+    for (i, material) in materials.iter().enumerate() {
+        writer.write_fmt(format_args!("  <material id=\"{}\">\n", i + 1))?;
+        writer.write_fmt(format_args!(
+            "    <metadata type=\"name\">{}</metadata>\n",
+            escape(&material.name)
+        ))?;
+        write_color(&mut writer, &material.color, "    ")?;
+        writer.write_all(b"  </material>\n")?;
+    }
+    writer.write_all(b"  <object id=\"0\">\n    <mesh>\n      <vertices>\n")?;
+    for p in mesh.positions() {
+        writer.write_fmt(format_args!(
+            "        <vertex><coordinates><x>{:.10e}</x><y>{:.10e}</y><z>{:.10e}</z></coordinates></vertex>\n",
+            p.x, p.y, p.z
+        ))?;
+    }
+    writer.write_all(b"      </vertices>\n")?;
+    for (i, volume) in volumes.iter().enumerate() {
+        if volume.is_empty() && !materials.is_empty() {
+            continue;
+        }
+        match materials.get(i) {
+            Some(material) => {
+                writer.write_fmt(format_args!("      <volume materialid=\"{}\">\n", i + 1))?;
+                write_color(&mut writer, &material.color, "        ")?;
+            }
+            None => writer.write_all(b"      <volume>\n")?,
+        }
+        for tri in volume {
+            writer.write_fmt(format_args!(
+                "        <triangle><v1>{}</v1><v2>{}</v2><v3>{}</v3></triangle>\n",
+                tri[0], tri[1], tri[2]
+            ))?;
+        }
+        writer.write_all(b"      </volume>\n")?;
+    }
+    writer.write_all(b"    </mesh>\n  </object>\n</amf>\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn invalid_input(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message)
+}
+
+fn write_color<W: Write>(writer: &mut W, color: &[f64; 4], indent: &str) -> Result<()> {
+    writer.write_fmt(format_args!(
+        "{indent}<color><r>{}</r><g>{}</g><b>{}</b><a>{}</a></color>\n",
+        color[0], color[1], color[2], color[3]
+    ))?;
+    Ok(())
+}
+
+fn escape(string: &str) -> String {
+    string
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,52 @@
+use crate::*;
+
+truck_base::impl_deep_size_without_heap!(StandardVertex, StandardAttribute);
+
+impl DeepSize for StandardAttributes {
+    fn heap_size(&self) -> usize {
+        self.positions.heap_size() + self.uv_coords.heap_size() + self.normals.heap_size()
+    }
+}
+
+impl<V: DeepSize> DeepSize for Faces<V> {
+    fn heap_size(&self) -> usize {
+        self.tri_faces.heap_size() + self.quad_faces.heap_size() + self.other_faces.heap_size()
+    }
+}
+
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let mut mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions: vec![
+///             Point3::new(0.0, 0.0, 0.0),
+///             Point3::new(1.0, 0.0, 0.0),
+///             Point3::new(0.0, 1.0, 0.0),
+///         ],
+///         ..Default::default()
+///     },
+///     Faces::from_iter(&[&[0, 1, 2]]),
+/// );
+/// let size = mesh.deep_size();
+/// assert!(size >= 3 * std::mem::size_of::<Point3>() + 3 * std::mem::size_of::<StandardVertex>());
+///
+/// // The size increases with the uv coordinates.
+/// mesh.push_uv_coord(Vector2::new(0.0, 0.0));
+/// assert!(mesh.deep_size() > size);
+/// ```
+impl<V: DeepSize, A: DeepSize> DeepSize for PolygonMesh<V, A> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.attributes.heap_size() + self.faces.heap_size() }
+}
+
+impl<P: DeepSize> DeepSize for PolylineCurve<P> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.0.heap_size() }
+}
+
+impl DeepSize for StructuredMesh {
+    fn heap_size(&self) -> usize {
+        self.positions.heap_size() + self.uv_division.heap_size() + self.normals.heap_size()
+    }
+}
@@ -319,6 +319,115 @@ impl<V: Copy> Faces<V> {
     }
 }
 
+impl<V: Copy + Ord> Faces<V> {
+    /// Rotates each face to start with the smallest vertex, and sorts the faces.
+    ///
+    /// The orientations of the faces are kept, so the faces with the same vertices in the same
+    /// cyclic order become equal. Hence, the canonicalized faces can be directly compared,
+    /// hashed, or deduplicated. The vertices are compared in the lexicographic order of
+    /// the indices of the position, the texture coordinate, and the normal.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let mut faces = Faces::<usize>::from_iter(&[
+    ///     [4, 2, 3].as_ref(),
+    ///     &[5, 3, 1, 2],
+    ///     &[2, 3, 4],
+    ///     &[3, 2, 4],
+    /// ]);
+    /// faces.canonicalize();
+    /// // the same triangles are adjacent, and the inverted one is distinguished.
+    /// assert_eq!(faces.tri_faces(), &vec![[2, 3, 4], [2, 3, 4], [2, 4, 3]]);
+    /// assert_eq!(faces.quad_faces(), &vec![[1, 2, 5, 3]]);
+    /// ```
+    pub fn canonicalize(&mut self) {
+        fn rotate<V: Ord>(face: &mut [V]) {
+            let min = (0..face.len()).min_by(|i, j| face[*i].cmp(&face[*j]));
+            if let Some(min) = min {
+                face.rotate_left(min);
+            }
+        }
+        self.face_iter_mut().for_each(rotate);
+        self.tri_faces.sort();
+        self.quad_faces.sort();
+        self.other_faces.sort();
+    }
+}
+
+#[test]
+fn canonicalize_standard_vertices() {
+    let mut faces = Faces::from_iter(&[
+        [(2, Some(1), None), (0, Some(2), None), (1, Some(0), None)].as_ref(),
+        &[(1, Some(4), None), (2, Some(5), None), (0, Some(3), None)],
+        &[
+            (4, None, Some(0)),
+            (3, None, Some(0)),
+            (2, None, Some(0)),
+            (1, None, Some(0)),
+            (0, None, Some(0)),
+        ],
+    ]);
+    faces.canonicalize();
+    let tri_faces = faces.tri_faces();
+    // the faces with the same positions are distinguished by the texture coordinates.
+    assert_eq!(
+        tri_faces[0].map(|v| (v.pos, v.uv)),
+        [(0, Some(2)), (1, Some(0)), (2, Some(1))]
+    );
+    assert_eq!(
+        tri_faces[1].map(|v| (v.pos, v.uv)),
+        [(0, Some(3)), (1, Some(4)), (2, Some(5))]
+    );
+    let positions: Vec<usize> = faces.other_faces()[0].iter().map(|v| v.pos).collect();
+    assert_eq!(positions, vec![0, 4, 3, 2, 1]);
+}
+
+impl Faces {
+    /// Returns the triangulation of the faces, whose polygons are triangulated by ear clipping
+    /// on their planes instead of the fans given by [`Faces::triangle_iter`].
+    ///
+    /// Concave polygons are also triangulated with no triangles outside the polygons.
+    /// The triangles are oriented in the same way as the original faces.
+    /// # Panics
+    /// Panics if a position index is out of the range of `positions`.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// // an arrowhead, whose fan from the vertex 0 makes a triangle outside
+    /// let positions = vec![
+    ///     Point3::new(1.0, 0.5, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(2.0, 0.5, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[&[0, 1, 2, 3]]);
+    /// let triangles = faces.triangulate(&positions);
+    /// assert_eq!(triangles.len(), 2);
+    /// // both triangles are counterclockwise, inside the arrowhead
+    /// triangles.tri_faces().iter().for_each(|tri| {
+    ///     let [a, b, c] = tri.map(|v| positions[v.pos]);
+    ///     assert!((b - a).cross(c - a).z > 0.0);
+    /// });
+    /// ```
+    pub fn triangulate(&self, positions: &[Point3]) -> Faces {
+        let mut tri_faces = self.tri_faces.clone();
+        let polygons = self
+            .quad_faces
+            .iter()
+            .map(AsRef::as_ref)
+            .chain(self.other_faces.iter().map(AsRef::as_ref));
+        polygons.for_each(|face: &[Vertex]| {
+            let polygon: Vec<Point3> = face.iter().map(|v| positions[v.pos]).collect();
+            tri_faces.extend(
+                triangulation::ear_clipping3d(&polygon)
+                    .into_iter()
+                    .map(|tri| tri.map(|i| face[i])),
+            );
+        });
+        Faces::from_tri_and_quad_faces(tri_faces, Vec::new())
+    }
+}
+
 impl<V> Default for Faces<V> {
     fn default() -> Self {
         Self {
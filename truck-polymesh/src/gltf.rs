@@ -0,0 +1,169 @@
+use crate::*;
+use std::io::{BufWriter, Write};
+type Result<T> = std::result::Result<T, errors::Error>;
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const TRIANGLES: u32 = 4;
+
+/// Writes the mesh as a glTF 2.0 file whose buffer is embedded as a data URI.
+///
+/// Each polygon is triangulated. The normals and the uv coordinates are output as
+/// `NORMAL` and `TEXCOORD_0` only if all vertices have them.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions: vec![
+///             Point3::new(0.0, 0.0, 0.0),
+///             Point3::new(1.0, 0.0, 0.0),
+///             Point3::new(1.0, 1.0, 0.0),
+///             Point3::new(0.0, 1.0, 0.0),
+///         ],
+///         ..Default::default()
+///     },
+///     Faces::from_iter(&[&[0, 1, 2, 3]]),
+/// );
+/// let mut gltf = Vec::new();
+/// gltf::write(&mesh, &mut gltf).unwrap();
+/// let gltf = String::from_utf8(gltf).unwrap();
+/// assert!(gltf.contains("\"version\":\"2.0\""));
+/// assert!(gltf.contains("\"count\":6")); // two triangles
+/// ```
+pub fn write<W: Write>(mesh: &PolygonMesh, writer: W) -> Result<()> {
+    let expanded = mesh.expands(|attr| attr);
+    let vertices = expanded.attributes();
+    let has_normal = !vertices.is_empty() && vertices.iter().all(|v| v.normal.is_some());
+    let has_uv = !vertices.is_empty() && vertices.iter().all(|v| v.uv_coord.is_some());
+    let indices: Vec<u32> = expanded
+        .faces()
+        .triangle_iter()
+        .flatten()
+        .map(|i| i as u32)
+        .collect();
+
+    let mut buffer = Vec::<u8>::new();
+    let mut views = Vec::<String>::new();
+    let mut accessors = Vec::<String>::new();
+    let mut attributes = Vec::<String>::new();
+    let mut push_view = |buffer: &mut Vec<u8>, data: Vec<u8>, target: u32| {
+        let view = format!(
+            "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{target}}}",
+            buffer.len(),
+            data.len(),
+        );
+        buffer.extend(data);
+        views.push(view);
+        views.len() - 1
+    };
+
+    let positions: Vec<[f32; 3]> = vertices
+        .iter()
+        .map(|v| v.position.cast::<f32>().unwrap().into())
+        .collect();
+    let (min, max) = positions.iter().fold(
+        ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]),
+        |(min, max), p| {
+            (
+                [min[0].min(p[0]), min[1].min(p[1]), min[2].min(p[2])],
+                [max[0].max(p[0]), max[1].max(p[1]), max[2].max(p[2])],
+            )
+        },
+    );
+    let view = push_view(
+        &mut buffer,
+        floats(positions.iter().flatten().copied()),
+        ARRAY_BUFFER,
+    );
+    let bounds = match positions.is_empty() {
+        true => String::new(),
+        false => format!(",\"min\":{min:?},\"max\":{max:?}"),
+    };
+    accessors.push(format!(
+        "{{\"bufferView\":{view},\"componentType\":{FLOAT},\"count\":{},\"type\":\"VEC3\"{bounds}}}",
+        positions.len(),
+    ));
+    attributes.push(format!("\"POSITION\":{}", accessors.len() - 1));
+    if has_normal {
+        let normals = vertices.iter().flat_map(|v| {
+            let n: [f32; 3] = v.normal.unwrap().cast::<f32>().unwrap().into();
+            n
+        });
+        let view = push_view(&mut buffer, floats(normals), ARRAY_BUFFER);
+        accessors.push(format!(
+            "{{\"bufferView\":{view},\"componentType\":{FLOAT},\"count\":{},\"type\":\"VEC3\"}}",
+            vertices.len(),
+        ));
+        attributes.push(format!("\"NORMAL\":{}", accessors.len() - 1));
+    }
+    if has_uv {
+        // The v-axis of glTF is downward.
+        let uvs = vertices.iter().flat_map(|v| {
+            let uv = v.uv_coord.unwrap();
+            [uv.x as f32, 1.0 - uv.y as f32]
+        });
+        let view = push_view(&mut buffer, floats(uvs), ARRAY_BUFFER);
+        accessors.push(format!(
+            "{{\"bufferView\":{view},\"componentType\":{FLOAT},\"count\":{},\"type\":\"VEC2\"}}",
+            vertices.len(),
+        ));
+        attributes.push(format!("\"TEXCOORD_0\":{}", accessors.len() - 1));
+    }
+    let index_bytes = indices.iter().flat_map(|i| i.to_le_bytes()).collect();
+    let view = push_view(&mut buffer, index_bytes, ELEMENT_ARRAY_BUFFER);
+    accessors.push(format!(
+        "{{\"bufferView\":{view},\"componentType\":{UNSIGNED_INT},\"count\":{},\"type\":\"SCALAR\"}}",
+        indices.len(),
+    ));
+
+    let mut writer = BufWriter::new(writer);
+    write!(
+        writer,
+        "{{\"asset\":{{\"version\":\"2.0\",\"generator\":\"truck\"}},\
+\"scene\":0,\"scenes\":[{{\"nodes\":[0]}}],\"nodes\":[{{\"mesh\":0}}],\
+\"meshes\":[{{\"primitives\":[{{\"attributes\":{{{}}},\"indices\":{},\"mode\":{TRIANGLES}}}]}}],\
+\"accessors\":[{}],\"bufferViews\":[{}],\
+\"buffers\":[{{\"byteLength\":{},\"uri\":\"data:application/octet-stream;base64,{}\"}}]}}",
+        attributes.join(","),
+        accessors.len() - 1,
+        accessors.join(","),
+        views.join(","),
+        buffer.len(),
+        base64(&buffer),
+    )?;
+    Ok(())
+}
+
+fn floats(iter: impl IntoIterator<Item = f32>) -> Vec<u8> {
+    iter.into_iter().flat_map(f32::to_le_bytes).collect()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut res = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    bytes.chunks(3).for_each(|chunk| {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        (0..4).for_each(|i| match i <= chunk.len() {
+            true => res.push(TABLE[(n >> (18 - 6 * i) & 63) as usize] as char),
+            false => res.push('='),
+        });
+    });
+    res
+}
+
+#[test]
+fn base64_test() {
+    assert_eq!(base64(b""), "");
+    assert_eq!(base64(b"f"), "Zg==");
+    assert_eq!(base64(b"fo"), "Zm8=");
+    assert_eq!(base64(b"foo"), "Zm9v");
+    assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+}
@@ -0,0 +1,394 @@
+//! A minimal writer of the tessellated JT files.
+//!
+//! The output is in the JT version 8.1 format and consists only of the following segments.
+//! - The LSG segment: the partition node, the range LOD node, and one tri-strip set shape node
+//! for each level of detail with the late loaded property atoms.
+//! - The shape LOD segments: one tri-strip set shape LOD element for each level of detail.
+//! Each triangle is written as a tri-strip with three vertices, and the positions and
+//! the normals are written as the lossless raw vertex data.
+//!
+//! B-rep, PMI, metadata, and compressions of the elements are not supported.
+
+use crate::*;
+use std::io::{BufWriter, Write};
+type Result<T> = std::result::Result<T, errors::Error>;
+
+/// The byte size of the file header.
+pub const HEADER_SIZE: usize = 80 + 1 + 4 + 4 + 16;
+/// The byte size of the segment header.
+pub const SEGMENT_HEADER_SIZE: usize = 16 + 4 + 4;
+/// The segment type of the logical scene graph.
+pub const LSG_SEGMENT_TYPE: i32 = 1;
+/// The segment type of the shape LOD with level 0.
+/// The level `n` has the segment type `SHAPE_LOD_SEGMENT_TYPE + n` up to the level 9.
+pub const SHAPE_LOD_SEGMENT_TYPE: i32 = 7;
+
+type Guid = (u32, u16, u16, [u8; 8]);
+
+const fn jt_guid(id: u32) -> Guid {
+    (
+        id,
+        0x2ac8,
+        0x11d1,
+        [0x9b, 0x6b, 0, 0x80, 0xc7, 0xbb, 0x59, 0x97],
+    )
+}
+
+const PARTITION_NODE: Guid = jt_guid(0x10dd103e);
+const RANGE_LOD_NODE: Guid = jt_guid(0x10dd104c);
+const TRI_STRIP_SET_SHAPE_NODE: Guid = jt_guid(0x10dd1077);
+const STRING_PROPERTY_ATOM: Guid = jt_guid(0x10dd106e);
+const TRI_STRIP_SET_SHAPE_LOD: Guid = jt_guid(0x10dd10ab);
+const LATE_LOADED_PROPERTY_ATOM: Guid = (
+    0xe0b05be5,
+    0xfbbd,
+    0x11d1,
+    [0xa3, 0xa7, 0, 0xaa, 0, 0xd1, 0x09, 0x54],
+);
+const END_OF_ELEMENTS: Guid = (0xffffffff, 0xffff, 0xffff, [0xff; 8]);
+
+const GROUP_GRAPH_NODE: u8 = 1;
+const SHAPE_GRAPH_NODE: u8 = 2;
+const SHAPE_LOD: u8 = 4;
+const BASE_PROPERTY: u8 = 5;
+
+/// the bindings of the vertex attributes
+const PER_VERTEX: i32 = 2;
+const NONE: i32 = 0;
+
+/// Writes the levels of detail of a mesh to the JT file.
+///
+/// `lods[0]` is the finest level, and up to ten levels can be written.
+/// Each polygon is triangulated, and the face normal is written for the vertices without normals.
+/// # Errors
+/// Returns an error if `lods` is empty or has more than ten levels.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions: vec![
+///             Point3::new(0.0, 0.0, 0.0),
+///             Point3::new(1.0, 0.0, 0.0),
+///             Point3::new(1.0, 1.0, 0.0),
+///             Point3::new(0.0, 1.0, 0.0),
+///         ],
+///         ..Default::default()
+///     },
+///     Faces::from_iter(&[&[0, 1, 2, 3]]),
+/// );
+/// let mut bytes = Vec::new();
+/// jt::write(&[mesh], &mut bytes).unwrap();
+/// assert_eq!(&bytes[..14], b"Version 8.1 JT");
+/// ```
+pub fn write<W: Write>(lods: &[PolygonMesh], writer: W) -> Result<()> {
+    if lods.is_empty() || lods.len() > 10 {
+        let error = std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "The number of levels of detail must be from 1 to 10.",
+        );
+        return Err(error.into());
+    }
+    let lsg_id = segment_guid(0);
+    let lod_ids: Vec<Guid> = (1..=lods.len()).map(|i| segment_guid(i as u32)).collect();
+    let mut segments = vec![(lsg_id, LSG_SEGMENT_TYPE, lsg_segment(lods, &lod_ids))];
+    lods.iter()
+        .zip(&lod_ids)
+        .enumerate()
+        .for_each(|(i, (mesh, id))| {
+            let segment_type = SHAPE_LOD_SEGMENT_TYPE + i as i32;
+            segments.push((*id, segment_type, shape_lod_segment(mesh)));
+        });
+
+    let toc_size = 4 + segments.len() * (16 + 4 + 4 + 4);
+    let mut bytes = Bytes::default();
+    bytes.0.extend(b"Version 8.1 JT");
+    bytes.0.resize(80, b' ');
+    bytes.u8(0);
+    bytes.i32(0);
+    bytes.i32(HEADER_SIZE as i32);
+    bytes.guid(lsg_id);
+
+    bytes.i32(segments.len() as i32);
+    let mut offset = HEADER_SIZE + toc_size;
+    segments.iter().for_each(|(id, segment_type, data)| {
+        let length = SEGMENT_HEADER_SIZE + data.len();
+        bytes.guid(*id);
+        bytes.i32(offset as i32);
+        bytes.i32(length as i32);
+        bytes.u32((*segment_type as u32) << 24);
+        offset += length;
+    });
+    segments.iter().for_each(|(id, segment_type, data)| {
+        bytes.guid(*id);
+        bytes.i32(*segment_type);
+        bytes.i32((SEGMENT_HEADER_SIZE + data.len()) as i32);
+        bytes.0.extend(data);
+    });
+
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(&bytes.0)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// little endian byte buffer
+#[derive(Clone, Debug, Default)]
+struct Bytes(Vec<u8>);
+
+impl Bytes {
+    fn u8(&mut self, x: u8) { self.0.push(x) }
+    fn i16(&mut self, x: i16) { self.0.extend(x.to_le_bytes()) }
+    fn u16(&mut self, x: u16) { self.0.extend(x.to_le_bytes()) }
+    fn i32(&mut self, x: i32) { self.0.extend(x.to_le_bytes()) }
+    fn u32(&mut self, x: u32) { self.0.extend(x.to_le_bytes()) }
+    fn f32(&mut self, x: f32) { self.0.extend(x.to_le_bytes()) }
+    fn guid(&mut self, (a, b, c, d): Guid) {
+        self.u32(a);
+        self.u16(b);
+        self.u16(c);
+        self.0.extend(d);
+    }
+    fn mbstring(&mut self, string: &str) {
+        let chars: Vec<u16> = string.encode_utf16().collect();
+        self.i32(chars.len() as i32);
+        chars.into_iter().for_each(|c| self.u16(c));
+    }
+    fn bbox(&mut self, bdd: &BoundingBox<Point3>) {
+        let (min, max) = match bdd.is_empty() {
+            true => (Point3::origin(), Point3::origin()),
+            false => (*bdd.min(), *bdd.max()),
+        };
+        [min, max]
+            .iter()
+            .for_each(|p| (0..3).for_each(|i| self.f32(p[i] as f32)));
+    }
+    fn count_range(&mut self, count: usize) {
+        self.i32(count as i32);
+        self.i32(count as i32);
+    }
+    /// Writes the element with the header, where `data` writes the contents after the object id.
+    fn element(&mut self, object_type: Guid, base_type: u8, data: impl FnOnce(&mut Bytes)) {
+        let mut element = Bytes::default();
+        element.guid(object_type);
+        element.u8(base_type);
+        data(&mut element);
+        self.i32(element.0.len() as i32);
+        self.0.extend(element.0);
+    }
+    fn end_of_elements(&mut self) {
+        self.i32(16 + 1);
+        self.guid(END_OF_ELEMENTS);
+        self.u8(0);
+    }
+}
+
+fn segment_guid(index: u32) -> Guid {
+    let mut last = [0; 8];
+    last[4..].copy_from_slice(&index.to_be_bytes());
+    // "truc", "k", "JT"
+    (0x63757274, 0x006b, 0x544a, last)
+}
+
+/// the unrolled triangles
+struct Triangles {
+    positions: Vec<Point3>,
+    normals: Vec<Vector3>,
+}
+
+impl Triangles {
+    fn new(mesh: &PolygonMesh) -> Self {
+        let (mut positions, mut normals) = (Vec::new(), Vec::new());
+        mesh.faces().triangle_iter().for_each(|tri| {
+            let p = tri.map(|v| mesh.positions()[v.pos]);
+            let face_normal = (p[1] - p[0]).cross(p[2] - p[0]);
+            let face_normal = match face_normal.so_small() {
+                true => Vector3::zero(),
+                false => face_normal.normalize(),
+            };
+            tri.iter().zip(p).for_each(|(v, p)| {
+                positions.push(p);
+                let normal = v
+                    .nor
+                    .and_then(|i| mesh.normals().as_slice().get(i).copied());
+                normals.push(normal.unwrap_or(face_normal));
+            });
+        });
+        Self { positions, normals }
+    }
+    fn len(&self) -> usize { self.positions.len() / 3 }
+    fn area(&self) -> f64 {
+        self.positions
+            .chunks(3)
+            .map(|p| (p[1] - p[0]).cross(p[2] - p[0]).magnitude() / 2.0)
+            .sum()
+    }
+}
+
+fn lsg_segment(lods: &[PolygonMesh], lod_ids: &[Guid]) -> Vec<u8> {
+    let triangles: Vec<Triangles> = lods.iter().map(Triangles::new).collect();
+    let bdd: BoundingBox<Point3> = lods.iter().flat_map(|mesh| mesh.positions()).collect();
+    let (partition_id, range_lod_id) = (1, 2);
+    let shape_ids: Vec<i32> = (0..lods.len() as i32).map(|i| 3 + i).collect();
+    let key_id = 3 + lods.len() as i32;
+    let value_ids: Vec<i32> = shape_ids.iter().map(|i| key_id + *i - 2).collect();
+
+    let mut bytes = Bytes::default();
+    // graph elements
+    bytes.element(PARTITION_NODE, GROUP_GRAPH_NODE, |bytes| {
+        group_node_data(bytes, partition_id, &[range_lod_id]);
+        bytes.i32(0);
+        bytes.mbstring("");
+        bytes.bbox(&bdd);
+        bytes.f32(triangles.first().map(Triangles::area).unwrap_or(0.0) as f32);
+        bytes.count_range(triangles.iter().map(|t| 3 * t.len()).max().unwrap_or(0));
+        bytes.count_range(lods.len() + 2);
+        bytes.count_range(triangles.iter().map(Triangles::len).max().unwrap_or(0));
+    });
+    bytes.element(RANGE_LOD_NODE, GROUP_GRAPH_NODE, |bytes| {
+        group_node_data(bytes, range_lod_id, &shape_ids);
+        // reserved fields of the LOD node
+        bytes.i32(0);
+        bytes.i32(0);
+        let diameter = match bdd.is_empty() {
+            true => 0.0,
+            false => bdd.diameter(),
+        };
+        bytes.i32(lods.len() as i32 - 1);
+        (1..lods.len()).for_each(|i| bytes.f32((diameter * 2.0 * i as f64) as f32));
+        let center = match bdd.is_empty() {
+            true => Point3::origin(),
+            false => bdd.center(),
+        };
+        (0..3).for_each(|i| bytes.f32(center[i] as f32));
+    });
+    triangles
+        .iter()
+        .zip(&shape_ids)
+        .for_each(|(triangles, id)| {
+            bytes.element(TRI_STRIP_SET_SHAPE_NODE, SHAPE_GRAPH_NODE, |bytes| {
+                base_node_data(bytes, *id);
+                let bdd: BoundingBox<Point3> = triangles.positions.iter().collect();
+                bytes.bbox(&bdd);
+                bytes.bbox(&bdd);
+                bytes.f32(triangles.area() as f32);
+                bytes.count_range(triangles.positions.len());
+                bytes.count_range(1);
+                bytes.count_range(triangles.len());
+                bytes.i32(triangles.positions.len() as i32 * 24);
+                bytes.f32(0.0);
+                vertex_bindings(bytes);
+            });
+        });
+    bytes.end_of_elements();
+
+    // property atom elements
+    bytes.element(STRING_PROPERTY_ATOM, BASE_PROPERTY, |bytes| {
+        base_property_atom_data(bytes, key_id);
+        bytes.mbstring("JT_LLPROP_SHAPEIMPL");
+    });
+    lod_ids
+        .iter()
+        .zip(&value_ids)
+        .enumerate()
+        .for_each(|(i, (guid, id))| {
+            bytes.element(LATE_LOADED_PROPERTY_ATOM, BASE_PROPERTY, |bytes| {
+                base_property_atom_data(bytes, *id);
+                bytes.guid(*guid);
+                bytes.i32(SHAPE_LOD_SEGMENT_TYPE + i as i32);
+            });
+        });
+    bytes.end_of_elements();
+
+    // property table
+    bytes.i16(1);
+    bytes.i32(shape_ids.len() as i32);
+    shape_ids
+        .iter()
+        .zip(&value_ids)
+        .for_each(|(shape_id, value_id)| {
+            bytes.i32(*shape_id);
+            bytes.i32(key_id);
+            bytes.i32(*value_id);
+            bytes.i32(0);
+        });
+    with_zlib_header(bytes.0)
+}
+
+fn base_node_data(bytes: &mut Bytes, id: i32) {
+    bytes.i32(id);
+    // node flags
+    bytes.u32(0);
+    // attribute count
+    bytes.i32(0);
+}
+
+fn group_node_data(bytes: &mut Bytes, id: i32, children: &[i32]) {
+    base_node_data(bytes, id);
+    bytes.i32(children.len() as i32);
+    children.iter().for_each(|child| bytes.i32(*child));
+}
+
+fn base_property_atom_data(bytes: &mut Bytes, id: i32) {
+    bytes.i32(id);
+    // state flags
+    bytes.u32(0);
+}
+
+fn vertex_bindings(bytes: &mut Bytes) {
+    // normal, texture coordinate, and color
+    bytes.i32(PER_VERTEX);
+    bytes.i32(NONE);
+    bytes.i32(NONE);
+    // no quantization of vertex, normal, texture coordinate, and color
+    (0..4).for_each(|_| bytes.u8(0));
+}
+
+/// The elements of the LSG segment have the header of the compression,
+/// which is written as the uncompressed one.
+fn with_zlib_header(data: Vec<u8>) -> Vec<u8> {
+    let mut bytes = Bytes::default();
+    // compression flag
+    bytes.i32(2);
+    // data length with the algorithm byte
+    bytes.i32(data.len() as i32 + 1);
+    // no compression
+    bytes.u8(1);
+    bytes.0.extend(data);
+    bytes.0
+}
+
+fn shape_lod_segment(mesh: &PolygonMesh) -> Vec<u8> {
+    let triangles = Triangles::new(mesh);
+    let mut bytes = Bytes::default();
+    bytes.element(TRI_STRIP_SET_SHAPE_LOD, SHAPE_LOD, |bytes| {
+        // version numbers of the base shape LOD and the vertex shape LOD
+        bytes.i16(1);
+        bytes.i16(1);
+        vertex_bindings(bytes);
+        // vertex based shape compressed rep data
+        bytes.i16(1);
+        vertex_bindings(bytes);
+        // primitive list indices by the null codec
+        let indices: Vec<i32> = (0..=triangles.len() as i32).map(|i| 3 * i).collect();
+        bytes.u8(0);
+        bytes.i32(indices.len() as i32);
+        indices.into_iter().for_each(|i| bytes.i32(i));
+        // lossless raw vertex data
+        let mut raw = Bytes::default();
+        triangles
+            .normals
+            .iter()
+            .zip(&triangles.positions)
+            .for_each(|(n, p)| {
+                (0..3).for_each(|i| raw.f32(n[i] as f32));
+                (0..3).for_each(|i| raw.f32(p[i] as f32));
+            });
+        bytes.i32(raw.0.len() as i32);
+        // the negative compressed size means that the data is not compressed.
+        bytes.i32(-(raw.0.len() as i32));
+        bytes.0.extend(raw.0);
+    });
+    bytes.end_of_elements();
+    bytes.0
+}
@@ -17,7 +17,10 @@ use serde::{Deserialize, Serialize};
 
 /// re-export `truck_base`.
 pub mod base {
-    pub use truck_base::{bounding_box::*, cgmath64::*, hash, hash::HashGen, tolerance::*};
+    pub use truck_base::{
+        bounding_box::*, bounding_sphere::*, cgmath64::*, deep_size::DeepSize, hash, hash::HashGen,
+        tolerance::*,
+    };
     pub use truck_geotrait::*;
 }
 pub use base::*;
@@ -53,7 +56,7 @@ pub struct StandardAttribute {
 }
 
 /// Index vertex of a face of the polygon mesh
-#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub struct StandardVertex {
     /// index of vertex's position
     pub pos: usize,
@@ -97,15 +100,24 @@ pub struct StructuredMesh {
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PolylineCurve<P>(pub Vec<P>);
 
+/// AMF output
+pub mod amf;
 mod attributes;
+mod deep_size;
 /// Defines errors
 pub mod errors;
 mod expand;
 /// Defines triangle
 pub mod faces;
+/// glTF output
+pub mod gltf;
+/// JT output
+pub mod jt;
 mod meshing_shape;
 /// wavefront obj I/O
 pub mod obj;
+/// PLY I/O
+pub mod ply;
 /// Defines [`PolygonMeshEditor`](./polygon_mesh/struct.PolygonMeshEditor.html).
 pub mod polygon_mesh;
 /// Defines generalized polyline curve.
@@ -113,3 +125,5 @@ pub mod polyline_curve;
 /// STL I/O
 pub mod stl;
 mod structured_mesh;
+/// polygon triangulation by ear clipping
+pub mod triangulation;
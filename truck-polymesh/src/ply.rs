@@ -0,0 +1,340 @@
+use crate::*;
+use std::io::{BufWriter, Read, Write};
+type Result<T> = std::result::Result<T, errors::Error>;
+
+/// Writes the mesh as an ASCII PLY file.
+///
+/// The vertices are the combinations of positions, uv coordinates and normals used by the faces.
+/// The normals and the uv coordinates, written as `nx, ny, nz` and `s, t`, are output only if
+/// all vertices have them.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let mesh = PolygonMesh::new(
+///     StandardAttributes {
+///         positions: vec![
+///             Point3::new(0.0, 0.0, 0.0),
+///             Point3::new(1.0, 0.0, 0.0),
+///             Point3::new(1.0, 1.0, 0.0),
+///             Point3::new(0.0, 1.0, 0.0),
+///         ],
+///         ..Default::default()
+///     },
+///     Faces::from_iter(&[&[0, 1, 2, 3]]),
+/// );
+/// let mut ply = Vec::new();
+/// ply::write(&mesh, &mut ply).unwrap();
+/// let read = ply::read(ply.as_slice()).unwrap();
+/// assert_eq!(read, mesh);
+/// ```
+pub fn write<W: Write>(mesh: &PolygonMesh, writer: W) -> Result<()> {
+    let expanded = mesh.expands(|attr| attr);
+    let vertices = expanded.attributes();
+    let has_normal = !vertices.is_empty() && vertices.iter().all(|v| v.normal.is_some());
+    let has_uv = !vertices.is_empty() && vertices.iter().all(|v| v.uv_coord.is_some());
+
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(b"ply\nformat ascii 1.0\ncomment written by truck\n")?;
+    writeln!(writer, "element vertex {}", vertices.len())?;
+    writer.write_all(b"property double x\nproperty double y\nproperty double z\n")?;
+    if has_normal {
+        writer.write_all(b"property double nx\nproperty double ny\nproperty double nz\n")?;
+    }
+    if has_uv {
+        writer.write_all(b"property double s\nproperty double t\n")?;
+    }
+    writeln!(writer, "element face {}", expanded.faces().len())?;
+    writer.write_all(b"property list uchar int vertex_indices\nend_header\n")?;
+    for v in vertices {
+        let p = v.position;
+        write!(writer, "{:?} {:?} {:?}", p.x, p.y, p.z)?;
+        if let (true, Some(n)) = (has_normal, v.normal) {
+            write!(writer, " {:?} {:?} {:?}", n.x, n.y, n.z)?;
+        }
+        if let (true, Some(uv)) = (has_uv, v.uv_coord) {
+            write!(writer, " {:?} {:?}", uv.x, uv.y)?;
+        }
+        writer.write_all(b"\n")?;
+    }
+    for face in expanded.face_iter() {
+        write!(writer, "{}", face.len())?;
+        for i in face {
+            write!(writer, " {i}")?;
+        }
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Result<Scalar> {
+        match name {
+            "char" | "int8" => Ok(Scalar::I8),
+            "uchar" | "uint8" => Ok(Scalar::U8),
+            "short" | "int16" => Ok(Scalar::I16),
+            "ushort" | "uint16" => Ok(Scalar::U16),
+            "int" | "int32" => Ok(Scalar::I32),
+            "uint" | "uint32" => Ok(Scalar::U32),
+            "float" | "float32" => Ok(Scalar::F32),
+            "double" | "float64" => Ok(Scalar::F64),
+            _ => Err(invalid_data(format!("unknown PLY type: {name}"))),
+        }
+    }
+    fn size(self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::F64 => 8,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Property {
+    name: String,
+    /// the type of the length if the property is a list
+    list: Option<Scalar>,
+    scalar: Scalar,
+}
+
+#[derive(Clone, Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+enum Body<'a> {
+    Ascii(std::str::SplitAsciiWhitespace<'a>),
+    Binary { bytes: &'a [u8], big_endian: bool },
+}
+
+impl<'a> Body<'a> {
+    fn next(&mut self, scalar: Scalar) -> Result<f64> {
+        match self {
+            Body::Ascii(tokens) => {
+                let token = tokens
+                    .next()
+                    .ok_or_else(|| invalid_data("unexpected end of PLY data"))?;
+                Ok(token.parse::<f64>()?)
+            }
+            Body::Binary { bytes, big_endian } => {
+                let size = scalar.size();
+                if bytes.len() < size {
+                    return Err(invalid_data("unexpected end of PLY data"));
+                }
+                let mut buf = [0u8; 8];
+                buf[..size].copy_from_slice(&bytes[..size]);
+                if *big_endian {
+                    buf[..size].reverse();
+                }
+                *bytes = &bytes[size..];
+                Ok(match scalar {
+                    Scalar::I8 => buf[0] as i8 as f64,
+                    Scalar::U8 => buf[0] as f64,
+                    Scalar::I16 => i16::from_le_bytes([buf[0], buf[1]]) as f64,
+                    Scalar::U16 => u16::from_le_bytes([buf[0], buf[1]]) as f64,
+                    Scalar::I32 => i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+                    Scalar::U32 => u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+                    Scalar::F32 => f32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+                    Scalar::F64 => f64::from_le_bytes(buf),
+                })
+            }
+        }
+    }
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, error).into()
+}
+
+fn parse_header(header: &str) -> Result<(Format, Vec<Element>)> {
+    let mut lines = header.lines();
+    if lines.next().map(str::trim) != Some("ply") {
+        return Err(invalid_data("PLY file must start with \"ply\"."));
+    }
+    let mut format = None;
+    let mut elements = Vec::<Element>::new();
+    for line in lines {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["format", "ascii", ..] => format = Some(Format::Ascii),
+            ["format", "binary_little_endian", ..] => format = Some(Format::BinaryLittleEndian),
+            ["format", "binary_big_endian", ..] => format = Some(Format::BinaryBigEndian),
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse()?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", len, scalar, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| invalid_data("property before element"))?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    list: Some(Scalar::parse(len)?),
+                    scalar: Scalar::parse(scalar)?,
+                });
+            }
+            ["property", scalar, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| invalid_data("property before element"))?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    list: None,
+                    scalar: Scalar::parse(scalar)?,
+                });
+            }
+            _ => {}
+        }
+    }
+    let format = format.ok_or_else(|| invalid_data("PLY format is not specified."))?;
+    Ok((format, elements))
+}
+
+/// Reads the mesh from a PLY file, in the ASCII or binary format.
+///
+/// The positions `x, y, z`, the normals `nx, ny, nz`, the uv coordinates `s, t` (or `u, v`,
+/// `texture_u, texture_v`) of the vertices, and the faces `vertex_indices` (or `vertex_index`)
+/// are read. The other elements and properties are skipped.
+pub fn read<R: Read>(mut reader: R) -> Result<PolygonMesh> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let end = bytes
+        .windows(10)
+        .position(|w| w == b"end_header")
+        .ok_or_else(|| invalid_data("PLY header is not terminated."))?;
+    let header = std::str::from_utf8(&bytes[..end]).map_err(invalid_data)?;
+    let (format, elements) = parse_header(header)?;
+    let rest = &bytes[end + 10..];
+    let rest = match rest.iter().position(|b| *b == b'\n') {
+        Some(i) => &rest[i + 1..],
+        None => &[],
+    };
+    let mut body = match format {
+        Format::Ascii => Body::Ascii(
+            std::str::from_utf8(rest)
+                .map_err(invalid_data)?
+                .split_ascii_whitespace(),
+        ),
+        _ => Body::Binary {
+            bytes: rest,
+            big_endian: format == Format::BinaryBigEndian,
+        },
+    };
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uv_coords = Vec::new();
+    let mut faces = Faces::default();
+    for element in &elements {
+        let find = |names: &[&str]| {
+            element
+                .properties
+                .iter()
+                .position(|p| names.contains(&p.name.as_str()))
+        };
+        let pos_idx = [find(&["x"]), find(&["y"]), find(&["z"])];
+        let nor_idx = [find(&["nx"]), find(&["ny"]), find(&["nz"])];
+        let uv_idx = [
+            find(&["s", "u", "texture_u"]),
+            find(&["t", "v", "texture_v"]),
+        ];
+        let face_idx = find(&["vertex_indices", "vertex_index"]);
+        for _ in 0..element.count {
+            let mut values = Vec::with_capacity(element.properties.len());
+            let mut indices = Vec::new();
+            for (i, property) in element.properties.iter().enumerate() {
+                match property.list {
+                    Some(len) => {
+                        let len = body.next(len)? as usize;
+                        let list = (0..len)
+                            .map(|_| body.next(property.scalar))
+                            .collect::<Result<Vec<_>>>()?;
+                        if Some(i) == face_idx {
+                            indices = list;
+                        }
+                        values.push(0.0);
+                    }
+                    None => values.push(body.next(property.scalar)?),
+                }
+            }
+            match element.name.as_str() {
+                "vertex" => {
+                    if let [Some(x), Some(y), Some(z)] = pos_idx {
+                        positions.push(Point3::new(values[x], values[y], values[z]));
+                    }
+                    if let [Some(x), Some(y), Some(z)] = nor_idx {
+                        normals.push(Vector3::new(values[x], values[y], values[z]));
+                    }
+                    if let [Some(u), Some(v)] = uv_idx {
+                        uv_coords.push(Vector2::new(values[u], values[v]));
+                    }
+                }
+                "face" if indices.len() >= 3 => {
+                    let face: Vec<StandardVertex> = indices
+                        .into_iter()
+                        .map(|i| {
+                            let i = i as usize;
+                            StandardVertex {
+                                pos: i,
+                                uv: (!uv_coords.is_empty()).then_some(i),
+                                nor: (!normals.is_empty()).then_some(i),
+                            }
+                        })
+                        .collect();
+                    faces.push(face);
+                }
+                _ => {}
+            }
+        }
+    }
+    PolygonMesh::try_new(
+        StandardAttributes {
+            positions,
+            uv_coords,
+            normals,
+        },
+        faces,
+    )
+}
+
+#[test]
+fn read_binary() {
+    let mut bytes = b"ply\nformat binary_little_endian 1.0\nelement vertex 3\n\
+property float x\nproperty float y\nproperty float z\nelement face 1\n\
+property list uchar uint vertex_indices\nend_header\n"
+        .to_vec();
+    [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+        .iter()
+        .flatten()
+        .for_each(|x| bytes.extend(x.to_le_bytes()));
+    bytes.push(3);
+    [0u32, 1, 2]
+        .iter()
+        .for_each(|i| bytes.extend(i.to_le_bytes()));
+    let mesh = read(bytes.as_slice()).unwrap();
+    assert_eq!(mesh.positions()[1], Point3::new(1.0, 0.0, 0.0));
+    assert_eq!(mesh.faces(), &Faces::from_iter(&[&[0, 1, 2]]));
+}
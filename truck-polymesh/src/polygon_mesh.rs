@@ -123,6 +123,14 @@ impl PolygonMesh {
     /// Creates the bounding box of the polygon mesh.
     #[inline(always)]
     pub fn bounding_box(&self) -> BoundingBox<Point3> { self.positions().iter().collect() }
+    /// Creates the minimal bounding sphere of the positions.
+    #[inline(always)]
+    pub fn bounding_sphere(&self) -> BoundingSphere { self.positions().iter().collect() }
+    /// Returns the principal axes of the positions.
+    ///
+    /// The positions are weighted equally, so the result depends on the density of the vertices.
+    #[inline(always)]
+    pub fn principal_axes(&self) -> PrincipalAxes { self.positions().iter().collect() }
 }
 
 impl Invertible for PolygonMesh {
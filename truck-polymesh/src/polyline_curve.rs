@@ -23,6 +23,11 @@ impl<P> FromIterator<P> for PolylineCurve<P> {
     fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self { Self(Vec::from_iter(iter)) }
 }
 
+impl<P: Quantize> Quantize for PolylineCurve<P> {
+    #[inline(always)]
+    fn quantize(&self, tol: f64, key: &mut QuantizedKey) { self.0.quantize(tol, key) }
+}
+
 impl<P: ControlPoint<f64>> ParametricCurve for PolylineCurve<P> {
     type Point = P;
     type Vector = P::Diff;
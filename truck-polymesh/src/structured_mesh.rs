@@ -1,5 +1,6 @@
 use crate::*;
 use errors::Error;
+use std::ops::Range;
 type Result<T> = std::result::Result<T, Error>;
 
 impl StandardVertex {
@@ -183,6 +184,196 @@ impl StructuredMesh {
             .map(|normals| normals.iter_mut().map(|arr| arr.as_mut()).collect())
     }
 
+    /// Returns the numbers of the rows and the columns of the lattice.
+    #[inline(always)]
+    pub fn size(&self) -> (usize, usize) {
+        (
+            self.positions.len(),
+            self.positions.first().map_or(0, Vec::len),
+        )
+    }
+
+    /// Returns the mesh with the lattice points at the parameters `u_div` x `v_div`.
+    ///
+    /// The parameters are the uv coordinates if `self` has the uv divisions,
+    /// and the indices of the rows and the columns otherwise.
+    /// The positions and the normals are interpolated bilinearly in each cell,
+    /// and the normals are normalized. The parameters out of the range are clamped.
+    /// The returned mesh has the uv divisions `(u_div, v_div)` if `self` has the uv divisions.
+    /// # Panics
+    /// Panic occurs if `self` has no lattice points.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let mesh = StructuredMesh::from_positions_and_uvs(
+    ///     vec![
+    ///         vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 2.0, 0.0)],
+    ///         vec![Point3::new(2.0, 0.0, 0.0), Point3::new(2.0, 2.0, 2.0)],
+    ///     ],
+    ///     (vec![0.0, 1.0], vec![0.0, 1.0]),
+    /// );
+    /// let resampled = mesh.resample(&[0.0, 0.5, 1.0], &[0.5]);
+    /// assert_eq!(resampled.size(), (3, 1));
+    /// assert_eq!(resampled.positions()[1][0], Point3::new(1.0, 1.0, 0.5));
+    /// assert_eq!(resampled.uv_division().unwrap().0, &vec![0.0, 0.5, 1.0]);
+    /// ```
+    pub fn resample(&self, u_div: &[f64], v_div: &[f64]) -> StructuredMesh {
+        let (m, n) = self.size();
+        assert!(m > 0 && n > 0, "the structured mesh has no lattice points");
+        let (udiv, vdiv) = match &self.uv_division {
+            Some((udiv, vdiv)) => (udiv.clone(), vdiv.clone()),
+            None => (
+                (0..m).map(|i| i as f64).collect(),
+                (0..n).map(|j| j as f64).collect(),
+            ),
+        };
+        let ucells: Vec<(usize, f64)> = u_div.iter().map(|u| locate(&udiv, *u)).collect();
+        let vcells: Vec<(usize, f64)> = v_div.iter().map(|v| locate(&vdiv, *v)).collect();
+        let positions = ucells
+            .iter()
+            .map(|ucell| {
+                vcells
+                    .iter()
+                    .map(|vcell| {
+                        let vec = bilinear(&self.positions, *ucell, *vcell, Point3::to_vec);
+                        Point3::from_vec(vec)
+                    })
+                    .collect()
+            })
+            .collect();
+        let normals = self.normals.as_ref().map(|normals| {
+            ucells
+                .iter()
+                .map(|ucell| {
+                    vcells
+                        .iter()
+                        .map(|vcell| {
+                            let normal = bilinear(normals, *ucell, *vcell, |n| n);
+                            match normal.magnitude2() > 0.0 {
+                                true => normal.normalize(),
+                                false => normal,
+                            }
+                        })
+                        .collect()
+                })
+                .collect()
+        });
+        StructuredMesh {
+            positions,
+            uv_division: self
+                .uv_division
+                .as_ref()
+                .map(|_| (u_div.to_vec(), v_div.to_vec())),
+            normals,
+        }
+    }
+
+    /// Divides each cell into `u_factor` x `v_factor` cells by [`StructuredMesh::resample`].
+    ///
+    /// The factor zero is regarded as one.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let mesh = StructuredMesh::from_positions(vec![
+    ///     vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+    ///     vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0)],
+    /// ]);
+    /// let refined = mesh.refine(2, 4);
+    /// assert_eq!(refined.size(), (3, 5));
+    /// assert_eq!(refined.positions()[1][2], Point3::new(0.5, 0.5, 0.25));
+    /// assert_eq!(refined.destruct().faces().len(), 8);
+    /// ```
+    pub fn refine(&self, u_factor: usize, v_factor: usize) -> StructuredMesh {
+        let (m, n) = self.size();
+        let subdivide = |div: Vec<f64>, factor: usize| -> Vec<f64> {
+            let factor = usize::max(factor, 1);
+            let mut res: Vec<f64> = div
+                .windows(2)
+                .flat_map(|w| {
+                    (0..factor).map(move |k| {
+                        let t = k as f64 / factor as f64;
+                        w[0] * (1.0 - t) + w[1] * t
+                    })
+                })
+                .collect();
+            res.extend(div.last());
+            res
+        };
+        let (udiv, vdiv) = match &self.uv_division {
+            Some((udiv, vdiv)) => (udiv.clone(), vdiv.clone()),
+            None => (
+                (0..m).map(|i| i as f64).collect(),
+                (0..n).map(|j| j as f64).collect(),
+            ),
+        };
+        self.resample(&subdivide(udiv, u_factor), &subdivide(vdiv, v_factor))
+    }
+
+    /// Keeps every `u_step`th row and every `v_step`th column, and the last ones.
+    ///
+    /// The step zero is regarded as one.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = (0..5)
+    ///     .map(|i| (0..4).map(|j| Point3::new(i as f64, j as f64, 0.0)).collect())
+    ///     .collect();
+    /// let mesh = StructuredMesh::from_positions(positions);
+    /// let coarse = mesh.coarsen(2, 2);
+    /// assert_eq!(coarse.size(), (3, 3));
+    /// assert_eq!(coarse.positions()[1][2], Point3::new(2.0, 3.0, 0.0));
+    /// ```
+    pub fn coarsen(&self, u_step: usize, v_step: usize) -> StructuredMesh {
+        let (m, n) = self.size();
+        let indices = |len: usize, step: usize| -> Vec<usize> {
+            let mut res: Vec<usize> = (0..len).step_by(usize::max(step, 1)).collect();
+            if len > 0 && res.last() != Some(&(len - 1)) {
+                res.push(len - 1);
+            }
+            res
+        };
+        self.select(&indices(m, u_step), &indices(n, v_step))
+    }
+
+    /// Returns the sub-patch of the lattice points in `rows` x `columns`.
+    /// # Panics
+    /// Panic occurs if the ranges are out of the lattice.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = (0..5)
+    ///     .map(|i| (0..4).map(|j| Point3::new(i as f64, j as f64, 0.0)).collect())
+    ///     .collect();
+    /// let udiv = (0..5).map(|i| i as f64 / 4.0).collect();
+    /// let vdiv = (0..4).map(|j| j as f64 / 3.0).collect();
+    /// let mesh = StructuredMesh::from_positions_and_uvs(positions, (udiv, vdiv));
+    /// let patch = mesh.sub_mesh(1..3, 2..4);
+    /// assert_eq!(patch.size(), (2, 2));
+    /// assert_eq!(patch.positions()[0][0], Point3::new(1.0, 2.0, 0.0));
+    /// assert_eq!(patch.uv_division().unwrap().0, &vec![0.25, 0.5]);
+    /// ```
+    pub fn sub_mesh(&self, rows: Range<usize>, columns: Range<usize>) -> StructuredMesh {
+        let (m, n) = self.size();
+        assert!(
+            rows.end <= m && columns.end <= n,
+            "the ranges are out of the lattice"
+        );
+        self.select(&rows.collect::<Vec<_>>(), &columns.collect::<Vec<_>>())
+    }
+
+    fn select(&self, rows: &[usize], columns: &[usize]) -> StructuredMesh {
+        StructuredMesh {
+            positions: select_matrix(&self.positions, rows, columns),
+            uv_division: self.uv_division.as_ref().map(|(udiv, vdiv)| {
+                (
+                    rows.iter().map(|i| udiv[*i]).collect(),
+                    columns.iter().map(|j| vdiv[*j]).collect(),
+                )
+            }),
+            normals: (self.normals.as_ref()).map(|normals| select_matrix(normals, rows, columns)),
+        }
+    }
+
     /// Creates new polygon mesh by destructing `self`.
     #[inline(always)]
     pub fn destruct(self) -> PolygonMesh {
@@ -232,6 +423,42 @@ impl StructuredMesh {
     }
 }
 
+fn select_matrix<T: Copy>(matrix: &[Vec<T>], rows: &[usize], columns: &[usize]) -> Vec<Vec<T>> {
+    rows.iter()
+        .map(|i| columns.iter().map(|j| matrix[*i][*j]).collect())
+        .collect()
+}
+
+/// Returns the index of the cell including `t` and the ratio in the cell.
+fn locate(div: &[f64], t: f64) -> (usize, f64) {
+    if div.len() < 2 {
+        return (0, 0.0);
+    }
+    let i = div[1..div.len() - 1].partition_point(|x| *x <= t);
+    let (t0, t1) = (div[i], div[i + 1]);
+    match t1 > t0 {
+        true => (i, f64::clamp((t - t0) / (t1 - t0), 0.0, 1.0)),
+        false => (i, 0.0),
+    }
+}
+
+/// Interpolates the values of the matrix bilinearly.
+fn bilinear<T: Copy>(
+    matrix: &[Vec<T>],
+    (i, s): (usize, f64),
+    (j, t): (usize, f64),
+    to_vec: impl Fn(T) -> Vector3,
+) -> Vector3 {
+    let at = |i: usize, j: usize| {
+        let row = &matrix[usize::min(i, matrix.len() - 1)];
+        to_vec(row[usize::min(j, row.len() - 1)])
+    };
+    at(i, j) * ((1.0 - s) * (1.0 - t))
+        + at(i + 1, j) * (s * (1.0 - t))
+        + at(i, j + 1) * ((1.0 - s) * t)
+        + at(i + 1, j + 1) * (s * t)
+}
+
 #[inline(always)]
 fn check_matrix_regularity<T>(matrix: &[Vec<T>]) -> Result<()> {
     for arr in matrix {
@@ -286,3 +513,39 @@ fn check_matrix_vectors_compatibility<T>(
     }
     Ok(())
 }
+
+#[test]
+fn refine_and_coarsen() {
+    let positions: Vec<Vec<Point3>> = (0..3)
+        .map(|i| {
+            (0..4)
+                .map(|j| Point3::new(i as f64, j as f64, (i * j) as f64))
+                .collect()
+        })
+        .collect();
+    let normals = (0..3)
+        .map(|i| {
+            (0..4)
+                .map(|j| Vector3::new(-(j as f64), -(i as f64), 1.0).normalize())
+                .collect()
+        })
+        .collect();
+    let udiv = vec![0.0, 0.5, 2.0];
+    let vdiv = vec![0.0, 1.0, 2.0, 3.0];
+    let mesh = StructuredMesh::new(positions.clone(), (udiv, vdiv), normals);
+    let refined = mesh.refine(3, 2);
+    assert_eq!(refined.size(), (7, 7));
+    let (udiv, vdiv) = refined.uv_division().unwrap();
+    assert_eq!(udiv[1], 0.5 / 3.0);
+    assert_eq!(udiv[4], 1.0);
+    assert_eq!(vdiv[3], 1.5);
+    refined
+        .normals()
+        .unwrap()
+        .iter()
+        .flatten()
+        .for_each(|n| assert!((n.magnitude() - 1.0).abs() < 1.0e-12));
+    let coarse = refined.coarsen(3, 2);
+    assert_eq!(coarse.positions(), &positions);
+    assert_eq!(coarse.uv_division(), mesh.uv_division());
+}
@@ -0,0 +1,300 @@
+use crate::*;
+use truck_base::polygon::{orient, signed_area};
+
+/// whether the open segments `ab` and `cd` cross properly
+fn crosses(a: Point2, b: Point2, c: Point2, d: Point2) -> bool {
+    let (d0, d1) = (orient(a, b, c), orient(a, b, d));
+    let (d2, d3) = (orient(c, d, a), orient(c, d, b));
+    d0 * d1 < 0.0 && d2 * d3 < 0.0
+}
+
+/// whether `m` is in the cone at `v` between the edges from `prev` and to `next`
+fn in_cone(prev: Point2, v: Point2, next: Point2, m: Point2) -> bool {
+    match orient(prev, v, next) >= 0.0 {
+        true => orient(v, m, prev) > 0.0 && orient(m, v, next) > 0.0,
+        false => !(orient(v, m, next) >= 0.0 && orient(m, v, prev) >= 0.0),
+    }
+}
+
+/// whether `p` is in the closed triangle `abc` oriented counterclockwise
+fn in_triangle(a: Point2, b: Point2, c: Point2, p: Point2) -> bool {
+    orient(a, b, p) >= 0.0 && orient(b, c, p) >= 0.0 && orient(c, a, p) >= 0.0
+}
+
+fn segments(ring: &[usize]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()]))
+}
+
+/// Connects `hole` to `ring` by a bridge from the rightmost vertex of the hole.
+fn bridge(points: &[Point2], ring: &mut Vec<usize>, hole: &[usize], others: &[Vec<usize>]) {
+    let m = (0..hole.len())
+        .max_by(|i, j| points[hole[*i]].x.total_cmp(&points[hole[*j]].x))
+        .unwrap();
+    let pm = points[hole[m]];
+    let visible = |k: usize| {
+        let pv = points[ring[k]];
+        let (prev, next) = (
+            ring[(k + ring.len() - 1) % ring.len()],
+            ring[(k + 1) % ring.len()],
+        );
+        in_cone(points[prev], pv, points[next], pm)
+            && std::iter::once(ring.as_slice())
+                .chain(std::iter::once(hole))
+                .chain(others.iter().map(Vec::as_slice))
+                .flat_map(segments)
+                .all(|(a, b)| !crosses(pm, pv, points[a], points[b]))
+    };
+    let mut candidates: Vec<usize> = (0..ring.len()).collect();
+    candidates.sort_by(|i, j| {
+        let (pi, pj) = (points[ring[*i]], points[ring[*j]]);
+        // the vertices on the right side of the hole are preferred.
+        (pi.x <= pm.x)
+            .cmp(&(pj.x <= pm.x))
+            .then(pi.distance2(pm).total_cmp(&pj.distance2(pm)))
+    });
+    let k = candidates
+        .iter()
+        .copied()
+        .find(|k| visible(*k))
+        .unwrap_or(candidates[0]);
+    let mut spliced = Vec::with_capacity(ring.len() + hole.len() + 2);
+    spliced.extend_from_slice(&ring[..=k]);
+    spliced.extend((0..=hole.len()).map(|i| hole[(m + i) % hole.len()]));
+    spliced.extend_from_slice(&ring[k..]);
+    *ring = spliced;
+}
+
+/// Clips the ears of the counterclockwise ring.
+fn clip_ears(points: &[Point2], mut ring: Vec<usize>) -> Vec<[usize; 3]> {
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+    // Starting from the vertex 1, convex polygons are triangulated to the fans from the vertex 0.
+    let mut i = 1;
+    while ring.len() > 3 {
+        let n = ring.len();
+        let triangle = |i: usize| [ring[(i + n - 1) % n], ring[i % n], ring[(i + 1) % n]];
+        let is_ear = |i: usize| {
+            let [a, b, c] = triangle(i);
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            orient(pa, pb, pc) > 0.0
+                && ring.iter().all(|v| {
+                    let p = points[*v];
+                    p == pa || p == pb || p == pc || !in_triangle(pa, pb, pc, p)
+                })
+        };
+        // If there are no ears because of the degeneracy, a convex vertex or any vertex is clipped.
+        let ear = (i..i + n)
+            .find(|i| is_ear(*i))
+            .or_else(|| {
+                (i..i + n).find(|i| {
+                    let [a, b, c] = triangle(*i);
+                    orient(points[a], points[b], points[c]) > 0.0
+                })
+            })
+            .unwrap_or(i)
+            % n;
+        triangles.push(triangle(ear));
+        ring.remove(ear);
+        i = ear;
+    }
+    if ring.len() == 3 {
+        triangles.push([ring[0], ring[1], ring[2]]);
+    }
+    triangles
+}
+
+/// Triangulates the polygon with holes by ear clipping.
+///
+/// The vertices are indexed serially from `outer` to the last hole, and the triangles are
+/// returned by these indices. The triangles are oriented in the same way as `outer`
+/// regardless of the orientations of the holes. The number of the triangles is
+/// `n + 2h - 2`, where `n` is the number of all vertices and `h` is the number of the holes.
+///
+/// Concave polygons are triangulated correctly as long as the boundaries do not intersect
+/// each other. The holes are connected to the outer boundary by bridges before clipping.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// // an L-shaped hexagon, whose fan from the vertex 0 makes a triangle outside
+/// let outer = vec![
+///     Point2::new(1.0, 1.0),
+///     Point2::new(0.0, 1.0),
+///     Point2::new(0.0, 0.0),
+///     Point2::new(2.0, 0.0),
+///     Point2::new(2.0, 2.0),
+///     Point2::new(1.0, 2.0),
+/// ];
+/// let triangles = triangulation::ear_clipping(&outer, &[]);
+/// assert_eq!(triangles.len(), 4);
+/// let area: f64 = triangles
+///     .iter()
+///     .map(|[a, b, c]| (outer[*b] - outer[*a]).perp_dot(outer[*c] - outer[*a]) / 2.0)
+///     .sum();
+/// assert!(area.near(&3.0));
+///
+/// // a square with a square hole
+/// let outer = vec![
+///     Point2::new(0.0, 0.0),
+///     Point2::new(3.0, 0.0),
+///     Point2::new(3.0, 3.0),
+///     Point2::new(0.0, 3.0),
+/// ];
+/// let hole = vec![
+///     Point2::new(1.0, 1.0),
+///     Point2::new(2.0, 1.0),
+///     Point2::new(2.0, 2.0),
+///     Point2::new(1.0, 2.0),
+/// ];
+/// let triangles = triangulation::ear_clipping(&outer, &[hole.clone()]);
+/// assert_eq!(triangles.len(), 8);
+/// let points: Vec<Point2> = outer.into_iter().chain(hole).collect();
+/// let area: f64 = triangles
+///     .iter()
+///     .map(|[a, b, c]| (points[*b] - points[*a]).perp_dot(points[*c] - points[*a]) / 2.0)
+///     .sum();
+/// assert!(area.near(&8.0));
+/// ```
+pub fn ear_clipping(outer: &[Point2], holes: &[Vec<Point2>]) -> Vec<[usize; 3]> {
+    let points: Vec<Point2> = outer
+        .iter()
+        .chain(holes.iter().flatten())
+        .copied()
+        .collect();
+    let mut ring: Vec<usize> = (0..outer.len()).collect();
+    let reversed = signed_area(outer) < 0.0;
+    if reversed {
+        ring.reverse();
+    }
+    let mut offset = outer.len();
+    let mut hole_rings: Vec<Vec<usize>> = holes
+        .iter()
+        .filter_map(|hole| {
+            let mut hole_ring: Vec<usize> = (offset..offset + hole.len()).collect();
+            offset += hole.len();
+            if signed_area(hole) > 0.0 {
+                hole_ring.reverse();
+            }
+            match hole_ring.is_empty() {
+                true => None,
+                false => Some(hole_ring),
+            }
+        })
+        .collect();
+    // The holes are bridged from the right, so that the bridges do not cross the other holes.
+    hole_rings.sort_by(|hole0, hole1| {
+        let max_x = |hole: &Vec<usize>| hole.iter().map(|i| points[*i].x).fold(f64::MIN, f64::max);
+        max_x(hole1).total_cmp(&max_x(hole0))
+    });
+    (0..hole_rings.len()).for_each(|i| {
+        bridge(&points, &mut ring, &hole_rings[i], &hole_rings[i + 1..]);
+    });
+    let mut triangles = clip_ears(&points, ring);
+    if reversed {
+        triangles.iter_mut().for_each(|tri| tri.swap(1, 2));
+    }
+    triangles
+}
+
+/// Triangulates the polygon in the space by ear clipping on the plane perpendicular
+/// to its normal given by Newell's method.
+///
+/// The triangles are returned by the indices of `polygon` and oriented in the same way
+/// as `polygon`.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// // a concave quadrangle, a dart, in the plane x + y + z = 1
+/// let polygon = vec![
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.4, 0.4, 0.2),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+/// ];
+/// let triangles = triangulation::ear_clipping3d(&polygon);
+/// // The reflex vertex 1 is shared by both triangles.
+/// assert_eq!(triangles.len(), 2);
+/// assert!(triangles.iter().all(|tri| tri.contains(&1)));
+/// ```
+pub fn ear_clipping3d(polygon: &[Point3]) -> Vec<[usize; 3]> {
+    let normal = (0..polygon.len()).fold(Vector3::zero(), |normal, i| {
+        let (p, q) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        normal + p.to_vec().cross(q.to_vec())
+    });
+    if normal.so_small() {
+        return (2..polygon.len()).map(|i| [0, i - 1, i]).collect();
+    }
+    let normal = normal.normalize();
+    let axis = match normal.x.abs() < 0.9 {
+        true => Vector3::unit_x(),
+        false => Vector3::unit_y(),
+    };
+    let u = axis.cross(normal).normalize();
+    let v = normal.cross(u);
+    let points: Vec<Point2> = polygon
+        .iter()
+        .map(|p| Point2::new(p.to_vec().dot(u), p.to_vec().dot(v)))
+        .collect();
+    ear_clipping(&points, &[])
+}
+
+#[test]
+fn star_with_holes() {
+    use std::f64::consts::PI;
+    let outer: Vec<Point2> = (0..24)
+        .map(|i| {
+            let (t, r) = (PI * i as f64 / 12.0, [1.0, 0.5][i % 2]);
+            Point2::new(r * f64::cos(t), r * f64::sin(t))
+        })
+        .collect();
+    let square = |c: Point2, cw: bool| {
+        let mut square = vec![
+            c + Vector2::new(-0.08, -0.08),
+            c + Vector2::new(0.08, -0.08),
+            c + Vector2::new(0.08, 0.08),
+            c + Vector2::new(-0.08, 0.08),
+        ];
+        if cw {
+            square.reverse();
+        }
+        square
+    };
+    let holes = vec![
+        square(Point2::new(0.2, 0.0), false),
+        square(Point2::new(-0.15, 0.15), true),
+        square(Point2::new(-0.1, -0.2), false),
+        square(Point2::new(0.2, 0.2), true),
+    ];
+    let points: Vec<Point2> = outer
+        .iter()
+        .chain(holes.iter().flatten())
+        .copied()
+        .collect();
+    let ring: Vec<usize> = (0..outer.len()).collect();
+    let area =
+        signed_area(&ring.iter().map(|&i| points[i]).collect::<Vec<_>>()) - 4.0 * 0.16 * 0.16;
+
+    let triangles = ear_clipping(&outer, &holes);
+    assert_eq!(triangles.len(), points.len() + 2 * holes.len() - 2);
+    triangles.iter().for_each(|tri| {
+        let [a, b, c] = tri.map(|i| points[i]);
+        assert!(orient(a, b, c) > 0.0);
+    });
+    let sum: f64 = triangles
+        .iter()
+        .map(|tri| signed_area(&tri.map(|i| points[i])))
+        .sum();
+    assert!(sum.near(&area), "{sum} {area}");
+
+    // clockwise outer boundary
+    let reversed: Vec<Point2> = outer.iter().rev().copied().collect();
+    let triangles = ear_clipping(&reversed, &holes);
+    let points: Vec<Point2> = reversed
+        .iter()
+        .chain(holes.iter().flatten())
+        .copied()
+        .collect();
+    let sum: f64 = triangles
+        .iter()
+        .map(|tri| signed_area(&tri.map(|i| points[i])))
+        .sum();
+    assert!(sum.near(&-area), "{sum} {area}");
+}
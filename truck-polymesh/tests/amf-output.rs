@@ -0,0 +1,62 @@
+use amf::AMFMaterial;
+use truck_polymesh::*;
+
+fn cube() -> PolygonMesh {
+    let positions = (0..8)
+        .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+        .collect();
+    let faces = Faces::from_iter(&[
+        [0, 2, 3, 1],
+        [0, 1, 5, 4],
+        [0, 4, 6, 2],
+        [7, 5, 1, 3],
+        [7, 3, 2, 6],
+        [7, 6, 4, 5],
+    ]);
+    PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    )
+}
+
+#[test]
+fn amf_materials() {
+    let mesh = cube();
+    let materials = [
+        AMFMaterial {
+            name: "<body> & \"frame\"".to_string(),
+            color: [0.5, 0.5, 0.5, 1.0],
+        },
+        AMFMaterial {
+            name: "unused".to_string(),
+            color: [0.0, 0.0, 0.0, 1.0],
+        },
+        AMFMaterial {
+            name: "top".to_string(),
+            color: [1.0, 0.0, 0.0, 0.5],
+        },
+    ];
+    let mut amf = Vec::new();
+    amf::write_with_materials(&mesh, &[0, 0, 0, 0, 0, 2], &materials, &mut amf).unwrap();
+    let amf = String::from_utf8(amf).unwrap();
+
+    assert_eq!(amf.matches("<material id=").count(), 3);
+    assert!(amf.contains("&lt;body&gt; &amp; &quot;frame&quot;"));
+    assert!(amf.contains("<r>1</r><g>0</g><b>0</b><a>0.5</a>"));
+    assert_eq!(amf.matches("<vertex>").count(), 8);
+    // the volume without faces is skipped.
+    assert_eq!(amf.matches("<volume").count(), 2);
+    let body = amf.split("<volume materialid=\"1\">").nth(1).unwrap();
+    let body = body.split("</volume>").next().unwrap();
+    assert_eq!(body.matches("<triangle>").count(), 10);
+    let top = amf.split("<volume materialid=\"3\">").nth(1).unwrap();
+    let top = top.split("</volume>").next().unwrap();
+    assert_eq!(top.matches("<triangle>").count(), 2);
+    assert!(top.contains("<triangle><v1>7</v1><v2>6</v2><v3>4</v3></triangle>"));
+
+    assert!(amf::write_with_materials(&mesh, &[0, 0, 0], &materials, &mut Vec::new()).is_err());
+    assert!(amf::write_with_materials(&mesh, &[0; 6], &[], &mut Vec::new()).is_err());
+}
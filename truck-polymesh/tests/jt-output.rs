@@ -0,0 +1,87 @@
+use truck_polymesh::*;
+
+fn i32_at(bytes: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn lods() -> Vec<PolygonMesh> {
+    (0..3)
+        .map(|level| {
+            let div = 8 >> level;
+            let positions = (0..=div)
+                .flat_map(|i| (0..=div).map(move |j| (i, j)))
+                .map(|(i, j)| Point3::new(i as f64 / div as f64, j as f64 / div as f64, 0.0))
+                .collect();
+            let faces = (0..div)
+                .flat_map(|i| (0..div).map(move |j| i * (div + 1) + j))
+                .map(|k| [k, k + div + 1, k + div + 2, k + 1])
+                .collect();
+            PolygonMesh::new(
+                StandardAttributes {
+                    positions,
+                    ..Default::default()
+                },
+                faces,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn jt_structure() {
+    let lods = lods();
+    let mut bytes = Vec::new();
+    jt::write(&lods, &mut bytes).unwrap();
+
+    assert!(bytes[..80].starts_with(b"Version 8.1 JT"));
+    assert_eq!(bytes[80], 0);
+    let toc_offset = i32_at(&bytes, 85) as usize;
+    assert_eq!(toc_offset, jt::HEADER_SIZE);
+    let lsg_id = &bytes[89..105];
+
+    let count = i32_at(&bytes, toc_offset) as usize;
+    assert_eq!(count, lods.len() + 1);
+    let mut end = toc_offset + 4 + count * 28;
+    (0..count).for_each(|i| {
+        let entry = toc_offset + 4 + i * 28;
+        let (id, offset, length) = (
+            &bytes[entry..entry + 16],
+            i32_at(&bytes, entry + 16) as usize,
+            i32_at(&bytes, entry + 20) as usize,
+        );
+        let segment_type = i32_at(&bytes, entry + 24) >> 24;
+        // the segments are contiguous.
+        assert_eq!(offset, end);
+        end = offset + length;
+        // the header of the segment
+        assert_eq!(&bytes[offset..offset + 16], id);
+        assert_eq!(i32_at(&bytes, offset + 16), segment_type);
+        assert_eq!(i32_at(&bytes, offset + 20) as usize, length);
+        match i {
+            0 => {
+                assert_eq!(id, lsg_id);
+                assert_eq!(segment_type, jt::LSG_SEGMENT_TYPE);
+            }
+            _ => {
+                assert_eq!(segment_type, jt::SHAPE_LOD_SEGMENT_TYPE + i as i32 - 1);
+                // the shape LOD element and the end of elements
+                let element = offset + jt::SEGMENT_HEADER_SIZE;
+                let element_length = i32_at(&bytes, element) as usize;
+                let end_of_elements = element + 4 + element_length;
+                assert_eq!(i32_at(&bytes, end_of_elements), 17);
+                assert_eq!(
+                    &bytes[end_of_elements + 4..end_of_elements + 20],
+                    &[0xff; 16]
+                );
+                assert_eq!(end_of_elements + 4 + 17, end);
+                // the raw vertex data: six floats for each vertex of the triangles
+                let num_triangles = lods[i - 1].faces().triangle_iter().len();
+                let raw_size = i32_at(&bytes, end_of_elements - num_triangles * 72 - 8);
+                assert_eq!(raw_size as usize, num_triangles * 72);
+            }
+        }
+    });
+    assert_eq!(end, bytes.len());
+
+    assert!(jt::write(&[], &mut Vec::new()).is_err());
+}
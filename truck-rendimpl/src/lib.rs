@@ -168,4 +168,7 @@ mod instance_creator;
 mod instance_descriptor;
 mod polygon_instance;
 mod polyrend;
+/// offscreen rendering of thumbnails
+#[cfg(feature = "thumbnail")]
+pub mod thumbnail;
 mod wireframe_instance;
@@ -0,0 +1,129 @@
+use crate::*;
+use image::{ImageOutputFormat, RgbaImage};
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+
+/// Configuration of thumbnails.
+#[derive(Debug, Clone)]
+pub struct ThumbnailConfig {
+    /// (width, height) of the thumbnail. Default is `(256, 256)`.
+    pub size: (u32, u32),
+    /// the view of the camera. Default is `StandardView::Isometric`.
+    pub view: StandardView,
+    /// material of the shape. Default is `Material::default()`.
+    pub material: Material,
+    /// background color. Default is `Color::WHITE`.
+    pub background: Color,
+    /// tolerance for meshing shapes. Default is `0.01`.
+    pub tolerance: f64,
+}
+
+impl Default for ThumbnailConfig {
+    #[inline(always)]
+    fn default() -> ThumbnailConfig {
+        ThumbnailConfig {
+            size: (256, 256),
+            view: StandardView::Isometric,
+            material: Default::default(),
+            background: Color::WHITE,
+            tolerance: 0.01,
+        }
+    }
+}
+
+/// Renders the polygon mesh to a PNG thumbnail by the default GPU device.
+///
+/// The camera is fitted to the bounding box of the mesh from `config.view`
+/// by [`Camera::fitted_perspective_camera`]. The light is put at the camera position.
+/// If the mesh has no normals, the normals of faces are added before rendering.
+///
+/// # Panics
+/// Panics if no GPU adapter is found.
+///
+/// # Examples
+/// ```no_run
+/// use truck_rendimpl::{thumbnail::*, *};
+/// use truck_meshalgo::prelude::obj;
+/// let mesh = obj::read(std::fs::File::open("cube.obj").unwrap()).unwrap();
+/// let png = pollster::block_on(polygon_thumbnail(&mesh, &Default::default()));
+/// std::fs::write("cube.png", png).unwrap();
+/// ```
+pub async fn polygon_thumbnail(mesh: &PolygonMesh, config: &ThumbnailConfig) -> Vec<u8> {
+    let image = render_thumbnail(mesh, config).await;
+    let mut png = Vec::new();
+    DynamicImage::ImageRgba8(image)
+        .write_to(&mut std::io::Cursor::new(&mut png), ImageOutputFormat::Png)
+        .expect("failed to encode a PNG image");
+    png
+}
+
+/// Meshes the shape with `config.tolerance` and renders it to a PNG thumbnail.
+///
+/// # Panics
+/// Panics if no GPU adapter is found.
+///
+/// # Examples
+/// ```no_run
+/// use truck_modeling::*;
+/// use truck_rendimpl::thumbnail::*;
+/// let v = builder::vertex(Point3::new(-0.5, -0.5, -0.5));
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube = builder::tsweep(&f, Vector3::unit_z());
+/// let png = pollster::block_on(shape_thumbnail(&cube, &Default::default()));
+/// std::fs::write("cube.png", png).unwrap();
+/// ```
+pub async fn shape_thumbnail<S>(shape: &S, config: &ThumbnailConfig) -> Vec<u8>
+where
+    S: MeshableShape,
+    S::MeshedShape: MeshedShape, {
+    let mesh = shape.triangulation(config.tolerance).to_polygon();
+    polygon_thumbnail(&mesh, config).await
+}
+
+/// Renders the polygon mesh to an RGBA image by the default GPU device.
+///
+/// The way of rendering is the same as [`polygon_thumbnail`].
+///
+/// # Panics
+/// Panics if no GPU adapter is found.
+pub async fn render_thumbnail(mesh: &PolygonMesh, config: &ThumbnailConfig) -> RgbaImage {
+    let mut mesh = mesh.clone();
+    if mesh.normals().is_empty() {
+        mesh.add_naive_normals(true);
+    }
+    let bdd_box = mesh.positions().iter().collect();
+    let (width, height) = config.size;
+    let as_rat = width as f64 / height as f64;
+    let camera = Camera::fitted_perspective_camera(&bdd_box, config.view, Rad(PI / 4.0), as_rat);
+    let light = Light {
+        position: camera.position(),
+        color: Vector3::new(1.0, 1.0, 1.0),
+        light_type: LightType::Point,
+    };
+    let scene_desc = SceneDescriptor {
+        studio: StudioConfig {
+            camera,
+            lights: vec![light],
+            background: config.background,
+        },
+        backend_buffer: BackendBufferConfig {
+            sample_count: 4,
+            ..Default::default()
+        },
+        render_texture: RenderTextureConfig {
+            canvas_size: config.size,
+            ..Default::default()
+        },
+    };
+    let mut scene = Scene::from_default_device(&scene_desc).await;
+    let state = PolygonState {
+        material: config.material,
+        backface_culling: false,
+        ..Default::default()
+    };
+    let instance: PolygonInstance = scene.instance_creator().create_instance(&mesh, &state);
+    scene.add_object(&instance);
+    let buffer = scene.render_to_buffer().await;
+    RgbaImage::from_raw(width, height, buffer).expect("the size of buffer is invalid")
+}
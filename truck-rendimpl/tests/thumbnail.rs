@@ -0,0 +1,22 @@
+#![cfg(feature = "thumbnail")]
+
+use truck_modeling::*;
+use truck_rendimpl::thumbnail::*;
+
+#[test]
+fn cube_thumbnail() {
+    let v = builder::vertex(Point3::new(-0.5, -0.5, -0.5));
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let config = ThumbnailConfig {
+        size: (128, 96),
+        ..Default::default()
+    };
+    let png = pollster::block_on(shape_thumbnail(&cube, &config));
+    let image = image::load_from_memory(&png).unwrap().to_rgba8();
+    assert_eq!(image.dimensions(), (128, 96));
+    // the background is white and the center is the cube.
+    assert_eq!(image.get_pixel(0, 0).0, [255, 255, 255, 255]);
+    assert_ne!(image.get_pixel(64, 48).0, [255, 255, 255, 255]);
+}
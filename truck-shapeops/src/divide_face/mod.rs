@@ -4,6 +4,7 @@ use crate::faces_classification::FacesClassification;
 use crate::loops_store::*;
 use rustc_hash::FxHashMap as HashMap;
 use std::ops::Deref;
+use truck_base::polygon;
 use truck_meshalgo::prelude::*;
 use truck_topology::*;
 
@@ -13,11 +14,7 @@ trait PolylineBoundary {
 }
 
 impl PolylineBoundary for PolylineCurve<Point2> {
-    fn area(&self) -> f64 {
-        self.windows(2).fold(0.0, |res, p| {
-            res + (p[1][0] + p[0][0]) * (p[1][1] - p[0][1])
-        }) / 2.0
-    }
+    fn area(&self) -> f64 { polygon::signed_area(self) }
     fn include(&self, c: Point2) -> bool {
         let t = 2.0 * std::f64::consts::PI * HashGen::hash1(c);
         let r = Vector2::new(f64::cos(t), f64::sin(t));
@@ -66,7 +63,10 @@ where
         });
         let mut p = *vec.last().unwrap();
         let closure = |q: &P| -> Option<Point2> {
-            p = surface.search_parameter(*q, Some(p.into()), 100)?.into();
+            p = surface
+                .search_parameter(*q, Some(p.into()), 100)
+                .or_else(|| surface.search_parameter(*q, None, 100))?
+                .into();
             Some(p)
         };
         let add: Option<Vec<Point2>> = match edge.orientation() {
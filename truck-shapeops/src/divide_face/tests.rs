@@ -105,6 +105,46 @@ crate::impl_from!(
 );
 crate::impl_from!(BSplineSurface<Point3>, Plane);
 
+/// The plane on which the search of parameters fails with hints, as the Newton method diverging
+/// from the parameter of the previous point.
+#[derive(Clone, Debug)]
+struct HintlessPlane(Plane);
+
+impl SearchParameter<D2> for HintlessPlane {
+    type Point = Point3;
+    fn search_parameter<H: Into<SPHint2D>>(
+        &self,
+        point: Point3,
+        hint: H,
+        trials: usize,
+    ) -> Option<(f64, f64)> {
+        match hint.into() {
+            SPHint2D::None => self.0.search_parameter(point, None, trials),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn parameter_boundary_without_hints() {
+    let v = Vertex::news([
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(2.0, 0.0, 0.0),
+        Point3::new(2.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ]);
+    let wire: Wire<_, _> = (0..4).map(|i| line(&v[i], &v[(i + 1) % 4])).collect();
+    let plane = Plane::new(
+        Point3::origin(),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    );
+    let face = Face::new(vec![wire.clone()], HintlessPlane(plane));
+    let poly = create_parameter_boundary(&face, &wire, &mut HashMap::default(), TOL).unwrap();
+    assert_eq!(poly.len(), 5);
+    assert_near!(poly.area(), 2.0);
+}
+
 fn parabola_surfaces() -> (AlternativeSurface, AlternativeSurface) {
     // define surfaces
     #[rustfmt::skip]
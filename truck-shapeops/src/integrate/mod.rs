@@ -47,11 +47,24 @@ fn process_one_pair_of_shells<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
     nonpositive_tolerance!(tol);
     let poly_shell0 = shell0.triangulation(tol);
     let poly_shell1 = shell1.triangulation(tol);
+    process_one_pair_of_meshed_shells(shell0, &poly_shell0, shell1, &poly_shell1, tol)
+}
+
+/// the tessellated shell whose faces are in the same order as the original shell
+type PolyShell = Shell<Point3, PolylineCurve<Point3>, Option<PolygonMesh>>;
+
+fn process_one_pair_of_meshed_shells<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
+    shell0: &Shell<Point3, C, S>,
+    poly_shell0: &PolyShell,
+    shell1: &Shell<Point3, C, S>,
+    poly_shell1: &PolyShell,
+    tol: f64,
+) -> Option<[Shell<Point3, C, S>; 2]> {
     let loops_store::LoopsStoreQuadruple {
         geom_loops_store0: loops_store0,
         geom_loops_store1: loops_store1,
         ..
-    } = loops_store::create_loops_stores(shell0, &poly_shell0, shell1, &poly_shell1, tol)?;
+    } = loops_store::create_loops_stores(shell0, poly_shell0, shell1, poly_shell1, tol)?;
     let mut cls0 = divide_face::divide_faces(shell0, &loops_store0, tol)?;
     cls0.integrate_by_component();
     let mut cls1 = divide_face::divide_faces(shell1, &loops_store1, tol)?;
@@ -99,49 +112,179 @@ fn process_one_pair_of_shells<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
     Some([and0, or0])
 }
 
-/// AND operation between two solids.
-pub fn and<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
+/// Processes the pairs of shells one by one, and returns the AND shell if `index == 0`,
+/// or the OR shell if `index == 1`.
+///
+/// `proceed` is called with the numbers of the processed pairs and all pairs before
+/// processing each pair. The operation is aborted if `proceed` returns `false`.
+pub(crate) fn boolean_operation<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
     solid0: &Solid<Point3, C, S>,
     solid1: &Solid<Point3, C, S>,
     tol: f64,
+    index: usize,
+    mut proceed: impl FnMut(usize, usize) -> bool,
 ) -> Option<Solid<Point3, C, S>> {
+    let total = solid0.boundaries().len() + solid1.boundaries().len() - 1;
     let mut iter0 = solid0.boundaries().iter();
     let mut iter1 = solid1.boundaries().iter();
     let shell0 = iter0.next().unwrap();
     let shell1 = iter1.next().unwrap();
-    let [mut and_shell, _] = process_one_pair_of_shells(shell0, shell1, tol)?;
-    for shell in iter0 {
-        let [res, _] = process_one_pair_of_shells(&and_shell, shell, tol)?;
-        and_shell = res;
+    if !proceed(0, total) {
+        return None;
     }
-    for shell in iter1 {
-        let [res, _] = process_one_pair_of_shells(&and_shell, shell, tol)?;
-        and_shell = res;
+    let [and_shell, or_shell] = process_one_pair_of_shells(shell0, shell1, tol)?;
+    let mut res_shell = [and_shell, or_shell].into_iter().nth(index)?;
+    for (i, shell) in iter0.chain(iter1).enumerate() {
+        if !proceed(i + 1, total) {
+            return None;
+        }
+        let shells = process_one_pair_of_shells(&res_shell, shell, tol)?;
+        res_shell = shells.into_iter().nth(index)?;
     }
-    let boundaries = and_shell.connected_components();
+    let boundaries = res_shell.connected_components();
     Some(Solid::new(boundaries))
 }
 
+/// AND operation between two solids.
+pub fn and<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
+    solid0: &Solid<Point3, C, S>,
+    solid1: &Solid<Point3, C, S>,
+    tol: f64,
+) -> Option<Solid<Point3, C, S>> {
+    boolean_operation(solid0, solid1, tol, 0, |_, _| true)
+}
+
 /// OR operation between two solids.
 pub fn or<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
     solid0: &Solid<Point3, C, S>,
     solid1: &Solid<Point3, C, S>,
     tol: f64,
 ) -> Option<Solid<Point3, C, S>> {
-    let mut iter0 = solid0.boundaries().iter();
-    let mut iter1 = solid1.boundaries().iter();
-    let shell0 = iter0.next().unwrap();
-    let shell1 = iter1.next().unwrap();
-    let [_, mut or_shell] = process_one_pair_of_shells(shell0, shell1, tol)?;
-    for shell in iter0 {
-        let [_, res] = process_one_pair_of_shells(&or_shell, shell, tol)?;
-        or_shell = res;
+    boolean_operation(solid0, solid1, tol, 1, |_, _| true)
+}
+
+/// Returns the bounding box of the tessellated shell, expanded by `tol`.
+fn bounding_box(poly_shell: &PolyShell, tol: f64) -> Option<BoundingBox<Point3>> {
+    let mut bdb = poly_shell
+        .face_iter()
+        .try_fold(BoundingBox::new(), |mut bdb, face| {
+            face.get_surface()?
+                .positions()
+                .iter()
+                .for_each(|p| bdb.push(p));
+            Some(bdb)
+        })?;
+    if !bdb.is_empty() {
+        let margin = Vector3::new(tol, tol, tol);
+        let (min, max) = (*bdb.min() - margin, *bdb.max() + margin);
+        bdb.push(&min);
+        bdb.push(&max);
+    }
+    Some(bdb)
+}
+
+/// Subtracts all `tools` from `target`, i.e. the AND operation between `target` and the
+/// inverted tools.
+///
+/// The result is the same as subtracting the tools one by one, but the work is shared:
+/// - the tools are tessellated only once,
+/// - the tools whose bounding boxes do not meet that of `target` are skipped, and
+/// - the tools whose bounding boxes are disjoint from each other are subtracted at once,
+///
+/// so the target is re-tessellated and intersected once for each batch of the disjoint tools,
+/// e.g. only once for dozens of separated holes, instead of once for each tool.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+///
+/// // the plate [0, 4] x [0, 1] x [0, 0.5]
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x() * 4.0);
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let plate: Solid = builder::tsweep(&f, Vector3::unit_z() * 0.5);
+///
+/// // four drills and one far away from the plate
+/// let drill = |x: f64| {
+///     let v = builder::vertex(Point3::new(x, 0.25, -0.5));
+///     let axis = Point3::new(x, 0.5, 0.0);
+///     let w = builder::rsweep(&v, axis, Vector3::unit_z(), Rad(7.0));
+///     let f = builder::try_attach_plane(&[w]).unwrap();
+///     builder::tsweep(&f, Vector3::unit_z() * 2.0)
+/// };
+/// let tools: Vec<Solid> = [0.5, 1.5, 2.5, 3.5, 10.0].into_iter().map(drill).collect();
+///
+/// let drilled = truck_shapeops::subtract_many(&plate, &tools, 0.05).unwrap();
+/// assert_eq!(drilled.boundaries().len(), 1);
+/// // the top, the bottom, the four sides, and the four holes of two faces
+/// let shell = &drilled.boundaries()[0];
+/// assert_eq!(shell.len(), 6 + 4 * 2);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// let holes = shell.face_iter().map(|face| face.boundaries().len() - 1).sum::<usize>();
+/// assert_eq!(holes, 8);
+/// ```
+pub fn subtract_many<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
+    target: &Solid<Point3, C, S>,
+    tools: &[Solid<Point3, C, S>],
+    tol: f64,
+) -> Option<Solid<Point3, C, S>> {
+    nonpositive_tolerance!(tol);
+    let target_box =
+        target
+            .boundaries()
+            .iter()
+            .try_fold(BoundingBox::new(), |mut bdb, shell| {
+                bdb += bounding_box(&shell.triangulation(tol), tol)?;
+                Some(bdb)
+            })?;
+    // the batches of the inverted tools, and the bounding boxes of the tools in the batches
+    let mut batches = Vec::<(
+        Vec<(Shell<Point3, C, S>, PolyShell)>,
+        Vec<BoundingBox<Point3>>,
+    )>::new();
+    for tool in tools {
+        let mut tool = tool.clone();
+        tool.not();
+        let mut tool_box = BoundingBox::new();
+        let shells = tool
+            .boundaries()
+            .iter()
+            .map(|shell| {
+                let poly_shell = shell.triangulation(tol);
+                tool_box += bounding_box(&poly_shell, tol)?;
+                Some((shell.clone(), poly_shell))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if (&target_box ^ &tool_box).is_empty() {
+            continue;
+        }
+        let batch = batches
+            .iter_mut()
+            .find(|(_, boxes)| boxes.iter().all(|bdb| (bdb ^ &tool_box).is_empty()));
+        match batch {
+            Some((batch, boxes)) => {
+                batch.extend(shells);
+                boxes.push(tool_box);
+            }
+            None => batches.push((shells, vec![tool_box])),
+        }
+    }
+    let mut iter = target.boundaries().iter();
+    let mut res_shell = iter.next()?.clone();
+    for shell in iter {
+        let [and_shell, _] = process_one_pair_of_shells(&res_shell, shell, tol)?;
+        res_shell = and_shell;
     }
-    for shell in iter1 {
-        let [_, res] = process_one_pair_of_shells(&or_shell, shell, tol)?;
-        or_shell = res;
+    for (batch, _) in batches {
+        let shell: Shell<Point3, C, S> =
+            batch.iter().flat_map(|(shell, _)| shell.clone()).collect();
+        let poly_shell: PolyShell = batch.into_iter().flat_map(|(_, poly)| poly).collect();
+        let poly_res = res_shell.triangulation(tol);
+        let [and_shell, _] =
+            process_one_pair_of_meshed_shells(&res_shell, &poly_res, &shell, &poly_shell, tol)?;
+        res_shell = and_shell;
     }
-    let boundaries = or_shell.connected_components();
+    let boundaries = res_shell.connected_components();
     Some(Solid::new(boundaries))
 }
 
@@ -19,3 +19,36 @@ fn punched_cube() {
     let file = std::fs::File::create("punched-cube.obj").unwrap();
     obj::write(&poly, file).unwrap();
 }
+
+#[test]
+fn subtract_overlapping_tools() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x() * 3.0);
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let plate: Solid = builder::tsweep(&f, Vector3::unit_z());
+
+    let drill = |x: f64| {
+        let v = builder::vertex(Point3::new(x, 0.25, -0.5));
+        let w = builder::rsweep(&v, Point3::new(x, 0.5, 0.0), Vector3::unit_z(), Rad(7.0));
+        let f = builder::try_attach_plane(&[w]).unwrap();
+        builder::tsweep(&f, Vector3::unit_z() * 2.0)
+    };
+    // The first two tools overlap, and are subtracted in the different batches.
+    let tools: Vec<Solid> = [0.8, 1.2, 2.5].into_iter().map(drill).collect();
+    let drilled = crate::subtract_many(&plate, &tools, 0.05).unwrap();
+
+    let sequential = tools.iter().fold(plate, |solid, tool| {
+        let mut tool = tool.clone();
+        tool.not();
+        crate::and(&solid, &tool, 0.05).unwrap()
+    });
+    assert_eq!(drilled.boundaries().len(), 1);
+    let (shell0, shell1) = (&drilled.boundaries()[0], &sequential.boundaries()[0]);
+    assert_eq!(shell0.len(), shell1.len());
+    assert_eq!(shell0.shell_condition(), ShellCondition::Closed);
+    let holes = |shell: &Shell| {
+        let count = shell.face_iter().map(|face| face.boundaries().len() - 1);
+        count.sum::<usize>()
+    };
+    assert_eq!(holes(shell0), holes(shell1));
+}
@@ -0,0 +1,291 @@
+use crate::integrate::boolean_operation;
+use crate::*;
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use thiserror::Error;
+use truck_meshalgo::prelude::*;
+use truck_topology::Solid;
+
+type MeshedSolid = Solid<Point3, PolylineCurve<Point3>, Option<PolygonMesh>>;
+
+/// Errors occurred by running jobs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum JobError {
+    /// The job was cancelled by [`Job::cancel`].
+    #[error("The job was cancelled.")]
+    Cancelled,
+    /// The job panicked.
+    #[error("The job panicked.")]
+    Panicked,
+}
+
+/// The kinds of boolean operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BooleanOp {
+    /// the AND operation, the same as [`and`]
+    And,
+    /// the OR operation, the same as [`or`]
+    Or,
+}
+
+#[derive(Debug, Default)]
+struct Control {
+    /// the bits of `f64`
+    progress: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+#[derive(Debug)]
+struct Slot<T> {
+    result: Option<Result<T, JobError>>,
+    /// whether the result has been taken
+    taken: bool,
+    waker: Option<Waker>,
+}
+
+impl<T> Slot<T> {
+    fn take(&mut self) -> Option<Result<T, JobError>> {
+        let res = self.result.take();
+        self.taken |= res.is_some();
+        res
+    }
+}
+
+#[derive(Debug)]
+struct Shared<T> {
+    slot: Mutex<Slot<T>>,
+    finished: Condvar,
+}
+
+/// The handle of the running job, passed to the closure of [`spawn_job`].
+#[derive(Debug, Clone)]
+pub struct JobContext {
+    control: Arc<Control>,
+}
+
+impl JobContext {
+    /// Reports the progress in `[0, 1]`.
+    #[inline(always)]
+    pub fn set_progress(&self, progress: f64) {
+        let progress = f64::clamp(progress, 0.0, 1.0);
+        self.control
+            .progress
+            .store(progress.to_bits(), Ordering::Relaxed);
+    }
+    /// Returns whether the job is cancelled. Long jobs should check it regularly and return
+    /// as soon as possible when it is `true`.
+    #[inline(always)]
+    pub fn is_cancelled(&self) -> bool { self.control.cancelled.load(Ordering::Relaxed) }
+}
+
+/// Geometric operation running on the background thread.
+///
+/// The result can be received by [`Job::wait`] blocking the thread, polled by [`Job::try_take`]
+/// in the event loops of GUI applications, or awaited since `Job` implements [`Future`].
+#[derive(Debug)]
+pub struct Job<T> {
+    control: Arc<Control>,
+    shared: Arc<Shared<T>>,
+}
+
+/// Runs `job` on the new thread.
+///
+/// The closure reports the progress and checks cancellation by [`JobContext`].
+/// If the job is cancelled, the result is [`JobError::Cancelled`] even if the closure
+/// returns some value.
+/// # Examples
+/// ```
+/// use truck_shapeops::jobs::*;
+/// let job = spawn_job(|ctx| {
+///     let mut sum = 0;
+///     for i in 0..=100 {
+///         if ctx.is_cancelled() {
+///             break;
+///         }
+///         sum += i;
+///         ctx.set_progress(i as f64 / 100.0);
+///     }
+///     sum
+/// });
+/// assert_eq!(job.wait(), Ok(5050));
+/// ```
+pub fn spawn_job<T, F>(job: F) -> Job<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&JobContext) -> T + Send + 'static, {
+    let control = Arc::new(Control::default());
+    let shared = Arc::new(Shared {
+        slot: Mutex::new(Slot {
+            result: None,
+            taken: false,
+            waker: None,
+        }),
+        finished: Condvar::new(),
+    });
+    let ctx = JobContext {
+        control: Arc::clone(&control),
+    };
+    let thread_shared = Arc::clone(&shared);
+    std::thread::spawn(move || {
+        let res = catch_unwind(AssertUnwindSafe(|| job(&ctx)));
+        let res = match (res, ctx.is_cancelled()) {
+            (_, true) => Err(JobError::Cancelled),
+            (Ok(res), false) => {
+                ctx.set_progress(1.0);
+                Ok(res)
+            }
+            (Err(_), false) => Err(JobError::Panicked),
+        };
+        let mut slot = thread_shared.slot.lock().unwrap();
+        slot.result = Some(res);
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+        thread_shared.finished.notify_all();
+    });
+    Job { control, shared }
+}
+
+impl<T> Job<T> {
+    /// Returns the progress in `[0, 1]` reported by the job.
+    #[inline(always)]
+    pub fn progress(&self) -> f64 { f64::from_bits(self.control.progress.load(Ordering::Relaxed)) }
+    /// Requests the cancellation of the job. The job is stopped at the next check point.
+    #[inline(always)]
+    pub fn cancel(&self) { self.control.cancelled.store(true, Ordering::Relaxed) }
+    /// Returns whether the cancellation is requested.
+    #[inline(always)]
+    pub fn is_cancelled(&self) -> bool { self.control.cancelled.load(Ordering::Relaxed) }
+    /// Returns whether the result is ready.
+    #[inline(always)]
+    pub fn is_finished(&self) -> bool { self.shared.slot.lock().unwrap().result.is_some() }
+    /// Takes the result if the job is finished, without blocking.
+    /// Returns `None` if the job is running or the result has already been taken.
+    #[inline(always)]
+    pub fn try_take(&mut self) -> Option<Result<T, JobError>> {
+        self.shared.slot.lock().unwrap().take()
+    }
+    /// Blocks the current thread until the job is finished, and returns the result.
+    /// # Panics
+    /// Panics if the result has already been taken by [`Job::try_take`] or by awaiting.
+    pub fn wait(self) -> Result<T, JobError> {
+        let mut slot = self.shared.slot.lock().unwrap();
+        loop {
+            assert!(!slot.taken, "the result of the job has already been taken.");
+            match slot.take() {
+                Some(res) => return res,
+                None => slot = self.shared.finished.wait(slot).unwrap(),
+            }
+        }
+    }
+}
+
+/// # Panics
+/// Panics if the result has already been taken by [`Job::try_take`] or by awaiting.
+impl<T> Future for Job<T> {
+    type Output = Result<T, JobError>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.shared.slot.lock().unwrap();
+        assert!(!slot.taken, "the result of the job has already been taken.");
+        match slot.take() {
+            Some(res) => Poll::Ready(res),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Tessellates the solid on the background thread, in the same way as
+/// [`MeshableShape::triangulation`].
+///
+/// The faces are tessellated one by one, and the progress is the ratio of the tessellated faces.
+/// The cancellation is checked for each face.
+/// # Panics
+/// `tol` must be more than `TOLERANCE`. Since the job panics on the background thread,
+/// the result is [`JobError::Panicked`].
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+/// use truck_shapeops::jobs::*;
+///
+/// // modeling a unit cube
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let job = spawn_tessellation(cube, 0.01);
+/// let mesh = job.wait().unwrap().to_polygon();
+/// assert_eq!(mesh.faces().len(), 12);
+/// ```
+pub fn spawn_tessellation<C, S>(solid: Solid<Point3, C, S>, tol: f64) -> Job<MeshedSolid>
+where
+    C: PolylineableCurve + Quantize + Send + Sync + 'static,
+    S: MeshableSurface + Quantize + Send + Sync + 'static, {
+    spawn_job(move |ctx| {
+        let mut cache = TessellationCache::new();
+        let total = solid.face_iter().count();
+        // The faces are tessellated one by one into the cache, and then gathered.
+        for (i, face) in solid.face_iter().enumerate() {
+            if ctx.is_cancelled() {
+                // the result is discarded since the job is cancelled.
+                return Solid::new_unchecked(Vec::new());
+            }
+            cache.triangulate_shell(&vec![face.clone()].into(), tol);
+            ctx.set_progress(i as f64 / total as f64);
+        }
+        cache.triangulate_solid(&solid, tol)
+    })
+}
+
+/// Runs the boolean operation on the background thread.
+///
+/// The result is the same as [`and`] or [`or`]. The progress is the ratio of the processed
+/// pairs of shells, and the cancellation is checked before each pair.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_shapeops::jobs::*;
+///
+/// // two unit cubes
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube0: Solid = builder::tsweep(&f, Vector3::unit_z());
+/// let cube1 = builder::translated(&cube0, Vector3::new(0.5, 0.5, 0.5));
+///
+/// let job = spawn_boolean(BooleanOp::Or, cube0, cube1, 0.05);
+/// let solid = job.wait().unwrap().unwrap();
+/// assert_eq!(solid.boundaries().len(), 1);
+/// ```
+pub fn spawn_boolean<C, S>(
+    op: BooleanOp,
+    solid0: Solid<Point3, C, S>,
+    solid1: Solid<Point3, C, S>,
+    tol: f64,
+) -> Job<Option<Solid<Point3, C, S>>>
+where
+    C: ShapeOpsCurve<S> + Send + Sync + 'static,
+    S: ShapeOpsSurface + Send + Sync + 'static,
+{
+    let index = match op {
+        BooleanOp::And => 0,
+        BooleanOp::Or => 1,
+    };
+    spawn_job(move |ctx| {
+        boolean_operation(&solid0, &solid1, tol, index, |i, total| {
+            ctx.set_progress(i as f64 / total as f64);
+            !ctx.is_cancelled()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests;
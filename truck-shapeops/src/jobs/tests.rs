@@ -0,0 +1,98 @@
+use super::*;
+use std::task::Wake;
+use std::thread::Thread;
+use truck_modeling::*;
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) { self.0.unpark() }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(res) => return res,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+fn unit_cube() -> truck_modeling::Solid {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    builder::tsweep(&f, Vector3::unit_z())
+}
+
+#[test]
+fn cancel_job() {
+    let job = spawn_job(|ctx| {
+        while !ctx.is_cancelled() {
+            ctx.set_progress(0.5);
+            std::thread::yield_now();
+        }
+    });
+    while job.progress() < 0.5 {
+        std::thread::yield_now();
+    }
+    assert!(!job.is_finished());
+    job.cancel();
+    assert!(job.is_cancelled());
+    assert_eq!(job.wait(), Err(JobError::Cancelled));
+}
+
+#[test]
+fn panicked_job() {
+    let job = spawn_job(|_| -> usize { panic!("intended panic") });
+    assert_eq!(job.wait(), Err(JobError::Panicked));
+}
+
+#[test]
+fn poll_job() {
+    let mut job = spawn_job(|_| 42);
+    let res = loop {
+        match job.try_take() {
+            Some(res) => break res,
+            None => std::thread::yield_now(),
+        }
+    };
+    assert_eq!(res, Ok(42));
+    assert!(job.try_take().is_none());
+    assert_eq!(job.progress(), 1.0);
+}
+
+#[test]
+#[should_panic(expected = "already been taken")]
+fn wait_after_take() {
+    let mut job = spawn_job(|_| 42);
+    while job.try_take().is_none() {
+        std::thread::yield_now();
+    }
+    let _ = job.wait();
+}
+
+#[test]
+fn await_tessellation() {
+    let cube = unit_cube();
+    let job = spawn_tessellation(cube.clone(), 0.01);
+    let meshed = block_on(job).unwrap();
+    let direct = cube.triangulation(0.01);
+    assert_eq!(meshed.face_iter().count(), direct.face_iter().count());
+    let (poly0, poly1) = (meshed.to_polygon(), direct.to_polygon());
+    assert_eq!(poly0.positions(), poly1.positions());
+    assert_eq!(poly0.faces().len(), poly1.faces().len());
+}
+
+#[test]
+fn await_boolean() {
+    let cube0 = unit_cube();
+    let cube1 = builder::translated(&cube0, Vector3::new(0.5, 0.5, 0.5));
+    let job = spawn_boolean(BooleanOp::And, cube0.clone(), cube1.clone(), 0.05);
+    let solid = block_on(job).unwrap().unwrap();
+    let direct = and(&cube0, &cube1, 0.05).unwrap();
+    assert_eq!(solid.face_iter().count(), direct.face_iter().count());
+}
@@ -19,6 +19,10 @@ mod integrate;
 mod intersection_curve;
 mod loops_store;
 mod polyline_construction;
-pub use integrate::{and, or, ShapeOpsCurve, ShapeOpsSurface};
+pub use integrate::{and, or, subtract_many, ShapeOpsCurve, ShapeOpsSurface};
+
+/// background jobs of tessellations and boolean operations with progress and cancellation
+#[cfg(not(target_arch = "wasm32"))]
+pub mod jobs;
 
 mod alternative;
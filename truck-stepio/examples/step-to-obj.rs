@@ -14,15 +14,17 @@ fn main() {
 
     println!("reading file...");
     let step_file = std::fs::read_to_string(&args[1]).unwrap();
-    let exchange = ruststep::parser::parse(&step_file).unwrap();
-    let table = Table::from_data_section(&exchange.data[0]);
+    let table = Table::from_step(&step_file).unwrap();
     println!("meshing...");
     let mut polymesh = PolygonMesh::default();
-    table.shell.iter().for_each(|shell| {
-        let shell = table.to_compressed_shell(&shell.1).unwrap();
-        let new_poly = shell.triangulation(0.05).to_polygon();
-        polymesh.merge(new_poly);
-    });
+    table
+        .to_compressed_shells()
+        .into_values()
+        .for_each(|shell| {
+            let shell = shell.unwrap();
+            let new_poly = shell.triangulation(0.05).to_polygon();
+            polymesh.merge(new_poly);
+        });
 
     println!("output obj...");
     let obj_file = std::fs::File::create(&args[2]).unwrap();
@@ -15,6 +15,7 @@ use truck_topology::compress::*;
 /// type alias
 pub mod alias;
 use alias::*;
+mod parallel;
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Table {
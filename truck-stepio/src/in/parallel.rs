@@ -0,0 +1,194 @@
+use super::*;
+use rayon::prelude::*;
+use ruststep::{error::TokenizeFailed, parser::exchange::entity_instance};
+use truck_topology::compress::CompressedShell;
+
+impl Table {
+    /// Creates the table from the whole STEP string.
+    ///
+    /// # Details
+    /// The STEP string is split into the statements by one sequential scan, and then the entity
+    /// instances in the DATA sections are tokenized and deserialized by the work-stealing
+    /// threads of `rayon`. If there are several DATA sections, all instances are registered
+    /// to one table. As [`Table::from_data_section`], the instances which cannot be deserialized
+    /// are reported to the standard error and skipped.
+    ///
+    /// # Errors
+    /// Returns an error if some entity instance in the DATA sections cannot be tokenized.
+    ///
+    /// # Examples
+    /// ```
+    /// use truck_stepio::r#in::*;
+    /// let step = "ISO-10303-21;
+    /// HEADER;
+    /// FILE_DESCRIPTION(('a; b'), '2;1');
+    /// ENDSEC;
+    /// DATA;
+    /// /* comment; with semicolon */
+    /// #1 = CARTESIAN_POINT('p;0', (0.0, 1.0, 2.0));
+    /// #2 = DIRECTION('', (1.0, 0.0, 0.0));
+    /// #3 = VECTOR('', #2, 2.0);
+    /// ENDSEC;
+    /// END-ISO-10303-21;";
+    /// let table = Table::from_step(step).unwrap();
+    /// assert_eq!(table.cartesian_point[&1].label, "p;0");
+    /// assert_eq!(table.direction.len(), 1);
+    /// assert_eq!(table.vector[&3].magnitude, 2.0);
+    /// ```
+    pub fn from_step(step: &str) -> Result<Table> {
+        let instances = data_statements(step)
+            .into_par_iter()
+            .map(|statement| match entity_instance(statement) {
+                Ok((_, instance)) => Ok(instance),
+                Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+                    Err(TokenizeFailed::new(statement, e).into())
+                }
+                Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never require more"),
+            })
+            .collect::<Result<Vec<EntityInstance>>>()?;
+        let table = instances
+            .par_iter()
+            .fold(Table::default, |mut table, instance| {
+                table
+                    .push_instance(instance)
+                    .unwrap_or_else(|e| eprintln!("{e}"));
+                table
+            })
+            .reduce(Table::default, Table::merge);
+        Ok(table)
+    }
+
+    /// Converts all shells in the table to the compressed shells in parallel.
+    ///
+    /// The shells are converted by [`Table::to_compressed_shell`] on the work-stealing threads,
+    /// and the results are returned with the ids of the shells.
+    pub fn to_compressed_shells(
+        &self,
+    ) -> HashMap<
+        u64,
+        std::result::Result<CompressedShell<Point3, Curve3D, Surface>, ExpressParseError>,
+    > {
+        self.shell
+            .par_iter()
+            .map(|(idx, shell)| (*idx, self.to_compressed_shell(shell)))
+            .collect()
+    }
+
+    fn merge(mut self, other: Table) -> Table {
+        let Table {
+            cartesian_point,
+            direction,
+            vector,
+            placement,
+            axis1_placement,
+            axis2_placement_2d,
+            axis2_placement_3d,
+            line,
+            polyline,
+            b_spline_curve_with_knots,
+            bezier_curve,
+            quasi_uniform_curve,
+            uniform_curve,
+            rational_b_spline_curve,
+            circle,
+            plane,
+            spherical_surface,
+            cylindrical_surface,
+            b_spline_surface_with_knots,
+            surface_of_revolution,
+            vertex_point,
+            edge_curve,
+            oriented_edge,
+            edge_loop,
+            face_bound,
+            face_surface,
+            oriented_face,
+            shell,
+            oriented_shell,
+        } = other;
+        self.cartesian_point.extend(cartesian_point);
+        self.direction.extend(direction);
+        self.vector.extend(vector);
+        self.placement.extend(placement);
+        self.axis1_placement.extend(axis1_placement);
+        self.axis2_placement_2d.extend(axis2_placement_2d);
+        self.axis2_placement_3d.extend(axis2_placement_3d);
+        self.line.extend(line);
+        self.polyline.extend(polyline);
+        self.b_spline_curve_with_knots
+            .extend(b_spline_curve_with_knots);
+        self.bezier_curve.extend(bezier_curve);
+        self.quasi_uniform_curve.extend(quasi_uniform_curve);
+        self.uniform_curve.extend(uniform_curve);
+        self.rational_b_spline_curve.extend(rational_b_spline_curve);
+        self.circle.extend(circle);
+        self.plane.extend(plane);
+        self.spherical_surface.extend(spherical_surface);
+        self.cylindrical_surface.extend(cylindrical_surface);
+        self.b_spline_surface_with_knots
+            .extend(b_spline_surface_with_knots);
+        self.surface_of_revolution.extend(surface_of_revolution);
+        self.vertex_point.extend(vertex_point);
+        self.edge_curve.extend(edge_curve);
+        self.oriented_edge.extend(oriented_edge);
+        self.edge_loop.extend(edge_loop);
+        self.face_bound.extend(face_bound);
+        self.face_surface.extend(face_surface);
+        self.oriented_face.extend(oriented_face);
+        self.shell.extend(shell);
+        self.oriented_shell.extend(oriented_shell);
+        self
+    }
+}
+
+/// Returns the statements, with the terminating semicolons, in the DATA sections.
+///
+/// The semicolons in the strings and the comments do not terminate statements.
+fn data_statements(step: &str) -> Vec<&str> {
+    let bytes = step.as_bytes();
+    let mut statements = Vec::new();
+    let (mut in_string, mut in_comment, mut in_data) = (false, false, false);
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], in_string, in_comment) {
+            // doubled apostrophes in strings toggle twice.
+            (b'\'', _, false) => in_string = !in_string,
+            (b'/', false, false) if bytes.get(i + 1) == Some(&b'*') => {
+                in_comment = true;
+                i += 1;
+            }
+            (b'*', false, true) if bytes.get(i + 1) == Some(&b'/') => {
+                in_comment = false;
+                i += 1;
+            }
+            (b';', false, false) => {
+                let statement = skip_spaces_and_comments(&step[start..=i]);
+                if in_data && statement.starts_with("ENDSEC") {
+                    in_data = false;
+                } else if in_data {
+                    statements.push(statement);
+                } else if let Some(rest) = statement.strip_prefix("DATA") {
+                    in_data = rest.trim_start().starts_with(['(', ';']);
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    statements
+}
+
+fn skip_spaces_and_comments(mut statement: &str) -> &str {
+    loop {
+        statement = statement.trim_start();
+        match statement.strip_prefix("/*") {
+            Some(rest) => match rest.find("*/") {
+                Some(end) => statement = &rest[end + 2..],
+                None => return "",
+            },
+            None => return statement,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use super::{topology::ShellEntityIndices, Result, *};
+use truck_modeling::annotation::{Anchor, Annotation};
+
+/// Display model with annotations as semantic PMI of AP242.
+#[derive(Clone, Debug)]
+pub struct StepAnnotatedModel<'a, P, C, S> {
+    model: StepModel<&'a CompressedSolid<P, C, S>>,
+    annotations: &'a [Annotation],
+}
+
+impl<'a, P, C, S> StepAnnotatedModel<'a, P, C, S> {
+    /// constructor
+    ///
+    /// The anchors of `annotations` refer the elements of `solid` by the indices in it.
+    #[inline(always)]
+    pub fn new(solid: &'a CompressedSolid<P, C, S>, annotations: &'a [Annotation]) -> Self {
+        Self {
+            model: StepModel::new(solid),
+            annotations,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct StepString<'a>(&'a str);
+
+impl<'a> Display for StepString<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.0.chars().try_for_each(|c| match c {
+            '\'' => f.write_str("''"),
+            '\\' => f.write_str("\\\\"),
+            _ => f.write_fmt(format_args!("{c}")),
+        })
+    }
+}
+
+fn anchor_index(shells: &[ShellEntityIndices], anchor: Anchor) -> Option<usize> {
+    match anchor {
+        Anchor::Vertex { shell, index } => Some(shells.get(shell)?.ep_vertices + index),
+        Anchor::Edge { shell, index } => Some(shells.get(shell)?.ep_edges + index),
+        Anchor::Face { shell, index } => shells.get(shell)?.faces.get(index).copied(),
+    }
+}
+
+/// Writes `SHAPE_ASPECT` identifying the entity `item`, and returns the next index.
+fn write_shape_aspect(
+    f: &mut Formatter<'_>,
+    idx: usize,
+    item: usize,
+) -> std::result::Result<usize, std::fmt::Error> {
+    f.write_fmt(format_args!(
+        "#{idx} = SHAPE_ASPECT('', '', #4, .T.);
+#{usage_idx} = GEOMETRIC_ITEM_SPECIFIC_USAGE('', '', #{idx}, #10, #{item});\n",
+        usage_idx = idx + 1,
+    ))?;
+    Ok(idx + 2)
+}
+
+/// Writes the nominal value and the tolerance of the dimension `dimension_idx`, and returns the next index.
+fn write_dimension_value(
+    f: &mut Formatter<'_>,
+    idx: usize,
+    dimension_idx: usize,
+    value: f64,
+    tolerance: Option<(f64, f64)>,
+) -> std::result::Result<usize, std::fmt::Error> {
+    f.write_fmt(format_args!(
+        "#{idx} = DIMENSIONAL_CHARACTERISTIC_REPRESENTATION(#{dimension_idx}, #{rep_idx});
+#{rep_idx} = SHAPE_DIMENSION_REPRESENTATION('', (#{value_idx}), #11);
+#{value_idx} = ( LENGTH_MEASURE_WITH_UNIT() MEASURE_REPRESENTATION_ITEM() MEASURE_WITH_UNIT(LENGTH_MEASURE({value:?}), #12) REPRESENTATION_ITEM('nominal value') );\n",
+        rep_idx = idx + 1,
+        value_idx = idx + 2,
+    ))?;
+    match tolerance {
+        Some((lower, upper)) => {
+            let idx = idx + 3;
+            f.write_fmt(format_args!(
+                "#{idx} = PLUS_MINUS_TOLERANCE(#{range_idx}, #{dimension_idx});
+#{range_idx} = TOLERANCE_VALUE(#{lower_idx}, #{upper_idx});
+#{lower_idx} = LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE({lower:?}), #12);
+#{upper_idx} = LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE({upper:?}), #12);\n",
+                range_idx = idx + 1,
+                lower_idx = idx + 2,
+                upper_idx = idx + 3,
+            ))?;
+            Ok(idx + 4)
+        }
+        None => Ok(idx + 3),
+    }
+}
+
+impl<'a, P, C, S> Display for StepAnnotatedModel<'a, P, C, S>
+where
+    P: Copy,
+    C: StepLength,
+    S: StepLength,
+    StepDisplay<&'a CompressedSolid<P, C, S>>: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(&self.model, f)?;
+        let (shells, mut cursor) = self.model.shape_display.entity_indices();
+        let item = |anchor: Anchor| anchor_index(&shells, anchor).ok_or(std::fmt::Error);
+        self.annotations.iter().try_for_each(|annotation| {
+            cursor = match annotation {
+                Annotation::LinearDimension {
+                    from,
+                    to,
+                    value,
+                    tolerance,
+                } => {
+                    let from_idx = cursor;
+                    let to_idx = write_shape_aspect(f, from_idx, item(*from)?)?;
+                    let dimension_idx = write_shape_aspect(f, to_idx, item(*to)?)?;
+                    f.write_fmt(format_args!(
+                        "#{dimension_idx} = DIMENSIONAL_LOCATION('linear distance', '', #{from_idx}, #{to_idx});\n"
+                    ))?;
+                    write_dimension_value(f, dimension_idx + 1, dimension_idx, *value, *tolerance)?
+                }
+                Annotation::Radius {
+                    anchor,
+                    value,
+                    tolerance,
+                } => {
+                    let aspect_idx = cursor;
+                    let dimension_idx = write_shape_aspect(f, aspect_idx, item(*anchor)?)?;
+                    f.write_fmt(format_args!(
+                        "#{dimension_idx} = DIMENSIONAL_SIZE(#{aspect_idx}, 'radius');\n"
+                    ))?;
+                    write_dimension_value(f, dimension_idx + 1, dimension_idx, *value, *tolerance)?
+                }
+                Annotation::Note {
+                    anchor,
+                    position,
+                    text,
+                } => {
+                    let aspect_idx = cursor;
+                    let idx = write_shape_aspect(f, aspect_idx, item(*anchor)?)?;
+                    f.write_fmt(format_args!(
+                        "#{idx} = PROPERTY_DEFINITION('note', '', #{aspect_idx});
+#{pdr_idx} = PROPERTY_DEFINITION_REPRESENTATION(#{idx}, #{rep_idx});
+#{rep_idx} = REPRESENTATION('', (#{text_idx}, #{point_idx}), #11);
+#{text_idx} = DESCRIPTIVE_REPRESENTATION_ITEM('text', '{text}');
+#{point_idx} = CARTESIAN_POINT('', {coordinates});\n",
+                        pdr_idx = idx + 1,
+                        rep_idx = idx + 2,
+                        text_idx = idx + 3,
+                        point_idx = idx + 4,
+                        text = StepString(text),
+                        coordinates = SliceDisplay(AsRef::<[f64; 3]>::as_ref(position)),
+                    ))?;
+                    idx + 5
+                }
+            };
+            Ok(())
+        })
+    }
+}
@@ -1,6 +1,6 @@
 use super::{Result, *};
 use truck_geometry::*;
-use truck_modeling::{Curve as ModelingCurve, Leader, Surface as ModelingSurface};
+use truck_modeling::{custom, Curve as ModelingCurve, Leader, Surface as ModelingSurface};
 use truck_polymesh::PolylineCurve;
 
 impl Display for StepDisplay<Point2> {
@@ -306,6 +306,10 @@ impl<'a> Display for StepDisplay<&'a ModelingCurve> {
             ModelingCurve::BSplineCurve(x) => Display::fmt(&StepDisplay::new(x, self.idx), f),
             ModelingCurve::NURBSCurve(x) => Display::fmt(&StepDisplay::new(x, self.idx), f),
             ModelingCurve::IntersectionCurve(x) => Display::fmt(&StepDisplay::new(x, self.idx), f),
+            ModelingCurve::Custom(x) => {
+                let curve = custom::curve_approximation(x, custom::APPROXIMATION_TOLERANCE);
+                Display::fmt(&StepDisplay::new(&curve, self.idx), f)
+            }
         }
     }
 }
@@ -323,6 +327,9 @@ impl StepLength for ModelingCurve {
             ModelingCurve::BSplineCurve(x) => x.step_length(),
             ModelingCurve::NURBSCurve(x) => x.step_length(),
             ModelingCurve::IntersectionCurve(x) => x.step_length(),
+            ModelingCurve::Custom(x) => {
+                custom::curve_approximation(x, custom::APPROXIMATION_TOLERANCE).step_length()
+            }
         }
     }
 }
@@ -557,6 +564,10 @@ impl<'a> Display for StepDisplay<&'a ModelingSurface> {
             ModelingSurface::BSplineSurface(x) => Display::fmt(&StepDisplay::new(x, self.idx), f),
             ModelingSurface::NURBSSurface(x) => Display::fmt(&StepDisplay::new(x, self.idx), f),
             ModelingSurface::RevolutedCurve(x) => Display::fmt(&StepDisplay::new(x, self.idx), f),
+            ModelingSurface::Custom(x) => {
+                let surface = custom::surface_approximation(x, custom::APPROXIMATION_TOLERANCE);
+                Display::fmt(&StepDisplay::new(&surface, self.idx), f)
+            }
         }
     }
 }
@@ -568,6 +579,9 @@ impl StepLength for ModelingSurface {
             ModelingSurface::BSplineSurface(x) => x.step_length(),
             ModelingSurface::NURBSSurface(x) => x.step_length(),
             ModelingSurface::RevolutedCurve(x) => x.entity().step_length(),
+            ModelingSurface::Custom(x) => {
+                custom::surface_approximation(x, custom::APPROXIMATION_TOLERANCE).step_length()
+            }
         }
     }
 }
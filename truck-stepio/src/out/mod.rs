@@ -240,6 +240,10 @@ impl<'a, P, C, S> ModelShape for &'a CompressedShell<P, C, S> {}
 impl<'a, P, C, S> ModelShape for &'a Solid<P, C, S> {}
 #[rustfmt::skip]
 impl<'a, P, C, S> ModelShape for &'a CompressedSolid<P, C, S> {}
+#[rustfmt::skip]
+impl<'a, P, C, S> ModelShape for Deduplicated<&'a CompressedShell<P, C, S>> {}
+#[rustfmt::skip]
+impl<'a, P, C, S> ModelShape for Deduplicated<&'a CompressedSolid<P, C, S>> {}
 
 impl<T: ModelShape> StepModel<T> {
     /// constructor
@@ -317,5 +321,8 @@ impl<T> CompleteStepDisplay<T> {
     }
 }
 
+mod annotation;
+pub use annotation::StepAnnotatedModel;
 mod geometry;
 mod topology;
+pub use topology::Deduplicated;
@@ -1,4 +1,6 @@
 use super::{Result, *};
+use truck_base::tolerance::TOLERANCE;
+use truck_geotrait::Quantize;
 
 #[derive(Clone, Debug)]
 struct StepShell<'a, P, C, S> {
@@ -6,23 +8,102 @@ struct StepShell<'a, P, C, S> {
     face_indices: Vec<usize>,
     ep_edges: usize,
     ep_vertices: usize,
+    /// the indices of the surfaces, shared by the identical surfaces
     surface_indices: Vec<usize>,
+    /// whether the surface is the first one of the identical surfaces, i.e. is output
+    unique_surfaces: Vec<bool>,
+    /// the indices of the curves, shared by the identical curves
     curve_indices: Vec<usize>,
+    /// whether the curve is the first one of the identical curves, i.e. is output
+    unique_curves: Vec<bool>,
     ep_points: usize,
 }
 
-impl<'a, P, C, S> StepDisplay<&'a CompressedShell<P, C, S>>
-where
-    P: Copy,
-    C: StepLength,
-    S: StepLength,
-{
-    fn to_step_shell(&self) -> StepShell<'a, P, C, S> {
-        let shell = self.entity;
+/// Display wrapper of the compressed shells and solids, whose geometries identical within
+/// `TOLERANCE` are output only once, e.g. the planes shared by the coplanar faces.
+///
+/// The geometries are compared by [`Quantize`], so it requires the curves and the surfaces to be
+/// `Quantize`. Without this wrapper, all geometries are output as they are.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_stepio::out::*;
+///
+/// // the unit cube, whose second face has the same surface as the first one
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+/// let mut shell = cube.compress().boundaries.pop().unwrap();
+/// shell.faces[1].surface = shell.faces[0].surface.clone();
+///
+/// let count_planes = |step: String| step.matches("= PLANE(").count();
+/// let step = CompleteStepDisplay::new(StepModel::new(&shell), Default::default());
+/// assert_eq!(count_planes(step.to_string()), 6);
+/// let step = CompleteStepDisplay::new(StepModel::new(Deduplicated(&shell)), Default::default());
+/// assert_eq!(count_planes(step.to_string()), 5);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Deduplicated<T>(pub T);
+
+/// the indices of the first geometries identical to each surface and each curve
+#[derive(Clone, Debug)]
+struct Identicals {
+    surfaces: Vec<usize>,
+    curves: Vec<usize>,
+}
+
+impl Identicals {
+    /// all geometries are distinct.
+    fn distinct<P, C, S>(shell: &CompressedShell<P, C, S>) -> Self {
+        Self {
+            surfaces: (0..shell.faces.len()).collect(),
+            curves: (0..shell.edges.len()).collect(),
+        }
+    }
+    /// the geometries identical within `TOLERANCE` are identified.
+    fn within_tolerance<P, C: Quantize, S: Quantize>(shell: &CompressedShell<P, C, S>) -> Self {
+        Self {
+            surfaces: shell.identical_surfaces(TOLERANCE),
+            curves: shell.identical_curves(TOLERANCE),
+        }
+    }
+}
+
+/// Returns the indices of the geometries starting from `cursor`, and whether each geometry is
+/// output. The geometries share the indices with the first identical ones in `identicals`.
+fn deduplicated_indices<T: StepLength>(
+    geometries: &[&T],
+    identicals: &[usize],
+    cursor: &mut usize,
+) -> (Vec<usize>, Vec<bool>) {
+    let mut indices = Vec::<usize>::with_capacity(geometries.len());
+    let unique = geometries
+        .iter()
+        .zip(identicals)
+        .enumerate()
+        .map(|(i, (geometry, first))| match *first == i {
+            true => {
+                indices.push(*cursor);
+                *cursor += geometry.step_length();
+                true
+            }
+            false => {
+                indices.push(indices[*first]);
+                false
+            }
+        })
+        .collect();
+    (indices, unique)
+}
+
+impl<'a, P, C: StepLength, S: StepLength> StepShell<'a, P, C, S> {
+    /// the shell whose entity index is `idx`
+    fn new(shell: &'a CompressedShell<P, C, S>, idx: usize, identicals: Identicals) -> Self {
         let faces = &shell.faces;
         let edges = &shell.edges;
         let vertices = &shell.vertices;
-        let mut cursor = self.idx + 1;
+        let mut cursor = idx + 1;
         let face_indices = faces
             .iter()
             .map(|f| {
@@ -34,30 +115,26 @@ where
         let ep_edges = cursor;
         let ep_vertices = ep_edges + edges.len();
         cursor = ep_vertices + vertices.len();
-        let surface_indices = faces
-            .iter()
-            .map(|f| {
-                let res = cursor;
-                cursor += f.surface.step_length();
-                res
-            })
-            .collect::<Vec<_>>();
-        let curve_indices = edges
-            .iter()
-            .map(|e| {
-                let res = cursor;
-                cursor += e.curve.step_length();
-                res
-            })
-            .collect::<Vec<_>>();
+        let (surface_indices, unique_surfaces) = deduplicated_indices(
+            &faces.iter().map(|f| &f.surface).collect::<Vec<_>>(),
+            &identicals.surfaces,
+            &mut cursor,
+        );
+        let (curve_indices, unique_curves) = deduplicated_indices(
+            &edges.iter().map(|e| &e.curve).collect::<Vec<_>>(),
+            &identicals.curves,
+            &mut cursor,
+        );
         let ep_points = cursor;
         StepShell {
-            entity: self.entity,
+            entity: shell,
             face_indices,
             ep_edges,
             ep_vertices,
             surface_indices,
+            unique_surfaces,
             curve_indices,
+            unique_curves,
             ep_points,
         }
     }
@@ -76,7 +153,9 @@ where
             ep_edges,
             ep_vertices,
             surface_indices,
+            unique_surfaces,
             curve_indices,
+            unique_curves,
             ep_points,
         } = self;
         let faces = &entity.faces;
@@ -138,13 +217,22 @@ where
                 vertex_geometry = ep_points + i,
             ))
         })?;
-        faces.iter().zip(surface_indices).try_for_each(|(f, idx)| {
-            Display::fmt(&StepDisplay::new(&f.surface, *idx), formatter)
-        })?;
+        faces
+            .iter()
+            .zip(surface_indices)
+            .zip(unique_surfaces)
+            .filter(|(_, unique)| **unique)
+            .try_for_each(|((f, idx), _)| {
+                Display::fmt(&StepDisplay::new(&f.surface, *idx), formatter)
+            })?;
         edges
             .iter()
             .zip(curve_indices)
-            .try_for_each(|(e, idx)| Display::fmt(&StepDisplay::new(&e.curve, *idx), formatter))?;
+            .zip(unique_curves)
+            .filter(|(_, unique)| **unique)
+            .try_for_each(|((e, idx), _)| {
+                Display::fmt(&StepDisplay::new(&e.curve, *idx), formatter)
+            })?;
         vertices
             .iter()
             .enumerate()
@@ -158,6 +246,28 @@ impl<'a, P, C, S> StepLength for StepShell<'a, P, C, S> {
     }
 }
 
+/// Writes the shell based surface model of `step_shell`, whose model index is `idx`.
+fn fmt_shell_model<'a, P: Copy, C, S>(
+    step_shell: &StepShell<'a, P, C, S>,
+    idx: usize,
+    f: &mut Formatter<'_>,
+) -> Result
+where
+    StepDisplay<P>: Display,
+    StepDisplay<&'a C>: Display,
+    StepDisplay<&'a S>: Display,
+{
+    let shell_idx = idx + 1;
+    f.write_fmt(format_args!(
+        "#{idx} = SHELL_BASED_SURFACE_MODEL('', (#{shell_idx}));\n"
+    ))?;
+    f.write_fmt(format_args!(
+        "#{shell_idx} = OPEN_SHELL('', {face_indices});\n",
+        face_indices = IndexSliceDisplay(step_shell.face_indices.iter().copied()),
+    ))?;
+    Display::fmt(step_shell, f)
+}
+
 impl<'a, P, C, S> Display for StepDisplay<&'a CompressedShell<P, C, S>>
 where
     P: Copy,
@@ -169,16 +279,95 @@ where
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         let StepDisplay { entity: shell, idx } = self;
+        let step_shell = StepShell::new(*shell, idx + 1, Identicals::distinct(*shell));
+        fmt_shell_model::<P, C, S>(&step_shell, *idx, f)
+    }
+}
+
+impl<'a, P, C, S> Display for StepDisplay<Deduplicated<&'a CompressedShell<P, C, S>>>
+where
+    P: Copy,
+    C: StepLength + Quantize,
+    S: StepLength + Quantize,
+    StepDisplay<P>: Display,
+    StepDisplay<&'a C>: Display,
+    StepDisplay<&'a S>: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let StepDisplay {
+            entity: Deduplicated(shell),
+            idx,
+        } = self;
+        let step_shell = StepShell::new(*shell, idx + 1, Identicals::within_tolerance(*shell));
+        fmt_shell_model::<P, C, S>(&step_shell, *idx, f)
+    }
+}
+
+/// Returns the boundary shells of `solid` whose entity index is `idx`.
+fn step_shells<'a, P, C: StepLength, S: StepLength>(
+    solid: &'a CompressedSolid<P, C, S>,
+    idx: usize,
+    identicals: impl Fn(&CompressedShell<P, C, S>) -> Identicals,
+) -> Vec<StepShell<'a, P, C, S>> {
+    let mut cursor = idx + 1;
+    solid
+        .boundaries
+        .iter()
+        .map(|shell| {
+            let res = StepShell::new(shell, cursor, identicals(shell));
+            cursor += 1 + res.step_length();
+            res
+        })
+        .collect()
+}
+
+/// Writes the solid of `step_shells`, whose entity index is `idx`.
+fn fmt_solid<'a, P: Copy, C, S>(
+    step_shells: &[StepShell<'a, P, C, S>],
+    idx: usize,
+    f: &mut Formatter<'_>,
+) -> Result
+where
+    StepDisplay<P>: Display,
+    StepDisplay<&'a C>: Display,
+    StepDisplay<&'a S>: Display,
+{
+    if step_shells.is_empty() {
+        f.pad("empty solid!")?;
+        Err(std::fmt::Error)
+    } else if step_shells.len() == 1 {
         let shell_idx = idx + 1;
         f.write_fmt(format_args!(
-            "#{idx} = SHELL_BASED_SURFACE_MODEL('', (#{shell_idx}));\n"
+            "#{idx} = MANIFOLD_SOLID_BREP('', #{shell_idx});\n"
         ))?;
-        let step_shell = StepDisplay::new(*shell, shell_idx).to_step_shell();
         f.write_fmt(format_args!(
-            "#{shell_idx} = OPEN_SHELL('', {face_indices});\n",
-            face_indices = IndexSliceDisplay(step_shell.face_indices.iter().copied()),
+            "#{shell_idx} = CLOSED_SHELL('', {face_indices});\n",
+            face_indices = IndexSliceDisplay(step_shells[0].face_indices.iter().copied()),
         ))?;
-        Display::fmt(&step_shell, f)
+        Display::fmt(&step_shells[0], f)
+    } else {
+        let first_shell_idx = step_shells[0].face_indices[0] - 1;
+        f.write_fmt(format_args!(
+            "#{idx} = BREP_WITH_VOIDS('', #{first_shell_idx}, {other_shells});\n",
+            other_shells = IndexSliceDisplay(
+                step_shells[1..]
+                    .iter()
+                    .map(|step_shell| step_shell.face_indices[0] - 1)
+            ),
+        ))?;
+        f.write_fmt(format_args!(
+            "#{first_shell_idx} = CLOSED_SHELL('', {face_indices});\n",
+            face_indices = IndexSliceDisplay(step_shells[0].face_indices.iter().copied()),
+        ))?;
+        Display::fmt(&step_shells[0], f)?;
+        step_shells[1..].iter().try_for_each(|step_shell| {
+            f.write_fmt(format_args!(
+                "#{shell_idx} = ORIENTED_CLOSED_SHELL('', {face_indices}, .T.);\n",
+                shell_idx = step_shell.face_indices[0] - 1,
+                face_indices = IndexSliceDisplay(step_shell.face_indices.iter().copied()),
+            ))?;
+            Display::fmt(step_shell, f)
+        })
     }
 }
 
@@ -192,55 +381,57 @@ where
     StepDisplay<&'a S>: Display,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let StepDisplay { entity: solid, idx } = self;
-        if solid.boundaries.is_empty() {
-            f.pad("empty solid!")?;
-            Err(std::fmt::Error)
-        } else if solid.boundaries.len() == 1 {
-            let shell_idx = idx + 1;
-            f.write_fmt(format_args!(
-                "#{idx} = MANIFOLD_SOLID_BREP('', #{shell_idx});\n"
-            ))?;
-            let shell = &solid.boundaries[0];
-            let step_shell = StepDisplay::new(shell, shell_idx).to_step_shell();
-            f.write_fmt(format_args!(
-                "#{shell_idx} = CLOSED_SHELL('', {face_indices});\n",
-                face_indices = IndexSliceDisplay(step_shell.face_indices.iter().copied()),
-            ))?;
-            Display::fmt(&step_shell, f)
-        } else {
-            let mut cursor = self.idx + 1;
-            let step_shells = solid
-                .boundaries
-                .iter()
-                .map(|shell| {
-                    let res = StepDisplay::new(shell, cursor).to_step_shell();
-                    cursor += 1 + res.step_length();
-                    res
-                })
-                .collect::<Vec<_>>();
-            let first_shell_idx = step_shells[0].face_indices[0] - 1;
-            f.write_fmt(format_args!(
-                "#{idx} = BREP_WITH_VOIDS('', #{first_shell_idx}, {other_shells});\n",
-                other_shells = IndexSliceDisplay(
-                    step_shells[1..]
-                        .iter()
-                        .map(|step_shell| step_shell.face_indices[0] - 1)
-                ),
-            ))?;
-            f.write_fmt(format_args!(
-                "#{first_shell_idx} = CLOSED_SHELL('', {face_indices});\n",
-                face_indices = IndexSliceDisplay(step_shells[0].face_indices.iter().copied()),
-            ))?;
-            Display::fmt(&step_shells[0], f)?;
-            step_shells[1..].iter().try_for_each(|step_shell| {
-                f.write_fmt(format_args!(
-                    "#{shell_idx} = ORIENTED_CLOSED_SHELL('', {face_indices}, .T.);\n",
-                    shell_idx = step_shell.face_indices[0] - 1,
-                    face_indices = IndexSliceDisplay(step_shell.face_indices.iter().copied()),
-                ))?;
-                Display::fmt(step_shell, f)
+        let step_shells = step_shells(self.entity, self.idx, Identicals::distinct);
+        fmt_solid::<P, C, S>(&step_shells, self.idx, f)
+    }
+}
+
+impl<'a, P, C, S> Display for StepDisplay<Deduplicated<&'a CompressedSolid<P, C, S>>>
+where
+    P: Copy,
+    C: StepLength + Quantize,
+    S: StepLength + Quantize,
+    StepDisplay<P>: Display,
+    StepDisplay<&'a C>: Display,
+    StepDisplay<&'a S>: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let step_shells = step_shells(self.entity.0, self.idx, Identicals::within_tolerance);
+        fmt_solid::<P, C, S>(&step_shells, self.idx, f)
+    }
+}
+
+/// STEP entity indices of the topological elements of a boundary shell
+#[derive(Clone, Debug)]
+pub(super) struct ShellEntityIndices {
+    pub(super) faces: Vec<usize>,
+    pub(super) ep_edges: usize,
+    pub(super) ep_vertices: usize,
+}
+
+impl<'a, P, C, S> StepDisplay<&'a CompressedSolid<P, C, S>>
+where
+    P: Copy,
+    C: StepLength,
+    S: StepLength,
+{
+    /// Returns the entity indices of each boundary shell and the index next to the last entity.
+    pub(super) fn entity_indices(&self) -> (Vec<ShellEntityIndices>, usize) {
+        let mut cursor = self.idx + 1;
+        let shells = self
+            .entity
+            .boundaries
+            .iter()
+            .map(|shell| {
+                let step_shell = StepShell::new(shell, cursor, Identicals::distinct(shell));
+                cursor += 1 + step_shell.step_length();
+                ShellEntityIndices {
+                    faces: step_shell.face_indices,
+                    ep_edges: step_shell.ep_edges,
+                    ep_vertices: step_shell.ep_vertices,
+                }
             })
-        }
+            .collect();
+        (shells, cursor)
     }
 }
@@ -496,3 +496,62 @@ ENDSEC;
     };
     assert_eq!(table, ans_table);
 }
+
+/// the data section whose statements contain semicolons in strings
+const PARALLEL_DATA: &str = "DATA;
+#1 = CARTESIAN_POINT('Point; 1', (0.0, 0.0, 0.0));
+#2 = CARTESIAN_POINT('Point; 2', (1.0, 0.0, 0.0));
+#3 = DIRECTION('Dir', (1.0, 0.0, 0.0));
+#4 = VECTOR('Vector', #3, 1.0);
+#5 = LINE('Line', #1, #4);
+#6 = VERTEX_POINT('Vertex', #1);
+#7 = VERTEX_POINT('Vertex', #2);
+#8 = EDGE_CURVE('Edge', #6, #7, #5, .T.);
+ENDSEC;
+";
+
+#[test]
+fn read_parallel() {
+    let step = format!(
+        "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('semicolons; in strings'), '2;1');
+FILE_NAME('', '', (''), (''), '', '', '');
+FILE_SCHEMA(('CONFIG_CONTROL_DESIGN'));
+ENDSEC;
+/* the statements are separated; not by this semicolon */
+{PARALLEL_DATA}END-ISO-10303-21;"
+    );
+    let table = Table::from_step(&step).unwrap();
+    let data_section = DataSection::from_str(PARALLEL_DATA).unwrap();
+    assert_eq!(table, Table::from_data_section(&data_section));
+
+    assert!(Table::from_step("DATA; #1 = CARTESIAN_POINT('', (0.0, 1.0); ENDSEC;").is_err());
+}
+
+#[test]
+fn compressed_shells_parallel() {
+    use truck_modeling::*;
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    let step = truck_stepio::out::CompleteStepDisplay::new(
+        truck_stepio::out::StepModel::new(&cube.compress()),
+        Default::default(),
+    )
+    .to_string();
+
+    let table = Table::from_step(&step).unwrap();
+    let exchange = ruststep::parser::parse(&step).unwrap();
+    assert_eq!(table, Table::from_data_section(&exchange.data[0]));
+
+    let shells = table.to_compressed_shells();
+    assert_eq!(shells.len(), 1);
+    shells.into_iter().for_each(|(idx, shell)| {
+        let shell = shell.unwrap();
+        assert_eq!(shell.faces.len(), 6);
+        let sequential = table.to_compressed_shell(&table.shell[&idx]).unwrap();
+        assert_eq!(shell.vertices, sequential.vertices);
+    });
+}
@@ -0,0 +1,72 @@
+use truck_modeling::{annotation::*, *};
+use truck_stepio::out::*;
+
+#[test]
+fn parse_annotated_solid() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+
+    let mut annotated = AnnotatedSolid::new(cube);
+    let faces: Vec<Face> = annotated.solid.face_iter().cloned().collect();
+    let edge = faces[0].boundaries()[0][0].clone();
+    let bottom = annotated.face_anchor(&faces[0]).unwrap();
+    let top = annotated.face_anchor(&faces[5]).unwrap();
+    let edge = annotated.edge_anchor(&edge).unwrap();
+    annotated.annotations = vec![
+        Annotation::LinearDimension {
+            from: bottom,
+            to: top,
+            value: 1.0,
+            tolerance: Some((-0.01, 0.01)),
+        },
+        Annotation::Radius {
+            anchor: edge,
+            value: 0.5,
+            tolerance: None,
+        },
+        Annotation::Note {
+            anchor: top,
+            position: Point3::new(0.5, 0.5, 1.5),
+            text: "polish".to_string(),
+        },
+    ];
+
+    let solid = annotated.solid.compress();
+    let step_string = CompleteStepDisplay::new(
+        StepAnnotatedModel::new(&solid, &annotated.annotations),
+        Default::default(),
+    )
+    .to_string();
+    ruststep::parser::parse(&step_string).unwrap_or_else(|e| {
+        panic!("failed to parse step\n[Error Message]\n{e}[STEP file]\n{step_string}")
+    });
+    assert!(step_string.contains("DIMENSIONAL_LOCATION('linear distance'"));
+    assert!(step_string.contains("PLUS_MINUS_TOLERANCE"));
+    assert!(step_string.contains("DIMENSIONAL_SIZE"));
+    assert!(step_string.contains("DESCRIPTIVE_REPRESENTATION_ITEM('text', 'polish')"));
+
+    // the geometric items of shape aspects are topological entities of the solid.
+    step_string
+        .lines()
+        .filter(|line| line.contains("GEOMETRIC_ITEM_SPECIFIC_USAGE"))
+        .for_each(|line| {
+            let item = line.rsplit('#').next().unwrap().trim_end_matches(");");
+            let prefix = format!("#{item} = ");
+            let entity = step_string
+                .lines()
+                .find(|line| line.starts_with(&prefix))
+                .unwrap();
+            assert!(entity.contains("FACE_SURFACE") || entity.contains("EDGE_CURVE"));
+        });
+
+    // invalid anchors cannot be displayed.
+    let annotations = [Annotation::Radius {
+        anchor: Anchor::Face { shell: 0, index: 6 },
+        value: 1.0,
+        tolerance: None,
+    }];
+    let display = StepAnnotatedModel::new(&solid, &annotations);
+    assert!(std::fmt::Write::write_fmt(&mut String::new(), format_args!("{display}")).is_err());
+}
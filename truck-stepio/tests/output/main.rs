@@ -1,2 +1,3 @@
+mod annotation;
 mod geometry;
 mod topology;
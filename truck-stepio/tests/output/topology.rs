@@ -44,3 +44,32 @@ fn parse_shell() {
         });
     }
 }
+
+#[test]
+fn deduplicate_geometries() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    let mut shell = cube.compress().boundaries.pop().unwrap();
+    let count_planes = |step_string: String| {
+        ruststep::parser::parse(&step_string).unwrap();
+        step_string.matches("= PLANE(").count()
+    };
+    let default_planes = |shell: &CompressedShell<Point3, Curve, Surface>| {
+        count_planes(
+            CompleteStepDisplay::new(StepModel::new(shell), Default::default()).to_string(),
+        )
+    };
+    let deduplicated_planes = |shell: &CompressedShell<Point3, Curve, Surface>| {
+        let model = StepModel::new(Deduplicated(shell));
+        count_planes(CompleteStepDisplay::new(model, Default::default()).to_string())
+    };
+    assert_eq!(default_planes(&shell), 6);
+    assert_eq!(deduplicated_planes(&shell), 6);
+    // the surfaces identical within the tolerance are output once only if deduplicated.
+    let trans = Matrix4::from_translation(Vector3::new(1.0e-9, 0.0, 0.0));
+    shell.faces[1].surface = shell.faces[0].surface.transformed(trans);
+    assert_eq!(default_planes(&shell), 6);
+    assert_eq!(deduplicated_planes(&shell), 5);
+}
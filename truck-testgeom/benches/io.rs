@@ -0,0 +1,53 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use truck_meshalgo::prelude::*;
+use truck_stepio::{out, r#in::Table};
+use truck_testgeom::*;
+
+fn io(c: &mut Criterion) {
+    let mut group = c.benchmark_group("io");
+    group.sample_size(10);
+    Complexity::ALL.into_iter().for_each(|complexity| {
+        let solid = punched_cube(complexity).compress();
+        let step = out::CompleteStepDisplay::new(out::StepModel::new(&solid), Default::default())
+            .to_string();
+        group.bench_function(format!("step_write/{complexity:?}"), |b| {
+            b.iter(|| {
+                out::CompleteStepDisplay::new(out::StepModel::new(&solid), Default::default())
+                    .to_string()
+            })
+        });
+        group.bench_function(format!("step_read/{complexity:?}"), |b| {
+            b.iter(|| Table::from_step(&step).unwrap().to_compressed_shells())
+        });
+
+        let mesh = gear(complexity).triangulation(0.001).to_polygon();
+        let mut obj = Vec::new();
+        obj::write(&mesh, &mut obj).unwrap();
+        let mut stl = Vec::new();
+        stl::write(&mesh, &mut stl, stl::STLType::Binary).unwrap();
+        group.bench_function(format!("obj_write/{complexity:?}"), |b| {
+            b.iter(|| {
+                let mut buffer = Vec::new();
+                obj::write(&mesh, &mut buffer).unwrap();
+                buffer
+            })
+        });
+        group.bench_function(format!("obj_read/{complexity:?}"), |b| {
+            b.iter(|| obj::read(obj.as_slice()).unwrap())
+        });
+        group.bench_function(format!("stl_write/{complexity:?}"), |b| {
+            b.iter(|| {
+                let mut buffer = Vec::new();
+                stl::write(&mesh, &mut buffer, stl::STLType::Binary).unwrap();
+                buffer
+            })
+        });
+        group.bench_function(format!("stl_read/{complexity:?}"), |b| {
+            b.iter(|| stl::read(stl.as_slice(), stl::STLType::Automatic).unwrap())
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, io);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use truck_modeling::*;
+use truck_testgeom::*;
+
+fn sweeps(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sweeps");
+    group.sample_size(10);
+    Complexity::ALL.into_iter().for_each(|complexity| {
+        group.bench_function(format!("bottle/{complexity:?}"), |b| {
+            b.iter(|| bottle(complexity))
+        });
+        group.bench_function(format!("punched_cube/{complexity:?}"), |b| {
+            b.iter(|| punched_cube(complexity))
+        });
+        group.bench_function(format!("gear/{complexity:?}"), |b| {
+            b.iter(|| gear(complexity))
+        });
+        // revolve the sections of the gear around the y-axis
+        let face = gear(complexity).boundaries()[0][0].clone();
+        let face = builder::translated(&face, Vector3::new(0.0, 2.0, 0.0));
+        group.bench_function(format!("revolved_gear/{complexity:?}"), |b| {
+            b.iter(|| builder::rsweep(&face, Point3::origin(), Vector3::unit_x(), Rad(1.0)))
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, sweeps);
+criterion_main!(benches);
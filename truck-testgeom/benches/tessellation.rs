@@ -0,0 +1,28 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use truck_meshalgo::prelude::*;
+use truck_testgeom::*;
+
+fn tessellation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tessellation");
+    group.sample_size(10);
+    Complexity::ALL.into_iter().for_each(|complexity| {
+        let solids = [
+            ("bottle", bottle(complexity)),
+            ("punched_cube", punched_cube(complexity)),
+            ("gear", gear(complexity)),
+        ];
+        solids.iter().for_each(|(name, solid)| {
+            group.bench_function(format!("{name}/{complexity:?}"), |b| {
+                b.iter(|| solid.triangulation(0.01).to_polygon())
+            });
+        });
+        let blade = turbine_blade(complexity);
+        group.bench_function(format!("turbine_blade/{complexity:?}"), |b| {
+            b.iter(|| blade.triangulation(0.001).to_polygon())
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, tessellation);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+//! Canonical test shapes for benchmarking truck
+//!
+//! The shapes are made at several [`Complexity`] levels, so that the costs of the heavy
+//! operations, e.g. tessellation, sweeps and I/O, can be compared on the same models.
+//! The benchmarks are in `truck-testgeom/benches`, measured by [criterion], and run by
+//! `cargo bench -p truck-testgeom`.
+//!
+//! [criterion]: https://docs.rs/criterion
+
+#![cfg_attr(not(debug_assertions), deny(warnings))]
+#![deny(clippy::all, rust_2018_idioms)]
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    trivial_casts,
+    trivial_numeric_casts,
+    unsafe_code,
+    unstable_features,
+    unused_import_braces,
+    unused_qualifications
+)]
+
+/// The complexity of the test shapes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Complexity {
+    /// a few faces, for quick checks
+    Low,
+    /// tens of faces
+    Medium,
+    /// hundreds of faces or dense control points
+    High,
+}
+
+impl Complexity {
+    /// All complexities in the ascending order.
+    pub const ALL: [Complexity; 3] = [Complexity::Low, Complexity::Medium, Complexity::High];
+
+    /// Returns the value corresponding to `self`.
+    #[inline(always)]
+    pub fn select<T>(self, low: T, medium: T, high: T) -> T {
+        match self {
+            Complexity::Low => low,
+            Complexity::Medium => medium,
+            Complexity::High => high,
+        }
+    }
+}
+
+mod shapes;
+pub use shapes::*;
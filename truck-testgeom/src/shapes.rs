@@ -0,0 +1,175 @@
+use crate::Complexity;
+use std::f64::consts::PI;
+use truck_modeling::*;
+
+/// closed wire through `points` made by circle arcs, whose transit points are `transits`
+fn arc_polygon(points: &[Point3], transits: &[Point3]) -> Wire {
+    let vertices: Vec<Vertex> = points.iter().map(|p| builder::vertex(*p)).collect();
+    (0..vertices.len())
+        .map(|i| {
+            let (v0, v1) = (&vertices[i], &vertices[(i + 1) % vertices.len()]);
+            builder::circle_arc(v0, v1, transits[i])
+        })
+        .collect()
+}
+
+/// closed polyline through `points`
+fn polygon(points: &[Point3]) -> Wire {
+    let vertices: Vec<Vertex> = points.iter().map(|p| builder::vertex(*p)).collect();
+    (0..vertices.len())
+        .map(|i| builder::line(&vertices[i], &vertices[(i + 1) % vertices.len()]))
+        .collect()
+}
+
+/// circle in the plane parallel to the xy-plane, oriented counterclockwise
+fn circle(center: Point3, radius: f64) -> Wire {
+    let vertex = builder::vertex(center + radius * Vector3::unit_x());
+    builder::rsweep(&vertex, center, Vector3::unit_z(), Rad(7.0))
+}
+
+/// the body of the bottle, an oval prism whose section consists of `division` arcs
+fn bottle_body(bottom: f64, height: f64, width: f64, thickness: f64, division: usize) -> Shell {
+    let oval = |t: f64| {
+        let (x, z) = (width / 2.0 * f64::cos(t), -thickness / 2.0 * f64::sin(t));
+        Point3::new(x, bottom, z)
+    };
+    let step = 2.0 * PI / division as f64;
+    let points: Vec<Point3> = (0..division).map(|i| oval(step * i as f64)).collect();
+    let transits: Vec<Point3> = (0..division)
+        .map(|i| oval(step * (i as f64 + 0.5)))
+        .collect();
+    let face = builder::try_attach_plane(&[arc_polygon(&points, &transits)]).unwrap();
+    let solid = builder::tsweep(&face, Vector3::new(0.0, height, 0.0));
+    solid.into_boundaries().pop().unwrap()
+}
+
+fn bottle_neck(bottom: f64, height: f64, radius: f64) -> Shell {
+    let vertex = builder::vertex(Point3::new(0.0, bottom, radius));
+    let circle = builder::rsweep(&vertex, Point3::origin(), Vector3::unit_y(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    let solid = builder::tsweep(&disk, Vector3::new(0.0, height, 0.0));
+    solid.into_boundaries().pop().unwrap()
+}
+
+fn glue_body_neck(body: &mut Shell, neck: Shell) {
+    let body_ceiling = body.last_mut().unwrap();
+    let wire = neck[0].boundaries()[0].clone();
+    body_ceiling.add_boundary(wire);
+    body.extend(neck.into_iter().skip(1));
+}
+
+/// The hollow bottle of the [OCCT tutorial] without fillets, whose height is `1.4` along the y-axis.
+///
+/// The section of the body is an oval approximated by 4, 12 or 36 circle arcs.
+///
+/// [OCCT tutorial]: https://dev.opencascade.org/doc/overview/html/occt__tutorial.html
+pub fn bottle(complexity: Complexity) -> Solid {
+    let (height, width, thickness) = (1.4, 1.0, 0.6);
+    let division = complexity.select(4, 12, 36);
+    let mut body = bottle_body(0.0, height, width, thickness, division);
+    let neck = bottle_neck(height, height / 10.0, thickness / 4.0);
+    glue_body_neck(&mut body, neck);
+
+    let eps = height / 50.0;
+    let mut inner_body = bottle_body(
+        eps,
+        height - 2.0 * eps,
+        width - 2.0 * eps,
+        thickness - 2.0 * eps,
+        division,
+    );
+    let inner_neck = bottle_neck(height - eps, height / 10.0 + eps, thickness / 4.0 - eps);
+    glue_body_neck(&mut inner_body, inner_neck);
+
+    let inner_hat = inner_body.pop().unwrap();
+    let wire = inner_hat.into_boundaries()[0].inverse();
+    body.last_mut().unwrap().add_boundary(wire);
+    body.extend(inner_body.into_iter().map(|face| face.inverse()));
+    Solid::new(vec![body])
+}
+
+/// The unit cube `[0, 1]^3` punched by `n x n` cylindrical holes along the z-axis,
+/// where `n` is 1, 3 or 8.
+///
+/// The radii of the holes are `0.3 / n`, so the volume is `1 - 0.09 PI` for all complexities.
+pub fn punched_cube(complexity: Complexity) -> Solid {
+    let n = complexity.select(1, 3, 8);
+    let v = builder::vertex(Point3::origin());
+    let edge = builder::tsweep(&v, Vector3::unit_x());
+    let mut face = builder::tsweep(&edge, Vector3::unit_y());
+    let radius = 0.3 / n as f64;
+    (0..n * n).for_each(|k| {
+        let (i, j) = ((k / n) as f64, (k % n) as f64);
+        let center = Point3::new((i + 0.5) / n as f64, (j + 0.5) / n as f64, 0.0);
+        face.add_boundary(circle(center, radius).inverse());
+    });
+    builder::tsweep(&face, Vector3::unit_z())
+}
+
+/// The spur gear with 8, 24 or 64 trapezoidal teeth and the bore of radius `0.3`.
+///
+/// The tip radius is `1`, the root radius is `0.85`, and the thickness is `0.3` along the z-axis.
+pub fn gear(complexity: Complexity) -> Solid {
+    let teeth = complexity.select(8, 24, 64);
+    let pitch = 2.0 * PI / teeth as f64;
+    let point = |radius: f64, angle: f64| {
+        Point3::new(radius * f64::cos(angle), radius * f64::sin(angle), 0.0)
+    };
+    let points: Vec<Point3> = (0..teeth)
+        .flat_map(|i| {
+            let angle = pitch * i as f64;
+            [
+                point(0.85, angle),
+                point(1.0, angle + 0.2 * pitch),
+                point(1.0, angle + 0.45 * pitch),
+                point(0.85, angle + 0.65 * pitch),
+            ]
+        })
+        .collect();
+    let bore = circle(Point3::origin(), 0.3).inverse();
+    let face = builder::try_attach_plane(&[polygon(&points), bore]).unwrap();
+    builder::tsweep(&face, 0.3 * Vector3::unit_z())
+}
+
+/// The twisted and cambered sheet like a turbine blade, a B-spline surface of degree 3
+/// with `4 x 4`, `8 x 8` or `16 x 16` control points.
+///
+/// The span is along the z-axis from `0.2` to `1`, and the blade is twisted by `0.8` radian
+/// from the hub to the tip. The high complexities add ripples on the camber.
+pub fn turbine_blade(complexity: Complexity) -> Shell {
+    let m = complexity.select(4, 8, 16);
+    let control_points: Vec<Vec<Point3>> = (0..m)
+        .map(|i| {
+            let s = i as f64 / (m - 1) as f64;
+            let (chord, twist) = (0.3 - 0.1 * s, 0.8 * s);
+            (0..m)
+                .map(|j| {
+                    let c = j as f64 / (m - 1) as f64;
+                    let ripple = 0.01 * f64::sin(m as f64 * PI * s) * f64::sin(PI * c);
+                    let camber = 0.08 * f64::sin(PI * c) + ripple;
+                    let (x, y) = ((c - 0.5) * chord, camber * chord);
+                    Point3::new(
+                        x * twist.cos() - y * twist.sin(),
+                        x * twist.sin() + y * twist.cos(),
+                        0.2 + 0.8 * s,
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    let knot_vec = KnotVec::uniform_knot(3, m - 3);
+    let surface = BSplineSurface::new((knot_vec.clone(), knot_vec), control_points);
+    let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+        .map(|(u, v)| builder::vertex(surface.subs(u, v)));
+    let wire: Wire = surface
+        .splitted_boundary()
+        .into_iter()
+        .enumerate()
+        .map(|(i, curve)| {
+            let (v0, v1) = (&corners[i], &corners[(i + 1) % 4]);
+            Edge::new(v0, v1, Curve::BSplineCurve(curve))
+        })
+        .collect();
+    let face = Face::new(vec![wire], Surface::BSplineSurface(surface));
+    vec![face].into()
+}
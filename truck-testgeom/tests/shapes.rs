@@ -0,0 +1,59 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_modeling::topology::Shell;
+use truck_testgeom::*;
+use truck_topology::shell::ShellCondition;
+
+fn closed_volume(shell: &Shell, tol: f64) -> f64 {
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    let mut mesh = shell.triangulation(tol).to_polygon();
+    mesh.put_together_same_attrs();
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    mesh.mass_properties().unwrap().volume
+}
+
+#[test]
+fn solids() {
+    let mut prev_faces = [0; 3];
+    Complexity::ALL.into_iter().for_each(|complexity| {
+        let bottle = bottle(complexity);
+        let volume = closed_volume(&bottle.boundaries()[0], 0.005);
+        // less than the oval body and the neck, and more than the thin wall
+        assert!(0.0 < volume && volume < 0.5 * 0.3 * PI * 1.4 + 0.15f64.powi(2) * PI * 0.14);
+        assert!(volume > 0.05, "{complexity:?}: {volume}");
+
+        let cube = punched_cube(complexity);
+        // the holes are small for the high complexity
+        let volume = closed_volume(&cube.boundaries()[0], 0.0005);
+        assert!(
+            (volume - (1.0 - 0.09 * PI)).abs() < 1.0e-2,
+            "{complexity:?}: {volume}"
+        );
+
+        let gear = gear(complexity);
+        let volume = closed_volume(&gear.boundaries()[0], 0.005);
+        let (min, max) = ((0.85f64.powi(2) - 0.09) * PI, (1.0 - 0.09) * PI);
+        assert!(
+            0.3 * min < volume && volume < 0.3 * max,
+            "{complexity:?}: {volume}"
+        );
+
+        let faces = [&bottle, &cube, &gear].map(|solid| solid.boundaries()[0].len());
+        (0..3).for_each(|i| assert!(faces[i] > prev_faces[i]));
+        prev_faces = faces;
+    });
+}
+
+#[test]
+fn blade() {
+    Complexity::ALL.into_iter().for_each(|complexity| {
+        let blade = turbine_blade(complexity);
+        assert_eq!(blade.len(), 1);
+        assert_eq!(blade.shell_condition(), ShellCondition::Oriented);
+        let mesh = blade.triangulation(0.001).to_polygon();
+        let bdd_box: BoundingBox<Point3> = mesh.positions().iter().collect();
+        assert!((bdd_box.min().z - 0.2).abs() < 1.0e-6);
+        assert!((bdd_box.max().z - 1.0).abs() < 1.0e-6);
+        assert_eq!(mesh.extract_boundaries().len(), 1);
+    });
+}
@@ -235,6 +235,48 @@ impl<P: Clone, C: Clone, S: Clone> Solid<P, C, S> {
     }
 }
 
+/// Returns the index of the first geometry with the same key as each geometry.
+fn first_identicals<'a, T: Quantize + 'a>(
+    geometries: impl Iterator<Item = &'a T>,
+    tol: f64,
+) -> Vec<usize> {
+    let mut map = HashMap::default();
+    geometries
+        .enumerate()
+        .map(|(i, geometry)| *map.entry(geometry.quantized_key(tol)).or_insert(i))
+        .collect()
+}
+
+impl<P, C: Quantize, S: Quantize> CompressedShell<P, C, S> {
+    /// Returns the index of the first edge whose curve is identical to the curve of each edge,
+    /// i.e. whose curve has the same [`QuantizedKey`] with `tol`.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// use truck_geotrait::*;
+    ///
+    /// // the curves are the numbers
+    /// let v = Vertex::news([(), (), ()]);
+    /// let wire: Wire<(), f64> = vec![
+    ///     Edge::new(&v[0], &v[1], 1.0),
+    ///     Edge::new(&v[1], &v[2], 2.0),
+    ///     Edge::new(&v[2], &v[0], 1.0 + 1.0e-9),
+    /// ]
+    /// .into();
+    /// let shell: Shell<(), f64, f64> = vec![Face::new(vec![wire], 0.0)].into();
+    /// assert_eq!(shell.compress().identical_curves(1.0e-6), vec![0, 1, 0]);
+    /// ```
+    pub fn identical_curves(&self, tol: f64) -> Vec<usize> {
+        first_identicals(self.edges.iter().map(|edge| &edge.curve), tol)
+    }
+
+    /// Returns the index of the first face whose surface is identical to the surface of
+    /// each face, i.e. whose surface has the same [`QuantizedKey`] with `tol`.
+    pub fn identical_surfaces(&self, tol: f64) -> Vec<usize> {
+        first_identicals(self.faces.iter().map(|face| &face.surface), tol)
+    }
+}
+
 // -------------------------- test -------------------------- //
 
 #[test]
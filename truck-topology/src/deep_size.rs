@@ -0,0 +1,175 @@
+use crate::compress::*;
+use crate::*;
+use rustc_hash::FxHashSet as HashSet;
+use std::mem::size_of;
+use truck_base::deep_size::DeepSize;
+
+/// Counts the shared allocations only once.
+#[derive(Debug, Default)]
+struct SharedCounter(HashSet<usize>);
+
+impl SharedCounter {
+    /// Returns the size of the allocation of `arc` if it has not been counted yet.
+    fn arc<T: DeepSize>(&mut self, arc: &Arc<Mutex<T>>) -> usize {
+        match self.0.insert(Arc::as_ptr(arc) as usize) {
+            // strong and weak counters, and the mutex
+            true => {
+                2 * size_of::<usize>() + size_of::<Mutex<T>>() + arc.lock().unwrap().heap_size()
+            }
+            false => 0,
+        }
+    }
+
+    /// Returns the size of the allocation of `geometry` if it has not been counted yet.
+    fn geometry<T: DeepSize>(&mut self, geometry: &Arc<Geometry<T>>) -> usize {
+        match self.0.insert(Arc::as_ptr(geometry) as usize) {
+            // strong and weak counters, and the mutex with the generation
+            true => {
+                2 * size_of::<usize>()
+                    + size_of::<Geometry<T>>()
+                    + geometry.lock().unwrap().heap_size()
+            }
+            false => 0,
+        }
+    }
+}
+
+/// The heap size of topological elements, counted through the shared counter.
+trait SharedHeapSize {
+    fn shared_heap_size(&self, counter: &mut SharedCounter) -> usize;
+}
+
+impl<P: DeepSize> SharedHeapSize for Vertex<P> {
+    #[inline(always)]
+    fn shared_heap_size(&self, counter: &mut SharedCounter) -> usize { counter.arc(&self.point) }
+}
+
+impl<P: DeepSize, C: DeepSize> SharedHeapSize for Edge<P, C> {
+    fn shared_heap_size(&self, counter: &mut SharedCounter) -> usize {
+        self.vertices.0.shared_heap_size(counter)
+            + self.vertices.1.shared_heap_size(counter)
+            + counter.geometry(&self.curve)
+    }
+}
+
+impl<P: DeepSize, C: DeepSize> SharedHeapSize for Wire<P, C> {
+    fn shared_heap_size(&self, counter: &mut SharedCounter) -> usize {
+        self.edge_list.capacity() * size_of::<Edge<P, C>>()
+            + self
+                .edge_list
+                .iter()
+                .map(|edge| edge.shared_heap_size(counter))
+                .sum::<usize>()
+    }
+}
+
+impl<P: DeepSize, C: DeepSize, S: DeepSize> SharedHeapSize for Face<P, C, S> {
+    fn shared_heap_size(&self, counter: &mut SharedCounter) -> usize {
+        self.boundaries.capacity() * size_of::<Wire<P, C>>()
+            + self
+                .boundaries
+                .iter()
+                .map(|wire| wire.shared_heap_size(counter))
+                .sum::<usize>()
+            + counter.geometry(&self.surface)
+    }
+}
+
+impl<P: DeepSize, C: DeepSize, S: DeepSize> SharedHeapSize for Shell<P, C, S> {
+    fn shared_heap_size(&self, counter: &mut SharedCounter) -> usize {
+        self.face_list.capacity() * size_of::<Face<P, C, S>>()
+            + self
+                .face_list
+                .iter()
+                .map(|face| face.shared_heap_size(counter))
+                .sum::<usize>()
+    }
+}
+
+impl<P: DeepSize, C: DeepSize, S: DeepSize> SharedHeapSize for Solid<P, C, S> {
+    fn shared_heap_size(&self, counter: &mut SharedCounter) -> usize {
+        self.boundaries.capacity() * size_of::<Shell<P, C, S>>()
+            + self
+                .boundaries
+                .iter()
+                .map(|shell| shell.shared_heap_size(counter))
+                .sum::<usize>()
+    }
+}
+
+macro_rules! impl_deep_size {
+    ($type: ident, $($gen: ident),*) => {
+        impl<$($gen: DeepSize),*> DeepSize for $type<$($gen),*> {
+            #[inline(always)]
+            fn heap_size(&self) -> usize { self.shared_heap_size(&mut SharedCounter::default()) }
+        }
+    };
+}
+
+impl_deep_size!(Vertex, P);
+impl_deep_size!(Edge, P, C);
+impl_deep_size!(Wire, P, C);
+impl_deep_size!(Face, P, C, S);
+/// The geometries shared by several elements are counted only once.
+/// # Examples
+/// ```
+/// use truck_base::deep_size::DeepSize;
+/// use truck_topology::*;
+/// let v = Vertex::news(&[(); 3]);
+/// let wire: Wire<(), ()> = vec![
+///     Edge::new(&v[0], &v[1], ()),
+///     Edge::new(&v[1], &v[2], ()),
+///     Edge::new(&v[2], &v[0], ()),
+/// ]
+/// .into();
+/// let face = Face::new(vec![wire], ());
+/// let shell: Shell<(), (), ()> = vec![face.clone()].into();
+/// let doubled: Shell<(), (), ()> = vec![face.clone(), face.inverse()].into();
+///
+/// // The second face shares the vertices, edges and surface with the first one.
+/// assert!(doubled.heap_size() < 2 * shell.heap_size());
+/// ```
+impl<P: DeepSize, C: DeepSize, S: DeepSize> DeepSize for Shell<P, C, S> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.shared_heap_size(&mut SharedCounter::default()) }
+}
+impl_deep_size!(Solid, P, C, S);
+
+impl<C: DeepSize> DeepSize for CompressedEdge<C> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.curve.heap_size() }
+}
+
+truck_base::impl_deep_size_without_heap!(CompressedEdgeIndex);
+
+impl<S: DeepSize> DeepSize for CompressedFace<S> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.boundaries.heap_size() + self.surface.heap_size() }
+}
+
+/// # Examples
+/// ```
+/// use truck_base::deep_size::DeepSize;
+/// use truck_topology::*;
+/// let v = Vertex::news(&[(); 3]);
+/// let wire: Wire<(), ()> = vec![
+///     Edge::new(&v[0], &v[1], ()),
+///     Edge::new(&v[1], &v[2], ()),
+///     Edge::new(&v[2], &v[0], ()),
+/// ]
+/// .into();
+/// let shell: Shell<(), (), ()> = vec![Face::new(vec![wire], ())].into();
+/// // The compressed shell does not have the pointers to the shared geometries.
+/// assert!(shell.compress().heap_size() < shell.heap_size());
+/// ```
+impl<P: DeepSize, C: DeepSize, S: DeepSize> DeepSize for CompressedShell<P, C, S> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize {
+        self.vertices.heap_size() + self.edges.heap_size() + self.faces.heap_size()
+    }
+}
+
+impl<P: DeepSize, C: DeepSize, S: DeepSize> DeepSize for CompressedSolid<P, C, S> {
+    #[inline(always)]
+    fn heap_size(&self) -> usize { self.boundaries.heap_size() }
+}
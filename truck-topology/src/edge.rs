@@ -43,7 +43,7 @@ impl<P, C> Edge<P, C> {
         Edge {
             vertices: (front.clone(), back.clone()),
             orientation: true,
-            curve: Arc::new(Mutex::new(curve)),
+            curve: Arc::new(Geometry::new(curve)),
         }
     }
 
@@ -272,7 +272,33 @@ impl<P, C> Edge<P, C> {
     /// assert_eq!(edge1.get_curve(), 1);
     /// ```
     #[inline(always)]
-    pub fn set_curve(&self, curve: C) { *self.curve.lock().unwrap() = curve; }
+    pub fn set_curve(&self, curve: C) { self.curve.set(curve); }
+
+    /// Returns the generation of the curve, which is renewed each time the curve is set.
+    ///
+    /// The generations are unique in the process, e.g. a new edge never has the generation of
+    /// a dropped edge, even if they have the same id. So, comparing the generations, one can
+    /// detect the edges edited after a tessellation.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(), ()]);
+    /// let edge0 = Edge::new(&v[0], &v[1], 0);
+    /// let edge1 = edge0.inverse();
+    /// let generation = edge0.generation();
+    /// assert_eq!(edge1.generation(), generation);
+    ///
+    /// // the generation is renewed by editing the curve.
+    /// edge1.set_curve(1);
+    /// assert_ne!(edge0.generation(), generation);
+    /// assert_eq!(edge0.generation(), edge1.generation());
+    ///
+    /// // the other edge has the other generation.
+    /// let edge2 = Edge::new(&v[0], &v[1], 1);
+    /// assert_ne!(edge2.generation(), edge0.generation());
+    /// ```
+    #[inline(always)]
+    pub fn generation(&self) -> usize { self.curve.generation() }
 
     /// Returns the id that does not depend on the direction of the edge.
     /// # Examples
@@ -285,7 +311,7 @@ impl<P, C> Edge<P, C> {
     /// assert_eq!(edge0.id(), edge1.id());
     /// ```
     #[inline(always)]
-    pub fn id(&self) -> EdgeID<C> { ID::new(Arc::as_ptr(&self.curve)) }
+    pub fn id(&self) -> EdgeID<C> { ID::new(&self.curve.entity) }
 
     /// Returns how many same edges.
     ///
@@ -414,12 +440,12 @@ impl<P, C> Edge<P, C> {
         let edge0 = Edge {
             vertices: (self.absolute_front().clone(), vertex.clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve0)),
+            curve: Arc::new(Geometry::new(curve0)),
         };
         let edge1 = Edge {
             vertices: (vertex.clone(), self.absolute_back().clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve1)),
+            curve: Arc::new(Geometry::new(curve1)),
         };
         if self.orientation {
             Some((edge0, edge1))
@@ -447,12 +473,12 @@ impl<P, C> Edge<P, C> {
         let edge0 = Edge {
             vertices: (self.absolute_front().clone(), vertex.clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve0)),
+            curve: Arc::new(Geometry::new(curve0)),
         };
         let edge1 = Edge {
             vertices: (vertex.clone(), self.absolute_back().clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve1)),
+            curve: Arc::new(Geometry::new(curve1)),
         };
         if self.orientation {
             Some((edge0, edge1))
@@ -581,7 +607,7 @@ impl<'a, P: Debug, C: Debug> Debug for DebugDisplay<'a, Edge<P, C>, EdgeDisplayF
         match self.format {
             EdgeDisplayFormat::Full { vertex_format } => f
                 .debug_struct("Edge")
-                .field("id", &Arc::as_ptr(&self.entity.curve))
+                .field("id", &self.entity.id())
                 .field(
                     "vertices",
                     &(
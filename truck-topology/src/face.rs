@@ -63,7 +63,7 @@ impl<P, C, S> Face<P, C, S> {
         Face {
             boundaries,
             orientation: true,
-            surface: Arc::new(Mutex::new(surface)),
+            surface: Arc::new(Geometry::new(surface)),
         }
     }
 
@@ -190,7 +190,7 @@ impl<P, C, S> Face<P, C, S> {
     fn renew_pointer(&mut self)
     where S: Clone {
         let surface = self.get_surface();
-        self.surface = Arc::new(Mutex::new(surface));
+        self.surface = Arc::new(Geometry::new(surface));
     }
 
     /// Adds a boundary to the face.
@@ -500,7 +500,33 @@ impl<P, C, S> Face<P, C, S> {
     /// assert_eq!(face1.get_surface(), 1);
     /// ```
     #[inline(always)]
-    pub fn set_surface(&self, surface: S) { *self.surface.lock().unwrap() = surface; }
+    pub fn set_surface(&self, surface: S) { self.surface.set(surface); }
+
+    /// Returns the generation of the surface, which is renewed each time the surface is set.
+    ///
+    /// The generation does not change by editing the boundary edges, whose generations are
+    /// given by [`Edge::generation`]. cf. [`Edge::generation`]
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(); 3]);
+    /// let wire = Wire::from(vec![
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[2], &v[0], ()),
+    /// ]);
+    /// let face0 = Face::new(vec![wire], 0);
+    /// let face1 = face0.inverse();
+    /// let generation = face0.generation();
+    /// assert_eq!(face1.generation(), generation);
+    ///
+    /// // the generation is renewed by editing the surface.
+    /// face1.set_surface(1);
+    /// assert_ne!(face0.generation(), generation);
+    /// assert_eq!(face0.generation(), face1.generation());
+    /// ```
+    #[inline(always)]
+    pub fn generation(&self) -> usize { self.surface.generation() }
 
     /// Inverts the direction of the face.
     /// # Examples
@@ -573,7 +599,7 @@ impl<P, C, S> Face<P, C, S> {
     /// assert_ne!(face0.id(), face2.id());
     /// ```
     #[inline(always)]
-    pub fn id(&self) -> FaceID<S> { ID::new(Arc::as_ptr(&self.surface)) }
+    pub fn id(&self) -> FaceID<S> { ID::new(&self.surface.entity) }
 
     /// Returns how many same faces.
     ///
@@ -747,7 +773,7 @@ impl<P, C, S> Face<P, C, S> {
         Some(Face {
             boundaries: vec![new_wire],
             orientation: self.orientation,
-            surface: Arc::new(Mutex::new(self.get_surface())),
+            surface: Arc::new(Geometry::new(self.get_surface())),
         })
     }
 
@@ -847,7 +873,7 @@ impl<P, C, S> Face<P, C, S> {
         Some(Face {
             boundaries,
             orientation: self.orientation(),
-            surface: Arc::new(Mutex::new(surface)),
+            surface: Arc::new(Geometry::new(surface)),
         })
     }
 
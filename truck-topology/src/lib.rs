@@ -79,6 +79,7 @@
 use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use truck_base::{id::ID, tolerance::*};
 use truck_geotrait::*;
@@ -116,7 +117,7 @@ pub struct Vertex<P> {
 pub struct Edge<P, C> {
     vertices: (Vertex<P>, Vertex<P>),
     orientation: bool,
-    curve: Arc<Mutex<C>>,
+    curve: Arc<Geometry<C>>,
 }
 
 /// Wire, a path or cycle which consists some edges.
@@ -147,7 +148,7 @@ pub struct Wire<P, C> {
 pub struct Face<P, C, S> {
     boundaries: Vec<Wire<P, C>>,
     orientation: bool,
-    surface: Arc<Mutex<S>>,
+    surface: Arc<Geometry<S>>,
 }
 
 /// Shell, a connected compounded faces.
@@ -165,6 +166,44 @@ pub struct Solid<P, C, S> {
     boundaries: Vec<Shell<P, C, S>>,
 }
 
+/// The geometry of an edge or a face, with the generation renewed each time the geometry is set.
+/// The generations are unique in the process.
+#[derive(Debug)]
+struct Geometry<T> {
+    entity: Mutex<T>,
+    generation: AtomicUsize,
+}
+
+/// the last generation issued in the process
+static LAST_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+impl<T> Geometry<T> {
+    #[inline(always)]
+    fn issue() -> usize { LAST_GENERATION.fetch_add(1, Ordering::Relaxed) + 1 }
+    #[inline(always)]
+    fn new(entity: T) -> Self {
+        Self {
+            entity: Mutex::new(entity),
+            generation: AtomicUsize::new(Self::issue()),
+        }
+    }
+    #[inline(always)]
+    fn generation(&self) -> usize { self.generation.load(Ordering::Relaxed) }
+    #[inline(always)]
+    fn renew(&self) { self.generation.store(Self::issue(), Ordering::Relaxed) }
+    #[inline(always)]
+    fn set(&self, entity: T) {
+        *self.entity.lock().unwrap() = entity;
+        self.renew();
+    }
+}
+
+impl<T> std::ops::Deref for Geometry<T> {
+    type Target = Mutex<T>;
+    #[inline(always)]
+    fn deref(&self) -> &Mutex<T> { &self.entity }
+}
+
 /// `Result` with crate's errors.
 pub type Result<T> = std::result::Result<T, crate::errors::Error>;
 
@@ -375,6 +414,7 @@ pub enum SolidDisplayFormat {
 }
 
 pub mod compress;
+mod deep_size;
 mod edge;
 /// classifies the errors that can occur in this crate.
 pub mod errors;
@@ -382,6 +422,7 @@ pub mod errors;
 pub mod face;
 /// classifies shell conditions and defines the face iterators.
 pub mod shell;
+pub mod snapshot;
 mod solid;
 mod vertex;
 /// define the edge iterators and the vertex iterator.
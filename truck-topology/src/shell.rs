@@ -1,6 +1,7 @@
 use crate::*;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use std::vec::Vec;
+use truck_base::{bounding_sphere::*, cgmath64::Point3};
 
 type FaceAdjacencyMap<'a, P, C, S> = HashMap<&'a Face<P, C, S>, Vec<&'a Face<P, C, S>>>;
 impl<P, C, S> Shell<P, C, S> {
@@ -44,6 +45,60 @@ impl<P, C, S> Shell<P, C, S> {
         self.edge_iter().map(|edge| edge.front().clone())
     }
 
+    /// Creates the minimal bounding sphere of the points of the vertices.
+    ///
+    /// The curves and surfaces are not considered, so sample them for the curved shells.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// use truck_base::{assert_near, cgmath64::*, tolerance::*};
+    /// let v = Vertex::news(&[
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(2.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 2.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 2.0),
+    /// ]);
+    /// let edge = [
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[0], &v[2], ()),
+    ///     Edge::new(&v[0], &v[3], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[1], &v[3], ()),
+    ///     Edge::new(&v[2], &v[3], ()),
+    /// ];
+    /// let wire = vec![
+    ///     Wire::from(vec![edge[0].clone(), edge[3].clone(), edge[1].inverse()]),
+    ///     Wire::from(vec![edge[0].clone(), edge[4].clone(), edge[2].inverse()]),
+    ///     Wire::from(vec![edge[1].clone(), edge[5].clone(), edge[2].inverse()]),
+    ///     Wire::from(vec![edge[3].clone(), edge[5].clone(), edge[4].inverse()]),
+    /// ];
+    /// let shell: Shell<_, _, _> = wire.into_iter().map(|w| Face::new(vec![w], ())).collect();
+    ///
+    /// let sphere = shell.bounding_sphere();
+    /// assert_near!(sphere.center(), Point3::new(2.0, 2.0, 2.0) / 3.0);
+    /// assert_near!(sphere.radius(), f64::sqrt(8.0 / 3.0));
+    /// let axes = shell.principal_axes();
+    /// assert_near!(axes.centroid(), Point3::new(0.5, 0.5, 0.5));
+    /// ```
+    pub fn bounding_sphere(&self) -> BoundingSphere
+    where P: Clone + Into<Point3> {
+        self.vertex_points().collect()
+    }
+
+    /// Returns the principal axes of the points of the vertices, each of which is counted once.
+    pub fn principal_axes(&self) -> PrincipalAxes
+    where P: Clone + Into<Point3> {
+        self.vertex_points().collect()
+    }
+
+    fn vertex_points(&self) -> impl Iterator<Item = Point3> + '_
+    where P: Clone + Into<Point3> {
+        let mut set = HashSet::default();
+        self.vertex_iter()
+            .filter(move |v| set.insert(v.id()))
+            .map(|v| v.get_point().into())
+    }
+
     /// Moves all the faces of `other` into `self`, leaving `other` empty.
     #[inline(always)]
     pub fn append(&mut self, other: &mut Shell<P, C, S>) {
@@ -844,7 +899,7 @@ pub type FaceIterMut<'a, P, C, S> = std::slice::IterMut<'a, Face<P, C, S>>;
 pub type FaceIntoIter<P, C, S> = std::vec::IntoIter<Face<P, C, S>>;
 
 /// The shell conditions being determined by the half-edge model.
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum ShellCondition {
     /// This shell is not regular.
     /// # Examples
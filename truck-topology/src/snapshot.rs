@@ -0,0 +1,301 @@
+//! Snapshots of shapes for undo and redo
+//!
+//! The geometries of the topological elements are shared by `Arc<Mutex<_>>` and edited in place,
+//! so cloning a shell or a solid does not save its state. A [`Snapshot`] saves the geometries,
+//! and the snapshot taken by [`Snapshot::update`] shares the unchanged geometries with the
+//! previous one. Hence, editor applications can keep the history of NURBS-heavy models by cloning
+//! only the edited geometries and the handles of the topological elements in each step.
+
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::hash::Hash;
+
+/// The geometries saved in a snapshot
+#[derive(Clone, Debug)]
+struct Geometries<P, C, S> {
+    points: HashMap<VertexID<P>, Arc<P>>,
+    curves: HashMap<EdgeID<C>, Arc<C>>,
+    surfaces: HashMap<FaceID<S>, Arc<S>>,
+}
+
+/// Saves `geometry`, sharing it with `prev` if it has not been changed.
+fn save<K: Copy + Hash + Eq, T: Clone + PartialEq>(
+    map: &mut HashMap<K, Arc<T>>,
+    prev: Option<&HashMap<K, Arc<T>>>,
+    id: K,
+    geometry: &Mutex<T>,
+) {
+    if map.contains_key(&id) {
+        return;
+    }
+    let geometry = geometry.lock().unwrap();
+    let saved = match prev.and_then(|prev| prev.get(&id)) {
+        Some(saved) if **saved == *geometry => Arc::clone(saved),
+        _ => Arc::new(geometry.clone()),
+    };
+    map.insert(id, saved);
+}
+
+/// Writes back `saved` to `geometry` if it has been changed, and returns whether it was written.
+fn load<T: Clone + PartialEq>(saved: &Arc<T>, geometry: &Mutex<T>) -> bool {
+    let mut geometry = geometry.lock().unwrap();
+    let changed = **saved != *geometry;
+    if changed {
+        *geometry = T::clone(saved);
+    }
+    changed
+}
+
+impl<P, C, S> Geometries<P, C, S>
+where
+    P: Clone + PartialEq,
+    C: Clone + PartialEq,
+    S: Clone + PartialEq,
+{
+    fn new(faces: Vec<&Face<P, C, S>>, prev: Option<&Self>) -> Self {
+        let mut geometries = Geometries {
+            points: HashMap::default(),
+            curves: HashMap::default(),
+            surfaces: HashMap::default(),
+        };
+        faces.into_iter().for_each(|face| {
+            let prev_surfaces = prev.map(|prev| &prev.surfaces);
+            save(
+                &mut geometries.surfaces,
+                prev_surfaces,
+                face.id(),
+                &face.surface,
+            );
+            face.boundaries.iter().flatten().for_each(|edge| {
+                let prev_curves = prev.map(|prev| &prev.curves);
+                save(&mut geometries.curves, prev_curves, edge.id(), &edge.curve);
+                let prev_points = prev.map(|prev| &prev.points);
+                [&edge.vertices.0, &edge.vertices.1]
+                    .into_iter()
+                    .for_each(|v| save(&mut geometries.points, prev_points, v.id(), &v.point));
+            });
+        });
+        geometries
+    }
+
+    fn restore(&self, faces: Vec<&Face<P, C, S>>) {
+        faces.into_iter().for_each(|face| {
+            if load(&self.surfaces[&face.id()], &face.surface) {
+                face.surface.renew();
+            }
+            face.boundaries.iter().flatten().for_each(|edge| {
+                if load(&self.curves[&edge.id()], &edge.curve) {
+                    edge.curve.renew();
+                }
+                [&edge.vertices.0, &edge.vertices.1]
+                    .into_iter()
+                    .for_each(|v| {
+                        load(&self.points[&v.id()], &v.point);
+                    });
+            });
+        });
+    }
+
+    fn num_shared(&self, other: &Self) -> usize {
+        fn count<K: Hash + Eq, T>(map0: &HashMap<K, Arc<T>>, map1: &HashMap<K, Arc<T>>) -> usize {
+            map0.iter()
+                .filter(|(id, x)| map1.get(id).is_some_and(|y| Arc::ptr_eq(x, y)))
+                .count()
+        }
+        count(&self.points, &other.points)
+            + count(&self.curves, &other.curves)
+            + count(&self.surfaces, &other.surfaces)
+    }
+}
+
+/// The shapes whose states can be saved in the snapshots, i.e. [`Shell`] and [`Solid`].
+pub trait Snapshotable<P, C, S>: Clone {
+    /// Returns the faces whose geometries are saved.
+    fn snapshot_faces(&self) -> Vec<&Face<P, C, S>>;
+}
+
+impl<P, C, S> Snapshotable<P, C, S> for Shell<P, C, S> {
+    #[inline(always)]
+    fn snapshot_faces(&self) -> Vec<&Face<P, C, S>> { self.face_iter().collect() }
+}
+
+impl<P: Clone, C: Clone, S: Clone> Snapshotable<P, C, S> for Solid<P, C, S> {
+    #[inline(always)]
+    fn snapshot_faces(&self) -> Vec<&Face<P, C, S>> { self.face_iter().collect() }
+}
+
+/// The saved state of a shell or a solid.
+#[derive(Clone, Debug)]
+pub struct Snapshot<T, P, C, S> {
+    shape: T,
+    geometries: Geometries<P, C, S>,
+}
+
+/// The history of a shell or a solid for undo and redo.
+/// # Examples
+/// ```
+/// use truck_topology::{snapshot::History, *};
+/// let v = Vertex::news(&[0, 1, 2]);
+/// let wire: Wire<i32, i32> = vec![
+///     Edge::new(&v[0], &v[1], 10),
+///     Edge::new(&v[1], &v[2], 11),
+///     Edge::new(&v[2], &v[0], 12),
+/// ]
+/// .into();
+/// let shell: Shell<i32, i32, i32> = vec![Face::new(vec![wire], 100)].into();
+///
+/// let mut history = History::new();
+/// history.commit(&shell);
+/// v[0].set_point(3);
+/// shell[0].set_surface(200);
+/// history.commit(&shell);
+///
+/// // Only the edited geometries are saved again.
+/// let snapshots = history.snapshots();
+/// assert_eq!(snapshots[1].num_shared_geometries(&snapshots[0]), 5);
+///
+/// let generation = shell[0].generation();
+/// let shell = history.undo().unwrap();
+/// assert_eq!(v[0].get_point(), 0);
+/// assert_eq!(shell[0].get_surface(), 100);
+/// // the generations of the written back geometries are renewed.
+/// assert_ne!(shell[0].generation(), generation);
+/// assert!(!history.can_undo());
+///
+/// let shell = history.redo().unwrap();
+/// assert_eq!(v[0].get_point(), 3);
+/// assert_eq!(shell[0].get_surface(), 200);
+/// assert!(!history.can_redo());
+/// ```
+#[derive(Clone, Debug)]
+pub struct History<T, P, C, S> {
+    undo: Vec<Snapshot<T, P, C, S>>,
+    redo: Vec<Snapshot<T, P, C, S>>,
+}
+
+impl<T, P, C, S> Default for History<T, P, C, S> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<T, P, C, S> History<T, P, C, S> {
+    /// Creates the empty history.
+    #[inline(always)]
+    pub fn new() -> Self { Self::default() }
+    /// Returns whether [`History::undo`] returns a shape or not.
+    #[inline(always)]
+    pub fn can_undo(&self) -> bool { self.undo.len() > 1 }
+    /// Returns whether [`History::redo`] returns a shape or not.
+    #[inline(always)]
+    pub fn can_redo(&self) -> bool { !self.redo.is_empty() }
+    /// Returns the snapshots which can be returned to by undo, including the current one.
+    #[inline(always)]
+    pub fn snapshots(&self) -> &[Snapshot<T, P, C, S>] { &self.undo }
+    /// Clears the history.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl<T, P, C, S> Snapshot<T, P, C, S>
+where
+    T: Snapshotable<P, C, S>,
+    P: Clone + PartialEq,
+    C: Clone + PartialEq,
+    S: Clone + PartialEq,
+{
+    /// Saves the state of `shape`.
+    pub fn new(shape: &T) -> Self {
+        Self {
+            shape: shape.clone(),
+            geometries: Geometries::new(shape.snapshot_faces(), None),
+        }
+    }
+    /// Saves the state of `shape`, sharing the unchanged geometries with `self`.
+    pub fn update(&self, shape: &T) -> Self {
+        Self {
+            shape: shape.clone(),
+            geometries: Geometries::new(shape.snapshot_faces(), Some(&self.geometries)),
+        }
+    }
+    /// Writes back the saved geometries to the elements and returns the saved shape.
+    /// The generations of the written back curves and surfaces are renewed.
+    pub fn restore(&self) -> T {
+        self.geometries.restore(self.shape.snapshot_faces());
+        self.shape.clone()
+    }
+    /// Returns the number of the geometries shared with `other`.
+    #[inline(always)]
+    pub fn num_shared_geometries(&self, other: &Self) -> usize {
+        self.geometries.num_shared(&other.geometries)
+    }
+}
+
+impl<T, P, C, S> History<T, P, C, S>
+where
+    T: Snapshotable<P, C, S>,
+    P: Clone + PartialEq,
+    C: Clone + PartialEq,
+    S: Clone + PartialEq,
+{
+    /// Saves the current state of `shape` and discards the redo history.
+    pub fn commit(&mut self, shape: &T) {
+        let snapshot = match self.undo.last() {
+            Some(last) => last.update(shape),
+            None => Snapshot::new(shape),
+        };
+        self.undo.push(snapshot);
+        self.redo.clear();
+    }
+    /// Returns to the previous state. Returns `None` if there is no previous state.
+    pub fn undo(&mut self) -> Option<T> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.redo.extend(self.undo.pop());
+        self.undo.last().map(Snapshot::restore)
+    }
+    /// Returns to the state before the last undo. Returns `None` if there is no such state.
+    pub fn redo(&mut self) -> Option<T> {
+        let snapshot = self.redo.pop()?;
+        let shape = snapshot.restore();
+        self.undo.push(snapshot);
+        Some(shape)
+    }
+}
+
+#[test]
+fn solid_history() {
+    let solid = solid::cube().mapped(|_| 0, |_| 0, |_| 0);
+    let face = &solid.boundaries()[0][0];
+    let edge = face.boundaries()[0][0].clone();
+
+    let mut history = History::new();
+    history.commit(&solid);
+    face.set_surface(1);
+    history.commit(&solid);
+    let snapshots = history.snapshots();
+    // 8 vertices, 12 edges and 5 faces are not changed.
+    assert_eq!(snapshots[1].num_shared_geometries(&snapshots[0]), 25);
+
+    history.undo();
+    assert_eq!(face.get_surface(), 0);
+    // the new commit discards the redo history.
+    edge.set_curve(2);
+    history.commit(&solid);
+    assert!(!history.can_redo());
+    assert_eq!(history.snapshots().len(), 2);
+
+    history.undo();
+    assert_eq!((face.get_surface(), edge.get_curve()), (0, 0));
+    assert!(history.undo().is_none());
+    history.redo();
+    assert_eq!((face.get_surface(), edge.get_curve()), (0, 2));
+}
@@ -3,6 +3,7 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use std::collections::vec_deque;
 use std::collections::VecDeque;
 use std::iter::Peekable;
+use truck_base::cgmath64::InnerSpace;
 
 impl<P, C> Wire<P, C> {
     /// Creates the empty wire.
@@ -470,6 +471,105 @@ impl<P, C> Wire<P, C> {
         self.iter().all(|edge| edge.is_geometric_consistent())
     }
 
+    /// Splits the wire into `n` pieces of equal arc length.
+    ///
+    /// The edges are cut at the new vertices, and the edges without cuts are shared with `self`.
+    /// The pieces are connected by the new vertices, or by the vertices of `self` if the points of
+    /// the division are on them.
+    /// # Failure
+    /// Returns `None` if `n == 0`, the wire is empty, or the curves cannot be cut at the points of
+    /// the division.
+    /// # Examples
+    /// ```
+    /// use truck_base::{assert_near, cgmath64::*, tolerance::*};
+    /// use truck_geotrait::*;
+    /// use truck_topology::*;
+    /// # #[derive(Clone, Debug)]
+    /// # struct Segment(Point2, Point2);
+    /// # impl ParametricCurve for Segment {
+    /// #     type Point = Point2;
+    /// #     type Vector = Vector2;
+    /// #     fn subs(&self, t: f64) -> Point2 { self.0 + (self.1 - self.0) * t }
+    /// #     fn der(&self, _: f64) -> Vector2 { self.1 - self.0 }
+    /// #     fn der2(&self, _: f64) -> Vector2 { Vector2::zero() }
+    /// # }
+    /// # impl BoundedCurve for Segment {
+    /// #     fn parameter_range(&self) -> (f64, f64) { (0.0, 1.0) }
+    /// # }
+    /// # impl Cut for Segment {
+    /// #     fn cut(&mut self, t: f64) -> Self {
+    /// #         let r = self.subs(t);
+    /// #         let res = Segment(r, self.1);
+    /// #         self.1 = r;
+    /// #         res
+    /// #     }
+    /// # }
+    /// // `Segment` is the line segment parametrized on `[0, 1]`.
+    /// let p = [Point2::new(0.0, 0.0), Point2::new(1.0, 0.0), Point2::new(1.0, 2.0)];
+    /// let v = Vertex::news(p);
+    /// let wire: Wire<Point2, Segment> = vec![
+    ///     Edge::new(&v[0], &v[1], Segment(p[0], p[1])),
+    ///     Edge::new(&v[2], &v[1], Segment(p[2], p[1])).inverse(),
+    /// ]
+    /// .into();
+    ///
+    /// // the total length is 3, and the second edge is cut at the middle.
+    /// let pieces = wire.subdivide_equal(2).unwrap();
+    /// assert_eq!(pieces.len(), 2);
+    /// assert_eq!(pieces[0].len(), 2);
+    /// assert_near!(pieces[0].back_vertex().unwrap().get_point(), Point2::new(1.0, 0.5));
+    /// assert_eq!(pieces[0].back_vertex(), pieces[1].front_vertex());
+    /// assert!(pieces.iter().all(|piece| piece.is_geometric_consistent()));
+    ///
+    /// // the point of the division is on the vertex.
+    /// let pieces = wire.subdivide_equal(3).unwrap();
+    /// assert_eq!(pieces[0].back_vertex(), Some(&v[1]));
+    /// assert_near!(pieces[1].back_vertex().unwrap().get_point(), Point2::new(1.0, 1.0));
+    /// ```
+    pub fn subdivide_equal(&self, n: usize) -> Option<Vec<Wire<P, C>>>
+    where
+        P: Clone + Tolerance,
+        C: Cut<Point = P>,
+        C::Vector: InnerSpace<Scalar = f64>, {
+        if n == 0 || self.is_empty() {
+            return None;
+        }
+        let lengths: Vec<f64> = self
+            .iter()
+            .map(|edge| arc_length(&edge.get_curve()))
+            .collect();
+        let total: f64 = lengths.iter().sum();
+        let step = total / n as f64;
+        let mut pieces = Vec::with_capacity(n);
+        let mut piece = Wire::new();
+        // the length from the current point to the next point of the division
+        let mut remaining = step;
+        for (edge, len) in self.iter().zip(lengths) {
+            let (mut rest, mut len) = (edge.clone(), len);
+            while pieces.len() + 1 < n && remaining + TOLERANCE < len {
+                let curve = rest.get_curve();
+                let s = match rest.orientation() {
+                    true => remaining,
+                    false => len - remaining,
+                };
+                let t = parameter_at_length(&curve, s);
+                let vertex = Vertex::new(curve.subs(t));
+                let (edge0, edge1) = rest.cut_with_parameter(&vertex, t)?;
+                piece.push_back(edge0);
+                pieces.push(std::mem::take(&mut piece));
+                (rest, len, remaining) = (edge1, len - remaining, step);
+            }
+            piece.push_back(rest);
+            remaining -= len;
+            if pieces.len() + 1 < n && remaining < TOLERANCE {
+                pieces.push(std::mem::take(&mut piece));
+                remaining = step;
+            }
+        }
+        pieces.push(piece);
+        Some(pieces)
+    }
+
     /// Creates display struct for debugging the wire.
     /// # Examples
     /// ```
@@ -731,3 +831,86 @@ impl<'a, P: Debug, C: Debug> Debug for DebugDisplay<'a, Wire<P, C>, WireDisplayF
         }
     }
 }
+
+/// the nodes and the weights of the Gauss–Legendre quadrature of five points on `[-1, 1]`
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.0, 0.568_888_888_888_888_9),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+
+/// the number of the divisions of the parameter range in computing the arc lengths
+const ARC_LENGTH_DIVISION: usize = 32;
+
+/// The arc length of `curve` on the parameter range `(t0, t1)`.
+fn gauss_length<C>(curve: &C, t0: f64, t1: f64) -> f64
+where
+    C: ParametricCurve,
+    C::Vector: InnerSpace<Scalar = f64>, {
+    let (mid, half) = ((t0 + t1) / 2.0, (t1 - t0) / 2.0);
+    GAUSS_LEGENDRE
+        .iter()
+        .map(|(x, w)| w * curve.der(mid + half * x).magnitude())
+        .sum::<f64>()
+        * half
+}
+
+/// The parameters dividing the parameter range of `curve` into the equal intervals.
+fn division_params<C: BoundedCurve>(curve: &C) -> Vec<f64> {
+    let (t0, t1) = curve.parameter_range();
+    (0..=ARC_LENGTH_DIVISION)
+        .map(|i| t0 + (t1 - t0) * i as f64 / ARC_LENGTH_DIVISION as f64)
+        .collect()
+}
+
+/// The arc length of the whole of `curve`.
+fn arc_length<C>(curve: &C) -> f64
+where
+    C: BoundedCurve,
+    C::Vector: InnerSpace<Scalar = f64>, {
+    division_params(curve)
+        .windows(2)
+        .map(|t| gauss_length(curve, t[0], t[1]))
+        .sum()
+}
+
+/// The parameter at which the arc length of `curve` from the front is `length`.
+fn parameter_at_length<C>(curve: &C, length: f64) -> f64
+where
+    C: BoundedCurve,
+    C::Vector: InnerSpace<Scalar = f64>, {
+    let params = division_params(curve);
+    let mut length = length;
+    let (t0, t1) = params
+        .windows(2)
+        .map(|t| (t[0], t[1]))
+        .find(|(t0, t1)| {
+            let len = gauss_length(curve, *t0, *t1);
+            let found = length <= len;
+            if !found {
+                length -= len;
+            }
+            found
+        })
+        .unwrap_or((params[ARC_LENGTH_DIVISION - 1], params[ARC_LENGTH_DIVISION]));
+    // Newton's method in the interval, starting from the linear interpolation
+    let len = gauss_length(curve, t0, t1);
+    let mut t = match len.so_small() {
+        true => t0,
+        false => t0 + (t1 - t0) * f64::min(length / len, 1.0),
+    };
+    for _ in 0..SEARCH_PARAMETER_TRIALS {
+        let speed = curve.der(t).magnitude();
+        if speed.so_small() {
+            break;
+        }
+        let delta = (gauss_length(curve, t0, t) - length) / speed;
+        t = f64::clamp(t - delta, t0, t1);
+        if delta.abs() < TOLERANCE * 1.0e-3 {
+            break;
+        }
+    }
+    t
+}